@@ -0,0 +1,481 @@
+//! Config-defined agent adapter.
+//!
+//! Teams can declare extra agent CLIs (aider, goose, local model wrappers)
+//! under `agents:` in team.yaml or as `~/.batty/agents/<name>.yaml` files.
+//! Each declaration supplies a launch command template, optional resume
+//! flags, instruction-file candidates, and the screen regexes the shim
+//! classifier needs. Loaded declarations are registered process-wide so
+//! [`super::adapter_from_name`] resolves them like built-in backends.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::agent::{AgentAdapter, BackendHealth, SpawnConfig};
+use crate::prompt::PromptPatterns;
+use crate::shim::classifier::CustomProfile;
+
+/// Placeholders accepted in `command` and `resume_args`.
+const TEMPLATE_PLACEHOLDERS: &[&str] = &["prompt", "session_id"];
+
+/// A custom agent declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomAgentConfig {
+    /// Shell command template. `{prompt}` expands to the single-quoted launch
+    /// prompt; `{session_id}` to the member's session id.
+    pub command: String,
+    /// Extra arguments appended to `command` when resuming a session.
+    /// Declaring them marks the agent as resumable.
+    #[serde(default)]
+    pub resume_args: Option<String>,
+    /// Binary checked on PATH for backend health. Defaults to the first word
+    /// of `command`.
+    #[serde(default)]
+    pub binary: Option<String>,
+    /// Project-root instruction files the agent reads, in preference order.
+    #[serde(default = "default_instruction_files")]
+    pub instruction_files: Vec<String>,
+    /// Whether the CLI is a raw-mode TUI (bracketed paste, CR for Enter)
+    /// rather than a line-oriented REPL.
+    #[serde(default = "default_raw_mode")]
+    pub raw_mode: bool,
+    #[serde(default)]
+    pub patterns: CustomAgentPatterns,
+}
+
+/// Screen regexes for a custom agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomAgentPatterns {
+    #[serde(default)]
+    pub prompt: Vec<String>,
+    #[serde(default)]
+    pub spinner: Vec<String>,
+    #[serde(default)]
+    pub context_exhausted: Vec<String>,
+    #[serde(default)]
+    pub quota: Vec<String>,
+}
+
+fn default_instruction_files() -> Vec<String> {
+    vec!["AGENTS.md".to_string(), "CLAUDE.md".to_string()]
+}
+
+fn default_raw_mode() -> bool {
+    true
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn template_placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        names.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+/// Substitute `{name}` placeholders in one pass, so text produced by one
+/// substitution is never expanded again. Unknown names are left as written.
+fn expand_template(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        match value(&after[..end]) {
+            Some(replacement) => expanded.push_str(&replacement),
+            None => expanded.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+impl CustomAgentConfig {
+    /// Screen classifier profile handed to the shim.
+    pub fn classifier_profile(&self) -> CustomProfile {
+        CustomProfile {
+            prompt: self.patterns.prompt.clone(),
+            spinner: self.patterns.spinner.clone(),
+            context_exhausted: self.patterns.context_exhausted.clone(),
+            quota: self.patterns.quota.clone(),
+            raw_mode: self.raw_mode,
+        }
+    }
+
+    /// Binary used for the PATH health check.
+    pub fn binary(&self) -> &str {
+        self.binary
+            .as_deref()
+            .or_else(|| self.command.split_whitespace().next())
+            .unwrap_or_default()
+    }
+
+    /// Expand the command template for a launch.
+    pub fn render_command(&self, prompt: &str, resume: bool, session_id: Option<&str>) -> String {
+        let mut command = self.command.trim().to_string();
+        if resume && let Some(resume_args) = self.resume_args.as_deref() {
+            command.push(' ');
+            command.push_str(resume_args.trim());
+        }
+        expand_template(&command, |name| match name {
+            "prompt" => Some(shell_quote(prompt)),
+            "session_id" => Some(shell_quote(session_id.unwrap_or_default())),
+            _ => None,
+        })
+    }
+
+    /// Problems that make this declaration unusable, in declaration order.
+    pub fn lint(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.command.trim().is_empty() {
+            problems.push("command is empty".to_string());
+        }
+        for (field, template) in [
+            ("command", Some(self.command.as_str())),
+            ("resume_args", self.resume_args.as_deref()),
+        ] {
+            let Some(template) = template else {
+                continue;
+            };
+            for name in template_placeholders(template) {
+                if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+                    problems.push(format!(
+                        "{field} uses unknown placeholder `{{{name}}}`; supported: {}",
+                        TEMPLATE_PLACEHOLDERS
+                            .iter()
+                            .map(|name| format!("{{{name}}}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+        }
+        if self.instruction_files.is_empty() {
+            problems.push("instruction_files must list at least one file".to_string());
+        }
+        if self.patterns.prompt.is_empty() {
+            problems.push(
+                "patterns.prompt is required so the shim can detect when the agent is idle"
+                    .to_string(),
+            );
+        }
+        for (kind, patterns) in [
+            ("prompt", &self.patterns.prompt),
+            ("spinner", &self.patterns.spinner),
+            ("context_exhausted", &self.patterns.context_exhausted),
+            ("quota", &self.patterns.quota),
+        ] {
+            for pattern in patterns {
+                match Regex::new(pattern) {
+                    Ok(regex) if regex.is_match("") => problems.push(format!(
+                        "patterns.{kind} `{pattern}` matches an empty line and would match every screen"
+                    )),
+                    Ok(_) => {}
+                    Err(error) => problems.push(format!(
+                        "patterns.{kind} `{pattern}` is not a valid regex: {error}"
+                    )),
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// Adapter driven entirely by a [`CustomAgentConfig`].
+pub struct CustomAgentAdapter {
+    name: String,
+    config: CustomAgentConfig,
+}
+
+impl CustomAgentAdapter {
+    pub fn new(name: &str, config: CustomAgentConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+        }
+    }
+}
+
+impl AgentAdapter for CustomAgentAdapter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn spawn_config(&self, task_description: &str, work_dir: &Path) -> SpawnConfig {
+        SpawnConfig {
+            program: "bash".to_string(),
+            args: vec![
+                "-lc".to_string(),
+                self.config.render_command(task_description, false, None),
+            ],
+            work_dir: work_dir.to_string_lossy().to_string(),
+            env: vec![],
+        }
+    }
+
+    fn prompt_patterns(&self) -> PromptPatterns {
+        PromptPatterns::from_custom(
+            &self.config.patterns.prompt,
+            &self.config.patterns.context_exhausted,
+        )
+    }
+
+    fn instruction_candidates(&self) -> Vec<&str> {
+        self.config
+            .instruction_files
+            .iter()
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn format_input(&self, response: &str) -> String {
+        format!("{response}\n")
+    }
+
+    fn launch_command(
+        &self,
+        prompt: &str,
+        _idle: bool,
+        resume: bool,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let resume = resume && self.supports_resume();
+        Ok(format!(
+            "exec {}",
+            self.config.render_command(prompt, resume, session_id)
+        ))
+    }
+
+    fn new_session_id(&self) -> Option<String> {
+        self.supports_resume()
+            .then(|| uuid::Uuid::new_v4().to_string())
+    }
+
+    fn supports_resume(&self) -> bool {
+        self.config.resume_args.is_some()
+    }
+
+    fn health_check(&self) -> BackendHealth {
+        super::check_binary_available(self.config.binary())
+    }
+}
+
+fn registry() -> &'static RwLock<BTreeMap<String, CustomAgentConfig>> {
+    static REGISTRY: OnceLock<RwLock<BTreeMap<String, CustomAgentConfig>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Make custom agents resolvable through [`super::adapter_from_name`].
+/// Registration is additive: a later definition with the same name wins.
+pub fn register_custom_agents(agents: &HashMap<String, CustomAgentConfig>) {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    for (name, config) in agents {
+        registry.insert(name.clone(), config.clone());
+    }
+}
+
+/// Look up a registered custom agent declaration.
+pub fn custom_agent(name: &str) -> Option<CustomAgentConfig> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned()
+}
+
+/// Names of every registered custom agent, sorted.
+pub fn registered_custom_agent_names() -> Vec<String> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect()
+}
+
+/// `~/.batty/agents`, where per-user agent declarations live.
+pub fn user_agents_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".batty").join("agents"))
+}
+
+/// Load every `<name>.yaml` / `<name>.yml` declaration in `dir`. The file
+/// stem is the agent name. A missing directory yields no agents.
+pub fn load_agent_dir(dir: &Path) -> Result<BTreeMap<String, CustomAgentConfig>> {
+    let mut agents = BTreeMap::new();
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(agents),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to read {}", dir.display()));
+        }
+    };
+    for entry in entries {
+        let path = entry?.path();
+        let is_yaml = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| matches!(ext, "yaml" | "yml"));
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if !is_yaml {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let config: CustomAgentConfig = serde_yaml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        agents.insert(name.to_string(), config);
+    }
+    Ok(agents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aider() -> CustomAgentConfig {
+        serde_yaml::from_str(
+            r#"
+command: aider --yes-always --message {prompt}
+resume_args: --restore-chat-history
+instruction_files: [CONVENTIONS.md, AGENTS.md]
+raw_mode: false
+patterns:
+  prompt: ['^>\s*$']
+  spinner: ['Waiting for', 'Thinking']
+  context_exhausted: ['exceeds the .* context window']
+  quota: ['(?i)rate limit']
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn declaration_defaults_are_applied() {
+        let config: CustomAgentConfig =
+            serde_yaml::from_str("command: goose session\npatterns:\n  prompt: ['^\\( O\\)>']\n")
+                .unwrap();
+        assert_eq!(config.instruction_files, vec!["AGENTS.md", "CLAUDE.md"]);
+        assert!(config.raw_mode);
+        assert!(config.resume_args.is_none());
+        assert_eq!(config.binary(), "goose");
+        assert!(config.lint().is_empty());
+    }
+
+    #[test]
+    fn render_command_quotes_prompt_and_appends_resume_args() {
+        let config = aider();
+        assert_eq!(
+            config.render_command("fix it's bug", false, None),
+            "aider --yes-always --message 'fix it'\\''s bug'"
+        );
+        assert_eq!(
+            config.render_command("go", true, Some("abc")),
+            "aider --yes-always --message 'go' --restore-chat-history"
+        );
+    }
+
+    #[test]
+    fn render_command_does_not_expand_placeholders_inside_the_prompt() {
+        let mut config = aider();
+        config.command = "mycli --session {session_id} --message {prompt} {other}".to_string();
+        assert_eq!(
+            config.render_command("echo {session_id} {prompt}", false, Some("abc")),
+            "mycli --session 'abc' --message 'echo {session_id} {prompt}' {other}"
+        );
+    }
+
+    #[test]
+    fn adapter_uses_declaration() {
+        let adapter = CustomAgentAdapter::new("aider", aider());
+        assert_eq!(adapter.name(), "aider");
+        assert_eq!(
+            adapter.instruction_candidates(),
+            &["CONVENTIONS.md", "AGENTS.md"]
+        );
+        assert!(adapter.supports_resume());
+        assert!(adapter.new_session_id().is_some());
+        assert_eq!(
+            adapter.launch_command("hi", true, true, None).unwrap(),
+            "exec aider --yes-always --message 'hi' --restore-chat-history"
+        );
+        assert!(adapter.prompt_patterns().detect("> ").is_some());
+
+        let profile = aider().classifier_profile();
+        assert_eq!(profile.quota, vec!["(?i)rate limit"]);
+        assert!(!profile.raw_mode);
+        assert!(profile.check().is_ok());
+    }
+
+    #[test]
+    fn lint_reports_unusable_declarations() {
+        let mut config = aider();
+        config.command = "mycli {task}".to_string();
+        config.patterns.prompt = vec!["(".to_string(), ".*".to_string()];
+        config.instruction_files.clear();
+        let problems = config.lint();
+        assert_eq!(problems.len(), 4, "{problems:?}");
+        assert!(problems[0].contains("unknown placeholder `{task}`"));
+        assert!(problems[1].contains("instruction_files"));
+        assert!(problems[2].contains("not a valid regex"));
+        assert!(problems[3].contains("matches an empty line"));
+
+        config.patterns.prompt.clear();
+        assert!(
+            config
+                .lint()
+                .iter()
+                .any(|problem| problem.contains("patterns.prompt is required"))
+        );
+    }
+
+    #[test]
+    fn registered_agents_resolve_through_adapter_from_name() {
+        let name = "custom-test-registry-agent";
+        assert!(super::super::adapter_from_name(name).is_none());
+        register_custom_agents(&HashMap::from([(name.to_string(), aider())]));
+        let adapter = super::super::adapter_from_name(name).unwrap();
+        assert_eq!(adapter.name(), name);
+        assert!(registered_custom_agent_names().contains(&name.to_string()));
+    }
+
+    #[test]
+    fn load_agent_dir_reads_yaml_files_by_stem() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("goose.yaml"),
+            "command: goose session\npatterns:\n  prompt: ['^> $']\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+        let agents = load_agent_dir(tmp.path()).unwrap();
+        assert_eq!(agents.keys().collect::<Vec<_>>(), vec!["goose"]);
+        assert!(
+            load_agent_dir(&tmp.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+
+        std::fs::write(tmp.path().join("bad.yaml"), "command: [").unwrap();
+        let error = load_agent_dir(tmp.path()).unwrap_err();
+        assert!(format!("{error:#}").contains("bad.yaml"));
+    }
+}
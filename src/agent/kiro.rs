@@ -84,8 +84,8 @@ impl AgentAdapter for KiroCliAdapter {
         PromptPatterns::kiro_cli()
     }
 
-    fn instruction_candidates(&self) -> &'static [&'static str] {
-        &["AGENTS.md", "CLAUDE.md"]
+    fn instruction_candidates(&self) -> Vec<&str> {
+        vec!["AGENTS.md", "CLAUDE.md"]
     }
 
     fn wrap_launch_prompt(&self, prompt: &str) -> String {
@@ -121,9 +121,9 @@ impl AgentAdapter for MockBackend {
         PromptPatterns::claude_code()
     }
 
-    fn instruction_candidates(&self) -> &'static [&'static str] {
+    fn instruction_candidates(&self) -> Vec<&str> {
         self.record(MockCall::InstructionCandidates);
-        &["CLAUDE.md", "AGENTS.md"]
+        vec!["CLAUDE.md", "AGENTS.md"]
     }
 
     fn wrap_launch_prompt(&self, prompt: &str) -> String {
@@ -12,6 +12,7 @@
 
 pub mod claude;
 pub mod codex;
+pub mod custom;
 pub mod kiro;
 pub mod mock;
 
@@ -100,8 +101,8 @@ pub trait AgentAdapter: Send + Sync {
     ///
     /// The first existing file is used as launch context. Adapters can
     /// override this to prefer agent-specific steering docs.
-    fn instruction_candidates(&self) -> &'static [&'static str] {
-        &["CLAUDE.md", "AGENTS.md"]
+    fn instruction_candidates(&self) -> Vec<&str> {
+        vec!["CLAUDE.md", "AGENTS.md"]
     }
 
     /// Allow adapters to wrap or transform the composed launch context.
@@ -156,9 +157,20 @@ pub const KNOWN_AGENT_NAMES: &[&str] = &["claude", "codex", "kiro-cli"];
 
 /// Look up an agent adapter by name.
 ///
-/// Returns `None` if the agent name is not recognized. New adapters are
-/// registered here as they're implemented.
+/// Returns `None` if the agent name is not recognized. Built-in adapters are
+/// registered here; config-defined agents resolve through
+/// [`custom::register_custom_agents`].
 pub fn adapter_from_name(name: &str) -> Option<Box<dyn AgentAdapter>> {
+    if let Some(adapter) = builtin_adapter_from_name(name) {
+        return Some(adapter);
+    }
+    custom::custom_agent(name).map(|config| {
+        Box::new(custom::CustomAgentAdapter::new(name, config)) as Box<dyn AgentAdapter>
+    })
+}
+
+/// Look up a compiled-in agent adapter by name.
+pub fn builtin_adapter_from_name(name: &str) -> Option<Box<dyn AgentAdapter>> {
     match name {
         "claude" | "claude-code" => Some(Box::new(claude::ClaudeCodeAdapter::new(None))),
         "codex" | "codex-cli" => Some(Box::new(codex::CodexCliAdapter::new(None))),
@@ -45,6 +45,18 @@ pub enum Command {
     /// Export run state for debugging
     ExportRun,
 
+    /// Replay a run export against fake shims and diff the daemon's decisions
+    Replay {
+        /// Run export directory written by `batty export-run`
+        export: PathBuf,
+        /// Virtual seconds between daemon ticks while no recorded event is due
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+        tick_secs: u64,
+        /// Emit machine-readable JSON output
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+
     /// Generate a run retrospective
     Retro {
         /// Path to events.jsonl (default: .batty/team_config/events.jsonl)
@@ -68,6 +80,9 @@ pub enum Command {
     /// Attach to the running team tmux session
     Attach,
 
+    /// Open the interactive console (chat, board, agents, peek) on the running daemon
+    Console,
+
     /// Show all team members and their states
     Status {
         /// Emit machine-readable JSON output
@@ -247,6 +262,18 @@ pub enum Command {
         command: GrafanaCommand,
     },
 
+    /// Sync the board with GitHub/GitLab issues and pull requests
+    Forge {
+        #[command(subcommand)]
+        command: ForgeCommand,
+    },
+
+    /// Inspect the retrieval index behind dispatch learnings
+    Learnings {
+        #[command(subcommand)]
+        command: LearningsCommand,
+    },
+
     /// Configure Discord human communication
     Discord {
         #[command(subcommand)]
@@ -288,12 +315,28 @@ pub enum Command {
         readiness: bool,
     },
 
+    /// List, grant, or deny human approvals for gated actions
+    Approval {
+        #[command(subcommand)]
+        command: ApprovalCommand,
+    },
+
+    /// Run an operator command on the daemon, e.g. `batty op board review`
+    Op {
+        /// Command words, with or without the leading `/` or `$`
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+
     /// Show pending dispatch queue entries
     Queue,
 
-    /// Explain which engineer would receive the next dispatch
+    /// Explain which engineer would receive the next dispatch, or train the allocation model
+    #[command(args_conflicts_with_subcommands = true)]
     Dispatch {
-        /// Print routing reasons for the chosen engineer
+        #[command(subcommand)]
+        command: Option<DispatchCommand>,
+        /// Print routing reasons and per-feature score contributions
         #[arg(long, default_value_t = false)]
         explain: bool,
         /// Explain routing for a specific task id
@@ -429,8 +472,20 @@ pub enum Command {
         /// Use SDK mode (NDJSON stdin/stdout) instead of PTY screen-scraping
         #[arg(long, default_value_t = false)]
         sdk_mode: bool,
+
+        /// JSON screen-classifier profile for `--agent-type custom`
+        #[arg(long)]
+        classifier_profile: Option<String>,
+
+        /// JSON sandbox policy; runs the agent inside a rootless sandbox
+        #[arg(long)]
+        sandbox: Option<String>,
     },
 
+    /// Internal: check staged files against the claimed task's scope (pre-commit hook)
+    #[command(hide = true, name = "scope-check")]
+    ScopeCheck,
+
     /// Internal: interactive shim pane bridge for tmux
     #[command(hide = true)]
     ConsolePane {
@@ -496,12 +551,20 @@ pub enum TelemetryCommand {
     Tasks,
     /// Show review pipeline metrics (auto-merge rate, rework, latency)
     Reviews,
+    /// Show flaky tests quarantined from the merge gate or on probation
+    Quarantine,
     /// Show recent events from the telemetry database
     Events {
         /// Maximum number of events to show
         #[arg(short = 'n', long = "limit", default_value_t = 50)]
         limit: usize,
     },
+    /// Apply pending telemetry schema migrations (backs up the database first)
+    Migrate {
+        /// List pending migrations without applying them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -512,6 +575,42 @@ pub enum GrafanaCommand {
     Status,
     /// Open the Grafana dashboard in the default browser
     Open,
+    /// Create a Prometheus datasource and import the live-metrics dashboard
+    Prometheus {
+        /// Prometheus server that scrapes the daemon's `/metrics` endpoint
+        #[arg(long, default_value = "http://localhost:9090")]
+        url: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ForgeCommand {
+    /// Run one sync pass against the configured forge now
+    Sync,
+    /// Show which issues and pull requests are linked to board tasks
+    Status {
+        /// Emit machine-readable JSON output
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LearningsCommand {
+    /// Show what the index returns for a query, best match first
+    Search {
+        /// Free-text query, e.g. a task title
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+
+        /// Maximum number of results
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+
+        /// Emit machine-readable JSON output
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -665,6 +764,42 @@ pub enum ProjectCommand {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum DispatchCommand {
+    /// Fit the learned allocation model from telemetry outcomes
+    Train {
+        /// Minimum completed assignments required before fitting
+        #[arg(long, default_value_t = 20)]
+        min_samples: usize,
+        /// Print the trained model as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ApprovalCommand {
+    /// Show pending approvals
+    List {
+        /// Include decided and expired approvals
+        #[arg(long, default_value_t = false)]
+        all: bool,
+    },
+    /// Approve a pending request
+    Grant {
+        /// Approval id
+        id: u32,
+    },
+    /// Deny a pending request
+    Deny {
+        /// Approval id
+        id: u32,
+        /// Why the action was denied
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum InboxCommand {
     /// Purge delivered messages from inbox cur/ directories
@@ -1391,6 +1526,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn replay_subcommand_parses_export_and_options() {
+        let cli = Cli::parse_from([
+            "batty",
+            "replay",
+            ".batty/exports/1700000000",
+            "--tick-secs",
+            "10",
+            "--json",
+        ]);
+        match cli.command {
+            Command::Replay {
+                export,
+                tick_secs,
+                json,
+            } => {
+                assert_eq!(export, PathBuf::from(".batty/exports/1700000000"));
+                assert_eq!(tick_secs, 10);
+                assert!(json);
+            }
+            other => panic!("expected replay command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["batty", "replay", "x", "--tick-secs", "0"]).is_err());
+    }
+
     #[test]
     fn export_run_subcommand_parses() {
         let cli = Cli::parse_from(["batty", "export-run"]);
@@ -1468,6 +1628,12 @@ mod tests {
         assert!(matches!(cli.command, Command::Attach));
     }
 
+    #[test]
+    fn console_subcommand_parses() {
+        let cli = Cli::parse_from(["batty", "console"]);
+        assert!(matches!(cli.command, Command::Console));
+    }
+
     #[test]
     fn status_subcommand_defaults() {
         let cli = Cli::parse_from(["batty", "status"]);
@@ -2310,7 +2476,11 @@ mod tests {
     fn dispatch_explain_subcommand_parses() {
         let cli = Cli::parse_from(["batty", "dispatch", "--explain", "--task", "42"]);
         match cli.command {
-            Command::Dispatch { explain, task } => {
+            Command::Dispatch {
+                command: None,
+                explain,
+                task,
+            } => {
                 assert!(explain);
                 assert_eq!(task, Some(42));
             }
@@ -2318,6 +2488,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_train_subcommand_parses() {
+        let cli = Cli::parse_from([
+            "batty",
+            "dispatch",
+            "train",
+            "--min-samples",
+            "50",
+            "--json",
+        ]);
+        match cli.command {
+            Command::Dispatch {
+                command: Some(DispatchCommand::Train { min_samples, json }),
+                ..
+            } => {
+                assert_eq!(min_samples, 50);
+                assert!(json);
+            }
+            other => panic!("expected dispatch train command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn approval_deny_subcommand_parses() {
+        let cli = Cli::parse_from(["batty", "approval", "deny", "4", "--reason", "not now"]);
+        match cli.command {
+            Command::Approval {
+                command: ApprovalCommand::Deny { id, reason },
+            } => {
+                assert_eq!(id, 4);
+                assert_eq!(reason.as_deref(), Some("not now"));
+            }
+            other => panic!("expected approval deny command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn op_subcommand_collects_command_words() {
+        let cli = Cli::parse_from(["batty", "op", "stop", "confirm"]);
+        match cli.command {
+            Command::Op { command } => assert_eq!(command, vec!["stop", "confirm"]),
+            other => panic!("expected op command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn cost_subcommand_parses() {
         let cli = Cli::parse_from(["batty", "cost"]);
@@ -2797,6 +3012,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn telemetry_quarantine_parses() {
+        let cli = Cli::parse_from(["batty", "telemetry", "quarantine"]);
+        match cli.command {
+            Command::Telemetry {
+                command: TelemetryCommand::Quarantine,
+            } => {}
+            other => panic!("expected telemetry quarantine, got {other:?}"),
+        }
+    }
+
     #[test]
     fn telemetry_events_default_limit() {
         let cli = Cli::parse_from(["batty", "telemetry", "events"]);
@@ -2808,6 +3034,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn telemetry_migrate_parses_dry_run() {
+        let cli = Cli::parse_from(["batty", "telemetry", "migrate", "--dry-run"]);
+        match cli.command {
+            Command::Telemetry {
+                command: TelemetryCommand::Migrate { dry_run },
+            } => assert!(dry_run),
+            other => panic!("expected telemetry migrate, got {other:?}"),
+        }
+    }
+
     #[test]
     fn telemetry_events_custom_limit() {
         let cli = Cli::parse_from(["batty", "telemetry", "events", "-n", "10"]);
@@ -2860,12 +3097,74 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn grafana_prometheus_parses_url() {
+        let cli = Cli::parse_from([
+            "batty",
+            "grafana",
+            "prometheus",
+            "--url",
+            "http://prom:9090",
+        ]);
+        match cli.command {
+            Command::Grafana {
+                command: GrafanaCommand::Prometheus { url },
+            } => assert_eq!(url, "http://prom:9090"),
+            other => panic!("expected grafana prometheus command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn grafana_rejects_missing_subcommand() {
         let result = Cli::try_parse_from(["batty", "grafana"]);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn forge_sync_parses() {
+        let cli = Cli::parse_from(["batty", "forge", "sync"]);
+        assert!(matches!(
+            cli.command,
+            Command::Forge {
+                command: ForgeCommand::Sync
+            }
+        ));
+    }
+
+    #[test]
+    fn forge_status_parses_json_flag() {
+        let cli = Cli::parse_from(["batty", "forge", "status", "--json"]);
+        assert!(matches!(
+            cli.command,
+            Command::Forge {
+                command: ForgeCommand::Status { json: true }
+            }
+        ));
+    }
+
+    #[test]
+    fn learnings_search_joins_query_words() {
+        let cli = Cli::parse_from([
+            "batty",
+            "learnings",
+            "search",
+            "retry",
+            "merge",
+            "--limit",
+            "3",
+        ]);
+        match cli.command {
+            Command::Learnings {
+                command: LearningsCommand::Search { query, limit, json },
+            } => {
+                assert_eq!(query, vec!["retry", "merge"]);
+                assert_eq!(limit, 3);
+                assert!(!json);
+            }
+            other => panic!("expected learnings search, got {other:?}"),
+        }
+    }
+
     // --- task auto-merge ---
 
     #[test]
@@ -3206,6 +3505,12 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scope_check_subcommand_parses() {
+        let cli = Cli::parse_from(["batty", "scope-check"]);
+        assert!(matches!(cli.command, Command::ScopeCheck));
+    }
+
     // --- completion generation tests ---
 
     /// Helper: generate completion script for a shell into a String.
@@ -0,0 +1,1365 @@
+//! `batty console`: a full-screen terminal UI attached to the running daemon.
+//!
+//! The console is a client of the daemon's console socket
+//! (`.batty/console.sock`). Chat, board, and agent state are polled from the
+//! control API, and every action — messages, task moves, review verdicts —
+//! goes back through it, so the daemon applies them between poll-loop steps
+//! exactly like CLI commands. The peek view replays shim PTY logs straight
+//! from disk. Detaching only closes the console; the daemon and its agents
+//! keep running, and `batty console` reattaches at any time.
+
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{Result, bail};
+use chrono::{Local, TimeZone};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::console_pane::{
+    RawTerminal, flush_retry, is_transient_io_error, set_nonblocking, terminal_size, write_retry,
+};
+use crate::team::api::ApiClient;
+use crate::team::config::TeamConfig;
+
+const TICK: Duration = Duration::from_millis(50);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CHAT_HISTORY: usize = 200;
+const EVENT_LIMIT: usize = 50;
+/// Bytes of PTY log replayed into the peek screen.
+const PEEK_TAIL_BYTES: u64 = 256 * 1024;
+/// The console always speaks as the CLI user.
+const CONSOLE_USER: &str = "human";
+/// Board columns shown in the board view, in display order.
+const BOARD_STATUSES: &[&str] = &["review", "in-progress", "todo", "backlog", "blocked"];
+/// Team events echoed into the chat view.
+const CHAT_EVENTS: &[&str] = &[
+    "task_assigned",
+    "task_completed",
+    "task_escalated",
+    "task_auto_merged",
+    "task_manual_merged",
+    "task_merge_failed",
+    "review_escalated",
+    "member_crashed",
+    "stall_detected",
+    "command_loop_detected",
+    "budget_hard_limit",
+    "main_broken",
+];
+
+const ENTER_ALT_SCREEN: &[u8] = b"\x1b[?1049h\x1b[?25l";
+const LEAVE_ALT_SCREEN: &[u8] = b"\x1b[?25h\x1b[?1049l";
+const REVERSE: &str = "\x1b[7m";
+const DIM: &str = "\x1b[2m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Attach to the daemon running in `project_root` and run until detached.
+pub fn run(project_root: &Path) -> Result<()> {
+    let api_config = TeamConfig::load(&crate::team::team_config_path(project_root))
+        .map(|config| config.api)
+        .unwrap_or_default();
+    let client = ApiClient::for_project(project_root, &api_config)?;
+    let mut state = ConsoleState::default();
+    // Fail before touching the terminal when the daemon is unreachable.
+    state.apply(fetch_snapshot(&client)?);
+    state.notice = attach_summary(&state.snapshot);
+
+    let raw = RawTerminal::new()?;
+    let mut stdout = io::stdout().lock();
+    let stdout_fd = stdout.as_raw_fd();
+    set_nonblocking(stdout_fd, false)?;
+    let mut stdin = io::stdin().lock();
+    set_nonblocking(stdin.as_raw_fd(), true)?;
+
+    write_retry(&mut stdout, ENTER_ALT_SCREEN)?;
+    let result = event_loop(
+        project_root,
+        &client,
+        &mut state,
+        &mut stdin,
+        &mut stdout,
+        stdout_fd,
+    );
+    write_retry(&mut stdout, LEAVE_ALT_SCREEN)?;
+    flush_retry(&mut stdout)?;
+    drop(stdout);
+    drop(raw);
+
+    result?;
+    println!("Detached from console; the team keeps running. Reattach with `batty console`.");
+    Ok(())
+}
+
+fn event_loop(
+    project_root: &Path,
+    client: &ApiClient,
+    state: &mut ConsoleState,
+    stdin: &mut impl Read,
+    stdout: &mut impl Write,
+    stdout_fd: i32,
+) -> Result<()> {
+    let mut next_poll = Instant::now() + POLL_INTERVAL;
+    let mut last_frame = String::new();
+    let mut peek = PeekCache::default();
+
+    loop {
+        let mut buf = [0u8; 256];
+        match stdin.read(&mut buf) {
+            Ok(0) => {}
+            Ok(n) => {
+                for key in parse_keys(&buf[..n]) {
+                    match state.handle_key(key) {
+                        Some(Action::Detach) => return Ok(()),
+                        Some(action) => {
+                            state.notice = match run_action(client, &action) {
+                                Ok(notice) => notice,
+                                Err(error) => format!("error: {error:#}"),
+                            };
+                            // Show the effect of the action on the next frame.
+                            next_poll = Instant::now();
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Err(error) if is_transient_io_error(&error) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        if Instant::now() >= next_poll {
+            match fetch_snapshot(client) {
+                Ok(snapshot) => state.apply(snapshot),
+                Err(error) => state.notice = format!("daemon unreachable: {error:#}"),
+            }
+            next_poll = Instant::now() + POLL_INTERVAL;
+        }
+
+        let (rows, cols) = terminal_size(stdout_fd).unwrap_or((24, 80));
+        let peek_lines = match (state.view, state.peek_target()) {
+            (View::Peek, Some(member)) => {
+                let body_rows = usize::from(rows).saturating_sub(FRAME_CHROME_ROWS + 1);
+                peek.lines(
+                    &crate::team::shim_log_path(project_root, &member),
+                    body_rows.max(1) as u16,
+                    cols.max(1),
+                )
+            }
+            _ => &[],
+        };
+        let frame = state
+            .render(usize::from(rows), usize::from(cols), peek_lines)
+            .iter()
+            .map(|line| format!("{line}{RESET}\x1b[K"))
+            .collect::<Vec<_>>()
+            .join("\r\n");
+        if frame != last_frame {
+            write_retry(stdout, format!("\x1b[H{frame}\x1b[J").as_bytes())?;
+            flush_retry(stdout)?;
+            last_frame = frame;
+        }
+
+        std::thread::sleep(TICK);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum View {
+    #[default]
+    Chat,
+    Board,
+    Agents,
+    Peek,
+}
+
+impl View {
+    const ALL: [Self; 4] = [Self::Chat, Self::Board, Self::Agents, Self::Peek];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Chat => "Chat",
+            Self::Board => "Board",
+            Self::Agents => "Agents",
+            Self::Peek => "Peek",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|view| *view == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn prev(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Esc,
+    Tab,
+    BackTab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Detach,
+}
+
+/// Decode raw terminal input into keys. Unknown escape sequences are dropped.
+fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut chars = text.chars().peekable();
+    let mut keys = Vec::new();
+    while let Some(ch) = chars.next() {
+        let key = match ch {
+            '\x1b' if matches!(chars.peek(), Some('[' | 'O')) => {
+                chars.next();
+                // Skip parameter bytes such as the `1;5` in `ESC [ 1 ; 5 A`.
+                let mut last = None;
+                for next in chars.by_ref() {
+                    if !(next.is_ascii_digit() || next == ';') {
+                        last = Some(next);
+                        break;
+                    }
+                }
+                match last {
+                    Some('A') => Key::Up,
+                    Some('B') => Key::Down,
+                    Some('C') => Key::Right,
+                    Some('D') => Key::Left,
+                    Some('Z') => Key::BackTab,
+                    _ => continue,
+                }
+            }
+            '\x1b' => Key::Esc,
+            '\r' | '\n' => Key::Enter,
+            '\x7f' | '\x08' => Key::Backspace,
+            '\t' => Key::Tab,
+            '\x03' | '\x04' => Key::Detach,
+            ch if !ch.is_control() => Key::Char(ch),
+            _ => continue,
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+/// A request the console sends to the daemon, or detaching from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    Send {
+        to: String,
+        message: String,
+    },
+    Transition {
+        task_id: u32,
+        status: String,
+    },
+    Review {
+        task_id: u32,
+        disposition: &'static str,
+        feedback: Option<String>,
+    },
+    Detach,
+}
+
+fn run_action(client: &ApiClient, action: &Action) -> Result<String> {
+    match action {
+        Action::Send { to, message } => {
+            client.post(
+                "/v1/send",
+                &json!({ "to": to, "message": message, "from": CONSOLE_USER }),
+            )?;
+            Ok(format!("sent to {to}"))
+        }
+        Action::Transition { task_id, status } => {
+            client.post(
+                &format!("/v1/tasks/{task_id}/transition"),
+                &json!({ "status": status }),
+            )?;
+            Ok(format!("moved #{task_id} to {status}"))
+        }
+        Action::Review {
+            task_id,
+            disposition,
+            feedback,
+        } => {
+            client.post(
+                &format!("/v1/tasks/{task_id}/review"),
+                &json!({
+                    "disposition": disposition,
+                    "feedback": feedback,
+                    "reviewer": CONSOLE_USER,
+                }),
+            )?;
+            Ok(format!("review #{task_id}: {disposition}"))
+        }
+        Action::Detach => bail!("detach is handled by the event loop"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PromptKind {
+    Move(u32),
+    RequestChanges(u32),
+    Message(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Prompt {
+    kind: PromptKind,
+    input: String,
+}
+
+impl Prompt {
+    fn label(&self) -> String {
+        match &self.kind {
+            PromptKind::Move(id) => format!("move #{id} to status"),
+            PromptKind::RequestChanges(id) => format!("feedback for #{id}"),
+            PromptKind::Message(member) => format!("message to {member}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct MemberRow {
+    name: String,
+    #[serde(default)]
+    role_type: String,
+    #[serde(default)]
+    agent: Option<String>,
+    #[serde(default)]
+    reports_to: Option<String>,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    pending_inbox: usize,
+    #[serde(default)]
+    active_owned_tasks: Vec<u32>,
+    #[serde(default)]
+    health_summary: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TaskRow {
+    id: u32,
+    title: String,
+    status: String,
+    #[serde(default)]
+    priority: String,
+    #[serde(default)]
+    claimed_by: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChatLine {
+    ts: u64,
+    text: String,
+    system: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    from: String,
+    to: String,
+    body: String,
+    timestamp: u64,
+}
+
+#[derive(Debug, Default)]
+struct Snapshot {
+    team: String,
+    paused: bool,
+    members: Vec<MemberRow>,
+    tasks: Vec<TaskRow>,
+    chat: Vec<ChatLine>,
+}
+
+fn fetch_snapshot(client: &ApiClient) -> Result<Snapshot> {
+    let status = client.get("/v1/status")?;
+    let board = client.get("/v1/board")?;
+    let members: Vec<MemberRow> =
+        serde_json::from_value(status.get("members").cloned().unwrap_or_default())
+            .unwrap_or_default();
+    let tasks: Vec<TaskRow> =
+        serde_json::from_value(board.get("tasks").cloned().unwrap_or_default()).unwrap_or_default();
+
+    let target = chat_target(&members).map(|member| member.name.clone());
+    let incoming = client.get(&format!("/v1/inbox/{CONSOLE_USER}"))?;
+    let outgoing = match &target {
+        Some(target) => client.get(&format!("/v1/inbox/{target}"))?,
+        None => Value::Null,
+    };
+    let events = client.get(&format!("/v1/events?limit={EVENT_LIMIT}"))?;
+
+    Ok(Snapshot {
+        team: status
+            .get("team")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        paused: status
+            .get("paused")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        members,
+        tasks,
+        chat: build_chat(&incoming, &outgoing, &events),
+    })
+}
+
+/// Merge messages to and from the console user with notable team events,
+/// oldest first.
+fn build_chat(incoming: &Value, outgoing: &Value, events: &Value) -> Vec<ChatLine> {
+    let messages = |inbox: &Value| -> Vec<ChatMessage> {
+        serde_json::from_value(inbox.get("messages").cloned().unwrap_or_default())
+            .unwrap_or_default()
+    };
+    let mut lines: Vec<ChatLine> = messages(incoming)
+        .into_iter()
+        .chain(
+            messages(outgoing)
+                .into_iter()
+                .filter(|message| message.from == CONSOLE_USER),
+        )
+        .map(|message| ChatLine {
+            ts: message.timestamp,
+            text: format!("{} -> {}: {}", message.from, message.to, message.body),
+            system: false,
+        })
+        .collect();
+
+    let events = events
+        .get("events")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    lines.extend(events.iter().filter_map(|event| {
+        let name = event.get("event")?.as_str()?;
+        if !CHAT_EVENTS.contains(&name) {
+            return None;
+        }
+        let field = |key: &str| event.get(key).and_then(Value::as_str);
+        let mut text = name.replace('_', " ");
+        if let Some(task) = field("task") {
+            text.push_str(&format!(" #{}", task.trim_start_matches('#')));
+        }
+        if let Some(role) = field("role") {
+            text.push_str(&format!(" ({role})"));
+        }
+        if let Some(reason) = field("reason") {
+            text.push_str(&format!(": {reason}"));
+        }
+        Some(ChatLine {
+            ts: event.get("ts").and_then(Value::as_u64).unwrap_or(0),
+            text,
+            system: true,
+        })
+    }));
+
+    lines.sort_by_key(|line| line.ts);
+    let skip = lines.len().saturating_sub(CHAT_HISTORY);
+    lines.split_off(skip)
+}
+
+/// The architect, or failing that the first top-level agent.
+fn chat_target(members: &[MemberRow]) -> Option<&MemberRow> {
+    members
+        .iter()
+        .find(|member| member.role_type == "architect")
+        .or_else(|| {
+            members
+                .iter()
+                .find(|member| member.reports_to.is_none() && member.role_type != "user")
+        })
+}
+
+fn attach_summary(snapshot: &Snapshot) -> String {
+    let active = snapshot
+        .tasks
+        .iter()
+        .filter(|task| task.status == "in-progress")
+        .count();
+    let review = snapshot
+        .tasks
+        .iter()
+        .filter(|task| task.status == "review")
+        .count();
+    let working = snapshot
+        .members
+        .iter()
+        .filter(|member| member.state == "working")
+        .count();
+    format!(
+        "attached to {}: {working}/{} members working, {active} in progress, {review} in review{}",
+        snapshot.team,
+        snapshot.members.len(),
+        if snapshot.paused { ", paused" } else { "" }
+    )
+}
+
+/// Rows used by the tab bar and the two footer lines.
+const FRAME_CHROME_ROWS: usize = 3;
+
+#[derive(Debug, Default)]
+struct ConsoleState {
+    view: View,
+    snapshot: Snapshot,
+    chat_input: String,
+    prompt: Option<Prompt>,
+    selected_task: usize,
+    selected_member: usize,
+    peek_member: Option<String>,
+    notice: String,
+}
+
+impl ConsoleState {
+    fn apply(&mut self, snapshot: Snapshot) {
+        self.snapshot = snapshot;
+        self.selected_task = self
+            .selected_task
+            .min(self.board_tasks().len().saturating_sub(1));
+        self.selected_member = self
+            .selected_member
+            .min(self.snapshot.members.len().saturating_sub(1));
+    }
+
+    fn chat_target(&self) -> Option<&str> {
+        chat_target(&self.snapshot.members).map(|member| member.name.as_str())
+    }
+
+    fn peek_target(&self) -> Option<String> {
+        self.peek_member.clone().or_else(|| {
+            self.snapshot
+                .members
+                .get(self.selected_member)
+                .map(|member| member.name.clone())
+        })
+    }
+
+    /// Tasks shown in the board view, grouped by column in display order.
+    fn board_tasks(&self) -> Vec<&TaskRow> {
+        BOARD_STATUSES
+            .iter()
+            .flat_map(|status| {
+                self.snapshot
+                    .tasks
+                    .iter()
+                    .filter(move |task| task.status == *status)
+            })
+            .collect()
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Action> {
+        if key == Key::Detach {
+            return Some(Action::Detach);
+        }
+        if self.prompt.is_some() {
+            return self.handle_prompt_key(key);
+        }
+        match key {
+            Key::Tab => {
+                self.view = self.view.next();
+                return None;
+            }
+            Key::BackTab => {
+                self.view = self.view.prev();
+                return None;
+            }
+            _ => {}
+        }
+        if self.view == View::Chat {
+            return self.handle_chat_key(key);
+        }
+        match key {
+            Key::Char('q') => return Some(Action::Detach),
+            Key::Char(digit @ '1'..='9') => {
+                let index = digit as usize - '1' as usize;
+                if let Some(member) = self.snapshot.members.get(index) {
+                    self.selected_member = index;
+                    self.peek_member = Some(member.name.clone());
+                    self.view = View::Peek;
+                }
+                return None;
+            }
+            _ => {}
+        }
+        match self.view {
+            View::Chat => None,
+            View::Board => self.handle_board_key(key),
+            View::Agents => self.handle_agents_key(key),
+            View::Peek => self.handle_peek_key(key),
+        }
+    }
+
+    fn handle_chat_key(&mut self, key: Key) -> Option<Action> {
+        match key {
+            Key::Char(ch) => self.chat_input.push(ch),
+            Key::Backspace => {
+                self.chat_input.pop();
+            }
+            Key::Esc => self.chat_input.clear(),
+            Key::Enter => {
+                let message = self.chat_input.trim().to_string();
+                if message.is_empty() {
+                    return None;
+                }
+                let Some(to) = self.chat_target().map(str::to_string) else {
+                    self.notice = "no architect to message".to_string();
+                    return None;
+                };
+                self.chat_input.clear();
+                return Some(Action::Send { to, message });
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_board_key(&mut self, key: Key) -> Option<Action> {
+        let count = self.board_tasks().len();
+        let selected = self
+            .board_tasks()
+            .get(self.selected_task)
+            .map(|task| task.id);
+        match (key, selected) {
+            (Key::Up | Key::Char('k'), _) => {
+                self.selected_task = self.selected_task.saturating_sub(1);
+            }
+            (Key::Down | Key::Char('j'), _) if self.selected_task + 1 < count => {
+                self.selected_task += 1;
+            }
+            (Key::Char('m'), Some(id)) => self.open_prompt(PromptKind::Move(id)),
+            (Key::Char('r'), Some(id)) => self.open_prompt(PromptKind::RequestChanges(id)),
+            (Key::Char('a'), Some(task_id)) => {
+                return Some(Action::Review {
+                    task_id,
+                    disposition: "approve",
+                    feedback: None,
+                });
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_agents_key(&mut self, key: Key) -> Option<Action> {
+        let count = self.snapshot.members.len();
+        let selected = self
+            .snapshot
+            .members
+            .get(self.selected_member)
+            .map(|member| member.name.clone());
+        match (key, selected) {
+            (Key::Up | Key::Char('k'), _) => {
+                self.selected_member = self.selected_member.saturating_sub(1);
+            }
+            (Key::Down | Key::Char('j'), _) if self.selected_member + 1 < count => {
+                self.selected_member += 1;
+            }
+            (Key::Enter | Key::Char('p'), Some(member)) => {
+                self.peek_member = Some(member);
+                self.view = View::Peek;
+            }
+            (Key::Char('m'), Some(member)) => self.open_prompt(PromptKind::Message(member)),
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_peek_key(&mut self, key: Key) -> Option<Action> {
+        let count = self.snapshot.members.len();
+        if count == 0 {
+            return None;
+        }
+        let current = self
+            .peek_target()
+            .and_then(|name| {
+                self.snapshot
+                    .members
+                    .iter()
+                    .position(|member| member.name == name)
+            })
+            .unwrap_or(0);
+        let next = match key {
+            Key::Right | Key::Char(']') => (current + 1) % count,
+            Key::Left | Key::Char('[') => (current + count - 1) % count,
+            Key::Esc => {
+                self.view = View::Agents;
+                return None;
+            }
+            _ => return None,
+        };
+        self.selected_member = next;
+        self.peek_member = Some(self.snapshot.members[next].name.clone());
+        None
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        self.prompt = Some(Prompt {
+            kind,
+            input: String::new(),
+        });
+    }
+
+    fn handle_prompt_key(&mut self, key: Key) -> Option<Action> {
+        let prompt = self.prompt.as_mut()?;
+        match key {
+            Key::Char(ch) => prompt.input.push(ch),
+            Key::Backspace => {
+                prompt.input.pop();
+            }
+            Key::Esc => self.prompt = None,
+            Key::Enter => {
+                let prompt = self.prompt.take()?;
+                let value = prompt.input.trim().to_string();
+                return match prompt.kind {
+                    PromptKind::Move(task_id) if !value.is_empty() => Some(Action::Transition {
+                        task_id,
+                        status: value,
+                    }),
+                    PromptKind::RequestChanges(task_id) => Some(Action::Review {
+                        task_id,
+                        disposition: "request-changes",
+                        feedback: (!value.is_empty()).then_some(value),
+                    }),
+                    PromptKind::Message(to) if !value.is_empty() => {
+                        Some(Action::Send { to, message: value })
+                    }
+                    _ => None,
+                };
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Lay out one frame. Lines may carry SGR styling but never exceed `cols`
+    /// visible characters.
+    fn render(&self, rows: usize, cols: usize, peek: &[String]) -> Vec<String> {
+        let body_rows = rows.saturating_sub(FRAME_CHROME_ROWS);
+        let mut lines = vec![self.render_tabs(cols)];
+        let mut body = match self.view {
+            View::Chat => self.render_chat(body_rows, cols),
+            View::Board => self.render_board(body_rows, cols),
+            View::Agents => self.render_agents(body_rows, cols),
+            View::Peek => self.render_peek(peek, cols),
+        };
+        body.truncate(body_rows);
+        body.resize(body_rows, String::new());
+        lines.extend(body);
+        lines.push(format!("{DIM}{}{RESET}", fit(&self.hints(), cols)));
+        lines.push(self.render_input(cols));
+        lines.truncate(rows);
+        lines
+    }
+
+    fn render_tabs(&self, cols: usize) -> String {
+        let mut title = format!(" batty console · {}", self.snapshot.team);
+        if self.snapshot.paused {
+            title.push_str(" [paused]");
+        }
+        let tabs: Vec<String> = View::ALL
+            .iter()
+            .map(|view| format!(" {} ", view.title()))
+            .collect();
+        let width =
+            title.chars().count() + 2 + tabs.iter().map(|t| t.chars().count()).sum::<usize>();
+        if width > cols {
+            return format!("{BOLD}{}{RESET}", fit(&title, cols));
+        }
+        let mut line = format!("{BOLD}{title}{RESET}  ");
+        for (view, tab) in View::ALL.iter().zip(tabs) {
+            if *view == self.view {
+                line.push_str(&format!("{REVERSE}{tab}{RESET}"));
+            } else {
+                line.push_str(&tab);
+            }
+        }
+        line
+    }
+
+    fn render_chat(&self, body_rows: usize, cols: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for entry in &self.snapshot.chat {
+            let prefix = format!("[{}] ", format_clock(entry.ts));
+            let text = if entry.system {
+                format!("· {}", entry.text)
+            } else {
+                entry.text.clone()
+            };
+            for (index, chunk) in wrap(&text, cols.saturating_sub(prefix.len()).max(1))
+                .into_iter()
+                .enumerate()
+            {
+                let lead = if index == 0 {
+                    prefix.clone()
+                } else {
+                    " ".repeat(prefix.len())
+                };
+                let line = fit(&format!("{lead}{chunk}"), cols);
+                lines.push(if entry.system {
+                    format!("{DIM}{line}{RESET}")
+                } else {
+                    line
+                });
+            }
+        }
+        if lines.is_empty() {
+            let hint = format!(
+                "No messages yet. Type below to talk to {}.",
+                self.chat_target().unwrap_or("the team")
+            );
+            lines.push(format!("{DIM}{}{RESET}", fit(&hint, cols)));
+        }
+        let skip = lines.len().saturating_sub(body_rows);
+        lines.split_off(skip)
+    }
+
+    fn render_board(&self, body_rows: usize, cols: usize) -> Vec<String> {
+        let tasks = self.board_tasks();
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+        let mut index = 0;
+        for status in BOARD_STATUSES {
+            let column: Vec<&&TaskRow> =
+                tasks.iter().filter(|task| task.status == *status).collect();
+            if column.is_empty() {
+                continue;
+            }
+            lines.push(format!(
+                "{BOLD}{}{RESET}",
+                fit(
+                    &format!("{} ({})", status.to_uppercase(), column.len()),
+                    cols
+                )
+            ));
+            for task in column {
+                let owner = task
+                    .claimed_by
+                    .as_deref()
+                    .or(task.assignee.as_deref())
+                    .map(|owner| format!("  @{owner}"))
+                    .unwrap_or_default();
+                let priority = if task.priority.is_empty() {
+                    String::new()
+                } else {
+                    format!("[{}] ", task.priority)
+                };
+                let line = fit(
+                    &format!("  #{} {priority}{}{owner}", task.id, task.title),
+                    cols,
+                );
+                if index == self.selected_task {
+                    selected_line = lines.len();
+                    lines.push(format!("{REVERSE}{line}{RESET}"));
+                } else {
+                    lines.push(line);
+                }
+                index += 1;
+            }
+        }
+        let done = self
+            .snapshot
+            .tasks
+            .iter()
+            .filter(|task| task.status == "done")
+            .count();
+        lines.push(format!(
+            "{DIM}{}{RESET}",
+            fit(&format!("{done} done"), cols)
+        ));
+
+        let skip = (selected_line + 1).saturating_sub(body_rows);
+        lines.split_off(skip)
+    }
+
+    fn render_agents(&self, body_rows: usize, cols: usize) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{BOLD}{}{RESET}",
+            fit(
+                &format!(
+                    "   {:<18} {:<10} {:<8} {:<10} {:>5} {:<10} {}",
+                    "MEMBER", "ROLE", "AGENT", "STATE", "INBOX", "TASKS", "HEALTH"
+                ),
+                cols
+            )
+        )];
+        for (index, member) in self.snapshot.members.iter().enumerate() {
+            let hotkey = if index < 9 {
+                format!("{}", index + 1)
+            } else {
+                " ".to_string()
+            };
+            let tasks = member
+                .active_owned_tasks
+                .iter()
+                .map(|id| format!("#{id}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            let line = fit(
+                &format!(
+                    "{hotkey}  {:<18} {:<10} {:<8} {:<10} {:>5} {:<10} {}",
+                    member.name,
+                    member.role_type,
+                    member.agent.as_deref().unwrap_or("-"),
+                    member.state,
+                    member.pending_inbox,
+                    if tasks.is_empty() { "-" } else { &tasks },
+                    member.health_summary
+                ),
+                cols,
+            );
+            lines.push(if index == self.selected_member {
+                format!("{REVERSE}{line}{RESET}")
+            } else {
+                line
+            });
+        }
+        // Keep the header pinned and the selected member in view.
+        let skip = (self.selected_member + 2).saturating_sub(body_rows);
+        lines.drain(1..1 + skip.min(lines.len() - 1));
+        lines
+    }
+
+    fn render_peek(&self, peek: &[String], cols: usize) -> Vec<String> {
+        let member = self.peek_target().unwrap_or_else(|| "-".to_string());
+        let mut lines = vec![format!(
+            "{BOLD}{}{RESET}",
+            fit(&format!("peek: {member}"), cols)
+        )];
+        lines.extend(peek.iter().cloned());
+        lines
+    }
+
+    fn hints(&self) -> String {
+        let view_hints = match self.view {
+            View::Chat => "Enter send  Esc clear",
+            View::Board => "j/k select  m move  a approve  r request changes",
+            View::Agents => "j/k select  Enter peek  m message  1-9 peek",
+            View::Peek => "[ ] switch agent  Esc back",
+        };
+        let detach = if self.view == View::Chat {
+            "Ctrl-D detach"
+        } else {
+            "q detach"
+        };
+        format!("Tab views  {view_hints}  {detach}")
+    }
+
+    fn render_input(&self, cols: usize) -> String {
+        if let Some(prompt) = &self.prompt {
+            return fit(&format!("{}: {}", prompt.label(), prompt.input), cols);
+        }
+        if self.view == View::Chat {
+            let target = self.chat_target().unwrap_or("team");
+            let line = format!("{target}> {}", self.chat_input);
+            // Keep the end of a long draft visible.
+            let overflow = line.chars().count().saturating_sub(cols);
+            return line.chars().skip(overflow).collect();
+        }
+        fit(&self.notice, cols)
+    }
+}
+
+/// Replays the tail of a member's PTY log into a virtual screen, re-rendering
+/// only when the log or the screen size changes.
+#[derive(Default)]
+struct PeekCache {
+    key: Option<(PathBuf, Option<SystemTime>, u16, u16)>,
+    lines: Vec<String>,
+}
+
+impl PeekCache {
+    fn lines(&mut self, path: &Path, rows: u16, cols: u16) -> &[String] {
+        let key = (
+            path.to_path_buf(),
+            fs::metadata(path).and_then(|meta| meta.modified()).ok(),
+            rows,
+            cols,
+        );
+        if self.key.as_ref() != Some(&key) {
+            self.lines = render_pty_tail(path, rows, cols);
+            self.key = Some(key);
+        }
+        &self.lines
+    }
+}
+
+fn render_pty_tail(path: &Path, rows: u16, cols: u16) -> Vec<String> {
+    let bytes = read_tail_bytes(path, PEEK_TAIL_BYTES);
+    let mut parser = vt100::Parser::new(rows, cols, 0);
+    parser.process(&bytes);
+    parser
+        .screen()
+        .rows_formatted(0, cols)
+        .map(|row| String::from_utf8_lossy(&row).into_owned())
+        .collect()
+}
+
+fn read_tail_bytes(path: &Path, max_bytes: u64) -> Vec<u8> {
+    let Ok(mut file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let len = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+    if len > max_bytes && file.seek(SeekFrom::Start(len - max_bytes)).is_err() {
+        return Vec::new();
+    }
+    let mut bytes = Vec::new();
+    let _ = file.read_to_end(&mut bytes);
+    bytes
+}
+
+fn format_clock(ts: u64) -> String {
+    Local
+        .timestamp_opt(ts as i64, 0)
+        .single()
+        .map(|dt| dt.format("%H:%M").to_string())
+        .unwrap_or_else(|| "--:--".to_string())
+}
+
+/// Truncate to `cols` characters, marking the cut with `…`.
+fn fit(text: &str, cols: usize) -> String {
+    let text = text.replace(['\n', '\r'], " ");
+    if text.chars().count() <= cols {
+        return text;
+    }
+    if cols == 0 {
+        return String::new();
+    }
+    let mut out: String = text.chars().take(cols - 1).collect();
+    out.push('…');
+    out
+}
+
+/// Split `text` into lines of at most `width` characters, breaking on
+/// whitespace where possible.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            let needed = current.chars().count() + usize::from(!current.is_empty());
+            if !current.is_empty() && needed + word.chars().count() > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            let mut word: Vec<char> = word.chars().collect();
+            while word.len() > width {
+                lines.push(word.drain(..width).collect());
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.extend(word);
+        }
+        lines.push(current);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, role_type: &str, reports_to: Option<&str>) -> MemberRow {
+        MemberRow {
+            name: name.to_string(),
+            role_type: role_type.to_string(),
+            reports_to: reports_to.map(str::to_string),
+            state: "idle".to_string(),
+            ..MemberRow::default()
+        }
+    }
+
+    fn task(id: u32, status: &str) -> TaskRow {
+        TaskRow {
+            id,
+            title: format!("task {id}"),
+            status: status.to_string(),
+            ..TaskRow::default()
+        }
+    }
+
+    fn state() -> ConsoleState {
+        let mut state = ConsoleState::default();
+        state.apply(Snapshot {
+            team: "demo".to_string(),
+            members: vec![
+                member("architect", "architect", None),
+                member("manager", "manager", Some("architect")),
+                member("eng-1-1", "engineer", Some("manager")),
+            ],
+            tasks: vec![task(3, "todo"), task(1, "review"), task(2, "done")],
+            ..Snapshot::default()
+        });
+        state
+    }
+
+    fn type_text(state: &mut ConsoleState, text: &str) {
+        for ch in text.chars() {
+            assert_eq!(state.handle_key(Key::Char(ch)), None);
+        }
+    }
+
+    fn strip_ansi(line: &str) -> String {
+        let mut out = String::new();
+        let mut chars = line.chars();
+        while let Some(ch) = chars.next() {
+            if ch == '\x1b' {
+                for next in chars.by_ref() {
+                    if next.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                out.push(ch);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn parse_keys_decodes_arrows_and_control_bytes() {
+        assert_eq!(
+            parse_keys(b"a\x1b[A\x1b[B\x1b[Z\x1b[1;5C\r\x7f\t\x03\x1b"),
+            vec![
+                Key::Char('a'),
+                Key::Up,
+                Key::Down,
+                Key::BackTab,
+                Key::Right,
+                Key::Enter,
+                Key::Backspace,
+                Key::Tab,
+                Key::Detach,
+                Key::Esc,
+            ]
+        );
+        assert_eq!(parse_keys("é".as_bytes()), vec![Key::Char('é')]);
+    }
+
+    #[test]
+    fn chat_enter_sends_to_architect() {
+        let mut state = state();
+        type_text(&mut state, "  ship it  ");
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Some(Action::Send {
+                to: "architect".to_string(),
+                message: "ship it".to_string(),
+            })
+        );
+        assert!(state.chat_input.is_empty());
+        assert_eq!(state.handle_key(Key::Enter), None);
+    }
+
+    #[test]
+    fn q_types_in_chat_but_detaches_elsewhere() {
+        let mut state = state();
+        assert_eq!(state.handle_key(Key::Char('q')), None);
+        assert_eq!(state.chat_input, "q");
+        state.handle_key(Key::Tab);
+        assert_eq!(state.view, View::Board);
+        assert_eq!(state.handle_key(Key::Char('q')), Some(Action::Detach));
+        assert_eq!(state.handle_key(Key::Detach), Some(Action::Detach));
+    }
+
+    #[test]
+    fn board_keys_move_and_review_selected_task() {
+        let mut state = state();
+        state.view = View::Board;
+        // Review column sorts first, so #1 is selected before #3.
+        assert_eq!(
+            state.handle_key(Key::Char('a')),
+            Some(Action::Review {
+                task_id: 1,
+                disposition: "approve",
+                feedback: None,
+            })
+        );
+
+        state.handle_key(Key::Down);
+        state.handle_key(Key::Down);
+        assert_eq!(state.selected_task, 1, "selection stops at the last task");
+        state.handle_key(Key::Char('m'));
+        type_text(&mut state, "in-progress");
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Some(Action::Transition {
+                task_id: 3,
+                status: "in-progress".to_string(),
+            })
+        );
+        assert!(state.prompt.is_none());
+
+        state.handle_key(Key::Up);
+        state.handle_key(Key::Char('r'));
+        type_text(&mut state, "needs tests");
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Some(Action::Review {
+                task_id: 1,
+                disposition: "request-changes",
+                feedback: Some("needs tests".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn prompt_escape_cancels_without_action() {
+        let mut state = state();
+        state.view = View::Board;
+        state.handle_key(Key::Char('m'));
+        type_text(&mut state, "done");
+        assert_eq!(state.handle_key(Key::Esc), None);
+        assert!(state.prompt.is_none());
+        assert_eq!(state.view, View::Board);
+    }
+
+    #[test]
+    fn agents_view_opens_peek_and_messages_members() {
+        let mut state = state();
+        state.view = View::Agents;
+        state.handle_key(Key::Char('j'));
+        state.handle_key(Key::Char('j'));
+        state.handle_key(Key::Char('m'));
+        type_text(&mut state, "status?");
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Some(Action::Send {
+                to: "eng-1-1".to_string(),
+                message: "status?".to_string(),
+            })
+        );
+
+        state.handle_key(Key::Enter);
+        assert_eq!(state.view, View::Peek);
+        assert_eq!(state.peek_target().as_deref(), Some("eng-1-1"));
+        state.handle_key(Key::Char(']'));
+        assert_eq!(state.peek_target().as_deref(), Some("architect"));
+        state.handle_key(Key::Char('2'));
+        assert_eq!(state.peek_target().as_deref(), Some("manager"));
+        state.handle_key(Key::Esc);
+        assert_eq!(state.view, View::Agents);
+    }
+
+    #[test]
+    fn render_fills_terminal_and_respects_width() {
+        let mut state = state();
+        for view in View::ALL {
+            state.view = view;
+            let frame = state.render(12, 40, &["screen row".to_string()]);
+            assert_eq!(frame.len(), 12, "{view:?}");
+            for line in &frame {
+                assert!(strip_ansi(line).chars().count() <= 40, "{view:?}: {line}");
+            }
+        }
+
+        state.view = View::Board;
+        let board: Vec<String> = state
+            .render(12, 60, &[])
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        let review = board
+            .iter()
+            .position(|l| l.starts_with("REVIEW (1)"))
+            .unwrap();
+        let todo = board
+            .iter()
+            .position(|l| l.starts_with("TODO (1)"))
+            .unwrap();
+        assert!(review < todo);
+        assert!(board.iter().any(|l| l == "1 done"));
+    }
+
+    #[test]
+    fn build_chat_merges_messages_and_events_in_time_order() {
+        let incoming = json!({ "messages": [
+            { "from": "architect", "to": "human", "body": "plan ready", "msg_type": "send", "timestamp": 30 }
+        ]});
+        let outgoing = json!({ "messages": [
+            { "from": "human", "to": "architect", "body": "build a cli", "msg_type": "send", "timestamp": 10 },
+            { "from": "manager", "to": "architect", "body": "not chat", "msg_type": "send", "timestamp": 15 }
+        ]});
+        let events = json!({ "events": [
+            { "event": "task_assigned", "role": "eng-1-1", "task": "7", "ts": 20 },
+            { "event": "daemon_heartbeat", "ts": 25 }
+        ]});
+
+        let chat = build_chat(&incoming, &outgoing, &events);
+        let texts: Vec<&str> = chat.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "human -> architect: build a cli",
+                "task assigned #7 (eng-1-1)",
+                "architect -> human: plan ready",
+            ]
+        );
+        assert!(chat[1].system);
+    }
+
+    #[test]
+    fn chat_target_falls_back_to_top_level_member() {
+        let members = vec![
+            member("human", "user", None),
+            member("lead", "manager", None),
+            member("eng", "engineer", Some("lead")),
+        ];
+        assert_eq!(
+            chat_target(&members).map(|member| member.name.as_str()),
+            Some("lead")
+        );
+        assert!(chat_target(&[]).is_none());
+    }
+
+    #[test]
+    fn wrap_and_fit_bound_line_width() {
+        assert_eq!(wrap("alpha beta gamma", 10), vec!["alpha beta", "gamma"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(fit("abcdef", 4), "abc…");
+        assert_eq!(fit("ab\ncd", 10), "ab cd");
+    }
+
+    #[test]
+    fn render_pty_tail_replays_screen() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("eng.pty.log");
+        fs::write(&path, "hello\r\n\x1b[31mred\x1b[0m").unwrap();
+        let rows = render_pty_tail(&path, 4, 20);
+        assert_eq!(rows.len(), 4);
+        assert!(strip_ansi(&rows[0]).starts_with("hello"));
+        assert!(rows[1].contains("red"));
+        assert!(
+            render_pty_tail(&tmp.path().join("missing"), 2, 10)
+                .iter()
+                .all(|row| strip_ansi(row).trim().is_empty())
+        );
+    }
+
+    #[test]
+    fn read_tail_bytes_keeps_the_end_of_large_logs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("pty.log");
+        fs::write(&path, "0123456789").unwrap();
+        assert_eq!(read_tail_bytes(&path, 4), b"6789");
+        assert_eq!(read_tail_bytes(&path, 100), b"0123456789");
+    }
+}
@@ -62,7 +62,7 @@ pub fn run(
     }
 }
 
-fn is_transient_io_error(error: &io::Error) -> bool {
+pub(crate) fn is_transient_io_error(error: &io::Error) -> bool {
     matches!(
         error.kind(),
         io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted | io::ErrorKind::TimedOut
@@ -354,7 +354,7 @@ fn clear_screen(stdout: &mut impl Write) -> Result<()> {
     Ok(())
 }
 
-fn write_retry(stdout: &mut impl Write, bytes: &[u8]) -> Result<()> {
+pub(crate) fn write_retry(stdout: &mut impl Write, bytes: &[u8]) -> Result<()> {
     let mut written = 0;
     while written < bytes.len() {
         match stdout.write(&bytes[written..]) {
@@ -369,7 +369,7 @@ fn write_retry(stdout: &mut impl Write, bytes: &[u8]) -> Result<()> {
     Ok(())
 }
 
-fn flush_retry(stdout: &mut impl Write) -> Result<()> {
+pub(crate) fn flush_retry(stdout: &mut impl Write) -> Result<()> {
     loop {
         match stdout.flush() {
             Ok(()) => return Ok(()),
@@ -381,7 +381,7 @@ fn flush_retry(stdout: &mut impl Write) -> Result<()> {
     }
 }
 
-fn terminal_size(fd: i32) -> Option<(u16, u16)> {
+pub(crate) fn terminal_size(fd: i32) -> Option<(u16, u16)> {
     let mut winsize = libc::winsize {
         ws_row: 0,
         ws_col: 0,
@@ -397,7 +397,7 @@ fn terminal_size(fd: i32) -> Option<(u16, u16)> {
     }
 }
 
-fn set_nonblocking(fd: i32, enabled: bool) -> Result<()> {
+pub(crate) fn set_nonblocking(fd: i32, enabled: bool) -> Result<()> {
     // Safety: fcntl on a valid file descriptor.
     let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
     if flags < 0 {
@@ -415,13 +415,13 @@ fn set_nonblocking(fd: i32, enabled: bool) -> Result<()> {
     Ok(())
 }
 
-struct RawTerminal {
+pub(crate) struct RawTerminal {
     fd: i32,
     original: libc::termios,
 }
 
 impl RawTerminal {
-    fn new() -> Result<Self> {
+    pub(crate) fn new() -> Result<Self> {
         let fd = io::stdin().as_raw_fd();
         let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
         // Safety: tcgetattr/tcsetattr operate on stdin fd and valid termios pointers.
@@ -3,9 +3,10 @@ use batty_cli::{
     agent,
     cli::{
         self, ActivityCommand, AutoMergeAction, BoardCommand, Cli, Command, DepsFormatArg,
-        DiscordCommand, GrafanaCommand, InboxCommand, NudgeCommand, OpenClawCommand,
-        OpenClawEventTopicArg, OpenClawFollowUpCommand, ProjectCommand, ResearchCommand,
-        ResearchFormatArg, ResearchKeepPolicyArg, ReviewDispositionArg, TaskCommand, TaskStateArg,
+        DiscordCommand, DispatchCommand, ForgeCommand, GrafanaCommand, InboxCommand,
+        LearningsCommand, NudgeCommand, OpenClawCommand, OpenClawEventTopicArg,
+        OpenClawFollowUpCommand, ProjectCommand, ResearchCommand, ResearchFormatArg,
+        ResearchKeepPolicyArg, ReviewDispositionArg, TaskCommand, TaskStateArg,
     },
     env_file, project_registry, release, team,
 };
@@ -330,6 +331,20 @@ fn main() -> Result<()> {
             println!("Run export written to {}", path.display());
         }
 
+        Command::Replay {
+            export,
+            tick_secs,
+            json,
+        } => {
+            let report =
+                team::replay::replay_run(&export, &team::replay::ReplayOptions { tick_secs })?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.render());
+            }
+        }
+
         Command::Retro { events } => {
             let stats = if let Some(events_path) = events {
                 // Explicit path: use JSONL directly.
@@ -364,6 +379,10 @@ fn main() -> Result<()> {
             team::attach_team(&root)?;
         }
 
+        Command::Console => {
+            batty_cli::console::run(&root)?;
+        }
+
         Command::Status {
             json,
             detail,
@@ -874,12 +893,21 @@ fn main() -> Result<()> {
                     print!("{}", team::board_health::format_health(&health));
                 }
                 None => {
-                    let status = std::process::Command::new("kanban-md")
+                    // The interactive TUI comes from the optional kanban-md
+                    // CLI; without it, fall back to the native table view.
+                    match std::process::Command::new("kanban-md")
                         .args(["tui", "--dir", &board_dir.to_string_lossy()])
                         .status()
-                        .context("failed to run kanban-md — is it installed?")?;
-                    if !status.success() {
-                        bail!("kanban-md tui failed");
+                    {
+                        Ok(status) if status.success() => {}
+                        Ok(_) => bail!("kanban-md tui failed"),
+                        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                            print!("{}", team::board_cmd::list_tasks(&board_dir, None)?);
+                            eprintln!(
+                                "Install kanban-md (`cargo install kanban-md --locked`) for the interactive board."
+                            );
+                        }
+                        Err(error) => return Err(error).context("failed to run kanban-md"),
                     }
                 }
             }
@@ -1083,6 +1111,86 @@ fn main() -> Result<()> {
             );
         }
 
+        Command::Telemetry {
+            command: cli::TelemetryCommand::Migrate { dry_run },
+        } => {
+            let report = team::telemetry_db::migrate(&root, dry_run)?;
+            if report.pending.is_empty() {
+                println!(
+                    "Telemetry schema is up to date (version {}).",
+                    report.from_version
+                );
+            } else {
+                println!(
+                    "Telemetry schema version {} -> {}{}",
+                    report.from_version,
+                    report.to_version,
+                    if dry_run { " (dry run)" } else { "" }
+                );
+                for migration in &report.pending {
+                    println!("  {:>3}  {}", migration.version, migration.name);
+                }
+                if let Some(backup) = &report.backup {
+                    println!("Backup written to {}", backup.display());
+                }
+            }
+        }
+
+        Command::Telemetry {
+            command: cli::TelemetryCommand::Quarantine,
+        } => {
+            let config_path = team::team_config_path(&root);
+            let policy = if config_path.exists() {
+                team::config::TeamConfig::load(&config_path)?
+                    .workflow_policy
+                    .flaky_tests
+            } else {
+                team::config::FlakyTestPolicy::default()
+            };
+            let state = team::quarantine::load_quarantine(&root)?;
+            let done = team::quarantine::done_task_ids(&team::team_config_dir(&root).join("board"));
+            if state.tests.is_empty() {
+                println!("No tests quarantined.");
+            } else {
+                println!(
+                    "{:<48} {:<12} {:<20} {:>6} {:>9} {:>7}",
+                    "TEST", "STATUS", "SINCE", "FLAKY", "FIX_TASK", "STABLE"
+                );
+                let now = chrono::Utc::now();
+                for (test, entry) in &state.tests {
+                    let since = chrono::DateTime::parse_from_rfc3339(&entry.since)
+                        .map(|since| format_ts(since.timestamp()))
+                        .unwrap_or_else(|_| entry.since.clone());
+                    let fix_task = entry
+                        .fix_task
+                        .map(|id| format!("#{id}"))
+                        .unwrap_or_else(|| "-".to_string());
+                    println!(
+                        "{:<48} {:<12} {:<20} {:>6} {:>9} {:>7}",
+                        test,
+                        entry.status(now, &policy, &done).as_str(),
+                        since,
+                        entry.flaky_runs,
+                        fix_task,
+                        format!("{}/{}", entry.stable_runs, policy.stable_runs)
+                    );
+                }
+            }
+            if !state.flaky_runs.is_empty() {
+                println!();
+                println!("Flaky, not yet quarantined:");
+                for (test, count) in &state.flaky_runs {
+                    println!("  {test} ({count}/{})", policy.quarantine_after);
+                }
+            }
+            if !policy.enabled {
+                println!();
+                println!(
+                    "workflow_policy.flaky_tests.enabled is false; the merge gate ignores this list."
+                );
+            }
+        }
+
         Command::Telemetry { command } => {
             let conn =
                 team::telemetry_db::open(&root).context("failed to open telemetry database")?;
@@ -1231,6 +1339,9 @@ fn main() -> Result<()> {
                     );
                     println!("Avg Review Latency: {}", avg_latency);
                 }
+                cli::TelemetryCommand::Migrate { .. } | cli::TelemetryCommand::Quarantine => {
+                    unreachable!("handled above")
+                }
                 cli::TelemetryCommand::Events { limit } => {
                     let rows = team::telemetry_db::query_recent_events(&conn, limit)?;
                     if rows.is_empty() {
@@ -1296,6 +1407,8 @@ fn main() -> Result<()> {
             graceful_shutdown_timeout_secs,
             auto_commit_on_restart,
             sdk_mode,
+            classifier_profile,
+            sandbox,
         } => {
             use batty_cli::shim;
             use std::os::unix::io::FromRawFd;
@@ -1303,6 +1416,16 @@ fn main() -> Result<()> {
 
             let at: shim::classifier::AgentType =
                 agent_type.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            if let Some(profile) = classifier_profile {
+                let profile: shim::classifier::CustomProfile = serde_json::from_str(&profile)
+                    .context("failed to parse --classifier-profile")?;
+                shim::classifier::install_custom_profile(&profile)
+                    .map_err(|e| anyhow::anyhow!(e))?;
+            }
+            let sandbox = sandbox
+                .map(|policy| serde_json::from_str::<shim::sandbox::SandboxPolicy>(&policy))
+                .transpose()
+                .context("failed to parse --sandbox")?;
 
             // Recover the channel socket from fd 3 (inherited from parent).
             let stream = unsafe { UnixStream::from_raw_fd(3) };
@@ -1318,6 +1441,7 @@ fn main() -> Result<()> {
                 pty_log_path: pty_log_path.map(PathBuf::from),
                 graceful_shutdown_timeout_secs,
                 auto_commit_on_restart,
+                sandbox,
             };
 
             if sdk_mode {
@@ -1337,6 +1461,10 @@ fn main() -> Result<()> {
             }
         }
 
+        Command::ScopeCheck => {
+            team::scope_fence::run_scope_check(&std::env::current_dir()?)?;
+        }
+
         Command::ConsolePane {
             project_root,
             member,
@@ -1418,6 +1546,14 @@ fn main() -> Result<()> {
             }
         }
 
+        Command::Approval { command } => {
+            team::approval::run(&root, command)?;
+        }
+
+        Command::Op { command } => {
+            team::operator::run_cli(&root, &command)?;
+        }
+
         Command::Queue => {
             let entries = team::daemon::load_dispatch_queue_snapshot(&root);
             if entries.is_empty() {
@@ -1441,7 +1577,18 @@ fn main() -> Result<()> {
             }
         }
 
-        Command::Dispatch { explain, task } => {
+        Command::Dispatch {
+            command: Some(DispatchCommand::Train { min_samples, json }),
+            ..
+        } => {
+            team::allocation_model::run_train(&root, min_samples, json)?;
+        }
+
+        Command::Dispatch {
+            command: None,
+            explain,
+            task,
+        } => {
             if !explain {
                 println!("Use `batty dispatch --explain` to inspect the current routing decision.");
             } else {
@@ -1540,6 +1687,9 @@ fn main() -> Result<()> {
                 GrafanaCommand::Setup => team::grafana::setup(&root, port)?,
                 GrafanaCommand::Status => team::grafana::status(port)?,
                 GrafanaCommand::Open => team::grafana::open(port)?,
+                GrafanaCommand::Prometheus { url } => {
+                    team::grafana::provision_prometheus_dashboard(port, &url)?
+                }
             }
         }
 
@@ -1547,6 +1697,17 @@ fn main() -> Result<()> {
             team::grafana::run_alert_webhook(std::path::Path::new(&project_root), port)?;
         }
 
+        Command::Forge { command } => match command {
+            ForgeCommand::Sync => team::forge_sync::run_sync(&root)?,
+            ForgeCommand::Status { json } => team::forge_sync::show_status(&root, json)?,
+        },
+
+        Command::Learnings { command } => match command {
+            LearningsCommand::Search { query, limit, json } => {
+                team::learnings_index::run_search(&root, &query.join(" "), limit, json)?
+            }
+        },
+
         Command::Discord { command } => match command.unwrap_or(DiscordCommand::Setup) {
             DiscordCommand::Setup => team::setup_discord(&root)?,
             DiscordCommand::Status => team::discord_status(&root)?,
@@ -1105,7 +1105,10 @@ fn load_project_status_report(
             active_tasks,
             review_queue,
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             optional_subsystems: None,
+            pattern_actions: None,
             members: rows,
         },
     ))
@@ -1811,8 +1814,11 @@ mod tests {
             active_tasks: Vec::new(),
             review_queue: Vec::new(),
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             members: Vec::new(),
             optional_subsystems: None,
+            pattern_actions: None,
         };
 
         let mut stopped = base.clone();
@@ -183,6 +183,28 @@ impl PromptPatterns {
         }
     }
 
+    /// Build prompt patterns for a config-defined agent.
+    ///
+    /// `prompt` regexes mark the agent idle at its input prompt;
+    /// `context_exhausted` regexes surface as errors. Invalid regexes are
+    /// skipped here — `batty validate` reports them.
+    pub fn from_custom(prompt: &[String], context_exhausted: &[String]) -> Self {
+        let mut patterns: Vec<(Regex, PromptClassifier)> = Vec::new();
+        for pattern in context_exhausted {
+            if let Ok(regex) = Regex::new(pattern) {
+                patterns.push((regex, |s| PromptKind::Error {
+                    detail: s.to_string(),
+                }));
+            }
+        }
+        for pattern in prompt {
+            if let Ok(regex) = Regex::new(pattern) {
+                patterns.push((regex, |_| PromptKind::WaitingForInput));
+            }
+        }
+        Self { patterns }
+    }
+
     /// Build prompt patterns for Aider.
     ///
     /// Aider uses a line-oriented interface, making it the most reliable
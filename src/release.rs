@@ -154,9 +154,10 @@ struct ConfiguredVerificationRunner {
 impl VerificationRunner for ConfiguredVerificationRunner {
     fn run(&self, project_root: &Path) -> Result<ReleaseVerification> {
         let command = resolve_verification_command(project_root, self.command_override.as_deref())?;
-        let run = run_automatic_verification(project_root, Some(&command)).with_context(|| {
-            format!("failed while running release verification command `{command}`")
-        })?;
+        let run = run_automatic_verification(project_root, Some(&command), None, None)
+            .with_context(|| {
+                format!("failed while running release verification command `{command}`")
+            })?;
         let summary = if run.passed {
             run.results
                 .summary
@@ -179,6 +180,7 @@ impl VerificationRunner for ConfiguredVerificationRunner {
 }
 
 pub fn cmd_release(project_root: &Path, requested_tag: Option<&str>) -> Result<()> {
+    require_release_approval(project_root, requested_tag)?;
     let verifier = ConfiguredVerificationRunner {
         command_override: None,
     };
@@ -651,6 +653,27 @@ fn resolve_verification_command(
     Ok(command)
 }
 
+/// Apply the `release` approval gate when the team config enables it. A
+/// release whose metadata cannot load is left to fail in the release run.
+fn require_release_approval(project_root: &Path, requested_tag: Option<&str>) -> Result<()> {
+    let team_config_path = crate::team::team_config_path(project_root);
+    if !team_config_path.exists() {
+        return Ok(());
+    }
+    let config = TeamConfig::load(&team_config_path)
+        .with_context(|| format!("failed to load {}", team_config_path.display()))?;
+    let Ok(metadata) = load_release_metadata(project_root, requested_tag) else {
+        return Ok(());
+    };
+    crate::team::approval::require_cli_approval(
+        project_root,
+        &config.workflow_policy.approvals,
+        crate::team::approval::ApprovalAction::Release,
+        &metadata.tag,
+        &format!("release {} {}", metadata.package_name, metadata.version),
+    )
+}
+
 fn load_release_metadata(
     project_root: &Path,
     requested_tag: Option<&str>,
@@ -56,6 +56,7 @@ mod tests {
             pty_log_path: None,
             graceful_shutdown_timeout_secs: 5,
             auto_commit_on_restart: true,
+            sandbox: None,
         };
 
         std::thread::spawn(move || {
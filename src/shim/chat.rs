@@ -26,7 +26,7 @@ pub fn default_cmd(agent_type: AgentType) -> &'static str {
         AgentType::Claude => "claude --dangerously-skip-permissions",
         AgentType::Codex => "codex --dangerously-bypass-approvals-and-sandbox",
         AgentType::Kiro => "kiro-cli",
-        AgentType::Generic => "bash",
+        AgentType::Generic | AgentType::Custom => "bash",
     }
 }
 
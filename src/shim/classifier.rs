@@ -1,8 +1,13 @@
 //! State classifiers: determine agent state from virtual screen content.
 //!
 //! Each agent type (Claude, Codex, Kiro, Generic) has different prompt
-//! patterns, spinner indicators, and context exhaustion messages.
+//! patterns, spinner indicators, and context exhaustion messages. Custom
+//! agents declared in config supply their own regexes through a
+//! [`CustomProfile`] installed once per shim process.
 
+use std::sync::OnceLock;
+
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 /// What the classifier thinks the agent is doing.
@@ -68,6 +73,8 @@ pub enum AgentType {
     Codex,
     Kiro,
     Generic,
+    /// Config-defined agent classified by the installed [`CustomProfile`].
+    Custom,
 }
 
 impl std::str::FromStr for AgentType {
@@ -78,6 +85,7 @@ impl std::str::FromStr for AgentType {
             "codex" => Ok(Self::Codex),
             "kiro" => Ok(Self::Kiro),
             "generic" | "bash" | "shell" => Ok(Self::Generic),
+            "custom" => Ok(Self::Custom),
             _ => Err(format!("unknown agent type: {s}")),
         }
     }
@@ -90,6 +98,7 @@ impl std::fmt::Display for AgentType {
             Self::Codex => write!(f, "codex"),
             Self::Kiro => write!(f, "kiro"),
             Self::Generic => write!(f, "generic"),
+            Self::Custom => write!(f, "custom"),
         }
     }
 }
@@ -125,6 +134,10 @@ pub fn classify_with_confidence(agent_type: AgentType, screen: &vt100::Screen) -
         AgentType::Codex => classify_codex(&content),
         AgentType::Kiro => classify_kiro(&content),
         AgentType::Generic => classify_generic(&content),
+        AgentType::Custom => match CUSTOM_PROFILE.get() {
+            Some(profile) => profile.classify(&content),
+            None => classify_generic(&content),
+        },
     }
 }
 
@@ -157,7 +170,7 @@ pub fn detect_meta_conversation(content: &str, agent_type: AgentType) -> bool {
             "target/",
         ],
         AgentType::Kiro => &["applying", "$ ", "\n$ ", "running…", "running..."],
-        AgentType::Generic => &["$ ", "\n$ ", "exit code:"],
+        AgentType::Generic | AgentType::Custom => &["$ ", "\n$ ", "exit code:"],
     };
     if tool_markers.iter().any(|marker| trimmed.contains(marker)) {
         return false;
@@ -333,7 +346,7 @@ fn has_command_or_tool_signal(line: &str, agent_type: AgentType) -> bool {
         AgentType::Kiro => ["applying", "running…", "running..."]
             .iter()
             .any(|marker| line.to_ascii_lowercase().contains(marker)),
-        AgentType::Generic => false,
+        AgentType::Generic | AgentType::Custom => false,
     }
 }
 
@@ -628,6 +641,126 @@ fn classify_generic(content: &str) -> Classification {
     Classification::unknown()
 }
 
+// ---------------------------------------------------------------------------
+// Custom (config-defined) classifier
+// ---------------------------------------------------------------------------
+
+/// Screen regexes for a config-defined agent. The daemon serializes this to
+/// JSON and hands it to the shim with `--classifier-profile`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomProfile {
+    /// Matches the idle input prompt on one of the last few screen lines.
+    #[serde(default)]
+    pub prompt: Vec<String>,
+    /// Matches a busy/spinner line; checked before `prompt`.
+    #[serde(default)]
+    pub spinner: Vec<String>,
+    /// Matches a context-exhausted message anywhere on screen.
+    #[serde(default)]
+    pub context_exhausted: Vec<String>,
+    /// Matches a quota or rate-limit screen.
+    #[serde(default)]
+    pub quota: Vec<String>,
+    /// Whether the CLI is a raw-mode TUI (bracketed paste, CR for Enter)
+    /// rather than a line-oriented REPL.
+    #[serde(default)]
+    pub raw_mode: bool,
+}
+
+#[derive(Debug)]
+struct CompiledProfile {
+    prompt: Vec<Regex>,
+    spinner: Vec<Regex>,
+    context_exhausted: Vec<Regex>,
+    quota: Vec<Regex>,
+    raw_mode: bool,
+}
+
+static CUSTOM_PROFILE: OnceLock<CompiledProfile> = OnceLock::new();
+
+fn compile_patterns(kind: &str, patterns: &[String]) -> Result<Vec<Regex>, String> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .map_err(|error| format!("invalid {kind} pattern `{pattern}`: {error}"))
+        })
+        .collect()
+}
+
+impl CustomProfile {
+    /// Compile every pattern, returning the first error.
+    pub fn check(&self) -> Result<(), String> {
+        self.compile().map(|_| ())
+    }
+
+    fn compile(&self) -> Result<CompiledProfile, String> {
+        Ok(CompiledProfile {
+            prompt: compile_patterns("prompt", &self.prompt)?,
+            spinner: compile_patterns("spinner", &self.spinner)?,
+            context_exhausted: compile_patterns("context_exhausted", &self.context_exhausted)?,
+            quota: compile_patterns("quota", &self.quota)?,
+            raw_mode: self.raw_mode,
+        })
+    }
+}
+
+/// Install the profile used by [`AgentType::Custom`]. A shim runs one agent,
+/// so this is set once at startup; later calls are rejected.
+pub fn install_custom_profile(profile: &CustomProfile) -> Result<(), String> {
+    let compiled = profile.compile()?;
+    CUSTOM_PROFILE
+        .set(compiled)
+        .map_err(|_| "custom classifier profile already installed".to_string())
+}
+
+/// Whether the installed custom profile describes a raw-mode TUI.
+pub fn custom_profile_raw_mode() -> bool {
+    CUSTOM_PROFILE.get().is_some_and(|profile| profile.raw_mode)
+}
+
+/// Return the screen line matching the installed profile's quota patterns.
+pub fn detect_custom_quota(content: &str) -> Option<String> {
+    CUSTOM_PROFILE.get()?.quota_line(content)
+}
+
+impl CompiledProfile {
+    fn classify(&self, content: &str) -> Classification {
+        if self.context_exhausted.iter().any(|re| re.is_match(content)) {
+            return Classification::exact(ScreenVerdict::ContextExhausted);
+        }
+
+        let recent_nonempty: Vec<&str> = content
+            .lines()
+            .rev()
+            .filter(|l| !l.trim().is_empty())
+            .take(6)
+            .collect();
+
+        if recent_nonempty
+            .iter()
+            .any(|line| self.spinner.iter().any(|re| re.is_match(line)))
+        {
+            return Classification::exact(ScreenVerdict::AgentWorking);
+        }
+        if recent_nonempty
+            .iter()
+            .any(|line| self.prompt.iter().any(|re| re.is_match(line)))
+        {
+            return Classification::exact(ScreenVerdict::AgentIdle);
+        }
+
+        Classification::unknown()
+    }
+
+    fn quota_line(&self, content: &str) -> Option<String> {
+        content
+            .lines()
+            .find(|line| self.quota.iter().any(|re| re.is_match(line)))
+            .map(|line| line.trim().to_string())
+    }
+}
+
 fn best_phrase_confidence(line: &str, phrases: &[&str]) -> Option<f32> {
     phrases
         .iter()
@@ -1109,6 +1242,7 @@ mod tests {
         assert_eq!("generic".parse::<AgentType>().unwrap(), AgentType::Generic);
         assert_eq!("bash".parse::<AgentType>().unwrap(), AgentType::Generic);
         assert_eq!("shell".parse::<AgentType>().unwrap(), AgentType::Generic);
+        assert_eq!("custom".parse::<AgentType>().unwrap(), AgentType::Custom);
         assert!("unknown".parse::<AgentType>().is_err());
     }
 
@@ -1118,6 +1252,7 @@ mod tests {
         assert_eq!(AgentType::Codex.to_string(), "codex");
         assert_eq!(AgentType::Kiro.to_string(), "kiro");
         assert_eq!(AgentType::Generic.to_string(), "generic");
+        assert_eq!(AgentType::Custom.to_string(), "custom");
     }
 
     #[test]
@@ -1189,4 +1324,63 @@ mod tests {
             );
         }
     }
+
+    fn aider_profile() -> CustomProfile {
+        CustomProfile {
+            prompt: vec![r"^(\w+\s*)?>\s*$".to_string()],
+            spinner: vec![r"Waiting for .*\.\.\.".to_string()],
+            context_exhausted: vec![r"exceeds the .* token limit".to_string()],
+            quota: vec![r"(?i)rate limit".to_string()],
+            raw_mode: false,
+        }
+    }
+
+    #[test]
+    fn custom_profile_classifies_prompt_spinner_and_exhaustion() {
+        let profile = aider_profile().compile().unwrap();
+
+        assert_eq!(
+            profile
+                .classify("Applied edit to src/lib.rs\ncode> ")
+                .verdict,
+            ScreenVerdict::AgentIdle
+        );
+
+        assert_eq!(
+            profile
+                .classify("code> fix the bug\nWaiting for gpt-4o...")
+                .verdict,
+            ScreenVerdict::AgentWorking
+        );
+
+        assert_eq!(
+            profile
+                .classify("Your chat exceeds the 128k token limit\ncode> ")
+                .verdict,
+            ScreenVerdict::ContextExhausted
+        );
+
+        assert_eq!(
+            profile.classify("compiling...").verdict,
+            ScreenVerdict::Unknown
+        );
+    }
+
+    #[test]
+    fn custom_profile_finds_quota_line() {
+        let profile = aider_profile().compile().unwrap();
+        assert_eq!(
+            profile.quota_line("ok\n  Rate limit reached, retry in 20s  \n> "),
+            Some("Rate limit reached, retry in 20s".to_string())
+        );
+        assert_eq!(profile.quota_line("all good\n> "), None);
+    }
+
+    #[test]
+    fn custom_profile_check_reports_bad_regex() {
+        let mut profile = aider_profile();
+        profile.spinner.push("([".to_string());
+        let err = profile.check().unwrap_err();
+        assert!(err.contains("invalid spinner pattern"), "{err}");
+    }
 }
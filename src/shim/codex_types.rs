@@ -6,6 +6,8 @@
 
 use serde::Deserialize;
 
+use super::tool_activity::ToolCall;
+
 pub const CODEX_DEFAULT_MODEL: &str = "gpt-5.5";
 pub const CODEX_DEFAULT_REASONING_EFFORT: &str = "high";
 
@@ -78,9 +80,55 @@ pub struct CodexItem {
     pub exit_code: Option<i32>,
     #[serde(default)]
     pub status: Option<String>,
+
+    // file_change
+    #[serde(default)]
+    pub changes: Vec<CodexFileChange>,
+
+    // mcp_tool_call
+    #[serde(default)]
+    pub tool: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CodexFileChange {
+    pub path: String,
+    #[serde(default)]
+    pub kind: Option<String>,
 }
 
+/// Item types that are agent output rather than tool calls.
+const NON_TOOL_ITEM_TYPES: &[&str] = &["agent_message", "reasoning", "todo_list", "error"];
+
 impl CodexItem {
+    /// Describe this item as a tool call, or `None` for text items.
+    pub fn tool_call(&self) -> Option<ToolCall> {
+        if NON_TOOL_ITEM_TYPES.contains(&self.item_type.as_str()) {
+            return None;
+        }
+        let paths = self
+            .changes
+            .iter()
+            .filter(|change| change.kind.as_deref() != Some("delete"))
+            .map(|change| change.path.clone())
+            .collect();
+        let tool = match (self.item_type.as_str(), self.tool.as_deref()) {
+            ("mcp_tool_call", Some(tool)) => tool.to_string(),
+            (item_type, _) => item_type.to_string(),
+        };
+        Some(ToolCall {
+            id: self.id.clone(),
+            tool,
+            paths,
+            command: self.command.clone(),
+        })
+    }
+
+    /// Whether a completed tool item failed.
+    pub fn tool_failed(&self) -> bool {
+        self.status.as_deref() == Some("failed") || self.exit_code.is_some_and(|code| code != 0)
+    }
+
     /// Extract text from an agent_message or reasoning item.
     pub fn agent_text(&self) -> Option<&str> {
         if self.item_type == "agent_message" || self.item_type == "reasoning" {
@@ -205,6 +253,35 @@ mod tests {
         assert!(item.agent_text().is_none());
     }
 
+    #[test]
+    fn command_and_file_change_items_map_to_tool_calls() {
+        let line = r#"{"type":"item.completed","item":{"id":"item_1","type":"command_execution","command":"cargo test","exit_code":101,"status":"failed"}}"#;
+        let item = serde_json::from_str::<CodexEvent>(line)
+            .unwrap()
+            .item
+            .unwrap();
+        let call = item.tool_call().unwrap();
+        assert_eq!(call.tool, "command_execution");
+        assert_eq!(call.command.as_deref(), Some("cargo test"));
+        assert!(item.tool_failed());
+
+        let line = r#"{"type":"item.completed","item":{"id":"item_2","type":"file_change","changes":[{"path":"src/a.rs","kind":"update"},{"path":"src/b.rs","kind":"delete"}],"status":"completed"}}"#;
+        let item = serde_json::from_str::<CodexEvent>(line)
+            .unwrap()
+            .item
+            .unwrap();
+        let call = item.tool_call().unwrap();
+        assert_eq!(call.paths, vec!["src/a.rs"]);
+        assert!(!item.tool_failed());
+
+        let line = r#"{"type":"item.completed","item":{"id":"item_3","type":"agent_message","text":"done"}}"#;
+        let item = serde_json::from_str::<CodexEvent>(line)
+            .unwrap()
+            .item
+            .unwrap();
+        assert!(item.tool_call().is_none());
+    }
+
     #[test]
     fn parse_error_event() {
         let line = r#"{"type":"error","message":"fatal"}"#;
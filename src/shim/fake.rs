@@ -20,8 +20,9 @@
 //! inside the next tick).
 //!
 //! Ticket #638 of the scenario framework execution plan.
-
-#![cfg(any(test, feature = "scenario-test"))]
+//!
+//! Unlike the rest of the scenario framework this module ships in release
+//! builds: `batty replay` drives recorded incidents through fake shims.
 
 use std::collections::VecDeque;
 use std::io;
@@ -155,6 +156,20 @@ impl FakeShim {
         self.state
     }
 
+    /// Send an unsolicited event to the daemon, as a real shim does when
+    /// the agent finishes, dies, or runs out of context on its own.
+    /// Tracks the fake's state for `StateChanged`, `Died`, and
+    /// `ContextExhausted` so later behaviors start from the right state.
+    pub fn emit(&mut self, event: Event) -> Result<()> {
+        match &event {
+            Event::StateChanged { to, .. } => self.state = *to,
+            Event::Died { .. } => self.state = ShimState::Dead,
+            Event::ContextExhausted { .. } => self.state = ShimState::ContextExhausted,
+            _ => {}
+        }
+        self.child.send(&event)
+    }
+
     /// Drain all pending commands from the channel and respond to each one
     /// according to its matching behavior. Returns the full event sequence
     /// that was sent back to the daemon (useful for assertions).
@@ -9,6 +9,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::tool_activity::ToolCall;
+
 // ---------------------------------------------------------------------------
 // JSON-RPC 2.0 request/response wrappers
 // ---------------------------------------------------------------------------
@@ -197,6 +199,71 @@ pub fn extract_message_chunk_text(params: &Value) -> Option<&str> {
         .and_then(|t| t.as_str())
 }
 
+/// Extract the tool call announced by a `tool_call` or `tool_call_update`
+/// update. `edit` calls carry their target paths in `locations`; `execute`
+/// calls carry the command line in `rawInput.command`.
+pub fn extract_tool_call(params: &Value) -> Option<ToolCall> {
+    let update = params.get("update")?;
+    let id = update.get("toolCallId")?.as_str()?.to_string();
+    let kind = update.get("kind").and_then(|k| k.as_str());
+    let tool = kind
+        .or_else(|| update.get("title").and_then(|t| t.as_str()))
+        .unwrap_or("tool")
+        .to_string();
+    let paths = match kind {
+        Some("edit") => update
+            .get("locations")
+            .and_then(|l| l.as_array())
+            .map(|locations| {
+                locations
+                    .iter()
+                    .filter_map(|l| l.get("path").and_then(|p| p.as_str()))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    let command = match kind {
+        Some("execute") => match update.get("rawInput").and_then(|i| i.get("command")) {
+            Some(Value::String(command)) => Some(command.clone()),
+            Some(Value::Array(argv)) => Some(
+                argv.iter()
+                    .filter_map(|a| a.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            _ => None,
+        },
+        _ => None,
+    };
+    Some(ToolCall {
+        id,
+        tool,
+        paths,
+        command,
+    })
+}
+
+/// Extract a tool call's `status` (`pending`, `in_progress`, `completed`,
+/// `failed`) from a `tool_call` or `tool_call_update` update.
+pub fn extract_tool_call_status(params: &Value) -> Option<&str> {
+    params
+        .get("update")
+        .and_then(|u| u.get("status"))
+        .and_then(|s| s.as_str())
+}
+
+/// Extract a command exit code from a tool call's `rawOutput`, if reported.
+pub fn extract_tool_call_exit_code(params: &Value) -> Option<i32> {
+    let output = params.get("update")?.get("rawOutput")?;
+    output
+        .get("exitCode")
+        .or_else(|| output.get("exit_code"))
+        .and_then(|c| c.as_i64())
+        .and_then(|c| i32::try_from(c).ok())
+}
+
 /// Extract the session ID from a `session/new` or `session/load` response result.
 pub fn extract_session_id(result: &Value) -> Option<&str> {
     result.get("sessionId").and_then(|v| v.as_str())
@@ -396,6 +463,40 @@ mod tests {
         assert_eq!(extract_message_chunk_text(&params), None);
     }
 
+    #[test]
+    fn extract_tool_call_reads_edit_locations_and_commands() {
+        let params = serde_json::json!({
+            "update": {
+                "sessionUpdate": "tool_call",
+                "toolCallId": "c1",
+                "title": "Editing src/lib.rs",
+                "kind": "edit",
+                "status": "pending",
+                "locations": [{"path": "/repo/src/lib.rs"}]
+            }
+        });
+        let call = extract_tool_call(&params).unwrap();
+        assert_eq!(call.id, "c1");
+        assert_eq!(call.tool, "edit");
+        assert_eq!(call.paths, vec!["/repo/src/lib.rs"]);
+        assert_eq!(extract_tool_call_status(&params), Some("pending"));
+
+        let params = serde_json::json!({
+            "update": {
+                "sessionUpdate": "tool_call_update",
+                "toolCallId": "c2",
+                "kind": "execute",
+                "status": "failed",
+                "rawInput": {"command": ["cargo", "test"]},
+                "rawOutput": {"exitCode": 101}
+            }
+        });
+        let call = extract_tool_call(&params).unwrap();
+        assert_eq!(call.command.as_deref(), Some("cargo test"));
+        assert!(call.paths.is_empty());
+        assert_eq!(extract_tool_call_exit_code(&params), Some(101));
+    }
+
     #[test]
     fn extract_session_id_from_result() {
         let result = serde_json::json!({"sessionId": "sess-xyz-123"});
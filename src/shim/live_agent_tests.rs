@@ -93,6 +93,7 @@ fn spawn_agent_shim(agent_type: AgentType, cmd: &str) -> Channel {
         pty_log_path: None,
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
 
     std::thread::spawn(move || {
@@ -119,6 +120,7 @@ fn spawn_agent_shim_with_log(agent_type: AgentType, cmd: &str, log_path: PathBuf
         pty_log_path: Some(log_path),
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
 
     std::thread::spawn(move || {
@@ -4,7 +4,6 @@ pub mod chat;
 pub mod classifier;
 pub mod codex_types;
 pub mod common;
-#[cfg(any(test, feature = "scenario-test"))]
 pub mod fake;
 pub mod kiro_types;
 #[cfg(test)]
@@ -16,6 +15,7 @@ pub mod runtime;
 pub mod runtime_codex;
 pub mod runtime_kiro;
 pub mod runtime_sdk;
+pub mod sandbox;
 pub mod sdk_types;
 #[cfg(test)]
 mod tests;
@@ -25,4 +25,5 @@ mod tests_codex;
 mod tests_kiro;
 #[cfg(test)]
 mod tests_sdk;
+pub mod tool_activity;
 pub mod tracker;
@@ -142,6 +142,38 @@ pub enum Event {
         context_usage_pct: Option<u8>,
     },
     Pong,
+    /// The agent started a tool call (SDK mode only).
+    ToolStarted {
+        tool_use_id: String,
+        tool: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        command: Option<String>,
+    },
+    /// A tool call finished.
+    ToolFinished {
+        tool_use_id: String,
+        tool: String,
+        duration_ms: u64,
+        is_error: bool,
+    },
+    /// A tool call wrote to a file. Paths are as the agent reported them.
+    FileEdited {
+        path: String,
+        tool: String,
+    },
+    /// A shell command finished.
+    CommandRun {
+        argv: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i32>,
+        duration_ms: u64,
+    },
+    /// The sandbox blocked a write outside the writable paths or a network
+    /// connection. `kind` is `filesystem` or `network`.
+    SandboxViolation {
+        kind: String,
+        detail: String,
+    },
     Warning {
         message: String,
         idle_secs: Option<u64>,
@@ -744,6 +776,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn roundtrip_event_command_run() {
+        let (a, b) = socketpair().unwrap();
+        let mut sender = Channel::new(a);
+        let mut receiver = Channel::new(b);
+
+        let evt = Event::CommandRun {
+            argv: vec!["cargo".into(), "test".into()],
+            exit_code: Some(101),
+            duration_ms: 42_000,
+        };
+        sender.send(&evt).unwrap();
+        let received: Event = receiver.recv::<Event>().unwrap().unwrap();
+        match received {
+            Event::CommandRun {
+                argv,
+                exit_code,
+                duration_ms,
+            } => {
+                assert_eq!(argv, vec!["cargo", "test"]);
+                assert_eq!(exit_code, Some(101));
+                assert_eq!(duration_ms, 42_000);
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[test]
+    fn roundtrip_event_file_edited() {
+        let (a, b) = socketpair().unwrap();
+        let mut sender = Channel::new(a);
+        let mut receiver = Channel::new(b);
+
+        let evt = Event::FileEdited {
+            path: "src/lib.rs".into(),
+            tool: "Edit".into(),
+        };
+        sender.send(&evt).unwrap();
+        let received: Event = receiver.recv::<Event>().unwrap().unwrap();
+        match received {
+            Event::FileEdited { path, tool } => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(tool, "Edit");
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
     #[test]
     fn roundtrip_event_error() {
         let (a, b) = socketpair().unwrap();
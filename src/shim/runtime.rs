@@ -22,6 +22,7 @@ use super::classifier::{self, AgentType, ScreenVerdict};
 use super::common::{self, QueuedMessage};
 use super::protocol::{Channel, Command, Event, ShimState};
 use super::pty_log::PtyLogWriter;
+use super::sandbox::{SandboxPolicy, ViolationMonitor};
 use crate::prompt::strip_ansi;
 
 // ---------------------------------------------------------------------------
@@ -410,7 +411,7 @@ fn pty_write_paced(
     // use synchronized output, causing "pasted text" indicators without
     // the Enter being processed.
     match agent_type {
-        AgentType::Generic => {
+        AgentType::Generic | AgentType::Custom if !is_raw_mode_agent(agent_type) => {
             // Generic/bash: write directly, no paste mode needed
             let mut writer = pty_writer.lock().unwrap();
             writer.write_all(body)?;
@@ -441,9 +442,19 @@ fn pty_write_paced(
 /// Most TUI agents run in raw mode and need \r (CR) for Enter.
 /// Generic/bash uses canonical mode and needs \n (LF).
 fn enter_seq(agent_type: AgentType) -> &'static str {
+    if is_raw_mode_agent(agent_type) {
+        "\r" // Claude, Codex, Kiro — raw-mode TUIs
+    } else {
+        "\n"
+    }
+}
+
+/// Custom agents declare whether they are raw-mode TUIs in their profile.
+fn is_raw_mode_agent(agent_type: AgentType) -> bool {
     match agent_type {
-        AgentType::Generic => "\n",
-        _ => "\r", // Claude, Codex, Kiro — raw-mode TUIs
+        AgentType::Generic => false,
+        AgentType::Custom => classifier::custom_profile_raw_mode(),
+        _ => true,
     }
 }
 
@@ -464,9 +475,32 @@ pub struct ShimArgs {
     pub pty_log_path: Option<PathBuf>,
     pub graceful_shutdown_timeout_secs: u64,
     pub auto_commit_on_restart: bool,
+    /// Run the agent inside this sandbox instead of directly on the host.
+    pub sandbox: Option<SandboxPolicy>,
 }
 
 impl ShimArgs {
+    /// Program and arguments that launch `program args...` for this agent,
+    /// wrapped in the role's sandbox when one is configured.
+    pub(crate) fn agent_command(
+        &self,
+        program: &str,
+        args: &[String],
+    ) -> Result<(String, Vec<String>)> {
+        match &self.sandbox {
+            Some(policy) => {
+                policy.check_available()?;
+                policy.wrap(&self.cwd, program, args)
+            }
+            None => Ok((program.to_string(), args.to_vec())),
+        }
+    }
+
+    /// Watches agent output for sandbox violations, when sandboxed.
+    pub(crate) fn violation_monitor(&self) -> Option<ViolationMonitor> {
+        self.sandbox.as_ref().map(ViolationMonitor::new)
+    }
+
     fn preserve_work_before_kill(&self, worktree_path: &Path) -> Result<bool> {
         if !self.auto_commit_on_restart {
             return Ok(false);
@@ -582,6 +616,9 @@ struct ShimInner {
     test_failure_iterations: u8,
     /// Whether a ContextApproaching event has already been emitted this session.
     context_approaching_emitted: bool,
+    /// Whether a QuotaBlocked event has already been emitted for a custom
+    /// agent's quota screen this session.
+    quota_blocked_emitted: bool,
 }
 
 impl ShimInner {
@@ -648,8 +685,10 @@ pub fn run(args: ShimArgs, channel: Channel) -> Result<()> {
     let shim_pid = std::process::id();
     let supervised_cmd = build_supervised_agent_command(&args.cmd, shim_pid);
 
-    let mut cmd = CommandBuilder::new("bash");
-    cmd.args(["-lc", &supervised_cmd]);
+    let (program, program_args) =
+        args.agent_command("bash", &["-lc".to_string(), supervised_cmd])?;
+    let mut cmd = CommandBuilder::new(program);
+    cmd.args(&program_args);
     cmd.cwd(&args.cwd);
     cmd.env_remove("CLAUDECODE"); // prevent nested detection
     cmd.env("TERM", "xterm-256color");
@@ -691,6 +730,7 @@ pub fn run(args: ShimArgs, channel: Channel) -> Result<()> {
         last_working_screen: String::new(),
         test_failure_iterations: 0,
         context_approaching_emitted: false,
+        quota_blocked_emitted: false,
     }));
 
     // -- PTY log writer (optional) --
@@ -713,6 +753,7 @@ pub fn run(args: ShimArgs, channel: Channel) -> Result<()> {
     let inner_pty = Arc::clone(&inner);
     let log_handle = pty_log.clone();
     let pty_writer_pty = Arc::clone(&pty_writer);
+    let mut violation_monitor = args.violation_monitor();
     let pty_handle = std::thread::spawn(move || {
         let mut buf = [0u8; 4096];
         loop {
@@ -724,6 +765,12 @@ pub fn run(args: ShimArgs, channel: Channel) -> Result<()> {
                         let _ = log.lock().unwrap().write(&buf[..n]);
                     }
 
+                    if let Some(monitor) = violation_monitor.as_mut() {
+                        for event in monitor.scan(&String::from_utf8_lossy(&buf[..n])) {
+                            let _ = evt_channel.send(&event);
+                        }
+                    }
+
                     let mut inner = inner_pty.lock().unwrap();
                     inner.last_pty_output_at = Instant::now();
                     inner.cumulative_output_bytes =
@@ -760,6 +807,22 @@ pub fn run(args: ShimArgs, channel: Channel) -> Result<()> {
                         continue;
                     }
 
+                    // Custom agents declare their own quota screen regexes;
+                    // report the first match so the daemon can park the member.
+                    if inner.agent_type == AgentType::Custom
+                        && !inner.quota_blocked_emitted
+                        && let Some(message) = classifier::detect_custom_quota(&content)
+                    {
+                        inner.quota_blocked_emitted = true;
+                        drop(inner);
+                        let _ = evt_channel.send(&Event::QuotaBlocked {
+                            message,
+                            retry_at_epoch_secs: None,
+                            retry_at_label: None,
+                        });
+                        continue;
+                    }
+
                     // Track screen content during Working state for response
                     // extraction. TUI agents may redraw the screen before the
                     // Working→Idle transition, wiping the response content.
@@ -2211,6 +2274,8 @@ editing src/lib.rs\n";
             completed: None,
             description: "Continue widget implementation.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: repo.path().join("task-42.md"),
         };
         preserve_handoff(repo.path(), &task, Some(recent_output)).unwrap();
@@ -2273,6 +2338,8 @@ editing src/lib.rs\n";
             completed: None,
             description: "No changes yet.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: repo.path().join("task-7.md"),
         };
         preserve_handoff(repo.path(), &task, None).unwrap();
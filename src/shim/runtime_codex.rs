@@ -21,7 +21,7 @@ use anyhow::{Context, Result};
 use chrono::TimeZone;
 use regex::Regex;
 
-use super::codex_types::{self, CodexEvent};
+use super::codex_types::{self, CodexEvent, CodexItem};
 use super::common::{
     self, MAX_QUEUE_DEPTH, QueuedMessage, SESSION_STATS_INTERVAL_SECS, drain_queue_errors,
     format_injected_message,
@@ -29,6 +29,8 @@ use super::common::{
 use super::protocol::{Channel, Command as ShimCommand, Event, ShimState};
 use super::pty_log::PtyLogWriter;
 use super::runtime::ShimArgs;
+use super::sandbox::{SandboxPolicy, ViolationMonitor};
+use super::tool_activity::ToolActivity;
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -59,6 +61,8 @@ struct CodexState {
     program: String,
     /// Working directory for spawning subprocesses.
     cwd: std::path::PathBuf,
+    /// Sandbox each `codex exec` runs in, if the role configures one.
+    sandbox: Option<SandboxPolicy>,
     /// Whether a ContextApproaching event has already been emitted this session.
     context_approaching_emitted: bool,
     /// When a backend quota block expires, if known.
@@ -89,6 +93,7 @@ pub fn run_codex_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
         cumulative_output_bytes: 0,
         program: "codex".to_string(),
         cwd: args.cwd.clone(),
+        sandbox: args.sandbox.clone(),
         context_approaching_emitted: false,
         quota_blocked_until: None,
     }));
@@ -365,16 +370,26 @@ fn run_codex_exec(
     evt_channel: &mut Channel,
     pty_log: Option<&Arc<Mutex<PtyLogWriter>>>,
 ) {
-    // Spawn the subprocess
-    let mut child = match Command::new(program)
-        .args(args)
-        .current_dir(cwd)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .env_remove("CLAUDECODE")
-        .spawn()
-    {
+    // Spawn the subprocess, inside the role's sandbox when configured
+    let sandbox = state.lock().unwrap().sandbox.clone();
+    let command = match &sandbox {
+        Some(policy) => policy
+            .check_available()
+            .and_then(|()| policy.wrap(cwd, program, args)),
+        None => Ok((program.to_string(), args.to_vec())),
+    };
+    let spawned = command.and_then(|(program, args)| {
+        Command::new(program)
+            .args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .env_remove("CLAUDECODE")
+            .spawn()
+            .map_err(anyhow::Error::from)
+    });
+    let mut child = match spawned {
         Ok(c) => c,
         Err(e) => {
             eprintln!("[shim-codex {shim_id}] failed to spawn codex exec: {e}");
@@ -413,18 +428,27 @@ fn run_codex_exec(
     let stdout = child.stdout.take().unwrap();
     let stderr = child.stderr.take().unwrap();
 
-    // stderr reader (log only). Non-recoverable auth failures are detected
+    // stderr reader (log plus sandbox violations). Non-recoverable auth failures are detected
     // in the structured `turn.failed` / `error` events below — the codex
     // CLI surfaces the same message there, so we don't need to race the
     // stderr thread against the stdout JSONL path.
     let shim_id_err = shim_id.to_string();
     let pty_log_err = pty_log.map(Arc::clone);
+    let mut violation_monitor = sandbox.as_ref().map(ViolationMonitor::new);
+    let mut violation_channel = evt_channel.try_clone().ok();
     thread::spawn(move || {
         let reader = BufReader::new(stderr);
         for line_result in reader.lines() {
             match line_result {
                 Ok(line) => {
                     eprintln!("[shim-codex {shim_id_err}] stderr: {line}");
+                    if let (Some(monitor), Some(channel)) =
+                        (violation_monitor.as_mut(), violation_channel.as_mut())
+                    {
+                        for event in monitor.scan(&line) {
+                            let _ = channel.send(&event);
+                        }
+                    }
                     if let Some(ref log) = pty_log_err {
                         let _ = log
                             .lock()
@@ -439,6 +463,7 @@ fn run_codex_exec(
 
     // stdout JSONL reader
     let reader = BufReader::new(stdout);
+    let mut tools = ToolActivity::default();
     for line_result in reader.lines() {
         let line = match line_result {
             Ok(l) => l,
@@ -469,7 +494,28 @@ fn run_codex_exec(
                 }
             }
 
+            "item.started" => {
+                if let Some(event) = evt
+                    .item
+                    .as_ref()
+                    .and_then(CodexItem::tool_call)
+                    .and_then(|call| tools.start(call))
+                {
+                    let _ = evt_channel.send(&event);
+                }
+            }
+
             "item.completed" | "item.updated" => {
+                if evt.event_type == "item.completed"
+                    && let Some(ref item) = evt.item
+                    && let Some(call) = item.tool_call()
+                {
+                    for event in
+                        tools.finish(&item.id, Some(call), item.tool_failed(), item.exit_code)
+                    {
+                        let _ = evt_channel.send(&event);
+                    }
+                }
                 if let Some(ref item) = evt.item {
                     if let Some(text) = item.agent_text() {
                         if !text.is_empty() {
@@ -640,7 +686,7 @@ fn run_codex_exec(
                 }
             }
 
-            // turn.started, turn.completed, item.started — informational, no action
+            // turn.started, turn.completed — informational, no action
             _ => {}
         }
     }
@@ -879,6 +925,7 @@ mod tests {
             cumulative_output_bytes: 0,
             program: "codex".into(),
             cwd: std::path::PathBuf::from("/tmp"),
+            sandbox: None,
             context_approaching_emitted: false,
             quota_blocked_until: None,
         };
@@ -18,6 +18,7 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
+use serde_json::Value;
 
 use super::common::{
     self, MAX_QUEUE_DEPTH, QueuedMessage, SESSION_STATS_INTERVAL_SECS, drain_queue_errors,
@@ -27,6 +28,7 @@ use super::kiro_types::{self, AcpMessage};
 use super::protocol::{Channel, Command as ShimCommand, Event, ShimState};
 use super::pty_log::PtyLogWriter;
 use super::runtime::ShimArgs;
+use super::tool_activity::ToolActivity;
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -67,6 +69,8 @@ struct KiroState {
     pending_prompt_request_id: Option<u64>,
     /// Whether a ContextApproaching event has already been emitted this session.
     context_approaching_emitted: bool,
+    /// Tool calls started in the current turn and not yet finished.
+    tools: ToolActivity,
 }
 
 /// Monotonically increasing JSON-RPC request ID counter.
@@ -86,8 +90,10 @@ fn next_request_id() -> u64 {
 /// `args.cmd` must launch `kiro-cli acp --trust-all-tools`.
 pub fn run_kiro_acp(args: ShimArgs, channel: Channel) -> Result<()> {
     // -- Spawn subprocess with piped stdin/stdout/stderr --
-    let mut child = Command::new("bash")
-        .args(["-lc", &args.cmd])
+    let (program, program_args) =
+        args.agent_command("bash", &["-lc".to_string(), args.cmd.clone()])?;
+    let mut child = Command::new(program)
+        .args(&program_args)
         .current_dir(&args.cwd)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -120,6 +126,7 @@ pub fn run_kiro_acp(args: ShimArgs, channel: Channel) -> Result<()> {
         sent_session_new: false,
         pending_prompt_request_id: None,
         context_approaching_emitted: false,
+        tools: ToolActivity::default(),
     }));
 
     // Shared stdin writer — wrapped in Option so Shutdown can take and close it.
@@ -285,6 +292,7 @@ pub fn run_kiro_acp(args: ShimArgs, channel: Channel) -> Result<()> {
                         let old = st.state;
                         st.state = ShimState::Idle;
                         st.state_changed_at = Instant::now();
+                        st.tools.clear();
 
                         // Drain queue
                         let queued_msg = if !st.message_queue.is_empty() {
@@ -395,6 +403,11 @@ pub fn run_kiro_acp(args: ShimArgs, channel: Channel) -> Result<()> {
                                 }
 
                                 "tool_call" | "ToolCall" => {
+                                    forward_tool_call_update(
+                                        &state_stdout,
+                                        &mut evt_channel,
+                                        params,
+                                    );
                                     // Log tool calls for visibility
                                     let title = params
                                         .get("update")
@@ -410,7 +423,11 @@ pub fn run_kiro_acp(args: ShimArgs, channel: Channel) -> Result<()> {
                                 }
 
                                 "tool_call_update" | "ToolCallUpdate" => {
-                                    // Tool progress — no action needed
+                                    forward_tool_call_update(
+                                        &state_stdout,
+                                        &mut evt_channel,
+                                        params,
+                                    );
                                 }
 
                                 "TurnEnd" | "turn_end" => {
@@ -548,12 +565,21 @@ pub fn run_kiro_acp(args: ShimArgs, channel: Channel) -> Result<()> {
     // -- stderr reader thread --
     let shim_id_err = args.id.clone();
     let pty_log_stderr = pty_log;
+    let mut violation_monitor = args.violation_monitor();
+    let mut violation_channel = cmd_channel.try_clone().ok();
     thread::spawn(move || {
         let reader = BufReader::new(child_stderr);
         for line_result in reader.lines() {
             match line_result {
                 Ok(line) => {
                     eprintln!("[shim-kiro {shim_id_err}] stderr: {line}");
+                    if let (Some(monitor), Some(channel)) =
+                        (violation_monitor.as_mut(), violation_channel.as_mut())
+                    {
+                        for event in monitor.scan(&line) {
+                            let _ = channel.send(&event);
+                        }
+                    }
                     if let Some(ref log) = pty_log_stderr {
                         let _ = log
                             .lock()
@@ -876,6 +902,34 @@ fn terminate_child(child: &mut Child) {
 }
 
 /// Extract the last N lines from a string.
+/// Track a `tool_call`/`tool_call_update` and forward the resulting
+/// tool events. Agents may report a call already completed in its first
+/// update, so terminal statuses are handled on either variant.
+fn forward_tool_call_update(
+    state: &Arc<Mutex<KiroState>>,
+    evt_channel: &mut Channel,
+    params: &Value,
+) {
+    let Some(call) = kiro_types::extract_tool_call(params) else {
+        return;
+    };
+    let status = kiro_types::extract_tool_call_status(params);
+    let mut st = state.lock().unwrap();
+    let events = match status {
+        Some("completed") | Some("failed") => st.tools.finish(
+            &call.id.clone(),
+            Some(call),
+            status == Some("failed"),
+            kiro_types::extract_tool_call_exit_code(params),
+        ),
+        _ => st.tools.start(call).into_iter().collect(),
+    };
+    drop(st);
+    for event in events {
+        let _ = evt_channel.send(&event);
+    }
+}
+
 fn last_n_lines_of(text: &str, n: usize) -> String {
     let lines: Vec<&str> = text.lines().collect();
     let start = lines.len().saturating_sub(n);
@@ -919,6 +973,7 @@ mod tests {
             sent_session_new: false,
             pending_prompt_request_id: None,
             context_approaching_emitted: false,
+            tools: ToolActivity::default(),
         };
         assert_eq!(st.state, ShimState::Starting);
         assert!(st.session_id.is_empty());
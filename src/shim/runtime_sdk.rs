@@ -22,6 +22,7 @@ use super::protocol::{Channel, Command as ShimCommand, Event, ShimState};
 use super::pty_log::PtyLogWriter;
 use super::runtime::ShimArgs;
 use super::sdk_types::{self, SdkControlResponse, SdkOutput, SdkUserMessage};
+use super::tool_activity::ToolActivity;
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -30,6 +31,10 @@ use super::sdk_types::{self, SdkControlResponse, SdkOutput, SdkUserMessage};
 const PROCESS_EXIT_POLL_MS: u64 = 100;
 const GROUP_TERM_GRACE_SECS: u64 = 2;
 const WORKING_READ_TIMEOUT: Duration = Duration::from_secs(120);
+/// A shell command still running is not a stall until it passes this age.
+/// Long cargo builds and test suites keep Claude's stdout silent while the
+/// Bash tool runs.
+const LONG_COMMAND_GRACE_SECS: u64 = 30 * 60;
 const STALLED_MID_TURN_MARKER: &str = "stalled mid-turn";
 const MESSAGE_PREVIEW_LIMIT: usize = 160;
 const SDK_COMMAND_POLL_MS: u64 = 1000;
@@ -73,6 +78,8 @@ struct SdkState {
     cumulative_output_tokens: u64,
     /// Approximate percent of the model context budget already consumed.
     context_usage_pct: Option<u8>,
+    /// Tool calls started in the current turn and not yet finished.
+    tools: ToolActivity,
 }
 
 #[derive(Debug, Clone)]
@@ -96,8 +103,10 @@ struct ForcedCompletion {
 /// `args.cmd` must be a shell command that launches Claude Code in stream-json mode.
 pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
     // -- Spawn subprocess with piped stdin/stdout/stderr --
-    let mut child = Command::new("bash")
-        .args(["-lc", &args.cmd])
+    let (program, program_args) =
+        args.agent_command("bash", &["-lc".to_string(), args.cmd.clone()])?;
+    let mut child = Command::new(program)
+        .args(&program_args)
         .current_dir(&args.cwd)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -134,6 +143,7 @@ pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
         cumulative_input_tokens: 0,
         cumulative_output_tokens: 0,
         context_usage_pct: None,
+        tools: ToolActivity::default(),
     }));
 
     // Shared stdin writer (used by both command loop and stdout reader for auto-approve)
@@ -179,6 +189,9 @@ pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
                 Some(timeout) => match line_rx.recv_timeout(timeout) {
                     Ok(line_result) => Some(line_result),
                     Err(RecvTimeoutError::Timeout) => {
+                        if command_still_running(&state_stdout) {
+                            continue;
+                        }
                         if let Some(forced) = force_stalled_completion(&state_stdout, &shim_id) {
                             emit_forced_completion(&mut evt_channel, &stdin_for_approve, forced);
                         }
@@ -214,6 +227,23 @@ pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
 
             match msg.msg_type.as_str() {
                 "assistant" => {
+                    if let Some(ref message) = msg.message {
+                        let calls = sdk_types::extract_tool_calls(message);
+                        if !calls.is_empty() {
+                            let mut st = state_stdout.lock().unwrap();
+                            if turn_in_flight(&st) {
+                                let events: Vec<Event> = calls
+                                    .into_iter()
+                                    .filter_map(|call| st.tools.start(call))
+                                    .collect();
+                                drop(st);
+                                for event in events {
+                                    let _ = evt_channel.send(&event);
+                                }
+                            }
+                        }
+                    }
+
                     // Extract text from the assistant message
                     if let Some(ref message) = msg.message {
                         let model_name = msg.model_name();
@@ -273,6 +303,31 @@ pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
                     }
                 }
 
+                "user" => {
+                    // Tool results come back to Claude as user messages.
+                    if let Some(ref message) = msg.message {
+                        let results = sdk_types::extract_tool_results(message);
+                        if !results.is_empty() {
+                            let mut st = state_stdout.lock().unwrap();
+                            let events: Vec<Event> = results
+                                .into_iter()
+                                .flat_map(|result| {
+                                    st.tools.finish(
+                                        &result.tool_use_id,
+                                        None,
+                                        result.is_error,
+                                        result.exit_code,
+                                    )
+                                })
+                                .collect();
+                            drop(st);
+                            for event in events {
+                                let _ = evt_channel.send(&event);
+                            }
+                        }
+                    }
+                }
+
                 "control_request" => {
                     // Auto-approve tool use requests
                     if msg.request_subtype().as_deref() == Some("can_use_tool")
@@ -293,6 +348,7 @@ pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
                     if !turn_in_flight(&st) {
                         continue;
                     }
+                    st.tools.clear();
 
                     // Capture session_id
                     if st.session_id.is_empty() {
@@ -489,12 +545,21 @@ pub fn run_sdk(args: ShimArgs, channel: Channel) -> Result<()> {
     // -- stderr reader thread --
     let shim_id_err = args.id.clone();
     let pty_log_stderr = pty_log;
+    let mut violation_monitor = args.violation_monitor();
+    let mut violation_channel = cmd_channel.try_clone().ok();
     thread::spawn(move || {
         let reader = BufReader::new(child_stderr);
         for line_result in reader.lines() {
             match line_result {
                 Ok(line) => {
                     eprintln!("[shim-sdk {shim_id_err}] stderr: {line}");
+                    if let (Some(monitor), Some(channel)) =
+                        (violation_monitor.as_mut(), violation_channel.as_mut())
+                    {
+                        for event in monitor.scan(&line) {
+                            let _ = channel.send(&event);
+                        }
+                    }
                     if let Some(ref log) = pty_log_stderr {
                         let _ = log
                             .lock()
@@ -996,6 +1061,15 @@ fn stdout_read_timeout(state: &Arc<Mutex<SdkState>>) -> Option<Duration> {
     (st.state == ShimState::Working).then_some(WORKING_READ_TIMEOUT)
 }
 
+/// Whether a shell command started this turn is still within its grace
+/// period, so read silence means "busy" rather than "hung".
+fn command_still_running(state: &Arc<Mutex<SdkState>>) -> bool {
+    let st = state.lock().unwrap();
+    st.tools
+        .command_in_flight_secs()
+        .is_some_and(|secs| secs < LONG_COMMAND_GRACE_SECS)
+}
+
 fn turn_in_flight(state: &SdkState) -> bool {
     state.state == ShimState::Working || state.pending_message_id.is_some()
 }
@@ -1144,6 +1218,7 @@ mod tests {
             cumulative_input_tokens: 0,
             cumulative_output_tokens: 0,
             context_usage_pct: None,
+            tools: ToolActivity::default(),
         };
         assert_eq!(st.state, ShimState::Idle);
         assert!(st.session_id.is_empty());
@@ -1267,6 +1342,7 @@ mod tests {
             cumulative_input_tokens: 0,
             cumulative_output_tokens: 0,
             context_usage_pct: None,
+            tools: ToolActivity::default(),
         }));
 
         let forced = force_stalled_completion(&state, "sdk-test").expect("forced completion");
@@ -1305,6 +1381,7 @@ mod tests {
             cumulative_input_tokens: 0,
             cumulative_output_tokens: 0,
             context_usage_pct: None,
+            tools: ToolActivity::default(),
         }));
 
         let forced = force_stalled_completion(&state, "sdk-test").expect("forced completion");
@@ -1340,6 +1417,7 @@ mod tests {
             cumulative_input_tokens: 0,
             cumulative_output_tokens: 0,
             context_usage_pct: None,
+            tools: ToolActivity::default(),
         }));
         let writer = Arc::new(Mutex::new(Vec::<u8>::new()));
         let mut last_keepalive = Instant::now();
@@ -1369,6 +1447,7 @@ mod tests {
             cumulative_input_tokens: 0,
             cumulative_output_tokens: 0,
             context_usage_pct: None,
+            tools: ToolActivity::default(),
         }));
         let writer = Arc::new(Mutex::new(Vec::<u8>::new()));
         let mut last_keepalive = Instant::now() - Duration::from_secs(SDK_KEEPALIVE_IDLE_SECS + 1);
@@ -1404,6 +1483,7 @@ mod tests {
             cumulative_input_tokens: 0,
             cumulative_output_tokens: 0,
             context_usage_pct: None,
+            tools: ToolActivity::default(),
         }));
         let writer = Arc::new(Mutex::new(Vec::<u8>::new()));
         let mut last_keepalive = Instant::now() - Duration::from_secs(SDK_KEEPALIVE_IDLE_SECS + 1);
@@ -0,0 +1,492 @@
+//! Rootless sandbox for agent processes.
+//!
+//! When a role sets `sandbox:`, the shim launches the agent through
+//! bubblewrap (`bwrap`) or nested `unshare` namespaces instead of running it
+//! directly on the host. The host filesystem is mounted read-only; only the
+//! agent's worktree, `/tmp` and the paths listed in the policy stay writable.
+//! Network access is either the host network or none at all.
+//!
+//! The daemon resolves the policy (absolute paths plus the shared cargo
+//! target, the git directory and the inboxes) before handing it to the shim
+//! as JSON with `--sandbox`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::protocol::Event;
+
+/// Paths every sandbox keeps writable in addition to the worktree.
+const ALWAYS_WRITABLE: &[&str] = &["/tmp"];
+
+/// Output fragments that mean a write hit the read-only host filesystem.
+const FILESYSTEM_VIOLATION_PATTERNS: &[&str] = &["read-only file system"];
+
+/// Output fragments that mean a connection failed for lack of a network.
+/// Only reported when the policy disables networking.
+const NETWORK_VIOLATION_PATTERNS: &[&str] = &[
+    "network is unreachable",
+    "temporary failure in name resolution",
+    "could not resolve host",
+    "name or service not known",
+];
+
+/// Cap on how much of an offending output line a violation event carries.
+const MAX_VIOLATION_DETAIL_CHARS: usize = 240;
+
+/// Mount/exec script run by the outer `unshare` namespace. Positional
+/// arguments: uid, gid, the number of writable paths, the paths themselves,
+/// the number of read-only paths, those paths, then the agent program and
+/// its arguments.
+///
+/// After the writable binds, every other mount in `/proc/self/mountinfo` is
+/// remounted read-only with its existing flags, since remounting `/` alone
+/// leaves `/home`, `/dev/shm`, `/run` and friends writable. `/proc` stays as
+/// it is: the nested namespace needs it to write its uid map, and the kernel
+/// gates privileged proc writes itself. A remount that fails aborts the
+/// launch rather than starting a half-sealed sandbox. The working directory
+/// is re-entered last: the inherited one still points at the directory
+/// underneath the new binds, which is now read-only.
+const UNSHARE_SCRIPT: &str = r#"set -e
+uid=$1; gid=$2; n=$3; shift 3
+tab=$(printf '\t')
+mount_points() {
+  KEEP="$keep" awk '
+    function unescape(s) { gsub(/\\040/, " ", s); gsub(/\\011/, "\t", s); gsub(/\\134/, "\\", s); return s }
+    { mp = unescape($5); opts[mp] = $6; if (!(mp in seen)) { seen[mp] = 1; order[++count] = mp } }
+    END {
+      split(ENVIRON["KEEP"], keep, "\n")
+      for (i = 1; i <= count; i++) {
+        mp = order[i]; kept = 0
+        for (k in keep) if (keep[k] != "" && (mp == keep[k] || index(mp, keep[k] "/") == 1)) kept = 1
+        if (kept || (only != "" && mp != only)) continue
+        flags = opts[mp]; sub(/^r[ow],?/, "", flags)
+        print mp "\t" (flags == "" ? "" : "," flags)
+      }
+    }' only="$1" /proc/self/mountinfo
+}
+lock() {
+  while IFS="$tab" read -r mp flags; do mount -o "remount,bind,ro$flags" "$mp"; done
+}
+mount --make-rprivate /
+keep=/proc
+while [ "$n" -gt 0 ]; do
+  if [ -e "$1" ]; then mount --bind "$1" "$1"; keep="$keep
+$1"; fi
+  shift; n=$((n - 1))
+done
+mount_points "" | lock
+n=$1; shift
+keep=
+while [ "$n" -gt 0 ]; do
+  if [ -e "$1" ]; then mount --bind "$1" "$1"; mount_points "$1" | lock; fi
+  shift; n=$((n - 1))
+done
+cd "$PWD"
+exec unshare --user --map-user="$uid" --map-group="$gid" -- "$@""#;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxBackend {
+    /// `bwrap` with a read-only bind of `/` and writable binds on top.
+    #[default]
+    Bubblewrap,
+    /// util-linux `unshare`: a user+mount namespace that remounts `/`
+    /// read-only, then a nested user namespace that maps back to the
+    /// caller's uid so agents do not run as namespace root.
+    Unshare,
+}
+
+impl SandboxBackend {
+    fn program(self) -> &'static str {
+        match self {
+            Self::Bubblewrap => "bwrap",
+            Self::Unshare => "unshare",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxNetwork {
+    /// Share the host network namespace.
+    #[default]
+    Host,
+    /// Run in an empty network namespace (loopback only).
+    None,
+}
+
+/// Per-role sandbox settings (`roles[].sandbox` in team.yaml).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxPolicy {
+    #[serde(default)]
+    pub backend: SandboxBackend,
+    #[serde(default)]
+    pub network: SandboxNetwork,
+    /// Extra paths mounted writable. Relative paths resolve against the
+    /// project root and a leading `~/` against `$HOME`.
+    #[serde(default)]
+    pub writable: Vec<String>,
+    /// Paths re-mounted read-only on top of the writable ones, resolved the
+    /// same way. Missing paths are skipped.
+    #[serde(default)]
+    pub read_only: Vec<String>,
+}
+
+impl SandboxPolicy {
+    /// Return a copy with every declared path made absolute, `extra_writable`
+    /// and `extra_read_only` appended, and duplicates dropped.
+    pub fn resolve(
+        &self,
+        project_root: &Path,
+        extra_writable: &[PathBuf],
+        extra_read_only: &[PathBuf],
+    ) -> Self {
+        Self {
+            backend: self.backend,
+            network: self.network,
+            writable: resolve_paths(project_root, &self.writable, extra_writable),
+            read_only: resolve_paths(project_root, &self.read_only, extra_read_only),
+        }
+    }
+
+    /// Every writable path for an agent running in `cwd`: the worktree,
+    /// the always-writable paths, then the declared ones.
+    fn writable_paths(&self, cwd: &Path) -> Vec<String> {
+        let mut paths = vec![cwd.to_string_lossy().into_owned()];
+        paths.extend(ALWAYS_WRITABLE.iter().map(|path| path.to_string()));
+        for path in &self.writable {
+            if !paths.contains(path) {
+                paths.push(path.clone());
+            }
+        }
+        paths
+    }
+
+    /// Wrap `program args...` so it runs inside the sandbox with `cwd` as
+    /// its working directory. Returns the program and arguments to spawn.
+    pub fn wrap(
+        &self,
+        cwd: &Path,
+        program: &str,
+        args: &[String],
+    ) -> Result<(String, Vec<String>)> {
+        if let Some(path) = self
+            .writable
+            .iter()
+            .chain(&self.read_only)
+            .find(|path| !path.starts_with('/'))
+        {
+            bail!("sandbox writable path '{path}' is not absolute; resolve the policy first");
+        }
+        let writable = self.writable_paths(cwd);
+        let mut argv = Vec::new();
+        match self.backend {
+            SandboxBackend::Bubblewrap => {
+                argv.extend(
+                    ["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"].map(String::from),
+                );
+                for path in &writable {
+                    argv.extend(["--bind-try".to_string(), path.clone(), path.clone()]);
+                }
+                for path in &self.read_only {
+                    argv.extend(["--ro-bind-try".to_string(), path.clone(), path.clone()]);
+                }
+                if self.network == SandboxNetwork::None {
+                    argv.push("--unshare-net".into());
+                }
+                argv.push("--die-with-parent".into());
+                argv.extend(["--chdir".to_string(), cwd.to_string_lossy().into_owned()]);
+                argv.push("--".into());
+            }
+            SandboxBackend::Unshare => {
+                argv.extend(["--user", "--map-root-user", "--mount"].map(String::from));
+                if self.network == SandboxNetwork::None {
+                    argv.push("--net".into());
+                }
+                argv.extend(["--", "sh", "-c", UNSHARE_SCRIPT, "sh"].map(String::from));
+                argv.push(unsafe { libc::getuid() }.to_string());
+                argv.push(unsafe { libc::getgid() }.to_string());
+                argv.push(writable.len().to_string());
+                argv.extend(writable);
+                argv.push(self.read_only.len().to_string());
+                argv.extend(self.read_only.iter().cloned());
+            }
+        }
+        argv.push(program.to_string());
+        argv.extend(args.iter().cloned());
+        Ok((self.backend.program().to_string(), argv))
+    }
+
+    /// Fail early with a readable error when the backend binary is missing.
+    pub fn check_available(&self) -> Result<()> {
+        let program = self.backend.program();
+        let found = std::env::var_os("PATH").is_some_and(|path| {
+            std::env::split_paths(&path).any(|dir| dir.join(program).is_file())
+        });
+        if !found {
+            bail!("sandbox backend '{program}' not found on PATH");
+        }
+        Ok(())
+    }
+}
+
+/// Make `declared` absolute (relative to `project_root`, `~/` to `$HOME`)
+/// and append `extra`, keeping the first occurrence of each path.
+fn resolve_paths(project_root: &Path, declared: &[String], extra: &[PathBuf]) -> Vec<String> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut seen = HashSet::new();
+    let mut resolved = Vec::new();
+    let declared = declared.iter().map(|path| {
+        if let (Some(rest), Some(home)) = (path.strip_prefix("~/"), home.as_ref()) {
+            home.join(rest)
+        } else if path == "~"
+            && let Some(home) = home.as_ref()
+        {
+            home.clone()
+        } else {
+            project_root.join(path)
+        }
+    });
+    for path in declared.chain(extra.iter().cloned()) {
+        let path = path.to_string_lossy().into_owned();
+        if seen.insert(path.clone()) {
+            resolved.push(path);
+        }
+    }
+    resolved
+}
+
+/// Scans agent output for signs that the sandbox blocked something and
+/// turns each distinct offending line into one `SandboxViolation` event.
+#[derive(Debug)]
+pub struct ViolationMonitor {
+    network: SandboxNetwork,
+    reported: HashSet<String>,
+}
+
+impl ViolationMonitor {
+    pub fn new(policy: &SandboxPolicy) -> Self {
+        Self {
+            network: policy.network,
+            reported: HashSet::new(),
+        }
+    }
+
+    pub fn scan(&mut self, output: &str) -> Vec<Event> {
+        let mut events = Vec::new();
+        for line in output.lines() {
+            let lower = line.to_ascii_lowercase();
+            let kind = if FILESYSTEM_VIOLATION_PATTERNS
+                .iter()
+                .any(|pattern| lower.contains(pattern))
+            {
+                "filesystem"
+            } else if self.network == SandboxNetwork::None
+                && NETWORK_VIOLATION_PATTERNS
+                    .iter()
+                    .any(|pattern| lower.contains(pattern))
+            {
+                "network"
+            } else {
+                continue;
+            };
+            let detail: String = line
+                .trim()
+                .chars()
+                .take(MAX_VIOLATION_DETAIL_CHARS)
+                .collect();
+            if self.reported.insert(detail.clone()) {
+                events.push(Event::SandboxViolation {
+                    kind: kind.to_string(),
+                    detail,
+                });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(backend: SandboxBackend, network: SandboxNetwork) -> SandboxPolicy {
+        SandboxPolicy {
+            backend,
+            network,
+            writable: vec!["/repo/.batty/shared-target".into()],
+            read_only: vec!["/repo/.git/config".into()],
+        }
+    }
+
+    #[test]
+    fn resolve_makes_paths_absolute_and_appends_extra() {
+        let declared = SandboxPolicy {
+            writable: vec!["data".into(), "/opt/cache".into(), "/opt/cache".into()],
+            read_only: vec!["data/locked".into()],
+            ..SandboxPolicy::default()
+        };
+        let resolved = declared.resolve(
+            Path::new("/repo"),
+            &[PathBuf::from("/repo/.batty/shared-target")],
+            &[PathBuf::from("/repo/.git/config")],
+        );
+        assert_eq!(
+            resolved.writable,
+            vec!["/repo/data", "/opt/cache", "/repo/.batty/shared-target"]
+        );
+        assert_eq!(
+            resolved.read_only,
+            vec!["/repo/data/locked", "/repo/.git/config"]
+        );
+    }
+
+    #[test]
+    fn bubblewrap_binds_worktree_and_declared_paths() {
+        let (program, argv) = policy(SandboxBackend::Bubblewrap, SandboxNetwork::None)
+            .wrap(
+                Path::new("/repo/.batty/worktrees/eng-1"),
+                "bash",
+                &["-lc".into(), "claude".into()],
+            )
+            .unwrap();
+        assert_eq!(program, "bwrap");
+        let joined = argv.join(" ");
+        assert!(joined.starts_with("--ro-bind / / --dev /dev --proc /proc"));
+        assert!(!joined.contains("--dev-bind"));
+        assert!(
+            joined.contains("--bind-try /repo/.batty/worktrees/eng-1 /repo/.batty/worktrees/eng-1")
+        );
+        assert!(joined.contains(
+            "--bind-try /repo/.batty/shared-target /repo/.batty/shared-target \
+             --ro-bind-try /repo/.git/config /repo/.git/config"
+        ));
+        assert!(joined.contains("--unshare-net"));
+        assert!(joined.ends_with("--chdir /repo/.batty/worktrees/eng-1 -- bash -lc claude"));
+    }
+
+    #[test]
+    fn unshare_passes_paths_positionally_and_keeps_host_network() {
+        let (program, argv) = policy(SandboxBackend::Unshare, SandboxNetwork::Host)
+            .wrap(Path::new("/wt"), "bash", &["-lc".into(), "codex".into()])
+            .unwrap();
+        assert_eq!(program, "unshare");
+        assert!(!argv.contains(&"--net".to_string()));
+        let count_at = argv.iter().position(|arg| arg == "3").unwrap();
+        assert_eq!(
+            &argv[count_at + 1..],
+            [
+                "/wt",
+                "/tmp",
+                "/repo/.batty/shared-target",
+                "1",
+                "/repo/.git/config",
+                "bash",
+                "-lc",
+                "codex"
+            ]
+        );
+    }
+
+    /// Run a probe script through `backend` for real. Returns `None` when the
+    /// backend is missing or this host cannot create the namespaces.
+    fn run_probe(backend: SandboxBackend, tmp: &Path) -> Option<String> {
+        let wt = tmp.join("wt");
+        let locked = wt.join("locked");
+        std::fs::create_dir_all(&locked).unwrap();
+        let sandbox = SandboxPolicy {
+            backend,
+            read_only: vec![locked.to_string_lossy().into_owned()],
+            ..SandboxPolicy::default()
+        };
+        sandbox.check_available().ok()?;
+        let (program, argv) = sandbox
+            .wrap(
+                &wt,
+                "sh",
+                &[
+                    "-c".into(),
+                    r#"touch "$1/ok" && echo worktree-writable
+touch "$1/locked/x" 2>/dev/null || echo read-only-path
+touch /dev/shm/batty-sandbox-probe 2>/dev/null || echo dev-shm-read-only
+touch /batty-sandbox-probe 2>/dev/null || echo root-read-only"#
+                        .into(),
+                    "sh".into(),
+                    wt.to_string_lossy().into_owned(),
+                ],
+            )
+            .unwrap();
+        let output = std::process::Command::new(program)
+            .args(argv)
+            .current_dir(&wt)
+            .output()
+            .unwrap();
+        let _ = std::fs::remove_file("/dev/shm/batty-sandbox-probe");
+        let _ = std::fs::remove_file("/batty-sandbox-probe");
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !stdout.contains("worktree-writable") {
+            eprintln!(
+                "skipping {backend:?} probe: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return None;
+        }
+        Some(stdout)
+    }
+
+    #[test]
+    fn sandbox_leaves_only_declared_paths_writable() {
+        for backend in [SandboxBackend::Unshare, SandboxBackend::Bubblewrap] {
+            let tmp = tempfile::tempdir().unwrap();
+            let Some(stdout) = run_probe(backend, tmp.path()) else {
+                continue;
+            };
+            for expected in ["read-only-path", "dev-shm-read-only", "root-read-only"] {
+                assert!(
+                    stdout.contains(expected),
+                    "{backend:?}: expected {expected} in {stdout:?}"
+                );
+            }
+            assert!(tmp.path().join("wt/ok").exists());
+            assert!(!tmp.path().join("wt/locked/x").exists());
+        }
+    }
+
+    #[test]
+    fn wrap_rejects_unresolved_relative_paths() {
+        let declared = SandboxPolicy {
+            writable: vec!["data".into()],
+            ..SandboxPolicy::default()
+        };
+        assert!(declared.wrap(Path::new("/wt"), "bash", &[]).is_err());
+    }
+
+    #[test]
+    fn monitor_reports_each_violation_once() {
+        let mut monitor =
+            ViolationMonitor::new(&policy(SandboxBackend::Bubblewrap, SandboxNetwork::None));
+        let output = "touch: cannot touch '/etc/x': Read-only file system\n\
+                      curl: (6) Could not resolve host: example.com\nok\n";
+        let events = monitor.scan(output);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            Event::SandboxViolation { kind, .. } if kind == "filesystem"
+        ));
+        assert!(matches!(
+            &events[1],
+            Event::SandboxViolation { kind, .. } if kind == "network"
+        ));
+        assert!(monitor.scan(output).is_empty());
+    }
+
+    #[test]
+    fn monitor_ignores_network_errors_with_host_network() {
+        let mut monitor =
+            ViolationMonitor::new(&policy(SandboxBackend::Bubblewrap, SandboxNetwork::Host));
+        assert!(monitor.scan("curl: Could not resolve host: x").is_empty());
+    }
+}
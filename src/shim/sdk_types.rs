@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::tool_activity::ToolCall;
+
 // ---------------------------------------------------------------------------
 // Messages written TO Claude's stdin
 // ---------------------------------------------------------------------------
@@ -297,6 +299,103 @@ pub fn extract_stream_text(event: &Value) -> Option<String> {
     None
 }
 
+// ---------------------------------------------------------------------------
+// Tool-call extraction helpers
+// ---------------------------------------------------------------------------
+
+/// Claude Code tools that write a file, with the input key holding its path.
+const FILE_WRITE_TOOLS: &[(&str, &str)] = &[
+    ("Edit", "file_path"),
+    ("MultiEdit", "file_path"),
+    ("Write", "file_path"),
+    ("NotebookEdit", "notebook_path"),
+];
+
+/// Extract `tool_use` blocks from an `assistant` message's `content` array.
+pub fn extract_tool_calls(message: &Value) -> Vec<ToolCall> {
+    let Some(Value::Array(content)) = message.get("content") else {
+        return Vec::new();
+    };
+
+    content
+        .iter()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+        .filter_map(|block| {
+            let id = block.get("id")?.as_str()?.to_string();
+            let tool = block.get("name")?.as_str()?.to_string();
+            let input = block.get("input");
+            let paths = FILE_WRITE_TOOLS
+                .iter()
+                .find(|(name, _)| *name == tool)
+                .and_then(|(_, key)| input?.get(*key)?.as_str())
+                .map(|path| vec![path.to_string()])
+                .unwrap_or_default();
+            let command = (tool == "Bash")
+                .then(|| input?.get("command")?.as_str().map(String::from))
+                .flatten();
+            Some(ToolCall {
+                id,
+                tool,
+                paths,
+                command,
+            })
+        })
+        .collect()
+}
+
+/// Outcome of a tool call, from a `tool_result` block in a `user` message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdkToolResult {
+    pub tool_use_id: String,
+    pub is_error: bool,
+    /// Shell exit code, when Claude Code reported a failing command as
+    /// `Exit code N`.
+    pub exit_code: Option<i32>,
+}
+
+/// Extract `tool_result` blocks from a `user` message's `content` array.
+pub fn extract_tool_results(message: &Value) -> Vec<SdkToolResult> {
+    let Some(Value::Array(content)) = message.get("content") else {
+        return Vec::new();
+    };
+
+    content
+        .iter()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+        .filter_map(|block| {
+            let tool_use_id = block.get("tool_use_id")?.as_str()?.to_string();
+            let is_error = block
+                .get("is_error")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let exit_code = tool_result_text(block.get("content")).and_then(|text| {
+                text.trim_start()
+                    .strip_prefix("Exit code ")?
+                    .split(|c: char| !c.is_ascii_digit() && c != '-')
+                    .next()?
+                    .parse()
+                    .ok()
+            });
+            Some(SdkToolResult {
+                tool_use_id,
+                is_error,
+                exit_code,
+            })
+        })
+        .collect()
+}
+
+fn tool_result_text(content: Option<&Value>) -> Option<String> {
+    match content? {
+        Value::String(text) => Some(text.clone()),
+        Value::Array(blocks) => blocks
+            .iter()
+            .find_map(|block| block.get("text").and_then(|t| t.as_str()))
+            .map(String::from),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -508,4 +607,58 @@ mod tests {
         let event = json!({"type": "content_block_start"});
         assert_eq!(extract_stream_text(&event), None);
     }
+
+    // --- Tool calls ---
+
+    #[test]
+    fn extract_tool_calls_reads_edit_paths_and_bash_commands() {
+        let msg = json!({
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "Fixing it"},
+                {"type": "tool_use", "id": "toolu_1", "name": "Edit",
+                 "input": {"file_path": "src/lib.rs", "old_string": "a", "new_string": "b"}},
+                {"type": "tool_use", "id": "toolu_2", "name": "Bash",
+                 "input": {"command": "cargo test"}},
+                {"type": "tool_use", "id": "toolu_3", "name": "Read",
+                 "input": {"file_path": "README.md"}}
+            ]
+        });
+        let calls = extract_tool_calls(&msg);
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].paths, vec!["src/lib.rs"]);
+        assert_eq!(calls[0].command, None);
+        assert_eq!(calls[1].command.as_deref(), Some("cargo test"));
+        assert!(calls[1].paths.is_empty());
+        assert!(calls[2].paths.is_empty());
+    }
+
+    #[test]
+    fn extract_tool_results_parses_errors_and_exit_codes() {
+        let msg = json!({
+            "role": "user",
+            "content": [
+                {"type": "tool_result", "tool_use_id": "toolu_1", "content": "ok"},
+                {"type": "tool_result", "tool_use_id": "toolu_2", "is_error": true,
+                 "content": [{"type": "text", "text": "Exit code 101\nerror: test failed"}]}
+            ]
+        });
+        let results = extract_tool_results(&msg);
+        assert_eq!(
+            results,
+            vec![
+                SdkToolResult {
+                    tool_use_id: "toolu_1".into(),
+                    is_error: false,
+                    exit_code: None,
+                },
+                SdkToolResult {
+                    tool_use_id: "toolu_2".into(),
+                    is_error: true,
+                    exit_code: Some(101),
+                },
+            ]
+        );
+        assert!(extract_tool_results(&json!({"content": "plain"})).is_empty());
+    }
 }
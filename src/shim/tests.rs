@@ -44,6 +44,7 @@ fn spawn_bash_shim() -> Channel {
         pty_log_path: None,
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
 
     std::thread::spawn(move || {
@@ -536,6 +537,7 @@ fn spawn_bash_shim_with_log(id: &str, log_path: PathBuf) -> Channel {
         pty_log_path: Some(log_path),
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
     spawn_shim_with_args(args)
 }
@@ -552,6 +554,7 @@ fn spawn_named_bash_shim(id: &str) -> Channel {
         pty_log_path: None,
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
     spawn_shim_with_args(args)
 }
@@ -1044,6 +1047,7 @@ fn shim_channel_lifecycle_e2e() {
         pty_log_path: None,
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
 
     std::thread::spawn(move || {
@@ -63,6 +63,7 @@ fn spawn_sdk_mock(mock_cmd: &str) -> Channel {
         pty_log_path: None,
         graceful_shutdown_timeout_secs: 5,
         auto_commit_on_restart: true,
+        sandbox: None,
     };
 
     std::thread::spawn(move || {
@@ -0,0 +1,275 @@
+//! Tool-call tracking for SDK-mode shims.
+//!
+//! Each SDK runtime sees tool calls in its own wire format (Claude
+//! `tool_use`/`tool_result` blocks, Codex thread items, Kiro ACP
+//! `tool_call` updates). The runtimes normalise them into [`ToolCall`]
+//! starts and finishes; [`ToolActivity`] pairs the two halves and produces
+//! the structured protocol events the daemon records.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::protocol::Event;
+
+/// A tool invocation as reported by the agent, normalised across backends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCall {
+    /// Backend-assigned call id used to pair start and finish.
+    pub id: String,
+    /// Tool name as the agent reports it (`Edit`, `Bash`, `command_execution`).
+    pub tool: String,
+    /// Files the call writes to.
+    pub paths: Vec<String>,
+    /// Shell command line, for tools that run one.
+    pub command: Option<String>,
+}
+
+#[derive(Debug)]
+struct InFlightTool {
+    call: ToolCall,
+    started_at: Instant,
+}
+
+/// Tool calls that have started but not yet finished.
+#[derive(Debug, Default)]
+pub struct ToolActivity {
+    in_flight: HashMap<String, InFlightTool>,
+}
+
+impl ToolActivity {
+    /// Record a tool start and return the `ToolStarted` event to forward.
+    ///
+    /// A repeated start for the same id (Codex re-sends `item.started`
+    /// payloads as `item.updated`) keeps the original start time and emits
+    /// nothing.
+    pub fn start(&mut self, call: ToolCall) -> Option<Event> {
+        if self.in_flight.contains_key(&call.id) {
+            return None;
+        }
+        let event = Event::ToolStarted {
+            tool_use_id: call.id.clone(),
+            tool: call.tool.clone(),
+            command: call.command.clone(),
+        };
+        self.in_flight.insert(
+            call.id.clone(),
+            InFlightTool {
+                call,
+                started_at: Instant::now(),
+            },
+        );
+        Some(event)
+    }
+
+    /// Record a tool finish and return the events to forward.
+    ///
+    /// `fallback` describes the call when the start was never seen (Codex
+    /// only reports some items on completion). Successful writes produce a
+    /// `FileEdited` per path; shell tools produce a `CommandRun`.
+    pub fn finish(
+        &mut self,
+        id: &str,
+        fallback: Option<ToolCall>,
+        is_error: bool,
+        exit_code: Option<i32>,
+    ) -> Vec<Event> {
+        let (call, duration_ms) = match self.in_flight.remove(id) {
+            Some(in_flight) => (
+                in_flight.call,
+                in_flight.started_at.elapsed().as_millis() as u64,
+            ),
+            None => match fallback {
+                Some(call) => (call, 0),
+                None => return Vec::new(),
+            },
+        };
+
+        let mut events = vec![Event::ToolFinished {
+            tool_use_id: call.id.clone(),
+            tool: call.tool.clone(),
+            duration_ms,
+            is_error,
+        }];
+        if !is_error {
+            events.extend(call.paths.iter().map(|path| Event::FileEdited {
+                path: path.clone(),
+                tool: call.tool.clone(),
+            }));
+        }
+        if let Some(command) = call.command {
+            events.push(Event::CommandRun {
+                argv: command_argv(&command),
+                exit_code: exit_code.or(if is_error { None } else { Some(0) }),
+                duration_ms,
+            });
+        }
+        events
+    }
+
+    /// How long the oldest in-flight shell command has been running.
+    pub fn command_in_flight_secs(&self) -> Option<u64> {
+        self.in_flight
+            .values()
+            .filter(|tool| tool.call.command.is_some())
+            .map(|tool| tool.started_at.elapsed().as_secs())
+            .max()
+    }
+
+    /// Forget every in-flight call (turn ended or agent restarted).
+    pub fn clear(&mut self) {
+        self.in_flight.clear();
+    }
+}
+
+/// Split a shell command line into words, honouring single quotes, double
+/// quotes, and backslash escapes. Operators such as `&&` stay as words; the
+/// result is for display and matching, not re-execution.
+pub fn command_argv(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+    let mut quote: Option<char> = None;
+
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                    in_word = true;
+                }
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit_call(id: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            tool: "Edit".to_string(),
+            paths: vec!["src/lib.rs".to_string()],
+            command: None,
+        }
+    }
+
+    fn bash_call(id: &str, command: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            tool: "Bash".to_string(),
+            paths: Vec::new(),
+            command: Some(command.to_string()),
+        }
+    }
+
+    #[test]
+    fn start_then_finish_emits_file_edit() {
+        let mut activity = ToolActivity::default();
+        let started = activity.start(edit_call("t1")).unwrap();
+        assert!(matches!(started, Event::ToolStarted { ref tool, .. } if tool == "Edit"));
+        assert!(activity.start(edit_call("t1")).is_none());
+
+        let events = activity.finish("t1", None, false, None);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            Event::ToolFinished {
+                is_error: false,
+                ..
+            }
+        ));
+        assert!(matches!(events[1], Event::FileEdited { ref path, .. } if path == "src/lib.rs"));
+        assert!(activity.finish("t1", None, false, None).is_empty());
+    }
+
+    #[test]
+    fn failed_edit_reports_no_file_change() {
+        let mut activity = ToolActivity::default();
+        activity.start(edit_call("t1"));
+        let events = activity.finish("t1", None, true, None);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            Event::ToolFinished { is_error: true, .. }
+        ));
+    }
+
+    #[test]
+    fn command_finish_reports_argv_and_exit_code() {
+        let mut activity = ToolActivity::default();
+        activity.start(bash_call("t2", "cargo test -p batty"));
+        assert_eq!(activity.command_in_flight_secs(), Some(0));
+
+        let events = activity.finish("t2", None, true, Some(101));
+        match &events[1] {
+            Event::CommandRun {
+                argv, exit_code, ..
+            } => {
+                assert_eq!(argv, &["cargo", "test", "-p", "batty"]);
+                assert_eq!(*exit_code, Some(101));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert_eq!(activity.command_in_flight_secs(), None);
+    }
+
+    #[test]
+    fn finish_without_start_uses_fallback() {
+        let mut activity = ToolActivity::default();
+        let events = activity.finish("t3", Some(bash_call("t3", "ls")), false, None);
+        assert!(matches!(
+            events.last(),
+            Some(Event::CommandRun {
+                exit_code: Some(0),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn command_argv_handles_quotes_and_escapes() {
+        assert_eq!(
+            command_argv(r#"git commit -m "fix \"the\" bug" && echo 'a b' c\ d"#),
+            vec![
+                "git",
+                "commit",
+                "-m",
+                r#"fix "the" bug"#,
+                "&&",
+                "echo",
+                "a b",
+                "c d"
+            ]
+        );
+        assert_eq!(command_argv("  "), Vec::<String>::new());
+        assert_eq!(command_argv("echo ''"), vec!["echo", ""]);
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result, bail};
 use chrono::{DateTime, FixedOffset, Utc};
+use regex::Regex;
 use serde::{Deserialize, Deserializer};
 use serde_yaml::{Mapping, Value};
 use std::path::{Path, PathBuf};
@@ -68,9 +69,228 @@ pub struct Task {
     pub completed: Option<String>,
     pub description: String,
     pub batty_config: Option<TaskBattyConfig>,
+    /// Paths the task may touch (from `scope:` frontmatter), enforced by the
+    /// pre-commit hook in engineer worktrees.
+    pub scope: Option<TaskScope>,
+    /// Machine-checkable criteria (from `acceptance:` frontmatter) that must
+    /// all pass in the engineer worktree before the task can reach review.
+    pub acceptance: Vec<AcceptanceCriterion>,
     pub source_path: PathBuf,
 }
 
+/// One entry in a task's `acceptance:` frontmatter list.
+///
+/// Each entry is a single-key mapping naming the check, for example
+/// `- command: cargo test -p api` or
+/// `- grep: { path: "src/**/*.rs", pattern: "fn retry" }`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptanceCriterion {
+    /// A shell command that must exit zero.
+    Command(String),
+    /// A worktree-relative path that must exist.
+    FileExists(String),
+    /// A regex that must match at least one file selected by `path` (a path
+    /// or glob), or, with `absent: true`, must match none of them.
+    Grep {
+        path: String,
+        pattern: String,
+        #[serde(default)]
+        absent: bool,
+    },
+    /// Coverage percentage printed by `command` (the last `NN.N%` in its
+    /// output) must be at least `min_delta` points above the baseline, which
+    /// is measured on trunk unless given. The default of `0` rejects any drop.
+    Coverage {
+        command: String,
+        #[serde(default)]
+        min_delta: f64,
+        #[serde(default)]
+        baseline: Option<f64>,
+    },
+    /// A number printed by `command` must stay within `min`/`max`. The first
+    /// capture group of `pattern` is used when given, otherwise the last
+    /// number in the output.
+    Benchmark {
+        command: String,
+        #[serde(default)]
+        pattern: Option<String>,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+}
+
+impl AcceptanceCriterion {
+    /// Short human-readable description used in evidence and review packets.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Command(command) => format!("command `{command}`"),
+            Self::FileExists(path) => format!("file exists `{path}`"),
+            Self::Grep {
+                path,
+                pattern,
+                absent,
+            } => {
+                let verb = if *absent { "absent from" } else { "in" };
+                format!("grep `{pattern}` {verb} `{path}`")
+            }
+            Self::Coverage {
+                command, min_delta, ..
+            } => format!("coverage delta >= {min_delta} from `{command}`"),
+            Self::Benchmark {
+                command, min, max, ..
+            } => {
+                let bounds = match (min, max) {
+                    (Some(min), Some(max)) => format!("{min}..={max}"),
+                    (Some(min), None) => format!(">= {min}"),
+                    (None, Some(max)) => format!("<= {max}"),
+                    (None, None) => "any value".to_string(),
+                };
+                format!("benchmark {bounds} from `{command}`")
+            }
+        }
+    }
+}
+
+/// Allow/deny globs from a task's `scope:` frontmatter.
+///
+/// Accepts either a mapping with `allow` and `deny` lists or a bare list,
+/// which is shorthand for `allow`. Entries without glob characters cover the
+/// path itself and everything below it, so `src/team` fences a directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "TaskScopeSpec")]
+pub struct TaskScope {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TaskScopeSpec {
+    Allow(Vec<String>),
+    Rules {
+        #[serde(default)]
+        allow: Vec<String>,
+        #[serde(default)]
+        deny: Vec<String>,
+    },
+}
+
+impl From<TaskScopeSpec> for TaskScope {
+    fn from(spec: TaskScopeSpec) -> Self {
+        let clean = |entries: Vec<String>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.trim().trim_end_matches('/').to_string())
+                .filter(|entry| !entry.is_empty())
+                .collect()
+        };
+        match spec {
+            TaskScopeSpec::Allow(allow) => Self {
+                allow: clean(allow),
+                deny: Vec::new(),
+            },
+            TaskScopeSpec::Rules { allow, deny } => Self {
+                allow: clean(allow),
+                deny: clean(deny),
+            },
+        }
+    }
+}
+
+/// Why a path falls outside a [`TaskScope`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopeViolation {
+    /// The path matches this `deny` entry.
+    Denied(String),
+    /// The path matches no `allow` entry.
+    NotAllowed,
+}
+
+impl TaskScope {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Check one repository-relative path. Deny entries win over allow
+    /// entries; an empty allow list permits anything not denied.
+    pub fn check(&self, path: &str) -> Option<ScopeViolation> {
+        if let Some(pattern) = self
+            .deny
+            .iter()
+            .find(|pattern| scope_entry_matches(pattern, path))
+        {
+            return Some(ScopeViolation::Denied(pattern.clone()));
+        }
+        if !self.allow.is_empty()
+            && !self
+                .allow
+                .iter()
+                .any(|pattern| scope_entry_matches(pattern, path))
+        {
+            return Some(ScopeViolation::NotAllowed);
+        }
+        None
+    }
+}
+
+pub(crate) fn scope_entry_matches(entry: &str, path: &str) -> bool {
+    if has_glob_magic(entry) {
+        return glob_matches_path(entry, path);
+    }
+    path == entry
+        || path
+            .strip_prefix(entry)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+pub(crate) fn has_glob_magic(path: &str) -> bool {
+    path.contains('*') || path.contains('?')
+}
+
+fn glob_to_regex(pattern: &str) -> Option<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                } else {
+                    regex.push_str("[^/]*");
+                }
+            }
+            '?' => regex.push_str("[^/]"),
+            '.' | '+' | '(' | ')' | '[' | ']' | '{' | '}' | '^' | '$' | '|' | '\\' => {
+                regex.push('\\');
+                regex.push(ch);
+            }
+            _ => regex.push(ch),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
+
+/// Match a repository-relative path against a glob where `*` and `?` stay
+/// within one path segment and `**` spans segments.
+pub(crate) fn glob_matches_path(pattern: &str, path: &str) -> bool {
+    if !has_glob_magic(pattern) {
+        return pattern == path;
+    }
+    glob_to_regex(pattern)
+        .map(|regex| regex.is_match(path))
+        .unwrap_or(false)
+}
+
 /// Per-task overrides from `## Batty Config` section.
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct TaskBattyConfig {
@@ -137,6 +357,10 @@ struct Frontmatter {
     cron_last_run: Option<String>,
     #[serde(default)]
     completed: Option<String>,
+    #[serde(default)]
+    scope: Option<TaskScope>,
+    #[serde(default, with = "serde_yaml::with::singleton_map_recursive")]
+    acceptance: Vec<AcceptanceCriterion>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -240,6 +464,8 @@ impl Task {
             completed: fm.completed,
             description,
             batty_config,
+            scope: fm.scope.filter(|scope| !scope.is_empty()),
+            acceptance: fm.acceptance,
             source_path: PathBuf::new(),
         })
     }
@@ -792,6 +1018,8 @@ Body.
             completed: None,
             description: "Touch *.rs and Cargo.toml.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::new(),
         };
 
@@ -838,6 +1066,8 @@ Body.
                  __Priya's__ outline** lands Fri. Touch src/foo.rs and *.md."
                 .to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::new(),
         };
 
@@ -1199,6 +1429,118 @@ Only cron_schedule set.
         assert!(task.cron_last_run.is_none());
     }
 
+    #[test]
+    fn parse_task_with_scope_allow_and_deny() {
+        let content = r#"---
+id: 204
+title: fenced task
+status: todo
+scope:
+  allow:
+    - src/team/
+    - "docs/**/*.md"
+  deny:
+    - src/team/daemon.rs
+---
+"#;
+        let task = Task::parse(content).unwrap();
+        let scope = task.scope.unwrap();
+        assert_eq!(scope.allow, vec!["src/team", "docs/**/*.md"]);
+        assert_eq!(scope.deny, vec!["src/team/daemon.rs"]);
+
+        assert_eq!(scope.check("src/team/slack.rs"), None);
+        assert_eq!(scope.check("docs/guides/setup.md"), None);
+        assert_eq!(
+            scope.check("src/team/daemon.rs"),
+            Some(ScopeViolation::Denied("src/team/daemon.rs".to_string()))
+        );
+        assert_eq!(
+            scope.check("src/team_extra.rs"),
+            Some(ScopeViolation::NotAllowed)
+        );
+        assert_eq!(
+            scope.check("docs/logo.png"),
+            Some(ScopeViolation::NotAllowed)
+        );
+    }
+
+    #[test]
+    fn parse_task_with_scope_list_shorthand_and_deny_only() {
+        let shorthand = Task::parse("---\nid: 1\ntitle: t\nstatus: todo\nscope: [src/]\n---\n")
+            .unwrap()
+            .scope
+            .unwrap();
+        assert_eq!(shorthand.allow, vec!["src"]);
+        assert!(shorthand.deny.is_empty());
+
+        let deny_only =
+            Task::parse("---\nid: 2\ntitle: t\nstatus: todo\nscope:\n  deny: [\"*.lock\"]\n---\n")
+                .unwrap()
+                .scope
+                .unwrap();
+        assert_eq!(deny_only.check("src/main.rs"), None);
+        assert!(matches!(
+            deny_only.check("Cargo.lock"),
+            Some(ScopeViolation::Denied(_))
+        ));
+
+        let empty = Task::parse("---\nid: 3\ntitle: t\nstatus: todo\nscope: []\n---\n").unwrap();
+        assert!(empty.scope.is_none());
+    }
+
+    #[test]
+    fn parse_task_with_acceptance_criteria() {
+        let content = r#"---
+id: 205
+title: accepted task
+status: todo
+acceptance:
+  - command: cargo test -p api
+  - file_exists: docs/api.md
+  - grep: { path: "src/**/*.rs", pattern: "fn retry", absent: true }
+  - coverage: { command: cargo llvm-cov --summary-only }
+  - benchmark:
+      command: cargo bench --bench parse
+      pattern: 'time:\s+([0-9.]+)'
+      max: 250
+---
+"#;
+        let task = Task::parse(content).unwrap();
+        assert_eq!(
+            task.acceptance,
+            vec![
+                AcceptanceCriterion::Command("cargo test -p api".to_string()),
+                AcceptanceCriterion::FileExists("docs/api.md".to_string()),
+                AcceptanceCriterion::Grep {
+                    path: "src/**/*.rs".to_string(),
+                    pattern: "fn retry".to_string(),
+                    absent: true,
+                },
+                AcceptanceCriterion::Coverage {
+                    command: "cargo llvm-cov --summary-only".to_string(),
+                    min_delta: 0.0,
+                    baseline: None,
+                },
+                AcceptanceCriterion::Benchmark {
+                    command: "cargo bench --bench parse".to_string(),
+                    pattern: Some(r"time:\s+([0-9.]+)".to_string()),
+                    min: None,
+                    max: Some(250.0),
+                },
+            ]
+        );
+        assert_eq!(
+            task.acceptance[4].label(),
+            "benchmark <= 250 from `cargo bench --bench parse`"
+        );
+        assert!(
+            Task::parse("---\nid: 1\ntitle: t\nstatus: todo\n---\n")
+                .unwrap()
+                .acceptance
+                .is_empty()
+        );
+    }
+
     #[test]
     fn missing_frontmatter_is_error() {
         let content = "# No frontmatter here\nJust markdown.";
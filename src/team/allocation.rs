@@ -7,7 +7,8 @@ use chrono::{DateTime, Duration, Utc};
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 
-use super::config::{AllocationPolicy, RoleType, TeamConfig};
+use super::allocation_model::{AllocationModel, load_allocation_model};
+use super::config::{AllocationPolicy, AllocationStrategy, RoleType, TeamConfig};
 use super::hierarchy::resolve_hierarchy;
 use super::standup::MemberState;
 use super::{daemon_state_path, team_config_dir};
@@ -46,6 +47,7 @@ pub struct EngineerRoutingBreakdown {
     pub avg_task_duration_secs: Option<f64>,
     pub first_pass_test_rate: Option<f64>,
     pub telemetry_completed_tasks: u32,
+    pub contributions: Vec<FeatureContribution>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -53,9 +55,74 @@ pub struct RoutingDecisionExplanation {
     pub chosen_engineer: Option<String>,
     pub fallback_to_round_robin: bool,
     pub fallback_reason: Option<String>,
+    pub learned_model: bool,
     pub breakdowns: Vec<EngineerRoutingBreakdown>,
 }
 
+/// Names of the engineer/task features, in [`AllocationFeatures::values`] order.
+pub const ALLOCATION_FEATURES: [&str; 6] = [
+    "tag_matches",
+    "file_matches",
+    "completion_rate",
+    "active_tasks",
+    "recent_conflicts",
+    "completions",
+];
+
+/// Engineer/task features shared by the heuristic score and the learned
+/// allocation model.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AllocationFeatures {
+    pub tag_matches: f64,
+    pub file_matches: f64,
+    pub completion_rate: f64,
+    pub active_tasks: f64,
+    pub recent_conflicts: f64,
+    pub completions: f64,
+}
+
+impl AllocationFeatures {
+    pub fn for_task(engineer: &EngineerProfile, task: &Task) -> Self {
+        let task_dirs = task_hint_directories(task);
+        let engineer_dirs: HashSet<String> = engineer
+            .active_file_paths
+            .iter()
+            .filter_map(|path| parent_dir(path))
+            .collect();
+        Self {
+            tag_matches: task
+                .tags
+                .iter()
+                .filter(|tag| engineer.domain_tags.contains(*tag))
+                .count() as f64,
+            file_matches: engineer_dirs.intersection(&task_dirs).count() as f64,
+            completion_rate: engineer.completion_rate,
+            active_tasks: f64::from(engineer.active_task_count),
+            recent_conflicts: f64::from(engineer.recent_merge_conflicts),
+            completions: f64::from(engineer.total_completions),
+        }
+    }
+
+    pub fn values(&self) -> [f64; ALLOCATION_FEATURES.len()] {
+        [
+            self.tag_matches,
+            self.file_matches,
+            self.completion_rate,
+            self.active_tasks,
+            self.recent_conflicts,
+            self.completions,
+        ]
+    }
+}
+
+/// How much one feature added to (or took from) an engineer's routing score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureContribution {
+    pub feature: String,
+    pub value: f64,
+    pub contribution: f64,
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct AllocationTaskFrontmatter {
     #[serde(default)]
@@ -294,33 +361,49 @@ pub fn score_engineer_for_task(
     task: &Task,
     policy: &AllocationPolicy,
 ) -> i32 {
-    let mut score = 0;
-
-    let tag_overlap = task
-        .tags
+    heuristic_contributions(engineer, task, policy)
         .iter()
-        .filter(|tag| engineer.domain_tags.contains(*tag))
-        .count() as i32;
-    score += tag_overlap * policy.tag_weight;
+        .map(|contribution| contribution.contribution)
+        .sum::<f64>()
+        .round() as i32
+}
 
-    let task_dirs = task_hint_directories(task);
-    let engineer_dirs: HashSet<String> = engineer
-        .active_file_paths
+/// Per-feature terms of [`score_engineer_for_task`]; they sum to the score.
+pub fn heuristic_contributions(
+    engineer: &EngineerProfile,
+    task: &Task,
+    policy: &AllocationPolicy,
+) -> Vec<FeatureContribution> {
+    let features = AllocationFeatures::for_task(engineer, task);
+    let experience_bonus = if engineer.total_completions > 3 {
+        policy.experience_bonus
+    } else {
+        0
+    };
+    let terms = [
+        features.tag_matches * f64::from(policy.tag_weight),
+        features.file_matches * f64::from(policy.file_overlap_weight),
+        (features.completion_rate * 100.0).round(),
+        -features.active_tasks * f64::from(policy.load_penalty),
+        -features.recent_conflicts * f64::from(policy.conflict_penalty),
+        f64::from(experience_bonus),
+    ];
+    let performance = performance_score(engineer.performance.as_ref());
+    ALLOCATION_FEATURES
         .iter()
-        .filter_map(|path| parent_dir(path))
-        .collect();
-    let dir_overlap = engineer_dirs.intersection(&task_dirs).count() as i32;
-    score += dir_overlap * policy.file_overlap_weight;
-    score += (engineer.completion_rate * 100.0).round() as i32;
-
-    score -= (engineer.active_task_count as i32) * policy.load_penalty;
-    score -= (engineer.recent_merge_conflicts as i32) * policy.conflict_penalty;
-    if engineer.total_completions > 3 {
-        score += policy.experience_bonus;
-    }
-    score += performance_score(engineer.performance.as_ref());
-
-    score
+        .zip(features.values())
+        .zip(terms)
+        .map(|((feature, value), contribution)| FeatureContribution {
+            feature: (*feature).to_string(),
+            value,
+            contribution,
+        })
+        .chain(std::iter::once(FeatureContribution {
+            feature: "performance".to_string(),
+            value: f64::from(performance),
+            contribution: f64::from(performance),
+        }))
+        .collect()
 }
 
 fn performance_score(performance: Option<&EngineerPerformanceProfile>) -> i32 {
@@ -369,7 +452,19 @@ pub fn rank_engineers_for_task(
     task: &Task,
     policy: &AllocationPolicy,
 ) -> Vec<String> {
-    explain_routing_for_task(engineers, profiles, task, policy)
+    rank_engineers_with_model(engineers, profiles, task, policy, None)
+}
+
+/// Rank engineers with the learned allocation model when one is given,
+/// falling back to the heuristic weights in `policy` otherwise.
+pub fn rank_engineers_with_model(
+    engineers: &[String],
+    profiles: &HashMap<String, EngineerProfile>,
+    task: &Task,
+    policy: &AllocationPolicy,
+    model: Option<&AllocationModel>,
+) -> Vec<String> {
+    explain_routing_with_model(engineers, profiles, task, policy, model)
         .breakdowns
         .into_iter()
         .map(|breakdown| breakdown.engineer)
@@ -382,9 +477,20 @@ pub fn explain_routing_for_task(
     task: &Task,
     policy: &AllocationPolicy,
 ) -> RoutingDecisionExplanation {
+    explain_routing_with_model(engineers, profiles, task, policy, None)
+}
+
+pub fn explain_routing_with_model(
+    engineers: &[String],
+    profiles: &HashMap<String, EngineerProfile>,
+    task: &Task,
+    policy: &AllocationPolicy,
+    model: Option<&AllocationModel>,
+) -> RoutingDecisionExplanation {
+    let learned_model = model.is_some();
     let mut breakdowns: Vec<EngineerRoutingBreakdown> = engineers
         .iter()
-        .map(|engineer| engineer_breakdown(engineer, profiles.get(engineer), task, policy))
+        .map(|engineer| engineer_breakdown(engineer, profiles.get(engineer), task, policy, model))
         .collect();
 
     let has_any_telemetry = breakdowns
@@ -412,6 +518,7 @@ pub fn explain_routing_for_task(
             chosen_engineer,
             fallback_to_round_robin: false,
             fallback_reason: None,
+            learned_model,
             breakdowns,
         };
     }
@@ -427,6 +534,7 @@ pub fn explain_routing_for_task(
             "telemetry fallback: each eligible engineer needs at least 5 completed tasks"
                 .to_string(),
         ),
+        learned_model,
         breakdowns,
     }
 }
@@ -454,12 +562,30 @@ pub fn print_dispatch_explanation(project_root: &Path, task_id: Option<u32>) ->
     engineers.retain(|engineer| !bench_state.benched.contains_key(engineer));
     engineers.sort();
     let profiles = load_engineer_profiles(project_root, &engineers, &tasks)?;
-    let explanation = explain_routing_for_task(
-        &engineers,
-        &profiles,
-        task,
-        &team_config.workflow_policy.allocation,
-    );
+    let policy = &team_config.workflow_policy.allocation;
+    let (model, scorer) = if policy.strategy == AllocationStrategy::Learned {
+        match load_allocation_model(project_root) {
+            Ok(Some(model)) => {
+                let scorer = format!(
+                    "learned model ({} samples, trained {})",
+                    model.samples, model.trained_at
+                );
+                (Some(model), scorer)
+            }
+            Ok(None) => (
+                None,
+                "heuristic weights (no trained model; run `batty dispatch train`)".to_string(),
+            ),
+            Err(error) => (
+                None,
+                format!("heuristic weights (allocation model unusable: {error:#})"),
+            ),
+        }
+    } else {
+        (None, "heuristic weights".to_string())
+    };
+    let explanation =
+        explain_routing_with_model(&engineers, &profiles, task, policy, model.as_ref());
 
     println!("Task #{}: {}", task.id, task.title);
     if let Some(chosen) = &explanation.chosen_engineer {
@@ -472,13 +598,14 @@ pub fn print_dispatch_explanation(project_root: &Path, task_id: Option<u32>) ->
     } else {
         println!("Routing mode: telemetry-scored");
     }
+    println!("Scoring: {scorer}");
     println!();
     println!(
         "{:<20} {:>6} {:>5} {:>5} {:>10} {:>10} {:>11} {:>8}",
         "ENGINEER", "SCORE", "TAGS", "FILES", "COMPLETE%", "AVG SECS", "FIRST PASS%", "SAMPLES"
     );
     println!("{}", "-".repeat(88));
-    for breakdown in explanation.breakdowns {
+    for breakdown in &explanation.breakdowns {
         println!(
             "{:<20} {:>6} {:>5} {:>5} {:>10.1} {:>10} {:>11.1} {:>8}",
             breakdown.engineer,
@@ -494,41 +621,66 @@ pub fn print_dispatch_explanation(project_root: &Path, task_id: Option<u32>) ->
             breakdown.telemetry_completed_tasks,
         );
     }
+    println!();
+    println!("Feature contributions:");
+    for breakdown in &explanation.breakdowns {
+        let terms: Vec<String> = breakdown
+            .contributions
+            .iter()
+            .map(|term| {
+                format!(
+                    "{}={} ({:+.1})",
+                    term.feature,
+                    format_feature_value(term.value),
+                    term.contribution
+                )
+            })
+            .collect();
+        println!("  {:<20} {}", breakdown.engineer, terms.join(", "));
+    }
 
     Ok(())
 }
 
+fn format_feature_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
 fn engineer_breakdown(
     engineer: &str,
     profile: Option<&EngineerProfile>,
     task: &Task,
     policy: &AllocationPolicy,
+    model: Option<&AllocationModel>,
 ) -> EngineerRoutingBreakdown {
     let profile = profile.cloned().unwrap_or_else(|| EngineerProfile {
         name: engineer.to_string(),
         ..EngineerProfile::default()
     });
-    let task_dirs = task_hint_directories(task);
-    let engineer_dirs: HashSet<String> = profile
-        .active_file_paths
-        .iter()
-        .filter_map(|path| parent_dir(path))
-        .collect();
-    let tag_matches = task
-        .tags
+    let features = AllocationFeatures::for_task(&profile, task);
+    let contributions = match model {
+        Some(model) => model.contributions(&features),
+        None => heuristic_contributions(&profile, task, policy),
+    };
+    let total_score = contributions
         .iter()
-        .filter(|tag| profile.domain_tags.contains(*tag))
-        .count();
-    let file_matches = engineer_dirs.intersection(&task_dirs).count();
+        .map(|term| term.contribution)
+        .sum::<f64>()
+        .round() as i32;
     EngineerRoutingBreakdown {
         engineer: engineer.to_string(),
-        total_score: score_engineer_for_task(&profile, task, policy),
-        tag_matches,
-        file_matches,
+        total_score,
+        tag_matches: features.tag_matches as usize,
+        file_matches: features.file_matches as usize,
         completion_rate: profile.completion_rate,
         avg_task_duration_secs: profile.avg_task_duration_secs,
         first_pass_test_rate: profile.first_pass_test_rate,
         telemetry_completed_tasks: profile.telemetry_completed_tasks,
+        contributions,
     }
 }
 
@@ -711,7 +863,7 @@ fn task_profile_paths(task: &Task) -> Result<HashSet<String>> {
     Ok(paths)
 }
 
-fn task_hint_directories(task: &Task) -> HashSet<String> {
+pub(crate) fn task_hint_directories(task: &Task) -> HashSet<String> {
     crate::task::task_file_hints(task)
         .unwrap_or_default()
         .into_iter()
@@ -719,7 +871,7 @@ fn task_hint_directories(task: &Task) -> HashSet<String> {
         .collect()
 }
 
-fn load_changed_paths(path: &Path) -> Result<Vec<String>> {
+pub(crate) fn load_changed_paths(path: &Path) -> Result<Vec<String>> {
     if path.as_os_str().is_empty() || !path.exists() {
         return Ok(Vec::new());
     }
@@ -791,7 +943,7 @@ fn extract_frontmatter(content: &str) -> Option<&str> {
     Some(&after_open[..close_pos])
 }
 
-fn parent_dir(path: &str) -> Option<String> {
+pub(crate) fn parent_dir(path: &str) -> Option<String> {
     PathBuf::from(path)
         .parent()
         .filter(|parent| !parent.as_os_str().is_empty())
@@ -837,6 +989,8 @@ mod tests {
             completed: None,
             description: description.to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::new(),
         }
     }
@@ -872,6 +1026,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn heuristic_contributions_sum_to_score() {
+        let profile = EngineerProfile {
+            domain_tags: HashSet::from(["dispatch".to_string()]),
+            active_file_paths: HashSet::from(["src/team/dispatch/queue.rs".to_string()]),
+            active_task_count: 1,
+            recent_merge_conflicts: 1,
+            total_completions: 5,
+            completion_rate: 0.667,
+            ..EngineerProfile::default()
+        };
+        let task = task(&["dispatch"], "Touch src/team/dispatch/mod.rs next.");
+        let contributions = heuristic_contributions(&profile, &task, &policy());
+        let total: f64 = contributions.iter().map(|term| term.contribution).sum();
+
+        assert_eq!(
+            total as i32,
+            score_engineer_for_task(&profile, &task, &policy())
+        );
+        let tag_term = contributions
+            .iter()
+            .find(|term| term.feature == "tag_matches")
+            .unwrap();
+        assert_eq!(tag_term.value, 1.0);
+        assert_eq!(tag_term.contribution, 15.0);
+    }
+
     #[test]
     fn score_penalizes_active_load() {
         let light = EngineerProfile {
@@ -1037,6 +1218,8 @@ mod tests {
             completed: None,
             description: "Resolve rebase conflict in src/team/daemon/mod.rs".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::new(),
         };
 
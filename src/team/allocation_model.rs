@@ -0,0 +1,752 @@
+//! Learned allocation model fitted from telemetry outcomes.
+//!
+//! `batty dispatch train` replays the `task_assigned` → merge history in
+//! `telemetry.db`, rebuilds the [`AllocationFeatures`] each engineer had at
+//! assignment time, and fits two heads on standardized features: a logistic
+//! regression for first-pass success (merged without rework or escalation)
+//! and a linear regression for log cycle time. The heads are folded into one
+//! linear score so `allocation.strategy: learned` ranks engineers the same way
+//! the heuristic does, with per-feature contributions for `--explain`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use super::allocation::{
+    ALLOCATION_FEATURES, AllocationFeatures, FeatureContribution, load_changed_paths, parent_dir,
+    task_hint_directories,
+};
+use crate::task::Task;
+
+const ALLOCATION_MODEL_FILE: &str = "allocation_model.json";
+const MODEL_VERSION: u32 = 1;
+/// Weight of the cycle-time head relative to the first-pass head.
+const CYCLE_TIME_WEIGHT: f64 = 0.5;
+/// Scale applied to the combined score so it reads like the heuristic's points.
+const SCORE_SCALE: f64 = 100.0;
+const TRAINING_ITERATIONS: usize = 2_000;
+const LEARNING_RATE: f64 = 0.1;
+const L2_PENALTY: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocationModel {
+    pub version: u32,
+    pub trained_at: String,
+    pub samples: usize,
+    pub first_pass_rate: f64,
+    pub features: Vec<String>,
+    pub means: Vec<f64>,
+    pub stds: Vec<f64>,
+    pub success_weights: Vec<f64>,
+    pub success_bias: f64,
+    pub cycle_weights: Vec<f64>,
+    pub cycle_bias: f64,
+    /// `success_weights - CYCLE_TIME_WEIGHT * cycle_weights`, used for ranking.
+    pub weights: Vec<f64>,
+}
+
+/// One historical assignment with its outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingSample {
+    pub engineer: String,
+    pub task_id: u32,
+    pub features: AllocationFeatures,
+    pub first_pass: bool,
+    pub cycle_secs: Option<u64>,
+}
+
+impl AllocationModel {
+    pub fn contributions(&self, features: &AllocationFeatures) -> Vec<FeatureContribution> {
+        let values = features.values();
+        ALLOCATION_FEATURES
+            .iter()
+            .enumerate()
+            .map(|(index, feature)| FeatureContribution {
+                feature: (*feature).to_string(),
+                value: values[index],
+                contribution: self.weights[index]
+                    * standardize(
+                        model_input(index, values[index]),
+                        self.means[index],
+                        self.stds[index],
+                    )
+                    * SCORE_SCALE,
+            })
+            .collect()
+    }
+
+    /// Probability that the engineer lands the task on the first pass.
+    pub fn first_pass_probability(&self, features: &AllocationFeatures) -> f64 {
+        sigmoid(self.success_bias + dot(&self.success_weights, &self.standardized(features)))
+    }
+
+    fn standardized(&self, features: &AllocationFeatures) -> Vec<f64> {
+        features
+            .values()
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                standardize(
+                    model_input(index, *value),
+                    self.means[index],
+                    self.stds[index],
+                )
+            })
+            .collect()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version != MODEL_VERSION {
+            bail!(
+                "model version {} is not supported (expected {MODEL_VERSION}); retrain it",
+                self.version
+            );
+        }
+        if self.features != ALLOCATION_FEATURES {
+            bail!("model features do not match this batty version; retrain it");
+        }
+        let width = ALLOCATION_FEATURES.len();
+        for (name, values) in [
+            ("means", &self.means),
+            ("stds", &self.stds),
+            ("success_weights", &self.success_weights),
+            ("cycle_weights", &self.cycle_weights),
+            ("weights", &self.weights),
+        ] {
+            if values.len() != width || values.iter().any(|value| !value.is_finite()) {
+                bail!("model {name} must hold {width} finite values");
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn allocation_model_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(ALLOCATION_MODEL_FILE)
+}
+
+/// Load the trained model. Returns `Ok(None)` when nothing has been trained.
+pub fn load_allocation_model(project_root: &Path) -> Result<Option<AllocationModel>> {
+    let path = allocation_model_path(project_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let model: AllocationModel = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    model
+        .validate()
+        .with_context(|| format!("invalid allocation model {}", path.display()))?;
+    Ok(Some(model))
+}
+
+/// The trained model as last read from disk, reloaded only when the file's
+/// mtime changes so dispatch does not re-read and re-validate it per task.
+#[derive(Debug, Default)]
+pub struct AllocationModelCache {
+    /// `None` until the first load; then the mtime seen (`None` inside when
+    /// the file did not exist).
+    loaded_mtime: Option<Option<std::time::SystemTime>>,
+    model: Option<AllocationModel>,
+}
+
+impl AllocationModelCache {
+    /// Reload the model if the file changed since the last call. An error is
+    /// returned only by the load that hit it; the cache then holds no model
+    /// until the file changes again.
+    pub fn refresh(&mut self, project_root: &Path) -> Result<()> {
+        let mtime = std::fs::metadata(allocation_model_path(project_root))
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if self.loaded_mtime == Some(mtime) {
+            return Ok(());
+        }
+        self.loaded_mtime = Some(mtime);
+        self.model = None;
+        self.model = load_allocation_model(project_root)?;
+        Ok(())
+    }
+
+    pub fn model(&self) -> Option<&AllocationModel> {
+        self.model.as_ref()
+    }
+}
+
+pub fn save_allocation_model(project_root: &Path, model: &AllocationModel) -> Result<()> {
+    let path = allocation_model_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    super::board_store::write_atomic(&path, &serde_json::to_string_pretty(model)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[derive(Debug, Default)]
+struct EngineerHistory {
+    assigned: u32,
+    completed: u32,
+    merged: u32,
+    active: u32,
+    conflicts: u32,
+    tags: HashSet<String>,
+    dirs: HashSet<String>,
+}
+
+#[derive(Debug)]
+struct OpenAssignment {
+    engineer: String,
+    assigned_at: i64,
+    features: AllocationFeatures,
+    reworked: bool,
+}
+
+/// Replay assignment and merge events into training samples.
+///
+/// A sample is emitted when an assignment merges (first pass unless it was
+/// reworked or escalated on the way) or when the task is reassigned to a
+/// different engineer (a failure with no cycle time). Assignments that are
+/// still open produce nothing.
+pub fn collect_training_samples(
+    conn: &Connection,
+    tasks: &HashMap<u32, Task>,
+) -> Result<Vec<TrainingSample>> {
+    let mut stmt = conn.prepare(
+        "SELECT timestamp, event_type, role, task_id, json_extract(payload, '$.reason')
+         FROM events
+         WHERE task_id IS NOT NULL
+           AND event_type IN ('task_assigned', 'task_completed', 'task_reworked',
+                              'task_escalated', 'task_auto_merged', 'task_manual_merged')
+         ORDER BY timestamp, id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut history: HashMap<String, EngineerHistory> = HashMap::new();
+    let mut open: HashMap<u32, OpenAssignment> = HashMap::new();
+    let mut samples = Vec::new();
+
+    for (ts, event_type, role, task_id, reason) in rows {
+        let Ok(task_id) = task_id.trim_start_matches('#').parse::<u32>() else {
+            continue;
+        };
+        match event_type.as_str() {
+            "task_assigned" => {
+                let Some(engineer) = role else {
+                    continue;
+                };
+                if let Some(previous) = open.get(&task_id) {
+                    if previous.engineer == engineer {
+                        continue;
+                    }
+                    let previous = open.remove(&task_id).expect("checked above");
+                    let entry = history.entry(previous.engineer.clone()).or_default();
+                    entry.active = entry.active.saturating_sub(1);
+                    samples.push(TrainingSample {
+                        engineer: previous.engineer,
+                        task_id,
+                        features: previous.features,
+                        first_pass: false,
+                        cycle_secs: None,
+                    });
+                }
+                let entry = history.entry(engineer.clone()).or_default();
+                let features = assignment_features(entry, tasks.get(&task_id));
+                entry.assigned += 1;
+                entry.active += 1;
+                open.insert(
+                    task_id,
+                    OpenAssignment {
+                        engineer,
+                        assigned_at: ts,
+                        features,
+                        reworked: false,
+                    },
+                );
+            }
+            "task_completed" => {
+                if let Some(assignment) = open.get(&task_id) {
+                    history
+                        .entry(assignment.engineer.clone())
+                        .or_default()
+                        .completed += 1;
+                }
+            }
+            "task_reworked" | "task_escalated" => {
+                if let Some(assignment) = open.get_mut(&task_id) {
+                    assignment.reworked = true;
+                    if reason
+                        .as_deref()
+                        .is_some_and(|reason| reason.contains("conflict"))
+                    {
+                        history
+                            .entry(assignment.engineer.clone())
+                            .or_default()
+                            .conflicts += 1;
+                    }
+                }
+            }
+            _ => {
+                let Some(assignment) = open.remove(&task_id) else {
+                    continue;
+                };
+                let entry = history.entry(assignment.engineer.clone()).or_default();
+                entry.active = entry.active.saturating_sub(1);
+                entry.merged += 1;
+                if let Some(task) = tasks.get(&task_id) {
+                    entry.tags.extend(task.tags.iter().cloned());
+                    entry.dirs.extend(task_hint_directories(task));
+                    entry.dirs.extend(
+                        load_changed_paths(&task.source_path)
+                            .unwrap_or_default()
+                            .iter()
+                            .filter_map(|path| parent_dir(path)),
+                    );
+                }
+                samples.push(TrainingSample {
+                    engineer: assignment.engineer,
+                    task_id,
+                    features: assignment.features,
+                    first_pass: !assignment.reworked,
+                    cycle_secs: Some((ts - assignment.assigned_at).max(0) as u64),
+                });
+            }
+        }
+    }
+
+    Ok(samples)
+}
+
+fn assignment_features(history: &EngineerHistory, task: Option<&Task>) -> AllocationFeatures {
+    let (tag_matches, file_matches) = task
+        .map(|task| {
+            let tags = task
+                .tags
+                .iter()
+                .filter(|tag| history.tags.contains(*tag))
+                .count();
+            let dirs = task_hint_directories(task)
+                .intersection(&history.dirs)
+                .count();
+            (tags as f64, dirs as f64)
+        })
+        .unwrap_or_default();
+    AllocationFeatures {
+        tag_matches,
+        file_matches,
+        completion_rate: if history.assigned > 0 {
+            f64::from(history.completed) / f64::from(history.assigned)
+        } else {
+            0.0
+        },
+        active_tasks: f64::from(history.active),
+        recent_conflicts: f64::from(history.conflicts),
+        completions: f64::from(history.merged),
+    }
+}
+
+/// Fit the model. Fails when fewer than `min_samples` outcomes are available
+/// or when every sample has the same first-pass outcome.
+pub fn fit_allocation_model(
+    samples: &[TrainingSample],
+    min_samples: usize,
+) -> Result<AllocationModel> {
+    if samples.len() < min_samples.max(2) {
+        bail!(
+            "need at least {} completed assignments to train, found {}",
+            min_samples.max(2),
+            samples.len()
+        );
+    }
+    let successes = samples.iter().filter(|sample| sample.first_pass).count();
+    if successes == 0 || successes == samples.len() {
+        bail!(
+            "every one of the {} assignments has the same first-pass outcome; nothing to learn",
+            samples.len()
+        );
+    }
+
+    let inputs: Vec<Vec<f64>> = samples
+        .iter()
+        .map(|sample| {
+            sample
+                .features
+                .values()
+                .iter()
+                .enumerate()
+                .map(|(index, value)| model_input(index, *value))
+                .collect()
+        })
+        .collect();
+    let width = ALLOCATION_FEATURES.len();
+    let mut means = vec![0.0; width];
+    let mut stds = vec![0.0; width];
+    for index in 0..width {
+        let column: Vec<f64> = inputs.iter().map(|row| row[index]).collect();
+        let mean = column.iter().sum::<f64>() / column.len() as f64;
+        let variance = column
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / column.len() as f64;
+        means[index] = mean;
+        stds[index] = if variance > f64::EPSILON {
+            variance.sqrt()
+        } else {
+            1.0
+        };
+    }
+    let standardized: Vec<Vec<f64>> = inputs
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(index, value)| standardize(*value, means[index], stds[index]))
+                .collect()
+        })
+        .collect();
+
+    let success_targets: Vec<f64> = samples
+        .iter()
+        .map(|sample| if sample.first_pass { 1.0 } else { 0.0 })
+        .collect();
+    let (success_weights, success_bias) = gradient_descent(&standardized, &success_targets, true);
+
+    let (cycle_rows, cycle_targets): (Vec<Vec<f64>>, Vec<f64>) = samples
+        .iter()
+        .zip(&standardized)
+        .filter_map(|(sample, row)| {
+            sample
+                .cycle_secs
+                .map(|secs| (row.clone(), (secs as f64 / 60.0).ln_1p()))
+        })
+        .unzip();
+    let (cycle_weights, cycle_bias) = if cycle_rows.len() >= 2 {
+        gradient_descent(&cycle_rows, &cycle_targets, false)
+    } else {
+        (vec![0.0; width], 0.0)
+    };
+
+    let weights = success_weights
+        .iter()
+        .zip(&cycle_weights)
+        .map(|(success, cycle)| success - CYCLE_TIME_WEIGHT * cycle)
+        .collect();
+
+    Ok(AllocationModel {
+        version: MODEL_VERSION,
+        trained_at: Utc::now().to_rfc3339(),
+        samples: samples.len(),
+        first_pass_rate: successes as f64 / samples.len() as f64,
+        features: ALLOCATION_FEATURES
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        means,
+        stds,
+        success_weights,
+        success_bias,
+        cycle_weights,
+        cycle_bias,
+        weights,
+    })
+}
+
+/// Batch gradient descent with L2 regularization on the weights. Least
+/// squares and logistic regression share this gradient form; `logistic`
+/// only changes the link applied to the linear predictor.
+fn gradient_descent(rows: &[Vec<f64>], targets: &[f64], logistic: bool) -> (Vec<f64>, f64) {
+    let width = rows.first().map(Vec::len).unwrap_or_default();
+    let count = rows.len() as f64;
+    let mean = targets.iter().sum::<f64>() / count;
+    let mut weights = vec![0.0; width];
+    let mut bias = if logistic {
+        let rate = mean.clamp(1e-3, 1.0 - 1e-3);
+        (rate / (1.0 - rate)).ln()
+    } else {
+        mean
+    };
+    for _ in 0..TRAINING_ITERATIONS {
+        let mut weight_gradient = vec![0.0; width];
+        let mut bias_gradient = 0.0;
+        for (row, target) in rows.iter().zip(targets) {
+            let linear = bias + dot(&weights, row);
+            let prediction = if logistic { sigmoid(linear) } else { linear };
+            let error = prediction - target;
+            for (gradient, value) in weight_gradient.iter_mut().zip(row) {
+                *gradient += error * value;
+            }
+            bias_gradient += error;
+        }
+        for (weight, gradient) in weights.iter_mut().zip(&weight_gradient) {
+            *weight -= LEARNING_RATE * (gradient / count + L2_PENALTY * *weight);
+        }
+        bias -= LEARNING_RATE * bias_gradient / count;
+    }
+    (weights, bias)
+}
+
+/// Completion counts are heavy-tailed, so the model sees `ln(1 + n)`.
+fn model_input(index: usize, value: f64) -> f64 {
+    if ALLOCATION_FEATURES[index] == "completions" {
+        value.max(0.0).ln_1p()
+    } else {
+        value
+    }
+}
+
+fn standardize(value: f64, mean: f64, std: f64) -> f64 {
+    (value - mean) / std
+}
+
+fn sigmoid(value: f64) -> f64 {
+    1.0 / (1.0 + (-value).exp())
+}
+
+fn dot(left: &[f64], right: &[f64]) -> f64 {
+    left.iter().zip(right).map(|(a, b)| a * b).sum()
+}
+
+fn load_board_tasks(project_root: &Path) -> Result<HashMap<u32, Task>> {
+    let board_dir = super::team_config_dir(project_root).join("board");
+    let mut tasks = HashMap::new();
+    for dir in [board_dir.join("tasks"), board_dir.join("archive")] {
+        if dir.is_dir() {
+            for task in crate::task::load_tasks_from_dir(&dir)? {
+                tasks.insert(task.id, task);
+            }
+        }
+    }
+    Ok(tasks)
+}
+
+/// `batty dispatch train`: fit the model from telemetry and save it.
+pub fn run_train(project_root: &Path, min_samples: usize, json: bool) -> Result<()> {
+    let Some(conn) = super::telemetry_db::open_readonly(project_root)? else {
+        bail!("no telemetry database found; run the team to collect outcomes first");
+    };
+    let tasks = load_board_tasks(project_root)?;
+    let samples = collect_training_samples(&conn, &tasks)?;
+    let model = fit_allocation_model(&samples, min_samples)?;
+    save_allocation_model(project_root, &model)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&model)?);
+        return Ok(());
+    }
+    println!(
+        "Trained allocation model on {} assignments ({:.0}% first-pass).",
+        model.samples,
+        model.first_pass_rate * 100.0
+    );
+    println!("Saved to {}", allocation_model_path(project_root).display());
+    println!();
+    println!(
+        "{:<18} {:>10} {:>10} {:>10}",
+        "FEATURE", "FIRST PASS", "CYCLE", "COMBINED"
+    );
+    println!("{}", "-".repeat(51));
+    for (index, feature) in model.features.iter().enumerate() {
+        println!(
+            "{:<18} {:>+10.3} {:>+10.3} {:>+10.3}",
+            feature, model.success_weights[index], model.cycle_weights[index], model.weights[index]
+        );
+    }
+    println!();
+    println!("Set `workflow_policy.allocation.strategy: learned` in team.yaml to route with it.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::events::TeamEvent;
+    use crate::team::telemetry_db;
+
+    fn sample(tag_matches: f64, first_pass: bool, cycle_mins: u64) -> TrainingSample {
+        TrainingSample {
+            engineer: "eng-1".to_string(),
+            task_id: 1,
+            features: AllocationFeatures {
+                tag_matches,
+                completions: 2.0,
+                ..AllocationFeatures::default()
+            },
+            first_pass,
+            cycle_secs: Some(cycle_mins * 60),
+        }
+    }
+
+    fn event_at(mut event: TeamEvent, ts: u64) -> TeamEvent {
+        event.ts = ts;
+        event
+    }
+
+    #[test]
+    fn replay_labels_first_pass_rework_and_reassignment() {
+        let conn = telemetry_db::open_in_memory().unwrap();
+        for event in [
+            event_at(TeamEvent::task_assigned("eng-1", "1"), 100),
+            event_at(TeamEvent::task_completed("eng-1", Some("1")), 200),
+            event_at(TeamEvent::task_auto_merged("eng-1", "1", 0.9, 1, 10), 400),
+            event_at(TeamEvent::task_assigned("eng-1", "2"), 500),
+            event_at(
+                TeamEvent::task_escalated("eng-1", "2", Some("merge_conflict")),
+                600,
+            ),
+            event_at(TeamEvent::task_manual_merged("2"), 900),
+            event_at(TeamEvent::task_assigned("eng-2", "3"), 1_000),
+            event_at(TeamEvent::task_assigned("eng-1", "3"), 1_100),
+            event_at(TeamEvent::task_assigned("eng-1", "4"), 1_200),
+        ] {
+            telemetry_db::insert_event(&conn, &event).unwrap();
+        }
+
+        let samples = collect_training_samples(&conn, &HashMap::new()).unwrap();
+        let outcomes: Vec<(u32, &str, bool, Option<u64>)> = samples
+            .iter()
+            .map(|sample| {
+                (
+                    sample.task_id,
+                    sample.engineer.as_str(),
+                    sample.first_pass,
+                    sample.cycle_secs,
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (1, "eng-1", true, Some(300)),
+                (2, "eng-1", false, Some(400)),
+                (3, "eng-2", false, None),
+            ]
+        );
+        // The second assignment sees the first merge in the engineer's history.
+        assert_eq!(samples[1].features.completions, 1.0);
+        assert_eq!(samples[1].features.completion_rate, 1.0);
+    }
+
+    #[test]
+    fn fit_learns_that_tag_matches_predict_first_pass() {
+        let mut samples = Vec::new();
+        for _ in 0..15 {
+            samples.push(sample(2.0, true, 30));
+            samples.push(sample(0.0, false, 240));
+        }
+        samples.push(sample(2.0, false, 60));
+        samples.push(sample(0.0, true, 90));
+
+        let model = fit_allocation_model(&samples, 20).unwrap();
+        let tag_index = 0;
+        assert!(model.success_weights[tag_index] > 0.5);
+        assert!(model.cycle_weights[tag_index] < 0.0);
+        assert!(model.weights[tag_index] > model.success_weights[tag_index]);
+
+        let matched = AllocationFeatures {
+            tag_matches: 2.0,
+            completions: 2.0,
+            ..AllocationFeatures::default()
+        };
+        let unmatched = AllocationFeatures {
+            completions: 2.0,
+            ..AllocationFeatures::default()
+        };
+        assert!(model.first_pass_probability(&matched) > 0.8);
+        assert!(model.first_pass_probability(&unmatched) < 0.2);
+        let total = |features: &AllocationFeatures| -> f64 {
+            model
+                .contributions(features)
+                .iter()
+                .map(|term| term.contribution)
+                .sum()
+        };
+        assert!(total(&matched) > total(&unmatched));
+    }
+
+    #[test]
+    fn fit_refuses_thin_or_uniform_history() {
+        let few = vec![sample(1.0, true, 10), sample(0.0, false, 20)];
+        assert!(
+            fit_allocation_model(&few, 20)
+                .unwrap_err()
+                .to_string()
+                .contains("need at least 20")
+        );
+        let uniform: Vec<_> = (0..25).map(|_| sample(1.0, true, 10)).collect();
+        assert!(
+            fit_allocation_model(&uniform, 20)
+                .unwrap_err()
+                .to_string()
+                .contains("same first-pass outcome")
+        );
+    }
+
+    #[test]
+    fn saved_model_round_trips_and_rejects_stale_features() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load_allocation_model(tmp.path()).unwrap().is_none());
+
+        let samples: Vec<_> = (0..10)
+            .flat_map(|_| [sample(2.0, true, 30), sample(0.0, false, 120)])
+            .collect();
+        let model = fit_allocation_model(&samples, 20).unwrap();
+        save_allocation_model(tmp.path(), &model).unwrap();
+        let loaded = load_allocation_model(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded.samples, 20);
+        assert_eq!(loaded.features, model.features);
+        assert!((loaded.weights[0] - model.weights[0]).abs() < 1e-9);
+
+        let mut stale = model;
+        stale.features.pop();
+        save_allocation_model(tmp.path(), &stale).unwrap();
+        assert!(load_allocation_model(tmp.path()).is_err());
+    }
+
+    #[test]
+    fn model_cache_reloads_only_when_the_file_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = allocation_model_path(tmp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let set_mtime = |secs: u64| {
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+                .unwrap();
+        };
+        let mut cache = AllocationModelCache::default();
+        cache.refresh(tmp.path()).unwrap();
+        assert!(cache.model().is_none());
+
+        std::fs::write(&path, "{not json").unwrap();
+        set_mtime(1_000);
+        assert!(cache.refresh(tmp.path()).is_err());
+        // The failure is cached: no second error until the file changes.
+        assert!(cache.refresh(tmp.path()).is_ok());
+        assert!(cache.model().is_none());
+
+        let samples: Vec<_> = (0..10)
+            .flat_map(|_| [sample(2.0, true, 30), sample(0.0, false, 120)])
+            .collect();
+        save_allocation_model(tmp.path(), &fit_allocation_model(&samples, 20).unwrap()).unwrap();
+        set_mtime(2_000);
+        cache.refresh(tmp.path()).unwrap();
+        assert_eq!(cache.model().unwrap().samples, 20);
+    }
+}
@@ -0,0 +1,1345 @@
+//! Local HTTP/JSON control API served by the running daemon.
+//!
+//! The listener binds to a loopback address (or a Unix socket) and requires a
+//! bearer token on every request. Read-only endpoints are answered from the
+//! listener thread with the same loaders the CLI uses. Mutating endpoints are
+//! queued to the daemon and executed between poll-loop steps, so API clients
+//! never race the daemon on board, inbox, or worktree state.
+//! `GET /v1/events/stream` relays every `TeamEvent` the daemon emits as
+//! server-sent events.
+//!
+//! The same routes are always served on `.batty/console.sock`, which is what
+//! `batty console` attaches to; `api.enabled` only controls the extra
+//! listener configured in `team.yaml`.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use super::config::ApiConfig;
+use super::events::{self, TeamEvent};
+use super::{inbox, messaging, session, task_cmd, team_config_dir, team_events_path};
+
+/// Largest request body the API accepts.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// How long a mutating request waits for the daemon to pick it up.
+const COMMAND_REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+/// Keepalive comment interval for idle event streams.
+const STREAM_KEEPALIVE: Duration = Duration::from_secs(15);
+/// Default number of events returned by `GET /v1/events`.
+const DEFAULT_EVENT_LIMIT: usize = 100;
+
+/// Path of the generated bearer token used when `api.token_env` is unset.
+pub fn api_token_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join("api_token")
+}
+
+/// Unix socket the daemon always serves for `batty console`.
+pub fn console_socket_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join("console.sock")
+}
+
+/// A mutating operation that must run on the daemon thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ApiCommand {
+    Send {
+        from: String,
+        to: String,
+        message: String,
+    },
+    Assign {
+        from: String,
+        engineer: String,
+        task: String,
+    },
+    Transition {
+        task_id: u32,
+        status: String,
+    },
+    Review {
+        task_id: u32,
+        disposition: String,
+        feedback: Option<String>,
+        reviewer: String,
+    },
+    Merge {
+        engineer: String,
+    },
+    Pause,
+    Resume,
+    /// An operator command (`/status`, `$stop confirm`, ...) run through the
+    /// same grammar and audit log as the chat bridges.
+    Operator {
+        user: String,
+        /// Agent session (`BATTY_MEMBER`) the caller runs in, if any.
+        member: Option<String>,
+        text: String,
+    },
+}
+
+impl ApiCommand {
+    /// Short description used in the orchestrator log.
+    pub(crate) fn summary(&self) -> String {
+        match self {
+            Self::Send { from, to, .. } => format!("send {from} -> {to}"),
+            Self::Assign { engineer, .. } => format!("assign {engineer}"),
+            Self::Transition { task_id, status } => format!("transition #{task_id} -> {status}"),
+            Self::Review {
+                task_id,
+                disposition,
+                ..
+            } => format!("review #{task_id} {disposition}"),
+            Self::Merge { engineer } => format!("merge {engineer}"),
+            Self::Pause => "pause".to_string(),
+            Self::Resume => "resume".to_string(),
+            Self::Operator { text, .. } => format!("operator {text}"),
+        }
+    }
+}
+
+/// A queued command plus the channel the HTTP thread is waiting on.
+pub(crate) struct ApiRequest {
+    pub(crate) command: ApiCommand,
+    reply: mpsc::Sender<std::result::Result<Value, String>>,
+}
+
+impl ApiRequest {
+    pub(crate) fn respond(self, result: Result<Value>) {
+        let _ = self
+            .reply
+            .send(result.map_err(|error| format!("{error:#}")));
+    }
+}
+
+/// Execute a queued command against the project. Called from the daemon thread.
+pub(crate) fn execute_command(project_root: &Path, command: &ApiCommand) -> Result<Value> {
+    let board_dir = team_config_dir(project_root).join("board");
+    match command {
+        ApiCommand::Send { from, to, message } => {
+            messaging::send_message_as(project_root, Some(from), to, message)?;
+            Ok(json!({ "ok": true }))
+        }
+        ApiCommand::Assign {
+            from,
+            engineer,
+            task,
+        } => {
+            let id = messaging::assign_task_as(project_root, Some(from), engineer, task)?;
+            Ok(json!({ "ok": true, "id": id }))
+        }
+        ApiCommand::Transition { task_id, status } => {
+            task_cmd::transition_task_with_attribution(
+                &board_dir,
+                *task_id,
+                status,
+                task_cmd::StatusTransitionAttribution::bridge("api.task.transition"),
+            )?;
+            Ok(json!({ "ok": true, "task_id": task_id, "status": status.trim() }))
+        }
+        ApiCommand::Review {
+            task_id,
+            disposition,
+            feedback,
+            reviewer,
+        } => {
+            task_cmd::cmd_review_structured_with_attribution(
+                &board_dir,
+                *task_id,
+                disposition,
+                feedback.as_deref(),
+                reviewer,
+                task_cmd::StatusTransitionAttribution::bridge("api.review"),
+            )?;
+            Ok(json!({ "ok": true, "task_id": task_id, "disposition": disposition }))
+        }
+        ApiCommand::Merge { engineer } => {
+            messaging::merge_worktree(project_root, engineer)?;
+            Ok(json!({ "ok": true, "engineer": engineer }))
+        }
+        ApiCommand::Pause => {
+            session::pause_team(project_root)?;
+            Ok(json!({ "ok": true, "paused": true }))
+        }
+        ApiCommand::Resume => {
+            session::resume_team(project_root)?;
+            Ok(json!({ "ok": true, "paused": false }))
+        }
+        ApiCommand::Operator { .. } => {
+            bail!("operator commands run on the daemon thread")
+        }
+    }
+}
+
+type Subscribers = Arc<Mutex<Vec<mpsc::Sender<String>>>>;
+
+struct ServerShared {
+    project_root: PathBuf,
+    token: String,
+    requests: mpsc::Sender<ApiRequest>,
+    subscribers: Subscribers,
+}
+
+/// Daemon-side handle for a running API listener.
+pub(crate) struct ApiServer {
+    requests: mpsc::Receiver<ApiRequest>,
+    subscribers: Subscribers,
+    endpoints: Vec<String>,
+}
+
+impl ApiServer {
+    /// Resolve the token, bind the console socket (plus the configured
+    /// listener when `api.enabled` is set), and start accepting connections
+    /// on background threads.
+    pub(crate) fn start(project_root: &Path, config: &ApiConfig) -> Result<Self> {
+        let token = resolve_token(project_root, config)?;
+        let (tx, rx) = mpsc::channel();
+        let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+        let shared = Arc::new(ServerShared {
+            project_root: project_root.to_path_buf(),
+            token,
+            requests: tx,
+            subscribers: Arc::clone(&subscribers),
+        });
+
+        let mut endpoints = Vec::new();
+        if config.enabled {
+            endpoints.push(match config.socket.as_deref() {
+                Some(socket) => {
+                    start_unix_listener(&project_root.join(socket), Arc::clone(&shared))?
+                }
+                None => start_tcp_listener(&config.listen, Arc::clone(&shared))?,
+            });
+        }
+        let console_socket = console_socket_path(project_root);
+        let configured_socket = config
+            .socket
+            .as_deref()
+            .filter(|_| config.enabled)
+            .map(|socket| project_root.join(socket));
+        if configured_socket.as_deref() != Some(console_socket.as_path()) {
+            match start_unix_listener(&console_socket, shared) {
+                Ok(endpoint) => endpoints.push(endpoint),
+                // The configured listener is still useful on its own.
+                Err(error) if !endpoints.is_empty() => {
+                    warn!(error = %error, "failed to bind console socket");
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        for endpoint in &endpoints {
+            info!(endpoint = %endpoint, "control API listening");
+        }
+        Ok(Self {
+            requests: rx,
+            subscribers,
+            endpoints,
+        })
+    }
+
+    /// Bound endpoints: the configured listener first (when enabled), then
+    /// the console socket.
+    pub(crate) fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Next queued mutating request, if any.
+    pub(crate) fn try_next_request(&self) -> Option<ApiRequest> {
+        self.requests.try_recv().ok()
+    }
+
+    /// Fan an event out to every connected `/v1/events/stream` client.
+    pub(crate) fn broadcast(&self, event: &TeamEvent) {
+        broadcast_event(&self.subscribers, event);
+    }
+}
+
+fn broadcast_event(subscribers: &Subscribers, event: &TeamEvent) {
+    let Ok(mut subscribers) = subscribers.lock() else {
+        return;
+    };
+    if subscribers.is_empty() {
+        return;
+    }
+    let Ok(data) = serde_json::to_string(event) else {
+        return;
+    };
+    let frame = format!("event: {}\ndata: {data}\n\n", event.event);
+    subscribers.retain(|subscriber| subscriber.send(frame.clone()).is_ok());
+}
+
+fn start_tcp_listener(listen: &str, shared: Arc<ServerShared>) -> Result<String> {
+    let addr: std::net::SocketAddr = listen
+        .parse()
+        .with_context(|| format!("invalid api.listen address '{listen}'"))?;
+    if !addr.ip().is_loopback() {
+        bail!("api.listen '{addr}' is not a loopback address");
+    }
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("failed to bind control API on {addr}"))?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    std::thread::Builder::new()
+        .name("batty-api".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+                        spawn_connection(stream, Arc::clone(&shared));
+                    }
+                    Err(error) => warn!(error = %error, "control API accept failed"),
+                }
+            }
+        })
+        .context("failed to spawn control API thread")?;
+    Ok(endpoint)
+}
+
+#[cfg(unix)]
+fn start_unix_listener(path: &Path, shared: Arc<ServerShared>) -> Result<String> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::UnixListener;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    if path.exists() {
+        // A previous daemon left its socket behind; nothing else can own it
+        // because only one daemon runs per project.
+        std::fs::remove_file(path)
+            .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    }
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("failed to bind control API socket {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("failed to restrict {}", path.display()))?;
+    let endpoint = format!("unix:{}", path.display());
+    std::thread::Builder::new()
+        .name("batty-api".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+                        spawn_connection(stream, Arc::clone(&shared));
+                    }
+                    Err(error) => warn!(error = %error, "control API accept failed"),
+                }
+            }
+        })
+        .context("failed to spawn control API thread")?;
+    Ok(endpoint)
+}
+
+#[cfg(not(unix))]
+fn start_unix_listener(_path: &Path, _shared: Arc<ServerShared>) -> Result<String> {
+    bail!("Unix sockets are only supported on Unix platforms")
+}
+
+fn spawn_connection<S>(stream: S, shared: Arc<ServerShared>)
+where
+    S: Read + Write + Send + 'static,
+{
+    let spawned = std::thread::Builder::new()
+        .name("batty-api-conn".to_string())
+        .spawn(move || {
+            if let Err(error) = handle_connection(stream, &shared) {
+                warn!(error = %error, "control API request failed");
+            }
+        });
+    if let Err(error) = spawned {
+        warn!(error = %error, "failed to spawn control API connection thread");
+    }
+}
+
+/// The token from `api.token_env`, else the one persisted in `.batty/api_token`.
+fn existing_token(project_root: &Path, config: &ApiConfig) -> Option<String> {
+    if let Ok(token) = std::env::var(&config.token_env) {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    let existing = std::fs::read_to_string(api_token_path(project_root)).ok()?;
+    let existing = existing.trim().to_string();
+    (!existing.is_empty()).then_some(existing)
+}
+
+fn resolve_token(project_root: &Path, config: &ApiConfig) -> Result<String> {
+    if let Some(token) = existing_token(project_root, config) {
+        return Ok(token);
+    }
+
+    let path = api_token_path(project_root);
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&path, format!("{token}\n"))
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("failed to restrict {}", path.display()))?;
+    }
+    Ok(token)
+}
+
+/// Blocking client for the daemon's console socket, one connection per
+/// request.
+#[cfg(unix)]
+pub(crate) struct ApiClient {
+    socket: PathBuf,
+    token: String,
+}
+
+#[cfg(unix)]
+impl ApiClient {
+    /// Connect to the console socket of the daemon running in `project_root`.
+    pub(crate) fn for_project(project_root: &Path, config: &ApiConfig) -> Result<Self> {
+        let socket = console_socket_path(project_root);
+        if !socket.exists() {
+            bail!(
+                "no daemon console socket at {}; start the team with `batty start`",
+                socket.display()
+            );
+        }
+        let Some(token) = existing_token(project_root, config) else {
+            bail!(
+                "no API token found in ${} or {}",
+                config.token_env,
+                api_token_path(project_root).display()
+            );
+        };
+        Ok(Self { socket, token })
+    }
+
+    pub(crate) fn get(&self, path: &str) -> Result<Value> {
+        self.request("GET", path, None)
+    }
+
+    pub(crate) fn post(&self, path: &str, body: &Value) -> Result<Value> {
+        self.request("POST", path, Some(body))
+    }
+
+    fn request(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+        let mut stream = std::os::unix::net::UnixStream::connect(&self.socket)
+            .with_context(|| format!("failed to connect to {}", self.socket.display()))?;
+        stream.set_read_timeout(Some(COMMAND_REPLY_TIMEOUT + Duration::from_secs(5)))?;
+        let body = body
+            .map(serde_json::to_string)
+            .transpose()?
+            .unwrap_or_default();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: batty\r\nAuthorization: Bearer {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.token,
+            body.len()
+        )?;
+        stream.flush()?;
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .with_context(|| format!("failed to read response to {method} {path}"))?;
+        parse_response(&response).with_context(|| format!("{method} {path} failed"))
+    }
+}
+
+#[cfg(unix)]
+/// Split a `Connection: close` response into status and JSON body, turning
+/// non-200 replies into errors carrying the server's message.
+fn parse_response(response: &str) -> Result<Value> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("malformed HTTP response")?;
+    let status: u16 = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .context("malformed HTTP status line")?;
+    let value: Value = serde_json::from_str(body).context("invalid JSON response")?;
+    if status != 200 {
+        let message = value
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("request failed");
+        bail!("{message} (HTTP {status})");
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Default)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn bearer_token(&self) -> Option<&str> {
+        self.headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+    }
+
+    fn query_flag(&self, name: &str) -> bool {
+        self.query
+            .get(name)
+            .is_some_and(|value| matches!(value.as_str(), "" | "1" | "true" | "yes"))
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Result<HttpRequest> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        bail!("malformed request line");
+    };
+
+    let mut request = HttpRequest {
+        method: method.to_ascii_uppercase(),
+        ..HttpRequest::default()
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    request.path = path.trim_end_matches('/').to_string();
+    if request.path.is_empty() {
+        request.path = "/".to_string();
+    }
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        request
+            .query
+            .insert(percent_decode(key), percent_decode(value));
+    }
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            request
+                .headers
+                .insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = request
+        .headers
+        .get("content-length")
+        .map(|value| value.parse::<usize>())
+        .transpose()
+        .context("invalid Content-Length")?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        bail!("request body exceeds {MAX_BODY_BYTES} bytes");
+    }
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[derive(Debug)]
+enum Response {
+    Json(u16, Value),
+    EventStream,
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::Json(status, json!({ "error": message.into() }))
+    }
+}
+
+fn handle_connection<S: Read + Write>(mut stream: S, shared: &ServerShared) -> Result<()> {
+    let request = {
+        let mut reader = BufReader::new(&mut stream);
+        read_request(&mut reader)
+    };
+    let response = match request {
+        Ok(request) => route(&request, shared),
+        Err(error) => Response::error(400, format!("{error:#}")),
+    };
+
+    match response {
+        Response::Json(status, body) => write_json(&mut stream, status, &body),
+        Response::EventStream => stream_events(&mut stream, &shared.subscribers),
+    }
+}
+
+fn write_json<W: Write>(stream: &mut W, status: u16, body: &Value) -> Result<()> {
+    let body = serde_json::to_string(body)?;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        status_reason(status),
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn status_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+fn stream_events<W: Write>(stream: &mut W, subscribers: &Subscribers) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    match subscribers.lock() {
+        Ok(mut subscribers) => subscribers.push(tx),
+        Err(_) => bail!("event subscriber registry is poisoned"),
+    }
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    stream.flush()?;
+    loop {
+        let frame = match rx.recv_timeout(STREAM_KEEPALIVE) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => ": keepalive\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+        // A write failure means the client went away; dropping `rx` lets the
+        // next broadcast prune this subscriber.
+        if stream.write_all(frame.as_bytes()).is_err() || stream.flush().is_err() {
+            return Ok(());
+        }
+    }
+}
+
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn route(request: &HttpRequest, shared: &ServerShared) -> Response {
+    if !request
+        .bearer_token()
+        .is_some_and(|token| tokens_match(token, &shared.token))
+    {
+        return Response::error(401, "missing or invalid bearer token");
+    }
+
+    let segments: Vec<&str> = request
+        .path
+        .trim_start_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let root = shared.project_root.as_path();
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "status"]) => session::team_status_json_report(
+            root,
+            request.query_flag("detail"),
+            request.query_flag("health"),
+        )
+        .and_then(|report| Ok(serde_json::to_value(report)?)),
+        ("GET", ["v1", "board"]) => board_snapshot(root, request.query.get("status")),
+        ("GET", ["v1", "queue"]) => Ok(json!({
+            "queue": super::daemon::load_dispatch_queue_snapshot(root)
+        })),
+        ("GET", ["v1", "inbox", member]) => inbox_snapshot(root, member, request),
+        ("GET", ["v1", "events"]) => recent_events(root, request),
+        ("GET", ["v1", "events", "stream"]) => return Response::EventStream,
+        ("POST", ["v1", ..]) => {
+            return match parse_command(&segments[1..], &request.body) {
+                Ok(Some(command)) => dispatch_command(command, &shared.requests),
+                Ok(None) => Response::error(404, format!("no route for POST {}", request.path)),
+                Err(error) => Response::error(400, format!("{error:#}")),
+            };
+        }
+        (_, ["v1", ..]) if known_route(&segments[1..]) => {
+            return Response::error(405, format!("{} not allowed here", request.method));
+        }
+        _ => {
+            return Response::error(
+                404,
+                format!("no route for {} {}", request.method, request.path),
+            );
+        }
+    };
+
+    match result {
+        Ok(body) => Response::Json(200, body),
+        Err(error) => Response::error(422, format!("{error:#}")),
+    }
+}
+
+fn known_route(segments: &[&str]) -> bool {
+    matches!(
+        segments,
+        ["status"
+            | "board"
+            | "queue"
+            | "events"
+            | "send"
+            | "assign"
+            | "merge"
+            | "pause"
+            | "resume"
+            | "operator"]
+            | ["inbox", _]
+            | ["events", "stream"]
+            | ["tasks", _, "transition" | "review"]
+    )
+}
+
+fn dispatch_command(command: ApiCommand, requests: &mpsc::Sender<ApiRequest>) -> Response {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    if requests
+        .send(ApiRequest {
+            command,
+            reply: reply_tx,
+        })
+        .is_err()
+    {
+        return Response::error(503, "daemon is not accepting commands");
+    }
+    match reply_rx.recv_timeout(COMMAND_REPLY_TIMEOUT) {
+        Ok(Ok(body)) => Response::Json(200, body),
+        Ok(Err(message)) => Response::error(422, message),
+        Err(RecvTimeoutError::Timeout) => {
+            Response::error(504, "daemon did not process the command in time")
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Response::error(503, "daemon dropped the command before running it")
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SendBody {
+    to: String,
+    message: String,
+    #[serde(default)]
+    from: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssignBody {
+    engineer: String,
+    task: String,
+    #[serde(default)]
+    from: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransitionBody {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewBody {
+    disposition: String,
+    #[serde(default)]
+    feedback: Option<String>,
+    #[serde(default)]
+    reviewer: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeBody {
+    engineer: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OperatorBody {
+    command: String,
+    #[serde(default)]
+    user: Option<String>,
+    #[serde(default)]
+    member: Option<String>,
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T> {
+    serde_json::from_slice(body).context("invalid JSON body")
+}
+
+fn parse_command(segments: &[&str], body: &[u8]) -> Result<Option<ApiCommand>> {
+    let command = match segments {
+        ["send"] => {
+            let body: SendBody = parse_body(body)?;
+            ApiCommand::Send {
+                from: body.from.unwrap_or_else(|| "human".to_string()),
+                to: body.to,
+                message: body.message,
+            }
+        }
+        ["assign"] => {
+            let body: AssignBody = parse_body(body)?;
+            ApiCommand::Assign {
+                from: body.from.unwrap_or_else(|| "human".to_string()),
+                engineer: body.engineer,
+                task: body.task,
+            }
+        }
+        ["tasks", id, "transition"] => {
+            let body: TransitionBody = parse_body(body)?;
+            ApiCommand::Transition {
+                task_id: parse_task_id(id)?,
+                status: body.status,
+            }
+        }
+        ["tasks", id, "review"] => {
+            let body: ReviewBody = parse_body(body)?;
+            if !matches!(
+                body.disposition.as_str(),
+                "approve" | "request-changes" | "reject"
+            ) {
+                bail!(
+                    "invalid disposition '{}'; expected approve, request-changes, or reject",
+                    body.disposition
+                );
+            }
+            ApiCommand::Review {
+                task_id: parse_task_id(id)?,
+                disposition: body.disposition,
+                feedback: body.feedback,
+                reviewer: body.reviewer.unwrap_or_else(|| "human".to_string()),
+            }
+        }
+        ["merge"] => {
+            let body: MergeBody = parse_body(body)?;
+            ApiCommand::Merge {
+                engineer: body.engineer,
+            }
+        }
+        ["pause"] => ApiCommand::Pause,
+        ["resume"] => ApiCommand::Resume,
+        ["operator"] => {
+            let body: OperatorBody = parse_body(body)?;
+            ApiCommand::Operator {
+                user: body.user.unwrap_or_else(|| "human".to_string()),
+                member: body.member.filter(|member| !member.trim().is_empty()),
+                text: body.command,
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(command))
+}
+
+fn parse_task_id(raw: &str) -> Result<u32> {
+    raw.trim_start_matches('#')
+        .parse()
+        .with_context(|| format!("invalid task id '{raw}'"))
+}
+
+#[derive(Debug, Serialize)]
+struct BoardTask {
+    id: u32,
+    title: String,
+    status: String,
+    priority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    claimed_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    review_owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocked: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocked_on: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_action: Option<String>,
+}
+
+impl From<crate::task::Task> for BoardTask {
+    fn from(task: crate::task::Task) -> Self {
+        Self {
+            id: task.id,
+            title: task.title,
+            status: task.status,
+            priority: task.priority,
+            assignee: task.assignee,
+            claimed_by: task.claimed_by,
+            review_owner: task.review_owner,
+            blocked: task.blocked,
+            blocked_on: task.blocked_on,
+            tags: task.tags,
+            depends_on: task.depends_on,
+            branch: task.branch,
+            next_action: task.next_action,
+        }
+    }
+}
+
+fn board_snapshot(project_root: &Path, status: Option<&String>) -> Result<Value> {
+    let tasks_dir = team_config_dir(project_root).join("board").join("tasks");
+    let mut tasks: Vec<BoardTask> = if tasks_dir.is_dir() {
+        crate::task::load_tasks_from_dir(&tasks_dir)?
+            .into_iter()
+            .filter(|task| status.is_none_or(|status| task.status == *status))
+            .map(BoardTask::from)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    tasks.sort_by_key(|task| task.id);
+    Ok(json!({ "tasks": tasks }))
+}
+
+fn inbox_snapshot(project_root: &Path, member: &str, request: &HttpRequest) -> Result<Value> {
+    let member = messaging::resolve_member_name(project_root, member)?;
+    let root = inbox::inboxes_root(project_root);
+    let pending_only = request.query_flag("pending");
+    let messages: Vec<Value> = inbox::all_messages(&root, &member)?
+        .into_iter()
+        .filter(|(_, delivered)| !(pending_only && *delivered))
+        .map(|(message, delivered)| {
+            let mut value = serde_json::to_value(message).unwrap_or(Value::Null);
+            if let Value::Object(map) = &mut value {
+                map.insert("delivered".to_string(), Value::Bool(delivered));
+            }
+            value
+        })
+        .collect();
+    Ok(json!({ "member": member, "messages": messages }))
+}
+
+fn recent_events(project_root: &Path, request: &HttpRequest) -> Result<Value> {
+    let limit = match request.query.get("limit") {
+        Some(raw) => raw
+            .parse::<usize>()
+            .with_context(|| format!("invalid limit '{raw}'"))?,
+        None => DEFAULT_EVENT_LIMIT,
+    };
+    let all = events::read_events(&team_events_path(project_root))?;
+    let skip = all.len().saturating_sub(limit);
+    let recent: Vec<TeamEvent> = all.into_iter().skip(skip).collect();
+    Ok(json!({ "events": recent }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn shared_for(root: &Path) -> (ServerShared, mpsc::Receiver<ApiRequest>) {
+        let (tx, rx) = mpsc::channel();
+        (
+            ServerShared {
+                project_root: root.to_path_buf(),
+                token: "secret".to_string(),
+                requests: tx,
+                subscribers: Arc::new(Mutex::new(Vec::new())),
+            },
+            rx,
+        )
+    }
+
+    fn request(method: &str, path: &str, body: &str) -> HttpRequest {
+        let raw = format!(
+            "{method} {path} HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        read_request(&mut Cursor::new(raw.into_bytes())).unwrap()
+    }
+
+    fn status_of(response: &Response) -> u16 {
+        match response {
+            Response::Json(status, _) => *status,
+            Response::EventStream => 200,
+        }
+    }
+
+    #[test]
+    fn read_request_parses_query_headers_and_body() {
+        let raw = "POST /v1/send/?to=eng%201&x HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n{}";
+        let request = read_request(&mut Cursor::new(raw.as_bytes().to_vec())).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/send");
+        assert_eq!(request.query.get("to").map(String::as_str), Some("eng 1"));
+        assert!(request.query_flag("x"));
+        assert_eq!(
+            request.headers.get("content-type").map(String::as_str),
+            Some("application/json")
+        );
+        assert_eq!(request.body, b"{}");
+    }
+
+    #[test]
+    fn read_request_rejects_oversized_body() {
+        let raw = format!(
+            "POST /v1/send HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert!(read_request(&mut Cursor::new(raw.into_bytes())).is_err());
+    }
+
+    #[test]
+    fn percent_decode_handles_escapes_and_trailing_percent() {
+        assert_eq!(percent_decode("a%2Fb+c"), "a/b c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn route_rejects_missing_or_wrong_token() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (shared, _rx) = shared_for(tmp.path());
+        let mut unauthenticated = request("GET", "/v1/queue", "");
+        unauthenticated.headers.remove("authorization");
+        assert_eq!(status_of(&route(&unauthenticated, &shared)), 401);
+
+        let mut wrong = request("GET", "/v1/queue", "");
+        wrong
+            .headers
+            .insert("authorization".to_string(), "Bearer nope".to_string());
+        assert_eq!(status_of(&route(&wrong, &shared)), 401);
+    }
+
+    #[test]
+    fn route_reports_unknown_paths_and_methods() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (shared, _rx) = shared_for(tmp.path());
+        assert_eq!(
+            status_of(&route(&request("GET", "/v2/x", ""), &shared)),
+            404
+        );
+        assert_eq!(
+            status_of(&route(&request("DELETE", "/v1/board", ""), &shared)),
+            405
+        );
+        assert_eq!(
+            status_of(&route(&request("POST", "/v1/nope", "{}"), &shared)),
+            404
+        );
+    }
+
+    #[test]
+    fn get_board_lists_tasks_filtered_by_status() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tasks_dir = team_config_dir(tmp.path()).join("board").join("tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+        std::fs::write(
+            tasks_dir.join("001-a.md"),
+            "---\nid: 1\ntitle: first\nstatus: todo\npriority: high\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tasks_dir.join("002-b.md"),
+            "---\nid: 2\ntitle: second\nstatus: done\npriority: low\n---\n",
+        )
+        .unwrap();
+        let (shared, _rx) = shared_for(tmp.path());
+
+        let Response::Json(200, body) = route(&request("GET", "/v1/board", ""), &shared) else {
+            panic!("expected board json");
+        };
+        assert_eq!(body["tasks"].as_array().unwrap().len(), 2);
+
+        let Response::Json(200, body) =
+            route(&request("GET", "/v1/board?status=todo", ""), &shared)
+        else {
+            panic!("expected filtered board json");
+        };
+        let tasks = body["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0]["title"], "first");
+    }
+
+    #[test]
+    fn get_events_returns_most_recent_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = team_events_path(tmp.path());
+        let mut sink = events::EventSink::new(&path).unwrap();
+        for name in ["eng-1", "eng-2", "eng-3"] {
+            sink.emit(TeamEvent::task_assigned(name, "work")).unwrap();
+        }
+        let (shared, _rx) = shared_for(tmp.path());
+
+        let Response::Json(200, body) = route(&request("GET", "/v1/events?limit=2", ""), &shared)
+        else {
+            panic!("expected events json");
+        };
+        let events = body["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["role"], "eng-3");
+    }
+
+    #[test]
+    fn post_commands_are_queued_for_the_daemon() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (shared, rx) = shared_for(tmp.path());
+        let worker = std::thread::spawn(move || {
+            let request = rx.recv().unwrap();
+            assert_eq!(
+                request.command,
+                ApiCommand::Transition {
+                    task_id: 7,
+                    status: "review".to_string(),
+                }
+            );
+            request.respond(Ok(json!({ "ok": true })));
+        });
+
+        let response = route(
+            &request("POST", "/v1/tasks/7/transition", r#"{"status":"review"}"#),
+            &shared,
+        );
+        worker.join().unwrap();
+        assert_eq!(status_of(&response), 200);
+    }
+
+    #[test]
+    fn post_command_errors_surface_as_unprocessable() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (shared, rx) = shared_for(tmp.path());
+        let worker = std::thread::spawn(move || {
+            rx.recv()
+                .unwrap()
+                .respond(Err(anyhow::anyhow!("Team is already paused.")));
+        });
+
+        let Response::Json(422, body) = route(&request("POST", "/v1/pause", ""), &shared) else {
+            panic!("expected 422");
+        };
+        worker.join().unwrap();
+        assert_eq!(body["error"], "Team is already paused.");
+    }
+
+    #[test]
+    fn parse_command_validates_bodies() {
+        assert_eq!(
+            parse_command(&["send"], br#"{"to":"manager","message":"hi"}"#).unwrap(),
+            Some(ApiCommand::Send {
+                from: "human".to_string(),
+                to: "manager".to_string(),
+                message: "hi".to_string(),
+            })
+        );
+        assert!(parse_command(&["send"], b"not json").is_err());
+        assert!(parse_command(&["tasks", "abc", "transition"], br#"{"status":"done"}"#).is_err());
+        assert!(parse_command(&["tasks", "3", "review"], br#"{"disposition":"maybe"}"#).is_err());
+        assert_eq!(
+            parse_command(&["pause"], b"").unwrap(),
+            Some(ApiCommand::Pause)
+        );
+        assert_eq!(
+            parse_command(&["operator"], br#"{"command":"/status"}"#).unwrap(),
+            Some(ApiCommand::Operator {
+                user: "human".to_string(),
+                member: None,
+                text: "/status".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_command(
+                &["operator"],
+                br#"{"command":"/stop confirm","user":"alice","member":"eng-1"}"#
+            )
+            .unwrap(),
+            Some(ApiCommand::Operator {
+                user: "alice".to_string(),
+                member: Some("eng-1".to_string()),
+                text: "/stop confirm".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn execute_command_pauses_and_resumes_team() {
+        let tmp = tempfile::tempdir().unwrap();
+        execute_command(tmp.path(), &ApiCommand::Pause).unwrap();
+        assert!(session::pause_marker_path(tmp.path()).exists());
+        assert!(execute_command(tmp.path(), &ApiCommand::Pause).is_err());
+        execute_command(tmp.path(), &ApiCommand::Resume).unwrap();
+        assert!(!session::pause_marker_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn broadcast_reaches_subscribers_and_prunes_closed_ones() {
+        let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+        let (live_tx, live_rx) = mpsc::channel();
+        let (dead_tx, dead_rx) = mpsc::channel::<String>();
+        drop(dead_rx);
+        subscribers.lock().unwrap().extend([live_tx, dead_tx]);
+
+        broadcast_event(&subscribers, &TeamEvent::task_assigned("eng-1", "work"));
+
+        let frame = live_rx.try_recv().unwrap();
+        assert!(frame.starts_with("event: task_assigned\ndata: {"));
+        assert!(frame.ends_with("\n\n"));
+        assert_eq!(subscribers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn resolve_token_persists_generated_token_when_env_unset() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = ApiConfig {
+            token_env: "BATTY_API_TOKEN_TEST_UNSET".to_string(),
+            ..ApiConfig::default()
+        };
+        let generated = resolve_token(tmp.path(), &config).unwrap();
+        assert_eq!(generated.len(), 64);
+        assert_eq!(resolve_token(tmp.path(), &config).unwrap(), generated);
+        let on_disk = std::fs::read_to_string(api_token_path(tmp.path())).unwrap();
+        assert_eq!(on_disk.trim(), generated);
+    }
+
+    #[test]
+    fn server_answers_over_tcp() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = ApiConfig {
+            enabled: true,
+            listen: "127.0.0.1:0".to_string(),
+            token_env: "BATTY_API_TOKEN_TEST_UNSET".to_string(),
+            ..ApiConfig::default()
+        };
+        let server = ApiServer::start(tmp.path(), &config).unwrap();
+        let token = std::fs::read_to_string(api_token_path(tmp.path())).unwrap();
+        let addr = server.endpoints()[0]
+            .trim_start_matches("http://")
+            .to_string();
+
+        let mut stream = std::net::TcpStream::connect(&addr).unwrap();
+        write!(
+            stream,
+            "GET /v1/queue HTTP/1.1\r\nAuthorization: Bearer {}\r\n\r\n",
+            token.trim()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with(r#"{"queue":[]}"#), "{response}");
+    }
+
+    #[test]
+    fn start_rejects_non_loopback_listen_address() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = ApiConfig {
+            enabled: true,
+            listen: "0.0.0.0:0".to_string(),
+            token_env: "BATTY_API_TOKEN_TEST_UNSET".to_string(),
+            ..ApiConfig::default()
+        };
+        assert!(ApiServer::start(tmp.path(), &config).is_err());
+    }
+
+    #[test]
+    fn console_socket_is_served_without_api_enabled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = ApiConfig {
+            token_env: "BATTY_API_TOKEN_TEST_UNSET".to_string(),
+            ..ApiConfig::default()
+        };
+        let server = ApiServer::start(tmp.path(), &config).unwrap();
+        assert_eq!(
+            server.endpoints(),
+            [format!(
+                "unix:{}",
+                console_socket_path(tmp.path()).display()
+            )]
+        );
+
+        let client = ApiClient::for_project(tmp.path(), &config).unwrap();
+        assert_eq!(client.get("/v1/queue").unwrap(), json!({ "queue": [] }));
+        let error = client.get("/v1/nope").unwrap_err();
+        assert!(format!("{error:#}").contains("HTTP 404"), "{error:#}");
+
+        let poster = std::thread::spawn(move || client.post("/v1/pause", &json!({})));
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let request = loop {
+            if let Some(request) = server.try_next_request() {
+                break request;
+            }
+            assert!(std::time::Instant::now() < deadline, "request never queued");
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(request.command, ApiCommand::Pause);
+        request.respond(Ok(json!({ "ok": true })));
+        assert_eq!(poster.join().unwrap().unwrap(), json!({ "ok": true }));
+    }
+
+    #[test]
+    fn api_client_requires_running_daemon() {
+        let tmp = tempfile::tempdir().unwrap();
+        let error = ApiClient::for_project(tmp.path(), &ApiConfig::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("batty start"), "{error}");
+    }
+
+    #[test]
+    fn parse_response_surfaces_server_errors() {
+        let ok = "HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\n{\"ok\":true}";
+        assert_eq!(parse_response(ok).unwrap(), json!({ "ok": true }));
+        let failed = "HTTP/1.1 422 Unprocessable Entity\r\n\r\n{\"error\":\"unknown task\"}";
+        assert_eq!(
+            parse_response(failed).unwrap_err().to_string(),
+            "unknown task (HTTP 422)"
+        );
+        assert!(parse_response("garbage").is_err());
+    }
+}
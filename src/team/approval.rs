@@ -0,0 +1,712 @@
+//! Human approval gates for sensitive actions, persisted in
+//! `.batty/approvals.json`.
+//!
+//! When `workflow_policy.approvals` lists an action, the code about to take
+//! it calls [`check_gate`] instead of acting. The first call records a
+//! pending approval and the action waits; the daemon delivers the request to
+//! the user role's channel, and a human grants or denies it from chat or with
+//! `batty approval`. Pending approvals expire after `expire_secs` and then
+//! count as denied. Each decision is consumed by the next gate check for the
+//! same action and subject, so a granted release covers one release.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::config::ApprovalPolicy;
+use crate::cli::ApprovalCommand;
+
+const APPROVALS_FILE: &str = "approvals.json";
+const APPROVALS_LOCK_FILE: &str = "approvals.lock";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalAction {
+    /// A merge that touches `auto_merge.sensitive_paths`.
+    SensitiveMerge,
+    /// A merge that changes a dependency manifest or lockfile.
+    DependencyChange,
+    /// `batty release`.
+    Release,
+    /// `batty scale engineers` beyond `scale_max_engineers`.
+    Scale,
+    /// Pruning a merged engineer branch.
+    DeleteBranch,
+}
+
+impl ApprovalAction {
+    pub const ALL: [Self; 5] = [
+        Self::SensitiveMerge,
+        Self::DependencyChange,
+        Self::Release,
+        Self::Scale,
+        Self::DeleteBranch,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SensitiveMerge => "sensitive_merge",
+            Self::DependencyChange => "dependency_change",
+            Self::Release => "release",
+            Self::Scale => "scale",
+            Self::DeleteBranch => "delete_branch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Denied,
+    Expired,
+}
+
+impl ApprovalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Denied => "denied",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Approval {
+    pub id: u32,
+    pub action: ApprovalAction,
+    /// What the action applies to, e.g. `task #42`, `v1.4.0`, a branch name.
+    pub subject: String,
+    pub detail: String,
+    pub requested_by: String,
+    pub requested_at: u64,
+    pub expires_at: u64,
+    pub status: ApprovalStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The daemon delivered the request to the user channel.
+    #[serde(default)]
+    pub notified: bool,
+    /// The daemon announced the decision.
+    #[serde(default)]
+    pub reported: bool,
+    /// A gate check acted on the decision.
+    #[serde(default)]
+    pub consumed: bool,
+    /// Branch head the request was made for. A decision only covers this
+    /// commit; a gate check at any other head asks again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    /// Files the change at `head` touches.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalState {
+    #[serde(default)]
+    pub next_id: u32,
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+}
+
+/// Result of a gate check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Gate {
+    /// Not gated, or a human approved it; go ahead.
+    Granted,
+    /// Waiting on approval `id`.
+    Pending(u32),
+    /// Denied or expired; the action must not run.
+    Denied(Box<Approval>),
+}
+
+impl ApprovalState {
+    pub fn get(&self, id: u32) -> Option<&Approval> {
+        self.approvals.iter().find(|approval| approval.id == id)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &Approval> {
+        self.approvals
+            .iter()
+            .filter(|approval| approval.status == ApprovalStatus::Pending)
+    }
+
+    /// Gate `action` on `subject`, recording a pending approval when there
+    /// is no open one.
+    pub fn check(
+        &mut self,
+        action: ApprovalAction,
+        subject: &str,
+        detail: &str,
+        requested_by: &str,
+        expire_secs: u64,
+        now: u64,
+    ) -> Gate {
+        self.check_at_head(
+            action,
+            subject,
+            detail,
+            requested_by,
+            None,
+            expire_secs,
+            now,
+        )
+    }
+
+    /// Like [`Self::check`], but bound to `head`: an open request for
+    /// another commit is superseded, so a grant never carries over to code
+    /// the human did not see.
+    #[allow(clippy::too_many_arguments)]
+    pub fn check_at_head(
+        &mut self,
+        action: ApprovalAction,
+        subject: &str,
+        detail: &str,
+        requested_by: &str,
+        head: Option<(&str, &[String])>,
+        expire_secs: u64,
+        now: u64,
+    ) -> Gate {
+        self.expire_due(now);
+        if let Some(approval) = self.approvals.iter_mut().rev().find(|approval| {
+            approval.action == action && approval.subject == subject && !approval.consumed
+        }) {
+            if let Some((commit, _)) = head
+                && approval.head.as_deref() != Some(commit)
+            {
+                approval.consumed = true;
+                if approval.status == ApprovalStatus::Pending {
+                    approval.status = ApprovalStatus::Expired;
+                    approval.decided_at = Some(now);
+                    approval.reason = Some(format!(
+                        "superseded: the branch moved to {}",
+                        short_commit(commit)
+                    ));
+                }
+                return self.request(
+                    action,
+                    subject,
+                    detail,
+                    requested_by,
+                    head,
+                    expire_secs,
+                    now,
+                );
+            }
+            return match approval.status {
+                ApprovalStatus::Pending => Gate::Pending(approval.id),
+                ApprovalStatus::Approved => {
+                    approval.consumed = true;
+                    Gate::Granted
+                }
+                ApprovalStatus::Denied | ApprovalStatus::Expired => {
+                    approval.consumed = true;
+                    Gate::Denied(Box::new(approval.clone()))
+                }
+            };
+        }
+
+        self.request(
+            action,
+            subject,
+            detail,
+            requested_by,
+            head,
+            expire_secs,
+            now,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn request(
+        &mut self,
+        action: ApprovalAction,
+        subject: &str,
+        detail: &str,
+        requested_by: &str,
+        head: Option<(&str, &[String])>,
+        expire_secs: u64,
+        now: u64,
+    ) -> Gate {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.approvals.push(Approval {
+            id,
+            action,
+            subject: subject.to_string(),
+            detail: detail.to_string(),
+            requested_by: requested_by.to_string(),
+            requested_at: now,
+            expires_at: now.saturating_add(expire_secs),
+            status: ApprovalStatus::Pending,
+            decided_by: None,
+            decided_at: None,
+            reason: None,
+            notified: false,
+            reported: false,
+            consumed: false,
+            head: head.map(|(commit, _)| commit.to_string()),
+            files: head.map(|(_, files)| files.to_vec()).unwrap_or_default(),
+        });
+        Gate::Pending(id)
+    }
+
+    /// Approve or deny pending approval `id`.
+    pub fn decide(
+        &mut self,
+        id: u32,
+        approve: bool,
+        decided_by: &str,
+        reason: Option<&str>,
+        now: u64,
+    ) -> Result<Approval> {
+        self.expire_due(now);
+        let Some(approval) = self.approvals.iter_mut().find(|approval| approval.id == id) else {
+            bail!("approval #{id} not found");
+        };
+        if approval.status != ApprovalStatus::Pending {
+            bail!("approval #{id} is already {}", approval.status.as_str());
+        }
+        approval.status = if approve {
+            ApprovalStatus::Approved
+        } else {
+            ApprovalStatus::Denied
+        };
+        approval.decided_by = Some(decided_by.to_string());
+        approval.decided_at = Some(now);
+        approval.reason = reason.map(str::to_string);
+        Ok(approval.clone())
+    }
+
+    /// Expire pending approvals past their deadline. Returns their ids.
+    pub fn expire_due(&mut self, now: u64) -> Vec<u32> {
+        let mut expired = Vec::new();
+        for approval in &mut self.approvals {
+            if approval.status == ApprovalStatus::Pending && approval.expires_at <= now {
+                approval.status = ApprovalStatus::Expired;
+                approval.decided_at = Some(now);
+                expired.push(approval.id);
+            }
+        }
+        expired
+    }
+}
+
+pub fn approvals_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(APPROVALS_FILE)
+}
+
+pub fn load_approvals(project_root: &Path) -> Result<ApprovalState> {
+    let path = approvals_path(project_root);
+    if !path.exists() {
+        return Ok(ApprovalState::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn save_approvals(project_root: &Path, state: &ApprovalState) -> Result<()> {
+    let path = approvals_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    super::board_store::write_atomic(&path, &serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Load, change and save the store while holding `.batty/approvals.lock`,
+/// so the daemon, the CLI and the chat bridges never overwrite each other's
+/// decisions. Saves only when `update` changed something.
+pub fn update_approvals<T>(
+    project_root: &Path,
+    update: impl FnOnce(&mut ApprovalState) -> Result<T>,
+) -> Result<T> {
+    let path = approvals_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let _lock =
+        super::board_store::BoardLock::acquire_file(&path.with_file_name(APPROVALS_LOCK_FILE))?;
+    let mut state = load_approvals(project_root)?;
+    let before = state.clone();
+    let result = update(&mut state)?;
+    if state != before {
+        save_approvals(project_root, &state)?;
+    }
+    Ok(result)
+}
+
+/// Gate `action` on `subject` under `policy`. Actions the policy does not
+/// list are granted without touching the store.
+pub fn check_gate(
+    project_root: &Path,
+    policy: &ApprovalPolicy,
+    action: ApprovalAction,
+    subject: &str,
+    detail: &str,
+    requested_by: &str,
+) -> Result<Gate> {
+    check_gate_at_head(
+        project_root,
+        policy,
+        action,
+        subject,
+        detail,
+        requested_by,
+        None,
+    )
+}
+
+/// [`check_gate`] bound to a commit and the files it changes; see
+/// [`ApprovalState::check_at_head`].
+#[allow(clippy::too_many_arguments)]
+pub fn check_gate_at_head(
+    project_root: &Path,
+    policy: &ApprovalPolicy,
+    action: ApprovalAction,
+    subject: &str,
+    detail: &str,
+    requested_by: &str,
+    head: Option<(&str, &[String])>,
+) -> Result<Gate> {
+    if !policy.requires(action) {
+        return Ok(Gate::Granted);
+    }
+    update_approvals(project_root, |state| {
+        Ok(state.check_at_head(
+            action,
+            subject,
+            detail,
+            requested_by,
+            head,
+            policy.expire_secs,
+            super::now_unix(),
+        ))
+    })
+}
+
+/// First 12 characters of a commit id, for messages.
+pub fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+/// Gate a CLI action: `Ok` when it may run, otherwise an error telling the
+/// operator what to do next.
+pub fn require_cli_approval(
+    project_root: &Path,
+    policy: &ApprovalPolicy,
+    action: ApprovalAction,
+    subject: &str,
+    detail: &str,
+) -> Result<()> {
+    let requested_by = std::env::var("BATTY_MEMBER").unwrap_or_else(|_| "cli".to_string());
+    match check_gate(project_root, policy, action, subject, detail, &requested_by)? {
+        Gate::Granted => Ok(()),
+        Gate::Pending(id) => bail!(
+            "{} of {subject} needs human approval (approval #{id}). \
+             Approve with `batty approval grant {id}` or from the user channel, then rerun.",
+            action.as_str()
+        ),
+        Gate::Denied(approval) => bail!("{}", denial_summary(&approval)),
+    }
+}
+
+/// Approve or deny `id` in the on-disk store.
+pub fn decide(
+    project_root: &Path,
+    id: u32,
+    approve: bool,
+    decided_by: &str,
+    reason: Option<&str>,
+) -> Result<Approval> {
+    update_approvals(project_root, |state| {
+        state.decide(id, approve, decided_by, reason, super::now_unix())
+    })
+}
+
+/// Who is deciding from `batty approval`: the OS login of the human at the
+/// terminal. Agent sessions carry `BATTY_MEMBER` and may not decide gates,
+/// including the ones they requested themselves.
+fn cli_decider() -> Result<String> {
+    if let Some(member) = std::env::var("BATTY_MEMBER")
+        .ok()
+        .filter(|member| !member.trim().is_empty())
+    {
+        bail!(
+            "approval decisions must come from a human; this shell belongs to agent '{member}' \
+             (BATTY_MEMBER is set)"
+        );
+    }
+    Ok(format!("cli:{}", super::os_user_name()))
+}
+
+/// Run a `batty approval` subcommand.
+pub fn run(project_root: &Path, command: ApprovalCommand) -> Result<()> {
+    match command {
+        ApprovalCommand::List { all } => {
+            let state = load_approvals(project_root)?;
+            if !all {
+                println!("{}", render_pending(&state));
+                return Ok(());
+            }
+            if state.approvals.is_empty() {
+                println!("No approvals recorded.");
+            }
+            for approval in &state.approvals {
+                println!(
+                    "#{} {:<8} {} {} — {}",
+                    approval.id,
+                    approval.status.as_str(),
+                    approval.action.as_str(),
+                    approval.subject,
+                    approval.detail
+                );
+            }
+        }
+        ApprovalCommand::Grant { id } => {
+            let approval = decide(project_root, id, true, &cli_decider()?, None)?;
+            println!(
+                "Approved #{id}: {} of {}.",
+                approval.action.as_str(),
+                approval.subject
+            );
+        }
+        ApprovalCommand::Deny { id, reason } => {
+            let decided_by = cli_decider()?;
+            let approval = decide(project_root, id, false, &decided_by, reason.as_deref())?;
+            println!("{}.", denial_summary(&approval));
+        }
+    }
+    Ok(())
+}
+
+/// One-line description of a denied or expired approval.
+pub fn denial_summary(approval: &Approval) -> String {
+    let mut summary = format!(
+        "{} of {} was {} (approval #{})",
+        approval.action.as_str(),
+        approval.subject,
+        approval.status.as_str(),
+        approval.id
+    );
+    if let Some(by) = approval.decided_by.as_deref() {
+        summary.push_str(&format!(" by {by}"));
+    }
+    if let Some(reason) = approval.reason.as_deref() {
+        summary.push_str(&format!(": {reason}"));
+    }
+    summary
+}
+
+/// Render the pending approvals for chat or the terminal.
+pub fn render_pending(state: &ApprovalState) -> String {
+    let lines: Vec<String> = state
+        .pending()
+        .map(|approval| {
+            format!(
+                "#{} {} {} — {} (requested by {})",
+                approval.id,
+                approval.action.as_str(),
+                approval.subject,
+                approval.detail,
+                approval.requested_by
+            )
+        })
+        .collect();
+    if lines.is_empty() {
+        "No pending approvals.".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_policy() -> ApprovalPolicy {
+        ApprovalPolicy {
+            enabled: true,
+            ..ApprovalPolicy::default()
+        }
+    }
+
+    #[test]
+    fn check_records_pending_then_consumes_the_grant() {
+        let mut state = ApprovalState::default();
+        let gate = state.check(ApprovalAction::Release, "v1.0.0", "tag", "cli", 60, 100);
+        assert_eq!(gate, Gate::Pending(1));
+        assert_eq!(
+            state.check(ApprovalAction::Release, "v1.0.0", "tag", "cli", 60, 110),
+            Gate::Pending(1)
+        );
+
+        state.decide(1, true, "human", None, 120).unwrap();
+        assert_eq!(
+            state.check(ApprovalAction::Release, "v1.0.0", "tag", "cli", 60, 130),
+            Gate::Granted
+        );
+        // The grant covered one release; the next one asks again.
+        assert_eq!(
+            state.check(ApprovalAction::Release, "v1.0.0", "tag", "cli", 60, 140),
+            Gate::Pending(2)
+        );
+    }
+
+    #[test]
+    fn denied_and_expired_approvals_block_once() {
+        let mut state = ApprovalState::default();
+        state.check(ApprovalAction::Scale, "engineers=12", "", "cli", 60, 0);
+        state
+            .decide(1, false, "human", Some("too costly"), 10)
+            .unwrap();
+        let Gate::Denied(approval) =
+            state.check(ApprovalAction::Scale, "engineers=12", "", "cli", 60, 20)
+        else {
+            panic!("expected denial");
+        };
+        assert_eq!(
+            denial_summary(&approval),
+            "scale of engineers=12 was denied (approval #1) by human: too costly"
+        );
+
+        state.check(ApprovalAction::Release, "v2", "", "cli", 60, 100);
+        assert_eq!(state.expire_due(200), vec![2]);
+        assert!(matches!(
+            state.check(ApprovalAction::Release, "v2", "", "cli", 60, 210),
+            Gate::Denied(approval) if approval.status == ApprovalStatus::Expired
+        ));
+    }
+
+    #[test]
+    fn decide_rejects_unknown_and_already_decided_approvals() {
+        let mut state = ApprovalState::default();
+        assert!(state.decide(7, true, "human", None, 0).is_err());
+        state.check(ApprovalAction::DeleteBranch, "eng-1/4", "", "daemon", 60, 0);
+        state.decide(1, true, "human", None, 1).unwrap();
+        let error = state.decide(1, false, "human", None, 2).unwrap_err();
+        assert_eq!(error.to_string(), "approval #1 is already approved");
+    }
+
+    #[test]
+    fn decisions_made_during_a_locked_update_are_not_lost() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        let now = crate::team::now_unix();
+        let mut state = ApprovalState::default();
+        state.check(ApprovalAction::Release, "v1", "", "cli", 600, now);
+        state.check(ApprovalAction::Release, "v2", "", "cli", 600, now);
+        save_approvals(&root, &state).unwrap();
+
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let daemon = std::thread::spawn({
+            let root = root.clone();
+            move || {
+                update_approvals(&root, |state| {
+                    started_tx.send(()).unwrap();
+                    std::thread::sleep(std::time::Duration::from_millis(200));
+                    state.approvals[0].notified = true;
+                    Ok(())
+                })
+                .unwrap();
+            }
+        });
+        started_rx.recv().unwrap();
+        decide(&root, 2, true, "human", None).unwrap();
+        daemon.join().unwrap();
+
+        let state = load_approvals(&root).unwrap();
+        assert!(state.get(1).unwrap().notified);
+        assert_eq!(state.get(2).unwrap().status, ApprovalStatus::Approved);
+    }
+
+    #[test]
+    fn check_gate_persists_and_skips_unlisted_actions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut policy = enabled_policy();
+        policy.actions = vec![ApprovalAction::Release];
+
+        let gate = check_gate(
+            tmp.path(),
+            &policy,
+            ApprovalAction::Scale,
+            "engineers=20",
+            "",
+            "cli",
+        )
+        .unwrap();
+        assert_eq!(gate, Gate::Granted);
+        assert!(!approvals_path(tmp.path()).exists());
+
+        let error = require_cli_approval(
+            tmp.path(),
+            &policy,
+            ApprovalAction::Release,
+            "v1.2.0",
+            "tag v1.2.0",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("approval #1"));
+        decide(tmp.path(), 1, true, "human", None).unwrap();
+        require_cli_approval(
+            tmp.path(),
+            &policy,
+            ApprovalAction::Release,
+            "v1.2.0",
+            "tag v1.2.0",
+        )
+        .unwrap();
+        assert!(
+            render_pending(&load_approvals(tmp.path()).unwrap()).contains("No pending approvals")
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn agent_sessions_cannot_decide_and_humans_are_recorded_by_login() {
+        let tmp = tempfile::tempdir().unwrap();
+        let now = crate::team::now_unix();
+        update_approvals(tmp.path(), |state| {
+            state.check(ApprovalAction::Release, "v1", "", "eng-1", 600, now);
+            state.check(ApprovalAction::Release, "v2", "", "eng-1", 600, now);
+            Ok(())
+        })
+        .unwrap();
+
+        {
+            let _member = crate::team::test_support::EnvVarGuard::set("BATTY_MEMBER", "eng-1");
+            let error = run(tmp.path(), ApprovalCommand::Grant { id: 1 }).unwrap_err();
+            assert!(error.to_string().contains("agent 'eng-1'"));
+            let error = run(
+                tmp.path(),
+                ApprovalCommand::Deny {
+                    id: 2,
+                    reason: None,
+                },
+            )
+            .unwrap_err();
+            assert!(error.to_string().contains("agent 'eng-1'"));
+            assert_eq!(load_approvals(tmp.path()).unwrap().pending().count(), 2);
+        }
+
+        let _member = crate::team::test_support::EnvVarGuard::unset("BATTY_MEMBER");
+        let _user = crate::team::test_support::EnvVarGuard::set("USER", "spoofed");
+        run(tmp.path(), ApprovalCommand::Grant { id: 1 }).unwrap();
+        let state = load_approvals(tmp.path()).unwrap();
+        let decided_by = state.get(1).unwrap().decided_by.clone().unwrap();
+        assert_eq!(decided_by, format!("cli:{}", crate::team::os_user_name()));
+        assert_ne!(decided_by, "cli:spoofed");
+    }
+}
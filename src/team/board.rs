@@ -11,6 +11,7 @@ use tracing::info;
 
 use super::errors::BoardError;
 use super::test_results::TestResults;
+use super::verification::AcceptanceResult;
 use crate::task::{
     Task, load_tasks_from_dir, parse_frontmatter_timestamp as parse_task_frontmatter_timestamp,
     parse_frontmatter_timestamp_compat,
@@ -26,6 +27,7 @@ pub(crate) struct WorkflowMetadata {
     pub tests_run: Option<bool>,
     pub tests_passed: Option<bool>,
     pub test_results: Option<TestResults>,
+    pub acceptance_results: Vec<AcceptanceResult>,
     pub artifacts: Vec<String>,
     pub outcome: Option<String>,
     pub review_blockers: Vec<String>,
@@ -48,6 +50,8 @@ struct WorkflowFrontmatter {
     #[serde(default)]
     test_results: Option<TestResults>,
     #[serde(default)]
+    acceptance_results: Vec<AcceptanceResult>,
+    #[serde(default)]
     artifacts: Vec<String>,
     #[serde(default)]
     outcome: Option<String>,
@@ -108,6 +112,7 @@ impl From<WorkflowFrontmatter> for WorkflowMetadata {
             tests_run: frontmatter.tests_run,
             tests_passed: frontmatter.tests_passed,
             test_results: frontmatter.test_results,
+            acceptance_results: frontmatter.acceptance_results,
             artifacts: frontmatter.artifacts,
             outcome: frontmatter.outcome,
             review_blockers: frontmatter.review_blockers,
@@ -201,44 +206,24 @@ pub(crate) fn compute_task_aging_at(
 }
 
 pub(crate) fn write_workflow_metadata(task_path: &Path, metadata: &WorkflowMetadata) -> Result<()> {
-    let content = std::fs::read_to_string(task_path)
-        .with_context(|| format!("failed to read {}", task_path.display()))?;
-    let (frontmatter, body) = split_task_frontmatter(&content)?;
-    let mut mapping: Mapping =
-        serde_yaml::from_str(frontmatter).context("failed to parse task frontmatter")?;
-
-    set_optional_string(&mut mapping, "branch", metadata.branch.as_deref());
-    set_optional_string(
-        &mut mapping,
-        "worktree_path",
-        metadata.worktree_path.as_deref(),
-    );
-    set_optional_string(&mut mapping, "commit", metadata.commit.as_deref());
-    set_string_list(&mut mapping, "changed_paths", &metadata.changed_paths);
-    set_optional_bool(&mut mapping, "tests_run", metadata.tests_run);
-    set_optional_bool(&mut mapping, "tests_passed", metadata.tests_passed);
-    set_optional_value(&mut mapping, "test_results", metadata.test_results.as_ref())?;
-    set_string_list(&mut mapping, "artifacts", &metadata.artifacts);
-    set_optional_string(&mut mapping, "outcome", metadata.outcome.as_deref());
-    set_string_list(&mut mapping, "review_blockers", &metadata.review_blockers);
-
-    let mut rendered =
-        serde_yaml::to_string(&mapping).context("failed to serialize task frontmatter")?;
-    if let Some(stripped) = rendered.strip_prefix("---\n") {
-        rendered = stripped.to_string();
-    }
-
-    let mut updated = String::from("---\n");
-    updated.push_str(&rendered);
-    if !updated.ends_with('\n') {
-        updated.push('\n');
-    }
-    updated.push_str("---\n");
-    updated.push_str(body);
-
-    std::fs::write(task_path, updated)
-        .with_context(|| format!("failed to write {}", task_path.display()))?;
-    Ok(())
+    super::task_cmd::try_update_task_frontmatter(task_path, |mapping| {
+        set_optional_string(mapping, "branch", metadata.branch.as_deref());
+        set_optional_string(mapping, "worktree_path", metadata.worktree_path.as_deref());
+        set_optional_string(mapping, "commit", metadata.commit.as_deref());
+        set_string_list(mapping, "changed_paths", &metadata.changed_paths);
+        set_optional_bool(mapping, "tests_run", metadata.tests_run);
+        set_optional_bool(mapping, "tests_passed", metadata.tests_passed);
+        set_optional_value(mapping, "test_results", metadata.test_results.as_ref())?;
+        set_optional_value(
+            mapping,
+            "acceptance_results",
+            Some(&metadata.acceptance_results).filter(|results| !results.is_empty()),
+        )?;
+        set_string_list(mapping, "artifacts", &metadata.artifacts);
+        set_optional_string(mapping, "outcome", metadata.outcome.as_deref());
+        set_string_list(mapping, "review_blockers", &metadata.review_blockers);
+        Ok(())
+    })
 }
 
 /// Lifecycle timestamps stored in task frontmatter.
@@ -998,6 +983,11 @@ mod tests {
                 }],
                 summary: Some("test result: FAILED. 2 passed; 1 failed; 0 ignored;".to_string()),
             }),
+            acceptance_results: vec![AcceptanceResult {
+                criterion: "file exists `docs/workflow.md`".to_string(),
+                passed: true,
+                detail: "present".to_string(),
+            }],
             artifacts: vec!["docs/workflow.md".to_string()],
             outcome: Some("ready_for_review".to_string()),
             review_blockers: vec!["missing screenshots".to_string()],
@@ -1011,6 +1001,7 @@ mod tests {
         assert!(content.contains("tests_run: true"));
         assert!(content.contains("tests_passed: true"));
         assert!(content.contains("test_results:"));
+        assert!(content.contains("acceptance_results:"));
         assert!(content.contains("review_blockers:"));
         assert!(content.contains("Task body."));
         assert_eq!(read_workflow_metadata(&task).unwrap(), metadata);
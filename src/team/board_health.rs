@@ -273,6 +273,8 @@ mod tests {
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("/tmp/fake.md"),
         }
     }
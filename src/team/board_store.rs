@@ -0,0 +1,1072 @@
+//! Native board store.
+//!
+//! Reads and writes the same on-disk layout kanban-md uses (`config.yml`
+//! plus one `tasks/NNN-slug.md` file per task with YAML frontmatter), so a
+//! board stays usable from the external CLI and TUI. Every mutation runs
+//! under an exclusive `flock` on `<board>/.board.lock` and replaces files
+//! with an atomic rename, which keeps the daemon, the CLI, and the chat
+//! bridges from handing out the same task id or the same claim twice.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde_yaml::{Mapping, Value};
+
+use super::errors::BoardError;
+use super::task_cmd::{
+    StatusTransitionAttribution, record_status_transition_activity, rewrite_task_frontmatter,
+    set_optional_string, yaml_key,
+};
+use crate::task::{Task, find_task_path_by_id, load_tasks_from_dir};
+
+const LOCK_FILE: &str = ".board.lock";
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+const CONFIG_FILE: &str = "config.yml";
+const CONFIG_VERSION: u64 = 10;
+const DEFAULT_STATUS: &str = "backlog";
+const DEFAULT_PRIORITY: &str = "medium";
+const MAX_SLUG_LEN: usize = 50;
+
+pub(crate) const STATUSES: &[&str] = &[
+    "backlog",
+    "todo",
+    "in-progress",
+    "review",
+    "blocked",
+    "done",
+    "archived",
+];
+const PRIORITIES: &[&str] = &["low", "medium", "high", "critical"];
+
+/// Statuses a task falls back to when work is rolled back. A claim never
+/// survives a move into one of these.
+const UNCLAIMED_STATUSES: &[&str] = &["backlog", "todo"];
+
+/// Frontmatter fields that describe an engineer's claim on a task.
+const CLAIM_FIELDS: &[&str] = &[
+    "claimed_by",
+    "claimed_at",
+    "claim_ttl_secs",
+    "claim_expires_at",
+    "last_progress_at",
+    "claim_warning_sent_at",
+    "claim_extensions",
+    "last_output_bytes",
+];
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Fields for a task created through [`BoardStore::create`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NewTask {
+    pub title: String,
+    pub body: String,
+    /// Falls back to the board's `defaults.status`, then `backlog`.
+    pub status: Option<String>,
+    /// Falls back to the board's `defaults.priority`, then `medium`.
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub depends_on: Vec<u32>,
+}
+
+/// Exclusive advisory lock on a board directory, released on drop.
+pub(crate) struct BoardLock {
+    _file: File,
+}
+
+impl BoardLock {
+    /// Wait up to five seconds for the lock. A board that stays locked longer
+    /// surfaces as a transient error so callers can retry.
+    pub(crate) fn acquire(board_dir: &Path) -> Result<Self, BoardError> {
+        fs::create_dir_all(board_dir)
+            .map_err(|error| io_error("create board directory", board_dir, error))?;
+        Self::acquire_file(&board_dir.join(LOCK_FILE))
+    }
+
+    /// Same as [`BoardLock::acquire`] for another store's lock file, such as
+    /// `.batty/approvals.lock`. The parent directory must exist.
+    pub(crate) fn acquire_file(path: &Path) -> Result<Self, BoardError> {
+        let path = path.to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|error| io_error("open board lock", &path, error))?;
+
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match try_lock_exclusive(&file) {
+                Ok(true) => return Ok(Self { _file: file }),
+                Ok(false) if Instant::now() < deadline => std::thread::sleep(LOCK_POLL_INTERVAL),
+                Ok(false) => {
+                    return Err(BoardError::Transient {
+                        message: format!(
+                            "board lock {} still held after {}s",
+                            path.display(),
+                            LOCK_TIMEOUT.as_secs()
+                        ),
+                        stderr: "board lock is busy".to_string(),
+                    });
+                }
+                Err(error) => return Err(io_error("lock", &path, error)),
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn try_lock_exclusive(file: &File) -> std::io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: flock only reads the descriptor, which `file` keeps open.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let error = std::io::Error::last_os_error();
+    if error.kind() == std::io::ErrorKind::WouldBlock {
+        Ok(false)
+    } else {
+        Err(error)
+    }
+}
+
+#[cfg(not(unix))]
+fn try_lock_exclusive(_file: &File) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+/// Write `content` to a sibling temp file and rename it over `path`, so
+/// readers only ever see the old or the new file.
+pub(crate) fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = dir.join(format!(
+        ".{file_name}.{}.{}.tmp",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Handle on a kanban-md compatible board directory.
+#[derive(Debug, Clone)]
+pub(crate) struct BoardStore {
+    board_dir: PathBuf,
+}
+
+impl BoardStore {
+    pub(crate) fn open(board_dir: &Path) -> Self {
+        Self {
+            board_dir: board_dir.to_path_buf(),
+        }
+    }
+
+    fn tasks_dir(&self) -> PathBuf {
+        self.board_dir.join("tasks")
+    }
+
+    fn config_path(&self) -> PathBuf {
+        self.board_dir.join(CONFIG_FILE)
+    }
+
+    /// Create `config.yml` and `tasks/` if they are missing. Returns `true`
+    /// when a new board was written.
+    pub(crate) fn init(&self) -> Result<bool, BoardError> {
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let tasks_dir = self.tasks_dir();
+        fs::create_dir_all(&tasks_dir)
+            .map_err(|error| io_error("create tasks directory", &tasks_dir, error))?;
+
+        let config_path = self.config_path();
+        if config_path.exists() {
+            return Ok(false);
+        }
+        let name = self
+            .board_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "board".to_string());
+        let next_id = next_task_id(&self.board_dir, &Mapping::new());
+        write_config(&config_path, &default_config(&name, next_id))?;
+        Ok(true)
+    }
+
+    /// Create a task and return its id. Ids come from the board's `next_id`,
+    /// bumped past any task file already on disk.
+    pub(crate) fn create(&self, task: &NewTask) -> Result<u32, BoardError> {
+        let title = task.title.trim();
+        if title.is_empty() {
+            return Err(permanent("task title must not be empty"));
+        }
+
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let tasks_dir = self.tasks_dir();
+        fs::create_dir_all(&tasks_dir)
+            .map_err(|error| io_error("create tasks directory", &tasks_dir, error))?;
+
+        let config_path = self.config_path();
+        let mut config = read_config(&config_path)?;
+        let status = task
+            .status
+            .clone()
+            .or_else(|| config_default(&config, "status"))
+            .unwrap_or_else(|| DEFAULT_STATUS.to_string());
+        validate_status(&status)?;
+        let priority = task
+            .priority
+            .clone()
+            .or_else(|| config_default(&config, "priority"))
+            .unwrap_or_else(|| DEFAULT_PRIORITY.to_string());
+        if !PRIORITIES.contains(&priority.as_str()) {
+            return Err(permanent(format!(
+                "invalid priority '{priority}' (expected one of: {})",
+                PRIORITIES.join(", ")
+            )));
+        }
+
+        let id = next_task_id(&self.board_dir, &config);
+        let now = timestamp();
+        let mut frontmatter = Mapping::new();
+        frontmatter.insert(yaml_key("id"), Value::Number(id.into()));
+        frontmatter.insert(yaml_key("title"), Value::String(title.to_string()));
+        frontmatter.insert(yaml_key("status"), Value::String(status));
+        frontmatter.insert(yaml_key("priority"), Value::String(priority));
+        frontmatter.insert(yaml_key("created"), Value::String(now.clone()));
+        frontmatter.insert(yaml_key("updated"), Value::String(now));
+        if !task.tags.is_empty() {
+            frontmatter.insert(
+                yaml_key("tags"),
+                Value::Sequence(task.tags.iter().cloned().map(Value::String).collect()),
+            );
+        }
+        if !task.depends_on.is_empty() {
+            frontmatter.insert(
+                yaml_key("depends_on"),
+                Value::Sequence(
+                    task.depends_on
+                        .iter()
+                        .map(|dep| Value::Number((*dep).into()))
+                        .collect(),
+                ),
+            );
+        }
+
+        let path = tasks_dir.join(format!("{id:03}-{}.md", slugify(title)));
+        write_atomic(&path, &render_task(&frontmatter, &task.body)?)
+            .map_err(|error| io_error("write task", &path, error))?;
+
+        if config_path.exists() {
+            config.insert(yaml_key("next_id"), Value::Number((id + 1).into()));
+            write_config(&config_path, &config)?;
+        }
+        Ok(id)
+    }
+
+    /// Move a task to `status`. With `claim`, the move fails if someone else
+    /// holds the task and otherwise records `claim` as the owner. Moving back
+    /// to `backlog` or `todo` always drops the claim.
+    pub(crate) fn move_task(
+        &self,
+        task_id: u32,
+        status: &str,
+        claim: Option<&str>,
+        attribution: &StatusTransitionAttribution,
+    ) -> Result<(), BoardError> {
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        self.move_locked(task_id, status, claim, attribution)
+    }
+
+    fn move_locked(
+        &self,
+        task_id: u32,
+        status: &str,
+        claim: Option<&str>,
+        attribution: &StatusTransitionAttribution,
+    ) -> Result<(), BoardError> {
+        validate_status(status)?;
+        let (path, task) = self.load_task(task_id)?;
+        if let (Some(claim), Some(owner)) = (claim, task.claimed_by.as_deref())
+            && claim != owner
+        {
+            return Err(permanent(format!("task #{task_id} is claimed by {owner}")));
+        }
+
+        let now = timestamp();
+        rewrite_task_frontmatter(&path, |mapping| {
+            set_task_status(mapping, status);
+            mapping.insert(yaml_key("updated"), Value::String(now.clone()));
+            if let Some(claim) = claim
+                && !UNCLAIMED_STATUSES.contains(&status)
+                && task.claimed_by.as_deref() != Some(claim)
+            {
+                set_optional_string(mapping, "claimed_by", Some(claim));
+                set_optional_string(mapping, "claimed_at", Some(&now));
+            }
+            Ok(())
+        })
+        .map_err(|error| frontmatter_error(&path, error))?;
+
+        record_status_transition_activity(
+            &self.board_dir,
+            task_id,
+            &task.status,
+            status,
+            attribution,
+        )
+        .map_err(|error| permanent(format!("{error:#}")))?;
+        Ok(())
+    }
+
+    /// Mark a task blocked with `reason`, leaving its status and claim alone.
+    pub(crate) fn block(&self, task_id: u32, reason: &str) -> Result<(), BoardError> {
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let (path, _) = self.load_task(task_id)?;
+        let now = timestamp();
+        rewrite_task_frontmatter(&path, |mapping| {
+            mapping.insert(yaml_key("blocked"), Value::Bool(true));
+            set_optional_string(mapping, "block_reason", Some(reason));
+            mapping.insert(yaml_key("updated"), Value::String(now));
+            Ok(())
+        })
+        .map_err(|error| frontmatter_error(&path, error))
+    }
+
+    /// Set or clear the task's `assignee`. Unlike a claim, the assignee is
+    /// informational and survives moves back to `todo`.
+    pub(crate) fn set_assignee(
+        &self,
+        task_id: u32,
+        assignee: Option<&str>,
+    ) -> Result<(), BoardError> {
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let (path, _) = self.load_task(task_id)?;
+        let now = timestamp();
+        rewrite_task_frontmatter(&path, |mapping| {
+            set_optional_string(mapping, "assignee", assignee);
+            mapping.insert(yaml_key("updated"), Value::String(now));
+            Ok(())
+        })
+        .map_err(|error| frontmatter_error(&path, error))
+    }
+
+    /// Load task `task_id` and rewrite its frontmatter with `mutator`, all
+    /// under the board lock, so checks the mutator makes against the loaded
+    /// task cannot race another writer. An error from `mutator` leaves the
+    /// file untouched.
+    pub(crate) fn update_task<T>(
+        &self,
+        task_id: u32,
+        mutator: impl FnOnce(&Task, &mut Mapping) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let path = find_task_path_by_id(&self.tasks_dir(), task_id)?;
+        let task = Task::from_file(&path)?;
+        rewrite_task_frontmatter(&path, |mapping| mutator(&task, mapping))
+    }
+
+    /// Append a paragraph to the end of the task body.
+    pub(crate) fn append_note(&self, task_id: u32, note: &str) -> Result<(), BoardError> {
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let (path, _) = self.load_task(task_id)?;
+        let mut content =
+            fs::read_to_string(&path).map_err(|error| io_error("read", &path, error))?;
+        content.truncate(content.trim_end().len());
+        content.push_str("\n\n");
+        content.push_str(note.trim_end());
+        content.push('\n');
+        write_atomic(&path, &content).map_err(|error| io_error("write", &path, error))
+    }
+
+    /// Claim the highest-priority unblocked, unclaimed backlog/todo task whose
+    /// dependencies are done, and move it to `move_to`. Selection and claim
+    /// happen under one lock, so two callers never pick the same task.
+    pub(crate) fn pick(
+        &self,
+        claim: &str,
+        move_to: &str,
+        attribution: &StatusTransitionAttribution,
+    ) -> Result<Option<u32>, BoardError> {
+        validate_status(move_to)?;
+        let _lock = BoardLock::acquire(&self.board_dir)?;
+        let tasks = self.load_tasks()?;
+        let Some(task_id) = pick_candidate(&tasks) else {
+            return Ok(None);
+        };
+        self.move_locked(task_id, move_to, Some(claim), attribution)?;
+        Ok(Some(task_id))
+    }
+
+    /// Human-readable detail view of a single task.
+    pub(crate) fn show(&self, task_id: u32) -> Result<String, BoardError> {
+        let (path, task) = self.load_task(task_id)?;
+        let content =
+            fs::read_to_string(&path).map_err(|error| io_error("read task", &path, error))?;
+        let body = content
+            .trim_start()
+            .strip_prefix("---")
+            .and_then(|rest| rest.find("\n---").map(|close| &rest[close + 4..]))
+            .unwrap_or_default()
+            .trim();
+
+        let mut out = format!("Task #{}: {}\n\n", task.id, task.title);
+        out.push_str(&format!("Status:      {}\n", task.status));
+        out.push_str(&format!("Priority:    {}\n", task.priority));
+        match (task.claimed_by.as_deref(), task.claimed_at.as_deref()) {
+            (Some(owner), Some(since)) => {
+                out.push_str(&format!("Claimed by:  {owner} (since {since})\n"))
+            }
+            (Some(owner), None) => out.push_str(&format!("Claimed by:  {owner}\n")),
+            (None, _) => out.push_str("Claimed by:  --\n"),
+        }
+        if task.blocked.is_some() {
+            let reason = task.blocked_on.as_deref().unwrap_or("yes");
+            out.push_str(&format!("Blocked:     {reason}\n"));
+        }
+        if !task.tags.is_empty() {
+            out.push_str(&format!("Tags:        {}\n", task.tags.join(", ")));
+        }
+        if !task.depends_on.is_empty() {
+            let deps = task
+                .depends_on
+                .iter()
+                .map(|dep| format!("#{dep}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!("Depends on:  {deps}\n"));
+        }
+        if !body.is_empty() {
+            out.push('\n');
+            out.push_str(body);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Table of tasks, optionally filtered to one status. Rows start with the
+    /// task id; the header row does not.
+    pub(crate) fn list(&self, status: Option<&str>) -> Result<String, BoardError> {
+        if let Some(status) = status {
+            validate_status(status)?;
+        }
+        let tasks = self.load_tasks()?;
+        let rows = tasks
+            .iter()
+            .filter(|task| status.is_none_or(|status| task.status == status))
+            .map(|task| {
+                [
+                    task.id.to_string(),
+                    task.status.clone(),
+                    task.priority.clone(),
+                    task.title.clone(),
+                    task.claimed_by
+                        .as_deref()
+                        .map_or_else(|| "--".to_string(), |owner| format!("@{owner}")),
+                    if task.tags.is_empty() {
+                        "--".to_string()
+                    } else {
+                        task.tags.join(",")
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return Ok("No tasks found.\n".to_string());
+        }
+
+        let header = ["ID", "STATUS", "PRIORITY", "TITLE", "CLAIMED", "TAGS"];
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let render_row = |cells: [&str; 6]| {
+            let line = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        };
+
+        let mut out = render_row(header);
+        for row in &rows {
+            out.push_str(&render_row(row.each_ref().map(String::as_str)));
+        }
+        Ok(out)
+    }
+
+    fn load_task(&self, task_id: u32) -> Result<(PathBuf, Task), BoardError> {
+        let path = find_task_path_by_id(&self.tasks_dir(), task_id).map_err(|_| {
+            BoardError::TaskNotFound {
+                id: task_id.to_string(),
+            }
+        })?;
+        let task = Task::from_file(&path).map_err(|error| frontmatter_error(&path, error))?;
+        Ok((path, task))
+    }
+
+    fn load_tasks(&self) -> Result<Vec<Task>, BoardError> {
+        let tasks_dir = self.tasks_dir();
+        if !tasks_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        load_tasks_from_dir(&tasks_dir).map_err(|error| permanent(format!("{error:#}")))
+    }
+}
+
+/// Set a task's `status`. Falling back to `backlog` or `todo` also drops
+/// every claim field, whichever code path moves the task.
+pub(crate) fn set_task_status(mapping: &mut Mapping, status: &str) {
+    mapping.insert(yaml_key("status"), Value::String(status.to_string()));
+    if UNCLAIMED_STATUSES.contains(&status) {
+        for field in CLAIM_FIELDS {
+            mapping.remove(yaml_key(field));
+        }
+    }
+}
+
+fn pick_candidate(tasks: &[Task]) -> Option<u32> {
+    let done = |id: u32| {
+        tasks
+            .iter()
+            .find(|task| task.id == id)
+            .is_none_or(|task| matches!(task.status.as_str(), "done" | "archived"))
+    };
+    tasks
+        .iter()
+        .filter(|task| UNCLAIMED_STATUSES.contains(&task.status.as_str()))
+        .filter(|task| task.claimed_by.is_none() && task.blocked.is_none())
+        .filter(|task| task.depends_on.iter().all(|dep| done(*dep)))
+        .min_by_key(|task| {
+            (
+                task.status != "todo",
+                priority_rank(&task.priority),
+                task.id,
+            )
+        })
+        .map(|task| task.id)
+}
+
+fn priority_rank(priority: &str) -> usize {
+    PRIORITIES
+        .iter()
+        .rev()
+        .position(|candidate| *candidate == priority)
+        .unwrap_or(PRIORITIES.len())
+}
+
+fn validate_status(status: &str) -> Result<(), BoardError> {
+    if STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(permanent(format!(
+            "invalid status '{status}' (expected one of: {})",
+            STATUSES.join(", ")
+        )))
+    }
+}
+
+/// Next free id: the config's `next_id`, bumped past every task file in
+/// `tasks/` and `archive/` so an out-of-date config never reuses an id.
+fn next_task_id(board_dir: &Path, config: &Mapping) -> u32 {
+    let configured = config
+        .get(yaml_key("next_id"))
+        .and_then(Value::as_u64)
+        .and_then(|id| u32::try_from(id).ok())
+        .unwrap_or(1);
+    let highest_on_disk = ["tasks", "archive"]
+        .iter()
+        .filter_map(|dir| fs::read_dir(board_dir.join(dir)).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.ends_with(".md") {
+                return None;
+            }
+            let digits = name
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>();
+            digits.parse::<u32>().ok()
+        })
+        .max()
+        .unwrap_or(0);
+    configured.max(highest_on_disk + 1).max(1)
+}
+
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for ch in title.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= MAX_SLUG_LEN {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "task".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn render_task(frontmatter: &Mapping, body: &str) -> Result<String, BoardError> {
+    let yaml = serde_yaml::to_string(frontmatter)
+        .map_err(|error| permanent(format!("failed to serialize task frontmatter: {error}")))?;
+    let mut content = format!("---\n{yaml}---\n");
+    let body = body.trim_end();
+    if !body.is_empty() {
+        content.push('\n');
+        content.push_str(body);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn default_config(name: &str, next_id: u32) -> Mapping {
+    let strings =
+        |values: &[&str]| Value::Sequence(values.iter().map(|v| Value::from(*v)).collect());
+    let mut board = Mapping::new();
+    board.insert(yaml_key("name"), Value::from(name));
+    let mut defaults = Mapping::new();
+    defaults.insert(yaml_key("status"), Value::from(DEFAULT_STATUS));
+    defaults.insert(yaml_key("priority"), Value::from(DEFAULT_PRIORITY));
+
+    let mut config = Mapping::new();
+    config.insert(yaml_key("version"), Value::Number(CONFIG_VERSION.into()));
+    config.insert(yaml_key("board"), Value::Mapping(board));
+    config.insert(yaml_key("tasks_dir"), Value::from("tasks"));
+    config.insert(yaml_key("statuses"), strings(STATUSES));
+    config.insert(yaml_key("priorities"), strings(PRIORITIES));
+    config.insert(yaml_key("defaults"), Value::Mapping(defaults));
+    config.insert(yaml_key("next_id"), Value::Number(next_id.into()));
+    config
+}
+
+fn read_config(path: &Path) -> Result<Mapping, BoardError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Mapping::new()),
+        Err(error) => return Err(io_error("read board config", path, error)),
+    };
+    if content.trim().is_empty() {
+        return Ok(Mapping::new());
+    }
+    serde_yaml::from_str(&content).map_err(|error| {
+        permanent(format!(
+            "failed to parse board config {}: {error}",
+            path.display()
+        ))
+    })
+}
+
+fn write_config(path: &Path, config: &Mapping) -> Result<(), BoardError> {
+    let rendered = serde_yaml::to_string(config)
+        .map_err(|error| permanent(format!("failed to serialize board config: {error}")))?;
+    write_atomic(path, &rendered).map_err(|error| io_error("write board config", path, error))
+}
+
+fn config_default(config: &Mapping, key: &str) -> Option<String> {
+    config
+        .get(yaml_key("defaults"))
+        .and_then(Value::as_mapping)
+        .and_then(|defaults| defaults.get(yaml_key(key)))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn timestamp() -> String {
+    chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+fn permanent(message: impl Into<String>) -> BoardError {
+    BoardError::Permanent {
+        message: message.into(),
+        stderr: String::new(),
+    }
+}
+
+fn frontmatter_error(path: &Path, error: anyhow::Error) -> BoardError {
+    BoardError::InvalidFrontmatter {
+        detail: format!("{}: {error:#}", path.display()),
+    }
+}
+
+fn io_error(action: &str, path: &Path, error: std::io::Error) -> BoardError {
+    let message = format!("failed to {action} {}", path.display());
+    let stderr = error.to_string();
+    match error.kind() {
+        std::io::ErrorKind::WouldBlock
+        | std::io::ErrorKind::Interrupted
+        | std::io::ErrorKind::TimedOut => BoardError::Transient { message, stderr },
+        _ => BoardError::Permanent { message, stderr },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::task_cmd::{transition_task, update_task_frontmatter};
+    use std::sync::Arc;
+
+    fn attribution() -> StatusTransitionAttribution {
+        StatusTransitionAttribution::daemon("test.board_store")
+    }
+
+    fn new_task(title: &str) -> NewTask {
+        NewTask {
+            title: title.to_string(),
+            body: "Task body.".to_string(),
+            ..NewTask::default()
+        }
+    }
+
+    fn init_store() -> (tempfile::TempDir, BoardStore) {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = BoardStore::open(&tmp.path().join("board"));
+        assert!(store.init().unwrap());
+        (tmp, store)
+    }
+
+    fn task_file(store: &BoardStore, id: u32) -> String {
+        fs::read_to_string(find_task_path_by_id(&store.tasks_dir(), id).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn init_writes_kanban_md_config_once() {
+        let (_tmp, store) = init_store();
+
+        let config = read_config(&store.config_path()).unwrap();
+        assert_eq!(config.get("version").and_then(Value::as_u64), Some(10));
+        assert_eq!(
+            config.get("tasks_dir").and_then(Value::as_str),
+            Some("tasks")
+        );
+        assert_eq!(config.get("next_id").and_then(Value::as_u64), Some(1));
+        assert!(store.tasks_dir().is_dir());
+        assert!(!store.init().unwrap());
+    }
+
+    #[test]
+    fn create_writes_kanban_md_task_file_and_bumps_next_id() {
+        let (_tmp, store) = init_store();
+
+        let id = store
+            .create(&NewTask {
+                priority: Some("high".to_string()),
+                tags: vec!["phase-8".to_string(), "wave-1".to_string()],
+                depends_on: vec![7],
+                ..new_task("Test task: round trip!")
+            })
+            .unwrap();
+
+        assert_eq!(id, 1);
+        let path = store.tasks_dir().join("001-test-task-round-trip.md");
+        let task = Task::from_file(&path).unwrap();
+        assert_eq!(task.title, "Test task: round trip!");
+        assert_eq!(task.status, "backlog");
+        assert_eq!(task.priority, "high");
+        assert_eq!(task.tags, vec!["phase-8", "wave-1"]);
+        assert_eq!(task.depends_on, vec![7]);
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .ends_with("---\n\nTask body.\n")
+        );
+        let config = read_config(&store.config_path()).unwrap();
+        assert_eq!(config.get("next_id").and_then(Value::as_u64), Some(2));
+    }
+
+    #[test]
+    fn create_skips_ids_already_on_disk_when_config_is_stale() {
+        let (_tmp, store) = init_store();
+        fs::write(
+            store.tasks_dir().join("041-existing.md"),
+            "---\nid: 41\ntitle: existing\nstatus: done\npriority: high\n---\n",
+        )
+        .unwrap();
+
+        assert_eq!(store.create(&new_task("Next")).unwrap(), 42);
+    }
+
+    #[test]
+    fn create_rejects_unknown_priority() {
+        let (_tmp, store) = init_store();
+        let error = store
+            .create(&NewTask {
+                priority: Some("urgent".to_string()),
+                ..new_task("Bad priority")
+            })
+            .unwrap_err();
+        assert!(error.to_string().contains("invalid priority 'urgent'"));
+    }
+
+    #[test]
+    fn concurrent_creates_allocate_distinct_ids() {
+        let (_tmp, store) = init_store();
+        let store = Arc::new(store);
+
+        let handles = (0..8)
+            .map(|n| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || store.create(&new_task(&format!("Task {n}"))).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let mut ids = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        assert_eq!(ids, (1..=8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn move_back_to_todo_drops_claim_and_keeps_unknown_fields() {
+        let (_tmp, store) = init_store();
+        let id = store.create(&new_task("Recurring")).unwrap();
+        let path = find_task_path_by_id(&store.tasks_dir(), id).unwrap();
+        update_task_frontmatter(&path, |mapping| {
+            set_optional_string(mapping, "cron_schedule", Some("0 9 * * 1"));
+        })
+        .unwrap();
+
+        store
+            .move_task(id, "in-progress", Some("eng-1-2"), &attribution())
+            .unwrap();
+        let claimed = task_file(&store, id);
+        assert!(claimed.contains("claimed_by: eng-1-2"));
+        assert!(claimed.contains("cron_schedule: 0 9 * * 1"));
+
+        store.move_task(id, "todo", None, &attribution()).unwrap();
+        let released = task_file(&store, id);
+        assert!(released.contains("status: todo"));
+        assert!(!released.contains("claimed_by"));
+        assert!(!released.contains("claimed_at"));
+        assert!(released.contains("cron_schedule: 0 9 * * 1"));
+    }
+
+    #[test]
+    fn concurrent_frontmatter_updates_are_not_lost() {
+        let (_tmp, store) = init_store();
+        let id = store.create(&new_task("Contended")).unwrap();
+        let path = Arc::new(find_task_path_by_id(&store.tasks_dir(), id).unwrap());
+
+        let handles = (0..8)
+            .map(|writer| {
+                let path = Arc::clone(&path);
+                std::thread::spawn(move || {
+                    update_task_frontmatter(&path, |mapping| {
+                        // Widen the read-modify-write window.
+                        std::thread::sleep(Duration::from_millis(5));
+                        set_optional_string(mapping, &format!("writer_{writer}"), Some("x"));
+                    })
+                    .unwrap();
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let content = task_file(&store, id);
+        for writer in 0..8 {
+            assert!(
+                content.contains(&format!("writer_{writer}: x")),
+                "{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn workflow_rollback_to_todo_drops_claim() {
+        let (tmp, store) = init_store();
+        let board_dir = tmp.path().join("board");
+        let id = store
+            .create(&NewTask {
+                status: Some("todo".to_string()),
+                ..new_task("Rolled back")
+            })
+            .unwrap();
+        store
+            .move_task(id, "in-progress", Some("eng-1"), &attribution())
+            .unwrap();
+        let path = find_task_path_by_id(&store.tasks_dir(), id).unwrap();
+        update_task_frontmatter(&path, |mapping| {
+            set_optional_string(mapping, "claim_expires_at", Some("2026-01-01T00:00:00Z"));
+        })
+        .unwrap();
+
+        transition_task(&board_dir, id, "todo").unwrap();
+
+        let task = Task::from_file(&path).unwrap();
+        assert_eq!(task.status, "todo");
+        assert_eq!(task.claimed_by, None);
+        assert!(!task_file(&store, id).contains("claim_expires_at"));
+    }
+
+    #[test]
+    fn move_rejects_claim_held_by_someone_else() {
+        let (_tmp, store) = init_store();
+        let id = store.create(&new_task("Owned")).unwrap();
+        store
+            .move_task(id, "in-progress", Some("eng-1-1"), &attribution())
+            .unwrap();
+
+        let error = store
+            .move_task(id, "done", Some("eng-1-2"), &attribution())
+            .unwrap_err();
+
+        assert!(error.to_string().contains("is claimed by eng-1-1"));
+        assert!(task_file(&store, id).contains("status: in-progress"));
+    }
+
+    #[test]
+    fn move_records_status_activity() {
+        let (_tmp, store) = init_store();
+        let id = store.create(&new_task("Tracked")).unwrap();
+
+        store.move_task(id, "todo", None, &attribution()).unwrap();
+
+        let activity = fs::read_to_string(store.board_dir.join("activity.jsonl")).unwrap();
+        assert!(activity.contains("\"detail\":\"backlog -> todo\""));
+        assert!(activity.contains("\"source\":\"test.board_store\""));
+    }
+
+    #[test]
+    fn block_sets_reason_without_touching_status() {
+        let (_tmp, store) = init_store();
+        let id = store.create(&new_task("Conflicted")).unwrap();
+
+        store.block(id, "merge conflicts after 2 retries").unwrap();
+
+        let task = Task::from_file(&find_task_path_by_id(&store.tasks_dir(), id).unwrap()).unwrap();
+        assert_eq!(task.status, "backlog");
+        assert!(task.blocked.is_some());
+        assert!(task_file(&store, id).contains("block_reason: merge conflicts after 2 retries"));
+    }
+
+    #[test]
+    fn pick_prefers_todo_then_priority_and_skips_unready_tasks() {
+        let (_tmp, store) = init_store();
+        let backlog_critical = store
+            .create(&NewTask {
+                priority: Some("critical".to_string()),
+                ..new_task("Backlog critical")
+            })
+            .unwrap();
+        let todo = |title: &str, priority: &str, depends_on: Vec<u32>| NewTask {
+            status: Some("todo".to_string()),
+            priority: Some(priority.to_string()),
+            depends_on,
+            ..new_task(title)
+        };
+        let waiting = store
+            .create(&todo("Waiting", "critical", vec![backlog_critical]))
+            .unwrap();
+        let blocked = store.create(&todo("Blocked", "high", vec![])).unwrap();
+        store.block(blocked, "needs input").unwrap();
+        let medium = store.create(&todo("Medium", "medium", vec![])).unwrap();
+        let high = store.create(&todo("High", "high", vec![])).unwrap();
+
+        let picks = (0..4)
+            .map(|_| {
+                store
+                    .pick("eng-1-1", "in-progress", &attribution())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            picks,
+            vec![Some(high), Some(medium), Some(backlog_critical), None]
+        );
+        assert!(task_file(&store, high).contains("claimed_by: eng-1-1"));
+        assert!(task_file(&store, waiting).contains("status: todo"));
+    }
+
+    #[test]
+    fn list_renders_rows_and_filters_by_status() {
+        let (_tmp, store) = init_store();
+        store.create(&new_task("First")).unwrap();
+        let second = store.create(&new_task("Second")).unwrap();
+        store
+            .move_task(second, "review", Some("eng-1"), &attribution())
+            .unwrap();
+
+        let all = store.list(None).unwrap();
+        assert!(all.starts_with("ID"));
+        assert!(all.contains("First"));
+        assert!(all.contains("@eng-1"));
+
+        let review = store.list(Some("review")).unwrap();
+        assert!(!review.contains("First"));
+        assert!(review.contains("Second"));
+        assert_eq!(store.list(Some("done")).unwrap(), "No tasks found.\n");
+        assert!(store.list(Some("wat")).is_err());
+    }
+
+    #[test]
+    fn show_reports_missing_task() {
+        let (_tmp, store) = init_store();
+        let id = store.create(&new_task("Visible")).unwrap();
+
+        let shown = store.show(id).unwrap();
+        assert!(shown.starts_with("Task #1: Visible"));
+        assert!(shown.contains("Claimed by:  --"));
+        assert!(shown.contains("Task body."));
+        assert!(matches!(
+            store.show(99),
+            Err(BoardError::TaskNotFound { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lock_excludes_other_holders_until_dropped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let held = BoardLock::acquire(tmp.path()).unwrap();
+        let handle = {
+            let dir = tmp.path().to_path_buf();
+            std::thread::spawn(move || {
+                let file = OpenOptions::new()
+                    .write(true)
+                    .open(dir.join(LOCK_FILE))
+                    .unwrap();
+                try_lock_exclusive(&file).unwrap()
+            })
+        };
+        assert!(!handle.join().unwrap());
+
+        drop(held);
+        assert!(BoardLock::acquire(tmp.path()).is_ok());
+    }
+}
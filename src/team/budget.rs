@@ -0,0 +1,675 @@
+//! Token and dollar budgets: the persisted usage ledger, limit evaluation,
+//! and the headroom view shared by the daemon, `batty status`, and `batty cost`.
+//!
+//! The daemon charges the ledger from shim `SessionStats` token counters. Those
+//! counters are cumulative per agent session, so the ledger keeps the last
+//! value it saw per member and only charges the delta.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::config::{BudgetConfig, BudgetLimit};
+use super::hierarchy::MemberInstance;
+
+/// Path of the persisted budget ledger.
+pub fn budget_ledger_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join("budget_ledger.json")
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    #[serde(default)]
+    pub tokens: u64,
+    #[serde(default)]
+    pub usd: f64,
+}
+
+impl BudgetUsage {
+    fn charge(&mut self, tokens: u64, usd: f64) {
+        self.tokens = self.tokens.saturating_add(tokens);
+        self.usd += usd;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemberBudgetUsage {
+    #[serde(flatten)]
+    pub usage: BudgetUsage,
+    /// Last model the shim reported for this member, used for pricing when
+    /// the role does not pin one.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    last_input_tokens: u64,
+    #[serde(default)]
+    last_output_tokens: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskBudgetUsage {
+    #[serde(flatten)]
+    pub usage: BudgetUsage,
+    #[serde(default)]
+    pub priority: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLedger {
+    #[serde(default)]
+    pub run_started_at: u64,
+    #[serde(default)]
+    pub run: BudgetUsage,
+    #[serde(default)]
+    pub day: String,
+    #[serde(default)]
+    pub day_usage: BudgetUsage,
+    #[serde(default)]
+    pub members: BTreeMap<String, MemberBudgetUsage>,
+    #[serde(default)]
+    pub tasks: BTreeMap<u32, TaskBudgetUsage>,
+    /// Scope/level keys that have already been reported, so each crossing
+    /// notifies once.
+    #[serde(default)]
+    notified: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLevel {
+    Soft,
+    Hard,
+}
+
+impl BudgetLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Soft => "soft",
+            Self::Hard => "hard",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BudgetScope {
+    Member { member: String, role: String },
+    Task { task_id: u32, priority: String },
+    Run,
+    Day,
+}
+
+impl BudgetScope {
+    fn key(&self, day: &str) -> String {
+        match self {
+            Self::Member { member, .. } => format!("member:{member}"),
+            Self::Task { task_id, .. } => format!("task:{task_id}"),
+            Self::Run => "run".to_string(),
+            Self::Day => format!("day:{day}"),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Member { member, role } => format!("{member} ({role})"),
+            Self::Task { task_id, priority } => format!("task #{task_id} ({priority})"),
+            Self::Run => "run".to_string(),
+            Self::Day => "day".to_string(),
+        }
+    }
+
+    /// Run and day limits gate the whole team rather than one member.
+    pub fn is_team_wide(&self) -> bool {
+        matches!(self, Self::Run | Self::Day)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetCrossing {
+    pub scope: BudgetScope,
+    pub level: BudgetLevel,
+    pub usage: BudgetUsage,
+    pub limit: BudgetLimit,
+}
+
+impl BudgetCrossing {
+    pub fn summary(&self) -> String {
+        let (token_limit, usd_limit) = match self.level {
+            BudgetLevel::Soft => (self.limit.soft_tokens, self.limit.soft_usd),
+            BudgetLevel::Hard => (self.limit.hard_tokens, self.limit.hard_usd),
+        };
+        format!(
+            "{} budget {} limit crossed: {} tokens{}, ${:.2}{}",
+            self.scope.label(),
+            self.level.as_str(),
+            self.usage.tokens,
+            token_limit
+                .map(|limit| format!(" of {limit}"))
+                .unwrap_or_default(),
+            self.usage.usd,
+            usd_limit
+                .map(|limit| format!(" of ${limit:.2}"))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+fn limit_reached(usage: &BudgetUsage, tokens: Option<u64>, usd: Option<f64>) -> bool {
+    tokens.is_some_and(|limit| usage.tokens >= limit) || usd.is_some_and(|limit| usage.usd >= limit)
+}
+
+fn level_reached(usage: &BudgetUsage, limit: &BudgetLimit) -> Option<BudgetLevel> {
+    if limit_reached(usage, limit.hard_tokens, limit.hard_usd) {
+        Some(BudgetLevel::Hard)
+    } else if limit_reached(usage, limit.soft_tokens, limit.soft_usd) {
+        Some(BudgetLevel::Soft)
+    } else {
+        None
+    }
+}
+
+impl BudgetLedger {
+    /// Reset run-scoped totals for a fresh `batty start`. Day and task totals
+    /// carry over.
+    pub fn start_run(&mut self, now: u64) {
+        self.run_started_at = now;
+        self.run = BudgetUsage::default();
+        for member in self.members.values_mut() {
+            member.usage = BudgetUsage::default();
+            member.last_input_tokens = 0;
+            member.last_output_tokens = 0;
+        }
+        self.notified
+            .retain(|key| !key.starts_with("member:") && !key.starts_with("run:"));
+    }
+
+    /// Start a new day bucket when the calendar date changes.
+    pub fn roll_day(&mut self, today: &str) {
+        if self.day == today {
+            return;
+        }
+        self.day = today.to_string();
+        self.day_usage = BudgetUsage::default();
+        self.notified.retain(|key| !key.starts_with("day:"));
+    }
+
+    pub fn note_model(&mut self, member: &str, model: &str) {
+        self.members.entry(member.to_string()).or_default().model = Some(model.to_string());
+    }
+
+    pub fn member_model(&self, member: &str) -> Option<&str> {
+        self.members
+            .get(member)
+            .and_then(|entry| entry.model.as_deref())
+    }
+
+    /// Convert cumulative session counters into a delta. A counter that goes
+    /// backwards means the shim started a new session.
+    pub fn session_delta(
+        &mut self,
+        member: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> (u64, u64) {
+        let entry = self.members.entry(member.to_string()).or_default();
+        let delta_input = if input_tokens >= entry.last_input_tokens {
+            input_tokens - entry.last_input_tokens
+        } else {
+            input_tokens
+        };
+        let delta_output = if output_tokens >= entry.last_output_tokens {
+            output_tokens - entry.last_output_tokens
+        } else {
+            output_tokens
+        };
+        entry.last_input_tokens = input_tokens;
+        entry.last_output_tokens = output_tokens;
+        (delta_input, delta_output)
+    }
+
+    /// Add usage to every scope the member's work counts against.
+    pub fn charge(&mut self, member: &str, task: Option<(u32, &str)>, tokens: u64, usd: f64) {
+        self.run.charge(tokens, usd);
+        self.day_usage.charge(tokens, usd);
+        self.members
+            .entry(member.to_string())
+            .or_default()
+            .usage
+            .charge(tokens, usd);
+        if let Some((task_id, priority)) = task {
+            let entry = self.tasks.entry(task_id).or_default();
+            entry.priority = priority.to_string();
+            entry.usage.charge(tokens, usd);
+        }
+    }
+
+    fn scopes_for(
+        &self,
+        config: &BudgetConfig,
+        member: &str,
+        role: &str,
+        task_id: Option<u32>,
+    ) -> Vec<(BudgetScope, BudgetUsage, BudgetLimit)> {
+        let mut scopes = Vec::new();
+        if let Some(limit) = config.roles.get(role) {
+            let usage = self
+                .members
+                .get(member)
+                .map(|entry| entry.usage)
+                .unwrap_or_default();
+            scopes.push((
+                BudgetScope::Member {
+                    member: member.to_string(),
+                    role: role.to_string(),
+                },
+                usage,
+                limit.clone(),
+            ));
+        }
+        if let Some(task_id) = task_id
+            && let Some(entry) = self.tasks.get(&task_id)
+            && let Some(limit) = config.task_priority.get(&entry.priority)
+        {
+            scopes.push((
+                BudgetScope::Task {
+                    task_id,
+                    priority: entry.priority.clone(),
+                },
+                entry.usage,
+                limit.clone(),
+            ));
+        }
+        if let Some(limit) = &config.run {
+            scopes.push((BudgetScope::Run, self.run, limit.clone()));
+        }
+        if let Some(limit) = &config.day {
+            scopes.push((BudgetScope::Day, self.day_usage, limit.clone()));
+        }
+        scopes
+    }
+
+    /// Return crossings that have not been reported yet and mark them
+    /// reported. A hard crossing also suppresses the matching soft one.
+    pub fn evaluate(
+        &mut self,
+        config: &BudgetConfig,
+        member: &str,
+        role: &str,
+        task_id: Option<u32>,
+    ) -> Vec<BudgetCrossing> {
+        let mut crossings = Vec::new();
+        for (scope, usage, limit) in self.scopes_for(config, member, role, task_id) {
+            let Some(level) = level_reached(&usage, &limit) else {
+                continue;
+            };
+            let key = scope.key(&self.day);
+            let level_key = format!("{key}:{}", level.as_str());
+            if !self.notified.insert(level_key) {
+                continue;
+            }
+            if level == BudgetLevel::Hard {
+                self.notified.insert(format!("{key}:soft"));
+            }
+            crossings.push(BudgetCrossing {
+                scope,
+                level,
+                usage,
+                limit,
+            });
+        }
+        crossings
+    }
+
+    /// The member-specific scope (role or active task) whose hard limit is
+    /// currently exceeded, if any.
+    pub fn member_hard_limited(
+        &self,
+        config: &BudgetConfig,
+        member: &str,
+        role: &str,
+        task_id: Option<u32>,
+    ) -> Option<BudgetScope> {
+        self.scopes_for(config, member, role, task_id)
+            .into_iter()
+            .filter(|(scope, _, _)| !scope.is_team_wide())
+            .find(|(_, usage, limit)| level_reached(usage, limit) == Some(BudgetLevel::Hard))
+            .map(|(scope, _, _)| scope)
+    }
+
+    /// The team-wide scope (run or day) whose hard limit is currently
+    /// exceeded, if any.
+    pub fn team_hard_limited(&self, config: &BudgetConfig) -> Option<BudgetScope> {
+        if config
+            .run
+            .as_ref()
+            .is_some_and(|limit| level_reached(&self.run, limit) == Some(BudgetLevel::Hard))
+        {
+            return Some(BudgetScope::Run);
+        }
+        if config
+            .day
+            .as_ref()
+            .is_some_and(|limit| level_reached(&self.day_usage, limit) == Some(BudgetLevel::Hard))
+        {
+            return Some(BudgetScope::Day);
+        }
+        None
+    }
+
+    /// Remaining headroom for every configured scope, in display order.
+    pub fn headroom_rows(
+        &self,
+        config: &BudgetConfig,
+        members: &[MemberInstance],
+    ) -> Vec<BudgetHeadroomRow> {
+        let mut rows = Vec::new();
+        if let Some(limit) = &config.run {
+            rows.push(BudgetHeadroomRow::new("run".to_string(), self.run, limit));
+        }
+        if let Some(limit) = &config.day {
+            rows.push(BudgetHeadroomRow::new(
+                format!("day {}", self.day),
+                self.day_usage,
+                limit,
+            ));
+        }
+        for member in members {
+            if let Some(limit) = config.roles.get(&member.role_name) {
+                let usage = self
+                    .members
+                    .get(&member.name)
+                    .map(|entry| entry.usage)
+                    .unwrap_or_default();
+                rows.push(BudgetHeadroomRow::new(member.name.clone(), usage, limit));
+            }
+        }
+        for (task_id, entry) in &self.tasks {
+            if let Some(limit) = config.task_priority.get(&entry.priority) {
+                rows.push(BudgetHeadroomRow::new(
+                    format!("task #{task_id}"),
+                    entry.usage,
+                    limit,
+                ));
+            }
+        }
+        rows
+    }
+}
+
+/// One line of the budget headroom table.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetHeadroomRow {
+    pub scope: String,
+    pub used_tokens: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_tokens: Option<u64>,
+    pub used_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_usd: Option<f64>,
+    pub state: &'static str,
+}
+
+impl BudgetHeadroomRow {
+    fn new(scope: String, usage: BudgetUsage, limit: &BudgetLimit) -> Self {
+        // Fall back to the soft ceiling when no hard one is set so headroom is
+        // still meaningful for warn-only budgets.
+        let ceiling_tokens = limit.hard_tokens.or(limit.soft_tokens);
+        let ceiling_usd = limit.hard_usd.or(limit.soft_usd);
+        Self {
+            scope,
+            used_tokens: usage.tokens,
+            hard_tokens: ceiling_tokens,
+            remaining_tokens: ceiling_tokens.map(|limit| limit.saturating_sub(usage.tokens)),
+            used_usd: usage.usd,
+            hard_usd: ceiling_usd,
+            remaining_usd: ceiling_usd.map(|limit| (limit - usage.usd).max(0.0)),
+            state: match level_reached(&usage, limit) {
+                Some(BudgetLevel::Hard) => "hard",
+                Some(BudgetLevel::Soft) => "soft",
+                None => "ok",
+            },
+        }
+    }
+}
+
+pub fn format_headroom(rows: &[BudgetHeadroomRow]) -> String {
+    let mut lines = vec![
+        "Budget Headroom".to_string(),
+        format!(
+            "{:<20} {:>12} {:>12} {:>10} {:>10} {:<6}",
+            "SCOPE", "TOKENS", "LEFT", "USD", "LEFT", "STATE"
+        ),
+    ];
+    for row in rows {
+        lines.push(format!(
+            "{:<20} {:>12} {:>12} {:>10} {:>10} {:<6}",
+            row.scope,
+            row.used_tokens,
+            row.remaining_tokens
+                .map(|left| left.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            format!("${:.2}", row.used_usd),
+            row.remaining_usd
+                .map(|left| format!("${left:.2}"))
+                .unwrap_or_else(|| "-".to_string()),
+            row.state,
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn load_ledger(project_root: &Path) -> BudgetLedger {
+    let path = budget_ledger_path(project_root);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return BudgetLedger::default();
+    };
+    match serde_json::from_str(&content) {
+        Ok(ledger) => ledger,
+        Err(error) => {
+            warn!(path = %path.display(), error = %error, "ignoring unreadable budget ledger");
+            BudgetLedger::default()
+        }
+    }
+}
+
+pub fn save_ledger(project_root: &Path, ledger: &BudgetLedger) -> Result<()> {
+    let path = budget_ledger_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(ledger)?;
+    std::fs::write(&path, content).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Headroom rows for the current project, or `None` when no budget is set.
+pub fn project_headroom(
+    project_root: &Path,
+    config: &BudgetConfig,
+    members: &[MemberInstance],
+) -> Option<Vec<BudgetHeadroomRow>> {
+    if !config.is_enabled() {
+        return None;
+    }
+    Some(load_ledger(project_root).headroom_rows(config, members))
+}
+
+pub fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn limit(soft_tokens: u64, hard_tokens: u64) -> BudgetLimit {
+        BudgetLimit {
+            soft_tokens: Some(soft_tokens),
+            hard_tokens: Some(hard_tokens),
+            ..BudgetLimit::default()
+        }
+    }
+
+    fn config() -> BudgetConfig {
+        BudgetConfig {
+            roles: HashMap::from([("engineer".to_string(), limit(100, 200))]),
+            task_priority: HashMap::from([("high".to_string(), limit(50, 150))]),
+            run: Some(BudgetLimit {
+                hard_usd: Some(10.0),
+                ..BudgetLimit::default()
+            }),
+            ..BudgetConfig::default()
+        }
+    }
+
+    #[test]
+    fn session_delta_handles_counter_reset() {
+        let mut ledger = BudgetLedger::default();
+        assert_eq!(ledger.session_delta("eng-1", 100, 10), (100, 10));
+        assert_eq!(ledger.session_delta("eng-1", 150, 30), (50, 20));
+        // New shim session restarts the cumulative counters.
+        assert_eq!(ledger.session_delta("eng-1", 40, 5), (40, 5));
+    }
+
+    #[test]
+    fn evaluate_reports_each_crossing_once() {
+        let config = config();
+        let mut ledger = BudgetLedger::default();
+        ledger.charge("eng-1", Some((7, "high")), 60, 0.5);
+
+        let crossings = ledger.evaluate(&config, "eng-1", "engineer", Some(7));
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].level, BudgetLevel::Soft);
+        assert!(matches!(
+            crossings[0].scope,
+            BudgetScope::Task { task_id: 7, .. }
+        ));
+        assert!(
+            ledger
+                .evaluate(&config, "eng-1", "engineer", Some(7))
+                .is_empty()
+        );
+
+        ledger.charge("eng-1", Some((7, "high")), 100, 0.5);
+        let crossings = ledger.evaluate(&config, "eng-1", "engineer", Some(7));
+        let levels: Vec<_> = crossings
+            .iter()
+            .map(|crossing| (crossing.scope.label(), crossing.level))
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                ("eng-1 (engineer)".to_string(), BudgetLevel::Soft),
+                ("task #7 (high)".to_string(), BudgetLevel::Hard),
+            ]
+        );
+    }
+
+    #[test]
+    fn hard_crossing_suppresses_later_soft_notice() {
+        let config = config();
+        let mut ledger = BudgetLedger::default();
+        ledger.charge("eng-1", None, 250, 0.0);
+        let crossings = ledger.evaluate(&config, "eng-1", "engineer", None);
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].level, BudgetLevel::Hard);
+        assert!(
+            ledger
+                .evaluate(&config, "eng-1", "engineer", None)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn member_and_team_hard_limits_follow_current_config() {
+        let mut config = config();
+        let mut ledger = BudgetLedger::default();
+        ledger.charge("eng-1", None, 250, 12.0);
+        assert!(
+            ledger
+                .member_hard_limited(&config, "eng-1", "engineer", None)
+                .is_some()
+        );
+        assert_eq!(ledger.team_hard_limited(&config), Some(BudgetScope::Run));
+
+        // Raising the limits in team.yaml releases the gates.
+        config
+            .roles
+            .insert("engineer".to_string(), limit(100, 1_000));
+        config.run = None;
+        assert!(
+            ledger
+                .member_hard_limited(&config, "eng-1", "engineer", None)
+                .is_none()
+        );
+        assert!(ledger.team_hard_limited(&config).is_none());
+    }
+
+    #[test]
+    fn start_run_and_roll_day_reset_their_buckets() {
+        let config = config();
+        let mut ledger = BudgetLedger::default();
+        ledger.roll_day("2026-10-16");
+        ledger.session_delta("eng-1", 500, 0);
+        ledger.charge("eng-1", Some((7, "high")), 250, 12.0);
+        assert_eq!(
+            ledger.evaluate(&config, "eng-1", "engineer", Some(7)).len(),
+            3
+        );
+
+        ledger.start_run(42);
+        assert_eq!(ledger.run, BudgetUsage::default());
+        assert_eq!(ledger.members["eng-1"].usage, BudgetUsage::default());
+        assert_eq!(ledger.session_delta("eng-1", 10, 0), (10, 0));
+        assert_eq!(ledger.tasks[&7].usage.tokens, 250);
+        assert_eq!(ledger.day_usage.tokens, 250);
+
+        ledger.roll_day("2026-10-17");
+        assert_eq!(ledger.day_usage, BudgetUsage::default());
+    }
+
+    #[test]
+    fn headroom_rows_report_remaining_budget() {
+        let config = config();
+        let mut ledger = BudgetLedger::default();
+        ledger.charge("eng-1", Some((7, "high")), 120, 2.5);
+        let members = vec![MemberInstance {
+            name: "eng-1".to_string(),
+            role_name: "engineer".to_string(),
+            ..MemberInstance::default()
+        }];
+
+        let rows = ledger.headroom_rows(&config, &members);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].scope, "run");
+        assert_eq!(rows[0].remaining_usd, Some(7.5));
+        assert_eq!(rows[1].scope, "eng-1");
+        assert_eq!(rows[1].remaining_tokens, Some(80));
+        assert_eq!(rows[1].state, "soft");
+        assert_eq!(rows[2].scope, "task #7");
+        assert_eq!(rows[2].remaining_tokens, Some(30));
+
+        let table = format_headroom(&rows);
+        assert!(table.contains("Budget Headroom"));
+        assert!(table.contains("task #7"));
+    }
+
+    #[test]
+    fn ledger_round_trips_through_disk() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut ledger = BudgetLedger::default();
+        ledger.note_model("eng-1", "claude-sonnet-4-6");
+        ledger.charge("eng-1", Some((3, "low")), 10, 0.01);
+        save_ledger(tmp.path(), &ledger).unwrap();
+        assert_eq!(load_ledger(tmp.path()), ledger);
+
+        std::fs::write(budget_ledger_path(tmp.path()), "not json").unwrap();
+        assert_eq!(load_ledger(tmp.path()), BudgetLedger::default());
+    }
+}
@@ -555,6 +555,8 @@ mod tests {
             completed: None,
             description: description.to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("/tmp/fake.md"),
         }
     }
@@ -14,6 +14,7 @@ use tracing::{debug, warn};
 use super::config::ChannelConfig;
 use super::discord::DiscordBot;
 use super::errors::DeliveryError;
+use super::slack::SlackBot;
 use super::telegram::TelegramBot;
 
 const TELEGRAM_DEDUP_TTL: Duration = Duration::from_secs(300);
@@ -258,6 +259,41 @@ impl Channel for DiscordChannel {
     }
 }
 
+/// Native Slack channel using the Web API directly.
+pub struct SlackChannel {
+    bot: SlackBot,
+    channel_id: String,
+}
+
+impl SlackChannel {
+    pub fn new(bot: SlackBot, channel_id: String) -> Self {
+        Self { bot, channel_id }
+    }
+
+    pub fn from_config(config: &ChannelConfig) -> Option<Self> {
+        let channel_id = config
+            .commands_channel_id
+            .clone()
+            .or_else(|| config.events_channel_id.clone())?;
+        SlackBot::from_config(config).map(|bot| Self::new(bot, channel_id))
+    }
+}
+
+impl Channel for SlackChannel {
+    fn send(&self, message: &str) -> std::result::Result<(), DeliveryError> {
+        self.bot
+            .send_formatted_message(&self.channel_id, message)
+            .map_err(|error| DeliveryError::ChannelSend {
+                recipient: self.channel_id.clone(),
+                detail: error.to_string(),
+            })
+    }
+
+    fn channel_type(&self) -> &str {
+        "slack"
+    }
+}
+
 /// Create a channel from config fields.
 pub fn channel_from_config(
     channel_type: &str,
@@ -276,6 +312,11 @@ pub fn channel_from_config(
             .ok_or_else(|| DeliveryError::UnsupportedChannel {
                 channel_type: "discord".to_string(),
             }),
+        "slack" => SlackChannel::from_config(config)
+            .map(|channel| Box::new(channel) as Box<dyn Channel>)
+            .ok_or_else(|| DeliveryError::UnsupportedChannel {
+                channel_type: "slack".to_string(),
+            }),
         other => Err(DeliveryError::UnsupportedChannel {
             channel_type: other.to_string(),
         }),
@@ -318,6 +359,9 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
         // Without bot_token (and assuming env var is not set), falls back to CLI channel.
         if std::env::var("BATTY_TELEGRAM_BOT_TOKEN").is_err() {
@@ -337,6 +381,9 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
         let ch = channel_from_config("telegram", &config).unwrap();
         assert_eq!(ch.channel_type(), "telegram-native");
@@ -353,6 +400,9 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
         // Only assert CLI fallback when the env var is also absent.
         if std::env::var("BATTY_TELEGRAM_BOT_TOKEN").is_err() {
@@ -372,8 +422,11 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
-        match channel_from_config("slack", &config) {
+        match channel_from_config("matrix", &config) {
             Err(e) => assert!(e.to_string().contains("unsupported")),
             Ok(_) => panic!("expected error for unsupported channel"),
         }
@@ -390,11 +443,36 @@ mod tests {
             agents_channel_id: Some("200".into()),
             commands_channel_id: Some("300".into()),
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
         let ch = channel_from_config("discord", &config).unwrap();
         assert_eq!(ch.channel_type(), "discord");
     }
 
+    #[test]
+    fn channel_from_config_slack_posts_to_commands_channel() {
+        let server = crate::team::slack::mock::MockSlack::start(
+            |_| serde_json::json!({ "ok": true, "ts": "1700000000.000100" }),
+        );
+        let config = ChannelConfig {
+            bot_token: Some("xoxb-test".into()),
+            commands_channel_id: Some("C-commands".into()),
+            api_base: Some(server.base.clone()),
+            ..Default::default()
+        };
+        let ch = channel_from_config("slack", &config).unwrap();
+        assert_eq!(ch.channel_type(), "slack");
+        ch.send("--- Message from architect ---\nPlan ready")
+            .unwrap();
+
+        let posts = server.calls("chat.postMessage");
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].body["channel"], "C-commands");
+        assert_eq!(posts[0].body["text"], "*architect*\nPlan ready");
+    }
+
     #[test]
     fn telegram_send_fails_gracefully_with_missing_provider() {
         let ch = TelegramChannel::new("12345".into(), "/nonexistent/binary".into());
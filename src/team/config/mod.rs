@@ -10,7 +10,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result, bail};
 
 use super::TEAM_CONFIG_DIR;
-use crate::agent;
+use crate::agent::{self, AgentAdapter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlanningDirectiveFile {
@@ -139,11 +139,44 @@ impl TeamConfig {
     pub fn load(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("failed to read {}", path.display()))?;
-        let config: TeamConfig = serde_yaml::from_str(&content)
+        let mut config: TeamConfig = serde_yaml::from_str(&content)
             .with_context(|| format!("failed to parse {}", path.display()))?;
+        config.merge_user_agents();
+        agent::custom::register_custom_agents(&config.agents);
         Ok(config)
     }
 
+    /// Fold `~/.batty/agents/*.yaml` declarations into `agents`. Entries in
+    /// team.yaml win over same-named user-level files.
+    fn merge_user_agents(&mut self) {
+        let Some(dir) = agent::custom::user_agents_dir() else {
+            return;
+        };
+        match agent::custom::load_agent_dir(&dir) {
+            Ok(user_agents) => {
+                for (name, agent_config) in user_agents {
+                    self.agents.entry(name).or_insert(agent_config);
+                }
+            }
+            Err(error) => {
+                tracing::warn!(dir = %dir.display(), error = %error, "failed to load user agent definitions");
+            }
+        }
+    }
+
+    /// Whether `name` resolves to a built-in or config-defined agent.
+    pub fn is_known_agent(&self, name: &str) -> bool {
+        self.agents.contains_key(name) || agent::adapter_from_name(name).is_some()
+    }
+
+    fn valid_agent_names(&self) -> String {
+        let mut names: Vec<&str> = agent::KNOWN_AGENT_NAMES.to_vec();
+        let mut custom: Vec<&str> = self.agents.keys().map(String::as_str).collect();
+        custom.sort_unstable();
+        names.extend(custom);
+        names.join(", ")
+    }
+
     /// Validate the team config. Returns an error if invalid.
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() {
@@ -165,11 +198,22 @@ impl TeamConfig {
             bail!("team must have at least one role");
         }
 
-        let valid_agents = agent::KNOWN_AGENT_NAMES.join(", ");
+        let mut custom_agents: Vec<_> = self.agents.iter().collect();
+        custom_agents.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, agent_config) in custom_agents {
+            if agent::builtin_adapter_from_name(name).is_some() {
+                bail!("agents.{name} shadows a built-in agent; pick a different name");
+            }
+            if let Some(problem) = agent_config.lint().into_iter().next() {
+                bail!("agents.{name}: {problem}");
+            }
+        }
+
+        let valid_agents = self.valid_agent_names();
 
         // Validate team-level agent if specified.
         if let Some(team_agent) = self.agent.as_deref() {
-            if agent::adapter_from_name(team_agent).is_none() {
+            if !self.is_known_agent(team_agent) {
                 bail!(
                     "unknown team-level agent '{}'; valid agents: {}",
                     team_agent,
@@ -211,7 +255,7 @@ impl TeamConfig {
             }
 
             if let Some(agent_name) = role.agent.as_deref()
-                && agent::adapter_from_name(agent_name).is_none()
+                && !self.is_known_agent(agent_name)
             {
                 bail!(
                     "role '{}' uses unknown agent '{}'; valid agents: {}",
@@ -221,9 +265,24 @@ impl TeamConfig {
                 );
             }
 
+            if role.role_type == RoleType::User && !role.failover.is_empty() {
+                bail!("user role '{}' cannot configure failover", role.name);
+            }
+            for fallback in &role.failover {
+                if !self.is_known_agent(&fallback.agent) {
+                    bail!(
+                        "role '{}' failover uses unknown agent '{}'; valid agents: {}",
+                        role.name,
+                        fallback.agent,
+                        valid_agents
+                    );
+                }
+            }
+
             for (instance_name, override_cfg) in &role.instance_overrides {
                 if let Some(agent_name) = override_cfg.agent.as_deref()
                     && !super::multi_provider::is_known_instance_override_backend(agent_name)
+                    && !self.agents.contains_key(agent_name)
                 {
                     bail!(
                         "role '{}' instance override '{}' uses unknown agent '{}'; valid agents: {}",
@@ -334,6 +393,154 @@ impl TeamConfig {
             }
         }
 
+        for role_name in self.budget.roles.keys() {
+            if !role_names.contains(role_name.as_str()) {
+                bail!(
+                    "budget.roles references unknown role '{}'; defined roles: {}",
+                    role_name,
+                    all_role_names.join(", ")
+                );
+            }
+        }
+        let budget_scopes =
+            self.budget
+                .roles
+                .iter()
+                .map(|(name, limit)| (format!("budget.roles.{name}"), limit))
+                .chain(
+                    self.budget.task_priority.iter().map(|(priority, limit)| {
+                        (format!("budget.task_priority.{priority}"), limit)
+                    }),
+                )
+                .chain(
+                    self.budget
+                        .run
+                        .iter()
+                        .map(|limit| ("budget.run".to_string(), limit)),
+                )
+                .chain(
+                    self.budget
+                        .day
+                        .iter()
+                        .map(|limit| ("budget.day".to_string(), limit)),
+                );
+        for (scope, limit) in budget_scopes {
+            validate_budget_limit(&scope, limit)?;
+        }
+
+        if self.api.enabled {
+            if self.api.socket.is_none() {
+                match self.api.listen.parse::<std::net::SocketAddr>() {
+                    Ok(addr) if addr.ip().is_loopback() => {}
+                    Ok(addr) => bail!(
+                        "api.listen '{}' is not a loopback address; the control API only binds to 127.0.0.1 or ::1",
+                        addr
+                    ),
+                    Err(_) => bail!(
+                        "api.listen '{}' is invalid; expected host:port such as 127.0.0.1:8788",
+                        self.api.listen
+                    ),
+                }
+            }
+            if !is_valid_env_name(&self.api.token_env) {
+                bail!(
+                    "api.token_env '{}' is invalid; expected shell env name",
+                    self.api.token_env
+                );
+            }
+        }
+
+        if self.prometheus.enabled
+            && self
+                .prometheus
+                .listen
+                .parse::<std::net::SocketAddr>()
+                .is_err()
+        {
+            bail!(
+                "prometheus.listen '{}' is invalid; expected host:port such as 127.0.0.1:9464",
+                self.prometheus.listen
+            );
+        }
+
+        if self.traces.enabled {
+            match self.traces.exporter {
+                TraceExporterKind::Otlp => {
+                    if !self.traces.endpoint.starts_with("http://")
+                        && !self.traces.endpoint.starts_with("https://")
+                    {
+                        bail!(
+                            "traces.endpoint '{}' is invalid; expected an http(s) URL such as http://127.0.0.1:4318/v1/traces",
+                            self.traces.endpoint
+                        );
+                    }
+                }
+                TraceExporterKind::File => {
+                    if self.traces.path.trim().is_empty() {
+                        bail!("traces.path must not be empty when traces.exporter is 'file'");
+                    }
+                }
+            }
+            if self.traces.service_name.trim().is_empty() {
+                bail!("traces.service_name must not be empty");
+            }
+        }
+
+        if self.forge.enabled {
+            if self
+                .forge
+                .repo
+                .as_deref()
+                .is_none_or(|repo| repo.trim().is_empty())
+            {
+                bail!("forge.repo is required when forge.enabled is true");
+            }
+            if !is_valid_env_name(self.forge.token_env()) {
+                bail!(
+                    "forge.token_env '{}' is invalid; expected shell env name",
+                    self.forge.token_env()
+                );
+            }
+            if self.forge.sync_interval_secs == 0 {
+                bail!("forge.sync_interval_secs must be greater than zero");
+            }
+        }
+
+        let mut webhook_names = HashSet::new();
+        for sink in &self.webhooks.sinks {
+            if sink.name.trim().is_empty() {
+                bail!("webhooks.sinks entries need a name");
+            }
+            if !webhook_names.insert(sink.name.as_str()) {
+                bail!("webhooks.sinks has duplicate name '{}'", sink.name);
+            }
+            if !sink.url.starts_with("http://") && !sink.url.starts_with("https://") {
+                bail!(
+                    "webhooks sink '{}' url '{}' must start with http:// or https://",
+                    sink.name,
+                    sink.url
+                );
+            }
+            if let Some(env) = sink.secret_env.as_deref() {
+                if !is_valid_env_name(env) {
+                    bail!(
+                        "webhooks sink '{}' secret_env '{env}' is invalid; expected shell env name",
+                        sink.name
+                    );
+                }
+                if std::env::var(env).map_or(true, |secret| secret.is_empty()) {
+                    bail!(
+                        "webhooks sink '{}' secret_env '{env}' is not set; export it or drop \
+                         secret_env to send unsigned payloads",
+                        sink.name
+                    );
+                }
+            }
+        }
+        if !self.webhooks.sinks.is_empty() && self.webhooks.max_attempts == 0 {
+            bail!("webhooks.max_attempts must be greater than zero");
+        }
+
         Ok(())
     }
 
@@ -374,9 +581,28 @@ impl TeamConfig {
             return checks;
         }
 
-        // 3. Team-level agent
+        // 3. Custom agent definitions
+        let mut custom_agents: Vec<_> = self.agents.iter().collect();
+        custom_agents.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, agent_config) in custom_agents {
+            let mut problems = agent_config.lint();
+            if agent::builtin_adapter_from_name(name).is_some() {
+                problems.insert(0, "shadows a built-in agent".to_string());
+            }
+            checks.push(ValidationCheck {
+                name: format!("custom_agent:{name}"),
+                passed: problems.is_empty(),
+                detail: if problems.is_empty() {
+                    format!("custom agent '{name}' is valid")
+                } else {
+                    format!("custom agent '{name}': {}", problems.join("; "))
+                },
+            });
+        }
+
+        // 4. Team-level agent
         let team_agent_ok = match self.agent.as_deref() {
-            Some(name) => agent::adapter_from_name(name).is_some(),
+            Some(name) => self.is_known_agent(name),
             None => true,
         };
         checks.push(ValidationCheck {
@@ -389,7 +615,7 @@ impl TeamConfig {
             },
         });
 
-        // 4. Per-role checks
+        // 5. Per-role checks
         let mut role_names: HashSet<&str> = HashSet::new();
         for role in &self.roles {
             let unique = role_names.insert(&role.name);
@@ -421,7 +647,7 @@ impl TeamConfig {
             });
 
             if let Some(agent_name) = role.agent.as_deref() {
-                let valid = agent::adapter_from_name(agent_name).is_some();
+                let valid = self.is_known_agent(agent_name);
                 checks.push(ValidationCheck {
                     name: format!("role_agent_valid:{}", role.name),
                     passed: valid,
@@ -436,7 +662,8 @@ impl TeamConfig {
             for (instance_name, override_cfg) in &role.instance_overrides {
                 if let Some(agent_name) = override_cfg.agent.as_deref() {
                     let valid =
-                        super::multi_provider::is_known_instance_override_backend(agent_name);
+                        super::multi_provider::is_known_instance_override_backend(agent_name)
+                            || self.agents.contains_key(agent_name);
                     checks.push(ValidationCheck {
                         name: format!("role_instance_agent_valid:{}:{}", role.name, instance_name),
                         passed: valid,
@@ -463,7 +690,7 @@ impl TeamConfig {
             });
         }
 
-        // 5. talks_to references
+        // 6. talks_to references
         for role in &self.roles {
             for target in &role.talks_to {
                 let valid = role_names.contains(target.as_str());
@@ -482,7 +709,7 @@ impl TeamConfig {
             }
         }
 
-        // 6. automation_sender
+        // 7. automation_sender
         if let Some(sender) = &self.automation_sender {
             let valid = role_names.contains(sender.as_str()) || sender == "human";
             checks.push(ValidationCheck {
@@ -496,7 +723,7 @@ impl TeamConfig {
             });
         }
 
-        // 7. Layout zones
+        // 8. Layout zones
         if let Some(layout) = &self.layout {
             let total_pct: u32 = layout.zones.iter().map(|z| z.width_pct).sum();
             let valid = total_pct <= 100;
@@ -511,7 +738,7 @@ impl TeamConfig {
             });
         }
 
-        // 8. Backend health checks (warnings, not failures)
+        // 9. Backend health checks (warnings, not failures)
         for (agent_name, health) in self.backend_health_results() {
             let healthy = health.is_healthy();
             checks.push(ValidationCheck {
@@ -538,8 +765,14 @@ impl TeamConfig {
         for role in &self.roles {
             if let Some(agent_name) = self.resolve_agent(role) {
                 if seen.insert(agent_name.clone()) {
-                    let health = agent::health_check_by_name(&agent_name)
-                        .unwrap_or(agent::BackendHealth::Unreachable);
+                    let health = match self.agents.get(&agent_name) {
+                        Some(custom) => {
+                            agent::custom::CustomAgentAdapter::new(&agent_name, custom.clone())
+                                .health_check()
+                        }
+                        None => agent::health_check_by_name(&agent_name)
+                            .unwrap_or(agent::BackendHealth::Unreachable),
+                    };
                     results.push((agent_name, health));
                 }
             }
@@ -592,6 +825,25 @@ pub struct ClaudeAuth {
     pub env: Vec<String>,
 }
 
+fn validate_budget_limit(scope: &str, limit: &BudgetLimit) -> Result<()> {
+    for (field, value) in [("soft_usd", limit.soft_usd), ("hard_usd", limit.hard_usd)] {
+        if value.is_some_and(|usd| !usd.is_finite() || usd < 0.0) {
+            bail!("{scope}.{field} must be a non-negative number");
+        }
+    }
+    if let (Some(soft), Some(hard)) = (limit.soft_tokens, limit.hard_tokens)
+        && soft > hard
+    {
+        bail!("{scope}.soft_tokens ({soft}) exceeds hard_tokens ({hard})");
+    }
+    if let (Some(soft), Some(hard)) = (limit.soft_usd, limit.hard_usd)
+        && soft > hard
+    {
+        bail!("{scope}.soft_usd ({soft}) exceeds hard_usd ({hard})");
+    }
+    Ok(())
+}
+
 fn is_valid_env_name(value: &str) -> bool {
     let mut chars = value.chars();
     match chars.next() {
@@ -182,6 +182,49 @@ roles:
     assert_eq!(channel_config.allowed_user_ids, vec![170281556, 170281557]);
 }
 
+#[test]
+fn parse_channel_permissions_by_level() {
+    let yaml = r#"
+name: test-team
+roles:
+  - name: human
+    role_type: user
+    channel: slack
+    channel_config:
+      target: C0123
+      allowed_user_ids: [100]
+      permissions:
+        viewer: [U0VIEW, 200]
+        operator: [U0OPS]
+        admin: [U0ADMIN]
+  - name: architect
+    role_type: architect
+    agent: claude
+"#;
+
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    let channel_config = config.roles[0].channel_config.as_ref().unwrap();
+    assert_eq!(channel_config.permissions.viewer, vec!["U0VIEW", "200"]);
+    assert_eq!(
+        channel_config.permission_for("U0VIEW"),
+        Some(crate::team::operator::Permission::Viewer)
+    );
+    assert_eq!(
+        channel_config.permission_for("U0OPS"),
+        Some(crate::team::operator::Permission::Operator)
+    );
+    assert_eq!(
+        channel_config.permission_for("U0ADMIN"),
+        Some(crate::team::operator::Permission::Admin)
+    );
+    assert_eq!(
+        channel_config.permission_for("100"),
+        Some(crate::team::operator::Permission::Admin)
+    );
+    assert_eq!(channel_config.permission_for("U0NOBODY"), None);
+    assert_eq!(channel_config.numeric_user_ids(), vec![100, 200]);
+}
+
 #[test]
 fn planning_directive_path_uses_team_config_directory() {
     let root = std::path::Path::new("/tmp/project");
@@ -478,6 +521,44 @@ roles:
     assert!(error.contains("valid agents:"));
 }
 
+#[test]
+fn parse_role_failover_list() {
+    let yaml = r#"
+name: test-team
+roles:
+  - name: engineer
+    role_type: engineer
+    agent: claude
+    failover:
+      - agent: codex
+        model: gpt-5
+      - agent: kiro
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(config.validate().is_ok());
+    let failover = &config.roles[0].failover;
+    assert_eq!(failover.len(), 2);
+    assert_eq!(failover[0].agent, "codex");
+    assert_eq!(failover[0].model.as_deref(), Some("gpt-5"));
+    assert_eq!(failover[1].model, None);
+}
+
+#[test]
+fn validate_rejects_unknown_failover_agent() {
+    let yaml = r#"
+name: test-team
+roles:
+  - name: engineer
+    role_type: engineer
+    agent: claude
+    failover:
+      - agent: mystery
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    let error = config.validate().unwrap_err().to_string();
+    assert!(error.contains("failover uses unknown agent 'mystery'"));
+}
+
 #[test]
 fn validate_rejects_unknown_instance_override_agent() {
     let yaml = r#"
@@ -1659,3 +1740,236 @@ roles:
     assert_eq!(override_cfg.agent.as_deref(), Some("gemini"));
     assert_eq!(override_cfg.model.as_deref(), Some("claude-opus-4.6-1m"));
 }
+
+#[test]
+fn api_config_defaults_to_disabled_loopback() {
+    let config: TeamConfig = serde_yaml::from_str(minimal_yaml()).unwrap();
+    assert!(!config.api.enabled);
+    assert_eq!(config.api.listen, "127.0.0.1:8788");
+    assert_eq!(config.api.token_env, "BATTY_API_TOKEN");
+    assert!(config.api.socket.is_none());
+}
+
+#[test]
+fn validate_rejects_non_loopback_api_listen() {
+    let yaml = format!(
+        "{}api:\n  enabled: true\n  listen: 0.0.0.0:8788\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("not a loopback address"));
+
+    let yaml = format!(
+        "{}api:\n  enabled: true\n  socket: .batty/api.sock\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    config.validate().unwrap();
+}
+
+#[test]
+fn budget_config_parses_scopes_and_hard_action() {
+    let yaml = format!(
+        "{}budget:\n  roles:\n    engineer:\n      soft_tokens: 100000\n      hard_usd: 5.0\n  task_priority:\n    high:\n      hard_tokens: 500000\n  run:\n    hard_usd: 50\n  on_hard_limit: shutdown\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    assert!(config.budget.is_enabled());
+    assert_eq!(config.budget.roles["engineer"].soft_tokens, Some(100_000));
+    assert_eq!(config.budget.roles["engineer"].hard_usd, Some(5.0));
+    assert_eq!(
+        config.budget.task_priority["high"].hard_tokens,
+        Some(500_000)
+    );
+    assert_eq!(config.budget.on_hard_limit, BudgetHardAction::Shutdown);
+    config.validate().unwrap();
+
+    let config: TeamConfig = serde_yaml::from_str(minimal_yaml()).unwrap();
+    assert!(!config.budget.is_enabled());
+    assert_eq!(config.budget.on_hard_limit, BudgetHardAction::PauseDispatch);
+}
+
+#[test]
+fn validate_rejects_bad_budget_limits() {
+    let yaml = format!(
+        "{}budget:\n  roles:\n    reviewer:\n      hard_tokens: 10\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("budget.roles references unknown role 'reviewer'"));
+
+    let yaml = format!(
+        "{}budget:\n  run:\n    soft_tokens: 10\n    hard_tokens: 5\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("budget.run.soft_tokens (10) exceeds hard_tokens (5)"));
+
+    let yaml = format!("{}budget:\n  day:\n    hard_usd: -1\n", minimal_yaml());
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("budget.day.hard_usd must be a non-negative number"));
+}
+
+#[test]
+fn forge_config_parses_provider_delivery_and_defaults() {
+    let config: TeamConfig = serde_yaml::from_str(minimal_yaml()).unwrap();
+    assert!(!config.forge.enabled);
+    assert!(!config.forge.delivers_pull_requests());
+    assert_eq!(config.forge.api_url(), "https://api.github.com");
+    assert_eq!(config.forge.token_env(), "GITHUB_TOKEN");
+    assert_eq!(config.forge.import_label, "batty");
+
+    let yaml = format!(
+        "{}forge:\n  enabled: true\n  provider: gitlab\n  repo: acme/widgets\n  delivery: pull_request\n  assignees:\n    eng-1: octo\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(config.forge.provider, ForgeProvider::Gitlab);
+    assert_eq!(config.forge.api_url(), "https://gitlab.com/api/v4");
+    assert_eq!(config.forge.token_env(), "GITLAB_TOKEN");
+    assert!(config.forge.delivers_pull_requests());
+    assert_eq!(config.forge.assignees["eng-1"], "octo");
+    config.validate().unwrap();
+}
+
+#[test]
+fn validate_rejects_enabled_forge_without_repo() {
+    let yaml = format!("{}forge:\n  enabled: true\n", minimal_yaml());
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("forge.repo is required"));
+}
+
+#[test]
+#[serial_test::serial]
+fn webhooks_config_parses_filters_and_defaults() {
+    let _secret = crate::team::test_support::EnvVarGuard::set("BATTY_WEBHOOK_SECRET", "s3cret");
+    let config: TeamConfig = serde_yaml::from_str(minimal_yaml()).unwrap();
+    assert!(config.webhooks.sinks.is_empty());
+    assert_eq!(config.webhooks.max_attempts, 8);
+
+    let yaml = format!(
+        "{}webhooks:\n  max_attempts: 3\n  sinks:\n    - name: pager\n      url: https://hooks.example.com/batty\n      secret_env: BATTY_WEBHOOK_SECRET\n      topics: [escalation, stall]\n      tasks: [41, \"42\"]\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let sink = &config.webhooks.sinks[0];
+    assert_eq!(config.webhooks.max_attempts, 3);
+    assert_eq!(config.webhooks.max_delay_secs, 900);
+    assert_eq!(
+        sink.topics,
+        vec![
+            crate::team::openclaw::OpenClawEventTopic::Escalation,
+            crate::team::openclaw::OpenClawEventTopic::Stall
+        ]
+    );
+    assert_eq!(sink.tasks, vec!["41", "42"]);
+    config.validate().unwrap();
+}
+
+#[test]
+fn validate_rejects_bad_webhook_sinks() {
+    for (sinks, expected) in [
+        (
+            "    - name: a\n      url: ftp://x\n",
+            "must start with http",
+        ),
+        (
+            "    - name: a\n      url: http://x\n    - name: a\n      url: http://y\n",
+            "duplicate name 'a'",
+        ),
+        (
+            "    - name: a\n      url: http://x\n      secret_env: bad-name\n",
+            "secret_env 'bad-name' is invalid",
+        ),
+        (
+            "    - name: a\n      url: http://x\n      secret_env: BATTY_WEBHOOK_SECRET_TEST_UNSET\n",
+            "secret_env 'BATTY_WEBHOOK_SECRET_TEST_UNSET' is not set",
+        ),
+    ] {
+        let yaml = format!("{}webhooks:\n  sinks:\n{sinks}", minimal_yaml());
+        let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+}
+
+#[test]
+fn custom_agents_parse_and_validate_as_role_agents() {
+    let yaml = r#"
+name: test-team
+agents:
+  aider:
+    command: "aider --message {prompt}"
+    resume_args: "--restore-chat-history"
+    raw_mode: false
+    patterns:
+      prompt: ['^(\w+\s*)?>\s*$']
+      context_exhausted: ['exceeds the .* token limit']
+      quota: ['(?i)rate limit']
+roles:
+  - name: engineer
+    role_type: engineer
+    agent: aider
+    instances: 2
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    let aider = &config.agents["aider"];
+    assert_eq!(aider.command, "aider --message {prompt}");
+    assert!(!aider.raw_mode);
+    assert_eq!(aider.instruction_files, vec!["AGENTS.md", "CLAUDE.md"]);
+    assert!(config.is_known_agent("aider"));
+    config.validate().unwrap();
+
+    let checks = config.validate_verbose();
+    assert!(
+        checks
+            .iter()
+            .any(|check| check.name == "custom_agent:aider" && check.passed)
+    );
+    assert!(
+        checks
+            .iter()
+            .any(|check| check.name == "role_agent_valid:engineer" && check.passed)
+    );
+}
+
+#[test]
+fn validate_rejects_broken_custom_agents() {
+    let yaml = format!(
+        "{}agents:\n  goose:\n    command: \"goose run {{task}}\"\n    patterns:\n      prompt: ['([']\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(
+        err.contains("agents.goose: command uses unknown placeholder `{task}`"),
+        "{err}"
+    );
+    let check = config
+        .validate_verbose()
+        .into_iter()
+        .find(|check| check.name == "custom_agent:goose")
+        .unwrap();
+    assert!(!check.passed);
+    assert!(
+        check.detail.contains("is not a valid regex"),
+        "{}",
+        check.detail
+    );
+
+    let yaml = format!(
+        "{}agents:\n  codex:\n    command: \"codex\"\n    patterns:\n      prompt: ['^> $']\n",
+        minimal_yaml()
+    );
+    let config: TeamConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(
+        err.contains("agents.codex shadows a built-in agent"),
+        "{err}"
+    );
+}
@@ -554,6 +554,107 @@ roles:
     assert_eq!(allocation.experience_bonus, 2);
 }
 
+#[test]
+fn parse_workflow_policy_learned_allocation_strategy() {
+    let yaml = r#"
+name: test
+workflow_policy:
+  allocation:
+    strategy: learned
+roles:
+  - name: worker
+    role_type: engineer
+    agent: codex
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        config.workflow_policy.allocation.strategy,
+        AllocationStrategy::Learned
+    );
+}
+
+#[test]
+fn parse_workflow_policy_flaky_tests_defaults_and_overrides() {
+    let yaml = r#"
+name: test
+workflow_policy:
+  flaky_tests:
+    enabled: true
+    retries: 1
+roles:
+  - name: worker
+    role_type: engineer
+    agent: codex
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    let flaky = &config.workflow_policy.flaky_tests;
+    assert!(flaky.enabled);
+    assert_eq!(flaky.retries, 1);
+    assert_eq!(flaky.quarantine_after, 3);
+    assert_eq!(flaky.stable_runs, 5);
+    assert!(!WorkflowPolicy::default().flaky_tests.enabled);
+}
+
+#[test]
+fn parse_workflow_policy_approvals_defaults_and_overrides() {
+    let yaml = r#"
+name: test
+workflow_policy:
+  approvals:
+    enabled: true
+    actions: [release, delete_branch]
+    expire_secs: 600
+roles:
+  - name: worker
+    role_type: engineer
+    agent: codex
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    let approvals = &config.workflow_policy.approvals;
+    assert!(approvals.requires(crate::team::approval::ApprovalAction::Release));
+    assert!(!approvals.requires(crate::team::approval::ApprovalAction::Scale));
+    assert_eq!(approvals.expire_secs, 600);
+    assert_eq!(approvals.scale_max_engineers, 8);
+    assert!(
+        approvals
+            .dependency_files
+            .iter()
+            .any(|file| file == "go.sum")
+    );
+    assert!(
+        !WorkflowPolicy::default()
+            .approvals
+            .requires(crate::team::approval::ApprovalAction::Release)
+    );
+}
+
+#[test]
+fn parse_role_sandbox_policy() {
+    let yaml = r#"
+name: test
+roles:
+  - name: worker
+    role_type: engineer
+    agent: codex
+    sandbox:
+      backend: unshare
+      network: none
+      writable: ["~/.codex", "scratch"]
+  - name: lead
+    role_type: manager
+    agent: claude
+"#;
+    let config: TeamConfig = serde_yaml::from_str(yaml).unwrap();
+    let sandbox = config.roles[0].sandbox.as_ref().unwrap();
+    assert_eq!(
+        sandbox.backend,
+        crate::shim::sandbox::SandboxBackend::Unshare
+    );
+    assert_eq!(sandbox.network, crate::shim::sandbox::SandboxNetwork::None);
+    assert_eq!(sandbox.writable, vec!["~/.codex", "scratch"]);
+    assert!(config.roles[1].sandbox.is_none());
+}
+
 #[test]
 fn parse_workflow_policy_file_level_locks_override() {
     let yaml = r#"
@@ -660,6 +761,8 @@ fn parse_auto_merge_policy_defaults() {
     assert!(am.require_tests_pass);
     assert!(am.post_merge_verify);
     assert!(!am.sensitive_paths.is_empty());
+    assert_eq!(am.train_size, 1);
+    assert_eq!(am.train_bisection, TrainBisection::Binary);
 }
 
 #[test]
@@ -676,6 +779,8 @@ workflow_policy:
     require_tests_pass: false
     post_merge_verify: false
     sensitive_paths: ["secrets.yaml"]
+    train_size: 6
+    train_bisection: linear
 roles:
   - name: worker
     role_type: engineer
@@ -691,6 +796,8 @@ roles:
     assert!(!am.require_tests_pass);
     assert!(!am.post_merge_verify);
     assert_eq!(am.sensitive_paths, vec!["secrets.yaml"]);
+    assert_eq!(am.train_size, 6);
+    assert_eq!(am.train_bisection, TrainBisection::Linear);
 }
 
 #[test]
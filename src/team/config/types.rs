@@ -2,9 +2,11 @@
 
 use std::collections::HashMap;
 
-use serde::{Deserialize, Deserializer, de};
+use serde::{Deserialize, Deserializer, Serialize, de};
 
 use super::super::DEFAULT_EVENT_LOG_MAX_BYTES;
+use crate::team::openclaw::OpenClawEventTopic;
+use crate::team::operator::Permission;
 
 #[derive(Debug, Clone)]
 pub struct TeamConfig {
@@ -31,6 +33,20 @@ pub struct TeamConfig {
     pub workflow_policy: WorkflowPolicy,
     pub cost: CostConfig,
     pub grafana: GrafanaConfig,
+    /// Optional local HTTP/JSON control API served by the daemon.
+    pub api: ApiConfig,
+    /// Optional Prometheus `/metrics` listener served by the daemon.
+    pub prometheus: PrometheusConfig,
+    /// Optional OpenTelemetry export of task lifecycle traces.
+    pub traces: TracesConfig,
+    /// Live token and dollar budgets enforced by the daemon.
+    pub budget: BudgetConfig,
+    /// Two-way board sync with GitHub or GitLab issues and pull requests.
+    pub forge: ForgeConfig,
+    /// Outbound HTTP sinks that receive team events as OpenClaw envelopes.
+    pub webhooks: WebhooksConfig,
+    /// Config-defined agent backends, keyed by the name roles reference.
+    pub agents: HashMap<String, crate::agent::custom::CustomAgentConfig>,
     /// When true, agents are spawned as shim subprocesses instead of
     /// directly in tmux panes. The shim manages PTY, state classification,
     /// and message delivery over a structured channel.
@@ -96,6 +112,20 @@ struct TeamConfigWire {
     #[serde(default)]
     pub grafana: GrafanaConfig,
     #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub prometheus: PrometheusConfig,
+    #[serde(default)]
+    pub traces: TracesConfig,
+    #[serde(default)]
+    pub budget: BudgetConfig,
+    #[serde(default)]
+    pub forge: ForgeConfig,
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+    #[serde(default)]
+    pub agents: HashMap<String, crate::agent::custom::CustomAgentConfig>,
+    #[serde(default)]
     pub use_shim: bool,
     #[serde(default = "default_use_sdk_mode")]
     pub use_sdk_mode: bool,
@@ -147,6 +177,13 @@ impl From<TeamConfigWire> for TeamConfig {
             workflow_policy: wire.workflow_policy,
             cost: wire.cost,
             grafana: wire.grafana,
+            api: wire.api,
+            prometheus: wire.prometheus,
+            traces: wire.traces,
+            budget: wire.budget,
+            forge: wire.forge,
+            webhooks: wire.webhooks,
+            agents: wire.agents,
             use_shim: wire.use_shim,
             use_sdk_mode: wire.use_sdk_mode,
             auto_respawn_on_crash: wire.auto_respawn_on_crash,
@@ -192,6 +229,369 @@ fn default_grafana_port() -> u16 {
     3000
 }
 
+/// Local control API exposed by the running daemon.
+///
+/// The API only ever listens on a loopback address or a Unix socket, and
+/// every request must carry `Authorization: Bearer <token>`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Loopback `host:port` for the TCP listener. Ignored when `socket` is set.
+    #[serde(default = "default_api_listen")]
+    pub listen: String,
+    /// Unix socket path (relative paths resolve against the project root).
+    #[serde(default)]
+    pub socket: Option<String>,
+    /// Environment variable holding the bearer token. When unset, the daemon
+    /// generates a token in `.batty/api_token`.
+    #[serde(default = "default_api_token_env")]
+    pub token_env: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_api_listen(),
+            socket: None,
+            token_env: default_api_token_env(),
+        }
+    }
+}
+
+fn default_api_listen() -> String {
+    "127.0.0.1:8788".to_string()
+}
+
+fn default_api_token_env() -> String {
+    "BATTY_API_TOKEN".to_string()
+}
+
+/// Prometheus text exposition served by the running daemon.
+///
+/// `GET /metrics` is answered from the daemon's in-memory state, refreshed
+/// once per poll tick. The endpoint is read-only and unauthenticated, so
+/// binding beyond loopback exposes member names and queue depths to anyone
+/// who can reach the port.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrometheusConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// `host:port` for the `/metrics` listener.
+    #[serde(default = "default_prometheus_listen")]
+    pub listen: String,
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_prometheus_listen(),
+        }
+    }
+}
+
+fn default_prometheus_listen() -> String {
+    "127.0.0.1:9464".to_string()
+}
+
+/// OpenTelemetry traces for the task lifecycle.
+///
+/// Each task becomes one trace with a span per lifecycle phase. Spans are
+/// exported as OTLP/HTTP JSON once per poll tick, either POSTed to a
+/// collector's `/v1/traces` endpoint or appended to a local JSON-lines file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TracesConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub exporter: TraceExporterKind,
+    /// OTLP/HTTP traces endpoint, used by the `otlp` exporter.
+    #[serde(default = "default_traces_endpoint")]
+    pub endpoint: String,
+    /// Output file for the `file` exporter, relative to the project root.
+    #[serde(default = "default_traces_path")]
+    pub path: String,
+    /// `service.name` resource attribute on every exported span.
+    #[serde(default = "default_traces_service_name")]
+    pub service_name: String,
+    /// Per-request timeout for the `otlp` exporter.
+    #[serde(default = "default_traces_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for TracesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exporter: TraceExporterKind::default(),
+            endpoint: default_traces_endpoint(),
+            path: default_traces_path(),
+            service_name: default_traces_service_name(),
+            timeout_secs: default_traces_timeout_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceExporterKind {
+    /// Append OTLP JSON requests to a local file for offline analysis.
+    #[default]
+    File,
+    /// POST OTLP JSON requests to `endpoint`.
+    Otlp,
+}
+
+fn default_traces_endpoint() -> String {
+    "http://127.0.0.1:4318/v1/traces".to_string()
+}
+
+fn default_traces_path() -> String {
+    ".batty/traces.jsonl".to_string()
+}
+
+fn default_traces_service_name() -> String {
+    "batty".to_string()
+}
+
+fn default_traces_timeout_secs() -> u64 {
+    5
+}
+
+/// Two-way sync between the board and a GitHub or GitLab project.
+///
+/// Issues carrying `import_label` become board tasks; task status, assignee,
+/// and comments are mirrored back. With `delivery: pull_request`, verified
+/// engineer branches are pushed and opened as pull requests (merge requests
+/// on GitLab) instead of being merged into trunk locally.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForgeConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub provider: ForgeProvider,
+    /// `owner/name` on GitHub, or the full project path on GitLab.
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// REST API base URL. Defaults to the provider's public API.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Environment variable holding the API token. Defaults to
+    /// `GITHUB_TOKEN` or `GITLAB_TOKEN`.
+    #[serde(default)]
+    pub token_env: Option<String>,
+    /// Only issues with this label are imported onto the board.
+    #[serde(default = "default_forge_import_label")]
+    pub import_label: String,
+    #[serde(default = "default_forge_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+    #[serde(default)]
+    pub delivery: DeliveryMode,
+    /// Git remote engineer branches are pushed to in `pull_request` mode.
+    #[serde(default = "default_forge_remote")]
+    pub remote: String,
+    /// Board member name to forge login. Unmapped names pass through as-is.
+    #[serde(default)]
+    pub assignees: HashMap<String, String>,
+}
+
+impl Default for ForgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: ForgeProvider::default(),
+            repo: None,
+            api_url: None,
+            token_env: None,
+            import_label: default_forge_import_label(),
+            sync_interval_secs: default_forge_sync_interval_secs(),
+            delivery: DeliveryMode::default(),
+            remote: default_forge_remote(),
+            assignees: HashMap::new(),
+        }
+    }
+}
+
+impl ForgeConfig {
+    pub fn api_url(&self) -> &str {
+        self.api_url
+            .as_deref()
+            .unwrap_or(match self.provider {
+                ForgeProvider::Github => "https://api.github.com",
+                ForgeProvider::Gitlab => "https://gitlab.com/api/v4",
+            })
+            .trim_end_matches('/')
+    }
+
+    pub fn token_env(&self) -> &str {
+        self.token_env.as_deref().unwrap_or(match self.provider {
+            ForgeProvider::Github => "GITHUB_TOKEN",
+            ForgeProvider::Gitlab => "GITLAB_TOKEN",
+        })
+    }
+
+    /// True when verified branches should become pull requests.
+    pub fn delivers_pull_requests(&self) -> bool {
+        self.enabled && self.delivery == DeliveryMode::PullRequest
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForgeProvider {
+    #[default]
+    Github,
+    Gitlab,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMode {
+    /// Merge verified branches into trunk locally through the merge queue.
+    #[default]
+    Merge,
+    /// Push verified branches and open a pull request per task.
+    PullRequest,
+}
+
+fn default_forge_import_label() -> String {
+    "batty".to_string()
+}
+
+fn default_forge_sync_interval_secs() -> u64 {
+    60
+}
+
+fn default_forge_remote() -> String {
+    "origin".to_string()
+}
+
+/// Outbound webhook sinks for team events.
+///
+/// Each new event that maps to an OpenClaw project-event envelope is queued
+/// once per matching sink in `.batty/webhooks.json` and POSTed as JSON.
+/// Failed deliveries back off exponentially between `base_delay_secs` and
+/// `max_delay_secs`; after `max_attempts`, or on a non-retryable response,
+/// they move to the dead-letter list shown by `batty status --health`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhooksConfig {
+    #[serde(default)]
+    pub sinks: Vec<WebhookSinkConfig>,
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_webhook_base_delay_secs")]
+    pub base_delay_secs: u64,
+    #[serde(default = "default_webhook_max_delay_secs")]
+    pub max_delay_secs: u64,
+    /// Per-request timeout.
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            sinks: Vec::new(),
+            max_attempts: default_webhook_max_attempts(),
+            base_delay_secs: default_webhook_base_delay_secs(),
+            max_delay_secs: default_webhook_max_delay_secs(),
+            timeout_secs: default_webhook_timeout_secs(),
+        }
+    }
+}
+
+/// One webhook endpoint. Empty filter lists match everything; non-empty
+/// lists must all match.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookSinkConfig {
+    pub name: String,
+    pub url: String,
+    /// Environment variable holding the HMAC-SHA256 signing secret. Payloads
+    /// are unsigned when unset.
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<OpenClawEventTopic>,
+    /// Envelope `eventType` values, e.g. `task.escalated`.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    /// Task IDs; numbers and strings are both accepted.
+    #[serde(default, deserialize_with = "deserialize_user_id_strings")]
+    pub tasks: Vec<String>,
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    8
+}
+
+fn default_webhook_base_delay_secs() -> u64 {
+    5
+}
+
+fn default_webhook_max_delay_secs() -> u64 {
+    900
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+/// Token and dollar budgets checked live against shim-reported usage.
+///
+/// Role limits apply to each member instance of the role for the current
+/// run; task limits are keyed by board priority and span the task's life.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BudgetConfig {
+    #[serde(default)]
+    pub roles: HashMap<String, BudgetLimit>,
+    #[serde(default)]
+    pub task_priority: HashMap<String, BudgetLimit>,
+    #[serde(default)]
+    pub run: Option<BudgetLimit>,
+    #[serde(default)]
+    pub day: Option<BudgetLimit>,
+    /// What the daemon does when a hard limit is crossed.
+    #[serde(default)]
+    pub on_hard_limit: BudgetHardAction,
+}
+
+impl BudgetConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.roles.is_empty()
+            || !self.task_priority.is_empty()
+            || self.run.is_some()
+            || self.day.is_some()
+    }
+}
+
+/// Soft and hard ceilings for one budget scope. Any unset field is unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct BudgetLimit {
+    #[serde(default)]
+    pub soft_tokens: Option<u64>,
+    #[serde(default)]
+    pub hard_tokens: Option<u64>,
+    #[serde(default)]
+    pub soft_usd: Option<f64>,
+    #[serde(default)]
+    pub hard_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetHardAction {
+    /// Stop dispatching new work to the affected member (or the whole team
+    /// for run/day limits) but leave running agents alone.
+    #[default]
+    PauseDispatch,
+    /// Also checkpoint and shut down the affected agent shims.
+    Shutdown,
+}
+
 fn default_use_sdk_mode() -> bool {
     true
 }
@@ -303,6 +703,10 @@ pub struct WorkflowPolicy {
     #[serde(default)]
     pub main_smoke: MainSmokePolicy,
     #[serde(default)]
+    pub flaky_tests: FlakyTestPolicy,
+    #[serde(default)]
+    pub approvals: ApprovalPolicy,
+    #[serde(default)]
     pub auto_merge: AutoMergePolicy,
     /// When true, context exhaustion restarts capture a work summary and
     /// inject it into the new agent session so it can continue where the
@@ -395,6 +799,8 @@ impl Default for WorkflowPolicy {
             claim_ttl: ClaimTtlPolicy::default(),
             allocation: AllocationPolicy::default(),
             main_smoke: MainSmokePolicy::default(),
+            flaky_tests: FlakyTestPolicy::default(),
+            approvals: ApprovalPolicy::default(),
             auto_merge: AutoMergePolicy::default(),
             context_handoff_enabled: default_context_handoff_enabled(),
             handoff_screen_history: default_handoff_screen_history(),
@@ -408,6 +814,9 @@ impl Default for WorkflowPolicy {
 pub enum AllocationStrategy {
     RoundRobin,
     Scored,
+    /// Score with the model fitted by `batty dispatch train`, falling back to
+    /// the `Scored` heuristic when no usable model exists.
+    Learned,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -439,6 +848,69 @@ impl Default for AllocationPolicy {
     }
 }
 
+/// Merge-gate retries and quarantine for tests that fail without a related diff.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlakyTestPolicy {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_flaky_retries")]
+    pub retries: u32,
+    #[serde(default = "default_flaky_quarantine_after")]
+    pub quarantine_after: u32,
+    #[serde(default = "default_flaky_quarantine_secs")]
+    pub quarantine_secs: u64,
+    #[serde(default = "default_flaky_stable_runs")]
+    pub stable_runs: u32,
+}
+
+impl Default for FlakyTestPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retries: default_flaky_retries(),
+            quarantine_after: default_flaky_quarantine_after(),
+            quarantine_secs: default_flaky_quarantine_secs(),
+            stable_runs: default_flaky_stable_runs(),
+        }
+    }
+}
+
+/// Actions that wait for a human decision before the daemon or CLI acts.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalPolicy {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_approval_actions")]
+    pub actions: Vec<crate::team::approval::ApprovalAction>,
+    /// `batty scale engineers N` needs approval when N exceeds this.
+    #[serde(default = "default_approval_scale_max_engineers")]
+    pub scale_max_engineers: u32,
+    /// Pending approvals expire (count as denied) after this long.
+    #[serde(default = "default_approval_expire_secs")]
+    pub expire_secs: u64,
+    /// File names whose change makes a merge a dependency change.
+    #[serde(default = "default_approval_dependency_files")]
+    pub dependency_files: Vec<String>,
+}
+
+impl ApprovalPolicy {
+    pub fn requires(&self, action: crate::team::approval::ApprovalAction) -> bool {
+        self.enabled && self.actions.contains(&action)
+    }
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            actions: default_approval_actions(),
+            scale_max_engineers: default_approval_scale_max_engineers(),
+            expire_secs: default_approval_expire_secs(),
+            dependency_files: default_approval_dependency_files(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MainSmokePolicy {
     #[serde(default = "default_main_smoke_enabled")]
@@ -475,6 +947,11 @@ pub struct VerificationPolicy {
     pub require_evidence: bool,
     #[serde(default)]
     pub test_command: Option<String>,
+    /// Structured report written by the test command (JUnit XML, vitest JSON,
+    /// `go test -json`, or libtest JSON), relative to the directory tests run
+    /// in. A directory merges every `.xml`/`.json` file inside it.
+    #[serde(default)]
+    pub test_report: Option<String>,
 }
 
 impl Default for VerificationPolicy {
@@ -484,6 +961,7 @@ impl Default for VerificationPolicy {
             auto_run_tests: default_verification_auto_run_tests(),
             require_evidence: default_verification_require_evidence(),
             test_command: None,
+            test_report: None,
         }
     }
 }
@@ -548,6 +1026,52 @@ fn default_allocation_experience_bonus() -> i32 {
     3
 }
 
+fn default_flaky_retries() -> u32 {
+    2
+}
+
+fn default_flaky_quarantine_after() -> u32 {
+    3
+}
+
+fn default_flaky_quarantine_secs() -> u64 {
+    7 * 24 * 3600
+}
+
+fn default_flaky_stable_runs() -> u32 {
+    5
+}
+
+fn default_approval_actions() -> Vec<crate::team::approval::ApprovalAction> {
+    crate::team::approval::ApprovalAction::ALL.to_vec()
+}
+
+fn default_approval_scale_max_engineers() -> u32 {
+    8
+}
+
+fn default_approval_expire_secs() -> u64 {
+    24 * 3600
+}
+
+fn default_approval_dependency_files() -> Vec<String> {
+    [
+        "Cargo.toml",
+        "Cargo.lock",
+        "package.json",
+        "package-lock.json",
+        "pnpm-lock.yaml",
+        "yarn.lock",
+        "go.mod",
+        "go.sum",
+        "pyproject.toml",
+        "requirements.txt",
+        "poetry.lock",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
 fn default_main_smoke_enabled() -> bool {
     true
 }
@@ -626,6 +1150,23 @@ pub struct AutoMergePolicy {
     pub require_tests_pass: bool,
     #[serde(default = "default_post_merge_verify")]
     pub post_merge_verify: bool,
+    /// Maximum queued branches stacked into one merge train and gated by a
+    /// single test run. `1` keeps the serial merge queue.
+    #[serde(default = "default_train_size")]
+    pub train_size: usize,
+    /// How a failing train is split to find the offending branch.
+    #[serde(default)]
+    pub train_bisection: TrainBisection,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrainBisection {
+    /// Halve the train until the first failing prefix is found.
+    #[default]
+    Binary,
+    /// Test each prefix in queue order; cheaper when trains are short.
+    Linear,
 }
 
 fn default_max_diff_lines() -> usize {
@@ -661,6 +1202,9 @@ fn default_require_tests_pass() -> bool {
 fn default_post_merge_verify() -> bool {
     true
 }
+fn default_train_size() -> usize {
+    1
+}
 
 impl Default for AutoMergePolicy {
     fn default() -> Self {
@@ -673,6 +1217,8 @@ impl Default for AutoMergePolicy {
             confidence_threshold: default_confidence_threshold(),
             require_tests_pass: default_require_tests_pass(),
             post_merge_verify: default_post_merge_verify(),
+            train_size: default_train_size(),
+            train_bisection: TrainBisection::default(),
         }
     }
 }
@@ -920,6 +1466,13 @@ pub struct RoleDef {
     pub barrier_group: Option<String>,
     #[serde(default)]
     pub use_worktrees: bool,
+    /// Run this role's agents inside a rootless filesystem/network sandbox.
+    #[serde(default)]
+    pub sandbox: Option<crate::shim::sandbox::SandboxPolicy>,
+    /// Fallback backends, in order, for members whose backend is
+    /// quota-blocked or needs re-authentication.
+    #[serde(default)]
+    pub failover: Vec<BackendFallback>,
 }
 
 impl Default for RoleDef {
@@ -946,10 +1499,20 @@ impl Default for RoleDef {
             owns: Vec::new(),
             barrier_group: None,
             use_worktrees: false,
+            sandbox: None,
+            failover: Vec::new(),
         }
     }
 }
 
+/// One agent/model pair in a role's `failover` list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendFallback {
+    pub agent: String,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct RoleInstanceOverride {
     #[serde(default)]
@@ -985,12 +1548,16 @@ pub struct ChannelConfig {
     /// Can also be set via `BATTY_TELEGRAM_BOT_TOKEN` env var.
     #[serde(default)]
     pub bot_token: Option<String>,
-    /// User IDs allowed to send messages (access control).
+    /// Legacy flat allow-list. Listed users get admin access; prefer
+    /// `permissions` for new configs.
     ///
     /// Accepts either numeric YAML values or quoted strings so the same field
     /// can represent Telegram integers and Discord snowflake IDs.
     #[serde(default, deserialize_with = "deserialize_user_id_list")]
     pub allowed_user_ids: Vec<i64>,
+    /// Operator command access by user ID.
+    #[serde(default)]
+    pub permissions: OperatorPermissions,
     #[serde(default)]
     pub events_channel_id: Option<String>,
     #[serde(default)]
@@ -999,6 +1566,73 @@ pub struct ChannelConfig {
     pub commands_channel_id: Option<String>,
     #[serde(default)]
     pub board_channel_id: Option<String>,
+    /// Slack member IDs (`U…`) allowed to send messages and commands. Slack
+    /// IDs are not numeric, so they live beside `allowed_user_ids`; an empty
+    /// list denies everyone.
+    #[serde(default)]
+    pub allowed_slack_user_ids: Vec<String>,
+    /// Web API base URL override for the Slack provider (proxies and local
+    /// mock servers). Defaults to `https://slack.com/api`.
+    #[serde(default)]
+    pub api_base: Option<String>,
+}
+
+/// User IDs per operator permission level (`channel_config.permissions`).
+/// IDs may be numbers (Telegram, Discord) or strings (Slack `U…` IDs).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OperatorPermissions {
+    #[serde(default, deserialize_with = "deserialize_user_id_strings")]
+    pub viewer: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_user_id_strings")]
+    pub operator: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_user_id_strings")]
+    pub admin: Vec<String>,
+}
+
+impl ChannelConfig {
+    /// Highest operator permission `user_id` holds. Users in the legacy
+    /// allow-lists are admins.
+    pub fn permission_for(&self, user_id: &str) -> Option<Permission> {
+        let listed = |ids: &[String]| ids.iter().any(|id| id == user_id);
+        let legacy = self
+            .allowed_user_ids
+            .iter()
+            .any(|id| id.to_string() == user_id)
+            || listed(&self.allowed_slack_user_ids);
+        if legacy || listed(&self.permissions.admin) {
+            Some(Permission::Admin)
+        } else if listed(&self.permissions.operator) {
+            Some(Permission::Operator)
+        } else if listed(&self.permissions.viewer) {
+            Some(Permission::Viewer)
+        } else {
+            None
+        }
+    }
+
+    /// Every user ID with any permission; the bridges drop everyone else.
+    pub fn authorized_user_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .allowed_user_ids
+            .iter()
+            .map(i64::to_string)
+            .chain(self.allowed_slack_user_ids.iter().cloned())
+            .chain(self.permissions.admin.iter().cloned())
+            .chain(self.permissions.operator.iter().cloned())
+            .chain(self.permissions.viewer.iter().cloned())
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(id.clone()));
+        ids
+    }
+
+    /// [`Self::authorized_user_ids`] for transports with numeric IDs.
+    pub fn numeric_user_ids(&self) -> Vec<i64> {
+        self.authorized_user_ids()
+            .iter()
+            .filter_map(|id| id.parse().ok())
+            .collect()
+    }
 }
 
 #[derive(Deserialize)]
@@ -1033,6 +1667,20 @@ pub enum RoleType {
     Engineer,
 }
 
+fn deserialize_user_id_strings<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Vec::<UserIdValue>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .map(|value| match value {
+            UserIdValue::Integer(id) => id.to_string(),
+            UserIdValue::String(raw) => raw,
+        })
+        .collect())
+}
+
 // --- Default value functions ---
 
 fn default_rotation_threshold() -> u32 {
@@ -109,6 +109,13 @@ mod tests {
             workflow_policy: Default::default(),
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: true,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
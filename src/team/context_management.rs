@@ -430,6 +430,8 @@ mod tests {
             completed: None,
             description: "Continue from the saved state.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("/tmp/task.md"),
         }
     }
@@ -97,9 +97,13 @@ pub fn show_cost(project_root: &Path) -> Result<()> {
     let team_config = TeamConfig::load(&config_path)?;
     let report = collect_cost_report(project_root, &team_config, &SessionRoots::default())?;
 
+    let members = crate::team::hierarchy::resolve_hierarchy(&team_config)?;
+    let budget = crate::team::budget::project_headroom(project_root, &team_config.budget, &members);
+
     println!("Run cost estimate for team {}", report.team_name);
     if report.entries.is_empty() {
         println!("No agent session files with token usage were found.");
+        print_budget_headroom(budget.as_deref());
         return Ok(());
     }
 
@@ -139,10 +143,18 @@ pub fn show_cost(project_root: &Path) -> Result<()> {
                 .join(", ")
         );
     }
+    print_budget_headroom(budget.as_deref());
 
     Ok(())
 }
 
+fn print_budget_headroom(rows: Option<&[crate::team::budget::BudgetHeadroomRow]>) {
+    if let Some(rows) = rows.filter(|rows| !rows.is_empty()) {
+        println!();
+        println!("{}", crate::team::budget::format_headroom(rows));
+    }
+}
+
 impl Default for SessionRoots {
     fn default() -> Self {
         let home = std::env::var_os("HOME")
@@ -387,6 +399,23 @@ fn built_in_model_pricing(model: &str) -> Option<ModelPricing> {
     None
 }
 
+/// Price a plain input/output token pair for live budget accounting. The shim
+/// does not split cached input, so everything is billed at the uncached rate.
+pub(crate) fn estimate_token_cost_usd(
+    overrides: &HashMap<String, ModelPricing>,
+    model: &str,
+    input_tokens: u64,
+    output_tokens: u64,
+) -> Option<f64> {
+    let pricing = pricing_for_model(overrides, model)?;
+    let usage = TokenUsage {
+        input_tokens,
+        output_tokens,
+        ..TokenUsage::default()
+    };
+    Some(estimate_cost_usd(&usage, &pricing))
+}
+
 fn estimate_cost_usd(usage: &TokenUsage, pricing: &ModelPricing) -> f64 {
     let classified_cache_creation =
         usage.cache_creation_5m_input_tokens + usage.cache_creation_1h_input_tokens;
@@ -678,6 +707,13 @@ mod tests {
             workflow_policy: Default::default(),
             cost: CostConfig { models },
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
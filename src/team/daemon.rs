@@ -68,16 +68,26 @@ const STALLED_MID_TURN_MAX_ATTEMPTS: u32 = 5;
 
 #[path = "daemon/agent_handle.rs"]
 pub(super) mod agent_handle;
+#[path = "daemon/approvals.rs"]
+mod approvals;
 #[path = "daemon/automation.rs"]
 mod automation;
+#[path = "daemon/backend_failover.rs"]
+mod backend_failover;
+#[path = "daemon/budget_enforcement.rs"]
+mod budget_enforcement;
 #[path = "daemon/config_reload.rs"]
 mod config_reload;
+#[path = "daemon/control_api.rs"]
+mod control_api;
 #[path = "discord_bridge.rs"]
 mod discord_bridge;
 #[path = "dispatch/mod.rs"]
 pub(crate) mod dispatch;
 #[path = "daemon/error_handling.rs"]
 mod error_handling;
+#[path = "daemon/forge_bridge.rs"]
+mod forge_bridge;
 #[path = "daemon/health/mod.rs"]
 pub(crate) mod health;
 #[path = "daemon/helpers.rs"]
@@ -90,8 +100,16 @@ mod interventions;
 mod launcher;
 #[path = "daemon/merge_queue.rs"]
 mod merge_queue;
+#[path = "daemon/merge_train.rs"]
+mod merge_train;
+#[path = "daemon/operator_commands.rs"]
+mod operator_commands;
+#[path = "daemon/pattern_actions.rs"]
+mod pattern_actions;
 #[path = "daemon/poll.rs"]
 mod poll;
+#[path = "daemon/prometheus_metrics.rs"]
+mod prometheus_metrics;
 #[path = "daemon/reconcile.rs"]
 mod reconcile;
 #[cfg(any(test, feature = "scenario-test"))]
@@ -101,18 +119,28 @@ pub mod scenario_api;
 mod shim_spawn;
 #[path = "daemon/shim_state.rs"]
 mod shim_state;
+#[path = "slack_bridge.rs"]
+mod slack_bridge;
 #[path = "daemon/spec_gen.rs"]
 mod spec_gen;
 #[path = "daemon/state.rs"]
 mod state;
+#[path = "daemon/task_tracing.rs"]
+mod task_tracing;
 #[path = "telegram_bridge.rs"]
 mod telegram_bridge;
 #[path = "daemon/telemetry.rs"]
 pub(crate) mod telemetry;
 #[path = "daemon/tick_report.rs"]
 pub mod tick_report;
+#[path = "daemon/time_warp.rs"]
+mod time_warp;
+#[path = "daemon/tool_activity.rs"]
+mod tool_activity;
 #[path = "daemon/verification.rs"]
 pub(crate) mod verification;
+#[path = "daemon/webhook_bridge.rs"]
+mod webhook_bridge;
 
 pub(crate) use self::discord_bridge::{
     build_shutdown_snapshot, send_discord_shutdown_notice, send_discord_shutdown_summary,
@@ -313,6 +341,12 @@ pub struct TeamDaemon {
     pub(super) discord_bot: Option<super::discord::DiscordBot>,
     pub(super) discord_event_cursor: usize,
     pub(super) telegram_bot: Option<super::telegram::TelegramBot>,
+    pub(super) slack_bot: Option<super::slack::SlackBot>,
+    pub(super) slack_event_cursor: usize,
+    pub(super) webhook_event_cursor: usize,
+    pub(super) webhook_worker: Option<super::webhooks::WebhookWorker>,
+    /// Event-log position up to which hook-written events are in telemetry.
+    pub(super) hook_event_cursor: usize,
     pub(super) failure_tracker: FailureTracker,
     pub(super) event_sink: EventSink,
     pub(super) paused_standups: HashSet<String>,
@@ -383,6 +417,11 @@ pub struct TeamDaemon {
     /// reset deadline passes. A successful poll_shim ping is NOT evidence
     /// of quota recovery — only the deadline or operator intervention is.
     pub(super) backend_quota_retry_at: HashMap<String, u64>,
+    /// Members moved to a fallback backend from their role's `failover`
+    /// list, with what to switch back to once the primary recovers.
+    pub(super) backend_failovers: HashMap<String, backend_failover::BackendFailover>,
+    /// Members recently switched back to their primary backend.
+    pub(super) restored_primaries: HashMap<String, backend_failover::RestoredPrimary>,
     /// Rolling capture history used to detect narration loops.
     pub(super) narration_tracker: health::narration::NarrationTracker,
     /// Per-session output tracking used for proactive context-pressure handling.
@@ -426,6 +465,28 @@ pub struct TeamDaemon {
     pub(super) last_binary_freshness_check: Instant,
     /// When the last tiered inbox expiry sweep ran (#658). Gated to at most once per minute.
     pub(super) last_tiered_inbox_sweep: Instant,
+    /// Local control API listener, when `api.enabled` is set.
+    pub(super) api_server: Option<super::api::ApiServer>,
+    /// Prometheus `/metrics` listener, when `prometheus.enabled` is set.
+    pub(super) prometheus: Option<super::prometheus::MetricsServer>,
+    /// Task lifecycle tracer and exporter, when `traces.enabled` is set.
+    pub(super) task_traces: Option<super::task_traces::TaskTraces>,
+    /// Persisted token and dollar usage for configured budgets.
+    pub(super) budget_ledger: super::budget::BudgetLedger,
+    /// Tool calls reported by SDK-mode shims, per member.
+    pub(super) tool_activity: HashMap<String, tool_activity::MemberToolActivity>,
+    /// Replaces real shim subprocesses on respawn (set by `batty replay`).
+    pub(super) shim_spawn_override: Option<shim_spawn::ShimSpawnOverride>,
+    /// When the last forge sync pass ran; `None` syncs on the next tick.
+    pub(super) last_forge_sync: Option<Instant>,
+    /// Rules from `patterns.yaml`, fed every emitted event.
+    pub(super) pattern_rules: super::pattern_rules::PatternRuleEngine,
+    /// `model_class` set per member by the `switch_model_class` pattern
+    /// action. Persisted and re-applied whenever `config.members` is rebuilt
+    /// so a reload or restart does not silently undo the switch.
+    pub(super) model_class_overrides: HashMap<String, String>,
+    /// `.batty/allocation_model.json`, reloaded when its mtime changes.
+    pub(super) allocation_model: super::allocation_model::AllocationModelCache,
 }
 
 #[cfg(any(test, feature = "scenario-test"))]
@@ -639,6 +700,15 @@ impl TeamDaemon {
         let discord_bot = discord_bridge::build_discord_bot(&config.team_config);
         // Create Telegram bot for inbound polling (if configured)
         let telegram_bot = telegram_bridge::build_telegram_bot(&config.team_config);
+        // Create Slack bot for inbound polling and event mirroring (if configured)
+        let slack_bot = slack_bridge::build_slack_bot(&config.team_config);
+        let task_traces = config.team_config.traces.enabled.then(|| {
+            super::task_traces::TaskTraces::new(
+                &config.team_config.traces,
+                &config.project_root,
+                &config.team_config.name,
+            )
+        });
         let narration_detection_enabled = config
             .team_config
             .workflow_policy
@@ -700,6 +770,12 @@ impl TeamDaemon {
             .team_config
             .workflow_policy
             .context_pressure_threshold_bytes;
+        let budget_ledger = super::budget::load_ledger(&config.project_root);
+        let pattern_rules = super::pattern_rules::PatternRuleEngine::load(&config.project_root)
+            .unwrap_or_else(|error| {
+                warn!(error = %format!("{error:#}"), "ignoring invalid patterns.yaml");
+                Default::default()
+            });
 
         Ok(Self {
             config,
@@ -723,6 +799,20 @@ impl TeamDaemon {
                 .map(|events| events.len())
                 .unwrap_or(0),
             telegram_bot,
+            slack_bot,
+            // Like Discord, only mirror events that happen after boot.
+            slack_event_cursor: crate::team::events::read_events(event_sink.path())
+                .map(|events| events.len())
+                .unwrap_or(0),
+            // Pending deliveries survive restarts in the webhook queue; events
+            // from before boot are not re-sent.
+            webhook_event_cursor: crate::team::events::read_events(event_sink.path())
+                .map(|events| events.len())
+                .unwrap_or(0),
+            webhook_worker: None,
+            hook_event_cursor: crate::team::events::read_events(event_sink.path())
+                .map(|events| events.len())
+                .unwrap_or(0),
             failure_tracker: FailureTracker::new(20),
             event_sink,
             paused_standups: HashSet::new(),
@@ -757,6 +847,8 @@ impl TeamDaemon {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: health::narration::NarrationTracker::new(
                 narration_detection_enabled,
                 narration_threshold_polls,
@@ -783,6 +875,16 @@ impl TeamDaemon {
             last_binary_freshness_check: Instant::now() - Duration::from_secs(7200),
             // First sweep runs on the first tick after startup.
             last_tiered_inbox_sweep: Instant::now() - Duration::from_secs(120),
+            api_server: None,
+            prometheus: None,
+            task_traces,
+            budget_ledger,
+            tool_activity: HashMap::new(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules,
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
         })
     }
 
@@ -840,6 +942,7 @@ impl TeamDaemon {
             watcher.activate();
         }
         self.update_automation_timers_for_state(member_name, MemberState::Working);
+        self.trace_member_working(member_name);
     }
 
     pub(super) fn set_member_idle(&mut self, member_name: &str) {
@@ -991,14 +1094,18 @@ impl TeamDaemon {
         let head = Self::short_head_commit(self.project_root())?;
         self.record_orchestrator_action(format!("main smoke: running `{command}` at {head}"));
 
-        let test_run =
-            crate::team::task_loop::run_tests_in_worktree(self.project_root(), Some(command))
-                .with_context(|| {
-                    format!(
-                        "failed while running main smoke command `{command}` in {}",
-                        self.project_root().display()
-                    )
-                })?;
+        let test_run = crate::team::task_loop::run_tests_in_worktree(
+            self.project_root(),
+            Some(command),
+            None,
+            None,
+        )
+        .with_context(|| {
+            format!(
+                "failed while running main smoke command `{command}` in {}",
+                self.project_root().display()
+            )
+        })?;
 
         if test_run.passed {
             let was_broken = self
@@ -0,0 +1,584 @@
+//! Daemon side of the human approval gates.
+//!
+//! The merge queue asks [`TeamDaemon::merge_approval_gate`] before landing a
+//! branch that touches sensitive paths or dependency manifests, and parks the
+//! request until the approval is decided. Branch pruning after a merge is
+//! gated the same way. Each tick, [`TeamDaemon::process_approvals`] expires
+//! overdue requests, delivers new ones to the user roles, announces
+//! decisions, and performs approved branch deletions.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Result;
+use tracing::{info, warn};
+
+use super::TeamDaemon;
+use super::merge_queue::{MergeQueue, MergeQueueOutcome, MergeRequest, mark_review_merge_blocked};
+use crate::team::approval::{
+    self, Approval, ApprovalAction, ApprovalStatus, Gate, check_gate, check_gate_at_head,
+    denial_summary,
+};
+use crate::team::config::{ApprovalPolicy, RoleType};
+use crate::team::events::TeamEvent;
+
+/// How many matching files an approval request lists before eliding.
+const MAX_LISTED_FILES: usize = 5;
+
+/// Pick the approval a merge of `files` needs under `policy`, if any. A
+/// dependency change wins over a sensitive-path change when both apply; the
+/// detail names every reason.
+pub(super) fn merge_approval_action(
+    policy: &ApprovalPolicy,
+    sensitive_paths: &[String],
+    files: &[String],
+) -> Option<(ApprovalAction, String)> {
+    let dependency_files: Vec<&String> = if policy.requires(ApprovalAction::DependencyChange) {
+        files
+            .iter()
+            .filter(|file| {
+                let name = Path::new(file.as_str())
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(file);
+                policy
+                    .dependency_files
+                    .iter()
+                    .any(|dependency| dependency == name || dependency == *file)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let sensitive_files: Vec<&String> = if policy.requires(ApprovalAction::SensitiveMerge) {
+        files
+            .iter()
+            .filter(|file| sensitive_paths.iter().any(|path| file.contains(path)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut reasons = Vec::new();
+    if !dependency_files.is_empty() {
+        reasons.push(format!(
+            "changes dependencies: {}",
+            list_files(&dependency_files)
+        ));
+    }
+    if !sensitive_files.is_empty() {
+        reasons.push(format!(
+            "touches sensitive paths: {}",
+            list_files(&sensitive_files)
+        ));
+    }
+    let action = if !dependency_files.is_empty() {
+        ApprovalAction::DependencyChange
+    } else if !sensitive_files.is_empty() {
+        ApprovalAction::SensitiveMerge
+    } else {
+        return None;
+    };
+    Some((action, reasons.join("; ")))
+}
+
+fn list_files(files: &[&String]) -> String {
+    let mut listed: Vec<&str> = files
+        .iter()
+        .take(MAX_LISTED_FILES)
+        .map(|file| file.as_str())
+        .collect();
+    let more = files.len().saturating_sub(MAX_LISTED_FILES);
+    let more = format!("+{more} more");
+    if files.len() > MAX_LISTED_FILES {
+        listed.push(&more);
+    }
+    listed.join(", ")
+}
+
+fn approval_request_message(approval: &Approval) -> String {
+    let id = approval.id;
+    let head = approval
+        .head
+        .as_deref()
+        .map(|head| {
+            format!(
+                "Commit: {} ({} file(s) changed)\n",
+                approval::short_commit(head),
+                approval.files.len()
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "Approval #{id} needed: {} of {}\n{}\n{head}Requested by: {}\n\
+         Reply /grant {id} or /deny {id} <reason> (Telegram), $grant {id} or $deny {id} <reason> \
+         (Discord/Slack), or run `batty approval grant {id}`.",
+        approval.action.as_str(),
+        approval.subject,
+        approval.detail,
+        approval.requested_by
+    )
+}
+
+impl TeamDaemon {
+    fn user_role_names(&self) -> Vec<String> {
+        self.config
+            .members
+            .iter()
+            .filter(|member| member.role_type == RoleType::User)
+            .map(|member| member.name.clone())
+            .collect()
+    }
+
+    /// Gate a queued merge on human approval. `None` means the merge may
+    /// proceed; otherwise the returned outcome ends this attempt.
+    pub(super) fn merge_approval_gate(
+        &mut self,
+        request: &MergeRequest,
+    ) -> Result<Option<MergeQueueOutcome>> {
+        let policy = self.config.team_config.workflow_policy.approvals.clone();
+        if !policy.requires(ApprovalAction::SensitiveMerge)
+            && !policy.requires(ApprovalAction::DependencyChange)
+        {
+            return Ok(None);
+        }
+        let files = super::verification::changed_files_from_trunk(
+            &request.worktree_dir,
+            self.config.team_config.trunk_branch(),
+        )?;
+        let Some((action, detail)) = merge_approval_action(
+            &policy,
+            &self
+                .config
+                .team_config
+                .workflow_policy
+                .auto_merge
+                .sensitive_paths,
+            &files,
+        ) else {
+            return Ok(None);
+        };
+
+        // Bind the request to the commit the human reviews: new commits on
+        // the branch after a grant need a fresh approval.
+        let head = crate::team::git_cmd::run_git(&request.worktree_dir, &["rev-parse", "HEAD"])
+            .map_err(|error| {
+                anyhow::anyhow!(
+                    "failed to read the head of {} for approval: {error}",
+                    request.branch
+                )
+            })?
+            .stdout
+            .trim()
+            .to_string();
+        let subject = format!("task #{}", request.task_id);
+        match check_gate_at_head(
+            self.project_root(),
+            &policy,
+            action,
+            &subject,
+            &detail,
+            &request.engineer,
+            Some((&head, &files)),
+        )? {
+            Gate::Granted => {
+                self.record_orchestrator_action(format!(
+                    "merge queue: task #{} {} approved; merging",
+                    request.task_id,
+                    action.as_str()
+                ));
+                Ok(None)
+            }
+            Gate::Pending(approval_id) => {
+                self.record_orchestrator_action(format!(
+                    "merge queue: task #{} held for human approval #{approval_id} ({detail})",
+                    request.task_id
+                ));
+                info!(
+                    task_id = request.task_id,
+                    approval_id, "merge held for human approval"
+                );
+                Ok(Some(MergeQueueOutcome::AwaitingApproval(approval_id)))
+            }
+            Gate::Denied(approval) => {
+                let summary = denial_summary(&approval);
+                mark_review_merge_blocked(&self.board_dir(), request.task_id, &summary);
+                self.record_orchestrator_action(format!(
+                    "merge queue: task #{} not merged; {summary}",
+                    request.task_id
+                ));
+                let notice = format!(
+                    "Task #{} was not merged: {summary}. The task stays in review, blocked, until someone decides the next step.",
+                    request.task_id
+                );
+                if let Some(manager_name) = self.manager_name(&request.engineer) {
+                    self.queue_message("daemon", &manager_name, &notice)?;
+                    self.mark_member_working(&manager_name);
+                }
+                self.queue_message("daemon", &request.engineer, &notice)?;
+                Ok(Some(MergeQueueOutcome::Skipped))
+            }
+        }
+    }
+
+    /// Move held merges whose approval was decided back onto the queue.
+    pub(super) fn release_approved_merges(&mut self, merge_queue: &mut MergeQueue) {
+        let state = match approval::load_approvals(self.project_root()) {
+            Ok(state) => state,
+            Err(error) => {
+                warn!(error = %error, "failed to load approvals; merges stay held");
+                return;
+            }
+        };
+        let now = crate::team::now_unix();
+        let released = merge_queue.release_held(|approval_id| {
+            state.get(approval_id).is_some_and(|approval| {
+                approval.status == ApprovalStatus::Pending && approval.expires_at > now
+            })
+        });
+        for task_id in released {
+            self.record_orchestrator_action(format!(
+                "merge queue: approval decided for task #{task_id}; requeued"
+            ));
+        }
+    }
+
+    /// Ask for approval before pruning a merged branch. Returns true when
+    /// the prune must not run now.
+    pub(super) fn request_branch_delete_approval(
+        &mut self,
+        request: &MergeRequest,
+    ) -> Result<bool> {
+        let policy = &self.config.team_config.workflow_policy.approvals;
+        let gate = check_gate(
+            self.project_root(),
+            policy,
+            ApprovalAction::DeleteBranch,
+            &request.branch,
+            &format!("prune merged branch of task #{}", request.task_id),
+            &request.engineer,
+        )?;
+        Ok(gate != Gate::Granted)
+    }
+
+    /// Expire, deliver, announce and apply approvals.
+    pub(super) fn process_approvals(&mut self) -> Result<()> {
+        let path = approval::approvals_path(self.project_root());
+        if !path.exists() {
+            return Ok(());
+        }
+        // Hold the store lock for the whole pass: a grant or deny that lands
+        // while branches are pruned would otherwise be overwritten.
+        let project_root = self.project_root().to_path_buf();
+        approval::update_approvals(&project_root, |state| self.process_approval_state(state))
+    }
+
+    fn process_approval_state(&mut self, state: &mut approval::ApprovalState) -> Result<()> {
+        state.expire_due(crate::team::now_unix());
+        let users = self.user_role_names();
+
+        for approval in state
+            .approvals
+            .iter_mut()
+            .filter(|approval| approval.status == ApprovalStatus::Pending && !approval.notified)
+        {
+            let message = approval_request_message(approval);
+            for user in &users {
+                if let Err(error) = self.queue_daemon_message(user, &message) {
+                    warn!(user, approval_id = approval.id, error = %error, "failed to deliver approval request");
+                }
+            }
+            self.record_orchestrator_action(format!(
+                "approvals: #{} requested for {} of {}",
+                approval.id,
+                approval.action.as_str(),
+                approval.subject
+            ));
+            self.emit_event(TeamEvent::approval_requested(
+                approval.id,
+                approval.action.as_str(),
+                &approval.subject,
+                &approval.requested_by,
+            ));
+            approval.notified = true;
+        }
+
+        for approval in state
+            .approvals
+            .iter_mut()
+            .filter(|approval| approval.status != ApprovalStatus::Pending && !approval.reported)
+        {
+            self.record_orchestrator_action(format!(
+                "approvals: #{} {} ({} of {})",
+                approval.id,
+                approval.status.as_str(),
+                approval.action.as_str(),
+                approval.subject
+            ));
+            self.emit_event(TeamEvent::approval_decided(
+                approval.id,
+                approval.action.as_str(),
+                &approval.subject,
+                approval.status.as_str(),
+                approval.decided_by.as_deref(),
+            ));
+            if approval.status == ApprovalStatus::Expired {
+                let message = match approval.reason.as_deref() {
+                    Some(reason) => format!(
+                        "Approval #{} closed ({reason}); a new request for {} of {} follows.",
+                        approval.id,
+                        approval.action.as_str(),
+                        approval.subject
+                    ),
+                    None => format!(
+                        "Approval #{} expired without a decision; {} of {} will not run.",
+                        approval.id,
+                        approval.action.as_str(),
+                        approval.subject
+                    ),
+                };
+                for user in &users {
+                    let _ = self.queue_daemon_message(user, &message);
+                }
+            }
+            approval.reported = true;
+        }
+
+        for approval in state.approvals.iter_mut().filter(|approval| {
+            approval.action == ApprovalAction::DeleteBranch
+                && approval.status != ApprovalStatus::Pending
+                && !approval.consumed
+        }) {
+            approval.consumed = true;
+            if approval.status != ApprovalStatus::Approved {
+                continue;
+            }
+            let output = Command::new("git")
+                .args(["branch", "-D", &approval.subject])
+                .current_dir(self.project_root())
+                .env_remove("GIT_DIR")
+                .env_remove("GIT_WORK_TREE")
+                .output()?;
+            if output.status.success() {
+                self.record_orchestrator_action(format!(
+                    "approvals: pruned branch {} (approval #{})",
+                    approval.subject, approval.id
+                ));
+            } else {
+                warn!(
+                    branch = approval.subject,
+                    stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                    "approved branch prune failed"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::approval::{ApprovalState, load_approvals, save_approvals};
+    use crate::team::test_support::{TestDaemonBuilder, git_ok, git_stdout, init_git_repo};
+
+    fn enabled_policy() -> ApprovalPolicy {
+        ApprovalPolicy {
+            enabled: true,
+            ..ApprovalPolicy::default()
+        }
+    }
+
+    #[test]
+    fn merge_approval_action_prefers_dependency_changes() {
+        let policy = enabled_policy();
+        let sensitive = vec!["src/auth".to_string()];
+        let files = vec![
+            "src/auth/token.rs".to_string(),
+            "crates/core/Cargo.toml".to_string(),
+            "README.md".to_string(),
+        ];
+        let (action, detail) = merge_approval_action(&policy, &sensitive, &files).unwrap();
+        assert_eq!(action, ApprovalAction::DependencyChange);
+        assert_eq!(
+            detail,
+            "changes dependencies: crates/core/Cargo.toml; touches sensitive paths: src/auth/token.rs"
+        );
+
+        let mut sensitive_only = policy.clone();
+        sensitive_only.actions = vec![ApprovalAction::SensitiveMerge];
+        let (action, _) = merge_approval_action(&sensitive_only, &sensitive, &files).unwrap();
+        assert_eq!(action, ApprovalAction::SensitiveMerge);
+
+        assert!(merge_approval_action(&policy, &sensitive, &["README.md".to_string()]).is_none());
+    }
+
+    #[test]
+    fn process_approvals_prunes_branch_after_grant() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = init_git_repo(&tmp, "batty-approvals-prune-test");
+        git_ok(&repo, &["branch", "eng-1/7"]);
+
+        let mut state = ApprovalState::default();
+        state.check(
+            ApprovalAction::DeleteBranch,
+            "eng-1/7",
+            "prune merged branch of task #7",
+            "eng-1",
+            3600,
+            crate::team::now_unix(),
+        );
+        state
+            .decide(1, true, "human", None, crate::team::now_unix())
+            .unwrap();
+        save_approvals(&repo, &state).unwrap();
+
+        let mut daemon = TestDaemonBuilder::new(&repo).build();
+        daemon.process_approvals().unwrap();
+
+        assert!(git_stdout(&repo, &["branch", "--list", "eng-1/7"]).is_empty());
+        let approval = load_approvals(&repo).unwrap().get(1).cloned().unwrap();
+        assert!(approval.consumed);
+        assert!(approval.reported);
+    }
+
+    #[test]
+    fn held_merge_is_released_once_decided() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state = ApprovalState::default();
+        state.check(
+            ApprovalAction::SensitiveMerge,
+            "task #5",
+            "",
+            "eng-1",
+            3600,
+            0,
+        );
+        state.check(
+            ApprovalAction::SensitiveMerge,
+            "task #6",
+            "",
+            "eng-1",
+            3600,
+            0,
+        );
+        state.decide(1, true, "human", None, 1).unwrap();
+        // Approval #2 is due before "now" and therefore releases as expired.
+        save_approvals(tmp.path(), &state).unwrap();
+
+        let request = |task_id| MergeRequest {
+            task_id,
+            engineer: "eng-1".to_string(),
+            branch: format!("eng-1/{task_id}"),
+            worktree_dir: tmp.path().to_path_buf(),
+            queued_at: std::time::Instant::now(),
+            test_passed: true,
+            should_post_merge_verify: false,
+            test_duration_ms: 1,
+            confidence: 1.0,
+            files_changed: 1,
+            lines_changed: 1,
+        };
+        let mut queue = MergeQueue::default();
+        queue.hold(1, request(5));
+        queue.hold(2, request(6));
+        queue.hold(9, request(7));
+
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.release_approved_merges(&mut queue);
+
+        assert_eq!(queue.queued_len(), 3);
+        assert_eq!(
+            queue
+                .take_train(3)
+                .iter()
+                .map(|request| request.task_id)
+                .collect::<Vec<_>>(),
+            vec![5, 6, 7]
+        );
+    }
+
+    #[test]
+    fn merge_grant_only_covers_the_approved_head() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = init_git_repo(&tmp, "batty-approvals-head-test");
+        git_ok(&repo, &["checkout", "-b", "eng-1/8"]);
+        let commit = |content: &str| {
+            std::fs::write(
+                repo.join("Cargo.toml"),
+                format!("[package]\nname = \"{content}\"\nversion = \"0.1.0\"\n"),
+            )
+            .unwrap();
+            git_ok(&repo, &["commit", "-am", content]);
+            git_stdout(&repo, &["rev-parse", "HEAD"]).trim().to_string()
+        };
+        let reviewed = commit("reviewed");
+
+        let mut daemon = TestDaemonBuilder::new(&repo).build();
+        daemon.config.team_config.workflow_policy.approvals = enabled_policy();
+        let request = MergeRequest {
+            task_id: 8,
+            engineer: "eng-1".to_string(),
+            branch: "eng-1/8".to_string(),
+            worktree_dir: repo.clone(),
+            queued_at: std::time::Instant::now(),
+            test_passed: true,
+            should_post_merge_verify: false,
+            test_duration_ms: 1,
+            confidence: 1.0,
+            files_changed: 1,
+            lines_changed: 1,
+        };
+
+        assert!(matches!(
+            daemon.merge_approval_gate(&request).unwrap(),
+            Some(MergeQueueOutcome::AwaitingApproval(1))
+        ));
+        let first = load_approvals(&repo).unwrap().get(1).cloned().unwrap();
+        assert_eq!(first.head.as_deref(), Some(reviewed.as_str()));
+        assert_eq!(first.files, vec!["Cargo.toml".to_string()]);
+        approval::decide(&repo, 1, true, "human", None).unwrap();
+
+        // The branch moves after the grant: the grant is spent and a new
+        // request covers the new commit.
+        let pushed = commit("pushed-after-grant");
+        assert!(matches!(
+            daemon.merge_approval_gate(&request).unwrap(),
+            Some(MergeQueueOutcome::AwaitingApproval(2))
+        ));
+        let state = load_approvals(&repo).unwrap();
+        assert!(state.get(1).unwrap().consumed);
+        assert_eq!(state.get(2).unwrap().head.as_deref(), Some(pushed.as_str()));
+
+        approval::decide(&repo, 2, true, "human", None).unwrap();
+        assert!(daemon.merge_approval_gate(&request).unwrap().is_none());
+    }
+
+    #[test]
+    fn pending_request_is_superseded_when_the_head_moves() {
+        let mut state = ApprovalState::default();
+        let files = vec!["Cargo.toml".to_string()];
+        let check = |state: &mut ApprovalState, head: &str| {
+            state.check_at_head(
+                ApprovalAction::DependencyChange,
+                "task #3",
+                "",
+                "eng-1",
+                Some((head, &files)),
+                3600,
+                10,
+            )
+        };
+        assert_eq!(check(&mut state, "aaaa"), Gate::Pending(1));
+        assert_eq!(check(&mut state, "aaaa"), Gate::Pending(1));
+        assert_eq!(check(&mut state, "bbbb"), Gate::Pending(2));
+        let superseded = state.get(1).unwrap();
+        assert_eq!(superseded.status, ApprovalStatus::Expired);
+        assert_eq!(
+            superseded.reason.as_deref(),
+            Some("superseded: the branch moved to bbbb")
+        );
+        assert!(state.decide(1, true, "human", None, 11).is_err());
+    }
+}
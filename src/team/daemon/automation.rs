@@ -1042,6 +1042,15 @@ impl TeamDaemon {
                 released_claims = true;
             }
             self.record_state_reconciliation(Some(&engineer), Some(task_id), correction);
+            match reason {
+                "task is done" => self.finish_task_trace(task_id, "done", false),
+                "task entered review" => self.trace_task_phase(
+                    task_id,
+                    crate::team::task_traces::TaskPhase::InReview,
+                    Some(&engineer),
+                ),
+                _ => {}
+            }
             // #697: when the engineer's claim was cleared by the engineer
             // themselves (not by a task transition to done/review/blocked),
             // record an exclusion so the dispatcher does not immediately
@@ -3385,67 +3394,12 @@ mod tests {
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         }
     }
 
-    fn setup_fake_kanban_for_planning(
-        tmp: &tempfile::TempDir,
-    ) -> (std::path::PathBuf, std::path::PathBuf) {
-        let fake_bin = tmp.path().join("fake-bin");
-        std::fs::create_dir_all(&fake_bin).unwrap();
-        let log_path = tmp.path().join("kanban.log");
-        let script = fake_bin.join("kanban-md");
-        std::fs::write(
-            &script,
-            format!(
-                "#!/bin/bash
-set -euo pipefail
-printf '%s\\n' \"$*\" >> '{}'
-if [ \"$1\" != \"create\" ]; then
-  exit 1
-fi
-title=\"$2\"
-body=\"\"
-priority=\"high\"
-tags=\"\"
-depends_on=\"\"
-shift 2
-while [ $# -gt 0 ]; do
-  case \"$1\" in
-    --body) body=\"$2\"; shift 2 ;;
-    --priority) priority=\"$2\"; shift 2 ;;
-    --tags) tags=\"$2\"; shift 2 ;;
-    --depends-on) depends_on=\"$2\"; shift 2 ;;
-    --dir) board_dir=\"$2\"; shift 2 ;;
-    *) shift ;;
-  esac
-done
-mkdir -p \"$board_dir/tasks\"
-count=$(find \"$board_dir/tasks\" -maxdepth 1 -name '*.md' | wc -l | tr -d ' ')
-id=$((count + 1))
-printf -- '---\nid: %s\ntitle: %s\nstatus: todo\npriority: %s\n' \"$id\" \"$title\" \"$priority\" > \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"
-if [ -n \"$tags\" ]; then
-  printf 'tags: [%s]\n' \"$tags\" >> \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"
-fi
-if [ -n \"$depends_on\" ]; then
-  printf 'depends_on: [%s]\n' \"$depends_on\" >> \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"
-fi
-printf -- '---\n\n%s\n' \"$body\" >> \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"
-printf 'Created task #%s\n' \"$id\"
-",
-                log_path.display()
-            ),
-        )
-        .unwrap();
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
-        }
-        (fake_bin, log_path)
-    }
-
     fn planning_test_daemon(tmp: &tempfile::TempDir, cooldown_secs: u64) -> TeamDaemon {
         let board_dir = tmp
             .path()
@@ -3852,15 +3806,7 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
 
     #[test]
     fn planning_cycle_round_trip_creates_board_tasks_and_resets_active_flag() {
-        let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
-        let (fake_bin, _log_path) = setup_fake_kanban_for_planning(&tmp);
-        let path = format!(
-            "{}:{}",
-            fake_bin.display(),
-            std::env::var("PATH").unwrap_or_default()
-        );
-        let _path_guard = EnvVarGuard::set("PATH", &path);
 
         let mut daemon = planning_test_daemon(&tmp, 300);
         daemon.maybe_trigger_planning_cycle().unwrap();
@@ -3907,15 +3853,7 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
 
     #[test]
     fn planning_round_trip_with_malformed_blocks_keeps_good_tasks() {
-        let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
-        let (fake_bin, _log_path) = setup_fake_kanban_for_planning(&tmp);
-        let path = format!(
-            "{}:{}",
-            fake_bin.display(),
-            std::env::var("PATH").unwrap_or_default()
-        );
-        let _path_guard = EnvVarGuard::set("PATH", &path);
 
         let mut daemon = planning_test_daemon(&tmp, 300);
         daemon.planning_cycle_active = true;
@@ -3927,15 +3865,7 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
 
     #[test]
     fn planning_round_trip_rejects_raw_log_generated_tasks() {
-        let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
-        let (fake_bin, _log_path) = setup_fake_kanban_for_planning(&tmp);
-        let path = format!(
-            "{}:{}",
-            fake_bin.display(),
-            std::env::var("PATH").unwrap_or_default()
-        );
-        let _path_guard = EnvVarGuard::set("PATH", &path);
 
         let mut daemon = planning_test_daemon(&tmp, 300);
         daemon.planning_cycle_active = true;
@@ -4003,15 +3933,7 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
 
     #[test]
     fn planning_response_missing_board_dir_returns_graceful_error_and_resets_cycle() {
-        let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
-        let (fake_bin, _log_path) = setup_fake_kanban_for_planning(&tmp);
-        let path = format!(
-            "{}:{}",
-            fake_bin.display(),
-            std::env::var("PATH").unwrap_or_default()
-        );
-        let _path_guard = EnvVarGuard::set("PATH", &path);
 
         let mut daemon = planning_test_daemon(&tmp, 300);
         std::fs::remove_dir_all(daemon.board_dir()).unwrap();
@@ -4025,7 +3947,7 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
     }
 
     #[test]
-    fn planning_response_missing_kanban_binary_returns_clear_error() {
+    fn planning_response_creates_tasks_without_kanban_binary() {
         let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
         let _path_guard = EnvVarGuard::set("PATH", tmp.path().display().to_string().as_str());
@@ -4033,27 +3955,17 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
         let mut daemon = planning_test_daemon(&tmp, 300);
         daemon.planning_cycle_active = true;
 
-        let error = daemon
+        let created = daemon
             .handle_planning_response(SINGLE_TASK_RESPONSE)
-            .unwrap_err();
-        let detail = error.to_string();
-        assert!(
-            detail.contains("failed to create board task") || detail.contains("failed to execute")
-        );
+            .unwrap();
+
+        assert_eq!(created, 1);
         assert!(!daemon.planning_cycle_active);
     }
 
     #[test]
     fn planning_response_double_apply_is_graceful() {
-        let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
-        let (fake_bin, _log_path) = setup_fake_kanban_for_planning(&tmp);
-        let path = format!(
-            "{}:{}",
-            fake_bin.display(),
-            std::env::var("PATH").unwrap_or_default()
-        );
-        let _path_guard = EnvVarGuard::set("PATH", &path);
 
         let mut daemon = planning_test_daemon(&tmp, 300);
         daemon.planning_cycle_active = true;
@@ -4122,16 +4034,8 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
     #[serial_test::serial]
     #[cfg_attr(not(feature = "integration"), ignore)]
     fn planning_cycle_end_to_end_includes_context_and_dispatches_created_work() {
-        let _path_lock = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
         write_planning_docs(&tmp);
-        let (fake_bin, _log_path) = setup_fake_kanban_for_planning(&tmp);
-        let path = format!(
-            "{}:{}",
-            fake_bin.display(),
-            std::env::var("PATH").unwrap_or_default()
-        );
-        let _path_guard = EnvVarGuard::set("PATH", &path);
 
         let mut daemon = planning_test_daemon(&tmp, 300);
         daemon
@@ -6074,6 +5978,8 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         };
         assert_eq!(
@@ -6127,6 +6033,8 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         };
         assert_eq!(
@@ -6180,6 +6088,8 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         };
         // Owner not in members → falls back to manager
@@ -6272,6 +6182,8 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         }];
 
@@ -6367,6 +6279,8 @@ thread 'tmux::tests::split_window_horizontal_creates_new_pane' panicked at src/t
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         }];
 
@@ -0,0 +1,735 @@
+//! Cross-provider failover for members whose backend is quota-blocked or
+//! needs re-authentication.
+//!
+//! A role's `failover:` list names fallback agent/model pairs in order. When
+//! the shim reports `QuotaBlocked` or `AuthRequired`, the member is
+//! checkpointed and cold-respawned on the first fallback whose backend is
+//! healthy, carrying the usual restart handoff. A fallback that gets blocked
+//! in turn moves the member further down the list. Once the primary's quota
+//! window has passed (or, without a deadline, after a recheck interval) and
+//! its backend probes healthy, the member is switched back the same way the
+//! next time it is not working. A primary that failed authentication is not
+//! retried on a timer: it waits for an operator to send `/reauth <backend>`.
+//! Each restore that fails, or that is followed by another block within an
+//! hour, doubles the wait before the next one. Every switch emits
+//! `backend_switched`.
+//!
+//! Active failovers and recent restores are persisted with the daemon state;
+//! failovers are re-applied after a config reload or restart, so the member
+//! keeps reporting the backend its shim actually runs.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::TeamDaemon;
+use crate::agent::{self, BackendHealth};
+use crate::team::config::BackendFallback;
+use crate::team::events::TeamEvent;
+use crate::team::standup::MemberState;
+
+/// How long to stay on a fallback before probing the primary again when the
+/// block came without a retry deadline. Doubles with each failed restore.
+const PRIMARY_RECHECK_SECS: u64 = 30 * 60;
+/// Longest wait between primary probes.
+const MAX_PRIMARY_RECHECK_SECS: u64 = 8 * 60 * 60;
+/// A restored primary that blocks again within this window counts as a
+/// failed restore.
+const RESTORE_PROBATION_SECS: u64 = 60 * 60;
+
+/// A member running on a fallback backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BackendFailover {
+    /// The agent/model the member was configured with.
+    primary: BackendFallback,
+    /// The fallback the member is running on.
+    current: BackendFallback,
+    /// Index into the role's `failover` list of the backend now in use.
+    fallback_index: usize,
+    /// Earliest epoch second at which the primary is probed again.
+    primary_retry_at: u64,
+    /// The primary failed authentication and is only retried after
+    /// `/reauth` names its backend.
+    #[serde(default)]
+    awaiting_reauth: bool,
+    /// Restores of the primary that failed or did not stick.
+    #[serde(default)]
+    restore_failures: u32,
+}
+
+/// A member recently switched back to its primary, remembered so a primary
+/// that blocks again right away backs off further instead of flapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RestoredPrimary {
+    restored_at: u64,
+    restore_failures: u32,
+}
+
+fn primary_recheck_secs(restore_failures: u32) -> u64 {
+    PRIMARY_RECHECK_SECS
+        .saturating_mul(1 << restore_failures.min(8))
+        .min(MAX_PRIMARY_RECHECK_SECS)
+}
+
+fn backend_label(agent: &str, model: Option<&str>) -> String {
+    match model {
+        Some(model) => format!("{agent}/{model}"),
+        None => agent.to_string(),
+    }
+}
+
+impl TeamDaemon {
+    /// Shim-event hook: try the role's failover list after the member's
+    /// backend was parked. Failures leave the member parked.
+    pub(super) fn fail_over_blocked_backend(
+        &mut self,
+        member_name: &str,
+        reason: &str,
+        retry_at: Option<u64>,
+    ) {
+        if let Err(error) = self.maybe_fail_over_backend(member_name, reason, retry_at) {
+            warn!(
+                member = member_name,
+                error = %error,
+                "backend failover failed; member stays parked"
+            );
+        }
+    }
+
+    /// Move `member_name` to the next healthy fallback backend after its
+    /// current backend reported `reason`. Returns `false` when the role has
+    /// no failover list or every fallback is unavailable, leaving the member
+    /// parked as before.
+    pub(super) fn maybe_fail_over_backend(
+        &mut self,
+        member_name: &str,
+        reason: &str,
+        retry_at: Option<u64>,
+    ) -> Result<bool> {
+        let Some(member) = self
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == member_name)
+        else {
+            return Ok(false);
+        };
+        let fallbacks = self
+            .config
+            .team_config
+            .role_def(&member.role_name)
+            .map(|role| role.failover.clone())
+            .unwrap_or_default();
+        if fallbacks.is_empty() {
+            return Ok(false);
+        }
+
+        let now = crate::team::now_unix();
+        let existing = self.backend_failovers.get(member_name).cloned();
+        let primary = existing
+            .as_ref()
+            .map(|failover| failover.primary.clone())
+            .unwrap_or_else(|| BackendFallback {
+                agent: member.agent.clone().unwrap_or_else(|| "claude".to_string()),
+                model: member.model.clone(),
+            });
+        let start = existing
+            .as_ref()
+            .map_or(0, |failover| failover.fallback_index + 1);
+        let Some((index, target)) = fallbacks
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, fallback)| {
+                **fallback != primary && self.fallback_available(member_name, fallback)
+            })
+            .map(|(index, fallback)| (index, fallback.clone()))
+        else {
+            self.record_orchestrator_action(format!(
+                "failover: no healthy fallback backend left for {member_name} ({reason}); member stays parked"
+            ));
+            return Ok(false);
+        };
+
+        let (primary_retry_at, awaiting_reauth, restore_failures) = match &existing {
+            Some(failover) => (
+                failover.primary_retry_at,
+                failover.awaiting_reauth,
+                failover.restore_failures,
+            ),
+            None => {
+                // Blocked again soon after a restore: the restore did not
+                // stick, so wait longer before the next one.
+                let restore_failures = self
+                    .restored_primaries
+                    .get(member_name)
+                    .filter(|restored| now < restored.restored_at + RESTORE_PROBATION_SECS)
+                    .map_or(0, |restored| restored.restore_failures + 1);
+                let deadline = retry_at
+                    .filter(|deadline| *deadline > now)
+                    .unwrap_or(now + PRIMARY_RECHECK_SECS);
+                let backoff = if restore_failures > 0 {
+                    now + primary_recheck_secs(restore_failures)
+                } else {
+                    0
+                };
+                (
+                    deadline.max(backoff),
+                    reason == "auth_required",
+                    restore_failures,
+                )
+            }
+        };
+        self.switch_member_backend(member_name, &target, reason)?;
+        self.restored_primaries.remove(member_name);
+        self.backend_failovers.insert(
+            member_name.to_string(),
+            BackendFailover {
+                primary,
+                current: target,
+                fallback_index: index,
+                primary_retry_at,
+                awaiting_reauth,
+                restore_failures,
+            },
+        );
+        self.persist_backend_failovers();
+        Ok(true)
+    }
+
+    /// Put members with an active failover back on their fallback after
+    /// `config.members` was rebuilt from `team.yaml`. The freshly configured
+    /// backend becomes the new primary; entries for removed members, or whose
+    /// configured backend now is the fallback, are dropped.
+    pub(super) fn apply_backend_failovers(&mut self) {
+        let members = &mut self.config.members;
+        let team_config = &self.config.team_config;
+        self.backend_failovers.retain(|name, failover| {
+            let Some(member) = members.iter_mut().find(|member| &member.name == name) else {
+                return false;
+            };
+            let configured = BackendFallback {
+                agent: member.agent.clone().unwrap_or_else(|| "claude".to_string()),
+                model: member.model.clone(),
+            };
+            if configured == failover.current {
+                return false;
+            }
+            failover.primary = configured;
+            let fallbacks = team_config
+                .role_def(&member.role_name)
+                .map(|role| role.failover.as_slice())
+                .unwrap_or_default();
+            failover.fallback_index = fallbacks
+                .iter()
+                .position(|fallback| *fallback == failover.current)
+                .unwrap_or(fallbacks.len());
+            member.agent = Some(failover.current.agent.clone());
+            member.model = failover.current.model.clone();
+            true
+        });
+    }
+
+    fn persist_backend_failovers(&self) {
+        if let Err(error) = self.persist_runtime_state(false) {
+            warn!(error = %error, "failed to persist backend failovers");
+        }
+    }
+
+    /// Switch members on a fallback back to their primary backend once it
+    /// has recovered and they are between turns. Primaries waiting on
+    /// re-authentication are skipped.
+    pub(super) fn maybe_restore_primary_backends(&mut self) -> Result<()> {
+        let now = crate::team::now_unix();
+        let before = self.restored_primaries.len();
+        self.restored_primaries
+            .retain(|_, restored| now < restored.restored_at + RESTORE_PROBATION_SECS);
+        let mut changed = self.restored_primaries.len() != before;
+        let due: Vec<(String, BackendFallback)> = self
+            .backend_failovers
+            .iter()
+            .filter(|(_, failover)| !failover.awaiting_reauth && failover.primary_retry_at <= now)
+            .map(|(name, failover)| (name.clone(), failover.primary.clone()))
+            .collect();
+        for (member_name, primary) in due {
+            if self.states.get(&member_name) == Some(&MemberState::Working) {
+                continue;
+            }
+            changed = true;
+            let restored = if self.fallback_available(&member_name, &primary) {
+                self.switch_member_backend(&member_name, &primary, "primary_recovered")
+            } else {
+                Err(anyhow::anyhow!("primary backend still unavailable"))
+            };
+            match restored {
+                Ok(()) => {
+                    if let Some(failover) = self.backend_failovers.remove(&member_name) {
+                        self.restored_primaries.insert(
+                            member_name,
+                            RestoredPrimary {
+                                restored_at: now,
+                                restore_failures: failover.restore_failures,
+                            },
+                        );
+                    }
+                }
+                Err(error) => {
+                    warn!(
+                        member = %member_name,
+                        error = %error,
+                        "failed to switch member back to primary backend"
+                    );
+                    if let Some(failover) = self.backend_failovers.get_mut(&member_name) {
+                        failover.restore_failures += 1;
+                        failover.primary_retry_at =
+                            now + primary_recheck_secs(failover.restore_failures);
+                    }
+                }
+            }
+        }
+        if changed {
+            self.persist_backend_failovers();
+        }
+        Ok(())
+    }
+
+    /// Operator signal that `agent`'s credentials were renewed. Members that
+    /// failed over from it for an auth failure switch back once idle.
+    /// Returns how many members were waiting on it.
+    pub(super) fn mark_backend_reauthenticated(&mut self, agent: &str) -> usize {
+        let now = crate::team::now_unix();
+        let mut members = Vec::new();
+        for (name, failover) in self
+            .backend_failovers
+            .iter_mut()
+            .filter(|(_, failover)| failover.awaiting_reauth && failover.primary.agent == agent)
+        {
+            failover.awaiting_reauth = false;
+            failover.restore_failures = 0;
+            failover.primary_retry_at = now;
+            members.push(name.clone());
+        }
+        if !members.is_empty() {
+            members.sort();
+            self.record_orchestrator_action(format!(
+                "failover: {agent} re-authenticated; {} will switch back when idle",
+                members.join(", ")
+            ));
+            self.persist_backend_failovers();
+        }
+        members.len()
+    }
+
+    /// A backend is usable when its binary probes healthy and no other
+    /// member on the same agent is currently parked on it.
+    fn fallback_available(&self, member_name: &str, target: &BackendFallback) -> bool {
+        if agent::health_check_by_name(&target.agent).is_some_and(|health| !health.is_healthy()) {
+            return false;
+        }
+        !self.config.members.iter().any(|member| {
+            member.name != member_name
+                && member.agent.as_deref().unwrap_or("claude") == target.agent
+                && self.member_backend_parked(&member.name)
+        })
+    }
+
+    /// Point `member_name` at `target`, clear the parked state left by the
+    /// old backend, and relaunch its shim there with a checkpoint and
+    /// handoff. Members without a shim pick up the new backend on their next
+    /// launch.
+    fn switch_member_backend(
+        &mut self,
+        member_name: &str,
+        target: &BackendFallback,
+        reason: &str,
+    ) -> Result<()> {
+        let Some(member) = self
+            .config
+            .members
+            .iter_mut()
+            .find(|member| member.name == member_name)
+        else {
+            return Ok(());
+        };
+        let previous = (member.agent.clone(), member.model.clone());
+        let from = backend_label(
+            previous.0.as_deref().unwrap_or("claude"),
+            previous.1.as_deref(),
+        );
+        let to = backend_label(&target.agent, target.model.as_deref());
+        member.agent = Some(target.agent.clone());
+        member.model = target.model.clone();
+
+        if self.shim_handles.contains_key(member_name)
+            && let Err(error) = self.respawn_member_on_configured_backend(
+                member_name,
+                &format!("backend failover to {to} ({reason})"),
+            )
+        {
+            if let Some(member) = self
+                .config
+                .members
+                .iter_mut()
+                .find(|member| member.name == member_name)
+            {
+                (member.agent, member.model) = previous;
+            }
+            return Err(error);
+        }
+
+        self.backend_health
+            .insert(member_name.to_string(), BackendHealth::Healthy);
+        self.backend_quota_retry_at.remove(member_name);
+        info!(member = member_name, from = %from, to = %to, reason, "switched member backend");
+        let task = self.active_task_id(member_name).map(|id| id.to_string());
+        self.emit_event(TeamEvent::backend_switched(
+            member_name,
+            task.as_deref(),
+            &from,
+            &to,
+            reason,
+        ));
+        self.record_orchestrator_action(format!(
+            "failover: {member_name} switched from {from} to {to} ({reason})"
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::team::config::{BackendFallback, RoleDef, RoleType};
+    use crate::team::events::read_events;
+    use crate::team::standup::MemberState;
+    use crate::team::test_helpers::make_test_daemon;
+    use crate::team::test_support::engineer_member;
+
+    /// Agent names unknown to the built-in adapters skip the binary probe,
+    /// so these tests do not depend on which CLIs are installed.
+    fn failover_daemon(tmp: &std::path::Path) -> crate::team::daemon::TeamDaemon {
+        let mut primary = engineer_member("eng-1", None, false);
+        primary.role_name = "engineer".to_string();
+        primary.agent = Some("primary-cli".to_string());
+        primary.model = Some("big".to_string());
+        let mut peer = engineer_member("eng-2", None, false);
+        peer.role_name = "peer".to_string();
+        peer.agent = Some("fallback-a".to_string());
+        let mut daemon = make_test_daemon(tmp, vec![primary, peer]);
+        daemon.config.team_config.roles.push(RoleDef {
+            name: "engineer".to_string(),
+            role_type: RoleType::Engineer,
+            agent: Some("primary-cli".to_string()),
+            failover: vec![
+                BackendFallback {
+                    agent: "fallback-a".to_string(),
+                    model: None,
+                },
+                BackendFallback {
+                    agent: "fallback-b".to_string(),
+                    model: Some("small".to_string()),
+                },
+            ],
+            ..RoleDef::default()
+        });
+        daemon
+    }
+
+    fn member_backend(
+        daemon: &crate::team::daemon::TeamDaemon,
+        name: &str,
+    ) -> (Option<String>, Option<String>) {
+        let member = daemon
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == name)
+            .unwrap();
+        (member.agent.clone(), member.model.clone())
+    }
+
+    #[test]
+    fn failover_walks_the_list_and_returns_to_primary() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = failover_daemon(tmp.path());
+        daemon.active_tasks.insert("eng-1".to_string(), 42);
+        daemon.backend_health.insert(
+            "eng-1".to_string(),
+            crate::agent::BackendHealth::QuotaExhausted,
+        );
+        let future = crate::team::now_unix() + 3600;
+        daemon
+            .backend_quota_retry_at
+            .insert("eng-1".to_string(), future);
+
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "quota_exhausted", Some(future))
+                .unwrap()
+        );
+        assert_eq!(
+            member_backend(&daemon, "eng-1"),
+            (Some("fallback-a".to_string()), None)
+        );
+        assert!(!daemon.member_backend_parked("eng-1"));
+
+        // The first fallback gets blocked too: move on down the list.
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "auth_required", None)
+                .unwrap()
+        );
+        assert_eq!(
+            member_backend(&daemon, "eng-1"),
+            (Some("fallback-b".to_string()), Some("small".to_string()))
+        );
+        assert!(
+            !daemon
+                .maybe_fail_over_backend("eng-1", "quota_exhausted", None)
+                .unwrap()
+        );
+
+        // Not due yet, then due but the member is mid-turn.
+        daemon.maybe_restore_primary_backends().unwrap();
+        assert_eq!(
+            member_backend(&daemon, "eng-1").0.as_deref(),
+            Some("fallback-b")
+        );
+        daemon
+            .backend_failovers
+            .get_mut("eng-1")
+            .unwrap()
+            .primary_retry_at = 0;
+        daemon
+            .states
+            .insert("eng-1".to_string(), MemberState::Working);
+        daemon.maybe_restore_primary_backends().unwrap();
+        assert_eq!(
+            member_backend(&daemon, "eng-1").0.as_deref(),
+            Some("fallback-b")
+        );
+
+        daemon.states.insert("eng-1".to_string(), MemberState::Idle);
+        daemon.maybe_restore_primary_backends().unwrap();
+        assert_eq!(
+            member_backend(&daemon, "eng-1"),
+            (Some("primary-cli".to_string()), Some("big".to_string()))
+        );
+        assert!(daemon.backend_failovers.is_empty());
+
+        let events = read_events(&crate::team::team_events_path(tmp.path())).unwrap();
+        let switches: Vec<_> = events
+            .iter()
+            .filter(|event| event.event == "backend_switched")
+            .map(|event| {
+                (
+                    event.from.clone().unwrap(),
+                    event.to.clone().unwrap(),
+                    event.reason.clone().unwrap(),
+                    event.task.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            switches,
+            vec![
+                (
+                    "primary-cli/big".to_string(),
+                    "fallback-a".to_string(),
+                    "quota_exhausted".to_string(),
+                    Some("42".to_string()),
+                ),
+                (
+                    "fallback-a".to_string(),
+                    "fallback-b/small".to_string(),
+                    "auth_required".to_string(),
+                    Some("42".to_string()),
+                ),
+                (
+                    "fallback-b/small".to_string(),
+                    "primary-cli/big".to_string(),
+                    "primary_recovered".to_string(),
+                    Some("42".to_string()),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn failover_survives_reconcile_and_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = failover_daemon(tmp.path());
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "quota_exhausted", None)
+                .unwrap()
+        );
+
+        // The reload rebuilds members from team.yaml, which now names a
+        // different primary model.
+        let mut members = daemon.config.members.clone();
+        members[0].agent = Some("primary-cli".to_string());
+        members[0].model = Some("bigger".to_string());
+        let new_config = daemon.config.team_config.clone();
+        daemon
+            .reconcile_topology(
+                crate::team::config_diff::TopologyDiff {
+                    added: Vec::new(),
+                    removed: Vec::new(),
+                    unchanged: vec!["eng-1".to_string(), "eng-2".to_string()],
+                },
+                new_config,
+                members,
+            )
+            .unwrap();
+        assert_eq!(
+            member_backend(&daemon, "eng-1"),
+            (Some("fallback-a".to_string()), None)
+        );
+        let failover = &daemon.backend_failovers["eng-1"];
+        assert_eq!(failover.current.agent, "fallback-a");
+        assert_eq!(failover.primary.model.as_deref(), Some("bigger"));
+
+        let mut restarted = failover_daemon(tmp.path());
+        assert_eq!(
+            member_backend(&restarted, "eng-1").0.as_deref(),
+            Some("primary-cli")
+        );
+        restarted.restore_member_overrides();
+        assert_eq!(
+            member_backend(&restarted, "eng-1"),
+            (Some("fallback-a".to_string()), None)
+        );
+        assert_eq!(restarted.backend_failovers["eng-1"].fallback_index, 0);
+
+        restarted
+            .backend_failovers
+            .get_mut("eng-1")
+            .unwrap()
+            .primary_retry_at = 0;
+        restarted.maybe_restore_primary_backends().unwrap();
+        assert_eq!(
+            member_backend(&restarted, "eng-1"),
+            (Some("primary-cli".to_string()), Some("big".to_string()))
+        );
+        let mut after_restore = failover_daemon(tmp.path());
+        after_restore.restore_member_overrides();
+        assert!(after_restore.backend_failovers.is_empty());
+    }
+
+    #[test]
+    fn auth_failover_waits_for_reauth() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = failover_daemon(tmp.path());
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "auth_required", None)
+                .unwrap()
+        );
+        daemon
+            .backend_failovers
+            .get_mut("eng-1")
+            .unwrap()
+            .primary_retry_at = 0;
+        daemon.maybe_restore_primary_backends().unwrap();
+        assert_eq!(
+            member_backend(&daemon, "eng-1").0.as_deref(),
+            Some("fallback-a")
+        );
+
+        assert_eq!(daemon.mark_backend_reauthenticated("fallback-a"), 0);
+        assert_eq!(daemon.mark_backend_reauthenticated("primary-cli"), 1);
+        daemon.maybe_restore_primary_backends().unwrap();
+        assert_eq!(
+            member_backend(&daemon, "eng-1"),
+            (Some("primary-cli".to_string()), Some("big".to_string()))
+        );
+    }
+
+    #[test]
+    fn failed_restores_back_off() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = failover_daemon(tmp.path());
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "quota_exhausted", None)
+                .unwrap()
+        );
+
+        // Another member parked on the primary keeps it unavailable.
+        daemon.config.members[1].agent = Some("primary-cli".to_string());
+        daemon.backend_health.insert(
+            "eng-2".to_string(),
+            crate::agent::BackendHealth::QuotaExhausted,
+        );
+        for (failures, wait) in [(1, 60 * 60), (2, 2 * 60 * 60)] {
+            daemon
+                .backend_failovers
+                .get_mut("eng-1")
+                .unwrap()
+                .primary_retry_at = 0;
+            let now = crate::team::now_unix();
+            daemon.maybe_restore_primary_backends().unwrap();
+            let failover = &daemon.backend_failovers["eng-1"];
+            assert_eq!(failover.current.agent, "fallback-a");
+            assert_eq!(failover.restore_failures, failures);
+            assert!(failover.primary_retry_at >= now + wait);
+            assert!(failover.primary_retry_at <= crate::team::now_unix() + wait);
+        }
+
+        // The primary comes back, but blocks again right after the restore.
+        daemon.backend_health.remove("eng-2");
+        daemon
+            .backend_failovers
+            .get_mut("eng-1")
+            .unwrap()
+            .primary_retry_at = 0;
+        daemon.maybe_restore_primary_backends().unwrap();
+        assert!(daemon.backend_failovers.is_empty());
+        let now = crate::team::now_unix();
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "quota_exhausted", None)
+                .unwrap()
+        );
+        let failover = &daemon.backend_failovers["eng-1"];
+        assert_eq!(failover.restore_failures, 3);
+        assert!(failover.primary_retry_at >= now + 4 * 60 * 60);
+        assert!(daemon.restored_primaries.is_empty());
+    }
+
+    #[test]
+    fn failover_skips_backends_parked_for_other_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = failover_daemon(tmp.path());
+        daemon.backend_health.insert(
+            "eng-2".to_string(),
+            crate::agent::BackendHealth::QuotaExhausted,
+        );
+
+        assert!(
+            daemon
+                .maybe_fail_over_backend("eng-1", "auth_required", None)
+                .unwrap()
+        );
+        assert_eq!(
+            member_backend(&daemon, "eng-1").0.as_deref(),
+            Some("fallback-b")
+        );
+    }
+
+    #[test]
+    fn roles_without_failover_stay_parked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = failover_daemon(tmp.path());
+        assert!(
+            !daemon
+                .maybe_fail_over_backend("eng-2", "quota_exhausted", None)
+                .unwrap()
+        );
+        assert_eq!(
+            member_backend(&daemon, "eng-2").0.as_deref(),
+            Some("fallback-a")
+        );
+        assert!(daemon.backend_failovers.is_empty());
+    }
+}
@@ -0,0 +1,409 @@
+//! Live budget enforcement: charge shim-reported token usage to the budget
+//! ledger and act on soft and hard limit crossings.
+
+use anyhow::Result;
+use tracing::{debug, info, warn};
+
+use super::TeamDaemon;
+use crate::shim::protocol::ShutdownReason;
+use crate::team::budget::{self, BudgetCrossing, BudgetLevel, BudgetScope};
+use crate::team::config::BudgetHardAction;
+use crate::team::cost;
+use crate::team::events::TeamEvent;
+
+const BUDGET_SHUTDOWN_TIMEOUT_SECS: u32 = 30;
+
+impl TeamDaemon {
+    /// Remember the model a shim reports so dollar usage can be priced for
+    /// roles that do not pin a model in team.yaml.
+    pub(super) fn record_budget_model(&mut self, member_name: &str, model: &str) {
+        if !self.config.team_config.budget.is_enabled()
+            || self.budget_ledger.member_model(member_name) == Some(model)
+        {
+            return;
+        }
+        self.budget_ledger.note_model(member_name, model);
+    }
+
+    /// Charge the delta between this and the previous cumulative session
+    /// counters, then act on any limit the charge pushed past.
+    pub(super) fn record_budget_session_stats(
+        &mut self,
+        member_name: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> Result<()> {
+        if !self.config.team_config.budget.is_enabled() {
+            return Ok(());
+        }
+        self.budget_ledger.roll_day(&budget::today());
+        let (delta_input, delta_output) =
+            self.budget_ledger
+                .session_delta(member_name, input_tokens, output_tokens);
+        let tokens = delta_input + delta_output;
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let member = self
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == member_name);
+        let role = member
+            .map(|member| member.role_name.clone())
+            .unwrap_or_else(|| member_name.to_string());
+        let model = member.and_then(|member| member.model.clone()).or_else(|| {
+            self.budget_ledger
+                .member_model(member_name)
+                .map(str::to_string)
+        });
+        let usd = model
+            .as_deref()
+            .and_then(|model| {
+                cost::estimate_token_cost_usd(
+                    &self.config.team_config.cost.models,
+                    model,
+                    delta_input,
+                    delta_output,
+                )
+            })
+            .unwrap_or(0.0);
+
+        let task = self.active_task_id(member_name).map(|task_id| {
+            let priority = self.budget_task_priority(task_id);
+            (task_id, priority)
+        });
+        self.budget_ledger.charge(
+            member_name,
+            task.as_ref()
+                .map(|(task_id, priority)| (*task_id, priority.as_str())),
+            tokens,
+            usd,
+        );
+        let crossings = self.budget_ledger.evaluate(
+            &self.config.team_config.budget,
+            member_name,
+            &role,
+            task.as_ref().map(|(task_id, _)| *task_id),
+        );
+        if let Err(error) = budget::save_ledger(&self.config.project_root, &self.budget_ledger) {
+            warn!(error = %error, "failed to persist budget ledger");
+        }
+
+        for crossing in crossings {
+            self.apply_budget_crossing(member_name, &role, &crossing);
+        }
+        Ok(())
+    }
+
+    fn budget_task_priority(&self, task_id: u32) -> String {
+        if let Some(entry) = self.budget_ledger.tasks.get(&task_id) {
+            return entry.priority.clone();
+        }
+        crate::task::load_tasks_from_dir(&self.board_dir().join("tasks"))
+            .ok()
+            .and_then(|tasks| tasks.into_iter().find(|task| task.id == task_id))
+            .map(|task| task.priority)
+            .unwrap_or_default()
+    }
+
+    fn apply_budget_crossing(&mut self, member_name: &str, role: &str, crossing: &BudgetCrossing) {
+        let summary = crossing.summary();
+        let scope = crossing.scope.label();
+        let task_id = match crossing.scope {
+            BudgetScope::Task { task_id, .. } => Some(task_id),
+            _ => self.active_task_id(member_name),
+        };
+        let event_role = (!crossing.scope.is_team_wide()).then_some(role);
+        let action = self.config.team_config.budget.on_hard_limit;
+
+        match crossing.level {
+            BudgetLevel::Soft => {
+                self.emit_event(TeamEvent::budget_soft_limit(
+                    &scope, event_role, task_id, &summary,
+                ));
+                self.record_orchestrator_action(format!("budget: {summary}"));
+                self.notify_budget_crossing(member_name, &format!("Budget warning: {summary}."));
+            }
+            BudgetLevel::Hard => {
+                let action_label = match action {
+                    BudgetHardAction::PauseDispatch => "pause_dispatch",
+                    BudgetHardAction::Shutdown => "shutdown",
+                };
+                self.emit_event(TeamEvent::budget_hard_limit(
+                    &scope,
+                    event_role,
+                    task_id,
+                    action_label,
+                    &summary,
+                ));
+                self.record_orchestrator_action(format!("budget: {summary} ({action_label})"));
+                let target = if crossing.scope.is_team_wide() {
+                    "the team"
+                } else {
+                    member_name
+                };
+                let consequence = match action {
+                    BudgetHardAction::PauseDispatch => {
+                        format!("Dispatch is paused for {target}")
+                    }
+                    BudgetHardAction::Shutdown => {
+                        format!("Agents for {target} were checkpointed and shut down")
+                    }
+                };
+                self.notify_budget_crossing(
+                    member_name,
+                    &format!(
+                        "Budget hard limit: {summary}. {consequence} until the limit is raised in team.yaml."
+                    ),
+                );
+                if action == BudgetHardAction::Shutdown {
+                    if crossing.scope.is_team_wide() {
+                        let members: Vec<String> = self.shim_handles.keys().cloned().collect();
+                        for member in members {
+                            self.shutdown_member_for_budget(&member);
+                        }
+                    } else {
+                        self.shutdown_member_for_budget(member_name);
+                    }
+                }
+            }
+        }
+    }
+
+    fn notify_budget_crossing(&mut self, member_name: &str, body: &str) {
+        let recipient = self.assignment_sender(member_name);
+        if let Err(error) = self.queue_daemon_message(&recipient, body) {
+            warn!(
+                member = member_name,
+                recipient = %recipient,
+                error = %error,
+                "failed to notify budget crossing"
+            );
+        }
+    }
+
+    fn shutdown_member_for_budget(&mut self, member_name: &str) {
+        if let Some(task_id) = self.active_task_id(member_name)
+            && let Ok(tasks) = crate::task::load_tasks_from_dir(&self.board_dir().join("tasks"))
+            && let Some(task) = tasks.iter().find(|task| task.id == task_id)
+        {
+            let checkpoint = crate::team::checkpoint::gather_checkpoint(
+                &self.config.project_root,
+                member_name,
+                task,
+            );
+            if let Err(error) =
+                crate::team::checkpoint::write_checkpoint(&self.config.project_root, &checkpoint)
+            {
+                warn!(
+                    member = member_name,
+                    task_id,
+                    error = %error,
+                    "failed to write budget checkpoint"
+                );
+            }
+        }
+        let Some(handle) = self.shim_handles.get_mut(member_name) else {
+            return;
+        };
+        if handle.is_terminal() {
+            return;
+        }
+        match handle
+            .send_shutdown_with_reason(BUDGET_SHUTDOWN_TIMEOUT_SECS, ShutdownReason::Requested)
+        {
+            Ok(()) => {
+                info!(member = member_name, "shut down shim at budget hard limit");
+                self.record_orchestrator_action(format!(
+                    "budget: shut down {member_name} at hard limit"
+                ));
+            }
+            Err(error) => warn!(
+                member = member_name,
+                error = %error,
+                "failed to shut down shim at budget hard limit"
+            ),
+        }
+    }
+
+    /// True when a hard role or task budget blocks new work for this member.
+    /// Evaluated against the live config so raising a limit releases the gate.
+    pub(in crate::team) fn member_budget_blocked(&self, member_name: &str) -> bool {
+        let budget = &self.config.team_config.budget;
+        if !budget.is_enabled() {
+            return false;
+        }
+        let Some(member) = self
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == member_name)
+        else {
+            return false;
+        };
+        let blocked = self
+            .budget_ledger
+            .member_hard_limited(
+                budget,
+                member_name,
+                &member.role_name,
+                self.active_task_id(member_name),
+            )
+            .is_some();
+        if blocked {
+            debug!(member = member_name, "member is over a hard budget");
+        }
+        blocked
+    }
+
+    /// True when a run or day hard budget gates all dispatch.
+    pub(super) fn dispatch_paused_by_budget(&self) -> bool {
+        let budget = &self.config.team_config.budget;
+        budget.is_enabled() && self.budget_ledger.team_hard_limited(budget).is_some()
+    }
+
+    /// Whether a shim that died should stay down because it was shut down
+    /// for budget.
+    pub(super) fn budget_holds_member_down(&self, member_name: &str) -> bool {
+        self.config.team_config.budget.on_hard_limit == BudgetHardAction::Shutdown
+            && (self.member_budget_blocked(member_name) || self.dispatch_paused_by_budget())
+    }
+
+    /// Reset run-scoped budget usage for a fresh (non-resumed) daemon start.
+    pub(super) fn start_budget_run(&mut self) {
+        if !self.config.team_config.budget.is_enabled() {
+            return;
+        }
+        let now = crate::team::now_unix();
+        self.budget_ledger.start_run(now);
+        self.budget_ledger.roll_day(&budget::today());
+        if let Err(error) = budget::save_ledger(&self.config.project_root, &self.budget_ledger) {
+            warn!(error = %error, "failed to persist budget ledger");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::team::config::{BudgetConfig, BudgetLimit};
+    use crate::team::inbox;
+    use crate::team::test_support::{TestDaemonBuilder, engineer_member, manager_member};
+
+    fn budget_daemon(tmp: &tempfile::TempDir, action: BudgetHardAction) -> TeamDaemon {
+        std::fs::create_dir_all(tmp.path().join(".batty").join("team_config")).unwrap();
+        inbox::init_inbox(&inbox::inboxes_root(tmp.path()), "manager").unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![
+                manager_member("manager", None),
+                engineer_member("eng-1", Some("manager"), false),
+            ])
+            .build();
+        daemon.config.team_config.budget = BudgetConfig {
+            roles: HashMap::from([(
+                "eng".to_string(),
+                BudgetLimit {
+                    soft_tokens: Some(100),
+                    hard_tokens: Some(200),
+                    ..BudgetLimit::default()
+                },
+            )]),
+            on_hard_limit: action,
+            ..BudgetConfig::default()
+        };
+        daemon
+    }
+
+    #[test]
+    fn session_stats_charge_deltas_and_notify_manager_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = budget_daemon(&tmp, BudgetHardAction::PauseDispatch);
+
+        daemon.record_budget_session_stats("eng-1", 60, 0).unwrap();
+        daemon.record_budget_session_stats("eng-1", 90, 30).unwrap();
+        daemon.record_budget_session_stats("eng-1", 90, 30).unwrap();
+        assert_eq!(daemon.budget_ledger.members["eng-1"].usage.tokens, 120);
+
+        let messages =
+            inbox::pending_messages(&inbox::inboxes_root(tmp.path()), "manager").unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].body.contains("Budget warning"));
+        assert!(!daemon.member_budget_blocked("eng-1"));
+
+        let persisted = budget::load_ledger(tmp.path());
+        assert_eq!(persisted.members["eng-1"].usage.tokens, 120);
+    }
+
+    #[test]
+    fn hard_limit_blocks_member_until_limit_raised() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = budget_daemon(&tmp, BudgetHardAction::PauseDispatch);
+
+        daemon
+            .record_budget_session_stats("eng-1", 150, 60)
+            .unwrap();
+        assert!(daemon.member_budget_blocked("eng-1"));
+        assert!(!daemon.dispatch_paused_by_budget());
+        assert!(!daemon.budget_holds_member_down("eng-1"));
+
+        let events = std::fs::read_to_string(
+            tmp.path()
+                .join(".batty")
+                .join("team_config")
+                .join("events.jsonl"),
+        )
+        .unwrap();
+        assert!(events.contains("budget_hard_limit"));
+        assert!(events.contains("pause_dispatch"));
+
+        daemon
+            .config
+            .team_config
+            .budget
+            .roles
+            .get_mut("eng")
+            .unwrap()
+            .hard_tokens = Some(1_000);
+        assert!(!daemon.member_budget_blocked("eng-1"));
+    }
+
+    #[test]
+    fn run_hard_limit_pauses_team_dispatch_and_holds_shims_down() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = budget_daemon(&tmp, BudgetHardAction::Shutdown);
+        daemon.config.team_config.budget.run = Some(BudgetLimit {
+            hard_tokens: Some(50),
+            ..BudgetLimit::default()
+        });
+
+        daemon.record_budget_session_stats("eng-1", 40, 20).unwrap();
+        assert!(daemon.dispatch_paused_by_budget());
+        assert!(daemon.budget_holds_member_down("eng-1"));
+
+        let messages =
+            inbox::pending_messages(&inbox::inboxes_root(tmp.path()), "manager").unwrap();
+        assert!(
+            messages
+                .iter()
+                .any(|message| message.body.contains("checkpointed and shut down"))
+        );
+    }
+
+    #[test]
+    fn start_budget_run_resets_run_usage() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = budget_daemon(&tmp, BudgetHardAction::PauseDispatch);
+        daemon
+            .record_budget_session_stats("eng-1", 150, 60)
+            .unwrap();
+        assert!(daemon.member_budget_blocked("eng-1"));
+
+        daemon.start_budget_run();
+        assert!(!daemon.member_budget_blocked("eng-1"));
+        assert_eq!(daemon.budget_ledger.run.tokens, 0);
+    }
+}
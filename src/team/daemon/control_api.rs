@@ -0,0 +1,182 @@
+//! Daemon side of the local control API: listener startup and the poll-loop
+//! step that executes queued mutating requests.
+
+use anyhow::Result;
+use serde_json::json;
+use tracing::warn;
+
+use super::TeamDaemon;
+use super::operator_commands::OperatorSource;
+use crate::team::api::{self, ApiCommand, ApiServer};
+use crate::team::operator::Permission;
+
+impl TeamDaemon {
+    /// Start the console socket, plus the configured control API listener
+    /// when `api.enabled` is set. A bind failure is logged and leaves the
+    /// daemon running without the API.
+    pub(super) fn start_api_server(&mut self) {
+        if self.api_server.is_some() {
+            return;
+        }
+        let config = &self.config.team_config.api;
+        match ApiServer::start(&self.config.project_root, config) {
+            Ok(server) => {
+                self.record_orchestrator_action(format!(
+                    "runtime: control API listening on {}",
+                    server.endpoints().join(", ")
+                ));
+                self.api_server = Some(server);
+            }
+            Err(error) => {
+                warn!(error = %error, "failed to start control API");
+                self.record_orchestrator_action(format!(
+                    "runtime: control API failed to start: {error:#}"
+                ));
+            }
+        }
+    }
+
+    /// Run every mutating API request queued since the last tick, in arrival
+    /// order, and hand each result back to the waiting HTTP connection.
+    pub(super) fn process_api_requests(&mut self) -> Result<()> {
+        loop {
+            let Some(request) = self
+                .api_server
+                .as_ref()
+                .and_then(ApiServer::try_next_request)
+            else {
+                return Ok(());
+            };
+            let summary = request.command.summary();
+            if let ApiCommand::Operator { user, member, text } = &request.command {
+                // Anyone holding the API token already controls the team, so
+                // human callers act with admin permission. Agents reach the
+                // same socket from their sessions; they keep operator access
+                // and never grant, deny, start or stop.
+                let permission = if member.is_some() {
+                    Permission::Operator
+                } else {
+                    Permission::Admin
+                };
+                let source = OperatorSource {
+                    transport: "cli",
+                    user,
+                    member: member.as_deref(),
+                    location: "console",
+                };
+                let reply = self.run_operator_text(source, Some(permission), text);
+                self.record_orchestrator_action(format!("api: {summary}"));
+                request.respond(Ok(json!({ "ok": true, "reply": reply })));
+                continue;
+            }
+            let result = api::execute_command(&self.config.project_root, &request.command);
+            match &result {
+                Ok(_) => self.record_orchestrator_action(format!("api: {summary}")),
+                Err(error) => {
+                    self.record_orchestrator_action(format!("api: {summary} failed: {error:#}"))
+                }
+            }
+            request.respond(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+    use crate::team::test_support::TestDaemonBuilder;
+
+    #[test]
+    fn process_api_requests_runs_queued_commands_on_daemon_thread() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.config.team_config.api.enabled = true;
+        daemon.config.team_config.api.listen = "127.0.0.1:0".to_string();
+        daemon.config.team_config.api.token_env = "BATTY_API_TOKEN_TEST_UNSET".to_string();
+        daemon.start_api_server();
+        let endpoint = daemon.api_server.as_ref().unwrap().endpoints()[0].clone();
+        let token = std::fs::read_to_string(api::api_token_path(tmp.path())).unwrap();
+
+        let client = std::thread::spawn(move || {
+            let addr = endpoint.trim_start_matches("http://");
+            let mut stream = std::net::TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "POST /v1/pause HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: 0\r\n\r\n",
+                token.trim()
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !crate::team::session::pause_marker_path(tmp.path()).exists() {
+            assert!(std::time::Instant::now() < deadline, "pause never ran");
+            daemon.process_api_requests().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.contains(r#""paused":true"#), "{response}");
+    }
+
+    #[test]
+    fn process_api_requests_is_noop_without_listener() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.process_api_requests().unwrap();
+        assert!(daemon.api_server.is_none());
+    }
+
+    #[test]
+    fn agent_sessions_get_operator_access_and_are_audited() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.config.team_config.api.enabled = true;
+        daemon.config.team_config.api.listen = "127.0.0.1:0".to_string();
+        daemon.config.team_config.api.token_env = "BATTY_API_TOKEN_TEST_UNSET".to_string();
+        daemon.start_api_server();
+        let endpoint = daemon.api_server.as_ref().unwrap().endpoints()[0].clone();
+        let token = std::fs::read_to_string(api::api_token_path(tmp.path())).unwrap();
+
+        let client = std::thread::spawn(move || {
+            let addr = endpoint.trim_start_matches("http://");
+            let body = r#"{"command":"/grant 1","user":"alice","member":"eng-1"}"#;
+            let mut stream = std::net::TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "POST /v1/operator HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{body}",
+                token.trim(),
+                body.len()
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let audit_path = crate::team::operator::audit_log_path(tmp.path());
+        while !audit_path.exists() {
+            assert!(std::time::Instant::now() < deadline, "operator never ran");
+            daemon.process_api_requests().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let response = client.join().unwrap();
+        assert!(
+            response.contains("Permission denied: grant needs admin access."),
+            "{response}"
+        );
+        let audit = crate::team::operator::read_audit(tmp.path()).unwrap();
+        assert_eq!(audit[0].user, "alice");
+        assert_eq!(audit[0].member.as_deref(), Some("eng-1"));
+        assert_eq!(audit[0].permission, Some(Permission::Operator));
+        assert_eq!(audit[0].result, crate::team::operator::AuditResult::Denied);
+    }
+}
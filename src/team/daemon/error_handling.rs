@@ -250,13 +250,25 @@ pub(crate) fn optional_subsystem_for_step(step: &str) -> Option<&'static str> {
         "telemetry_emit_event" => Some("telemetry"),
         "process_discord_queue" => Some("discord"),
         "process_telegram_queue" => Some("telegram"),
+        "process_slack_queue" => Some("slack"),
+        "process_webhooks" => Some("webhooks"),
+        "export_task_traces" => Some("traces"),
         "maybe_generate_standup" => Some("standup"),
         _ => None,
     }
 }
 
-pub(crate) fn optional_subsystem_names() -> [&'static str; 5] {
-    ["telemetry", "discord", "telegram", "grafana", "standup"]
+pub(crate) fn optional_subsystem_names() -> [&'static str; 8] {
+    [
+        "telemetry",
+        "discord",
+        "telegram",
+        "slack",
+        "webhooks",
+        "traces",
+        "grafana",
+        "standup",
+    ]
 }
 
 fn optional_subsystem_backoff_key(subsystem: &str) -> String {
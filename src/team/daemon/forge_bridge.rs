@@ -0,0 +1,38 @@
+//! Forge sync orchestration for the daemon poll loop.
+//!
+//! Runs [`crate::team::forge_sync::sync_once`] at most once per
+//! `forge.sync_interval_secs` and records what changed in the orchestrator
+//! log. Failures count against the `forge` optional-subsystem error budget,
+//! so an unreachable forge backs off instead of failing every tick.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use super::TeamDaemon;
+use crate::team::forge::ForgeClient;
+use crate::team::forge_sync;
+
+impl TeamDaemon {
+    pub(super) fn maybe_sync_forge(&mut self) -> Result<()> {
+        let config = &self.config.team_config.forge;
+        if !config.enabled {
+            return Ok(());
+        }
+        let interval = Duration::from_secs(config.sync_interval_secs);
+        if self
+            .last_forge_sync
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return Ok(());
+        }
+        self.last_forge_sync = Some(Instant::now());
+
+        let client = ForgeClient::from_config(config)?;
+        let report = forge_sync::sync_once(&self.config.project_root, config, &client)?;
+        for action in report.actions {
+            self.record_orchestrator_action(format!("forge sync: {action}"));
+        }
+        Ok(())
+    }
+}
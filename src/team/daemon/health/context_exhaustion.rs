@@ -824,6 +824,8 @@ mod tests {
             completed: None,
             description: "resume".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: tmp.path().join("task-42.md"),
         };
         crate::team::context_management::stage_restart_context(
@@ -367,6 +367,13 @@ exit 1
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -427,15 +434,15 @@ mod tests {
     }
 
     #[test]
-    fn nonfatal_kanban_failures_are_relayed_to_known_members() {
+    fn nonfatal_board_failures_are_relayed_to_known_members() {
         let tmp = tempfile::tempdir().unwrap();
         let mut daemon = TestDaemonBuilder::new(tmp.path())
             .members(vec![manager_member("manager", None)])
             .build();
 
-        daemon.report_nonfatal_kanban_failure(
+        daemon.report_nonfatal_board_failure(
             "move task #42 to done",
-            "kanban-md stderr goes here",
+            "task #42 is claimed by eng-2",
             ["manager"],
         );
 
@@ -444,7 +451,7 @@ mod tests {
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0].from, "daemon");
         assert!(messages[0].body.contains("move task #42 to done"));
-        assert!(messages[0].body.contains("kanban-md stderr goes here"));
+        assert!(messages[0].body.contains("task #42 is claimed by eng-2"));
     }
 
     // ── restart_assignment_message tests ──
@@ -481,6 +488,8 @@ mod tests {
             cron_last_run: None,
             completed: None,
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("/tmp/task-42.md"),
         };
         let msg = TeamDaemon::restart_assignment_message(&task);
@@ -525,6 +534,8 @@ mod tests {
             cron_last_run: None,
             completed: None,
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("/tmp/task-99.md"),
         };
         let msg = TeamDaemon::restart_assignment_message(&task);
@@ -306,6 +306,7 @@ impl TeamDaemon {
                         self.working_since
                             .entry(member_name.to_string())
                             .or_insert_with(Instant::now);
+                        self.trace_member_working(member_name);
                     } else {
                         self.working_since.remove(member_name);
                     }
@@ -406,6 +407,7 @@ impl TeamDaemon {
                     handle.apply_state_change(ShimState::Idle);
                     handle.clear_in_flight_message();
                 }
+                self.clear_tool_activity(member_name);
                 self.states
                     .insert(member_name.to_string(), MemberState::Idle);
                 self.update_automation_timers_for_state(member_name, MemberState::Idle);
@@ -455,6 +457,7 @@ impl TeamDaemon {
                 exit_code,
                 last_lines,
             } => {
+                self.clear_tool_activity(member_name);
                 let _ = append_shim_event_log(
                     &self.config.project_root,
                     member_name,
@@ -490,6 +493,13 @@ impl TeamDaemon {
                     self.record_orchestrator_action(format!(
                         "lifecycle: skipped shim respawn for {member_name} — backend parked"
                     ));
+                } else if self.budget_holds_member_down(member_name) {
+                    // Budget shutdown: the shim exited because a hard limit
+                    // was crossed. Keep it down until the limit is raised.
+                    self.record_member_crashed(member_name, false);
+                    self.record_orchestrator_action(format!(
+                        "lifecycle: skipped shim respawn for {member_name} — over hard budget"
+                    ));
                 } else if self.config.team_config.auto_respawn_on_crash {
                     self.record_member_crashed(member_name, true);
                     let respawn = if self.should_cold_respawn_codex_member(member_name, &last_lines)
@@ -593,8 +603,9 @@ impl TeamDaemon {
             Event::SessionStats {
                 output_bytes,
                 uptime_secs,
+                input_tokens,
+                output_tokens,
                 context_usage_pct,
-                ..
             } => {
                 if let Some(handle) = self.shim_handles.get_mut(member_name) {
                     handle.record_output_bytes(output_bytes);
@@ -612,6 +623,7 @@ impl TeamDaemon {
                     uptime_secs,
                     context_usage_pct,
                 )?;
+                self.record_budget_session_stats(member_name, input_tokens, output_tokens)?;
             }
 
             Event::ContextWarning {
@@ -626,6 +638,9 @@ impl TeamDaemon {
                 if let Some(handle) = self.shim_handles.get_mut(member_name) {
                     handle.record_output_bytes(output_bytes);
                 }
+                if let Some(model) = model.as_deref() {
+                    self.record_budget_model(member_name, model);
+                }
                 let model_label = model
                     .as_deref()
                     .map(|value| format!(" model={value}"))
@@ -659,6 +674,35 @@ impl TeamDaemon {
                 )?;
             }
 
+            Event::ToolStarted { .. }
+            | Event::ToolFinished { .. }
+            | Event::FileEdited { .. }
+            | Event::CommandRun { .. } => {
+                self.handle_tool_event(member_name, &event);
+            }
+
+            Event::SandboxViolation { kind, detail } => {
+                let _ = append_shim_event_log(
+                    &self.config.project_root,
+                    member_name,
+                    &format!("<- sandbox_violation {kind}: {detail}"),
+                );
+                warn!(
+                    member = member_name,
+                    kind = kind.as_str(),
+                    detail = detail.as_str(),
+                    "sandbox blocked agent access"
+                );
+                self.record_orchestrator_action(format!(
+                    "sandbox: {member_name} blocked {kind} access — {detail}"
+                ));
+                self.emit_event(crate::team::events::TeamEvent::sandbox_violation(
+                    member_name,
+                    &kind,
+                    &detail,
+                ));
+            }
+
             Event::Warning { message, idle_secs } => {
                 let _ = append_shim_event_log(
                     &self.config.project_root,
@@ -761,6 +805,7 @@ impl TeamDaemon {
                         member_name,
                         &reason,
                     ));
+                    self.fail_over_blocked_backend(member_name, "quota_exhausted", None);
                     return Ok(());
                 }
 
@@ -816,6 +861,7 @@ impl TeamDaemon {
                     member_name,
                     &message,
                 ));
+                self.fail_over_blocked_backend(member_name, "quota_exhausted", retry_at_epoch_secs);
             }
 
             Event::AuthRequired { message } => {
@@ -842,6 +888,7 @@ impl TeamDaemon {
                     member_name,
                     &message,
                 ));
+                self.fail_over_blocked_backend(member_name, "auth_required", None);
             }
         }
 
@@ -1052,24 +1099,12 @@ impl TeamDaemon {
         reason: &str,
         disposition: ShimDisconnectDisposition,
     ) -> Result<()> {
-        let sdk_mode =
-            agent_supports_sdk_mode(&plan.agent_type) && self.config.team_config.use_sdk_mode;
-        let new_handle = super::super::shim_spawn::spawn_shim(
+        let new_handle = self.spawn_member_shim(
             member_name,
             &plan.agent_type,
             &plan.agent_cmd,
-            &self.config.project_root,
             &plan.work_dir,
             log_path,
-            self.config
-                .team_config
-                .workflow_policy
-                .graceful_shutdown_timeout_secs,
-            self.config
-                .team_config
-                .workflow_policy
-                .auto_commit_on_restart,
-            sdk_mode,
         )?;
 
         if let Some(identity) = plan.identity.clone() {
@@ -1242,6 +1277,25 @@ impl TeamDaemon {
         &mut self,
         member_name: &str,
         reason: &str,
+    ) -> Result<()> {
+        self.cold_respawn_member(member_name, reason, false)
+    }
+
+    /// Cold-respawn `member_name` on the agent and model now in its member
+    /// config, replacing a shim launched on a different backend.
+    pub(in crate::team::daemon) fn respawn_member_on_configured_backend(
+        &mut self,
+        member_name: &str,
+        reason: &str,
+    ) -> Result<()> {
+        self.cold_respawn_member(member_name, reason, true)
+    }
+
+    fn cold_respawn_member(
+        &mut self,
+        member_name: &str,
+        reason: &str,
+        switch_backend: bool,
     ) -> Result<()> {
         if let Some(task) = self.active_task(member_name)?
             && let Some(member) = self
@@ -1262,15 +1316,25 @@ impl TeamDaemon {
             );
         }
 
-        let Some(plan) = self.cold_respawn_plan(member_name)? else {
+        let Some(mut plan) = self.cold_respawn_plan(member_name)? else {
             return Ok(());
         };
+        if switch_backend
+            && let Some(agent) = self
+                .config
+                .members
+                .iter()
+                .find(|member| member.name == member_name)
+                .and_then(|member| member.agent.clone())
+        {
+            plan.agent_type = agent;
+        }
 
         self.preserve_worktree_before_restart(member_name, &plan.work_dir, reason);
 
         info!(
             member = member_name,
-            reason, "downgrading warm resume to cold shim respawn"
+            reason, switch_backend, "downgrading warm resume to cold shim respawn"
         );
 
         let log_path = shim_log_path(&self.config.project_root, member_name);
@@ -1425,9 +1489,10 @@ impl TeamDaemon {
         let stuck_members: Vec<String> = self
             .shim_handles
             .iter()
-            .filter(|(_, handle)| {
+            .filter(|(name, handle)| {
                 handle.state == ShimState::Working
                     && handle.secs_since_state_change() > timeout_secs
+                    && !self.tool_activity_shows_progress(name, timeout_secs)
             })
             .map(|(name, _)| name.clone())
             .collect();
@@ -2367,6 +2432,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn handle_shim_event_sandbox_violation_emits_team_event() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        insert_mock_handle(&mut daemon, "eng-1");
+
+        daemon
+            .handle_shim_event(
+                "eng-1",
+                Event::SandboxViolation {
+                    kind: "filesystem".into(),
+                    detail: "touch: cannot touch '/etc/x': Read-only file system".into(),
+                },
+            )
+            .unwrap();
+
+        let events = crate::team::events::read_events(
+            &tmp.path()
+                .join(".batty")
+                .join("team_config")
+                .join("events.jsonl"),
+        )
+        .unwrap();
+        assert!(events.iter().any(|event| {
+            event.event == "sandbox_violation"
+                && event.role.as_deref() == Some("eng-1")
+                && event.reason.as_deref() == Some("filesystem")
+        }));
+    }
+
     #[test]
     fn handle_shim_event_error_counts_toward_context_pressure() {
         let tmp = tempfile::tempdir().unwrap();
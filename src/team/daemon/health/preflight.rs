@@ -5,8 +5,8 @@ use tracing::{info, warn};
 
 use super::super::helpers::{
     board_dir, ensure_agent_binaries_available, ensure_board_initialized, ensure_git_ready,
-    ensure_kanban_available, ensure_telemetry_writable, ensure_tmux_session_ready,
-    ensure_worktree_operations,
+    ensure_telemetry_writable, ensure_tmux_session_ready, ensure_worktree_operations,
+    kanban_cli_available,
 };
 use super::super::*;
 use super::STARTUP_PREFLIGHT_RESPAWN_DELAY;
@@ -48,7 +48,11 @@ impl TeamDaemon {
         ensure_telemetry_writable(&self.config.project_root)?;
         ensure_agent_binaries_available(&self.config.members)?;
         self.ensure_member_panes_ready()?;
-        ensure_kanban_available()?;
+        if !kanban_cli_available() {
+            warn!(
+                "kanban-md not found on PATH; the board is managed natively, but agents cannot run kanban-md commands"
+            );
+        }
         if ensure_board_initialized(&self.config.project_root)? {
             let board_dir = board_dir(&self.config.project_root);
             info!(
@@ -153,7 +157,7 @@ mod tests {
     use super::super::super::*;
     use super::super::helpers::{
         board_dir, ensure_agent_binaries_available, ensure_board_initialized, ensure_git_ready,
-        ensure_kanban_available, ensure_telemetry_writable, ensure_worktree_operations,
+        ensure_telemetry_writable, ensure_worktree_operations, kanban_cli_available,
     };
     use super::super::test_helpers::{EnvVarGuard, PATH_LOCK, setup_fake_kanban, test_team_config};
     use crate::team::config::{
@@ -196,16 +200,29 @@ mod tests {
     }
 
     #[test]
-    fn startup_preflight_reports_missing_kanban_binary() {
+    fn startup_preflight_detects_missing_kanban_binary() {
+        let _path_guard = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+        let tmp = tempfile::tempdir().unwrap();
+        let empty_bin = tmp.path().join("empty-bin");
+        std::fs::create_dir_all(&empty_bin).unwrap();
+        let _path = EnvVarGuard::set("PATH", empty_bin.to_string_lossy().as_ref());
+
+        assert!(!kanban_cli_available());
+    }
+
+    #[test]
+    fn startup_preflight_initializes_board_without_kanban_binary() {
         let _path_guard = PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner());
         let tmp = tempfile::tempdir().unwrap();
         let empty_bin = tmp.path().join("empty-bin");
         std::fs::create_dir_all(&empty_bin).unwrap();
         let _path = EnvVarGuard::set("PATH", empty_bin.to_string_lossy().as_ref());
 
-        let error = ensure_kanban_available().unwrap_err();
+        assert!(ensure_board_initialized(tmp.path()).unwrap());
 
-        assert!(format!("{error:#}").contains("kanban-md"));
+        let board_dir = board_dir(tmp.path());
+        assert!(board_dir.join("config.yml").is_file());
+        assert!(board_dir.join("tasks").is_dir());
     }
 
     #[test]
@@ -565,6 +582,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -783,6 +807,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -887,6 +918,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
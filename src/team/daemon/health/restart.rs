@@ -372,6 +372,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -503,6 +510,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -643,6 +657,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -755,6 +776,8 @@ mod tests {
             cron_last_run: None,
             completed: None,
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: tmp.path().join("task-42.md"),
         };
         let handoff_path = tmp.path().join(crate::shim::runtime::HANDOFF_FILE_NAME);
@@ -875,6 +898,8 @@ mod tests {
             cron_last_run: None,
             completed: None,
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: tmp.path().join("task-7.md"),
         };
         let handoff_path = tmp.path().join(crate::shim::runtime::HANDOFF_FILE_NAME);
@@ -925,6 +950,8 @@ mod tests {
             cron_last_run: None,
             completed: None,
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: tmp.path().join("task-99.md"),
         };
 
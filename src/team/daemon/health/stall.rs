@@ -1497,6 +1497,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -1663,6 +1670,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
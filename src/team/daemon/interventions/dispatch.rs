@@ -662,6 +662,8 @@ mod tests {
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::from("task-50.md"),
         };
         let sig = manager_dispatch_intervention_signature(&[], &[&idle], &[&task]);
@@ -716,6 +718,8 @@ mod tests {
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::from("task-1.md"),
         };
         let sig = manager_dispatch_intervention_signature(&[&active], &[&idle], &[&task]);
@@ -428,6 +428,8 @@ mod tests {
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::from(format!("task-{id}.md")),
         }
     }
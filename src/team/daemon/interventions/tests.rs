@@ -2109,6 +2109,8 @@ fn review_backlog_owner_for_task_prefers_reporting_manager() {
         completed: None,
         description: "Task body".to_string(),
         batty_config: None,
+        scope: None,
+        acceptance: Vec::new(),
         source_path: std::path::PathBuf::from("task-42.md"),
     };
     let members = vec![
@@ -2154,6 +2156,8 @@ fn review_backlog_owner_for_task_falls_back_to_claimed_by_when_member_missing()
         completed: None,
         description: "Task body".to_string(),
         batty_config: None,
+        scope: None,
+        acceptance: Vec::new(),
         source_path: std::path::PathBuf::from("task-43.md"),
     };
 
@@ -2194,6 +2198,8 @@ fn review_backlog_owner_for_task_ignores_non_review_tasks() {
         completed: None,
         description: "Task body".to_string(),
         batty_config: None,
+        scope: None,
+        acceptance: Vec::new(),
         source_path: std::path::PathBuf::from("task-44.md"),
     };
 
@@ -3704,6 +3710,8 @@ fn make_task(id: u32, status: &str, claimed_by: Option<&str>) -> crate::task::Ta
         completed: None,
         description: String::new(),
         batty_config: None,
+        scope: None,
+        acceptance: Vec::new(),
         source_path: std::path::PathBuf::new(),
     }
 }
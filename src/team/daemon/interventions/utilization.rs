@@ -543,6 +543,8 @@ mod tests {
                 completed: None,
                 description: String::new(),
                 batty_config: None,
+                scope: None,
+                acceptance: Vec::new(),
                 source_path: std::path::PathBuf::new(),
             }],
         );
@@ -9,12 +9,14 @@ use tracing::{debug, info, warn};
 use super::TeamDaemon;
 use crate::task::load_tasks_from_dir;
 use crate::team::board::{WorkflowMetadata, read_workflow_metadata};
+use crate::team::board_store::BoardStore;
 use crate::team::daemon::verification::run_automatic_verification;
 use crate::team::merge::{
-    MergeLock, MergeMode, MergeOutcome, RootDirtyState, infer_merge_mode_from_failure,
-    inspect_root_dirty_state, merge_engineer_branch,
+    MergeLock, MergeMode, MergeOutcome, MergeSuccess, RootDirtyState,
+    infer_merge_mode_from_failure, inspect_root_dirty_state, merge_engineer_branch,
 };
 use crate::team::task_loop::{current_worktree_branch, read_task_title};
+use crate::team::task_traces::TaskPhase;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct MergeRequest {
@@ -39,6 +41,26 @@ pub(crate) enum MergeQueueOutcome {
     Reverted,
     Skipped,
     Failed,
+    /// Bisection blamed this branch for a failing merge train; it was sent
+    /// back to its engineer for rework.
+    Rework,
+    /// The merge needs human approval `id`; the request is held until the
+    /// approval is decided.
+    AwaitingApproval(u32),
+}
+
+impl MergeQueueOutcome {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Self::Success => "merged",
+            Self::Conflict => "conflicted",
+            Self::Reverted => "reverted",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+            Self::Rework => "sent back",
+            Self::AwaitingApproval(_) => "awaiting approval",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -46,6 +68,8 @@ pub(crate) struct MergeQueueEvent {
     pub task_id: u32,
     pub engineer: String,
     pub outcome: MergeQueueOutcome,
+    /// When the request first entered the queue.
+    pub queued_at: Instant,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -56,7 +80,7 @@ struct MergeQueueLastResult {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum AutoMergeSkipReason {
+pub(super) enum AutoMergeSkipReason {
     WrongStatus,
     MissingPacket,
     NoBranch,
@@ -76,6 +100,8 @@ impl AutoMergeSkipReason {
 pub(crate) struct MergeQueue {
     queue: VecDeque<MergeRequest>,
     active: Option<MergeRequest>,
+    /// Requests parked on a pending approval, keyed by approval id.
+    held: Vec<(u32, MergeRequest)>,
     last_result: Option<MergeQueueLastResult>,
     last_reported_status: Option<String>,
 }
@@ -90,11 +116,60 @@ impl MergeQueue {
         self.queue.len()
     }
 
+    /// Requests parked on a pending approval.
+    pub(crate) fn held_len(&self) -> usize {
+        self.held.len()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn active_task_id(&self) -> Option<u32> {
         self.active.as_ref().map(|request| request.task_id)
     }
 
+    /// Pop up to `max` queued requests, in queue order, for one merge train.
+    pub(crate) fn take_train(&mut self, max: usize) -> Vec<MergeRequest> {
+        let count = max.min(self.queue.len());
+        self.queue.drain(..count).collect()
+    }
+
+    /// Put requests back at the head of the queue, keeping their order.
+    pub(crate) fn requeue_front(&mut self, requests: Vec<MergeRequest>) {
+        for request in requests.into_iter().rev() {
+            self.queue.push_front(request);
+        }
+    }
+
+    /// Park `request` until approval `approval_id` is decided.
+    pub(crate) fn hold(&mut self, approval_id: u32, request: MergeRequest) {
+        self.held.push((approval_id, request));
+    }
+
+    /// Requeue held requests whose approval is no longer pending. The gate
+    /// check on the next attempt consumes the decision.
+    pub(crate) fn release_held<F>(&mut self, mut still_pending: F) -> Vec<u32>
+    where
+        F: FnMut(u32) -> bool,
+    {
+        let (waiting, released): (Vec<_>, Vec<_>) = std::mem::take(&mut self.held)
+            .into_iter()
+            .partition(|(approval_id, _)| still_pending(*approval_id));
+        self.held = waiting;
+        let task_ids = released
+            .iter()
+            .map(|(_, request)| request.task_id)
+            .collect();
+        self.requeue_front(released.into_iter().map(|(_, request)| request).collect());
+        task_ids
+    }
+
+    pub(crate) fn record_result(&mut self, task_id: u32, outcome: MergeQueueOutcome) {
+        self.last_result = Some(MergeQueueLastResult {
+            task_id,
+            outcome,
+            finished_at: Instant::now(),
+        });
+    }
+
     pub(crate) fn process_next<F>(&mut self, mut processor: F) -> Result<Option<MergeQueueEvent>>
     where
         F: FnMut(&MergeRequest) -> Result<MergeQueueOutcome>,
@@ -116,6 +191,9 @@ impl MergeQueue {
             }
         };
         self.active = None;
+        if let MergeQueueOutcome::AwaitingApproval(approval_id) = outcome {
+            self.hold(approval_id, request.clone());
+        }
         self.last_result = Some(MergeQueueLastResult {
             task_id: request.task_id,
             outcome: outcome.clone(),
@@ -126,11 +204,16 @@ impl MergeQueue {
             task_id: request.task_id,
             engineer: request.engineer,
             outcome,
+            queued_at: request.queued_at,
         }))
     }
 
     fn status_line(&self) -> Option<String> {
-        if self.queue.is_empty() && self.active.is_none() && self.last_result.is_none() {
+        if self.queue.is_empty()
+            && self.active.is_none()
+            && self.held.is_empty()
+            && self.last_result.is_none()
+        {
             return None;
         }
 
@@ -147,20 +230,19 @@ impl MergeQueue {
                 format!(
                     "#{} {} {}s ago",
                     result.task_id,
-                    match result.outcome {
-                        MergeQueueOutcome::Success => "merged",
-                        MergeQueueOutcome::Conflict => "conflicted",
-                        MergeQueueOutcome::Reverted => "reverted",
-                        MergeQueueOutcome::Skipped => "skipped",
-                        MergeQueueOutcome::Failed => "failed",
-                    },
+                    result.outcome.label(),
                     result.finished_at.elapsed().as_secs()
                 )
             })
             .unwrap_or_else(|| "none".to_string());
+        let held = if self.held.is_empty() {
+            String::new()
+        } else {
+            format!(" | awaiting approval: {}", self.held.len())
+        };
 
         Some(format!(
-            "[merge] queued: {queued} | merging: {merging} | last: {last}"
+            "[merge] queued: {queued} | merging: {merging}{held} | last: {last}"
         ))
     }
 
@@ -177,6 +259,7 @@ impl MergeQueue {
 impl TeamDaemon {
     pub(super) fn process_merge_queue(&mut self) -> Result<()> {
         let mut merge_queue = std::mem::take(&mut self.merge_queue);
+        self.release_approved_merges(&mut merge_queue);
         let queued = merge_queue.queued_len();
         if queued > 0 || merge_queue.active_task_id().is_some() {
             debug!(
@@ -185,14 +268,48 @@ impl TeamDaemon {
                 "processing merge queue"
             );
         }
-        let event = merge_queue.process_next(|request| self.execute_queued_merge(request))?;
-        if let Some(ref event) = event {
+        let train_size = self
+            .config
+            .team_config
+            .workflow_policy
+            .auto_merge
+            .train_size;
+        let events = if train_size > 1 && queued > 1 && !self.is_multi_repo {
+            let train = merge_queue.take_train(train_size);
+            let run = self.execute_merge_train(train)?;
+            merge_queue.requeue_front(run.requeue);
+            for (approval_id, request) in run.held {
+                merge_queue.hold(approval_id, request);
+            }
+            for event in &run.events {
+                merge_queue.record_result(event.task_id, event.outcome.clone());
+            }
+            run.events
+        } else {
+            merge_queue
+                .process_next(|request| self.execute_queued_merge(request))?
+                .into_iter()
+                .collect()
+        };
+        for event in &events {
             info!(
                 task_id = event.task_id,
                 engineer = %event.engineer,
                 outcome = ?event.outcome,
                 "merge queue processed request"
             );
+            if let Some(metrics) = &self.prometheus {
+                metrics.observe_merge(event.outcome.label(), event.queued_at.elapsed());
+            }
+            match event.outcome {
+                MergeQueueOutcome::Success | MergeQueueOutcome::Skipped => {
+                    self.finish_task_trace(event.task_id, event.outcome.label(), false)
+                }
+                MergeQueueOutcome::AwaitingApproval(_) => {
+                    self.end_task_trace_phase(event.task_id, event.outcome.label(), false)
+                }
+                _ => self.end_task_trace_phase(event.task_id, event.outcome.label(), true),
+            }
         }
         if let Some(status) = merge_queue.take_status_update() {
             self.record_orchestrator_action(status);
@@ -208,43 +325,46 @@ impl TeamDaemon {
             engineer = %request.engineer,
             "enqueuing merge request"
         );
+        self.trace_task_phase(
+            request.task_id,
+            TaskPhase::QueuedForMerge,
+            Some(&request.engineer),
+        );
         self.merge_queue.enqueue(request);
     }
 
-    fn execute_queued_merge(&mut self, request: &MergeRequest) -> Result<MergeQueueOutcome> {
+    pub(super) fn execute_queued_merge(
+        &mut self,
+        request: &MergeRequest,
+    ) -> Result<MergeQueueOutcome> {
         if self.is_multi_repo {
             bail!("merge queue execution is not yet implemented for multi-repo projects");
         }
+        self.trace_task_phase(request.task_id, TaskPhase::Merging, Some(&request.engineer));
 
         if let Some((reason, detail)) = merge_request_skip_reason(
             self.project_root(),
             request,
             self.config.team_config.trunk_branch(),
         )? {
-            warn!(
-                task_id = request.task_id,
-                engineer = request.engineer,
-                reason = reason.as_str(),
-                detail = %detail,
-                "skipping daemon auto-merge request"
-            );
-            if reason == AutoMergeSkipReason::MissingPacket
-                && detail.contains("verification_retry_required")
-            {
-                self.redispatch_verification_retry_required(request, &detail)?;
-            }
-            self.record_orchestrator_action(format!(
-                "merge queue: skipped auto-merge for task #{} ({reason}: {detail})",
-                request.task_id,
-                reason = reason.as_str()
-            ));
+            self.skip_queued_merge(request, reason, &detail)?;
             return Ok(MergeQueueOutcome::Skipped);
         }
+        if let Some(outcome) = self.merge_approval_gate(request)? {
+            return Ok(outcome);
+        }
+        self.merge_gated_request(request)
+    }
 
+    /// Merge a request that already passed the skip checks and the approval
+    /// gate.
+    pub(super) fn merge_gated_request(
+        &mut self,
+        request: &MergeRequest,
+    ) -> Result<MergeQueueOutcome> {
         let _lock =
             MergeLock::acquire(self.project_root()).context("failed to acquire merge lock")?;
         let board_dir = self.board_dir();
-        let board_dir_str = board_dir.to_string_lossy().to_string();
         let manager_name = self.manager_name(&request.engineer);
         let task_title = read_task_title(&board_dir, request.task_id);
         let root_dirty = inspect_root_dirty_state(self.project_root())?;
@@ -331,9 +451,13 @@ impl TeamDaemon {
                         .workflow_policy
                         .test_command
                         .as_deref());
-                    let verification =
-                        run_automatic_verification(self.project_root(), test_command)
-                            .context("post-merge verification on main failed to execute")?;
+                    let verification = run_automatic_verification(
+                        self.project_root(),
+                        test_command,
+                        verification_policy.test_report.as_deref(),
+                        None,
+                    )
+                    .context("post-merge verification on main failed to execute")?;
                     if !verification.passed {
                         self.record_auto_merge_post_verify_result(
                             &request.engineer,
@@ -378,105 +502,7 @@ impl TeamDaemon {
                     );
                 }
 
-                let board_update_ok = move_task_to_done(
-                    self,
-                    &board_dir,
-                    &board_dir_str,
-                    request,
-                    manager_name.as_deref(),
-                );
-                if let Err(error) = crate::team::merge::record_merge_test_timing(
-                    self,
-                    request.task_id,
-                    &request.engineer,
-                    &request.branch,
-                    request.test_duration_ms,
-                ) {
-                    warn!(
-                        engineer = request.engineer,
-                        task_id = request.task_id,
-                        error = %error,
-                        "failed to record merge test timing"
-                    );
-                }
-                self.record_task_auto_merged(
-                    &request.engineer,
-                    request.task_id,
-                    request.confidence,
-                    request.files_changed,
-                    request.lines_changed,
-                    success.mode,
-                );
-                self.check_binary_freshness_after_merge();
-
-                if let Some(ref manager_name) = manager_name {
-                    let msg = format!(
-                        "[{}] Task #{} completed from merge queue.\nTitle: {}\nTests: passed\nMerge: success{}{}{}",
-                        request.engineer,
-                        request.task_id,
-                        task_title,
-                        if success.mode == MergeMode::IsolatedIntegration {
-                            "\nMerge mode: isolated integration checkout"
-                        } else {
-                            ""
-                        },
-                        if let Some(reason) = success.reason.as_deref() {
-                            format!("\nMerge reason: {reason}")
-                        } else {
-                            String::new()
-                        },
-                        if board_update_ok {
-                            ""
-                        } else {
-                            "\nBoard: update failed; decide next board action manually."
-                        }
-                    );
-                    self.queue_message(&request.engineer, manager_name, &msg)?;
-                    self.mark_member_working(manager_name);
-                    let rollup = format!(
-                        "Rollup: Task #{} completed by {} from the merge queue. Tests passed, merged to main.{}",
-                        request.task_id,
-                        request.engineer,
-                        if board_update_ok {
-                            ""
-                        } else {
-                            " Board automation failed; decide manually."
-                        }
-                    );
-                    self.notify_reports_to(manager_name, &rollup)?;
-                }
-
-                // Post-merge disk hygiene: clean build artifacts and prune branch
-                let hygiene_config = &self.config.team_config.automation.disk_hygiene;
-                let hygiene_report = super::health::disk_hygiene::post_merge_cleanup(
-                    self.project_root(),
-                    &request.engineer,
-                    request.task_id,
-                    &request.branch,
-                    hygiene_config,
-                );
-                if hygiene_report.any_action_taken() {
-                    let summary = hygiene_report.summary();
-                    info!(
-                        engineer = request.engineer,
-                        task_id = request.task_id,
-                        summary = %summary,
-                        "post-merge disk hygiene"
-                    );
-                    self.record_orchestrator_action(format!(
-                        "disk-hygiene: post-merge cleanup for {} task #{}: {summary}",
-                        request.engineer, request.task_id
-                    ));
-                }
-
-                self.clear_active_task(&request.engineer);
-                self.record_task_completed(&request.engineer, Some(request.task_id));
-                self.set_member_idle(&request.engineer);
-                info!(
-                    engineer = request.engineer,
-                    task_id = request.task_id,
-                    "merge queue processed request successfully"
-                );
+                self.finish_landed_merge(request, &success, manager_name.as_deref(), &task_title)?;
                 Ok(MergeQueueOutcome::Success)
             }
             MergeOutcome::RebaseConflict(conflict_info) => {
@@ -507,15 +533,9 @@ impl TeamDaemon {
                         request.task_id.to_string(),
                         Some("merge_conflict"),
                     );
-                    self.run_kanban_md_nonfatal(
-                        &[
-                            "edit",
-                            &request.task_id.to_string(),
-                            "--block",
-                            "merge conflicts after 2 retries",
-                            "--dir",
-                            &board_dir_str,
-                        ],
+                    self.board_update_nonfatal(
+                        BoardStore::open(&board_dir)
+                            .block(request.task_id, "merge conflicts after 2 retries"),
                         &format!(
                             "block task #{} after merge conflict retries",
                             request.task_id
@@ -578,7 +598,148 @@ impl TeamDaemon {
     }
 }
 
-fn merge_request_skip_reason(
+impl TeamDaemon {
+    pub(super) fn skip_queued_merge(
+        &mut self,
+        request: &MergeRequest,
+        reason: AutoMergeSkipReason,
+        detail: &str,
+    ) -> Result<()> {
+        warn!(
+            task_id = request.task_id,
+            engineer = request.engineer,
+            reason = reason.as_str(),
+            detail = %detail,
+            "skipping daemon auto-merge request"
+        );
+        if reason == AutoMergeSkipReason::MissingPacket
+            && detail.contains("verification_retry_required")
+        {
+            self.redispatch_verification_retry_required(request, detail)?;
+        }
+        self.record_orchestrator_action(format!(
+            "merge queue: skipped auto-merge for task #{} ({reason}: {detail})",
+            request.task_id,
+            reason = reason.as_str()
+        ));
+        Ok(())
+    }
+
+    /// Board, telemetry, messaging, and disk-hygiene follow-up once a queued
+    /// request has landed on trunk.
+    pub(super) fn finish_landed_merge(
+        &mut self,
+        request: &MergeRequest,
+        success: &MergeSuccess,
+        manager_name: Option<&str>,
+        task_title: &str,
+    ) -> Result<()> {
+        let board_dir = self.board_dir();
+        let board_update_ok = move_task_to_done(self, &board_dir, request, manager_name);
+        if let Err(error) = crate::team::merge::record_merge_test_timing(
+            self,
+            request.task_id,
+            &request.engineer,
+            &request.branch,
+            request.test_duration_ms,
+        ) {
+            warn!(
+                engineer = request.engineer,
+                task_id = request.task_id,
+                error = %error,
+                "failed to record merge test timing"
+            );
+        }
+        self.record_task_auto_merged(
+            &request.engineer,
+            request.task_id,
+            request.confidence,
+            request.files_changed,
+            request.lines_changed,
+            success.mode,
+        );
+        self.check_binary_freshness_after_merge();
+
+        if let Some(manager_name) = manager_name {
+            let msg = format!(
+                "[{}] Task #{} completed from merge queue.\nTitle: {}\nTests: passed\nMerge: success{}{}{}",
+                request.engineer,
+                request.task_id,
+                task_title,
+                if success.mode == MergeMode::IsolatedIntegration {
+                    "\nMerge mode: isolated integration checkout"
+                } else {
+                    ""
+                },
+                if let Some(reason) = success.reason.as_deref() {
+                    format!("\nMerge reason: {reason}")
+                } else {
+                    String::new()
+                },
+                if board_update_ok {
+                    ""
+                } else {
+                    "\nBoard: update failed; decide next board action manually."
+                }
+            );
+            self.queue_message(&request.engineer, manager_name, &msg)?;
+            self.mark_member_working(manager_name);
+            let rollup = format!(
+                "Rollup: Task #{} completed by {} from the merge queue. Tests passed, merged to main.{}",
+                request.task_id,
+                request.engineer,
+                if board_update_ok {
+                    ""
+                } else {
+                    " Board automation failed; decide manually."
+                }
+            );
+            self.notify_reports_to(manager_name, &rollup)?;
+        }
+
+        // Post-merge disk hygiene: clean build artifacts and prune branch.
+        // A gated prune waits for approval and runs from the approval sweep.
+        let mut hygiene_config = self.config.team_config.automation.disk_hygiene.clone();
+        if hygiene_config.enabled
+            && hygiene_config.prune_merged_branches
+            && self.request_branch_delete_approval(request)?
+        {
+            hygiene_config.prune_merged_branches = false;
+        }
+        let hygiene_report = super::health::disk_hygiene::post_merge_cleanup(
+            self.project_root(),
+            &request.engineer,
+            request.task_id,
+            &request.branch,
+            &hygiene_config,
+        );
+        if hygiene_report.any_action_taken() {
+            let summary = hygiene_report.summary();
+            info!(
+                engineer = request.engineer,
+                task_id = request.task_id,
+                summary = %summary,
+                "post-merge disk hygiene"
+            );
+            self.record_orchestrator_action(format!(
+                "disk-hygiene: post-merge cleanup for {} task #{}: {summary}",
+                request.engineer, request.task_id
+            ));
+        }
+
+        self.clear_active_task(&request.engineer);
+        self.record_task_completed(&request.engineer, Some(request.task_id));
+        self.set_member_idle(&request.engineer);
+        info!(
+            engineer = request.engineer,
+            task_id = request.task_id,
+            "merge queue processed request successfully"
+        );
+        Ok(())
+    }
+}
+
+pub(super) fn merge_request_skip_reason(
     project_root: &std::path::Path,
     request: &MergeRequest,
     trunk_branch: &str,
@@ -687,7 +848,7 @@ impl TeamDaemon {
     }
 }
 
-fn dirty_main_review_merge_block_detail(root_dirty: &RootDirtyState) -> Option<String> {
+pub(super) fn dirty_main_review_merge_block_detail(root_dirty: &RootDirtyState) -> Option<String> {
     if root_dirty.source_paths.is_empty() {
         return None;
     }
@@ -724,7 +885,11 @@ fn dirty_main_review_merge_block_detail(root_dirty: &RootDirtyState) -> Option<S
     Some(parts.join(" "))
 }
 
-fn mark_review_merge_blocked(board_dir: &std::path::Path, task_id: u32, block_detail: &str) {
+pub(super) fn mark_review_merge_blocked(
+    board_dir: &std::path::Path,
+    task_id: u32,
+    block_detail: &str,
+) {
     let mut fields = HashMap::new();
     fields.insert("blocked_on".to_string(), block_detail.to_string());
     fields.insert("block_reason".to_string(), block_detail.to_string());
@@ -737,7 +902,7 @@ fn mark_review_merge_blocked(board_dir: &std::path::Path, task_id: u32, block_de
     }
 }
 
-fn snapshot_runtime_dirty_main(
+pub(super) fn snapshot_runtime_dirty_main(
     project_root: &std::path::Path,
     request: &MergeRequest,
     task_title: &str,
@@ -821,6 +986,8 @@ fn fallback_snapshot_task(request: &MergeRequest, task_title: &str) -> crate::ta
         completed: None,
         description: String::new(),
         batty_config: None,
+        scope: None,
+        acceptance: Vec::new(),
         source_path: PathBuf::new(),
     }
 }
@@ -884,6 +1051,18 @@ fn missing_completion_packet_detail(
             metadata.review_blockers.join(", ")
         ));
     }
+    let failed_acceptance: Vec<&str> = metadata
+        .acceptance_results
+        .iter()
+        .filter(|result| !result.passed)
+        .map(|result| result.criterion.as_str())
+        .collect();
+    if !failed_acceptance.is_empty() {
+        missing.push(format!(
+            "acceptance criteria failed: {}",
+            failed_acceptance.join(", ")
+        ));
+    }
     match metadata
         .outcome
         .as_deref()
@@ -985,7 +1164,7 @@ fn commits_ahead_of_trunk(worktree_dir: &std::path::Path, trunk_branch: &str) ->
         })
 }
 
-fn git_head(repo_dir: &std::path::Path) -> Result<String> {
+pub(super) fn git_head(repo_dir: &std::path::Path) -> Result<String> {
     let output = Command::new("git")
         .args(["rev-parse", "HEAD"])
         .current_dir(repo_dir)
@@ -1031,7 +1210,6 @@ fn reset_main_to(repo_dir: &std::path::Path, target: &str) -> Result<()> {
 fn move_task_to_done(
     daemon: &mut TeamDaemon,
     board_dir: &std::path::Path,
-    board_dir_str: &str,
     request: &MergeRequest,
     manager_name: Option<&str>,
 ) -> bool {
@@ -1067,16 +1245,13 @@ fn move_task_to_done(
         return true;
     }
 
-    daemon.run_kanban_md_nonfatal(
-        &[
-            "move",
-            &request.task_id.to_string(),
+    daemon.board_update_nonfatal(
+        BoardStore::open(board_dir).move_task(
+            request.task_id,
             "done",
-            "--claim",
-            &request.engineer,
-            "--dir",
-            board_dir_str,
-        ],
+            Some(&request.engineer),
+            &crate::team::task_cmd::StatusTransitionAttribution::daemon("daemon.merge_queue"),
+        ),
         &format!("move task #{} to done", request.task_id),
         manager_name
             .into_iter()
@@ -1151,6 +1326,33 @@ mod tests {
         assert_eq!(queue.active_task_id(), None);
     }
 
+    #[test]
+    fn take_train_pops_in_order_and_requeue_front_preserves_order() {
+        let mut queue = MergeQueue::default();
+        for task_id in 1..=4 {
+            queue.enqueue(request(task_id));
+        }
+
+        let train = queue.take_train(3);
+        assert_eq!(
+            train
+                .iter()
+                .map(|request| request.task_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        queue.requeue_front(train[1..].to_vec());
+
+        let rest = queue.take_train(10);
+        assert_eq!(
+            rest.iter()
+                .map(|request| request.task_id)
+                .collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(queue.queued_len(), 0);
+    }
+
     #[test]
     fn take_status_update_reports_queue_state_changes() {
         let mut queue = MergeQueue::default();
@@ -1264,6 +1466,7 @@ mod tests {
                 tests_run: Some(true),
                 tests_passed: Some(true),
                 test_results: None,
+                acceptance_results: Vec::new(),
                 artifacts: Vec::new(),
                 outcome: Some("verification_passed".to_string()),
                 review_blockers: Vec::new(),
@@ -1289,6 +1492,7 @@ mod tests {
                 tests_run: Some(true),
                 tests_passed: Some(false),
                 test_results: None,
+                acceptance_results: Vec::new(),
                 artifacts: Vec::new(),
                 outcome: Some("verification_retry_required".to_string()),
                 review_blockers: Vec::new(),
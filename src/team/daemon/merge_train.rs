@@ -0,0 +1,673 @@
+//! Speculative batched merges for the daemon merge queue.
+//!
+//! When `workflow_policy.auto_merge.train_size` is above one, the queue pops
+//! up to that many requests, stacks their branches on trunk in an integration
+//! worktree, and runs the test gate once on the stacked head. A green train
+//! lands in one trunk update. A red train is bisected over its prefixes to
+//! find the first branch that breaks the gate: the branches before it land,
+//! that branch goes back to its engineer as rework, and the branches after it
+//! are requeued so the next train re-tests them without the culprit.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+
+use super::TeamDaemon;
+use super::merge_queue::{
+    MergeQueueEvent, MergeQueueOutcome, MergeRequest, dirty_main_review_merge_block_detail,
+    git_head, merge_request_skip_reason, snapshot_runtime_dirty_main,
+};
+use crate::shim::sandbox::SandboxPolicy;
+use crate::team::config::TrainBisection;
+use crate::team::daemon::verification::{VerificationRunResult, run_automatic_verification};
+use crate::team::merge::{
+    MergeLock, MergeSuccess, advance_trunk_to_commit, gate_test_command, inspect_root_dirty_state,
+    reset_engineer_worktree_to_trunk,
+};
+use crate::team::task_loop::read_task_title;
+use crate::team::task_traces::TaskPhase;
+
+/// What one merge-train pass did, plus the requests to retry next tick.
+#[derive(Debug, Default)]
+pub(super) struct MergeTrainRun {
+    pub events: Vec<MergeQueueEvent>,
+    pub requeue: Vec<MergeRequest>,
+    /// Requests parked on a pending approval, keyed by approval id.
+    pub held: Vec<(u32, MergeRequest)>,
+}
+
+/// A branch merged into the integration worktree, and the stacked head
+/// after its merge.
+#[derive(Debug, Clone)]
+struct StackedBranch {
+    request: MergeRequest,
+    head: String,
+}
+
+/// Outcome of building and gating a train while holding the merge lock.
+#[derive(Debug, Default)]
+struct LockedTrainResult {
+    landed: Vec<MergeRequest>,
+    landed_mode: Option<crate::team::merge::MergeMode>,
+    offender: Option<(MergeRequest, String)>,
+    conflicted: Vec<MergeRequest>,
+    requeue: Vec<MergeRequest>,
+    gate_runs: usize,
+}
+
+/// Index of the first branch whose inclusion turns the stacked train red.
+///
+/// `prefix_passes(k)` runs the gate on trunk plus the first `k` branches.
+/// The empty prefix is assumed green and the full train of `len` branches is
+/// known to be red, so neither is re-tested.
+pub(super) fn first_failing_branch<F>(
+    len: usize,
+    strategy: TrainBisection,
+    mut prefix_passes: F,
+) -> Result<usize>
+where
+    F: FnMut(usize) -> Result<bool>,
+{
+    match strategy {
+        TrainBisection::Binary => {
+            let (mut green, mut red) = (0, len);
+            while red - green > 1 {
+                let mid = green + (red - green) / 2;
+                if prefix_passes(mid)? {
+                    green = mid;
+                } else {
+                    red = mid;
+                }
+            }
+            Ok(red.saturating_sub(1))
+        }
+        TrainBisection::Linear => {
+            for prefix in 1..len {
+                if !prefix_passes(prefix)? {
+                    return Ok(prefix - 1);
+                }
+            }
+            Ok(len.saturating_sub(1))
+        }
+    }
+}
+
+impl TeamDaemon {
+    pub(super) fn execute_merge_train(
+        &mut self,
+        train: Vec<MergeRequest>,
+    ) -> Result<MergeTrainRun> {
+        let mut run = MergeTrainRun::default();
+        let trunk_branch = self.config.team_config.trunk_branch().to_string();
+
+        let mut eligible = Vec::new();
+        for request in train {
+            self.trace_task_phase(request.task_id, TaskPhase::Merging, Some(&request.engineer));
+            match merge_request_skip_reason(self.project_root(), &request, &trunk_branch)? {
+                Some((reason, detail)) => {
+                    self.skip_queued_merge(&request, reason, &detail)?;
+                    run.events
+                        .push(queue_event(&request, MergeQueueOutcome::Skipped));
+                }
+                None => match self.merge_approval_gate(&request)? {
+                    Some(outcome) => {
+                        if let MergeQueueOutcome::AwaitingApproval(approval_id) = outcome {
+                            run.held.push((approval_id, request.clone()));
+                        }
+                        run.events.push(queue_event(&request, outcome));
+                    }
+                    None => eligible.push(request),
+                },
+            }
+        }
+
+        // Trains of one, and trains blocked by dirty source edits on main,
+        // take the serial path so its notices and retries apply unchanged.
+        let root_dirty = inspect_root_dirty_state(self.project_root())?;
+        if eligible.len() < 2 || dirty_main_review_merge_block_detail(&root_dirty).is_some() {
+            for request in eligible {
+                let outcome = self.merge_gated_request(&request)?;
+                run.events.push(queue_event(&request, outcome));
+            }
+            return Ok(run);
+        }
+        if root_dirty.is_runtime_only() {
+            let title = read_task_title(&self.board_dir(), eligible[0].task_id);
+            if let Err(error) =
+                snapshot_runtime_dirty_main(self.project_root(), &eligible[0], &title, &root_dirty)
+            {
+                warn!(error = %error, "failed to snapshot runtime-only dirty main before merge train");
+            }
+        }
+
+        let train_len = eligible.len();
+        let locked = {
+            let _lock =
+                MergeLock::acquire(self.project_root()).context("failed to acquire merge lock")?;
+            self.build_and_gate_train(eligible, &trunk_branch)?
+        };
+
+        let landed_count = locked.landed.len();
+        let mode = locked
+            .landed_mode
+            .unwrap_or(crate::team::merge::MergeMode::IsolatedIntegration);
+        for request in &locked.landed {
+            if let Err(error) = reset_engineer_worktree_to_trunk(
+                self.project_root(),
+                &request.engineer,
+                &trunk_branch,
+            ) {
+                warn!(
+                    engineer = request.engineer,
+                    error = %error,
+                    "worktree reset failed after merge train landed"
+                );
+            }
+            self.record_auto_merge_post_verify_result(
+                &request.engineer,
+                request.task_id,
+                Some(true),
+                "passed",
+                Some("merge train gate passed"),
+            );
+            let manager_name = self.manager_name(&request.engineer);
+            let task_title = read_task_title(&self.board_dir(), request.task_id);
+            let success = MergeSuccess {
+                mode,
+                reason: Some(format!(
+                    "landed in a merge train of {landed_count} branch(es)"
+                )),
+            };
+            self.finish_landed_merge(request, &success, manager_name.as_deref(), &task_title)?;
+            run.events
+                .push(queue_event(request, MergeQueueOutcome::Success));
+        }
+
+        if let Some((request, output)) = &locked.offender {
+            self.send_back_train_offender(request, output, train_len)?;
+            run.events
+                .push(queue_event(request, MergeQueueOutcome::Rework));
+        }
+
+        self.record_orchestrator_action(format!(
+            "merge train: {} stacked, {} landed, {} sent back, {} requeued, {} conflicted, {} gate run(s)",
+            train_len,
+            landed_count,
+            usize::from(locked.offender.is_some()),
+            locked.requeue.len(),
+            locked.conflicted.len(),
+            locked.gate_runs
+        ));
+
+        // Branches that did not merge cleanly onto the stack go through the
+        // serial path, which rebases them and handles conflicts per engineer.
+        for request in locked.conflicted {
+            let outcome = self.merge_gated_request(&request)?;
+            run.events.push(queue_event(&request, outcome));
+        }
+        run.requeue = locked.requeue;
+        Ok(run)
+    }
+
+    fn build_and_gate_train(
+        &mut self,
+        train: Vec<MergeRequest>,
+        trunk_branch: &str,
+    ) -> Result<LockedTrainResult> {
+        let mut result = LockedTrainResult::default();
+        let trunk_before = git_head_of(self.project_root(), trunk_branch)?;
+        let integration = crate::worktree::prepare_integration_worktree(
+            self.project_root(),
+            "merge-train-",
+            &trunk_before,
+        )?;
+        let dir = integration.path();
+
+        let mut stacked: Vec<StackedBranch> = Vec::new();
+        for request in train {
+            let message = format!(
+                "Merge branch '{}' (task #{}) via merge train",
+                request.branch, request.task_id
+            );
+            let merged = git(
+                dir,
+                &[
+                    "merge",
+                    "--no-ff",
+                    "--no-edit",
+                    "-m",
+                    &message,
+                    &request.branch,
+                ],
+            )?;
+            if merged.status.success() {
+                stacked.push(StackedBranch {
+                    head: git_head(dir)?,
+                    request,
+                });
+            } else {
+                let _ = git(dir, &["merge", "--abort"]);
+                info!(
+                    task_id = request.task_id,
+                    branch = %request.branch,
+                    "branch conflicts with the merge train stack"
+                );
+                result.conflicted.push(request);
+            }
+        }
+        if stacked.is_empty() {
+            return Ok(result);
+        }
+
+        // The stack runs every engineer's code; if any of them is sandboxed,
+        // so is the gate.
+        let sandbox = stacked
+            .iter()
+            .find_map(|branch| self.gate_sandbox(&branch.request.engineer, dir));
+        let full = self.run_train_gate(dir, sandbox.as_ref())?;
+        result.gate_runs += 1;
+        let land_count = if full.passed {
+            stacked.len()
+        } else {
+            let strategy = self
+                .config
+                .team_config
+                .workflow_policy
+                .auto_merge
+                .train_bisection;
+            let mut red_outputs = HashMap::from([(stacked.len(), full.output)]);
+            let mut gate_runs = 0;
+            let offender = first_failing_branch(stacked.len(), strategy, |prefix| {
+                checkout_detached(dir, &stacked[prefix - 1].head)?;
+                let gate = self.run_train_gate(dir, sandbox.as_ref())?;
+                gate_runs += 1;
+                if !gate.passed {
+                    red_outputs.insert(prefix, gate.output);
+                }
+                Ok(gate.passed)
+            })?;
+            result.gate_runs += gate_runs;
+            let output = red_outputs.remove(&(offender + 1)).unwrap_or_default();
+            result.offender = Some((stacked[offender].request.clone(), output));
+            result.requeue = stacked[offender + 1..]
+                .iter()
+                .map(|branch| branch.request.clone())
+                .collect();
+            offender
+        };
+
+        if land_count > 0 {
+            let landed_head = &stacked[land_count - 1].head;
+            match advance_trunk_to_commit(
+                self.project_root(),
+                trunk_branch,
+                &trunk_before,
+                landed_head,
+            ) {
+                Ok(mode) => {
+                    result.landed_mode = Some(mode);
+                    result.landed = stacked[..land_count]
+                        .iter()
+                        .map(|branch| branch.request.clone())
+                        .collect();
+                }
+                Err(error) => {
+                    warn!(error = %error, "failed to land merge train; requeueing");
+                    self.record_orchestrator_action(format!(
+                        "merge train: could not advance {trunk_branch}, requeueing {land_count} branch(es): {error}"
+                    ));
+                    let mut requeue: Vec<MergeRequest> = stacked[..land_count]
+                        .iter()
+                        .map(|branch| branch.request.clone())
+                        .collect();
+                    requeue.append(&mut result.requeue);
+                    result.requeue = requeue;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn run_train_gate(
+        &self,
+        dir: &Path,
+        sandbox: Option<&SandboxPolicy>,
+    ) -> Result<VerificationRunResult> {
+        let workflow_policy = &self.config.team_config.workflow_policy;
+        let test_command = workflow_policy
+            .verification
+            .test_command
+            .as_deref()
+            .or(workflow_policy.test_command.as_deref());
+        let (test_command, _skipped) = gate_test_command(
+            self.project_root(),
+            &self.board_dir(),
+            &workflow_policy.flaky_tests,
+            test_command,
+        );
+        run_automatic_verification(
+            dir,
+            test_command.as_deref(),
+            workflow_policy.verification.test_report.as_deref(),
+            sandbox,
+        )
+        .context("merge train gate failed to execute")
+    }
+
+    fn send_back_train_offender(
+        &mut self,
+        request: &MergeRequest,
+        output: &str,
+        train_len: usize,
+    ) -> Result<()> {
+        let board_dir = self.board_dir();
+        self.record_auto_merge_post_verify_result(
+            &request.engineer,
+            request.task_id,
+            Some(false),
+            "failed",
+            Some("merge train gate failed; bisection blamed this branch"),
+        );
+        if let Err(error) = crate::team::task_cmd::transition_task_with_attribution(
+            &board_dir,
+            request.task_id,
+            "in-progress",
+            crate::team::task_cmd::StatusTransitionAttribution::daemon("daemon.merge_train.bisect"),
+        ) {
+            warn!(
+                task_id = request.task_id,
+                error = %error,
+                "failed to move merge-train offender back to in-progress"
+            );
+        }
+
+        let engineer_notice = format!(
+            "Task #{} was sent back from the merge train: stacked on main with the other queued branches, \
+             bisection found that your branch `{}` is the first one that breaks the test gate.\n\
+             Fix it on the same branch and report completion again.\nLatest output:\n{}",
+            request.task_id, request.branch, output
+        );
+        self.queue_message("daemon", &request.engineer, &engineer_notice)?;
+        self.active_tasks
+            .insert(request.engineer.clone(), request.task_id);
+        self.mark_member_working(&request.engineer);
+
+        if let Some(manager_name) = self.manager_name(&request.engineer) {
+            let title = read_task_title(&board_dir, request.task_id);
+            let manager_notice = format!(
+                "[{}] Task #{} broke the merge train gate ({} branch(es) stacked) and was sent back for rework.\nTitle: {}",
+                request.engineer, request.task_id, train_len, title
+            );
+            self.queue_message("daemon", &manager_name, &manager_notice)?;
+            self.mark_member_working(&manager_name);
+        }
+        warn!(
+            engineer = request.engineer,
+            task_id = request.task_id,
+            "merge train bisection sent branch back for rework"
+        );
+        Ok(())
+    }
+}
+
+fn queue_event(request: &MergeRequest, outcome: MergeQueueOutcome) -> MergeQueueEvent {
+    MergeQueueEvent {
+        task_id: request.task_id,
+        engineer: request.engineer.clone(),
+        outcome,
+        queued_at: request.queued_at,
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<std::process::Output> {
+    std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("failed to run git {} in {}", args.join(" "), dir.display()))
+}
+
+fn git_head_of(repo_dir: &Path, branch: &str) -> Result<String> {
+    let output = git(repo_dir, &["rev-parse", &format!("refs/heads/{branch}")])?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to resolve {branch}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn checkout_detached(dir: &Path, commit: &str) -> Result<()> {
+    let output = git(dir, &["checkout", "--detach", "-f", commit])?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to check out merge train prefix {commit}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let _ = git(dir, &["clean", "-fd"]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::board::{WorkflowMetadata, write_workflow_metadata};
+    use crate::team::standup::MemberState;
+    use crate::team::task_loop::{current_worktree_branch, setup_engineer_worktree};
+    use crate::team::test_helpers::make_test_daemon;
+    use crate::team::test_support::{
+        engineer_member, git_ok, git_stdout, init_git_repo, manager_member,
+    };
+    use std::time::Instant;
+
+    #[test]
+    fn first_failing_branch_binary_halves_the_train() {
+        let mut probed = Vec::new();
+        let offender = first_failing_branch(8, TrainBisection::Binary, |prefix| {
+            probed.push(prefix);
+            Ok(prefix < 6)
+        })
+        .unwrap();
+        assert_eq!(offender, 5);
+        assert_eq!(probed, vec![4, 6, 5]);
+    }
+
+    #[test]
+    fn first_failing_branch_linear_walks_prefixes_in_order() {
+        let mut probed = Vec::new();
+        let offender = first_failing_branch(4, TrainBisection::Linear, |prefix| {
+            probed.push(prefix);
+            Ok(prefix < 2)
+        })
+        .unwrap();
+        assert_eq!(offender, 1);
+        assert_eq!(probed, vec![1, 2]);
+
+        // The full train is already known red, so the last branch is blamed
+        // without another gate run.
+        let offender = first_failing_branch(3, TrainBisection::Linear, |_| Ok(true)).unwrap();
+        assert_eq!(offender, 2);
+        let offender = first_failing_branch(1, TrainBisection::Binary, |_| {
+            panic!("a single-branch train needs no bisection")
+        })
+        .unwrap();
+        assert_eq!(offender, 0);
+    }
+
+    fn queue_engineer_branch(
+        repo: &Path,
+        engineer: &str,
+        task_id: u32,
+        file: &str,
+    ) -> MergeRequest {
+        let tasks_dir = repo.join(".batty/team_config/board/tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+        let task_path = tasks_dir.join(format!("{task_id:03}-train-task.md"));
+        std::fs::write(
+            &task_path,
+            format!(
+                "---\nid: {task_id}\ntitle: train-task-{task_id}\nstatus: review\npriority: high\nclaimed_by: {engineer}\nclass: standard\n---\n\nTask description.\n"
+            ),
+        )
+        .unwrap();
+
+        let worktree_dir = repo.join(".batty/worktrees").join(engineer);
+        setup_engineer_worktree(
+            repo,
+            &worktree_dir,
+            engineer,
+            &repo.join(".batty/team_config"),
+        )
+        .unwrap();
+        std::fs::write(worktree_dir.join(file), format!("{engineer}\n")).unwrap();
+        git_ok(&worktree_dir, &["add", file]);
+        git_ok(&worktree_dir, &["commit", "-m", &format!("add {file}")]);
+        let branch = current_worktree_branch(&worktree_dir).unwrap();
+        write_workflow_metadata(
+            &task_path,
+            &WorkflowMetadata {
+                branch: Some(branch.clone()),
+                worktree_path: Some(worktree_dir.to_string_lossy().into_owned()),
+                commit: Some(git_stdout(&worktree_dir, &["rev-parse", "HEAD"])),
+                changed_paths: vec![file.to_string()],
+                tests_run: Some(true),
+                tests_passed: Some(true),
+                test_results: None,
+                acceptance_results: Vec::new(),
+                artifacts: Vec::new(),
+                outcome: Some("verification_passed".to_string()),
+                review_blockers: Vec::new(),
+            },
+        )
+        .unwrap();
+
+        MergeRequest {
+            task_id,
+            engineer: engineer.to_string(),
+            branch,
+            worktree_dir,
+            queued_at: Instant::now(),
+            test_passed: true,
+            should_post_merge_verify: true,
+            test_duration_ms: 1,
+            confidence: 0.95,
+            files_changed: 1,
+            lines_changed: 1,
+        }
+    }
+
+    fn task_status(repo: &Path, task_id: u32) -> String {
+        crate::task::Task::from_file(
+            &repo
+                .join(".batty/team_config/board/tasks")
+                .join(format!("{task_id:03}-train-task.md")),
+        )
+        .unwrap()
+        .status
+    }
+
+    #[test]
+    fn merge_train_lands_green_prefix_sends_back_offender_and_requeues_rest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = init_git_repo(&tmp, "batty-merge-train-test");
+        let requests = [
+            queue_engineer_branch(&repo, "eng-1", 11, "one.txt"),
+            queue_engineer_branch(&repo, "eng-2", 12, "bad.txt"),
+            queue_engineer_branch(&repo, "eng-3", 13, "three.txt"),
+        ];
+
+        let members = vec![
+            manager_member("manager", None),
+            engineer_member("eng-1", Some("manager"), true),
+            engineer_member("eng-2", Some("manager"), true),
+            engineer_member("eng-3", Some("manager"), true),
+        ];
+        let mut daemon = make_test_daemon(&repo, members);
+        let policy = &mut daemon.config.team_config.workflow_policy;
+        policy.test_command = Some("sh -c 'test ! -f bad.txt'".to_string());
+        policy.auto_merge.train_size = 3;
+        for request in requests {
+            daemon.set_active_task_for_test(&request.engineer, request.task_id);
+            daemon.set_member_state_for_test(&request.engineer, MemberState::Working);
+            daemon.enqueue_merge_request(request);
+        }
+
+        daemon.process_merge_queue().unwrap();
+
+        assert_eq!(git_stdout(&repo, &["show", "main:one.txt"]), "eng-1");
+        assert!(
+            git_stdout(&repo, &["ls-tree", "--name-only", "main"])
+                .lines()
+                .all(|path| path != "bad.txt" && path != "three.txt")
+        );
+        assert_eq!(task_status(&repo, 11), "done");
+        assert_eq!(task_status(&repo, 12), "in-progress");
+        assert_eq!(task_status(&repo, 13), "review");
+        assert_eq!(daemon.merge_queue.queued_len(), 1);
+        assert_eq!(daemon.active_task_id("eng-2"), Some(12));
+        let messages =
+            crate::team::inbox::pending_messages(&crate::team::inbox::inboxes_root(&repo), "eng-2")
+                .unwrap();
+        assert!(
+            messages
+                .iter()
+                .any(|message| message.body.contains("sent back from the merge train"))
+        );
+
+        // The requeued branch is re-tested on its own next tick.
+        daemon.process_merge_queue().unwrap();
+        assert_eq!(git_stdout(&repo, &["show", "main:three.txt"]), "eng-3");
+        assert_eq!(task_status(&repo, 13), "done");
+    }
+
+    #[test]
+    fn merge_train_lands_whole_green_train_in_one_gate_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = init_git_repo(&tmp, "batty-merge-train-green-test");
+        let requests = [
+            queue_engineer_branch(&repo, "eng-1", 21, "a.txt"),
+            queue_engineer_branch(&repo, "eng-2", 22, "b.txt"),
+        ];
+        let members = vec![
+            manager_member("manager", None),
+            engineer_member("eng-1", Some("manager"), true),
+            engineer_member("eng-2", Some("manager"), true),
+        ];
+        let mut daemon = make_test_daemon(&repo, members);
+        let gate_log = tmp.path().join("gate.log");
+        daemon.config.team_config.workflow_policy.test_command =
+            Some(format!("sh -c 'echo run >> {}'", gate_log.display()));
+        daemon
+            .config
+            .team_config
+            .workflow_policy
+            .auto_merge
+            .train_size = 4;
+        daemon
+            .config
+            .team_config
+            .workflow_policy
+            .auto_merge
+            .train_bisection = TrainBisection::Linear;
+        for request in requests {
+            daemon.enqueue_merge_request(request);
+        }
+
+        daemon.process_merge_queue().unwrap();
+
+        assert_eq!(git_stdout(&repo, &["show", "main:a.txt"]), "eng-1");
+        assert_eq!(git_stdout(&repo, &["show", "main:b.txt"]), "eng-2");
+        assert_eq!(task_status(&repo, 21), "done");
+        assert_eq!(task_status(&repo, 22), "done");
+        assert_eq!(daemon.merge_queue.queued_len(), 0);
+        assert_eq!(
+            std::fs::read_to_string(&gate_log).unwrap().lines().count(),
+            1,
+            "a green train runs the gate once"
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! Daemon-side execution of operator commands.
+//!
+//! Every bridge hands inbound text to [`TeamDaemon::handle_operator_message`],
+//! which looks up the sender's permission in the user role's channel config,
+//! parses the text with [`crate::team::operator`], runs permitted commands,
+//! and appends each invocation to the operator audit log. `batty op` reaches
+//! [`TeamDaemon::run_operator_text`] through the control API with admin
+//! access, or operator access when it runs inside an agent session.
+
+use anyhow::Result;
+use tracing::warn;
+
+use super::telegram_bridge;
+use super::{RoleType, TeamDaemon};
+use crate::team::operator::{
+    AuditEntry, AuditResult, MESSAGE_PERMISSION, OperatorCommand, Permission, append_audit,
+    help_text, parse_operator_command,
+};
+
+/// Cap on how much of a reply or error the audit log keeps.
+const MAX_AUDIT_DETAIL_CHARS: usize = 200;
+
+/// Where an operator message came from.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct OperatorSource<'a> {
+    pub transport: &'a str,
+    pub user: &'a str,
+    /// Agent session behind a `batty op` call.
+    pub member: Option<&'a str>,
+    pub location: &'a str,
+}
+
+impl TeamDaemon {
+    /// Highest permission `user` holds on `transport` across the user roles
+    /// that use that channel.
+    pub(super) fn operator_permission(&self, transport: &str, user: &str) -> Option<Permission> {
+        self.config
+            .team_config
+            .roles
+            .iter()
+            .filter(|role| {
+                role.role_type == RoleType::User && role.channel.as_deref() == Some(transport)
+            })
+            .filter_map(|role| role.channel_config.as_ref())
+            .filter_map(|config| config.permission_for(user))
+            .max()
+    }
+
+    /// Handle inbound chat text. Returns the reply to send back, or `None`
+    /// when the text is a plain message the caller should route to the team.
+    pub(super) fn handle_operator_message(
+        &mut self,
+        source: OperatorSource<'_>,
+        text: &str,
+    ) -> Option<String> {
+        let permission = self.operator_permission(source.transport, source.user);
+        self.run_operator_text(source, permission, text)
+    }
+
+    /// Parse, authorize, execute and audit one operator message.
+    pub(super) fn run_operator_text(
+        &mut self,
+        source: OperatorSource<'_>,
+        permission: Option<Permission>,
+        text: &str,
+    ) -> Option<String> {
+        let command = match parse_operator_command(text) {
+            Ok(Some(command)) => command,
+            Ok(None) => {
+                if permission >= Some(MESSAGE_PERMISSION) {
+                    return None;
+                }
+                let reply = format!(
+                    "Permission denied: messaging the team needs {MESSAGE_PERMISSION} access."
+                );
+                self.audit_operator(
+                    source,
+                    text,
+                    permission,
+                    Some(MESSAGE_PERMISSION),
+                    AuditResult::Denied,
+                    &reply,
+                );
+                return Some(reply);
+            }
+            Err(error) => {
+                let reply = error.to_string();
+                self.audit_operator(source, text, permission, None, AuditResult::Invalid, &reply);
+                return Some(reply);
+            }
+        };
+
+        let required = command.permission();
+        if permission < Some(required) {
+            let reply = format!(
+                "Permission denied: {} needs {required} access.",
+                command.name()
+            );
+            self.audit_operator(
+                source,
+                text,
+                permission,
+                Some(required),
+                AuditResult::Denied,
+                &reply,
+            );
+            return Some(reply);
+        }
+
+        let result = match command {
+            OperatorCommand::Help => Ok(help_text(text.trim().chars().next().unwrap_or('/'))),
+            command => self.execute_operator_command(command),
+        };
+        let (result, reply) = match result {
+            Ok(reply) => (AuditResult::Ok, reply),
+            Err(error) => (AuditResult::Error, format!("Command failed: {error}")),
+        };
+        self.audit_operator(source, text, permission, Some(required), result, &reply);
+        Some(reply)
+    }
+
+    fn audit_operator(
+        &self,
+        source: OperatorSource<'_>,
+        text: &str,
+        permission: Option<Permission>,
+        required: Option<Permission>,
+        result: AuditResult,
+        detail: &str,
+    ) {
+        let entry = AuditEntry {
+            ts: crate::team::now_unix(),
+            transport: source.transport.to_string(),
+            user: source.user.to_string(),
+            member: source.member.map(str::to_string),
+            location: source.location.to_string(),
+            command: text.trim().to_string(),
+            permission,
+            required,
+            result,
+            detail: detail.chars().take(MAX_AUDIT_DETAIL_CHARS).collect(),
+        };
+        if let Err(error) = append_audit(&self.config.project_root, &entry) {
+            warn!(error = %error, "failed to append operator audit entry");
+        }
+    }
+
+    pub(super) fn execute_operator_command(&mut self, command: OperatorCommand) -> Result<String> {
+        match command {
+            OperatorCommand::Status => Ok(self.render_telegram_status_summary()),
+            OperatorCommand::Board { filter } => telegram_bridge::render_telegram_board_summary(
+                &self.config.project_root,
+                filter.as_deref(),
+            ),
+            OperatorCommand::Logs { member } => {
+                telegram_bridge::render_telegram_logs(&self.config.project_root, &member)
+            }
+            OperatorCommand::Health => telegram_bridge::render_telegram_health_summary(
+                &self.config.project_root,
+                &self.config.members,
+            ),
+            OperatorCommand::Assign { engineer, task } => {
+                self.execute_telegram_assign_command(&engineer, &task)
+            }
+            OperatorCommand::Merge { task_id } => self.execute_telegram_merge_command(task_id),
+            OperatorCommand::Approve { task_id } => {
+                crate::team::task_cmd::cmd_review_structured_with_attribution(
+                    &self.board_dir(),
+                    task_id,
+                    "approve",
+                    None,
+                    "human",
+                    crate::team::task_cmd::StatusTransitionAttribution::bridge(
+                        "bridge.chat.approve",
+                    ),
+                )?;
+                Ok(format!("Task #{task_id} approved."))
+            }
+            OperatorCommand::Approvals => Ok(crate::team::approval::render_pending(
+                &crate::team::approval::load_approvals(&self.config.project_root)?,
+            )),
+            OperatorCommand::Grant { id } => {
+                let approval = crate::team::approval::decide(
+                    &self.config.project_root,
+                    id,
+                    true,
+                    "human",
+                    None,
+                )?;
+                Ok(format!(
+                    "Approved #{id}: {} of {}.",
+                    approval.action.as_str(),
+                    approval.subject
+                ))
+            }
+            OperatorCommand::Deny { id, reason } => {
+                let approval = crate::team::approval::decide(
+                    &self.config.project_root,
+                    id,
+                    false,
+                    "human",
+                    reason.as_deref(),
+                )?;
+                Ok(format!(
+                    "{}.",
+                    crate::team::approval::denial_summary(&approval)
+                ))
+            }
+            OperatorCommand::Reauth { backend } => {
+                let waiting = self.mark_backend_reauthenticated(&backend);
+                Ok(if waiting == 0 {
+                    format!("No member is waiting on {backend} re-authentication.")
+                } else {
+                    format!(
+                        "{backend} marked re-authenticated; {waiting} member(s) switch back when idle."
+                    )
+                })
+            }
+            OperatorCommand::Kick { member } => self.execute_telegram_kick_command(&member),
+            OperatorCommand::Pause => {
+                crate::team::pause_team(&self.config.project_root)?;
+                Ok("Automation paused.".to_string())
+            }
+            OperatorCommand::Resume => {
+                crate::team::resume_team(&self.config.project_root)?;
+                Ok("Automation resumed.".to_string())
+            }
+            OperatorCommand::Goal { text } => {
+                telegram_bridge::write_telegram_goal(&self.config.project_root, &text)?;
+                Ok(format!(
+                    "Goal updated: {}",
+                    telegram_bridge::preview_text(&text, 180)
+                ))
+            }
+            OperatorCommand::Task { title } => {
+                telegram_bridge::create_telegram_task(&self.config.project_root, &title)
+            }
+            OperatorCommand::Block { task_id, reason } => {
+                telegram_bridge::block_telegram_task(&self.config.project_root, task_id, &reason)
+            }
+            OperatorCommand::Stop { confirm } => {
+                if !confirm {
+                    Ok("Send stop confirm to stop the team.".to_string())
+                } else {
+                    let root = self.config.project_root.clone();
+                    std::thread::spawn(move || {
+                        std::thread::sleep(std::time::Duration::from_millis(250));
+                        let _ = crate::team::stop_team(&root);
+                    });
+                    Ok("Stopping team.".to_string())
+                }
+            }
+            OperatorCommand::Start => {
+                let session = format!("batty-{}", self.config.team_config.name);
+                if crate::tmux::session_exists(&session) {
+                    Ok(format!("Team already running: {session}"))
+                } else {
+                    let started = crate::team::start_team(&self.config.project_root, false)?;
+                    Ok(format!("Started team: {started}"))
+                }
+            }
+            OperatorCommand::Help => Ok(help_text('/')),
+            OperatorCommand::Send { role, message } => {
+                crate::team::messaging::send_message_as(
+                    &self.config.project_root,
+                    Some("human"),
+                    &role,
+                    &message,
+                )?;
+                Ok(format!(
+                    "Sent to {role}: {}",
+                    telegram_bridge::preview_text(&message, 120)
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::config::{ChannelConfig, OperatorPermissions, RoleDef};
+    use crate::team::operator::read_audit;
+    use crate::team::test_support::TestDaemonBuilder;
+
+    fn slack_daemon(tmp: &tempfile::TempDir) -> TeamDaemon {
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.config.team_config.roles.push(RoleDef {
+            name: "human".to_string(),
+            role_type: RoleType::User,
+            channel: Some("slack".to_string()),
+            channel_config: Some(ChannelConfig {
+                target: "C0123".to_string(),
+                provider: "slack".to_string(),
+                permissions: OperatorPermissions {
+                    viewer: vec!["U0VIEW".to_string()],
+                    operator: vec!["U0OPS".to_string()],
+                    admin: Vec::new(),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        daemon
+    }
+
+    fn slack(user: &str) -> OperatorSource<'_> {
+        OperatorSource {
+            transport: "slack",
+            user,
+            member: None,
+            location: "C0123",
+        }
+    }
+
+    #[test]
+    fn viewer_can_read_but_not_stop_or_message() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = slack_daemon(&tmp);
+
+        let help = daemon
+            .handle_operator_message(slack("U0VIEW"), "$help")
+            .unwrap();
+        assert!(help.contains("viewer: $status"), "{help}");
+        assert_eq!(
+            daemon
+                .handle_operator_message(slack("U0VIEW"), "$stop confirm")
+                .unwrap(),
+            "Permission denied: stop needs admin access."
+        );
+        assert!(
+            daemon
+                .handle_operator_message(slack("U0VIEW"), "ship it")
+                .unwrap()
+                .starts_with("Permission denied")
+        );
+        assert_eq!(
+            daemon.handle_operator_message(slack("U0OPS"), "ship it"),
+            None
+        );
+
+        let audit = read_audit(tmp.path()).unwrap();
+        assert_eq!(audit.len(), 3);
+        assert_eq!(audit[0].result, AuditResult::Ok);
+        assert_eq!(audit[1].user, "U0VIEW");
+        assert_eq!(audit[1].location, "C0123");
+        assert_eq!(audit[1].command, "$stop confirm");
+        assert_eq!(audit[1].permission, Some(Permission::Viewer));
+        assert_eq!(audit[1].required, Some(Permission::Admin));
+        assert_eq!(audit[1].result, AuditResult::Denied);
+    }
+
+    #[test]
+    fn unknown_users_and_invalid_usage_are_audited() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = slack_daemon(&tmp);
+
+        assert_eq!(daemon.operator_permission("slack", "U0STRANGER"), None);
+        assert_eq!(daemon.operator_permission("telegram", "U0OPS"), None);
+        assert_eq!(
+            daemon
+                .handle_operator_message(slack("U0STRANGER"), "$status")
+                .unwrap(),
+            "Permission denied: status needs viewer access."
+        );
+        assert_eq!(
+            daemon
+                .handle_operator_message(slack("U0OPS"), "$merge nope")
+                .unwrap(),
+            "Usage: $merge <task>"
+        );
+
+        let audit = read_audit(tmp.path()).unwrap();
+        assert_eq!(audit[0].permission, None);
+        assert_eq!(audit[0].result, AuditResult::Denied);
+        assert_eq!(audit[1].result, AuditResult::Invalid);
+    }
+}
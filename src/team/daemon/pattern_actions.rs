@@ -0,0 +1,373 @@
+//! Remediation actions for `patterns.yaml` rules.
+//!
+//! [`crate::team::pattern_rules::PatternRuleEngine`] sees every emitted event
+//! and decides when a rule fires; this module carries out the rule's actions
+//! on the daemon and records each one as a `pattern_action_taken` event and an
+//! orchestrator log line. A failed action is recorded with `success: false`
+//! and does not stop the remaining actions.
+
+use anyhow::{Context, Result, bail};
+
+use super::*;
+use crate::team::board_store::{BoardStore, NewTask};
+use crate::team::pattern_rules::{PatternAction, RuleTrigger};
+use crate::team::task_cmd::StatusTransitionAttribution;
+
+impl TeamDaemon {
+    pub(super) fn maybe_run_pattern_rules(&mut self) -> Result<()> {
+        if self.pattern_rules.is_empty() {
+            return Ok(());
+        }
+        for trigger in self.pattern_rules.take_triggers(now_unix()) {
+            for action in &trigger.actions {
+                let (success, details) = match self.apply_pattern_action(&trigger, action) {
+                    Ok(details) => (true, details),
+                    Err(error) => (false, format!("{error:#}")),
+                };
+                self.record_orchestrator_action(format!(
+                    "pattern rule {}: {} {} ({details})",
+                    trigger.rule,
+                    action.as_str(),
+                    if success { "applied" } else { "failed" },
+                ));
+                self.emit_event(TeamEvent::pattern_action_taken(
+                    &trigger.rule,
+                    action.as_str(),
+                    trigger.role.as_deref(),
+                    trigger.task,
+                    success,
+                    &details,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_pattern_action(
+        &mut self,
+        trigger: &RuleTrigger,
+        action: &PatternAction,
+    ) -> Result<String> {
+        let reason = format!(
+            "pattern rule '{}' matched {} time(s)",
+            trigger.rule, trigger.count
+        );
+        match action {
+            PatternAction::NotifyManager => self.notify_pattern_role(RoleType::Manager, trigger),
+            PatternAction::NotifyArchitect => {
+                self.notify_pattern_role(RoleType::Architect, trigger)
+            }
+            PatternAction::BenchEngineer => {
+                let engineer = self.pattern_member(trigger, RoleType::Engineer)?;
+                crate::team::bench::bench_engineer(self.project_root(), &engineer, Some(&reason))?;
+                Ok(format!("benched {engineer}"))
+            }
+            PatternAction::SplitTask => {
+                let task_id = pattern_task(trigger)?;
+                task_cmd::block_task_with_reason_and_attribution(
+                    &self.board_dir(),
+                    task_id,
+                    &format!("{reason}; split into smaller tasks"),
+                    StatusTransitionAttribution::daemon("daemon.pattern_rule.split_task"),
+                )?;
+                let body = format!(
+                    "Task #{task_id} was blocked because {reason}. Split it into smaller tasks, then close or unblock #{task_id}.{}",
+                    last_error_suffix(trigger)
+                );
+                let notified = self.message_role_type(RoleType::Manager, &body)?;
+                Ok(format!(
+                    "blocked #{task_id} for splitting; notified {notified} manager(s)"
+                ))
+            }
+            PatternAction::PinFlakyTest => {
+                let Some(test) = trigger.test.as_deref() else {
+                    bail!("the matching event did not name a test");
+                };
+                let pinned = crate::team::quarantine::pin_test(
+                    self.project_root(),
+                    test,
+                    &reason,
+                    trigger.task,
+                )?;
+                Ok(if pinned {
+                    format!("pinned {test} as flaky")
+                } else {
+                    format!("{test} was already pinned")
+                })
+            }
+            PatternAction::SwitchModelClass(class) => {
+                let name = self.pattern_member(trigger, RoleType::Engineer)?;
+                let previous = self
+                    .config
+                    .members
+                    .iter()
+                    .find(|member| member.name == name)
+                    .context("member disappeared from the team")?
+                    .model_class
+                    .clone();
+                self.model_class_overrides
+                    .insert(name.clone(), class.clone());
+                self.apply_model_class_overrides();
+                if let Err(error) = self.persist_runtime_state(false) {
+                    warn!(error = %error, "failed to persist model_class override");
+                }
+                Ok(format!(
+                    "{name} model_class {} -> {class}; applies from its next restart",
+                    previous.as_deref().unwrap_or("default")
+                ))
+            }
+            PatternAction::OpenFixupTask => {
+                let subject = match (trigger.task, trigger.role.as_deref()) {
+                    (Some(task_id), _) => format!("task #{task_id}"),
+                    (None, Some(role)) => role.to_string(),
+                    (None, None) => "the team".to_string(),
+                };
+                let id = BoardStore::open(&self.board_dir())
+                    .create(&NewTask {
+                        title: format!("Fix-up: {} ({subject})", trigger.rule),
+                        body: format!(
+                            "Opened automatically because {reason}.{}",
+                            last_error_suffix(trigger)
+                        ),
+                        status: Some("todo".to_string()),
+                        priority: Some("high".to_string()),
+                        tags: vec!["fixup".to_string()],
+                        depends_on: Vec::new(),
+                    })
+                    .map_err(|error| anyhow::anyhow!("{error}"))?;
+                Ok(format!("opened task #{id}"))
+            }
+        }
+    }
+
+    /// Write `model_class_overrides` onto `config.members`, dropping entries
+    /// for members no longer on the team.
+    pub(super) fn apply_model_class_overrides(&mut self) {
+        let members = &mut self.config.members;
+        self.model_class_overrides
+            .retain(|name, _| members.iter().any(|member| &member.name == name));
+        for member in members.iter_mut() {
+            if let Some(class) = self.model_class_overrides.get(&member.name) {
+                member.model_class = Some(class.clone());
+            }
+        }
+    }
+
+    fn notify_pattern_role(
+        &mut self,
+        role_type: RoleType,
+        trigger: &RuleTrigger,
+    ) -> Result<String> {
+        let subject = match (trigger.role.as_deref(), trigger.task) {
+            (Some(role), Some(task_id)) => format!(" for {role} on task #{task_id}"),
+            (Some(role), None) => format!(" for {role}"),
+            (None, Some(task_id)) => format!(" on task #{task_id}"),
+            (None, None) => String::new(),
+        };
+        let body = format!(
+            "Pattern rule '{}' fired{subject}: {} matching event(s) in its window.{}",
+            trigger.rule,
+            trigger.count,
+            last_error_suffix(trigger)
+        );
+        let notified = self.message_role_type(role_type, &body)?;
+        Ok(format!("notified {notified} member(s)"))
+    }
+
+    fn message_role_type(&mut self, role_type: RoleType, body: &str) -> Result<usize> {
+        let recipients: Vec<String> = self
+            .config
+            .members
+            .iter()
+            .filter(|member| member.role_type == role_type)
+            .map(|member| member.name.clone())
+            .collect();
+        for recipient in &recipients {
+            self.queue_daemon_message(recipient, body)?;
+        }
+        Ok(recipients.len())
+    }
+
+    fn pattern_member(&self, trigger: &RuleTrigger, role_type: RoleType) -> Result<String> {
+        let Some(role) = trigger.role.as_deref() else {
+            bail!("the matching event has no member");
+        };
+        if !self
+            .config
+            .members
+            .iter()
+            .any(|member| member.name == role && member.role_type == role_type)
+        {
+            bail!("{role} is not a {role_type:?} on this team");
+        }
+        Ok(role.to_string())
+    }
+}
+
+fn pattern_task(trigger: &RuleTrigger) -> Result<u32> {
+    trigger.task.context("the matching event has no task id")
+}
+
+fn last_error_suffix(trigger: &RuleTrigger) -> String {
+    trigger
+        .last_error
+        .as_deref()
+        .map(|error| format!("\nLatest match: {}", error.lines().next().unwrap_or(error)))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::pattern_rules::{PatternRuleEngine, PatternRulesFile};
+    use crate::team::test_support::{TestDaemonBuilder, engineer_member, manager_member};
+
+    fn engine(yaml: &str) -> PatternRuleEngine {
+        let file: PatternRulesFile = serde_yaml::from_str(yaml).unwrap();
+        PatternRuleEngine::from_rules(file.rules).unwrap()
+    }
+
+    fn action_events(root: &std::path::Path) -> Vec<TeamEvent> {
+        crate::team::events::read_events(&crate::team::team_events_path(root))
+            .unwrap()
+            .into_iter()
+            .filter(|event| event.event == "pattern_action_taken")
+            .collect()
+    }
+
+    #[test]
+    fn fired_rule_runs_actions_and_records_events() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![
+                manager_member("manager", None),
+                engineer_member("eng-1", Some("manager"), false),
+            ])
+            .build();
+        daemon.pattern_rules = engine(
+            r#"
+rules:
+  - name: stuck
+    events: [task_escalated]
+    threshold: 2
+    actions:
+      - notify_manager
+      - switch_model_class: frontier
+      - open_fixup_task
+      - bench_engineer
+"#,
+        );
+        std::fs::create_dir_all(daemon.board_dir().join("tasks")).unwrap();
+
+        daemon.emit_event(TeamEvent::task_escalated("eng-1", "12", Some("tests hang")));
+        daemon.maybe_run_pattern_rules().unwrap();
+        assert!(action_events(tmp.path()).is_empty());
+
+        daemon.emit_event(TeamEvent::task_escalated("eng-1", "12", Some("tests hang")));
+        daemon.maybe_run_pattern_rules().unwrap();
+
+        let events = action_events(tmp.path());
+        let actions: Vec<(&str, bool)> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.action_type.as_deref().unwrap(),
+                    event.success.unwrap(),
+                )
+            })
+            .collect();
+        // No team.yaml on disk, so benching fails and is recorded as such.
+        assert_eq!(
+            actions,
+            vec![
+                ("notify_manager", true),
+                ("switch_model_class", true),
+                ("open_fixup_task", true),
+                ("bench_engineer", false),
+            ]
+        );
+        assert!(
+            events
+                .iter()
+                .all(|event| event.reason.as_deref() == Some("stuck"))
+        );
+        let eng = daemon
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == "eng-1")
+            .unwrap();
+        assert_eq!(eng.model_class.as_deref(), Some("frontier"));
+        let fixups = crate::task::load_tasks_from_dir(&daemon.board_dir().join("tasks")).unwrap();
+        assert_eq!(fixups.len(), 1);
+        assert_eq!(fixups[0].title, "Fix-up: stuck (task #12)");
+        assert_eq!(fixups[0].status, "todo");
+    }
+
+    #[test]
+    fn switched_model_class_survives_reconcile_and_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let members = vec![engineer_member("eng-1", None, false)];
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(members.clone())
+            .build();
+        daemon.pattern_rules = engine(
+            "rules:\n  - name: stuck\n    threshold: 1\n    actions:\n      - switch_model_class: frontier\n",
+        );
+        daemon.emit_event(TeamEvent::task_escalated("eng-1", "12", None));
+        daemon.maybe_run_pattern_rules().unwrap();
+
+        let model_class = |daemon: &TeamDaemon| daemon.config.members[0].model_class.clone();
+        let new_config = daemon.config.team_config.clone();
+        daemon
+            .reconcile_topology(
+                crate::team::config_diff::TopologyDiff {
+                    added: Vec::new(),
+                    removed: Vec::new(),
+                    unchanged: vec!["eng-1".to_string()],
+                },
+                new_config,
+                members.clone(),
+            )
+            .unwrap();
+        assert_eq!(model_class(&daemon).as_deref(), Some("frontier"));
+
+        let mut restarted = TestDaemonBuilder::new(tmp.path()).members(members).build();
+        assert_eq!(model_class(&restarted), None);
+        restarted.restore_member_overrides();
+        assert_eq!(model_class(&restarted).as_deref(), Some("frontier"));
+    }
+
+    #[test]
+    fn split_task_blocks_the_task() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![manager_member("manager", None)])
+            .build();
+        daemon.pattern_rules = engine(
+            "rules:\n  - name: too-big\n    group_by: task\n    threshold: 1\n    actions: [split_task]\n",
+        );
+        let id = BoardStore::open(&daemon.board_dir())
+            .create(&NewTask {
+                title: "Rewrite everything".to_string(),
+                status: Some("in-progress".to_string()),
+                ..NewTask::default()
+            })
+            .unwrap();
+
+        daemon.emit_event(TeamEvent::task_escalated(
+            "eng-1",
+            &id.to_string(),
+            Some("context exhausted"),
+        ));
+        daemon.maybe_run_pattern_rules().unwrap();
+
+        let task = crate::task::load_tasks_from_dir(&daemon.board_dir().join("tasks"))
+            .unwrap()
+            .into_iter()
+            .find(|task| task.id == id)
+            .unwrap();
+        assert_eq!(task.status, "blocked");
+        assert!(action_events(tmp.path())[0].success.unwrap());
+    }
+}
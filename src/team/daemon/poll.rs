@@ -37,7 +37,15 @@ impl TeamDaemon {
         }
 
         self.run_startup_preflight()?;
+        self.start_api_server();
+        self.start_prometheus_server();
+        if !resume && !is_hot_reload {
+            self.start_budget_run();
+        }
 
+        if resume {
+            self.restore_member_overrides();
+        }
         // Spawn agents in all panes
         self.spawn_all_agents(resume)?;
         if resume {
@@ -265,6 +273,9 @@ impl TeamDaemon {
         self.run_recoverable_step("drain_legacy_command_queue", |daemon| {
             daemon.drain_legacy_command_queue()
         });
+        self.run_recoverable_step("process_api_requests", |daemon| {
+            daemon.process_api_requests()
+        });
 
         // -- Critical subsystems: errors logged but no consecutive-failure tracking --
         self.run_loop_step("deliver_inbox_messages", |daemon| {
@@ -306,6 +317,7 @@ impl TeamDaemon {
         self.run_recoverable_step("maybe_auto_unblock_blocked_tasks", |daemon| {
             daemon.maybe_auto_unblock_blocked_tasks()
         });
+        self.run_recoverable_step("process_approvals", |daemon| daemon.process_approvals());
         self.run_recoverable_step("process_merge_queue", |daemon| daemon.process_merge_queue());
         self.run_recoverable_step("maybe_refresh_stale_daemon_binary", |daemon| {
             daemon.maybe_refresh_stale_daemon_binary()
@@ -348,10 +360,28 @@ impl TeamDaemon {
         self.run_optional_subsystem_step("process_telegram_queue", "telegram", |daemon| {
             daemon.process_telegram_queue()
         });
+        self.run_optional_subsystem_step("process_slack_queue", "slack", |daemon| {
+            daemon.process_slack_queue()
+        });
+        self.run_optional_subsystem_step("process_webhooks", "webhooks", |daemon| {
+            daemon.process_webhooks()
+        });
+        self.run_optional_subsystem_step("ingest_hook_events", "telemetry", |daemon| {
+            daemon.ingest_hook_events()
+        });
+        self.run_optional_subsystem_step("export_task_traces", "traces", |daemon| {
+            daemon.export_task_traces()
+        });
+        self.run_optional_subsystem_step("maybe_sync_forge", "forge", |daemon| {
+            daemon.maybe_sync_forge()
+        });
         self.run_recoverable_step("maybe_fire_nudges", |daemon| daemon.maybe_fire_nudges());
         self.run_recoverable_step("check_backend_health", |daemon| {
             daemon.check_backend_health()
         });
+        self.run_recoverable_step("maybe_restore_primary_backends", |daemon| {
+            daemon.maybe_restore_primary_backends()
+        });
         self.run_recoverable_step("maybe_check_binary_freshness", |daemon| {
             daemon.maybe_check_binary_freshness();
             Ok(())
@@ -412,6 +442,12 @@ impl TeamDaemon {
         self.run_recoverable_step("maybe_notify_failure_patterns", |daemon| {
             daemon.maybe_notify_failure_patterns()
         });
+        self.run_recoverable_step("maybe_run_pattern_rules", |daemon| {
+            daemon.maybe_run_pattern_rules()
+        });
+        self.run_recoverable_step("refresh_prometheus_metrics", |daemon| {
+            daemon.refresh_prometheus_metrics()
+        });
         status::update_pane_status_labels(status::PaneStatusLabelUpdateContext {
             project_root: &self.config.project_root,
             members: &self.config.members,
@@ -0,0 +1,153 @@
+//! Daemon side of the Prometheus endpoint: listener startup and the poll-loop
+//! step that samples in-memory state into a fresh gauge snapshot.
+
+use anyhow::Result;
+use tracing::warn;
+
+use super::TeamDaemon;
+use crate::team::inbox;
+use crate::team::inbox_tiered;
+use crate::team::prometheus::{MemberMetrics, MetricsServer, MetricsSnapshot};
+use crate::team::standup::MemberState;
+
+impl TeamDaemon {
+    /// Start the `/metrics` listener when `prometheus.enabled` is set. A bind
+    /// failure is logged and leaves the daemon running without it.
+    pub(super) fn start_prometheus_server(&mut self) {
+        let config = &self.config.team_config.prometheus;
+        if !config.enabled || self.prometheus.is_some() {
+            return;
+        }
+        match MetricsServer::start(config) {
+            Ok(server) => {
+                self.record_orchestrator_action(format!(
+                    "runtime: Prometheus metrics listening on {}",
+                    server.endpoint()
+                ));
+                self.prometheus = Some(server);
+            }
+            Err(error) => {
+                warn!(error = %error, "failed to start Prometheus listener");
+                self.record_orchestrator_action(format!(
+                    "runtime: Prometheus listener failed to start: {error:#}"
+                ));
+            }
+        }
+    }
+
+    /// Replace the exported gauges with the current daemon state.
+    pub(super) fn refresh_prometheus_metrics(&mut self) -> Result<()> {
+        let Some(server) = &self.prometheus else {
+            return Ok(());
+        };
+        server.set_snapshot(self.metrics_snapshot());
+        Ok(())
+    }
+
+    pub(super) fn metrics_snapshot(&self) -> MetricsSnapshot {
+        let inboxes_root = inbox::inboxes_root(&self.config.project_root);
+        let members = self
+            .config
+            .members
+            .iter()
+            .map(|member| {
+                let state = match self.states.get(&member.name) {
+                    Some(MemberState::Working) => "working",
+                    _ => "idle",
+                };
+                let usage = self
+                    .budget_ledger
+                    .members
+                    .get(&member.name)
+                    .map(|entry| entry.usage)
+                    .unwrap_or_default();
+                let mut inbox = vec![(
+                    "flat",
+                    inbox::pending_message_count(&inboxes_root, &member.name).unwrap_or(0),
+                )];
+                if let Ok(tiers) = inbox_tiered::tiered_pending_counts(&inboxes_root, &member.name)
+                {
+                    inbox.extend(tiers.iter().map(|(tier, count)| (tier.subdir(), *count)));
+                }
+                MemberMetrics {
+                    member: member.name.clone(),
+                    role: member.role_name.clone(),
+                    state,
+                    backend_health: self
+                        .backend_health
+                        .get(&member.name)
+                        .copied()
+                        .unwrap_or_default()
+                        .as_str(),
+                    active_task: self.active_tasks.get(&member.name).copied(),
+                    tokens: usage.tokens,
+                    cost_usd: usage.usd,
+                    inbox,
+                }
+            })
+            .collect();
+
+        MetricsSnapshot {
+            members,
+            dispatch_queue: self.dispatch_queue.len(),
+            merge_queued: self.merge_queue.queued_len(),
+            merge_active: usize::from(self.merge_queue.active_task_id().is_some()),
+            merge_awaiting_approval: self.merge_queue.held_len(),
+            review_queue: self.review_first_seen.len(),
+            main_smoke: self
+                .main_smoke_state
+                .as_ref()
+                .map(|state| (state.broken, state.last_run_at)),
+            run_tokens: self.budget_ledger.run.tokens,
+            run_cost_usd: self.budget_ledger.run.usd,
+            poll_cycles: self.poll_cycle_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::team::daemon::merge_queue::MergeRequest;
+    use crate::team::test_helpers::make_test_daemon;
+    use crate::team::test_support::engineer_member;
+
+    #[test]
+    fn snapshot_reflects_in_memory_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        daemon.active_tasks.insert("eng-1".to_string(), 7);
+        daemon.backend_health.insert(
+            "eng-1".to_string(),
+            crate::agent::BackendHealth::AuthRequired,
+        );
+        daemon.review_first_seen.insert(9, 0);
+        daemon.budget_ledger.charge("eng-1", None, 500, 0.5);
+        daemon.enqueue_merge_request(MergeRequest {
+            task_id: 7,
+            engineer: "eng-1".to_string(),
+            branch: "eng-1/7".to_string(),
+            worktree_dir: tmp.path().to_path_buf(),
+            queued_at: std::time::Instant::now(),
+            test_passed: true,
+            should_post_merge_verify: false,
+            test_duration_ms: 0,
+            confidence: 1.0,
+            files_changed: 1,
+            lines_changed: 1,
+        });
+
+        let snapshot = daemon.metrics_snapshot();
+
+        let member = &snapshot.members[0];
+        assert_eq!(member.member, "eng-1");
+        assert_eq!(member.state, "idle");
+        assert_eq!(member.backend_health, "auth_required");
+        assert_eq!(member.active_task, Some(7));
+        assert_eq!(member.tokens, 500);
+        assert_eq!(member.inbox.len(), 5);
+        assert_eq!(snapshot.merge_queued, 1);
+        assert_eq!(snapshot.review_queue, 1);
+        assert_eq!(snapshot.run_tokens, 500);
+        assert!(snapshot.main_smoke.is_none());
+    }
+}
@@ -61,6 +61,8 @@ impl TeamDaemon {
         // Phase 3: Update daemon config to reflect new topology
         self.config.team_config = new_config;
         self.config.members = new_members;
+        self.apply_model_class_overrides();
+        self.apply_backend_failovers();
 
         info!(
             added = diff.added.len(),
@@ -129,24 +131,12 @@ impl TeamDaemon {
                 );
             }
 
-            let sdk_mode = super::launcher::agent_supports_sdk_mode(agent_name)
-                && self.config.team_config.use_sdk_mode;
-            match super::shim_spawn::spawn_shim(
+            match self.spawn_member_shim(
                 &member.name,
                 agent_name,
                 &agent_cmd,
-                &self.config.project_root,
                 &work_dir,
                 Some(&pty_log_path),
-                self.config
-                    .team_config
-                    .workflow_policy
-                    .graceful_shutdown_timeout_secs,
-                self.config
-                    .team_config
-                    .workflow_policy
-                    .auto_commit_on_restart,
-                sdk_mode,
             ) {
                 Ok(handle) => {
                     self.shim_handles.insert(member.name.clone(), handle);
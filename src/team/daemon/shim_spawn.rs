@@ -2,14 +2,22 @@
 //! pass the child socket on fd 3, and return an `AgentHandle`.
 
 use std::os::unix::io::IntoRawFd;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 use anyhow::{Context, Result};
 use tracing::{debug, info, warn};
 
+use super::TeamDaemon;
 use super::agent_handle::AgentHandle;
 use crate::shim::protocol::{self, Channel};
+use crate::shim::sandbox::SandboxPolicy;
+use crate::team::hierarchy::MemberInstance;
+
+/// Stand-in for [`spawn_shim`] that returns the parent side of an in-process
+/// shim for `(member_name, work_dir)` instead of launching `batty shim`.
+/// Installed by `batty replay` so recorded restarts never start real agents.
+pub(in crate::team) type ShimSpawnOverride = Box<dyn FnMut(&str, &Path) -> Result<Channel>>;
 
 /// Kill any orphaned shim processes from previous daemon sessions.
 ///
@@ -113,6 +121,7 @@ pub(in crate::team) fn spawn_shim(
     graceful_shutdown_timeout_secs: u64,
     auto_commit_on_restart: bool,
     sdk_mode: bool,
+    sandbox: Option<&SandboxPolicy>,
 ) -> Result<AgentHandle> {
     let (parent_sock, child_sock) =
         protocol::socketpair().context("failed to create socketpair for shim")?;
@@ -122,12 +131,23 @@ pub(in crate::team) fn spawn_shim(
     let batty = batty_binary();
 
     let mut cmd = Command::new(&batty);
-    cmd.arg("shim")
-        .arg("--id")
-        .arg(member_name)
-        .arg("--agent-type")
-        .arg(agent_type)
-        .arg("--cmd")
+    cmd.arg("shim").arg("--id").arg(member_name);
+    // Config-defined agents run under the data-driven classifier; the shim
+    // receives their screen patterns as a JSON profile.
+    match crate::agent::custom::custom_agent(agent_type) {
+        Some(custom) => {
+            let profile = serde_json::to_string(&custom.classifier_profile())
+                .context("failed to serialize custom classifier profile")?;
+            cmd.arg("--agent-type")
+                .arg("custom")
+                .arg("--classifier-profile")
+                .arg(profile);
+        }
+        None => {
+            cmd.arg("--agent-type").arg(agent_type);
+        }
+    }
+    cmd.arg("--cmd")
         .arg(agent_cmd)
         .arg("--cwd")
         .arg(work_dir.to_string_lossy().as_ref());
@@ -144,6 +164,10 @@ pub(in crate::team) fn spawn_shim(
     if sdk_mode {
         cmd.arg("--sdk-mode");
     }
+    if let Some(policy) = sandbox {
+        let policy = serde_json::to_string(policy).context("failed to serialize sandbox policy")?;
+        cmd.arg("--sandbox").arg(policy);
+    }
 
     // Query the tmux pane size for this member and pass it to the shim
     // so the agent's PTY matches the actual display dimensions.
@@ -253,6 +277,176 @@ pub(in crate::team) fn spawn_shim(
     Ok(handle)
 }
 
+impl TeamDaemon {
+    /// Spawn a shim for a member using the team's shutdown, auto-commit and
+    /// SDK-mode settings, or hand off to the installed
+    /// [`ShimSpawnOverride`] when there is one.
+    pub(in crate::team) fn spawn_member_shim(
+        &mut self,
+        member_name: &str,
+        agent_type: &str,
+        agent_cmd: &str,
+        work_dir: &Path,
+        pty_log_path: Option<&Path>,
+    ) -> Result<AgentHandle> {
+        if let Some(spawn) = self.shim_spawn_override.as_mut() {
+            let channel = spawn(member_name, work_dir)?;
+            return Ok(AgentHandle::new(
+                member_name.to_string(),
+                channel,
+                0,
+                agent_type.to_string(),
+                agent_cmd.to_string(),
+                work_dir.to_path_buf(),
+            ));
+        }
+
+        let team_config = &self.config.team_config;
+        let sdk_mode =
+            super::launcher::agent_supports_sdk_mode(agent_type) && team_config.use_sdk_mode;
+        spawn_shim(
+            member_name,
+            agent_type,
+            agent_cmd,
+            &self.config.project_root,
+            work_dir,
+            pty_log_path,
+            team_config.workflow_policy.graceful_shutdown_timeout_secs,
+            team_config.workflow_policy.auto_commit_on_restart,
+            sdk_mode,
+            self.member_sandbox(member_name).as_ref(),
+        )
+    }
+
+    /// The sandbox policy of `member_name`'s role, resolved against the
+    /// project root, or `None` when the role runs unsandboxed.
+    pub(in crate::team) fn member_sandbox(&self, member_name: &str) -> Option<SandboxPolicy> {
+        let (member, policy) = self.role_sandbox(member_name)?;
+        let project_root = &self.config.project_root;
+        let worktree = member.use_worktrees.then(|| self.worktree_dir(member_name));
+        let (mut writable, read_only) = sandbox_git_paths(project_root, worktree.as_deref());
+        writable.extend(sandbox_shared_paths(project_root, member_name));
+        Some(policy.resolve(project_root, &writable, &read_only))
+    }
+
+    /// The sandbox that `member_name`'s gate commands (tests, acceptance
+    /// criteria, coverage) run in when checked out at `dir`, or `None` when
+    /// the role runs unsandboxed. The commands run the agent's code, so they
+    /// get no more than the agent: `dir` itself, the shared cargo target and
+    /// a cargo home that unsandboxed runs never read. `dir/.git` stays
+    /// read-only.
+    pub(in crate::team) fn gate_sandbox(
+        &self,
+        member_name: &str,
+        dir: &Path,
+    ) -> Option<SandboxPolicy> {
+        let (_, policy) = self.role_sandbox(member_name)?;
+        let project_root = &self.config.project_root;
+        let shared_target = crate::team::task_loop::shared_cargo_target_dir(project_root);
+        let cargo_home = crate::team::task_loop::sandbox_cargo_home_dir(project_root);
+        for dir in [&shared_target, &cargo_home] {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        Some(policy.resolve(
+            project_root,
+            &[shared_target, cargo_home],
+            &[dir.join(".git")],
+        ))
+    }
+
+    fn role_sandbox(&self, member_name: &str) -> Option<(&MemberInstance, &SandboxPolicy)> {
+        let member = self
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == member_name)?;
+        let role = self
+            .config
+            .team_config
+            .roles
+            .iter()
+            .find(|role| role.name == member.role_name)?;
+        Some((member, role.sandbox.as_ref()?))
+    }
+}
+
+/// Paths outside the worktree that every sandboxed agent must be able to
+/// write: the shared cargo target, the inboxes and board for `batty send` /
+/// task updates, and the member's MCP namespace.
+fn sandbox_shared_paths(project_root: &Path, member_name: &str) -> Vec<PathBuf> {
+    let batty_dir = project_root.join(".batty");
+    let shared_target = crate::team::task_loop::shared_cargo_target_dir(project_root);
+    let _ = std::fs::create_dir_all(&shared_target);
+    vec![
+        shared_target,
+        crate::team::inbox::inboxes_root(project_root),
+        crate::team::team_config_dir(project_root).join("board"),
+        batty_dir.join("mcp").join("namespaces").join(member_name),
+        batty_dir.join("mcp").join("shared-locks"),
+    ]
+}
+
+/// Writable and read-only git paths for an agent, in that order.
+///
+/// The daemon runs git unsandboxed in the project root and in worktrees, so
+/// nothing that tells git what to run (`config`, `config.worktree`, hooks)
+/// or where to find it (`commondir`, `gitdir`, a worktree's `.git` file) may
+/// be writable. A registered linked worktree gets the object store, refs and
+/// reflogs, plus its own private git dir: git replaces `index` and `HEAD`
+/// through `*.lock` files next to them, so the directory has to be writable,
+/// and the redirecting files in it are re-mounted read-only. Everything else
+/// under `.git`, including other worktrees, stays read-only. A member
+/// working in the project root gets no git writes at all.
+fn sandbox_git_paths(project_root: &Path, worktree: Option<&Path>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let common_dir = project_root.join(".git");
+    let Some(worktree) = worktree else {
+        return (Vec::new(), vec![common_dir]);
+    };
+    let mut read_only = vec![worktree.join(".git")];
+    let Some(private_dir) = registered_worktree_git_dir(&common_dir, worktree) else {
+        return (Vec::new(), read_only);
+    };
+    let config_worktree = private_dir.join("config.worktree");
+    let hooks_dir = private_dir.join(crate::worktree::SCOPE_FENCE_HOOKS_DIR);
+    // Read-only binds skip missing paths, so create these up front rather
+    // than leave the agent free to create them.
+    if !config_worktree.exists() {
+        let _ = std::fs::write(&config_worktree, "");
+    }
+    let _ = std::fs::create_dir_all(&hooks_dir);
+    read_only.extend([
+        private_dir.join("commondir"),
+        private_dir.join("gitdir"),
+        config_worktree,
+        hooks_dir,
+    ]);
+    let writable = vec![
+        common_dir.join("objects"),
+        common_dir.join("refs"),
+        common_dir.join("logs"),
+        private_dir,
+    ];
+    (writable, read_only)
+}
+
+/// The private git dir git registered for `worktree`, found by matching the
+/// `gitdir` back-links under `.git/worktrees`. The worktree's own `.git`
+/// file is never read: the agent can rewrite it.
+fn registered_worktree_git_dir(common_dir: &Path, worktree: &Path) -> Option<PathBuf> {
+    let expected = worktree.join(".git");
+    let expected = expected.canonicalize().unwrap_or(expected);
+    std::fs::read_dir(common_dir.join("worktrees"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|private_dir| {
+            std::fs::read_to_string(private_dir.join("gitdir")).is_ok_and(|content| {
+                let linked = PathBuf::from(content.trim());
+                linked.canonicalize().unwrap_or(linked) == expected
+            })
+        })
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -286,4 +480,251 @@ mod tests {
                 ))
         );
     }
+
+    #[test]
+    fn member_sandbox_resolves_role_policy_with_shared_paths() {
+        use crate::team::test_support::{TestDaemonBuilder, engineer_member};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![
+                engineer_member("eng-1", None, true),
+                engineer_member("eng-2", None, false),
+            ])
+            .build();
+        assert!(daemon.member_sandbox("eng-1").is_none());
+
+        daemon.config.team_config.roles[0].sandbox = Some(SandboxPolicy {
+            writable: vec!["scratch".into()],
+            ..SandboxPolicy::default()
+        });
+        let policy = daemon.member_sandbox("eng-1").unwrap();
+        let root = tmp.path();
+        assert_eq!(policy.writable[0], root.join("scratch").to_string_lossy());
+        for shared in [
+            root.join(".batty").join("shared-target"),
+            root.join(".batty").join("inboxes"),
+        ] {
+            assert!(
+                policy
+                    .writable
+                    .contains(&shared.to_string_lossy().into_owned())
+            );
+        }
+        // No registered worktree yet: no git writes, and the `.git` file the
+        // worktree will get is read-only.
+        assert!(!policy.writable.iter().any(|path| path.contains(".git")));
+        let worktree_git_file = root.join(".batty/worktrees/eng-1/.git");
+        assert!(
+            policy
+                .read_only
+                .contains(&worktree_git_file.to_string_lossy().into_owned())
+        );
+
+        // A member working in the project root cannot touch `.git` at all.
+        let root_policy = daemon.member_sandbox("eng-2").unwrap();
+        assert!(
+            root_policy
+                .read_only
+                .contains(&root.join(".git").to_string_lossy().into_owned())
+        );
+        assert!(daemon.member_sandbox("nobody").is_none());
+    }
+
+    /// A scratch dir outside `/tmp`, which every sandbox keeps writable and
+    /// would hide what the policy itself leaves open.
+    fn probe_tempdir() -> tempfile::TempDir {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("target");
+        std::fs::create_dir_all(&base).unwrap();
+        tempfile::tempdir_in(base).unwrap()
+    }
+
+    /// A repo with linked worktrees for `members`, and a daemon whose
+    /// engineers run in an unshare sandbox.
+    fn sandboxed_repo(
+        tmp: &tempfile::TempDir,
+        members: &[&str],
+    ) -> (PathBuf, crate::team::daemon::TeamDaemon) {
+        use crate::team::test_support::{
+            TestDaemonBuilder, engineer_member, git_ok, init_git_repo,
+        };
+
+        let repo = init_git_repo(tmp, "sandboxed");
+        for member in members {
+            let worktree = repo.join(".batty").join("worktrees").join(member);
+            git_ok(
+                &repo,
+                &["worktree", "add", "-b", member, worktree.to_str().unwrap()],
+            );
+        }
+        let mut daemon = TestDaemonBuilder::new(&repo)
+            .members(
+                members
+                    .iter()
+                    .map(|member| engineer_member(member, None, true))
+                    .collect(),
+            )
+            .build();
+        daemon.config.team_config.roles[0].sandbox = Some(SandboxPolicy {
+            backend: crate::shim::sandbox::SandboxBackend::Unshare,
+            ..SandboxPolicy::default()
+        });
+        (repo, daemon)
+    }
+
+    #[test]
+    fn member_sandbox_opens_only_the_registered_worktree_git_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (repo, daemon) = sandboxed_repo(&tmp, &["eng-1"]);
+        let worktree = repo.join(".batty/worktrees/eng-1");
+        let common_dir = repo.join(".git");
+        let private_dir = common_dir.join("worktrees").join("eng-1");
+        // The agent points its `.git` file somewhere it controls.
+        let forged = tmp.path().join("forged-gitdir");
+        std::fs::create_dir_all(&forged).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", forged.display()),
+        )
+        .unwrap();
+
+        let policy = daemon.member_sandbox("eng-1").unwrap();
+        let paths = |paths: &[PathBuf]| -> Vec<String> {
+            paths
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect()
+        };
+        for writable in paths(&[
+            common_dir.join("objects"),
+            common_dir.join("refs"),
+            common_dir.join("logs"),
+            private_dir.clone(),
+        ]) {
+            assert!(policy.writable.contains(&writable), "{writable}");
+        }
+        for protected in paths(&[
+            worktree.join(".git"),
+            private_dir.join("commondir"),
+            private_dir.join("gitdir"),
+            private_dir.join("config.worktree"),
+            private_dir.join(crate::worktree::SCOPE_FENCE_HOOKS_DIR),
+        ]) {
+            assert!(policy.read_only.contains(&protected), "{protected}");
+        }
+        assert!(
+            !policy
+                .writable
+                .iter()
+                .chain(&policy.read_only)
+                .any(|path| path.contains("forged-gitdir"))
+        );
+        assert!(
+            !policy
+                .writable
+                .contains(&common_dir.to_string_lossy().into_owned())
+        );
+    }
+
+    #[test]
+    fn sandboxed_agent_can_commit_but_not_rewrite_what_host_git_runs() {
+        let tmp = probe_tempdir();
+        let (repo, daemon) = sandboxed_repo(&tmp, &["eng-1", "eng-2"]);
+        let worktree = repo.join(".batty/worktrees/eng-1");
+        let common_dir = repo.join(".git");
+        let other_dir = common_dir.join("worktrees").join("eng-2");
+        std::fs::create_dir_all(other_dir.join(crate::worktree::SCOPE_FENCE_HOOKS_DIR)).unwrap();
+        std::fs::write(other_dir.join("config.worktree"), "").unwrap();
+        let policy = daemon.member_sandbox("eng-1").unwrap();
+        if policy.check_available().is_err() {
+            return;
+        }
+
+        let (program, argv) = policy
+            .wrap(
+                &worktree,
+                "sh",
+                &[
+                    "-c".into(),
+                    r#"echo started
+echo change > probe.txt && git add probe.txt && git commit -qm probe && echo commit-ok
+for target in .git "$1/config" "$1/hooks/pre-commit" "$1/modules" \
+  "$2/commondir" "$2/gitdir" "$2/config.worktree" "$2/batty-hooks/pre-commit" \
+  "$3/config.worktree" "$3/batty-hooks/pre-commit" "$3/HEAD"; do
+  (echo x >> "$target") 2>/dev/null && echo "writable: $target"
+done
+echo probed"#
+                        .into(),
+                    "sh".into(),
+                    common_dir.to_string_lossy().into_owned(),
+                    common_dir
+                        .join("worktrees/eng-1")
+                        .to_string_lossy()
+                        .into_owned(),
+                    other_dir.to_string_lossy().into_owned(),
+                ],
+            )
+            .unwrap();
+        let output = Command::new(program)
+            .args(argv)
+            .current_dir(&worktree)
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !stdout.contains("started") {
+            eprintln!(
+                "skipping sandbox git probe: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return;
+        }
+        assert!(
+            stdout.contains("commit-ok"),
+            "{stdout}\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(stdout.contains("probed"), "{stdout}");
+        assert!(!stdout.contains("writable:"), "{stdout}");
+        let log = Command::new("git")
+            .args(["log", "-1", "--format=%s", "eng-1"])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "probe");
+    }
+
+    #[test]
+    fn gate_commands_run_inside_the_member_sandbox() {
+        use crate::task::AcceptanceCriterion;
+        use crate::team::daemon::verification::run_acceptance_criteria;
+
+        let tmp = probe_tempdir();
+        let (repo, daemon) = sandboxed_repo(&tmp, &["eng-1"]);
+        let worktree = repo.join(".batty/worktrees/eng-1");
+        let board = repo.join(".batty/team_config/board");
+        std::fs::create_dir_all(&board).unwrap();
+        let sandbox = daemon.gate_sandbox("eng-1", &worktree).unwrap();
+        if sandbox.check_available().is_err() {
+            return;
+        }
+
+        let criteria = vec![
+            AcceptanceCriterion::Command("touch inside".to_string()),
+            AcceptanceCriterion::Command(format!("touch {}/escaped", repo.display())),
+            AcceptanceCriterion::Command(format!("touch {}/task.md", board.display())),
+            AcceptanceCriterion::Command("echo x >> .git".to_string()),
+        ];
+        let results = run_acceptance_criteria(&repo, &worktree, &criteria, Some(&sandbox));
+        if !results[0].passed {
+            eprintln!("skipping sandbox gate probe: {}", results[0].detail);
+            return;
+        }
+        assert!(worktree.join("inside").exists());
+        for result in &results[1..] {
+            assert!(!result.passed, "{}: {}", result.criterion, result.detail);
+        }
+        assert!(!repo.join("escaped").exists());
+        assert!(!board.join("task.md").exists());
+        assert!(daemon.gate_sandbox("nobody", &worktree).is_none());
+    }
 }
@@ -91,24 +91,12 @@ impl TeamDaemon {
                 work_dir = %persisted.work_dir.display(),
                 "restoring shim from saved state"
             );
-            let sdk_mode = super::launcher::agent_supports_sdk_mode(&persisted.agent_type)
-                && self.config.team_config.use_sdk_mode;
-            match super::shim_spawn::spawn_shim(
+            match self.spawn_member_shim(
                 &persisted.id,
                 &persisted.agent_type,
                 &persisted.agent_cmd,
-                &self.config.project_root,
                 &persisted.work_dir,
                 None,
-                self.config
-                    .team_config
-                    .workflow_policy
-                    .graceful_shutdown_timeout_secs,
-                self.config
-                    .team_config
-                    .workflow_policy
-                    .auto_commit_on_restart,
-                sdk_mode,
             ) {
                 Ok(handle) => {
                     self.shim_handles.insert(name.clone(), handle);
@@ -119,9 +119,34 @@ pub(super) struct PersistedDaemonState {
     /// u64-only form (pre-v0.11.60) and the full struct form.
     #[serde(default)]
     pub recently_released_by: HashMap<String, PersistedReleaseRecord>,
+    /// Per-member `model_class` switched by pattern rules.
+    #[serde(default)]
+    pub model_class_overrides: HashMap<String, String>,
+    /// Members running on a fallback backend after a failover.
+    #[serde(default)]
+    pub backend_failovers: HashMap<String, super::backend_failover::BackendFailover>,
+    /// Members recently switched back to their primary, for restore backoff.
+    #[serde(default)]
+    pub restored_primaries: HashMap<String, super::backend_failover::RestoredPrimary>,
+    /// Task traces still open, so a restart resumes them.
+    #[serde(default)]
+    pub task_traces: HashMap<u32, crate::team::task_traces::PersistedTaskTrace>,
 }
 
 impl TeamDaemon {
+    /// Re-apply persisted per-member overrides to `config.members` before
+    /// agents are spawned, so a resumed member launches with them.
+    pub(super) fn restore_member_overrides(&mut self) {
+        let Some(state) = load_daemon_state(&self.config.project_root) else {
+            return;
+        };
+        self.model_class_overrides = state.model_class_overrides;
+        self.apply_model_class_overrides();
+        self.backend_failovers = state.backend_failovers;
+        self.restored_primaries = state.restored_primaries;
+        self.apply_backend_failovers();
+    }
+
     pub(super) fn restore_runtime_state(&mut self) {
         let Some(state) = load_daemon_state(&self.config.project_root) else {
             return;
@@ -224,6 +249,7 @@ impl TeamDaemon {
                 ))
             })
             .collect();
+        self.restore_task_traces(state.task_traces);
     }
 
     pub(super) fn persist_runtime_state(&self, clean_shutdown: bool) -> Result<()> {
@@ -289,6 +315,14 @@ impl TeamDaemon {
                     )
                 })
                 .collect(),
+            model_class_overrides: self.model_class_overrides.clone(),
+            backend_failovers: self.backend_failovers.clone(),
+            restored_primaries: self.restored_primaries.clone(),
+            task_traces: self
+                .task_traces
+                .as_ref()
+                .map(|traces| traces.tracer.snapshot())
+                .unwrap_or_default(),
         };
         save_daemon_state(&self.config.project_root, &state)
     }
@@ -0,0 +1,503 @@
+//! Daemon side of task lifecycle tracing: phase hooks called from dispatch,
+//! completion, and the merge queue, transitions derived from emitted events,
+//! and the poll-loop step that ends traces of tasks the board has closed and
+//! exports finished spans.
+
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use tracing::warn;
+
+use super::TeamDaemon;
+use crate::team::events::TeamEvent;
+use crate::team::task_traces::{Attributes, PersistedTaskTrace, TaskPhase, unix_nanos_now};
+
+impl TeamDaemon {
+    /// Move `task_id` into `phase`. No-op when `traces.enabled` is unset.
+    pub(crate) fn trace_task_phase(
+        &mut self,
+        task_id: u32,
+        phase: TaskPhase,
+        engineer: Option<&str>,
+    ) {
+        if self.task_traces.is_none() {
+            return;
+        }
+        let attributes = engineer
+            .map(|engineer| self.member_trace_attributes(engineer))
+            .unwrap_or_default();
+        if let Some(traces) = &mut self.task_traces {
+            traces
+                .tracer
+                .enter_phase(task_id, phase, engineer, attributes, unix_nanos_now());
+        }
+    }
+
+    /// Close the current phase of `task_id` without entering another, e.g.
+    /// when a merge fails and the next step is not yet known.
+    pub(crate) fn end_task_trace_phase(&mut self, task_id: u32, outcome: &str, error: bool) {
+        if let Some(traces) = &mut self.task_traces {
+            traces
+                .tracer
+                .end_phase(task_id, outcome, error, unix_nanos_now());
+        }
+    }
+
+    /// End the trace for `task_id`, stamping the root span with the
+    /// engineer's backend and model and the task's token usage.
+    pub(crate) fn finish_task_trace(&mut self, task_id: u32, outcome: &str, error: bool) {
+        let Some(traces) = &self.task_traces else {
+            return;
+        };
+        let mut attributes = traces
+            .tracer
+            .engineer(task_id)
+            .map(|engineer| self.member_trace_attributes(engineer))
+            .unwrap_or_default();
+        if let Some(entry) = self.budget_ledger.tasks.get(&task_id) {
+            attributes.push(("batty.tokens", entry.usage.tokens.into()));
+            attributes.push(("batty.cost_usd", entry.usage.usd.into()));
+        }
+        if let Some(traces) = &mut self.task_traces {
+            traces
+                .tracer
+                .finish(task_id, outcome, error, attributes, unix_nanos_now());
+        }
+    }
+
+    /// Promote the member's assigned task to `working` once the agent
+    /// actually starts on it.
+    pub(super) fn trace_member_working(&mut self, member_name: &str) {
+        let Some(task_id) = self.active_task_id(member_name) else {
+            return;
+        };
+        let Some(traces) = &self.task_traces else {
+            return;
+        };
+        if matches!(
+            traces.tracer.phase(task_id),
+            Some(TaskPhase::Queued | TaskPhase::Assigned)
+        ) {
+            self.trace_task_phase(task_id, TaskPhase::Working, Some(member_name));
+        }
+    }
+
+    /// Derive lifecycle transitions from events that already mark them.
+    pub(super) fn observe_task_trace_event(&mut self, event: &TeamEvent) {
+        if self.task_traces.is_none() {
+            return;
+        }
+        let Some(task_id) = event
+            .task
+            .as_deref()
+            .and_then(|task| task.trim_start_matches('#').parse::<u32>().ok())
+        else {
+            return;
+        };
+        let role = event.role.as_deref();
+        match event.event.as_str() {
+            "verification_phase_changed" => match event.step.as_deref() {
+                Some("verifying") => self.trace_task_phase(task_id, TaskPhase::Verifying, role),
+                Some("executing" | "fixing") => {
+                    self.trace_task_phase(task_id, TaskPhase::Working, role)
+                }
+                Some("failed") => self.end_task_trace_phase(task_id, "verification_failed", true),
+                _ => {}
+            },
+            "task_reworked" => self.trace_task_phase(task_id, TaskPhase::Working, role),
+            "agent_restarted" | "agent_handoff" => {
+                let name = if event.event == "agent_restarted" {
+                    "restart"
+                } else {
+                    "handoff"
+                };
+                let mut attributes: Attributes = Vec::new();
+                if let Some(role) = role {
+                    attributes.push(("batty.engineer", role.into()));
+                }
+                if let Some(reason) = &event.reason {
+                    attributes.push(("batty.reason", reason.as_str().into()));
+                }
+                if let Some(count) = event.restart_count {
+                    attributes.push(("batty.restart_count", count.into()));
+                }
+                let error = event.success == Some(false);
+                if let Some(traces) = &mut self.task_traces {
+                    traces
+                        .tracer
+                        .record_child(task_id, name, attributes, error, unix_nanos_now());
+                }
+            }
+            "task_merge_failed" => self.end_task_trace_phase(task_id, "merge_failed", true),
+            "task_auto_merged" | "task_manual_merged" => {
+                self.finish_task_trace(task_id, "merged", false)
+            }
+            "board_task_archived" => self.finish_task_trace(task_id, "archived", false),
+            _ => {}
+        }
+    }
+
+    /// Resume traces saved with the daemon state.
+    pub(super) fn restore_task_traces(&mut self, saved: HashMap<u32, PersistedTaskTrace>) {
+        if self.task_traces.is_none() || saved.is_empty() {
+            return;
+        }
+        let attributes: HashMap<String, Attributes> = saved
+            .values()
+            .filter_map(|trace| trace.engineer.as_deref())
+            .map(|engineer| (engineer.to_string(), self.member_trace_attributes(engineer)))
+            .collect();
+        if let Some(traces) = &mut self.task_traces {
+            traces.tracer.restore(saved, |engineer| {
+                attributes.get(engineer).cloned().unwrap_or_default()
+            });
+        }
+    }
+
+    /// End open traces whose task is done, archived, gone from the board, or
+    /// claimed by someone other than the traced engineer. Merges and
+    /// archives seen as events end their traces sooner; this catches every
+    /// other way a task leaves an engineer's hands.
+    fn finish_closed_task_traces(&mut self) -> Result<()> {
+        let open = match &self.task_traces {
+            Some(traces) => traces
+                .tracer
+                .open_tasks()
+                .into_iter()
+                .map(|(task_id, engineer)| (task_id, engineer.map(str::to_string)))
+                .collect::<Vec<_>>(),
+            None => return Ok(()),
+        };
+        if open.is_empty() {
+            return Ok(());
+        }
+        let tasks = crate::task::load_tasks_from_dir(&self.board_dir().join("tasks"))?;
+        for (task_id, engineer) in open {
+            let outcome = match tasks.iter().find(|task| task.id == task_id) {
+                None => "cancelled",
+                Some(task) if matches!(task.status.as_str(), "done" | "archived") => {
+                    task.status.as_str()
+                }
+                Some(task)
+                    if task
+                        .claimed_by
+                        .as_deref()
+                        .zip(engineer.as_deref())
+                        .is_some_and(|(claimed, traced)| claimed != traced) =>
+                {
+                    "reassigned"
+                }
+                Some(_) => continue,
+            };
+            self.finish_task_trace(task_id, outcome, false);
+        }
+        Ok(())
+    }
+
+    /// Hand spans that ended since the last tick to the export worker and
+    /// surface any export failure it reported.
+    pub(super) fn export_task_traces(&mut self) -> Result<()> {
+        if self.task_traces.is_none() {
+            return Ok(());
+        }
+        if let Err(error) = self.finish_closed_task_traces() {
+            warn!(error = %error, "failed to check the board for closed task traces");
+        }
+        let Some(traces) = &mut self.task_traces else {
+            return Ok(());
+        };
+        if let Some(failure) = traces.export_finished()?.pop() {
+            bail!("{failure}");
+        }
+        Ok(())
+    }
+
+    fn member_trace_attributes(&self, member_name: &str) -> Attributes {
+        let member = self
+            .config
+            .members
+            .iter()
+            .find(|member| member.name == member_name);
+        let mut attributes: Attributes = Vec::new();
+        if let Some(agent) = member.and_then(|member| member.agent.as_deref()) {
+            attributes.push(("batty.backend", agent.into()));
+        }
+        if let Some(model) = self
+            .budget_ledger
+            .member_model(member_name)
+            .or_else(|| member.and_then(|member| member.model.as_deref()))
+        {
+            attributes.push(("batty.model", model.into()));
+        }
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::team::config::TracesConfig;
+    use crate::team::events::TeamEvent;
+    use crate::team::task_traces::{TaskPhase, TaskTraces};
+    use crate::team::test_helpers::make_test_daemon;
+    use crate::team::test_support::{engineer_member, write_owned_task_file};
+
+    /// Wait for the export worker to write the trace file.
+    fn read_trace_file(project_root: &std::path::Path) -> String {
+        let path = project_root.join(".batty/traces.jsonl");
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        loop {
+            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            if content.ends_with('\n') || std::time::Instant::now() > deadline {
+                return content;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn events_and_hooks_build_a_complete_task_trace() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut member = engineer_member("eng-1", None, false);
+        member.agent = Some("codex".to_string());
+        member.model = Some("gpt-5".to_string());
+        let mut daemon = make_test_daemon(tmp.path(), vec![member]);
+        daemon.task_traces = Some(TaskTraces::new(
+            &TracesConfig {
+                enabled: true,
+                ..TracesConfig::default()
+            },
+            tmp.path(),
+            "demo",
+        ));
+        daemon
+            .budget_ledger
+            .charge("eng-1", Some((42, "high")), 900, 0.3);
+
+        daemon.trace_task_phase(42, TaskPhase::Queued, Some("eng-1"));
+        daemon.trace_task_phase(42, TaskPhase::Assigned, Some("eng-1"));
+        daemon.active_tasks.insert("eng-1".to_string(), 42);
+        daemon.trace_member_working("eng-1");
+        daemon.emit_event(TeamEvent::agent_restarted("eng-1", "42", "stall", 1));
+        daemon.emit_event(TeamEvent::verification_phase_changed(
+            &crate::team::events::VerificationPhaseChangeInfo {
+                engineer: "eng-1",
+                task: "42",
+                from_phase: "executing",
+                to_phase: "verifying",
+                iteration: 1,
+            },
+        ));
+        daemon.trace_task_phase(42, TaskPhase::QueuedForMerge, Some("eng-1"));
+        daemon.trace_task_phase(42, TaskPhase::Merging, Some("eng-1"));
+        daemon.emit_event(TeamEvent::task_auto_merged("eng-1", "42", 0.9, 2, 30));
+        daemon.export_task_traces().unwrap();
+
+        let content = read_trace_file(tmp.path());
+        let request: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        let spans = request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        let names: Vec<_> = spans
+            .iter()
+            .map(|span| span["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "queued",
+                "assigned",
+                "restart",
+                "working",
+                "verifying",
+                "queued_for_merge",
+                "merging",
+                "task #42",
+            ]
+        );
+        let root = &spans[7];
+        let attribute = |key: &str| {
+            root["attributes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|attribute| attribute["key"] == key)
+                .map(|attribute| attribute["value"].clone())
+        };
+        assert_eq!(attribute("batty.backend").unwrap()["stringValue"], "codex");
+        assert_eq!(attribute("batty.model").unwrap()["stringValue"], "gpt-5");
+        assert_eq!(attribute("batty.tokens").unwrap()["intValue"], "900");
+        assert_eq!(attribute("batty.outcome").unwrap()["stringValue"], "merged");
+        assert!(
+            daemon
+                .task_traces
+                .as_ref()
+                .unwrap()
+                .tracer
+                .phase(42)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn tracing_hooks_are_noops_when_disabled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        daemon.trace_task_phase(1, TaskPhase::Queued, Some("eng-1"));
+        daemon.finish_task_trace(1, "merged", false);
+        daemon.export_task_traces().unwrap();
+        assert!(!tmp.path().join(".batty/traces.jsonl").exists());
+    }
+
+    #[test]
+    fn closed_board_tasks_end_their_traces() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        daemon.task_traces = Some(TaskTraces::new(
+            &TracesConfig {
+                enabled: true,
+                ..TracesConfig::default()
+            },
+            tmp.path(),
+            "demo",
+        ));
+        write_owned_task_file(tmp.path(), 1, "finished", "done", "eng-1");
+        write_owned_task_file(tmp.path(), 2, "moved", "in-progress", "eng-2");
+        write_owned_task_file(tmp.path(), 3, "running", "in-progress", "eng-1");
+        for task_id in 1..=4 {
+            daemon.trace_task_phase(task_id, TaskPhase::Working, Some("eng-1"));
+        }
+
+        daemon.export_task_traces().unwrap();
+
+        let content = read_trace_file(tmp.path());
+        let request: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        let mut outcomes: Vec<(String, String)> =
+            request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|span| span.get("parentSpanId").is_none())
+                .map(|span| {
+                    let outcome = span["attributes"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .find(|attribute| attribute["key"] == "batty.outcome")
+                        .unwrap();
+                    (
+                        span["name"].as_str().unwrap().to_string(),
+                        outcome["value"]["stringValue"]
+                            .as_str()
+                            .unwrap()
+                            .to_string(),
+                    )
+                })
+                .collect();
+        outcomes.sort();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("task #1".to_string(), "done".to_string()),
+                ("task #2".to_string(), "reassigned".to_string()),
+                ("task #4".to_string(), "cancelled".to_string()),
+            ]
+        );
+        let tracer = &daemon.task_traces.as_ref().unwrap().tracer;
+        assert_eq!(tracer.open_tasks(), vec![(3, Some("eng-1"))]);
+    }
+
+    #[test]
+    fn open_traces_survive_a_daemon_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let traces = || {
+            Some(TaskTraces::new(
+                &TracesConfig {
+                    enabled: true,
+                    ..TracesConfig::default()
+                },
+                tmp.path(),
+                "demo",
+            ))
+        };
+        let mut daemon = make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        daemon.task_traces = traces();
+        daemon.trace_task_phase(8, TaskPhase::Working, Some("eng-1"));
+        daemon.persist_runtime_state(false).unwrap();
+
+        let mut restarted =
+            make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        restarted.task_traces = traces();
+        restarted.restore_runtime_state();
+        let tracer = &restarted.task_traces.as_ref().unwrap().tracer;
+        assert_eq!(tracer.phase(8), Some(TaskPhase::Working));
+        assert_eq!(tracer.engineer(8), Some("eng-1"));
+    }
+
+    #[test]
+    fn unreachable_collector_does_not_stall_the_tick() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        // Connections land in the backlog but are never accepted or answered.
+        let blackhole = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        daemon.task_traces = Some(TaskTraces::new(
+            &TracesConfig {
+                enabled: true,
+                exporter: crate::team::config::TraceExporterKind::Otlp,
+                endpoint: format!("http://{}/v1/traces", blackhole.local_addr().unwrap()),
+                timeout_secs: 30,
+                ..TracesConfig::default()
+            },
+            tmp.path(),
+            "demo",
+        ));
+
+        for task_id in 1..=3 {
+            daemon.trace_task_phase(task_id, TaskPhase::Queued, Some("eng-1"));
+            daemon.trace_task_phase(task_id, TaskPhase::Assigned, Some("eng-1"));
+            let started = std::time::Instant::now();
+            daemon.export_task_traces().unwrap();
+            assert!(
+                started.elapsed() < std::time::Duration::from_secs(2),
+                "tick took {:?}",
+                started.elapsed()
+            );
+        }
+    }
+
+    #[test]
+    fn export_failures_from_the_worker_fail_the_step() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = make_test_daemon(tmp.path(), vec![engineer_member("eng-1", None, false)]);
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/v1/traces", closed.local_addr().unwrap());
+        drop(closed);
+        daemon.task_traces = Some(TaskTraces::new(
+            &TracesConfig {
+                enabled: true,
+                exporter: crate::team::config::TraceExporterKind::Otlp,
+                endpoint,
+                timeout_secs: 1,
+                ..TracesConfig::default()
+            },
+            tmp.path(),
+            "demo",
+        ));
+        daemon.trace_task_phase(1, TaskPhase::Queued, Some("eng-1"));
+        daemon.trace_task_phase(1, TaskPhase::Assigned, Some("eng-1"));
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        let error = loop {
+            if let Err(error) = daemon.export_task_traces() {
+                break error;
+            }
+            assert!(std::time::Instant::now() < deadline, "no export failure");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        };
+        assert!(
+            error
+                .to_string()
+                .contains("1 span(s) held for the next export"),
+            "{error}"
+        );
+    }
+}
@@ -33,6 +33,7 @@ impl BinaryFreshnessTrigger {
 impl TeamDaemon {
     pub(crate) fn emit_event(&mut self, event: TeamEvent) {
         self.failure_tracker.push(&event);
+        self.pattern_rules.observe(&event);
 
         // Dual-write to SQLite telemetry database (best-effort).
         if self.optional_subsystem_ready("telemetry") {
@@ -54,11 +55,42 @@ impl TeamDaemon {
             debug!("telemetry subsystem disabled by error budget; skipping SQLite write");
         }
 
+        if let Some(api) = &self.api_server {
+            api.broadcast(&event);
+        }
+        if let Some(metrics) = &self.prometheus {
+            metrics.observe_event(&event);
+        }
+        self.observe_task_trace_event(&event);
+
         if let Err(error) = self.event_sink.emit(event) {
             warn!(error = %error, "failed to write daemon event; continuing");
         }
     }
 
+    /// Copy events that worktree git hooks appended to `events.jsonl` into
+    /// `telemetry.db`. Hooks never open the database themselves, so they do
+    /// not contend with this process's SQLite writer.
+    pub(super) fn ingest_hook_events(&mut self) -> Result<()> {
+        let Some(conn) = &self.telemetry_db else {
+            return Ok(());
+        };
+        let events = crate::team::events::read_events(self.event_sink.path())?;
+        if events.len() < self.hook_event_cursor {
+            self.hook_event_cursor = 0;
+        }
+        for (index, event) in events.iter().enumerate().skip(self.hook_event_cursor) {
+            if crate::team::scope_fence::is_hook_event(event)
+                && let Err(error) = crate::team::telemetry_db::insert_event(conn, event)
+            {
+                self.hook_event_cursor = index;
+                return Err(error);
+            }
+        }
+        self.hook_event_cursor = events.len();
+        Ok(())
+    }
+
     pub(super) fn record_daemon_started(&mut self) {
         self.emit_event(TeamEvent::daemon_started());
     }
@@ -1139,6 +1171,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -1312,6 +1351,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
@@ -1377,6 +1423,8 @@ mod tests {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: Default::default(),
             context_pressure_tracker: Default::default(),
             last_health_check: Instant::now(),
@@ -1397,6 +1445,21 @@ mod tests {
             merge_queue: crate::team::daemon::MergeQueue::default(),
             last_binary_freshness_check: Instant::now(),
             last_tiered_inbox_sweep: Instant::now(),
+            budget_ledger: Default::default(),
+            tool_activity: Default::default(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules: Default::default(),
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
+            slack_bot: None,
+            slack_event_cursor: 0,
+            webhook_event_cursor: 0,
+            webhook_worker: None,
+            hook_event_cursor: 0,
+            api_server: None,
+            prometheus: None,
+            task_traces: None,
         };
 
         let sent = Arc::new(Mutex::new(Vec::new()));
@@ -1715,6 +1778,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -1795,6 +1865,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
@@ -1860,6 +1937,8 @@ mod tests {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: Default::default(),
             context_pressure_tracker: Default::default(),
             last_health_check: Instant::now(),
@@ -1880,6 +1959,21 @@ mod tests {
             merge_queue: crate::team::daemon::MergeQueue::default(),
             last_binary_freshness_check: Instant::now(),
             last_tiered_inbox_sweep: Instant::now(),
+            budget_ledger: Default::default(),
+            tool_activity: Default::default(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules: Default::default(),
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
+            slack_bot: None,
+            slack_event_cursor: 0,
+            webhook_event_cursor: 0,
+            webhook_worker: None,
+            hook_event_cursor: 0,
+            api_server: None,
+            prometheus: None,
+            task_traces: None,
         };
 
         daemon.poll_watchers().unwrap();
@@ -1895,4 +1989,39 @@ mod tests {
 
         let _ = crate::tmux::kill_session(&session);
     }
+
+    #[test]
+    fn hook_written_scope_violations_are_ingested_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = crate::team::test_support::TestDaemonBuilder::new(tmp.path()).build();
+        daemon.telemetry_db = Some(crate::team::telemetry_db::open_in_memory().unwrap());
+        daemon.emit_event(TeamEvent::scope_fence_violation(
+            "eng-1",
+            42,
+            "stage=edit commit_rejected=false files=Cargo.toml",
+        ));
+        // What the pre-commit hook appends from its own process.
+        EventSink::new(&crate::team::team_events_path(tmp.path()))
+            .unwrap()
+            .emit(TeamEvent::scope_fence_violation(
+                "eng-1",
+                42,
+                "stage=pre-commit commit_rejected=true files=Cargo.toml",
+            ))
+            .unwrap();
+
+        daemon.ingest_hook_events().unwrap();
+        daemon.ingest_hook_events().unwrap();
+
+        let rows = crate::team::telemetry_db::query_recent_events(
+            daemon.telemetry_db.as_ref().unwrap(),
+            10,
+        )
+        .unwrap();
+        let violations = rows
+            .iter()
+            .filter(|row| row.event_type == "scope_fence_violation")
+            .count();
+        assert_eq!(violations, 2);
+    }
 }
@@ -225,6 +225,13 @@ fn daemon_registers_per_role_nudge_intervals_from_prompt_sections() {
             workflow_policy: WorkflowPolicy::default(),
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -390,6 +397,10 @@ fn daemon_state_round_trip_preserves_runtime_fields() {
                 count: 2,
             },
         )]),
+        model_class_overrides: HashMap::from([("eng-1".to_string(), "frontier".to_string())]),
+        backend_failovers: HashMap::new(),
+        restored_primaries: HashMap::new(),
+        task_traces: HashMap::new(),
     };
 
     save_daemon_state(tmp.path(), &state).unwrap();
@@ -731,6 +742,13 @@ fn test_auto_dispatch_filters_idle_engineers_only() {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1172,6 +1190,13 @@ fn daemon_lifecycle_happy_path_exercises_decomposed_modules() {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -3661,6 +3686,13 @@ fn resume_decision_logged_to_orchestrator() {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -3938,6 +3970,13 @@ fn spawn_all_agents_resume_reports_missing_sessions_across_primary_roles() {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -4096,6 +4135,13 @@ fn prepare_member_launch_injects_handoff_for_active_task() {
             workflow_policy: WorkflowPolicy::default(),
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -4181,6 +4227,8 @@ fn prepare_member_launch_injects_handoff_for_active_task() {
             completed: None,
             description: "Resume task body.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: tmp.path().join("task-42.md"),
         },
         "context_pressure",
@@ -4266,6 +4314,8 @@ fn clear_active_task_removes_restart_context_guard() {
         completed: None,
         description: "Resume task body.".to_string(),
         batty_config: None,
+        scope: None,
+        acceptance: Vec::new(),
         source_path: tmp.path().join("task-42.md"),
     };
     crate::team::context_management::stage_restart_context(
@@ -4387,6 +4437,13 @@ fn clean_room_test_daemon(project_root: &Path) -> TeamDaemon {
         },
         cost: Default::default(),
         grafana: Default::default(),
+        api: Default::default(),
+        budget: Default::default(),
+        agents: Default::default(),
+        forge: Default::default(),
+        webhooks: Default::default(),
+        traces: Default::default(),
+        prometheus: Default::default(),
         use_shim: false,
         use_sdk_mode: false,
         auto_respawn_on_crash: false,
@@ -5919,6 +5976,10 @@ fn save_daemon_state_returns_error_on_readonly_dir() {
             planning_cycle_consecutive_empty: 0,
             recently_rescued_tasks: HashMap::new(),
             recently_released_by: HashMap::new(),
+            model_class_overrides: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
+            task_traces: HashMap::new(),
         };
 
         let result = save_daemon_state(tmp.path(), &state);
@@ -0,0 +1,104 @@
+//! Virtual-clock support for `batty replay`: shift every timer the daemon
+//! compares against `Instant::now()` into the past so the next tick behaves
+//! as if that much wall-clock time had elapsed.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use super::TeamDaemon;
+
+impl TeamDaemon {
+    /// Advance the daemon's view of time by `by`. Intervals, cooldowns,
+    /// idle/working durations and shim activity all age by the same amount.
+    pub(in crate::team) fn advance_clock(&mut self, by: Duration) {
+        if by.is_zero() {
+            return;
+        }
+
+        for instant in [
+            &mut self.last_board_rotation,
+            &mut self.last_auto_archive,
+            &mut self.last_auto_dispatch,
+            &mut self.last_main_smoke_check,
+            &mut self.last_health_check,
+            &mut self.last_shared_target_cleanup,
+            &mut self.last_disk_hygiene_check,
+            &mut self.last_shim_health_check,
+            &mut self.last_binary_freshness_check,
+            &mut self.last_tiered_inbox_sweep,
+        ] {
+            backdate(instant, by);
+        }
+        for instant in [
+            &mut self.pipeline_starvation_last_fired,
+            &mut self.planning_cycle_last_fired,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            backdate(instant, by);
+        }
+
+        backdate_all(&mut self.idle_started_at, by);
+        backdate_all(&mut self.intervention_cooldowns, by);
+        backdate_all(&mut self.last_standup, by);
+        backdate_all(&mut self.recent_dispatches, by);
+        backdate_all(&mut self.recent_escalations, by);
+        backdate_all(&mut self.manual_assign_cooldowns, by);
+        backdate_all(&mut self.last_uncommitted_warn, by);
+        backdate_all(&mut self.working_since, by);
+        for schedule in self.nudges.values_mut() {
+            if let Some(instant) = schedule.idle_since.as_mut() {
+                backdate(instant, by);
+            }
+        }
+
+        for handle in self.shim_handles.values_mut() {
+            backdate(&mut handle.state_changed_at, by);
+            if let Some(instant) = handle.last_pong_at.as_mut() {
+                backdate(instant, by);
+            }
+            if let Some(instant) = handle.last_activity_at.as_mut() {
+                backdate(instant, by);
+            }
+        }
+    }
+}
+
+/// `Instant` cannot go before the platform's epoch (boot time on Linux); a
+/// timer that would underflow is already older than any threshold, so it is
+/// left where it is.
+fn backdate(instant: &mut Instant, by: Duration) {
+    if let Some(earlier) = instant.checked_sub(by) {
+        *instant = earlier;
+    }
+}
+
+fn backdate_all<K: Eq + Hash>(timers: &mut HashMap<K, Instant>, by: Duration) {
+    for instant in timers.values_mut() {
+        backdate(instant, by);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::test_support::TestDaemonBuilder;
+
+    #[test]
+    fn advance_clock_ages_intervals_and_per_member_timers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.last_auto_dispatch = Instant::now();
+        daemon
+            .idle_started_at
+            .insert("eng-1".to_string(), Instant::now());
+
+        daemon.advance_clock(Duration::from_secs(120));
+
+        assert!(daemon.last_auto_dispatch.elapsed() >= Duration::from_secs(120));
+        assert!(daemon.idle_started_at["eng-1"].elapsed() >= Duration::from_secs(120));
+        assert!(daemon.planning_cycle_last_fired.is_none());
+    }
+}
@@ -0,0 +1,473 @@
+//! Live tool activity from SDK-mode shims: record structured tool events in
+//! telemetry, flag failing command loops and edits outside the task's
+//! `scope:`, and tell long-running commands apart from hung agents for stall
+//! detection.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::time::Instant;
+
+use tracing::{info, warn};
+
+use super::TeamDaemon;
+use crate::shim::protocol::Event;
+use crate::team::append_shim_event_log;
+use crate::team::events::TeamEvent;
+use crate::team::telemetry_db::{self, ToolEventRow};
+
+/// Consecutive identical failing commands that count as a loop.
+const COMMAND_LOOP_THRESHOLD: usize = 3;
+/// A running command holds off the working-state timeout for this many
+/// multiples of the timeout before it is treated as hung.
+const LONG_COMMAND_TIMEOUT_MULTIPLIER: u64 = 3;
+
+#[derive(Debug)]
+struct InFlightTool {
+    command: Option<String>,
+    started_at: Instant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CommandOutcome {
+    command: String,
+    failed: bool,
+}
+
+/// Per-member view of the tool calls a shim has reported.
+#[derive(Debug, Default)]
+pub(crate) struct MemberToolActivity {
+    in_flight: HashMap<String, InFlightTool>,
+    last_event_at: Option<Instant>,
+    recent_commands: VecDeque<CommandOutcome>,
+    loop_reported: bool,
+    /// `(task, path)` edits already reported as scope-fence violations.
+    scope_violations_reported: HashSet<(u32, String)>,
+}
+
+impl MemberToolActivity {
+    /// Age of the oldest shell command still running.
+    fn command_running_secs(&self) -> Option<u64> {
+        self.in_flight
+            .values()
+            .filter(|tool| tool.command.is_some())
+            .map(|tool| tool.started_at.elapsed().as_secs())
+            .max()
+    }
+
+    /// Record a finished command; returns the repeat count when this
+    /// completes a new failing-command loop.
+    fn push_command(&mut self, outcome: CommandOutcome) -> Option<usize> {
+        if self
+            .recent_commands
+            .back()
+            .is_some_and(|last| last.command != outcome.command || !outcome.failed)
+        {
+            self.loop_reported = false;
+        }
+        self.recent_commands.push_back(outcome);
+        while self.recent_commands.len() > COMMAND_LOOP_THRESHOLD {
+            self.recent_commands.pop_front();
+        }
+
+        let looping = self.recent_commands.len() == COMMAND_LOOP_THRESHOLD
+            && self.recent_commands.iter().all(|outcome| outcome.failed)
+            && self
+                .recent_commands
+                .iter()
+                .all(|outcome| outcome.command == self.recent_commands[0].command);
+        if looping && !self.loop_reported {
+            self.loop_reported = true;
+            return Some(COMMAND_LOOP_THRESHOLD);
+        }
+        None
+    }
+}
+
+/// Whether a command line runs a test suite.
+pub(super) fn is_test_command(argv: &[String]) -> bool {
+    let words: Vec<&str> = argv.iter().map(String::as_str).collect();
+    words
+        .iter()
+        .any(|word| matches!(*word, "pytest" | "vitest" | "jest"))
+        || words.windows(2).any(|pair| {
+            matches!(
+                pair,
+                ["cargo", "test" | "nextest"]
+                    | ["go", "test"]
+                    | ["npm" | "pnpm" | "yarn" | "bun", "test"]
+            )
+        })
+}
+
+impl TeamDaemon {
+    /// Handle a `ToolStarted`/`ToolFinished`/`FileEdited`/`CommandRun` event.
+    pub(super) fn handle_tool_event(&mut self, member_name: &str, event: &Event) {
+        let task_id = self.active_task_id(member_name);
+        let activity = self
+            .tool_activity
+            .entry(member_name.to_string())
+            .or_default();
+        activity.last_event_at = Some(Instant::now());
+
+        let row = |kind: &str| ToolEventRow {
+            timestamp: chrono::Utc::now().timestamp(),
+            role: member_name.to_string(),
+            task_id: task_id.map(|id| id.to_string()),
+            kind: kind.to_string(),
+            tool: None,
+            path: None,
+            command: None,
+            exit_code: None,
+            duration_ms: None,
+            is_error: false,
+        };
+
+        let mut edited_path = None;
+        let record = match event {
+            Event::ToolStarted {
+                tool_use_id,
+                command,
+                ..
+            } => {
+                activity.in_flight.insert(
+                    tool_use_id.clone(),
+                    InFlightTool {
+                        command: command.clone(),
+                        started_at: Instant::now(),
+                    },
+                );
+                None
+            }
+            Event::ToolFinished {
+                tool_use_id,
+                tool,
+                duration_ms,
+                is_error,
+            } => {
+                activity.in_flight.remove(tool_use_id);
+                Some(ToolEventRow {
+                    tool: Some(tool.clone()),
+                    duration_ms: Some(*duration_ms as i64),
+                    is_error: *is_error,
+                    ..row("tool_finished")
+                })
+            }
+            Event::FileEdited { path, tool } => {
+                let _ = append_shim_event_log(
+                    &self.config.project_root,
+                    member_name,
+                    &format!("<- file edited ({tool}): {path}"),
+                );
+                edited_path = Some(path.as_str());
+                Some(ToolEventRow {
+                    tool: Some(tool.clone()),
+                    path: Some(path.clone()),
+                    ..row("file_edited")
+                })
+            }
+            Event::CommandRun {
+                argv,
+                exit_code,
+                duration_ms,
+            } => {
+                let command = argv.join(" ");
+                let failed = *exit_code != Some(0);
+                let _ = append_shim_event_log(
+                    &self.config.project_root,
+                    member_name,
+                    &format!(
+                        "<- command{} exit={exit_code:?} {duration_ms}ms: {command}",
+                        if is_test_command(argv) { " [test]" } else { "" }
+                    ),
+                );
+                if let Some(repeats) = activity.push_command(CommandOutcome {
+                    command: command.clone(),
+                    failed,
+                }) {
+                    warn!(
+                        member = member_name,
+                        command = command.as_str(),
+                        repeats,
+                        "member is looping on a failing command"
+                    );
+                    self.emit_event(TeamEvent::command_loop_detected(
+                        member_name,
+                        task_id,
+                        &command,
+                        repeats as u32,
+                    ));
+                    self.record_orchestrator_action(format!(
+                        "health: {member_name} ran `{command}` {repeats} times in a row, failing each time"
+                    ));
+                }
+                Some(ToolEventRow {
+                    command: Some(command),
+                    exit_code: *exit_code,
+                    duration_ms: Some(*duration_ms as i64),
+                    is_error: failed,
+                    ..row("command_run")
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(record) = record
+            && let Some(conn) = &self.telemetry_db
+            && let Err(error) = telemetry_db::record_tool_event(conn, &record)
+        {
+            warn!(member = member_name, error = %error, "failed to record tool event");
+        }
+        if let (Some(task_id), Some(path)) = (task_id, edited_path) {
+            self.check_edit_scope(member_name, task_id, path);
+        }
+    }
+
+    /// Report an edit outside the active task's `scope:` while it happens,
+    /// once per task and path. The pre-commit hook still rejects the commit.
+    fn check_edit_scope(&mut self, member_name: &str, task_id: u32, path: &str) {
+        let Some(relative) = self.member_relative_path(member_name, path) else {
+            return;
+        };
+        let task = match crate::task::load_task_by_id(&self.board_dir().join("tasks"), task_id) {
+            Ok(task) => task,
+            Err(error) => {
+                warn!(member = member_name, task_id, error = %error, "failed to load task for scope check");
+                return;
+            }
+        };
+        let Ok(Some(scope)) = crate::team::scope_fence::effective_task_scope(&task) else {
+            return;
+        };
+        let Some(violation) = scope.check(&relative) else {
+            return;
+        };
+        let newly_reported = self
+            .tool_activity
+            .entry(member_name.to_string())
+            .or_default()
+            .scope_violations_reported
+            .insert((task_id, relative.clone()));
+        if !newly_reported {
+            return;
+        }
+
+        let reason = crate::team::scope_fence::violation_reason(&violation);
+        warn!(
+            member = member_name,
+            task_id,
+            path = relative.as_str(),
+            reason = reason.as_str(),
+            "member edited a file outside its task scope"
+        );
+        self.emit_event(TeamEvent::scope_fence_violation(
+            member_name,
+            task_id,
+            &format!("stage=edit commit_rejected=false files={relative}"),
+        ));
+        self.record_orchestrator_action(format!(
+            "scope: {member_name} edited {relative} outside the scope of task #{task_id} ({reason})"
+        ));
+    }
+
+    /// `path` relative to the member's checkout, or `None` for files outside
+    /// it. Relative paths are taken as already relative to the checkout.
+    fn member_relative_path(&self, member_name: &str, path: &str) -> Option<String> {
+        let path = Path::new(path);
+        if path.is_relative() {
+            return Some(path.to_string_lossy().trim_start_matches("./").to_string());
+        }
+        let worktree = self.worktree_dir(member_name);
+        [worktree.as_path(), self.config.project_root.as_path()]
+            .into_iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .map(|relative| relative.to_string_lossy().into_owned())
+    }
+
+    /// Forget in-flight tools once a turn ends or the agent goes away.
+    pub(super) fn clear_tool_activity(&mut self, member_name: &str) {
+        if let Some(activity) = self.tool_activity.get_mut(member_name) {
+            activity.in_flight.clear();
+        }
+    }
+
+    /// Whether reported tool activity shows a Working member is busy rather
+    /// than stuck: a shell command is still inside its grace period, or the
+    /// shim reported a tool event within the last `timeout_secs`.
+    pub(super) fn tool_activity_shows_progress(
+        &self,
+        member_name: &str,
+        timeout_secs: u64,
+    ) -> bool {
+        let Some(activity) = self.tool_activity.get(member_name) else {
+            return false;
+        };
+        if let Some(secs) = activity.command_running_secs()
+            && secs < timeout_secs.saturating_mul(LONG_COMMAND_TIMEOUT_MULTIPLIER)
+        {
+            info!(
+                member = member_name,
+                command_secs = secs,
+                "working-state timeout deferred: command still running"
+            );
+            return true;
+        }
+        activity
+            .last_event_at
+            .is_some_and(|at| at.elapsed().as_secs() < timeout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::test_support::{TestDaemonBuilder, engineer_member};
+
+    fn command_run(argv: &[&str], exit_code: i32) -> Event {
+        Event::CommandRun {
+            argv: argv.iter().map(|word| word.to_string()).collect(),
+            exit_code: Some(exit_code),
+            duration_ms: 1_000,
+        }
+    }
+
+    #[test]
+    fn is_test_command_recognizes_common_runners() {
+        let argv = |words: &[&str]| words.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert!(is_test_command(&argv(&["cargo", "test", "-p", "batty"])));
+        assert!(is_test_command(&argv(&["go", "test", "./..."])));
+        assert!(is_test_command(&argv(&["npx", "vitest", "run"])));
+        assert!(is_test_command(&argv(&["python", "-m", "pytest"])));
+        assert!(!is_test_command(&argv(&["python", "-m", "http.server"])));
+        assert!(!is_test_command(&argv(&["cargo", "build"])));
+    }
+
+    #[test]
+    fn tool_events_are_recorded_in_telemetry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![engineer_member("eng-1", Some("manager"), false)])
+            .build();
+        daemon.telemetry_db = Some(telemetry_db::open_in_memory().unwrap());
+
+        daemon.handle_tool_event(
+            "eng-1",
+            &Event::FileEdited {
+                path: "src/lib.rs".into(),
+                tool: "Edit".into(),
+            },
+        );
+        daemon.handle_tool_event("eng-1", &command_run(&["cargo", "test"], 0));
+
+        let rows = telemetry_db::query_tool_events(daemon.telemetry_db.as_ref().unwrap(), None, 10)
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].kind, "command_run");
+        assert_eq!(rows[0].command.as_deref(), Some("cargo test"));
+        assert_eq!(rows[0].exit_code, Some(0));
+        assert!(!rows[0].is_error);
+        assert_eq!(rows[1].kind, "file_edited");
+        assert_eq!(rows[1].path.as_deref(), Some("src/lib.rs"));
+    }
+
+    #[test]
+    fn out_of_scope_edits_are_reported_once_per_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![engineer_member("eng-1", Some("manager"), true)])
+            .build();
+        let tasks_dir = daemon.board_dir().join("tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+        std::fs::write(
+            tasks_dir.join("042-fenced.md"),
+            "---\nid: 42\ntitle: fenced\nstatus: in-progress\npriority: high\nclaimed_by: eng-1\nscope:\n  allow:\n    - src/team/**\n---\n\nTask body.\n",
+        )
+        .unwrap();
+        daemon.active_tasks.insert("eng-1".to_string(), 42);
+        let worktree = daemon.worktree_dir("eng-1");
+        let edit = |path: String| Event::FileEdited {
+            path,
+            tool: "Edit".into(),
+        };
+
+        daemon.handle_tool_event("eng-1", &edit("src/team/slack.rs".into()));
+        daemon.handle_tool_event(
+            "eng-1",
+            &edit(worktree.join("Cargo.toml").display().to_string()),
+        );
+        daemon.handle_tool_event("eng-1", &edit("Cargo.toml".into()));
+        daemon.handle_tool_event("eng-1", &edit("/etc/hosts".into()));
+
+        let events =
+            crate::team::events::read_events(&crate::team::team_events_path(tmp.path())).unwrap();
+        let violations: Vec<_> = events
+            .iter()
+            .filter(|event| event.event == "scope_fence_violation")
+            .collect();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].role.as_deref(), Some("eng-1"));
+        assert_eq!(violations[0].task.as_deref(), Some("42"));
+        assert_eq!(
+            violations[0].reason.as_deref(),
+            Some("stage=edit commit_rejected=false files=Cargo.toml")
+        );
+    }
+
+    #[test]
+    fn repeated_failing_command_is_reported_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![engineer_member("eng-1", Some("manager"), false)])
+            .build();
+
+        for _ in 0..5 {
+            daemon.handle_tool_event("eng-1", &command_run(&["cargo", "test"], 101));
+        }
+        let events =
+            crate::team::events::read_events(&crate::team::team_events_path(tmp.path())).unwrap();
+        let loops: Vec<_> = events
+            .iter()
+            .filter(|event| event.event == "command_loop_detected")
+            .collect();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].details.as_deref(), Some("cargo test"));
+
+        // A passing run breaks the streak; a new streak is reported again.
+        daemon.handle_tool_event("eng-1", &command_run(&["cargo", "test"], 0));
+        for _ in 0..3 {
+            daemon.handle_tool_event("eng-1", &command_run(&["cargo", "test"], 101));
+        }
+        let events =
+            crate::team::events::read_events(&crate::team::team_events_path(tmp.path())).unwrap();
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| event.event == "command_loop_detected")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn running_command_shows_progress_until_cleared() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path())
+            .members(vec![engineer_member("eng-1", Some("manager"), false)])
+            .build();
+        assert!(!daemon.tool_activity_shows_progress("eng-1", 60));
+
+        daemon.handle_tool_event(
+            "eng-1",
+            &Event::ToolStarted {
+                tool_use_id: "t1".into(),
+                tool: "Bash".into(),
+                command: Some("cargo build --release".into()),
+            },
+        );
+        assert!(daemon.tool_activity_shows_progress("eng-1", 60));
+
+        daemon.clear_tool_activity("eng-1");
+        let activity = daemon.tool_activity.get_mut("eng-1").unwrap();
+        activity.last_event_at = None;
+        assert!(!daemon.tool_activity_shows_progress("eng-1", 60));
+    }
+}
@@ -4,13 +4,15 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::task::{Task, load_tasks_from_dir};
+use crate::shim::sandbox::SandboxPolicy;
+use crate::task::{AcceptanceCriterion, Task, TaskScope, load_tasks_from_dir, scope_entry_matches};
 use crate::team::config::TeamConfig;
 use crate::team::hierarchy::resolve_hierarchy;
 use crate::team::inbox;
 use crate::team::review::task_reference_mismatch_blockers;
 use crate::team::task_loop::run_tests_in_worktree;
 use crate::team::test_results::TestResults;
+use crate::team::verification::AcceptanceResult;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct VerificationRunResult {
@@ -56,6 +58,8 @@ pub(crate) struct ScopeFenceCheck {
 pub(crate) fn run_automatic_verification(
     worktree_dir: &Path,
     test_command: Option<&str>,
+    test_report: Option<&str>,
+    sandbox: Option<&SandboxPolicy>,
 ) -> Result<VerificationRunResult> {
     if let Some(conflict_failure) = active_claim_conflict_failure(worktree_dir)? {
         return Ok(conflict_failure);
@@ -67,7 +71,7 @@ pub(crate) fn run_automatic_verification(
         return Ok(scope_failure);
     }
 
-    let test_run = run_tests_in_worktree(worktree_dir, test_command)?;
+    let test_run = run_tests_in_worktree(worktree_dir, test_command, test_report, sandbox)?;
     let (failures, _failure_paths) =
         parse_test_output(&test_run.output, &test_run.results, test_run.passed);
     let file_paths =
@@ -82,6 +86,253 @@ pub(crate) fn run_automatic_verification(
     })
 }
 
+/// Run a task's acceptance criteria in `worktree_dir`, with commands inside
+/// `sandbox` when the member is sandboxed. A criterion that cannot be
+/// evaluated (bad regex, unparseable output) counts as failed, with the
+/// reason as its detail.
+pub(crate) fn run_acceptance_criteria(
+    project_root: &Path,
+    worktree_dir: &Path,
+    criteria: &[AcceptanceCriterion],
+    sandbox: Option<&SandboxPolicy>,
+) -> Vec<AcceptanceResult> {
+    criteria
+        .iter()
+        .map(|criterion| {
+            let (passed, detail) =
+                match check_acceptance_criterion(project_root, worktree_dir, criterion, sandbox) {
+                    Ok(outcome) => outcome,
+                    Err(error) => (false, format!("{error:#}")),
+                };
+            AcceptanceResult {
+                criterion: criterion.label(),
+                passed,
+                detail,
+            }
+        })
+        .collect()
+}
+
+fn check_acceptance_criterion(
+    project_root: &Path,
+    worktree_dir: &Path,
+    criterion: &AcceptanceCriterion,
+    sandbox: Option<&SandboxPolicy>,
+) -> Result<(bool, String)> {
+    match criterion {
+        AcceptanceCriterion::Command(command) => {
+            let run = run_tests_in_worktree(worktree_dir, Some(command), None, sandbox)?;
+            if run.passed {
+                Ok((true, "exited 0".to_string()))
+            } else {
+                Ok((false, format!("failed: {}", last_output_line(&run.output))))
+            }
+        }
+        AcceptanceCriterion::FileExists(path) => {
+            if worktree_dir.join(path).exists() {
+                Ok((true, "present".to_string()))
+            } else {
+                Ok((false, "missing".to_string()))
+            }
+        }
+        AcceptanceCriterion::Grep {
+            path,
+            pattern,
+            absent,
+        } => {
+            let regex = regex::Regex::new(pattern)
+                .with_context(|| format!("invalid grep pattern `{pattern}`"))?;
+            let files = worktree_files_matching(worktree_dir, path)?;
+            if files.is_empty() {
+                return Ok((*absent, format!("no files match `{path}`")));
+            }
+            let matched: Vec<&String> = files
+                .iter()
+                .filter(|file| {
+                    std::fs::read_to_string(worktree_dir.join(file))
+                        .is_ok_and(|content| regex.is_match(&content))
+                })
+                .collect();
+            let listed = matched
+                .iter()
+                .take(5)
+                .map(|file| file.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            if matched.is_empty() {
+                Ok((*absent, format!("no match in {} file(s)", files.len())))
+            } else {
+                Ok((!*absent, format!("matched in {listed}")))
+            }
+        }
+        AcceptanceCriterion::Coverage {
+            command,
+            min_delta,
+            baseline,
+        } => {
+            let current = command_number(worktree_dir, command, COVERAGE_PATTERN, sandbox)?;
+            let baseline = match baseline {
+                Some(baseline) => *baseline,
+                None => coverage_baseline(project_root, command, sandbox)?,
+            };
+            let delta = current - baseline;
+            Ok((
+                delta >= *min_delta,
+                format!("coverage {current:.2}% vs baseline {baseline:.2}% (delta {delta:+.2})"),
+            ))
+        }
+        AcceptanceCriterion::Benchmark {
+            command,
+            pattern,
+            min,
+            max,
+        } => {
+            let value = command_number(
+                worktree_dir,
+                command,
+                pattern.as_deref().unwrap_or(NUMBER_PATTERN),
+                sandbox,
+            )?;
+            let passed = min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max);
+            Ok((passed, format!("measured {value}")))
+        }
+    }
+}
+
+const COVERAGE_PATTERN: &str = r"(\d+(?:\.\d+)?)\s*%";
+const NUMBER_PATTERN: &str = r"(-?\d+(?:\.\d+)?)";
+
+/// Run `command` and read a number from its output: the first capture group
+/// (or the whole match) of the last match of `pattern`.
+fn command_number(
+    dir: &Path,
+    command: &str,
+    pattern: &str,
+    sandbox: Option<&SandboxPolicy>,
+) -> Result<f64> {
+    let regex =
+        regex::Regex::new(pattern).with_context(|| format!("invalid pattern `{pattern}`"))?;
+    let run = run_tests_in_worktree(dir, Some(command), None, sandbox)?;
+    if !run.passed {
+        anyhow::bail!("`{command}` failed: {}", last_output_line(&run.output));
+    }
+    let captures = regex
+        .captures_iter(&run.output)
+        .last()
+        .with_context(|| format!("no value matching `{pattern}` in `{command}` output"))?;
+    let text = captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map(|value| value.as_str())
+        .unwrap_or_default();
+    text.trim()
+        .parse::<f64>()
+        .with_context(|| format!("`{text}` from `{command}` is not a number"))
+}
+
+/// Coverage of `command` at the trunk head, measured in a scratch
+/// integration worktree so the main checkout's branch and uncommitted edits
+/// never leak in. Cached per trunk commit in
+/// `.batty/reports/acceptance/coverage-baselines.json`, so the worktree is
+/// only built once each time trunk moves. The command comes from the task
+/// file, so it runs in `sandbox` too, with the scratch worktree's `.git`
+/// kept read-only.
+fn coverage_baseline(
+    project_root: &Path,
+    command: &str,
+    sandbox: Option<&SandboxPolicy>,
+) -> Result<f64> {
+    let trunk = trunk_branch_for_project(project_root)?;
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "--verify", &format!("{trunk}^{{commit}}")])
+        .current_dir(project_root)
+        .output()
+        .with_context(|| format!("failed to run git rev-parse in {}", project_root.display()))?;
+    if !head.status.success() {
+        anyhow::bail!(
+            "failed to resolve trunk branch `{trunk}`: {}",
+            String::from_utf8_lossy(&head.stderr).trim()
+        );
+    }
+    let head = String::from_utf8_lossy(&head.stdout).trim().to_string();
+    let cache_path = project_root
+        .join(".batty")
+        .join("reports")
+        .join("acceptance")
+        .join("coverage-baselines.json");
+    let mut cache: std::collections::BTreeMap<String, f64> = std::fs::read_to_string(&cache_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let key = format!("{head} {command}");
+    if let Some(baseline) = cache.get(&key) {
+        return Ok(*baseline);
+    }
+    let integration =
+        crate::worktree::prepare_integration_worktree(project_root, "coverage-baseline-", &head)?;
+    let sandbox = sandbox.map(|sandbox| {
+        let mut sandbox = sandbox.clone();
+        sandbox.read_only.push(
+            integration
+                .path()
+                .join(".git")
+                .to_string_lossy()
+                .into_owned(),
+        );
+        sandbox
+    });
+    let baseline = command_number(
+        integration.path(),
+        command,
+        COVERAGE_PATTERN,
+        sandbox.as_ref(),
+    )
+    .context("failed to measure baseline coverage on trunk")?;
+    drop(integration);
+    cache.retain(|cached, _| cached.starts_with(&head));
+    cache.insert(key, baseline);
+    if let Some(parent) = cache_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)
+        .with_context(|| format!("failed to write {}", cache_path.display()))?;
+    Ok(baseline)
+}
+
+/// Tracked and untracked (but not ignored) files under `worktree_dir` that
+/// `entry` covers, using the same path/glob rules as task scopes.
+fn worktree_files_matching(worktree_dir: &Path, entry: &str) -> Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .args(["ls-files", "--cached", "--others", "--exclude-standard"])
+        .current_dir(worktree_dir)
+        .output()
+        .with_context(|| format!("failed to run git ls-files in {}", worktree_dir.display()))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git ls-files failed in {}: {}",
+            worktree_dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let entry = entry.trim().trim_end_matches('/');
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|file| !file.is_empty() && scope_entry_matches(entry, file))
+        .map(str::to_string)
+        .collect())
+}
+
+fn last_output_line(output: &str) -> &str {
+    output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no output")
+}
+
 fn active_claim_conflict_failure(worktree_dir: &Path) -> Result<Option<VerificationRunResult>> {
     let Some((project_root, engineer)) = engineer_worktree_context(worktree_dir) else {
         return Ok(None);
@@ -452,7 +703,7 @@ fn trunk_branch_for_worktree(worktree_dir: &Path) -> Result<String> {
     trunk_branch_for_project(&project_root)
 }
 
-fn trunk_branch_for_project(project_root: &Path) -> Result<String> {
+pub(crate) fn trunk_branch_for_project(project_root: &Path) -> Result<String> {
     let team_config_path = project_root
         .join(".batty")
         .join("team_config")
@@ -487,6 +738,32 @@ pub(crate) fn validate_declared_scope(
     }
 }
 
+/// Like [`validate_declared_scope`], but for a structured `scope:` field.
+/// The reported declared scope lists allow entries followed by deny entries
+/// prefixed with `!`.
+pub(crate) fn validate_task_scope(
+    scope: &TaskScope,
+    changed_files: &[String],
+) -> ScopeValidationResult {
+    let declared_scope = scope
+        .allow
+        .iter()
+        .cloned()
+        .chain(scope.deny.iter().map(|entry| format!("!{entry}")))
+        .collect();
+    let out_of_scope_files = changed_files
+        .iter()
+        .filter(|path| scope.check(path).is_some())
+        .cloned()
+        .collect();
+
+    ScopeValidationResult {
+        declared_scope,
+        changed_files: changed_files.to_vec(),
+        out_of_scope_files,
+    }
+}
+
 fn scope_acknowledged(
     project_root: &Path,
     engineer: &str,
@@ -548,7 +825,10 @@ pub(crate) fn inspect_scope_fence(worktree_dir: &Path) -> Result<Option<ScopeFen
         .with_context(|| format!("failed to read {}", task.source_path.display()))?;
     let changed_files =
         changed_files_from_trunk(worktree_dir, &trunk_branch_for_project(&project_root)?)?;
-    let scope = validate_declared_scope(&task_text, &changed_files);
+    let scope = match task.scope.as_ref() {
+        Some(task_scope) => validate_task_scope(task_scope, &changed_files),
+        None => validate_declared_scope(&task_text, &changed_files),
+    };
     if scope.declared_scope.is_empty() {
         return Ok(None);
     }
@@ -624,7 +904,7 @@ fn scope_validation_failure(worktree_dir: &Path) -> Result<Option<VerificationRu
     }))
 }
 
-fn engineer_worktree_context(worktree_dir: &Path) -> Option<(PathBuf, String)> {
+pub(crate) fn engineer_worktree_context(worktree_dir: &Path) -> Option<(PathBuf, String)> {
     let engineer = worktree_dir.file_name()?.to_str()?.to_string();
     let worktrees_dir = worktree_dir.parent()?;
     if worktrees_dir.file_name()?.to_str()? != "worktrees" {
@@ -637,7 +917,7 @@ fn engineer_worktree_context(worktree_dir: &Path) -> Option<(PathBuf, String)> {
     Some((batty_dir.parent()?.to_path_buf(), engineer))
 }
 
-fn find_claimed_task_for_worktree(
+pub(crate) fn find_claimed_task_for_worktree(
     project_root: &Path,
     engineer: &str,
     worktree_dir: &Path,
@@ -816,15 +1096,16 @@ fn path_within_scope(path: &str, scope_entries: &[String]) -> bool {
 mod tests {
     use std::path::{Path, PathBuf};
 
+    use crate::task::AcceptanceCriterion;
     use crate::team::inbox;
     use crate::team::test_results::{TestFailure, TestResults};
 
     use super::{
         ScopeValidationResult, active_claim_conflict_failure, commit_subjects_since_main,
-        current_branch_name, engineer_worktree_context, find_claimed_task_for_worktree,
-        inspect_scope_fence, is_scope_ack_message, parse_scope_fence, parse_test_output,
-        run_automatic_verification, scope_validation_failure, task_mismatch_validation_failure,
-        validate_declared_scope,
+        coverage_baseline, current_branch_name, engineer_worktree_context,
+        find_claimed_task_for_worktree, inspect_scope_fence, is_scope_ack_message,
+        parse_scope_fence, parse_test_output, run_acceptance_criteria, run_automatic_verification,
+        scope_validation_failure, task_mismatch_validation_failure, validate_declared_scope,
     };
 
     fn write_scope_ack_team_config(project_root: &Path) {
@@ -1476,8 +1757,126 @@ src/parser.rs:12: failure here\n";
         let result = run_automatic_verification(
             &worktree_dir,
             Some("printf 'error: src/noisy.rs:9:1 boom\\n'; exit 1"),
+            None,
+            None,
         )
         .unwrap();
         assert_eq!(result.file_paths, vec!["src/owned.rs".to_string()]);
     }
+
+    #[test]
+    fn run_acceptance_criteria_reports_each_criterion() {
+        let tmp = tempfile::tempdir().unwrap();
+        let worktree_dir = tmp.path();
+        std::fs::create_dir_all(worktree_dir.join("src")).unwrap();
+        std::fs::write(
+            worktree_dir.join("src").join("retry.rs"),
+            "pub fn retry() {}\n",
+        )
+        .unwrap();
+        assert!(
+            std::process::Command::new("git")
+                .arg("init")
+                .current_dir(worktree_dir)
+                .output()
+                .unwrap()
+                .status
+                .success()
+        );
+
+        let criteria = vec![
+            AcceptanceCriterion::Command("true".to_string()),
+            AcceptanceCriterion::Command("echo nope; exit 3".to_string()),
+            AcceptanceCriterion::FileExists("src/retry.rs".to_string()),
+            AcceptanceCriterion::FileExists("docs/retry.md".to_string()),
+            AcceptanceCriterion::Grep {
+                path: "src/**/*.rs".to_string(),
+                pattern: r"fn retry\(".to_string(),
+                absent: false,
+            },
+            AcceptanceCriterion::Grep {
+                path: "src".to_string(),
+                pattern: "todo!".to_string(),
+                absent: true,
+            },
+            AcceptanceCriterion::Coverage {
+                command: "echo 'TOTAL  81.50%'".to_string(),
+                min_delta: 0.0,
+                baseline: Some(80.0),
+            },
+            AcceptanceCriterion::Benchmark {
+                command: "echo 'parse time: 312.5 ms'".to_string(),
+                pattern: Some(r"time: ([0-9.]+)".to_string()),
+                min: None,
+                max: Some(250.0),
+            },
+            AcceptanceCriterion::Benchmark {
+                command: "echo no numbers".to_string(),
+                pattern: Some(r"time: ([0-9.]+)".to_string()),
+                min: None,
+                max: None,
+            },
+        ];
+        let results = run_acceptance_criteria(worktree_dir, worktree_dir, &criteria, None);
+        let outcomes: Vec<(bool, &str)> = results
+            .iter()
+            .map(|result| (result.passed, result.detail.as_str()))
+            .collect();
+        assert_eq!(
+            outcomes[..8],
+            [
+                (true, "exited 0"),
+                (false, "failed: nope"),
+                (true, "present"),
+                (false, "missing"),
+                (true, "matched in src/retry.rs"),
+                (true, "no match in 1 file(s)"),
+                (true, "coverage 81.50% vs baseline 80.00% (delta +1.50)"),
+                (false, "measured 312.5"),
+            ]
+        );
+        assert!(!results[8].passed);
+        assert!(results[8].detail.contains("no value matching"));
+        assert_eq!(results[0].criterion, "command `true`");
+    }
+
+    #[test]
+    fn coverage_baseline_is_measured_at_the_trunk_head() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(repo.join(".gitignore"), ".batty/\n").unwrap();
+        std::fs::write(repo.join("coverage.txt"), "TOTAL 60.00%\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "base"]);
+        // The main checkout sits on another branch with uncommitted edits.
+        git(&["checkout", "-b", "scratch"]);
+        std::fs::write(repo.join("coverage.txt"), "TOTAL 95.00%\n").unwrap();
+
+        let baseline = coverage_baseline(repo, "cat coverage.txt", None).unwrap();
+
+        assert_eq!(baseline, 60.0);
+        assert_eq!(
+            std::fs::read_to_string(repo.join("coverage.txt")).unwrap(),
+            "TOTAL 95.00%\n"
+        );
+        let scratch = repo.join(".batty").join("integration-worktrees");
+        assert_eq!(std::fs::read_dir(scratch).unwrap().count(), 0);
+        // Cached per trunk commit.
+        std::fs::write(repo.join("coverage.txt"), "TOTAL 10.00%\n").unwrap();
+        assert_eq!(
+            coverage_baseline(repo, "cat coverage.txt", None).unwrap(),
+            60.0
+        );
+    }
 }
@@ -0,0 +1,221 @@
+//! Webhook sink delivery for the daemon poll loop.
+//!
+//! Each tick maps events appended since the last pass to OpenClaw envelopes
+//! and hands one per matching sink in `webhooks:` to the
+//! [`crate::team::webhooks::WebhookWorker`], which owns the queue file and
+//! does the network I/O off this thread. Dead letters the worker reports are
+//! recorded as orchestrator actions on the next tick.
+
+use anyhow::{Context, Result};
+
+use super::TeamDaemon;
+use crate::team::events::read_events;
+use crate::team::{openclaw, webhooks};
+
+/// Deliveries the worker attempts per pass before picking up new batches.
+const WEBHOOK_DELIVERY_BATCH_LIMIT: usize = 20;
+
+impl TeamDaemon {
+    pub(super) fn process_webhooks(&mut self) -> Result<()> {
+        let config = &self.config.team_config.webhooks;
+        if config.sinks.is_empty() {
+            return Ok(());
+        }
+        let project_root = self.config.project_root.clone();
+
+        let event_path = self.event_sink.path().to_path_buf();
+        let events = read_events(&event_path)
+            .with_context(|| format!("failed to read event log {}", event_path.display()))?;
+        if events.len() < self.webhook_event_cursor {
+            self.webhook_event_cursor = 0;
+        }
+        let mut deliveries = Vec::new();
+        let new_events = &events[self.webhook_event_cursor..];
+        if !new_events.is_empty() {
+            let project =
+                openclaw::local_project_identity(&project_root, &self.config.team_config.name);
+            for event in new_events {
+                let Some(envelope) = openclaw::project_event_envelope(&project, event) else {
+                    continue;
+                };
+                for sink in config
+                    .sinks
+                    .iter()
+                    .filter(|sink| webhooks::sink_matches(sink, &envelope))
+                {
+                    deliveries.push((sink.name.clone(), envelope.clone()));
+                }
+            }
+        }
+
+        if self.webhook_worker.is_none() {
+            self.webhook_worker = Some(webhooks::WebhookWorker::start(
+                &project_root,
+                WEBHOOK_DELIVERY_BATCH_LIMIT,
+            )?);
+        }
+        let worker = self
+            .webhook_worker
+            .as_ref()
+            .expect("webhook worker started");
+        if let Err(error) = worker.submit(config, deliveries) {
+            // Restart the worker from the saved queue on the next tick; the
+            // cursor stays put so these events are offered again.
+            self.webhook_worker = None;
+            return Err(error);
+        }
+        self.webhook_event_cursor = events.len();
+
+        for delivery in worker.take_dead_letters() {
+            self.record_orchestrator_action(format!(
+                "webhooks: dead-lettered {} delivery #{} to '{}' after {} attempt(s): {}",
+                delivery.event_type,
+                delivery.id,
+                delivery.sink,
+                delivery.attempts,
+                delivery.last_error.as_deref().unwrap_or("unknown error"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    use crate::team::config::WebhookSinkConfig;
+    use crate::team::events::TeamEvent;
+    use crate::team::openclaw::OpenClawEventTopic;
+    use crate::team::test_support::TestDaemonBuilder;
+    use crate::team::webhooks::{WebhookQueue, load_queue};
+
+    /// Accept one request and hand its body back over a channel.
+    fn receive_one() -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_ascii_lowercase();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n"
+            );
+            tx.send(String::from_utf8(body).unwrap()).unwrap();
+        });
+        (url, rx)
+    }
+
+    /// Poll the saved queue until the worker has written a state matching
+    /// `done`.
+    fn wait_for_queue(project_root: &Path, done: impl Fn(&WebhookQueue) -> bool) -> WebhookQueue {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Ok(queue) = load_queue(project_root) {
+                if done(&queue) || Instant::now() > deadline {
+                    return queue;
+                }
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    fn ops_sink(url: String) -> WebhookSinkConfig {
+        WebhookSinkConfig {
+            name: "ops".to_string(),
+            url,
+            secret_env: None,
+            topics: vec![OpenClawEventTopic::Completion],
+            event_types: Vec::new(),
+            roles: vec!["eng-1".to_string()],
+            tasks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn process_webhooks_sends_new_matching_events_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        let (url, bodies) = receive_one();
+        daemon.config.team_config.webhooks.sinks = vec![ops_sink(url)];
+
+        daemon.emit_event(TeamEvent::task_completed("eng-2", Some("40")));
+        daemon.emit_event(TeamEvent::task_escalated("eng-1", "41", None));
+        daemon.emit_event(TeamEvent::task_completed("eng-1", Some("42")));
+        daemon.process_webhooks().unwrap();
+
+        let body: serde_json::Value =
+            serde_json::from_str(&bodies.recv_timeout(Duration::from_secs(5)).unwrap()).unwrap();
+        assert_eq!(body["eventType"], "task.completed");
+        assert_eq!(body["identifiers"]["taskId"], "42");
+        let queue = wait_for_queue(tmp.path(), |queue| queue.pending.is_empty());
+        assert_eq!(queue.next_id, 1);
+        assert!(queue.pending.is_empty());
+        assert!(queue.dead_letters.is_empty());
+
+        // Only the new event is queued; the receiver is gone, so it stays
+        // pending for a retry.
+        daemon.emit_event(TeamEvent::task_completed("eng-1", Some("43")));
+        daemon.process_webhooks().unwrap();
+        let queue = wait_for_queue(tmp.path(), |queue| !queue.pending.is_empty());
+        assert_eq!(queue.next_id, 2);
+        assert_eq!(queue.pending.len(), 1);
+    }
+
+    #[test]
+    fn unreachable_sink_does_not_stall_the_tick() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        // Connections land in the backlog but are never accepted or answered.
+        let blackhole = TcpListener::bind("127.0.0.1:0").unwrap();
+        daemon.config.team_config.webhooks.sinks = vec![ops_sink(format!(
+            "http://{}/hook",
+            blackhole.local_addr().unwrap()
+        ))];
+        daemon.config.team_config.webhooks.timeout_secs = 30;
+
+        for task in 1..=5 {
+            daemon.emit_event(TeamEvent::task_completed("eng-1", Some(&task.to_string())));
+        }
+        for _ in 0..3 {
+            let started = Instant::now();
+            daemon.process_webhooks().unwrap();
+            assert!(
+                started.elapsed() < Duration::from_secs(2),
+                "tick took {:?}",
+                started.elapsed()
+            );
+        }
+        let queue = wait_for_queue(tmp.path(), |queue| queue.next_id == 5);
+        assert_eq!(queue.next_id, 5);
+        assert_eq!(queue.pending.len(), 5);
+    }
+
+    #[test]
+    fn process_webhooks_is_noop_without_sinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut daemon = TestDaemonBuilder::new(tmp.path()).build();
+        daemon.emit_event(TeamEvent::task_completed("eng-1", Some("1")));
+        daemon.process_webhooks().unwrap();
+        assert!(!crate::team::webhooks::webhook_state_path(tmp.path()).exists());
+    }
+}
@@ -2278,6 +2278,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -2493,6 +2500,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: true,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
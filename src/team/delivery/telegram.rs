@@ -226,6 +226,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
@@ -288,6 +295,8 @@ mod tests {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: Default::default(),
             context_pressure_tracker: Default::default(),
             last_health_check: Instant::now(),
@@ -308,6 +317,21 @@ mod tests {
             merge_queue: crate::team::daemon::MergeQueue::default(),
             last_binary_freshness_check: Instant::now(),
             last_tiered_inbox_sweep: Instant::now(),
+            budget_ledger: Default::default(),
+            tool_activity: Default::default(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules: Default::default(),
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
+            slack_bot: None,
+            slack_event_cursor: 0,
+            webhook_event_cursor: 0,
+            webhook_worker: None,
+            hook_event_cursor: 0,
+            api_server: None,
+            prometheus: None,
+            task_traces: None,
         }
     }
 
@@ -379,6 +403,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
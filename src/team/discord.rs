@@ -264,7 +264,7 @@ impl DiscordBot {
         let commands_channel_id = config.commands_channel_id.clone()?;
         Some(Self::new(
             token,
-            config.allowed_user_ids.clone(),
+            config.numeric_user_ids(),
             commands_channel_id,
         ))
     }
@@ -3,11 +3,11 @@
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result, anyhow, bail};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Local, Utc};
 use tracing::{info, warn};
 
-use super::telegram_bridge::TelegramCommand;
+use super::operator_commands::OperatorSource;
 use super::*;
 use crate::task::{Task, load_tasks_from_dir};
 use crate::team::config::{ChannelConfig, RoleType, TeamConfig};
@@ -100,7 +100,14 @@ impl TeamDaemon {
                 "discord inbound"
             );
 
-            if let Some(reply) = self.handle_discord_command(&msg.text) {
+            let user = msg.from_user_id.to_string();
+            let source = OperatorSource {
+                transport: "discord",
+                user: &user,
+                member: None,
+                location: &msg.channel_id,
+            };
+            if let Some(reply) = self.handle_operator_message(source, &msg.text) {
                 if let Some(bot) = self.discord_bot.as_ref() {
                     if let Err(error) = bot.send_command_reply(&reply) {
                         warn!(error = %error, "failed to send discord command reply");
@@ -126,19 +133,6 @@ impl TeamDaemon {
         Ok(())
     }
 
-    fn handle_discord_command(&mut self, text: &str) -> Option<String> {
-        let command = match parse_discord_command(text) {
-            Ok(Some(command)) => command,
-            Ok(None) => return None,
-            Err(error) => return Some(error.to_string()),
-        };
-
-        Some(match self.execute_telegram_command(command) {
-            Ok(reply) => reply,
-            Err(error) => format!("Command failed: {error}"),
-        })
-    }
-
     fn sync_discord_events(&mut self) -> Result<()> {
         if self.discord_bot.is_none() {
             return Ok(());
@@ -356,12 +350,12 @@ fn build_board_message_body(
     }))
 }
 
-struct BoardSection {
-    total: usize,
-    rendered: String,
+pub(super) struct BoardSection {
+    pub(super) total: usize,
+    pub(super) rendered: String,
 }
 
-fn summarize_in_progress_tasks(tasks: &[Task]) -> BoardSection {
+pub(super) fn summarize_in_progress_tasks(tasks: &[Task]) -> BoardSection {
     let mut tasks = tasks
         .iter()
         .filter(|task| matches!(task.status.as_str(), "in-progress" | "in_progress"))
@@ -395,7 +389,7 @@ fn summarize_in_progress_tasks(tasks: &[Task]) -> BoardSection {
     }
 }
 
-fn summarize_todo_tasks(tasks: &[Task]) -> BoardSection {
+pub(super) fn summarize_todo_tasks(tasks: &[Task]) -> BoardSection {
     let mut tasks = tasks
         .iter()
         .filter(|task| matches!(task.status.as_str(), "todo" | "backlog"))
@@ -422,7 +416,7 @@ fn summarize_todo_tasks(tasks: &[Task]) -> BoardSection {
     }
 }
 
-fn summarize_review_tasks(tasks: &[Task]) -> BoardSection {
+pub(super) fn summarize_review_tasks(tasks: &[Task]) -> BoardSection {
     let mut tasks = tasks
         .iter()
         .filter(|task| task.status == "review")
@@ -463,7 +457,7 @@ fn render_section_lines(lines: Vec<String>, total: usize) -> String {
     rendered
 }
 
-fn build_health_footer(
+pub(super) fn build_health_footer(
     members: &[MemberInstance],
     states: &HashMap<String, MemberState>,
     backend_health: &HashMap<String, crate::agent::BackendHealth>,
@@ -506,7 +500,7 @@ fn role_activity_counts(
     (active, members.len())
 }
 
-fn count_done_today(tasks: &[Task], now: DateTime<Local>) -> usize {
+pub(super) fn count_done_today(tasks: &[Task], now: DateTime<Local>) -> usize {
     let today = now.date_naive();
     tasks
         .iter()
@@ -559,7 +553,7 @@ fn priority_icon(priority: &str) -> &'static str {
     }
 }
 
-fn is_telemetry_only_event(event: &TeamEvent) -> bool {
+pub(super) fn is_telemetry_only_event(event: &TeamEvent) -> bool {
     matches!(
         event.event.as_str(),
         "discord_event_sent" | "notification_delivery_sample"
@@ -668,7 +662,10 @@ fn shutdown_notice_channel_id(config: &ChannelConfig) -> Option<&str> {
         .or(config.events_channel_id.as_deref())
 }
 
-fn event_channel_id<'a>(config: &'a ChannelConfig, event: &TeamEvent) -> Option<&'a str> {
+pub(super) fn event_channel_id<'a>(
+    config: &'a ChannelConfig,
+    event: &TeamEvent,
+) -> Option<&'a str> {
     // Route by event kind:
     //  - Agent lifecycle (spawned / started / stalled / context exhausted /
     //    pattern detected) → agents channel. These are "what are the
@@ -694,7 +691,7 @@ fn event_channel_id<'a>(config: &'a ChannelConfig, event: &TeamEvent) -> Option<
 }
 
 /// Events that are daemon internals — not interesting to a human reading Discord.
-fn is_noise_event(event: &TeamEvent) -> bool {
+pub(super) fn is_noise_event(event: &TeamEvent) -> bool {
     matches!(
         event.event.as_str(),
         "daemon_heartbeat"
@@ -709,7 +706,7 @@ fn is_noise_event(event: &TeamEvent) -> bool {
     )
 }
 
-fn is_agent_event(event: &TeamEvent) -> bool {
+pub(super) fn is_agent_event(event: &TeamEvent) -> bool {
     matches!(
         event.event.as_str(),
         "agent_spawned"
@@ -759,7 +756,7 @@ fn build_event_embed(event: &TeamEvent) -> RichEmbed {
 /// format moves the role attribution into the author block and keeps
 /// the title focused on "what happened". One leading emoji, a short
 /// verb phrase, plus a task id when relevant.
-fn event_title(event: &TeamEvent) -> String {
+pub(super) fn event_title(event: &TeamEvent) -> String {
     let action = event_action_label(&event.event);
     if let Some(task) = event.task.as_deref()
         && let Some(task_id) = extract_task_id(task)
@@ -800,7 +797,7 @@ fn event_action_label(event: &str) -> String {
 
 /// One- or two-sentence narrative description. Optional — not every
 /// event has something useful to say beyond its structured fields.
-fn event_summary_line(event: &TeamEvent) -> Option<String> {
+pub(super) fn event_summary_line(event: &TeamEvent) -> Option<String> {
     match event.event.as_str() {
         "task_assigned" => {
             let engineer = event_actor_label(event);
@@ -1189,153 +1186,10 @@ fn latest_test_health(tasks: &[Task]) -> String {
     "unknown".to_string()
 }
 
-fn parse_discord_command(text: &str) -> Result<Option<TelegramCommand>> {
-    let trimmed = text.trim();
-    if !trimmed.starts_with('$') {
-        return Ok(None);
-    }
-
-    let (name, rest) = trimmed
-        .split_once(char::is_whitespace)
-        .map(|(name, rest)| (name, rest.trim()))
-        .unwrap_or((trimmed, ""));
-
-    match name {
-        "$status" => Ok(Some(TelegramCommand::Status)),
-        "$board" => Ok(Some(TelegramCommand::Board {
-            filter: (!rest.is_empty()).then(|| rest.to_string()),
-        })),
-        "$logs" => {
-            if rest.is_empty() {
-                bail!("usage: $logs <member>");
-            }
-            Ok(Some(TelegramCommand::Logs {
-                member: rest.to_string(),
-            }))
-        }
-        "$health" => Ok(Some(TelegramCommand::Health)),
-        "$assign" => {
-            let (engineer, task) = split_two_part_command(rest, "$assign <engineer> <task>")?;
-            Ok(Some(TelegramCommand::Assign { engineer, task }))
-        }
-        "$merge" => Ok(Some(TelegramCommand::Merge {
-            task_id: parse_task_id_token(rest)?,
-        })),
-        "$kick" => {
-            if rest.is_empty() {
-                bail!("usage: $kick <member>");
-            }
-            Ok(Some(TelegramCommand::Kick {
-                member: rest.to_string(),
-            }))
-        }
-        "$pause" => Ok(Some(TelegramCommand::Pause)),
-        "$resume" => Ok(Some(TelegramCommand::Resume)),
-        "$goal" => {
-            if rest.is_empty() {
-                bail!("usage: $goal <text>");
-            }
-            Ok(Some(TelegramCommand::Goal {
-                text: rest.to_string(),
-            }))
-        }
-        "$task" => {
-            if rest.is_empty() {
-                bail!("usage: $task <title>");
-            }
-            Ok(Some(TelegramCommand::Task {
-                title: rest.to_string(),
-            }))
-        }
-        "$block" => {
-            let (task, reason) = split_two_part_command(rest, "$block <task_id> <reason>")?;
-            Ok(Some(TelegramCommand::Block {
-                task_id: parse_task_id_token(&task)?,
-                reason,
-            }))
-        }
-        "$stop" => Ok(Some(TelegramCommand::Stop { confirm: true })),
-        "$go" => Ok(Some(TelegramCommand::Start)),
-        "$help" => Ok(Some(TelegramCommand::Help)),
-        "$send" => {
-            let (role, message) = split_two_part_command(rest, "$send <role> <message>")?;
-            Ok(Some(TelegramCommand::Send { role, message }))
-        }
-        other => Err(anyhow!("unknown Discord command: {other}")),
-    }
-}
-
-fn split_two_part_command(input: &str, usage: &str) -> Result<(String, String)> {
-    let (first, rest) = input
-        .split_once(char::is_whitespace)
-        .map(|(first, rest)| (first.trim(), rest.trim()))
-        .filter(|(first, rest)| !first.is_empty() && !rest.is_empty())
-        .ok_or_else(|| anyhow!("usage: {usage}"))?;
-    Ok((first.to_string(), rest.to_string()))
-}
-
-fn parse_task_id_token(input: &str) -> Result<u32> {
-    let trimmed = input.trim().trim_start_matches('#');
-    if trimmed.is_empty() {
-        bail!("missing task id");
-    }
-    trimmed
-        .parse::<u32>()
-        .with_context(|| format!("invalid task id '{input}'"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn parse_discord_command_supports_walkaway_aliases() {
-        assert_eq!(
-            parse_discord_command("$go").unwrap(),
-            Some(TelegramCommand::Start)
-        );
-        assert_eq!(
-            parse_discord_command("$status").unwrap(),
-            Some(TelegramCommand::Status)
-        );
-        assert_eq!(
-            parse_discord_command("$board review").unwrap(),
-            Some(TelegramCommand::Board {
-                filter: Some("review".to_string())
-            })
-        );
-        assert_eq!(
-            parse_discord_command("$stop").unwrap(),
-            Some(TelegramCommand::Stop { confirm: true })
-        );
-    }
-
-    #[test]
-    fn parse_discord_command_parses_send_and_assign() {
-        assert_eq!(
-            parse_discord_command("$send architect Focus on stability").unwrap(),
-            Some(TelegramCommand::Send {
-                role: "architect".to_string(),
-                message: "Focus on stability".to_string(),
-            })
-        );
-        assert_eq!(
-            parse_discord_command("$assign eng-1 Task #41: fix flakes").unwrap(),
-            Some(TelegramCommand::Assign {
-                engineer: "eng-1".to_string(),
-                task: "Task #41: fix flakes".to_string(),
-            })
-        );
-    }
-
-    #[test]
-    fn parse_discord_command_rejects_invalid_usage() {
-        assert!(parse_discord_command("$assign eng-1").is_err());
-        assert!(parse_discord_command("$merge nope").is_err());
-        assert!(parse_discord_command("$unknown").is_err());
-        assert_eq!(parse_discord_command("focus on quality").unwrap(), None);
-    }
-
     #[test]
     fn event_channel_id_routes_agent_lifecycle_to_agents_and_everything_else_to_events() {
         // Regression test for the channel-routing fix: attention / error
@@ -1352,6 +1206,9 @@ mod tests {
             agents_channel_id: Some("agents".into()),
             commands_channel_id: Some("commands".into()),
             board_channel_id: Some("board".into()),
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
 
         // Error / escalation events belong on the main events timeline,
@@ -1385,6 +1242,9 @@ mod tests {
             agents_channel_id: Some("agents".into()),
             commands_channel_id: Some("commands".into()),
             board_channel_id: Some("board".into()),
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
 
         assert_eq!(shutdown_notice_channel_id(&config), Some("agents"));
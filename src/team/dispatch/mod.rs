@@ -16,13 +16,13 @@ mod wip;
 #[cfg(test)]
 mod tests;
 
+use super::super::errors::BoardError;
 use super::super::events::TeamEvent;
 use super::super::task_loop::engineer_base_branch_name;
 use super::super::task_loop::prepare_engineer_assignment_worktree_from_trunk;
 use super::super::task_loop::refresh_engineer_worktree_if_stale_from_trunk;
 use super::super::task_loop::{WorktreeRefreshAction, WorktreeRefreshOutcome};
 use super::super::workspace::{WorkspaceAssignmentWorktree, prepare_workspace_assignment_worktree};
-use super::helpers::describe_command_failure;
 use super::launcher::{
     agent_supports_sdk_mode, canonical_agent_name, new_member_session_id, strip_nudge_section,
     write_launch_script,
@@ -492,34 +492,28 @@ impl TeamDaemon {
         })
     }
 
-    pub(crate) fn run_kanban_md_nonfatal<'a, I>(
+    /// Relay a failed board update to `recipients` instead of aborting the
+    /// caller. Returns whether the update succeeded.
+    pub(crate) fn board_update_nonfatal<'a, I>(
         &mut self,
-        args: &[&str],
+        result: Result<(), BoardError>,
         action: &str,
         recipients: I,
     ) -> bool
     where
         I: IntoIterator<Item = &'a str>,
     {
-        match std::process::Command::new("kanban-md").args(args).output() {
-            Ok(output) if output.status.success() => true,
-            Ok(output) => {
-                let detail = describe_command_failure("kanban-md", args, &output);
-                self.report_nonfatal_kanban_failure(action, &detail, recipients);
-                false
-            }
+        match result {
+            Ok(()) => true,
             Err(error) => {
-                let detail = format!(
-                    "failed while trying to {action}: could not execute `kanban-md {}`: {error}",
-                    args.join(" ")
-                );
-                self.report_nonfatal_kanban_failure(action, &detail, recipients);
+                let detail = format!("failed while trying to {action}: {error}");
+                self.report_nonfatal_board_failure(action, &detail, recipients);
                 false
             }
         }
     }
 
-    pub(super) fn report_nonfatal_kanban_failure<'a, I>(
+    pub(super) fn report_nonfatal_board_failure<'a, I>(
         &mut self,
         action: &str,
         detail: &str,
@@ -527,11 +521,7 @@ impl TeamDaemon {
     ) where
         I: IntoIterator<Item = &'a str>,
     {
-        warn!(
-            action,
-            error = detail,
-            "kanban-md command failed; continuing"
-        );
+        warn!(action, error = detail, "board update failed; continuing");
 
         let body = format!(
             "Board automation failed while trying to {action}.\n{detail}\nDecide the next board action manually."
@@ -542,7 +532,7 @@ impl TeamDaemon {
                 continue;
             }
             if let Err(error) = self.queue_daemon_message(recipient, &body) {
-                warn!(to = recipient, error = %error, "failed to relay kanban-md failure");
+                warn!(to = recipient, error = %error, "failed to relay board update failure");
             }
         }
     }
@@ -698,6 +688,11 @@ impl TeamDaemon {
             self.launch_task_assignment_as(sender, engineer, task, effective_task_id, true)?;
         if let Some(task_id) = effective_task_id {
             self.active_tasks.insert(engineer.to_string(), task_id);
+            self.trace_task_phase(
+                task_id,
+                crate::team::task_traces::TaskPhase::Assigned,
+                Some(engineer),
+            );
         }
         Ok(launch)
     }
@@ -711,6 +706,10 @@ impl TeamDaemon {
             debug!("auto-dispatch skipped because main smoke has gated dispatch");
             return Ok(());
         }
+        if self.dispatch_paused_by_budget() {
+            debug!("auto-dispatch skipped because a run or day budget is exhausted");
+            return Ok(());
+        }
 
         let elapsed = self.last_auto_dispatch.elapsed();
         if elapsed < Duration::from_secs(10) {
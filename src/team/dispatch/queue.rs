@@ -5,7 +5,6 @@ use std::path::Path;
 use std::time::Instant;
 
 use anyhow::Result;
-use regex::Regex;
 use tracing::{debug, info, warn};
 
 use super::super::super::policy::check_wip_limit;
@@ -15,8 +14,9 @@ use super::super::task_cmd::{
     transition_task_with_attribution,
 };
 use super::super::*;
+use crate::task::{glob_matches_path, has_glob_magic};
 use crate::team::allocation::{
-    EngineerProfile, load_engineer_profiles, predict_task_file_paths, rank_engineers_for_task,
+    EngineerProfile, load_engineer_profiles, predict_task_file_paths, rank_engineers_with_model,
 };
 use crate::team::config::AllocationStrategy;
 use serde::Deserialize;
@@ -309,49 +309,6 @@ fn normalize_predicted_path(path: &str) -> String {
         .to_string()
 }
 
-fn has_glob_magic(path: &str) -> bool {
-    path.contains('*') || path.contains('?')
-}
-
-fn glob_to_regex(pattern: &str) -> Option<Regex> {
-    let mut regex = String::from("^");
-    let mut chars = pattern.chars().peekable();
-    while let Some(ch) = chars.next() {
-        match ch {
-            '*' => {
-                if chars.peek() == Some(&'*') {
-                    chars.next();
-                    if chars.peek() == Some(&'/') {
-                        chars.next();
-                        regex.push_str("(?:.*/)?");
-                    } else {
-                        regex.push_str(".*");
-                    }
-                } else {
-                    regex.push_str("[^/]*");
-                }
-            }
-            '?' => regex.push_str("[^/]"),
-            '.' | '+' | '(' | ')' | '[' | ']' | '{' | '}' | '^' | '$' | '|' | '\\' => {
-                regex.push('\\');
-                regex.push(ch);
-            }
-            _ => regex.push(ch),
-        }
-    }
-    regex.push('$');
-    Regex::new(&regex).ok()
-}
-
-fn glob_matches_path(pattern: &str, path: &str) -> bool {
-    if !has_glob_magic(pattern) {
-        return pattern == path;
-    }
-    glob_to_regex(pattern)
-        .map(|regex| regex.is_match(path))
-        .unwrap_or(false)
-}
-
 fn glob_literal_prefix(pattern: &str) -> Option<&str> {
     let idx = pattern
         .char_indices()
@@ -899,6 +856,12 @@ impl TeamDaemon {
         // the record the moment the gate opens would reset `count` to 1
         // on every release, killing the exponential backoff the same way
         // the pre-#689 rescue map did.
+        if self.config.team_config.workflow_policy.allocation.strategy
+            == AllocationStrategy::Learned
+            && let Err(error) = self.allocation_model.refresh(&self.config.project_root)
+        {
+            warn!(error = %error, "allocation model unusable; using heuristic scoring");
+        }
         let release_base_window = self.release_exclusion_window();
         self.recently_released_by
             .retain(|_, record| record.in_cascade_window(release_base_window));
@@ -1108,6 +1071,11 @@ impl TeamDaemon {
 
             queued_task_ids.insert(task.id);
             queued_engineers.insert(engineer_name.clone());
+            self.trace_task_phase(
+                task.id,
+                crate::team::task_traces::TaskPhase::Queued,
+                Some(&engineer_name),
+            );
             self.dispatch_queue.push(DispatchQueueEntry {
                 engineer: engineer_name,
                 task_id: task.id,
@@ -1645,6 +1613,13 @@ impl TeamDaemon {
                     );
                     return false;
                 }
+                if self.member_budget_blocked(engineer_name) {
+                    debug!(
+                        engineer = %engineer_name,
+                        "skipping dispatch — over hard budget"
+                    );
+                    return false;
+                }
                 let Some(assigned_at) = self.manual_assign_cooldowns.get(engineer_name) else {
                     return true;
                 };
@@ -1715,12 +1690,13 @@ impl TeamDaemon {
             _ => std::borrow::Cow::Borrowed(task),
         };
 
-        rank_engineers_for_task(
-            &eligible,
-            profiles,
-            &task_for_ranking,
-            &self.config.team_config.workflow_policy.allocation,
-        )
+        let policy = &self.config.team_config.workflow_policy.allocation;
+        let model = if policy.strategy == AllocationStrategy::Learned {
+            self.allocation_model.model()
+        } else {
+            None
+        };
+        rank_engineers_with_model(&eligible, profiles, &task_for_ranking, policy, model)
     }
 }
 
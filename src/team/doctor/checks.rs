@@ -974,6 +974,8 @@ roles:
             completed: None,
             description: "done".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: repo
                 .join(".batty")
                 .join("team_config")
@@ -1176,6 +1178,8 @@ roles:
             completed: None,
             description: "Task body.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("task-88.md"),
         };
 
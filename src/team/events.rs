@@ -564,6 +564,45 @@ impl TeamEvent {
         }
     }
 
+    /// A `patterns.yaml` rule fired and ran one of its actions.
+    pub fn pattern_action_taken(
+        rule: &str,
+        action: &str,
+        role: Option<&str>,
+        task: Option<u32>,
+        success: bool,
+        details: &str,
+    ) -> Self {
+        Self {
+            reason: Some(rule.into()),
+            action_type: Some(action.into()),
+            role: role.map(str::to_string),
+            task: task.map(|id| id.to_string()),
+            success: Some(success),
+            details: Some(details.into()),
+            ..Self::base("pattern_action_taken")
+        }
+    }
+
+    /// The merge gate quarantined a flaky test (or quarantined it again).
+    pub fn test_quarantined(test: &str, task: u32, details: &str) -> Self {
+        Self {
+            reason: Some(test.into()),
+            task: Some(task.to_string()),
+            details: Some(details.into()),
+            ..Self::base("test_quarantined")
+        }
+    }
+
+    /// A quarantined test stayed green on probation and runs in the gate again.
+    pub fn test_quarantine_released(test: &str, details: &str) -> Self {
+        Self {
+            reason: Some(test.into()),
+            details: Some(details.into()),
+            ..Self::base("test_quarantine_released")
+        }
+    }
+
     pub fn disk_hygiene_cleanup(summary: &str) -> Self {
         Self {
             details: Some(summary.into()),
@@ -709,6 +748,57 @@ impl TeamEvent {
         }
     }
 
+    /// A configured budget's soft (warn-only) limit was crossed.
+    pub fn budget_soft_limit(
+        scope: &str,
+        role: Option<&str>,
+        task: Option<u32>,
+        details: &str,
+    ) -> Self {
+        Self {
+            role: role.map(str::to_string),
+            task: task.map(|id| id.to_string()),
+            reason: Some(scope.into()),
+            details: Some(details.into()),
+            ..Self::base("budget_soft_limit")
+        }
+    }
+
+    /// A configured budget's hard limit was crossed; `action` is the
+    /// enforcement applied (`pause_dispatch` or `shutdown`).
+    pub fn budget_hard_limit(
+        scope: &str,
+        role: Option<&str>,
+        task: Option<u32>,
+        action: &str,
+        details: &str,
+    ) -> Self {
+        Self {
+            role: role.map(str::to_string),
+            task: task.map(|id| id.to_string()),
+            reason: Some(scope.into()),
+            action_type: Some(action.into()),
+            details: Some(details.into()),
+            ..Self::base("budget_hard_limit")
+        }
+    }
+
+    /// A member ran the same failing shell command several times in a row.
+    pub fn command_loop_detected(
+        role: &str,
+        task: Option<u32>,
+        command: &str,
+        repeats: u32,
+    ) -> Self {
+        Self {
+            role: Some(role.into()),
+            task: task.map(|id| id.to_string()),
+            reason: Some(format!("failed {repeats} times in a row")),
+            details: Some(command.into()),
+            ..Self::base("command_loop_detected")
+        }
+    }
+
     pub fn message_routed(from: &str, to: &str) -> Self {
         Self {
             from: Some(from.into()),
@@ -788,6 +878,61 @@ impl TeamEvent {
         }
     }
 
+    /// Emitted when failover moves a member to another backend, or back to
+    /// its primary once that recovers.
+    pub fn backend_switched(
+        role: &str,
+        task: Option<&str>,
+        from: &str,
+        to: &str,
+        reason: &str,
+    ) -> Self {
+        Self {
+            role: Some(role.into()),
+            task: task.map(str::to_string),
+            from: Some(from.into()),
+            to: Some(to.into()),
+            reason: Some(reason.into()),
+            ..Self::base("backend_switched")
+        }
+    }
+
+    pub fn sandbox_violation(role: &str, kind: &str, detail: &str) -> Self {
+        Self {
+            role: Some(role.into()),
+            reason: Some(kind.into()),
+            details: Some(detail.into()),
+            ..Self::base("sandbox_violation")
+        }
+    }
+
+    pub fn approval_requested(id: u32, action: &str, subject: &str, requested_by: &str) -> Self {
+        Self {
+            role: Some(requested_by.into()),
+            action_type: Some(action.into()),
+            reason: Some(format!("approval #{id}")),
+            details: Some(subject.into()),
+            ..Self::base("approval_requested")
+        }
+    }
+
+    pub fn approval_decided(
+        id: u32,
+        action: &str,
+        subject: &str,
+        status: &str,
+        decided_by: Option<&str>,
+    ) -> Self {
+        Self {
+            role: decided_by.map(str::to_string),
+            action_type: Some(action.into()),
+            reason: Some(format!("approval #{id} {status}")),
+            details: Some(subject.into()),
+            success: Some(status == "approved"),
+            ..Self::base("approval_decided")
+        }
+    }
+
     pub fn agent_spawned(role: &str) -> Self {
         Self {
             role: Some(role.into()),
@@ -1553,6 +1698,37 @@ mod tests {
                 "pattern_detected",
                 TeamEvent::pattern_detected("merge_conflict_recurrence", 5),
             ),
+            (
+                "pattern_action_taken",
+                TeamEvent::pattern_action_taken(
+                    "stuck-engineer",
+                    "bench_engineer",
+                    Some("eng-1"),
+                    Some(42),
+                    true,
+                    "benched eng-1",
+                ),
+            ),
+            (
+                "test_quarantined",
+                TeamEvent::test_quarantined("api::retries", 42, "fix task #43"),
+            ),
+            (
+                "test_quarantine_released",
+                TeamEvent::test_quarantine_released("api::retries", "5 green runs"),
+            ),
+            (
+                "sandbox_violation",
+                TeamEvent::sandbox_violation("eng-1", "network", "Could not resolve host"),
+            ),
+            (
+                "approval_requested",
+                TeamEvent::approval_requested(3, "release", "v1.2.0", "cli"),
+            ),
+            (
+                "approval_decided",
+                TeamEvent::approval_decided(3, "release", "v1.2.0", "approved", Some("human")),
+            ),
             ("member_crashed", TeamEvent::member_crashed("eng-1", true)),
             ("pane_death", TeamEvent::pane_death("eng-1")),
             ("pane_respawned", TeamEvent::pane_respawned("eng-1")),
@@ -2505,6 +2681,22 @@ mod tests {
         assert_eq!(event.reason.as_deref(), Some("healthy→unreachable"));
     }
 
+    #[test]
+    fn backend_switched_event_fields() {
+        let event = TeamEvent::backend_switched(
+            "eng-1-1",
+            Some("42"),
+            "claude",
+            "codex/gpt-5",
+            "quota_exhausted",
+        );
+        assert_eq!(event.event, "backend_switched");
+        assert_eq!(event.task.as_deref(), Some("42"));
+        assert_eq!(event.from.as_deref(), Some("claude"));
+        assert_eq!(event.to.as_deref(), Some("codex/gpt-5"));
+        assert_eq!(event.reason.as_deref(), Some("quota_exhausted"));
+    }
+
     #[test]
     fn health_changed_event_serializes_to_jsonl() {
         let event = TeamEvent::health_changed("eng-1-2", "unreachable→healthy");
@@ -232,7 +232,7 @@ fn pattern_sort_key(pattern_type: &PatternType) -> u8 {
     }
 }
 
-fn is_failure_relevant(event: &TeamEvent) -> bool {
+pub(crate) fn is_failure_relevant(event: &TeamEvent) -> bool {
     event.event == "task_escalated"
         || event.error.is_some()
         || contains_failure_keyword(&event.event)
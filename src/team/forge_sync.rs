@@ -0,0 +1,968 @@
+//! Two-way sync between the board and GitHub/GitLab issues and pull requests.
+//!
+//! Issues carrying the configured label are imported as board tasks. After
+//! that, each side's changes since the last pass are mirrored to the other:
+//! closing or reopening an issue moves the task, task status changes update
+//! `status:*` labels and the issue state, assignees follow board claims, and
+//! issue comments are appended to the task and forwarded to its engineer.
+//! In `delivery: pull_request` mode each verified task branch gets a pull
+//! request; reviews requesting changes come back as `ReworkRequired`
+//! dispositions and a merged pull request completes the task.
+//!
+//! Everything the sync has already mirrored is kept in
+//! `.batty/forge_sync.json`, which is what makes each pass incremental.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::board_store::{BoardStore, NewTask};
+use super::config::ForgeConfig;
+use super::forge::{
+    ForgeClient, ForgeIssue, IssueUpdate, PullRequestDraft, PullRequestState, ReviewVerdict,
+    provider_name,
+};
+use super::inbox;
+use super::review::{MergeDisposition, record_external_review};
+use super::task_cmd::StatusTransitionAttribution;
+use crate::task::{Task, load_tasks_from_dir};
+
+const STATE_FILE: &str = "forge_sync.json";
+/// Marks comments posted by the sync so they are not imported back.
+const COMMENT_MARKER: &str = "<!-- batty-sync -->";
+const STATUS_LABEL_PREFIX: &str = "status:";
+const SYNC_SOURCE: &str = "forge.sync";
+/// Sender name used for forge comments and reviews delivered to inboxes.
+const INBOX_SENDER: &str = "forge";
+
+/// Everything the sync has already mirrored, keyed by issue number and by
+/// board task id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForgeSyncState {
+    #[serde(default)]
+    pub issues: BTreeMap<u64, IssueLink>,
+    #[serde(default)]
+    pub pull_requests: BTreeMap<u32, PullRequestLink>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueLink {
+    pub task_id: u32,
+    pub url: String,
+    /// Board status last mirrored to the issue.
+    pub status: String,
+    /// Board member last mirrored as the issue assignee.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    pub open: bool,
+    #[serde(default)]
+    pub comment_count: u64,
+    #[serde(default)]
+    pub seen_comments: BTreeSet<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequestLink {
+    pub number: u64,
+    pub url: String,
+    pub branch: String,
+    /// `open`, `closed`, or `merged`.
+    pub state: String,
+    #[serde(default)]
+    pub seen_reviews: BTreeSet<String>,
+}
+
+/// Human-readable record of what one sync pass changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub actions: Vec<String>,
+}
+
+pub fn state_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(STATE_FILE)
+}
+
+pub fn load_state(project_root: &Path) -> Result<ForgeSyncState> {
+    let path = state_path(project_root);
+    if !path.exists() {
+        return Ok(ForgeSyncState::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn save_state(project_root: &Path, state: &ForgeSyncState) -> Result<()> {
+    let path = state_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(state)?;
+    super::board_store::write_atomic(&path, &content)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn board_dir(project_root: &Path) -> PathBuf {
+    super::team_config_dir(project_root).join("board")
+}
+
+/// Run one sync pass. State is saved even when a step fails part-way, so
+/// whatever was mirrored before the failure is not repeated.
+pub fn sync_once(
+    project_root: &Path,
+    config: &ForgeConfig,
+    client: &ForgeClient,
+) -> Result<SyncReport> {
+    let mut sync = Sync {
+        project_root,
+        board_dir: board_dir(project_root),
+        config,
+        client,
+        state: load_state(project_root)?,
+        report: SyncReport::default(),
+    };
+    let issues = sync.sync_issues();
+    let pull_requests = sync.sync_pull_requests();
+    save_state(project_root, &sync.state)?;
+    issues?;
+    pull_requests?;
+    Ok(sync.report)
+}
+
+/// Push a verified task branch and open (or reuse) its pull request.
+pub fn deliver_pull_request(
+    project_root: &Path,
+    config: &ForgeConfig,
+    client: &ForgeClient,
+    request: &PullRequestDelivery<'_>,
+) -> Result<PullRequestLink> {
+    push_branch(request.worktree_dir, &config.remote, request.branch)?;
+
+    let mut state = load_state(project_root)?;
+    let mut body = format!("Batty task #{}.", request.task_id);
+    if let Some(issue) = state
+        .issues
+        .iter()
+        .find_map(|(number, link)| (link.task_id == request.task_id).then_some(number))
+    {
+        body.push_str(&format!("\n\nCloses #{issue}"));
+    }
+    let pr = client.find_or_open_pull_request(&PullRequestDraft {
+        head: request.branch.to_string(),
+        base: request.base.to_string(),
+        title: format!("{} (#{})", request.title, request.task_id),
+        body,
+    })?;
+
+    let seen_reviews = state
+        .pull_requests
+        .get(&request.task_id)
+        .filter(|link| link.number == pr.number)
+        .map(|link| link.seen_reviews.clone())
+        .unwrap_or_default();
+    let link = PullRequestLink {
+        number: pr.number,
+        url: pr.url,
+        branch: request.branch.to_string(),
+        state: pull_request_state_name(pr.state).to_string(),
+        seen_reviews,
+    };
+    state.pull_requests.insert(request.task_id, link.clone());
+    save_state(project_root, &state)?;
+    Ok(link)
+}
+
+/// What [`deliver_pull_request`] needs to know about the finished task.
+pub struct PullRequestDelivery<'a> {
+    pub task_id: u32,
+    pub title: &'a str,
+    pub branch: &'a str,
+    pub base: &'a str,
+    pub worktree_dir: &'a Path,
+}
+
+fn push_branch(worktree_dir: &Path, remote: &str, branch: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args([
+            "push",
+            "--force-with-lease",
+            remote,
+            &format!("{branch}:refs/heads/{branch}"),
+        ])
+        .current_dir(worktree_dir)
+        .output()
+        .with_context(|| format!("failed to run git push in {}", worktree_dir.display()))?;
+    if !output.status.success() {
+        bail!(
+            "git push {remote} {branch} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+struct Sync<'a> {
+    project_root: &'a Path,
+    board_dir: PathBuf,
+    config: &'a ForgeConfig,
+    client: &'a ForgeClient,
+    state: ForgeSyncState,
+    report: SyncReport,
+}
+
+impl Sync<'_> {
+    fn store(&self) -> BoardStore {
+        BoardStore::open(&self.board_dir)
+    }
+
+    fn tasks(&self) -> Result<HashMap<u32, Task>> {
+        let tasks_dir = self.board_dir.join("tasks");
+        if !tasks_dir.is_dir() {
+            return Ok(HashMap::new());
+        }
+        Ok(load_tasks_from_dir(&tasks_dir)?
+            .into_iter()
+            .map(|task| (task.id, task))
+            .collect())
+    }
+
+    fn sync_issues(&mut self) -> Result<()> {
+        let issues = self
+            .client
+            .list_labelled_issues(&self.config.import_label)?;
+        for issue in issues {
+            if !self.state.issues.contains_key(&issue.number) {
+                if !issue.open {
+                    continue;
+                }
+                self.import_issue(&issue)?;
+            }
+            self.reconcile_issue(&issue)?;
+        }
+        Ok(())
+    }
+
+    fn import_issue(&mut self, issue: &ForgeIssue) -> Result<()> {
+        let mut body = issue.body.trim().to_string();
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(&format!("Source: {}", issue.url));
+        let task_id = self.store().create(&NewTask {
+            title: issue.title.clone(),
+            body,
+            status: Some("todo".to_string()),
+            tags: vec!["forge".to_string()],
+            ..NewTask::default()
+        })?;
+        // `status` starts empty so the first reconcile labels the issue.
+        self.state.issues.insert(
+            issue.number,
+            IssueLink {
+                task_id,
+                url: issue.url.clone(),
+                status: String::new(),
+                assignee: None,
+                open: true,
+                comment_count: 0,
+                seen_comments: BTreeSet::new(),
+            },
+        );
+        self.report.actions.push(format!(
+            "imported issue #{} as task #{task_id}",
+            issue.number
+        ));
+        Ok(())
+    }
+
+    fn reconcile_issue(&mut self, issue: &ForgeIssue) -> Result<()> {
+        let mut link = self.state.issues[&issue.number].clone();
+        let result = self.reconcile_linked_issue(issue, &mut link);
+        self.state.issues.insert(issue.number, link);
+        result
+    }
+
+    fn reconcile_linked_issue(&mut self, issue: &ForgeIssue, link: &mut IssueLink) -> Result<()> {
+        let task_id = link.task_id;
+        let tasks = self.tasks()?;
+        let task = tasks.get(&task_id);
+        // Tasks that left `tasks/` were archived by the board rotation.
+        let mut board_status = task
+            .map(|task| task.status.clone())
+            .unwrap_or_else(|| "archived".to_string());
+        let mut board_assignee =
+            task.and_then(|task| task.claimed_by.clone().or_else(|| task.assignee.clone()));
+
+        // Forge -> board: the issue was closed or reopened on the forge.
+        let mut status_from_forge = false;
+        if issue.open != link.open && task.is_some() {
+            let target = match (issue.open, is_terminal(&board_status)) {
+                (false, false) => Some("done"),
+                (true, true) => Some("todo"),
+                _ => None,
+            };
+            if let Some(target) = target {
+                self.store()
+                    .move_task(task_id, target, None, &attribution())?;
+                self.report.actions.push(format!(
+                    "issue #{} {} -> task #{task_id} moved to {target}",
+                    issue.number,
+                    if issue.open { "reopened" } else { "closed" }
+                ));
+                board_status = target.to_string();
+                status_from_forge = true;
+            }
+        }
+        link.open = issue.open;
+
+        // Forge -> board: the issue assignee changed while the board did not.
+        let forge_assignee = issue
+            .assignees
+            .first()
+            .map(|login| member_for_login(self.config, login));
+        if forge_assignee != link.assignee && board_assignee == link.assignee && task.is_some() {
+            self.store()
+                .set_assignee(task_id, forge_assignee.as_deref())?;
+            self.report.actions.push(format!(
+                "issue #{} assignee -> task #{task_id} assignee {}",
+                issue.number,
+                forge_assignee.as_deref().unwrap_or("cleared")
+            ));
+            board_assignee = forge_assignee;
+            link.assignee = board_assignee.clone();
+        }
+
+        // Board -> forge: status labels, open/closed state, and assignee.
+        let mut update = IssueUpdate::default();
+        if board_status != link.status {
+            update.labels = Some(status_labels(&issue.labels, &board_status));
+            let open = !is_terminal(&board_status);
+            if open != link.open {
+                update.open = Some(open);
+                link.open = open;
+            }
+            if !status_from_forge && !link.status.is_empty() {
+                let comment = status_comment(task_id, &link.status, &board_status, task);
+                let posted = self.client.post_issue_comment(issue.number, &comment)?;
+                link.seen_comments.insert(posted.id);
+            }
+            link.status = board_status.clone();
+        }
+        if board_assignee != link.assignee {
+            update.assignees = Some(
+                board_assignee
+                    .iter()
+                    .map(|member| login_for_member(self.config, member))
+                    .collect(),
+            );
+            link.assignee = board_assignee;
+        }
+        if !update.is_empty() {
+            self.client.update_issue(issue.number, &update)?;
+            self.report.actions.push(format!(
+                "task #{task_id} -> issue #{} updated ({})",
+                issue.number,
+                describe_update(&update)
+            ));
+        }
+
+        // Forge -> board: new comments. Only fetched when the count moved.
+        if issue.comment_count != link.comment_count {
+            for comment in self.client.list_issue_comments(issue.number)? {
+                if !link.seen_comments.insert(comment.id) || comment.body.contains(COMMENT_MARKER) {
+                    continue;
+                }
+                if task.is_none() {
+                    continue;
+                }
+                let note = format!(
+                    "Comment from @{} on issue #{}:\n{}",
+                    comment.author,
+                    issue.number,
+                    comment.body.trim()
+                );
+                self.store().append_note(task_id, &note)?;
+                if let Some(engineer) = task.and_then(|task| task.claimed_by.as_deref()) {
+                    self.notify(engineer, &format!("Task #{task_id}: {note}"))?;
+                }
+                self.report.actions.push(format!(
+                    "issue #{} comment {} -> task #{task_id}",
+                    issue.number, comment.id
+                ));
+            }
+            link.comment_count = issue.comment_count;
+        }
+        Ok(())
+    }
+
+    fn sync_pull_requests(&mut self) -> Result<()> {
+        let open = self
+            .state
+            .pull_requests
+            .iter()
+            .filter(|(_, link)| link.state == "open")
+            .map(|(task_id, _)| *task_id)
+            .collect::<Vec<_>>();
+        for task_id in open {
+            let mut link = self.state.pull_requests[&task_id].clone();
+            let result = self.sync_pull_request(task_id, &mut link);
+            self.state.pull_requests.insert(task_id, link);
+            result?;
+        }
+        Ok(())
+    }
+
+    fn sync_pull_request(&mut self, task_id: u32, link: &mut PullRequestLink) -> Result<()> {
+        let pr = self.client.pull_request(link.number)?;
+        let tasks = self.tasks()?;
+        let task = tasks.get(&task_id);
+        match pr.state {
+            PullRequestState::Merged => {
+                if task.is_some_and(|task| !is_terminal(&task.status)) {
+                    self.store()
+                        .move_task(task_id, "done", None, &attribution())?;
+                }
+                self.report.actions.push(format!(
+                    "pull request #{} merged -> task #{task_id} done",
+                    link.number
+                ));
+            }
+            PullRequestState::Closed => {
+                self.report.actions.push(format!(
+                    "pull request #{} for task #{task_id} closed without merging",
+                    link.number
+                ));
+            }
+            PullRequestState::Open => {
+                let requested = self
+                    .client
+                    .list_reviews(link.number)?
+                    .into_iter()
+                    .filter(|review| link.seen_reviews.insert(review.id.clone()))
+                    .filter(|review| review.verdict == ReviewVerdict::ChangesRequested)
+                    .collect::<Vec<_>>();
+                // Reviews that land while the engineer is already reworking
+                // are still marked seen; their comments stay on the pull
+                // request for the next round.
+                if !requested.is_empty() && task.is_some_and(|task| task.status == "review") {
+                    let noun = pull_request_noun(self.client);
+                    let feedback = requested
+                        .iter()
+                        .map(|review| {
+                            let mut text = format!(
+                                "Changes requested on {noun} #{} by @{}",
+                                link.number, review.reviewer
+                            );
+                            if !review.body.is_empty() {
+                                text.push_str(&format!(":\n{}", review.body));
+                            }
+                            text
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    let reviewers = requested
+                        .iter()
+                        .map(|review| format!("@{}", review.reviewer))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    record_external_review(
+                        &self.board_dir,
+                        task_id,
+                        MergeDisposition::ReworkRequired,
+                        &reviewers,
+                        Some(&feedback),
+                        attribution(),
+                    )?;
+                    self.report.actions.push(format!(
+                        "{noun} #{} review by {reviewers} -> task #{task_id} changes requested",
+                        link.number
+                    ));
+                }
+            }
+        }
+        link.state = pull_request_state_name(pr.state).to_string();
+        Ok(())
+    }
+
+    fn notify(&self, member: &str, body: &str) -> Result<()> {
+        let message = inbox::InboxMessage::new_send(INBOX_SENDER, member, body);
+        inbox::deliver_to_inbox(&inbox::inboxes_root(self.project_root), &message)?;
+        Ok(())
+    }
+}
+
+fn attribution() -> StatusTransitionAttribution {
+    StatusTransitionAttribution::bridge(SYNC_SOURCE)
+}
+
+fn is_terminal(status: &str) -> bool {
+    matches!(status, "done" | "archived")
+}
+
+/// Replace any `status:*` labels with the one for `status`.
+fn status_labels(labels: &[String], status: &str) -> Vec<String> {
+    labels
+        .iter()
+        .filter(|label| !label.starts_with(STATUS_LABEL_PREFIX))
+        .cloned()
+        .chain(std::iter::once(format!("{STATUS_LABEL_PREFIX}{status}")))
+        .collect()
+}
+
+fn status_comment(task_id: u32, from: &str, to: &str, task: Option<&Task>) -> String {
+    let mut comment =
+        format!("{COMMENT_MARKER}\nBatty moved task #{task_id} from `{from}` to `{to}`.");
+    let detail = task.and_then(|task| match to {
+        "blocked" => task.blocked_on.as_deref().or(task.blocked.as_deref()),
+        _ => task.next_action.as_deref(),
+    });
+    if let Some(detail) = detail.filter(|detail| !detail.trim().is_empty()) {
+        comment.push_str(&format!("\n\n> {}", detail.trim()));
+    }
+    comment
+}
+
+fn describe_update(update: &IssueUpdate) -> String {
+    let mut parts = Vec::new();
+    if let Some(open) = update.open {
+        parts.push(if open { "reopened" } else { "closed" }.to_string());
+    }
+    if let Some(labels) = &update.labels {
+        parts.push(format!("labels {}", labels.join(",")));
+    }
+    if let Some(assignees) = &update.assignees {
+        parts.push(if assignees.is_empty() {
+            "unassigned".to_string()
+        } else {
+            format!("assigned {}", assignees.join(","))
+        });
+    }
+    parts.join(", ")
+}
+
+fn login_for_member(config: &ForgeConfig, member: &str) -> String {
+    config
+        .assignees
+        .get(member)
+        .cloned()
+        .unwrap_or_else(|| member.to_string())
+}
+
+fn member_for_login(config: &ForgeConfig, login: &str) -> String {
+    config
+        .assignees
+        .iter()
+        .find_map(|(member, mapped)| (mapped == login).then(|| member.clone()))
+        .unwrap_or_else(|| login.to_string())
+}
+
+fn pull_request_state_name(state: PullRequestState) -> &'static str {
+    match state {
+        PullRequestState::Open => "open",
+        PullRequestState::Closed => "closed",
+        PullRequestState::Merged => "merged",
+    }
+}
+
+fn pull_request_noun(client: &ForgeClient) -> &'static str {
+    match client.provider() {
+        super::config::ForgeProvider::Github => "pull request",
+        super::config::ForgeProvider::Gitlab => "merge request",
+    }
+}
+
+/// Entry point for `batty forge sync`.
+pub fn run_sync(project_root: &Path) -> Result<()> {
+    let config = load_forge_config(project_root)?;
+    let client = ForgeClient::from_config(&config)?;
+    let report = sync_once(project_root, &config, &client)?;
+    if report.actions.is_empty() {
+        println!("{} sync: nothing to do.", provider_name(config.provider));
+    }
+    for action in &report.actions {
+        println!("{action}");
+    }
+    Ok(())
+}
+
+/// Entry point for `batty forge status`.
+pub fn show_status(project_root: &Path, json: bool) -> Result<()> {
+    let state = load_state(project_root)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&state)?);
+        return Ok(());
+    }
+    if state.issues.is_empty() && state.pull_requests.is_empty() {
+        println!("No issues or pull requests synced yet.");
+        return Ok(());
+    }
+    if !state.issues.is_empty() {
+        println!("Issues:");
+        for (number, link) in &state.issues {
+            println!(
+                "  #{number:<6} task #{:<5} {:<12} {:<7} {}",
+                link.task_id,
+                link.status,
+                if link.open { "open" } else { "closed" },
+                link.assignee.as_deref().unwrap_or("--")
+            );
+        }
+    }
+    if !state.pull_requests.is_empty() {
+        println!("Pull requests:");
+        for (task_id, link) in &state.pull_requests {
+            println!(
+                "  task #{task_id:<5} #{:<6} {:<7} {}",
+                link.number, link.state, link.url
+            );
+        }
+    }
+    Ok(())
+}
+
+fn load_forge_config(project_root: &Path) -> Result<ForgeConfig> {
+    let config = super::config::TeamConfig::load(&super::team_config_path(project_root))?;
+    if !config.forge.enabled {
+        bail!("forge sync is disabled; set `forge.enabled: true` in team.yaml");
+    }
+    Ok(config.forge)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::team::config::ForgeProvider;
+    use crate::team::forge::mock::MockForge;
+
+    const ISSUES: &str = "GET /repos/acme/widgets/issues";
+
+    fn setup() -> (tempfile::TempDir, MockForge, ForgeClient, ForgeConfig) {
+        let tmp = tempfile::tempdir().unwrap();
+        BoardStore::open(&board_dir(tmp.path())).init().unwrap();
+        let mock = MockForge::start();
+        let client = ForgeClient::new(ForgeProvider::Github, &mock.base, "acme/widgets", "token");
+        let config = ForgeConfig {
+            enabled: true,
+            repo: Some("acme/widgets".to_string()),
+            assignees: HashMap::from([("eng-1".to_string(), "octo-eng".to_string())]),
+            ..ForgeConfig::default()
+        };
+        mock.respond("PATCH /repos/acme/widgets/issues/7", json!({ "number": 7 }));
+        mock.respond(
+            "POST /repos/acme/widgets/issues/7/comments",
+            json!({ "id": 900, "body": "" }),
+        );
+        (tmp, mock, client, config)
+    }
+
+    fn issue(state: &str, assignees: &[&str], comments: u64) -> serde_json::Value {
+        json!({
+            "number": 7, "title": "Crash on save", "body": "Steps to reproduce.",
+            "state": state,
+            "labels": [{ "name": "batty" }],
+            "assignees": assignees.iter().map(|login| json!({ "login": login })).collect::<Vec<_>>(),
+            "comments": comments,
+            "html_url": "https://github.com/acme/widgets/issues/7"
+        })
+    }
+
+    fn task(root: &Path, id: u32) -> Task {
+        let path = crate::task::find_task_path_by_id(&board_dir(root).join("tasks"), id).unwrap();
+        Task::from_file(&path).unwrap()
+    }
+
+    fn move_task(root: &Path, id: u32, status: &str, claim: Option<&str>) {
+        BoardStore::open(&board_dir(root))
+            .move_task(id, status, claim, &attribution())
+            .unwrap();
+    }
+
+    #[test]
+    fn import_creates_todo_task_and_labels_issue_once() {
+        let (tmp, mock, client, config) = setup();
+        let mut closed = issue("closed", &[], 0);
+        closed["number"] = json!(8);
+        mock.respond(ISSUES, json!([issue("open", &[], 0), closed]));
+
+        let report = sync_once(tmp.path(), &config, &client).unwrap();
+
+        assert_eq!(report.actions[0], "imported issue #7 as task #1");
+        let imported = task(tmp.path(), 1);
+        assert_eq!(imported.title, "Crash on save");
+        assert_eq!(imported.status, "todo");
+        assert!(imported.tags.contains(&"forge".to_string()));
+        assert!(
+            imported
+                .description
+                .contains("Source: https://github.com/acme/widgets/issues/7")
+        );
+        let patches = mock.requests("PATCH", "/repos/acme/widgets/issues/7");
+        assert_eq!(patches.len(), 1);
+        assert_eq!(
+            patches[0].body,
+            json!({ "labels": ["batty", "status:todo"] })
+        );
+        assert!(
+            mock.requests("POST", "/repos/acme/widgets/issues/7/comments")
+                .is_empty()
+        );
+        let state = load_state(tmp.path()).unwrap();
+        assert_eq!(state.issues.len(), 1);
+        assert_eq!(state.issues[&7].status, "todo");
+
+        let report = sync_once(tmp.path(), &config, &client).unwrap();
+        assert!(report.actions.is_empty(), "{:?}", report.actions);
+        assert_eq!(
+            mock.requests("PATCH", "/repos/acme/widgets/issues/7").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn board_progress_is_mirrored_as_labels_assignee_comment_and_close() {
+        let (tmp, mock, client, config) = setup();
+        mock.respond(ISSUES, json!([issue("open", &[], 0)]));
+        sync_once(tmp.path(), &config, &client).unwrap();
+
+        move_task(tmp.path(), 1, "in-progress", Some("eng-1"));
+        sync_once(tmp.path(), &config, &client).unwrap();
+
+        let patch = mock
+            .requests("PATCH", "/repos/acme/widgets/issues/7")
+            .pop()
+            .unwrap();
+        assert_eq!(
+            patch.body,
+            json!({ "labels": ["batty", "status:in-progress"], "assignees": ["octo-eng"] })
+        );
+        let comment = mock
+            .requests("POST", "/repos/acme/widgets/issues/7/comments")
+            .pop()
+            .unwrap();
+        let text = comment.body["body"].as_str().unwrap();
+        assert!(text.starts_with(COMMENT_MARKER));
+        assert!(text.contains("from `todo` to `in-progress`"));
+
+        move_task(tmp.path(), 1, "done", None);
+        sync_once(tmp.path(), &config, &client).unwrap();
+
+        let patch = mock
+            .requests("PATCH", "/repos/acme/widgets/issues/7")
+            .pop()
+            .unwrap();
+        assert_eq!(patch.body["state"], "closed");
+        assert_eq!(patch.body["labels"], json!(["batty", "status:done"]));
+        assert!(!load_state(tmp.path()).unwrap().issues[&7].open);
+    }
+
+    #[test]
+    fn closing_and_reassigning_on_the_forge_updates_the_board() {
+        let (tmp, mock, client, config) = setup();
+        mock.respond(ISSUES, json!([issue("open", &[], 0)]));
+        sync_once(tmp.path(), &config, &client).unwrap();
+
+        mock.respond(ISSUES, json!([issue("open", &["octo-eng"], 0)]));
+        sync_once(tmp.path(), &config, &client).unwrap();
+        assert_eq!(task(tmp.path(), 1).assignee.as_deref(), Some("eng-1"));
+
+        let patches_before = mock.requests("PATCH", "/repos/acme/widgets/issues/7").len();
+        mock.respond(ISSUES, json!([issue("closed", &["octo-eng"], 0)]));
+        let report = sync_once(tmp.path(), &config, &client).unwrap();
+
+        assert_eq!(task(tmp.path(), 1).status, "done");
+        assert!(report.actions[0].contains("issue #7 closed -> task #1 moved to done"));
+        // The label follows, but no comment echoes the forge's own change.
+        let patches = mock.requests("PATCH", "/repos/acme/widgets/issues/7");
+        assert_eq!(patches.len(), patches_before + 1);
+        assert_eq!(
+            patches.last().unwrap().body,
+            json!({ "labels": ["batty", "status:done"] })
+        );
+        assert!(
+            mock.requests("POST", "/repos/acme/widgets/issues/7/comments")
+                .is_empty()
+        );
+
+        mock.respond(ISSUES, json!([issue("open", &["octo-eng"], 0)]));
+        sync_once(tmp.path(), &config, &client).unwrap();
+        assert_eq!(task(tmp.path(), 1).status, "todo");
+    }
+
+    #[test]
+    fn new_issue_comments_reach_the_task_and_its_engineer() {
+        let (tmp, mock, client, config) = setup();
+        mock.respond(ISSUES, json!([issue("open", &[], 0)]));
+        sync_once(tmp.path(), &config, &client).unwrap();
+        move_task(tmp.path(), 1, "in-progress", Some("eng-1"));
+        sync_once(tmp.path(), &config, &client).unwrap();
+
+        mock.respond(ISSUES, json!([issue("open", &["octo-eng"], 2)]));
+        mock.respond(
+            "GET /repos/acme/widgets/issues/7/comments",
+            json!([
+                { "id": 900, "user": { "login": "batty-bot" }, "body": format!("{COMMENT_MARKER}\nBatty moved task") },
+                { "id": 901, "user": { "login": "reporter" }, "body": "Also happens on autosave." }
+            ]),
+        );
+        let report = sync_once(tmp.path(), &config, &client).unwrap();
+
+        assert_eq!(report.actions, vec!["issue #7 comment 901 -> task #1"]);
+        let body = std::fs::read_to_string(task(tmp.path(), 1).source_path).unwrap();
+        assert!(body.ends_with("Comment from @reporter on issue #7:\nAlso happens on autosave.\n"));
+        assert!(!body.contains("Batty moved task"));
+        let pending = inbox::pending_messages(&inbox::inboxes_root(tmp.path()), "eng-1").unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].from, INBOX_SENDER);
+        assert!(pending[0].body.contains("Also happens on autosave."));
+
+        sync_once(tmp.path(), &config, &client).unwrap();
+        assert_eq!(
+            mock.requests("GET", "/repos/acme/widgets/issues/7/comments")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn changes_requested_review_reworks_task_and_merge_completes_it() {
+        let (tmp, mock, client, config) = setup();
+        let tasks_dir = board_dir(tmp.path()).join("tasks");
+        std::fs::write(
+            tasks_dir.join("042-widget.md"),
+            "---\nid: 42\ntitle: Widget\nstatus: review\npriority: high\nclaimed_by: eng-1\n---\n\nBuild it.\n",
+        )
+        .unwrap();
+        let mut state = ForgeSyncState::default();
+        state.pull_requests.insert(
+            42,
+            PullRequestLink {
+                number: 12,
+                url: "https://github.com/acme/widgets/pull/12".to_string(),
+                branch: "eng-1/42".to_string(),
+                state: "open".to_string(),
+                seen_reviews: BTreeSet::from(["400".to_string()]),
+            },
+        );
+        save_state(tmp.path(), &state).unwrap();
+        mock.respond(ISSUES, json!([]));
+        mock.respond(
+            "GET /repos/acme/widgets/pulls/12",
+            json!({ "number": 12, "html_url": "https://github.com/acme/widgets/pull/12", "state": "open", "merged_at": null }),
+        );
+        mock.respond(
+            "GET /repos/acme/widgets/pulls/12/reviews",
+            json!([
+                { "id": 400, "user": { "login": "lead" }, "state": "CHANGES_REQUESTED", "body": "old round" },
+                { "id": 401, "user": { "login": "lead" }, "state": "APPROVED", "body": "" },
+                { "id": 402, "user": { "login": "lead" }, "state": "CHANGES_REQUESTED", "body": "Add a regression test." }
+            ]),
+        );
+        mock.respond("GET /repos/acme/widgets/pulls/12/comments", json!([]));
+
+        let report = sync_once(tmp.path(), &config, &client).unwrap();
+
+        assert_eq!(
+            report.actions,
+            vec!["pull request #12 review by @lead -> task #42 changes requested"]
+        );
+        let reworked = std::fs::read_to_string(task(tmp.path(), 42).source_path).unwrap();
+        assert!(reworked.contains("status: in-progress"));
+        assert!(reworked.contains("review_disposition: changes_requested"));
+        assert!(reworked.contains("reviewed_by: '@lead'"));
+        assert!(reworked.contains("Add a regression test."));
+        assert!(!reworked.contains("old round"));
+        let pending = inbox::pending_messages(&inbox::inboxes_root(tmp.path()), "eng-1").unwrap();
+        assert!(pending[0].body.contains("Add a regression test."));
+
+        mock.respond(
+            "GET /repos/acme/widgets/pulls/12",
+            json!({ "number": 12, "html_url": "https://github.com/acme/widgets/pull/12", "state": "closed", "merged_at": "2026-10-17T00:00:00Z" }),
+        );
+        sync_once(tmp.path(), &config, &client).unwrap();
+
+        assert_eq!(task(tmp.path(), 42).status, "done");
+        assert_eq!(
+            load_state(tmp.path()).unwrap().pull_requests[&42].state,
+            "merged"
+        );
+        sync_once(tmp.path(), &config, &client).unwrap();
+        assert_eq!(
+            mock.requests("GET", "/repos/acme/widgets/pulls/12").len(),
+            2
+        );
+    }
+
+    #[test]
+    fn deliver_pull_request_pushes_branch_and_links_issue() {
+        let (tmp, mock, client, config) = setup();
+        let git = |dir: &Path, args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "git {args:?}: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        };
+        let remote = tmp.path().join("remote.git");
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(tmp.path(), &["init", "-q", "--bare", "remote.git"]);
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "batty-test@example.com"]);
+        git(&repo, &["config", "user.name", "Batty Test"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&repo, &["checkout", "-q", "-b", "eng-1/1"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "work"]);
+        git(
+            &repo,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
+        );
+
+        mock.respond(ISSUES, json!([issue("open", &[], 0)]));
+        sync_once(tmp.path(), &config, &client).unwrap();
+        mock.respond("GET /repos/acme/widgets/pulls", json!([]));
+        mock.respond(
+            "POST /repos/acme/widgets/pulls",
+            json!({ "number": 15, "html_url": "https://github.com/acme/widgets/pull/15", "state": "open", "merged_at": null }),
+        );
+
+        let link = deliver_pull_request(
+            tmp.path(),
+            &config,
+            &client,
+            &PullRequestDelivery {
+                task_id: 1,
+                title: "Crash on save",
+                branch: "eng-1/1",
+                base: "main",
+                worktree_dir: &repo,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(link.number, 15);
+        assert_eq!(link.state, "open");
+        git(&remote, &["rev-parse", "--verify", "refs/heads/eng-1/1"]);
+        let created = &mock.requests("POST", "/repos/acme/widgets/pulls")[0];
+        assert_eq!(
+            created.body,
+            json!({
+                "title": "Crash on save (#1)",
+                "head": "eng-1/1",
+                "base": "main",
+                "body": "Batty task #1.\n\nCloses #7",
+            })
+        );
+        assert_eq!(load_state(tmp.path()).unwrap().pull_requests[&1], link);
+    }
+}
@@ -14,6 +14,12 @@ use std::time::Duration;
 
 /// Raw JSON for the Grafana dashboard template.
 pub const DASHBOARD_JSON: &str = include_str!("grafana/dashboard.json");
+/// Raw JSON for the variant of the dashboard backed by a Prometheus
+/// datasource scraping the daemon's `/metrics` endpoint.
+pub const PROMETHEUS_DASHBOARD_JSON: &str = include_str!("grafana/dashboard-prometheus.json");
+
+/// Datasource uid the Prometheus dashboard variant queries.
+const PROMETHEUS_DATASOURCE_UID: &str = "batty-prometheus";
 
 /// Default Grafana port.
 pub const DEFAULT_PORT: u16 = 3000;
@@ -34,6 +40,14 @@ pub const REQUIRED_ROWS: &[&str] = &[
     "Board Health",
 ];
 
+/// Expected row titles in the Prometheus dashboard variant.
+pub const REQUIRED_PROMETHEUS_ROWS: &[&str] = &[
+    "Session Overview",
+    "Pipeline Health",
+    "Agent Performance",
+    "Health Signals",
+];
+
 /// Expected alert names provisioned alongside the dashboard.
 pub const REQUIRED_ALERTS: &[&str] = &[
     "Zero Activity",
@@ -265,6 +279,42 @@ pub fn provision_dashboard(project_root: &Path, port: u16) -> Result<()> {
         r#"{{"name":"Batty Telemetry","uid":"batty-telemetry","type":"frser-sqlite-datasource","access":"proxy","jsonData":{{"path":"{}"}}}}"#,
         db_path.display()
     );
+    create_datasource(&base_url, &ds_body);
+
+    println!("Importing dashboard...");
+    import_dashboard(&base_url, DASHBOARD_JSON)?;
+
+    let url = format!("{base_url}/d/batty-project");
+    println!("Dashboard at: {url}");
+    Ok(())
+}
+
+/// Provision a Prometheus datasource and import the live-metrics dashboard,
+/// which reads the daemon's `/metrics` endpoint through Prometheus instead of
+/// `telemetry.db`.
+pub fn provision_prometheus_dashboard(port: u16, prometheus_url: &str) -> Result<()> {
+    let base_url = grafana_url(port);
+
+    println!("Creating Prometheus datasource...");
+    let ds_body = json!({
+        "name": "Batty Prometheus",
+        "uid": PROMETHEUS_DATASOURCE_UID,
+        "type": "prometheus",
+        "access": "proxy",
+        "url": prometheus_url,
+    })
+    .to_string();
+    create_datasource(&base_url, &ds_body);
+
+    println!("Importing Prometheus dashboard...");
+    import_dashboard(&base_url, PROMETHEUS_DASHBOARD_JSON)?;
+
+    let url = format!("{base_url}/d/batty-project-prometheus");
+    println!("Dashboard at: {url}");
+    Ok(())
+}
+
+fn create_datasource(base_url: &str, body: &str) {
     let ds_result = ProcessCommand::new("curl")
         .args([
             "-sf",
@@ -276,18 +326,19 @@ pub fn provision_dashboard(project_root: &Path, port: u16) -> Result<()> {
             "-u",
             "admin:admin",
             "-d",
-            &ds_body,
+            body,
         ])
         .output();
     match ds_result {
         Ok(out) if out.status.success() => println!("Datasource created."),
         _ => println!("Datasource may already exist (continuing)."),
     }
+}
 
-    println!("Importing dashboard...");
+fn import_dashboard(base_url: &str, dashboard_json: &str) -> Result<()> {
     let dashboard_payload = format!(
         r#"{{"dashboard":{},"overwrite":true,"folderId":0}}"#,
-        DASHBOARD_JSON
+        dashboard_json
     );
     let tmp_file = std::env::temp_dir().join("batty-grafana-import.json");
     std::fs::write(&tmp_file, &dashboard_payload)?;
@@ -319,9 +370,6 @@ pub fn provision_dashboard(project_root: &Path, port: u16) -> Result<()> {
             println!("Dashboard import may have failed. Check Grafana UI.");
         }
     }
-
-    let url = format!("{base_url}/d/batty-project");
-    println!("Dashboard at: {url}");
     Ok(())
 }
 
@@ -883,6 +931,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn prometheus_dashboard_has_rows_and_only_prometheus_queries() {
+        let parsed: serde_json::Value = serde_json::from_str(PROMETHEUS_DASHBOARD_JSON)
+            .expect("dashboard-prometheus.json must be valid JSON");
+        assert_ne!(
+            parsed["uid"],
+            serde_json::from_str::<serde_json::Value>(DASHBOARD_JSON).unwrap()["uid"],
+            "variants must not overwrite each other on import"
+        );
+        let panels = parsed["panels"].as_array().unwrap();
+        let row_titles: Vec<&str> = panels
+            .iter()
+            .filter(|p| p["type"].as_str() == Some("row"))
+            .filter_map(|p| p["title"].as_str())
+            .collect();
+        for expected in REQUIRED_PROMETHEUS_ROWS {
+            assert!(row_titles.contains(expected), "missing row: {expected}");
+        }
+
+        for panel in panels.iter().filter(|p| p["type"].as_str() != Some("row")) {
+            let title = panel["title"].as_str().unwrap_or("?");
+            assert_eq!(
+                panel["datasource"]["uid"].as_str(),
+                Some(PROMETHEUS_DATASOURCE_UID),
+                "panel '{title}' has unexpected datasource"
+            );
+            for target in panel["targets"].as_array().unwrap() {
+                let expr = target["expr"].as_str().unwrap_or_default();
+                assert!(
+                    expr.contains("batty_"),
+                    "panel '{title}' does not query a batty metric: {expr}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn dashboard_alert_count_matches_expected() {
         assert_eq!(REQUIRED_ALERTS.len(), ALERT_RULES.len());
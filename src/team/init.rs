@@ -3,7 +3,7 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
-use tracing::{info, warn};
+use tracing::info;
 
 use super::{
     TEAM_CONFIG_FILE, daemon_log_path, now_unix, orchestrator_log_path, team_config_dir,
@@ -199,36 +199,12 @@ pub fn init_team_with_overrides(
         scaffold_cleanroom_assets(project_root, force, &mut created)?;
     }
 
-    // Initialize kanban-md board in the team config directory
+    // Initialize the kanban-md compatible board in the team config directory
     let board_dir = config_dir.join("board");
     if !board_dir.exists() {
-        let output = std::process::Command::new("kanban-md")
-            .args(["init", "--dir", &board_dir.to_string_lossy()])
-            .output();
-        match output {
-            Ok(out) if out.status.success() => {
-                created.push(board_dir);
-            }
-            Ok(out) => {
-                let stderr = String::from_utf8_lossy(&out.stderr);
-                warn!("kanban-md init failed: {stderr}; falling back to plain kanban.md");
-                let kanban_path = config_dir.join("kanban.md");
-                std::fs::write(
-                    &kanban_path,
-                    "# Kanban Board\n\n## Backlog\n\n## In Progress\n\n## Done\n",
-                )?;
-                created.push(kanban_path);
-            }
-            Err(_) => {
-                warn!("kanban-md not found; falling back to plain kanban.md");
-                let kanban_path = config_dir.join("kanban.md");
-                std::fs::write(
-                    &kanban_path,
-                    "# Kanban Board\n\n## Backlog\n\n## In Progress\n\n## Done\n",
-                )?;
-                created.push(kanban_path);
-            }
-        }
+        super::board_cmd::init(&board_dir)
+            .with_context(|| format!("failed to initialize board at {}", board_dir.display()))?;
+        created.push(board_dir);
     }
 
     info!(dir = %config_dir.display(), files = created.len(), "scaffolded team config");
@@ -797,7 +773,7 @@ fn copy_file_if_exists(source: &Path, destination: &Path) -> Result<()> {
     Ok(())
 }
 
-fn copy_dir_if_exists(source: &Path, destination: &Path) -> Result<()> {
+pub(super) fn copy_dir_if_exists(source: &Path, destination: &Path) -> Result<()> {
     if source.is_dir() {
         let mut created = Vec::new();
         copy_template_dir(source, destination, &mut created)?;
@@ -882,9 +858,9 @@ mod tests {
                 .join("escalation_policy.md")
                 .exists()
         );
-        // kanban-md creates board/ directory; fallback creates kanban.md
-        let config = team_config_dir(tmp.path());
-        assert!(config.join("board").is_dir() || config.join("kanban.md").exists());
+        let board = team_config_dir(tmp.path()).join("board");
+        assert!(board.join("config.yml").is_file());
+        assert!(board.join("tasks").is_dir());
         let team_yaml = std::fs::read_to_string(team_config_path(tmp.path())).unwrap();
         assert!(team_yaml.contains("auto_respawn_on_crash: true"));
     }
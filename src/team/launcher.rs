@@ -395,6 +395,7 @@ impl TeamDaemon {
                         shim_agent_type_name(member.agent.as_deref().unwrap_or("claude"));
                     let sdk_mode = agent_supports_sdk_mode(&agent_type)
                         && self.config.team_config.use_sdk_mode;
+                    let sandbox = self.member_sandbox(&member.name);
                     match shim_spawn::spawn_shim(
                         &member.name,
                         &agent_type,
@@ -411,6 +412,7 @@ impl TeamDaemon {
                             .workflow_policy
                             .auto_commit_on_restart,
                         sdk_mode,
+                        sandbox.as_ref(),
                     ) {
                         Ok(handle) => {
                             if let Some(watcher) = self.watchers.get_mut(&member.name) {
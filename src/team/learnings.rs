@@ -10,12 +10,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::task::Task;
 
+use super::learnings_index::{DocumentKind, LearningsIndex, SearchHit};
+
 const LEARNINGS_DIR: &str = "learnings";
 const TASK_LEARNINGS_FILE: &str = "task_learnings.jsonl";
 const MAX_RELEVANT_LEARNINGS: usize = 3;
 const MAX_RELATED_TASKS: usize = 3;
 const MAX_FILE_PREDICTIONS: usize = 5;
 const MAX_FAILURE_PATTERNS: usize = 2;
+const MAX_REVIEW_NOTES: usize = 2;
+/// Candidates pulled from the retrieval index before per-section ranking.
+const MAX_RETRIEVAL_HITS: usize = 64;
 const MAX_CONTEXT_WORDS: usize = 500;
 
 #[derive(Debug, Clone, Default)]
@@ -30,6 +35,7 @@ struct TaskMetricsSummary {
 struct RelatedTaskContext {
     task: Task,
     score: usize,
+    relevance: f64,
     changed_paths: Vec<String>,
     metrics: TaskMetricsSummary,
     git_summary: Option<String>,
@@ -134,10 +140,26 @@ pub(crate) fn augment_assignment_message(
 }
 
 fn build_dispatch_context(project_root: &Path, task: &Task) -> Result<String> {
-    let related = related_completed_tasks(project_root, task, MAX_RELATED_TASKS)?;
-    let learnings = relevant_learnings(project_root, task, MAX_RELEVANT_LEARNINGS)?;
+    let index = LearningsIndex::build(project_root)?;
+    let hits: Vec<SearchHit> = index
+        .search(&retrieval_query(task), &[], MAX_RETRIEVAL_HITS)
+        .into_iter()
+        .filter(|hit| hit.document.task_id != Some(task.id))
+        .collect();
+    let related = related_completed_tasks(project_root, task, &hits, MAX_RELATED_TASKS)?;
+    let learnings = relevant_learnings(project_root, task, &hits, MAX_RELEVANT_LEARNINGS)?;
     let file_predictions = predict_files(&related, task);
     let failure_patterns = relevant_failure_patterns(project_root, &related, MAX_FAILURE_PATTERNS)?;
+    let similar_failures: Vec<&SearchHit> = hits
+        .iter()
+        .filter(|hit| hit.document.kind == DocumentKind::FailurePattern)
+        .take(MAX_FAILURE_PATTERNS.saturating_sub(failure_patterns.len()))
+        .collect();
+    let review_notes: Vec<&SearchHit> = hits
+        .iter()
+        .filter(|hit| hit.document.kind == DocumentKind::ReviewFeedback)
+        .take(MAX_REVIEW_NOTES)
+        .collect();
 
     let mut sections = Vec::new();
 
@@ -173,11 +195,22 @@ fn build_dispatch_context(project_root: &Path, task: &Task) -> Result<String> {
         sections.push(section);
     }
 
-    if !failure_patterns.is_empty() {
+    if !failure_patterns.is_empty() || !similar_failures.is_empty() {
         let mut section = String::from("Failure history from similar tasks:\n");
         for pattern in failure_patterns {
             section.push_str(&format!("- {}\n", pattern.description));
         }
+        for hit in similar_failures {
+            section.push_str(&format!("- {}\n", hit_line(hit)));
+        }
+        sections.push(section);
+    }
+
+    if !review_notes.is_empty() {
+        let mut section = String::from("Review feedback on similar work:\n");
+        for hit in review_notes {
+            section.push_str(&format!("- {}\n", hit_line(hit)));
+        }
         sections.push(section);
     }
 
@@ -208,14 +241,49 @@ fn build_dispatch_context(project_root: &Path, task: &Task) -> Result<String> {
     Ok(limit_word_count(&sections.join("\n"), MAX_CONTEXT_WORDS))
 }
 
+fn retrieval_query(task: &Task) -> String {
+    format!(
+        "{}\n{}\n{}",
+        task.title,
+        task.description,
+        task.tags.join(" ")
+    )
+}
+
+fn hit_line(hit: &SearchHit) -> String {
+    let text = hit
+        .document
+        .text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    match hit.document.task_id {
+        Some(task_id) => format!("Task #{task_id}: {text}"),
+        None => text,
+    }
+}
+
+/// Best retrieval score per task among the given document kinds.
+fn retrieval_scores(hits: &[SearchHit], kinds: &[DocumentKind]) -> HashMap<u32, f64> {
+    let mut scores: HashMap<u32, f64> = HashMap::new();
+    for hit in hits.iter().filter(|hit| kinds.contains(&hit.document.kind)) {
+        if let Some(task_id) = hit.document.task_id {
+            let best = scores.entry(task_id).or_insert(0.0);
+            *best = best.max(hit.score);
+        }
+    }
+    scores
+}
+
+pub(crate) fn learnings_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(LEARNINGS_DIR)
+}
+
 fn task_learnings_path(project_root: &Path) -> PathBuf {
-    project_root
-        .join(".batty")
-        .join(LEARNINGS_DIR)
-        .join(TASK_LEARNINGS_FILE)
+    learnings_dir(project_root).join(TASK_LEARNINGS_FILE)
 }
 
-fn load_task_learnings(project_root: &Path) -> Result<Vec<LearningEntry>> {
+pub(crate) fn load_task_learnings(project_root: &Path) -> Result<Vec<LearningEntry>> {
     let path = task_learnings_path(project_root);
     if !path.exists() {
         return Ok(Vec::new());
@@ -244,6 +312,7 @@ fn load_task_learnings(project_root: &Path) -> Result<Vec<LearningEntry>> {
 fn related_completed_tasks(
     project_root: &Path,
     task: &Task,
+    hits: &[SearchHit],
     limit: usize,
 ) -> Result<Vec<RelatedTaskContext>> {
     let board_dir = project_root
@@ -255,6 +324,14 @@ fn related_completed_tasks(
         return Ok(Vec::new());
     }
     let mut metrics_by_task = load_task_metrics(project_root)?;
+    let retrieval = retrieval_scores(
+        hits,
+        &[
+            DocumentKind::Completion,
+            DocumentKind::MergeDiff,
+            DocumentKind::ReviewFeedback,
+        ],
+    );
 
     let mut scored = Vec::new();
     for candidate in crate::task::load_tasks_from_dir(&tasks_dir)? {
@@ -264,7 +341,8 @@ fn related_completed_tasks(
 
         let changed_paths = load_changed_paths(candidate.source_path.as_path())?;
         let score = related_task_score(task, &candidate, &changed_paths);
-        if score == 0 {
+        let relevance = score as f64 + retrieval.get(&candidate.id).copied().unwrap_or_default();
+        if relevance <= 0.0 {
             continue;
         }
 
@@ -278,14 +356,15 @@ fn related_completed_tasks(
             git_summary,
             changed_paths,
             score,
+            relevance,
             task: candidate,
         });
     }
 
     scored.sort_by(|left, right| {
         right
-            .score
-            .cmp(&left.score)
+            .relevance
+            .total_cmp(&left.relevance)
             .then_with(|| right.metrics.completed_at.cmp(&left.metrics.completed_at))
             .then_with(|| right.task.completed.cmp(&left.task.completed))
             .then_with(|| right.task.id.cmp(&left.task.id))
@@ -297,19 +376,24 @@ fn related_completed_tasks(
 fn relevant_learnings(
     project_root: &Path,
     task: &Task,
+    hits: &[SearchHit],
     limit: usize,
 ) -> Result<Vec<LearningEntry>> {
-    let task_keywords: HashSet<String> =
-        extract_keywords(&format!("{}\n{}", task.title, task.description))
-            .into_iter()
-            .collect();
+    let retrieval: HashMap<(u32, &str), f64> = hits
+        .iter()
+        .filter(|hit| hit.document.kind == DocumentKind::Learning)
+        .filter_map(|hit| {
+            let task_id = hit.document.task_id?;
+            Some(((task_id, hit.document.title.as_str()), hit.score))
+        })
+        .collect();
     let task_tags: HashSet<String> = task
         .tags
         .iter()
         .map(|tag| tag.to_ascii_lowercase())
         .collect();
 
-    let mut scored: Vec<(usize, LearningEntry)> = load_task_learnings(project_root)?
+    let mut scored: Vec<(f64, LearningEntry)> = load_task_learnings(project_root)?
         .into_iter()
         .filter(|entry| entry.task_id != task.id)
         .filter_map(|entry| {
@@ -319,20 +403,19 @@ fn relevant_learnings(
                 .map(|tag| tag.to_ascii_lowercase())
                 .filter(|tag| task_tags.contains(tag))
                 .count();
-            let keyword_matches = entry
-                .keywords
-                .iter()
-                .filter(|keyword| task_keywords.contains(*keyword))
-                .count();
-            let score = tag_matches * 3 + keyword_matches;
-            (score > 0).then_some((score, entry))
+            let score = (tag_matches * 3) as f64
+                + retrieval
+                    .get(&(entry.task_id, entry.summary.as_str()))
+                    .copied()
+                    .unwrap_or_default();
+            (score > 0.0).then_some((score, entry))
         })
         .collect();
 
     scored.sort_by(|left, right| {
         right
             .0
-            .cmp(&left.0)
+            .total_cmp(&left.0)
             .then_with(|| right.1.completed_at.cmp(&left.1.completed_at))
     });
     Ok(scored
@@ -429,14 +512,6 @@ fn related_task_score(task: &Task, candidate: &Task, changed_paths: &[String]) -
         .iter()
         .map(|tag| tag.to_ascii_lowercase())
         .collect();
-    let task_keywords: HashSet<String> =
-        extract_keywords(&format!("{}\n{}", task.title, task.description))
-            .into_iter()
-            .collect();
-    let candidate_keywords: HashSet<String> =
-        extract_keywords(&format!("{}\n{}", candidate.title, candidate.description))
-            .into_iter()
-            .collect();
     let task_dirs: HashSet<String> = extract_path_hints(task)
         .into_iter()
         .filter_map(|path| parent_dir(&path))
@@ -447,9 +522,8 @@ fn related_task_score(task: &Task, candidate: &Task, changed_paths: &[String]) -
         .collect();
 
     let tag_matches = task_tags.intersection(&candidate_tags).count();
-    let keyword_matches = task_keywords.intersection(&candidate_keywords).count();
     let dir_matches = task_dirs.intersection(&candidate_dirs).count();
-    tag_matches * 4 + dir_matches * 3 + keyword_matches
+    tag_matches * 4 + dir_matches * 3
 }
 
 fn load_changed_paths(path: &Path) -> Result<Vec<String>> {
@@ -503,7 +577,7 @@ fn parent_dir(path: &str) -> Option<String> {
         .filter(|parent| !parent.is_empty() && parent != ".")
 }
 
-fn extract_frontmatter(content: &str) -> Option<&str> {
+pub(crate) fn extract_frontmatter(content: &str) -> Option<&str> {
     let trimmed = content.trim_start();
     if !trimmed.starts_with("---") {
         return None;
@@ -619,6 +693,8 @@ mod tests {
             completed: Some("2026-04-06T08:00:00Z".to_string()),
             description: "Teach dispatch queue scoring to prefer daemon work.".to_string(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: PathBuf::from("/tmp/task.md"),
         }
     }
@@ -721,6 +797,33 @@ mod tests {
         assert!(augmented.contains("src/team/learnings.rs"));
     }
 
+    #[test]
+    fn retrieval_surfaces_differently_worded_completion_and_review_feedback() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_task_file(
+            tmp.path(),
+            "011-backoff.md",
+            "---\nid: 11\ntitle: Retry shim respawns with backoff\nstatus: done\npriority: high\ntags:\n  - shim\nreview_feedback: Cap the respawn delay so stalled shims recover within a minute.\n---\n\nRespawning crashed shims immediately caused restart storms.\n",
+        );
+        write_task_file(
+            tmp.path(),
+            "012-grafana.md",
+            "---\nid: 12\ntitle: Grafana alert panels\nstatus: done\npriority: low\n---\n\nAdd token spend dashboards.\n",
+        );
+
+        let mut current = sample_task();
+        current.id = 99;
+        current.tags = Vec::new();
+        current.title = "Stop restart storm when a shim keeps crashing".to_string();
+        current.description = "Crashing shims are respawned in a tight loop.".to_string();
+
+        let augmented = augment_assignment_message(tmp.path(), &current, "manager").unwrap();
+        assert!(augmented.contains("- Task #11: Retry shim respawns with backoff"));
+        assert!(!augmented.contains("Task #12"));
+        assert!(augmented.contains("Review feedback on similar work:"));
+        assert!(augmented.contains("Cap the respawn delay"));
+    }
+
     #[test]
     fn dispatch_context_is_capped_to_500_words() {
         let repeated = std::iter::repeat_n("context", 700)
@@ -0,0 +1,623 @@
+//! Offline retrieval index over past work, used to pick dispatch context.
+//!
+//! Documents come from four local sources: completed board tasks (with their
+//! review feedback), the task learnings log, merge commits of done tasks, and
+//! failure events from `events.jsonl`. They are ranked with BM25 over two
+//! token streams: lightly stemmed words, which reward exact vocabulary, and
+//! character trigrams of those words, which catch the same idea worded a
+//! little differently (`retry`/`retries`, `dispatcher`/`dispatching`). No
+//! model or network call is involved.
+//!
+//! Commit text is the only expensive input, so it is cached per commit in
+//! `.batty/learnings/diff_cache.json`; everything else is re-read on each
+//! build.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::task::Task;
+
+const DIFF_CACHE_FILE: &str = "diff_cache.json";
+/// Words of patch text kept per merge commit.
+const MAX_DIFF_WORDS: usize = 400;
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+/// Trigram matches break ties and bridge wording; word matches dominate.
+const TRIGRAM_WEIGHT: f64 = 0.35;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "in", "into", "is",
+    "it", "its", "of", "on", "or", "so", "that", "the", "this", "to", "was", "were", "when",
+    "with", "we", "you", "should", "must", "can", "will", "not", "but", "all", "any", "task",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    Completion,
+    Learning,
+    MergeDiff,
+    ReviewFeedback,
+    FailurePattern,
+}
+
+impl DocumentKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Completion => "completion",
+            Self::Learning => "learning",
+            Self::MergeDiff => "merge_diff",
+            Self::ReviewFeedback => "review_feedback",
+            Self::FailurePattern => "failure_pattern",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexedDocument {
+    pub kind: DocumentKind,
+    pub task_id: Option<u32>,
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    pub score: f64,
+    #[serde(flatten)]
+    pub document: IndexedDocument,
+}
+
+struct TermStats {
+    words: HashMap<String, u32>,
+    word_len: f64,
+    trigrams: HashMap<String, u32>,
+    trigram_len: f64,
+}
+
+pub struct LearningsIndex {
+    documents: Vec<IndexedDocument>,
+    stats: Vec<TermStats>,
+    word_df: HashMap<String, usize>,
+    trigram_df: HashMap<String, usize>,
+    avg_word_len: f64,
+    avg_trigram_len: f64,
+}
+
+impl LearningsIndex {
+    /// Build the index from everything recorded under `project_root`.
+    pub fn build(project_root: &Path) -> Result<Self> {
+        let mut documents = Vec::new();
+        let done_tasks = load_done_tasks(project_root)?;
+        for task in &done_tasks {
+            documents.push(completion_document(task));
+            if let Some(feedback) = review_feedback(task) {
+                documents.push(IndexedDocument {
+                    kind: DocumentKind::ReviewFeedback,
+                    task_id: Some(task.id),
+                    title: task.title.clone(),
+                    text: feedback,
+                });
+            }
+        }
+        for entry in super::learnings::load_task_learnings(project_root)? {
+            documents.push(IndexedDocument {
+                kind: DocumentKind::Learning,
+                task_id: Some(entry.task_id),
+                text: format!(
+                    "{}\n{}\n{}",
+                    entry.title,
+                    entry.summary,
+                    entry.tags.join(" ")
+                ),
+                title: entry.summary,
+            });
+        }
+        documents.extend(merge_diff_documents(project_root, &done_tasks)?);
+        documents.extend(failure_documents(project_root)?);
+        Ok(Self::from_documents(documents))
+    }
+
+    pub fn from_documents(documents: Vec<IndexedDocument>) -> Self {
+        let stats: Vec<TermStats> = documents
+            .iter()
+            .map(|document| {
+                let words = term_counts(word_tokens(&document.text));
+                let trigrams = term_counts(trigram_tokens(&word_tokens(&document.text)));
+                TermStats {
+                    word_len: words.values().sum::<u32>() as f64,
+                    trigram_len: trigrams.values().sum::<u32>() as f64,
+                    words,
+                    trigrams,
+                }
+            })
+            .collect();
+
+        let mut word_df = HashMap::new();
+        let mut trigram_df = HashMap::new();
+        for stat in &stats {
+            for term in stat.words.keys() {
+                *word_df.entry(term.clone()).or_insert(0) += 1;
+            }
+            for term in stat.trigrams.keys() {
+                *trigram_df.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+        let count = stats.len().max(1) as f64;
+        let avg_word_len = stats.iter().map(|stat| stat.word_len).sum::<f64>() / count;
+        let avg_trigram_len = stats.iter().map(|stat| stat.trigram_len).sum::<f64>() / count;
+
+        Self {
+            documents,
+            stats,
+            word_df,
+            trigram_df,
+            avg_word_len,
+            avg_trigram_len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Rank documents against `query`. Only documents sharing at least one
+    /// word with the query are returned, so trigram overlap alone never
+    /// surfaces unrelated work.
+    pub fn search(&self, query: &str, kinds: &[DocumentKind], limit: usize) -> Vec<SearchHit> {
+        let query_words = word_tokens(query);
+        let query_trigrams = trigram_tokens(&query_words);
+        let query_words: HashSet<String> = query_words.into_iter().collect();
+        let query_trigrams: HashSet<String> = query_trigrams.into_iter().collect();
+        let count = self.documents.len();
+
+        let mut hits: Vec<SearchHit> = self
+            .documents
+            .iter()
+            .zip(&self.stats)
+            .filter(|(document, _)| kinds.is_empty() || kinds.contains(&document.kind))
+            .filter_map(|(document, stat)| {
+                let word_score = bm25(
+                    &query_words,
+                    &stat.words,
+                    stat.word_len,
+                    self.avg_word_len,
+                    &self.word_df,
+                    count,
+                );
+                if word_score <= 0.0 {
+                    return None;
+                }
+                let trigram_score = bm25(
+                    &query_trigrams,
+                    &stat.trigrams,
+                    stat.trigram_len,
+                    self.avg_trigram_len,
+                    &self.trigram_df,
+                    count,
+                );
+                Some(SearchHit {
+                    score: word_score + TRIGRAM_WEIGHT * trigram_score,
+                    document: document.clone(),
+                })
+            })
+            .collect();
+        hits.sort_by(|left, right| {
+            right
+                .score
+                .total_cmp(&left.score)
+                .then_with(|| right.document.task_id.cmp(&left.document.task_id))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+fn bm25(
+    query: &HashSet<String>,
+    terms: &HashMap<String, u32>,
+    doc_len: f64,
+    avg_len: f64,
+    df: &HashMap<String, usize>,
+    doc_count: usize,
+) -> f64 {
+    let avg_len = if avg_len > 0.0 { avg_len } else { 1.0 };
+    query
+        .iter()
+        .filter_map(|term| {
+            let tf = f64::from(*terms.get(term)?);
+            let n = *df.get(term).unwrap_or(&0) as f64;
+            let idf = ((doc_count as f64 - n + 0.5) / (n + 0.5) + 1.0).ln();
+            let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / avg_len);
+            Some(idf * tf * (BM25_K1 + 1.0) / (tf + norm))
+        })
+        .sum()
+}
+
+/// Lowercased, stopword-free, lightly stemmed words.
+pub(crate) fn word_tokens(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_ascii_alphanumeric())
+        .map(str::to_ascii_lowercase)
+        .filter(|word| word.len() >= 2 && !word.chars().all(|ch| ch.is_ascii_digit()))
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .map(|word| stem(&word))
+        .collect()
+}
+
+fn trigram_tokens(words: &[String]) -> Vec<String> {
+    words
+        .iter()
+        .flat_map(|word| {
+            let padded: Vec<char> = format!("^{word}$").chars().collect();
+            padded
+                .windows(3)
+                .map(|window| window.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Strip the commonest English suffixes so inflections share a term.
+fn stem(word: &str) -> String {
+    for suffix in [
+        "ations", "ation", "ings", "ing", "ies", "ed", "es", "ly", "s",
+    ] {
+        if let Some(root) = word.strip_suffix(suffix)
+            && root.len() >= 3
+        {
+            return match suffix {
+                "ies" => format!("{root}y"),
+                _ => root.to_string(),
+            };
+        }
+    }
+    word.to_string()
+}
+
+fn term_counts(tokens: Vec<String>) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for token in tokens {
+        *counts.entry(token).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn load_done_tasks(project_root: &Path) -> Result<Vec<Task>> {
+    let board_dir = super::team_config_dir(project_root).join("board");
+    let mut tasks = Vec::new();
+    for dir in [board_dir.join("tasks"), board_dir.join("archive")] {
+        if dir.is_dir() {
+            tasks.extend(
+                crate::task::load_tasks_from_dir(&dir)?
+                    .into_iter()
+                    .filter(|task| matches!(task.status.as_str(), "done" | "archived")),
+            );
+        }
+    }
+    Ok(tasks)
+}
+
+fn completion_document(task: &Task) -> IndexedDocument {
+    IndexedDocument {
+        kind: DocumentKind::Completion,
+        task_id: Some(task.id),
+        title: task.title.clone(),
+        text: format!(
+            "{}\n{}\n{}",
+            task.title,
+            task.description,
+            task.tags.join(" ")
+        ),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ReviewFrontmatter {
+    #[serde(default)]
+    review_feedback: Option<String>,
+}
+
+fn review_feedback(task: &Task) -> Option<String> {
+    let content = std::fs::read_to_string(&task.source_path).ok()?;
+    let frontmatter = super::learnings::extract_frontmatter(&content)?;
+    let parsed: ReviewFrontmatter = serde_yaml::from_str(frontmatter).unwrap_or_default();
+    parsed
+        .review_feedback
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn diff_cache_path(project_root: &Path) -> PathBuf {
+    super::learnings::learnings_dir(project_root).join(DIFF_CACHE_FILE)
+}
+
+fn merge_diff_documents(project_root: &Path, tasks: &[Task]) -> Result<Vec<IndexedDocument>> {
+    let path = diff_cache_path(project_root);
+    let mut cache: BTreeMap<String, String> = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => BTreeMap::new(),
+    };
+    let mut cache_changed = false;
+
+    let mut documents = Vec::new();
+    for task in tasks {
+        let Some(commit) = task.commit.as_deref().map(str::trim) else {
+            continue;
+        };
+        if commit.is_empty() {
+            continue;
+        }
+        if !cache.contains_key(commit) {
+            let Some(text) = commit_text(project_root, commit) else {
+                continue;
+            };
+            cache.insert(commit.to_string(), text);
+            cache_changed = true;
+        }
+        documents.push(IndexedDocument {
+            kind: DocumentKind::MergeDiff,
+            task_id: Some(task.id),
+            title: format!("{} ({commit})", task.title),
+            text: cache[commit].clone(),
+        });
+    }
+
+    if cache_changed {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        super::board_store::write_atomic(&path, &serde_json::to_string(&cache)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(documents)
+}
+
+/// Commit message, touched paths, and the start of the patch.
+fn commit_text(project_root: &Path, commit: &str) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args([
+            "show",
+            "--no-color",
+            "--format=%B",
+            "--unified=0",
+            commit,
+            "--",
+        ])
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut words = 0;
+    let mut kept = Vec::new();
+    for line in text.lines() {
+        let line = line
+            .strip_prefix("diff --git ")
+            .or_else(|| line.strip_prefix("+++ b/"))
+            .unwrap_or(line);
+        if line.starts_with("@@") || line.starts_with("index ") || line.starts_with("--- ") {
+            continue;
+        }
+        words += line.split_whitespace().count();
+        kept.push(line);
+        if words >= MAX_DIFF_WORDS {
+            break;
+        }
+    }
+    Some(kept.join("\n"))
+}
+
+fn failure_documents(project_root: &Path) -> Result<Vec<IndexedDocument>> {
+    let events_path = super::team_events_path(project_root);
+    if !events_path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut by_task: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+    for event in super::events::read_events(&events_path)? {
+        if !super::failure_patterns::is_failure_relevant(&event) {
+            continue;
+        }
+        let Some(task_id) = event.task.as_deref().and_then(|task| task.parse().ok()) else {
+            continue;
+        };
+        let detail = [event.reason.as_deref(), event.error.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(": ");
+        let line = if detail.is_empty() {
+            event.event.replace('_', " ")
+        } else {
+            format!("{}: {detail}", event.event.replace('_', " "))
+        };
+        let lines = by_task.entry(task_id).or_default();
+        if !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    Ok(by_task
+        .into_iter()
+        .map(|(task_id, lines)| IndexedDocument {
+            kind: DocumentKind::FailurePattern,
+            task_id: Some(task_id),
+            title: lines[0].clone(),
+            text: lines.join("\n"),
+        })
+        .collect())
+}
+
+/// Entry point for `batty learnings search`.
+pub fn run_search(project_root: &Path, query: &str, limit: usize, json: bool) -> Result<()> {
+    let index = LearningsIndex::build(project_root)?;
+    let hits = index.search(query, &[], limit);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+    if hits.is_empty() {
+        println!(
+            "No matches among {} indexed document(s) for \"{query}\".",
+            index.len()
+        );
+        return Ok(());
+    }
+    for hit in hits {
+        let task = hit
+            .document
+            .task_id
+            .map(|id| format!("#{id}"))
+            .unwrap_or_else(|| "--".to_string());
+        println!(
+            "{:>6.2}  {:<16} {:<6} {}",
+            hit.score,
+            hit.document.kind.as_str(),
+            task,
+            first_line(&hit.document.title)
+        );
+    }
+    Ok(())
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(kind: DocumentKind, task_id: u32, text: &str) -> IndexedDocument {
+        IndexedDocument {
+            kind,
+            task_id: Some(task_id),
+            title: text.lines().next().unwrap_or_default().to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn stemming_and_trigrams_match_differently_worded_text() {
+        let index = LearningsIndex::from_documents(vec![
+            doc(
+                DocumentKind::Completion,
+                1,
+                "Retries for flaky merges\nThe merger retried twice before escalating.",
+            ),
+            doc(
+                DocumentKind::Completion,
+                2,
+                "Grafana dashboards\nAlert panels for token spend.",
+            ),
+        ]);
+
+        let hits = index.search("retry a merge that failed", &[], 5);
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document.task_id, Some(1));
+    }
+
+    #[test]
+    fn rarer_shared_terms_outrank_common_ones() {
+        let index = LearningsIndex::from_documents(vec![
+            doc(DocumentKind::Learning, 1, "daemon dispatch queue ordering"),
+            doc(DocumentKind::Learning, 2, "daemon shim respawn backoff"),
+            doc(DocumentKind::Learning, 3, "daemon telemetry schema"),
+        ]);
+
+        let hits = index.search("daemon respawn storms", &[], 5);
+
+        assert_eq!(hits[0].document.task_id, Some(2));
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn search_filters_by_kind_and_requires_a_shared_word() {
+        let index = LearningsIndex::from_documents(vec![
+            doc(DocumentKind::Completion, 1, "worktree cleanup"),
+            doc(
+                DocumentKind::ReviewFeedback,
+                1,
+                "worktree cleanup left stale branches",
+            ),
+            doc(DocumentKind::Completion, 2, "workflow"),
+        ]);
+
+        let hits = index.search("worktree", &[DocumentKind::ReviewFeedback], 5);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document.kind, DocumentKind::ReviewFeedback);
+
+        // "workflow" shares trigrams with "worktree" but no word.
+        assert!(
+            index
+                .search("worktrees", &[], 5)
+                .iter()
+                .all(|hit| hit.document.task_id == Some(1))
+        );
+    }
+
+    #[test]
+    fn build_indexes_tasks_feedback_commits_and_failures() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{:?}", output);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "batty-test@example.com"]);
+        git(&["config", "user.name", "Batty Test"]);
+        std::fs::write(root.join("throttle.rs"), "fn ratelimit_bucket() {}\n").unwrap();
+        git(&["add", "throttle.rs"]);
+        git(&["commit", "-q", "-m", "Add token bucket"]);
+        let commit = git(&["rev-parse", "HEAD"]);
+
+        let tasks_dir = root.join(".batty/team_config/board/tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+        std::fs::write(
+            tasks_dir.join("007-limits.md"),
+            format!(
+                "---\nid: 7\ntitle: Limit API calls\nstatus: done\npriority: high\ncommit: {commit}\nreview_feedback: Cover the burst path with a test.\n---\n\nThrottle outbound requests.\n"
+            ),
+        )
+        .unwrap();
+        let mut sink =
+            crate::team::events::EventSink::new(&crate::team::team_events_path(root)).unwrap();
+        sink.emit(crate::team::events::TeamEvent::task_escalated(
+            "eng-1",
+            "7",
+            Some("burst tests flaked"),
+        ))
+        .unwrap();
+
+        let index = LearningsIndex::build(root).unwrap();
+
+        let kinds = |query: &str| {
+            index
+                .search(query, &[], 10)
+                .into_iter()
+                .map(|hit| hit.document.kind)
+                .collect::<Vec<_>>()
+        };
+        assert!(kinds("ratelimit bucket").contains(&DocumentKind::MergeDiff));
+        assert!(kinds("burst").contains(&DocumentKind::ReviewFeedback));
+        assert!(kinds("burst").contains(&DocumentKind::FailurePattern));
+        assert!(kinds("throttle outbound").contains(&DocumentKind::Completion));
+        let cache = std::fs::read_to_string(diff_cache_path(root)).unwrap();
+        assert!(cache.contains(&commit));
+    }
+}
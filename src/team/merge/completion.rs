@@ -18,8 +18,14 @@ use crate::team::auto_merge::{self, AutoMergeDecisionKind};
 #[cfg(test)]
 use crate::team::board::WorkflowMetadata;
 use crate::team::board::{read_workflow_metadata, write_workflow_metadata};
-use crate::team::daemon::verification::{inspect_scope_fence, run_automatic_verification};
+use crate::team::board_store::BoardStore;
+use crate::team::daemon::verification::{
+    VerificationRunResult, inspect_scope_fence, run_acceptance_criteria, run_automatic_verification,
+};
 use crate::team::daemon::{MergeRequest, TeamDaemon};
+use crate::team::forge::{self, ForgeClient};
+use crate::team::forge_sync;
+use crate::team::task_cmd::StatusTransitionAttribution;
 use crate::team::task_loop::{
     checkout_worktree_branch_from_trunk, current_worktree_branch, engineer_base_branch_name,
     read_task_title,
@@ -28,6 +34,7 @@ use crate::team::telemetry_db;
 use crate::team::test_results::{TestResults, TestRunOutput};
 use crate::team::verification::{EvidenceKind, VerificationPhase, VerificationState};
 
+use super::flaky::run_gate_verification;
 use super::git_ops::{
     code_files_changed_from_trunk, commits_ahead_of_trunk, diff_stat_from_trunk,
     files_changed_from_trunk, now_unix, run_git_with_context,
@@ -70,6 +77,102 @@ fn record_verification_evidence(
     daemon.record_verification_evidence_collected(engineer, task_id, &kind_name, &detail);
 }
 
+/// Run the task's `acceptance:` criteria after a green test run, record one
+/// evidence entry per criterion, and store the results in the completion
+/// packet. Any failing criterion turns `verification_run` red so the normal
+/// retry path sends the task back. Returns `criterion: detail` for failures.
+fn run_task_acceptance(
+    daemon: &mut TeamDaemon,
+    engineer: &str,
+    task_id: u32,
+    board_dir: &Path,
+    worktree_dir: &Path,
+    state: &mut VerificationState,
+    verification_run: &mut VerificationRunResult,
+) -> Result<Vec<String>> {
+    let task_path = crate::team::task_cmd::find_task_path(board_dir, task_id)?;
+    let criteria = crate::task::Task::from_file(&task_path)?.acceptance;
+    if criteria.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sandbox = daemon.gate_sandbox(engineer, worktree_dir);
+    let results = run_acceptance_criteria(
+        daemon.project_root(),
+        worktree_dir,
+        &criteria,
+        sandbox.as_ref(),
+    );
+    let mut failures = Vec::new();
+    for result in &results {
+        let kind = if result.passed {
+            EvidenceKind::AcceptancePassed
+        } else {
+            failures.push(format!("{}: {}", result.criterion, result.detail));
+            EvidenceKind::AcceptanceFailed
+        };
+        record_verification_evidence(
+            daemon,
+            engineer,
+            task_id,
+            state,
+            kind,
+            format!("{}: {}", result.criterion, result.detail),
+        );
+    }
+    daemon.record_orchestrator_action(format!(
+        "verification: task #{task_id} passed {}/{} acceptance criteria",
+        results.len() - failures.len(),
+        results.len()
+    ));
+
+    let mut metadata = read_workflow_metadata(&task_path)?;
+    metadata.acceptance_results = results;
+    write_workflow_metadata(&task_path, &metadata)?;
+
+    if !failures.is_empty() {
+        verification_run.passed = false;
+        verification_run.failures.extend(
+            failures
+                .iter()
+                .map(|failure| format!("acceptance: {failure}")),
+        );
+        verification_run
+            .output
+            .push_str("\n\nAcceptance criteria failed:\n");
+        for failure in &failures {
+            verification_run.output.push_str(&format!("- {failure}\n"));
+        }
+        state.last_test_passed = false;
+        state.last_test_output = Some(verification_run.output.clone());
+    }
+    Ok(failures)
+}
+
+/// Acceptance results from the completion packet, formatted for the
+/// reviewer's notification. Empty when the task declares no criteria.
+fn acceptance_review_lines(board_dir: &Path, task_id: u32) -> String {
+    let Ok(metadata) = crate::team::task_cmd::find_task_path(board_dir, task_id)
+        .and_then(|task_path| read_workflow_metadata(&task_path))
+    else {
+        return String::new();
+    };
+    let results = &metadata.acceptance_results;
+    if results.is_empty() {
+        return String::new();
+    }
+    let passed = results.iter().filter(|result| result.passed).count();
+    let mut lines = format!("\nAcceptance: {passed}/{} criteria passed", results.len());
+    for result in results {
+        let mark = if result.passed { "pass" } else { "FAIL" };
+        lines.push_str(&format!(
+            "\n- [{mark}] {}: {}",
+            result.criterion, result.detail
+        ));
+    }
+    lines
+}
+
 fn kind_name(kind: &EvidenceKind) -> String {
     let mut name = String::new();
     for (index, ch) in format!("{kind:?}").chars().enumerate() {
@@ -575,9 +678,99 @@ fn move_task_to_review(
             "failed to set review owner"
         );
     }
+    daemon.trace_task_phase(
+        task_id,
+        crate::team::task_traces::TaskPhase::InReview,
+        Some(engineer),
+    );
     Ok(true)
 }
 
+/// Open a pull request for a verified task branch instead of merging it
+/// locally. The task waits in review until the forge sync sees the pull
+/// request merged or a reviewer requesting changes.
+#[allow(clippy::too_many_arguments)]
+fn deliver_pull_request(
+    daemon: &mut TeamDaemon,
+    engineer: &str,
+    task_id: u32,
+    board_dir: &Path,
+    task_branch: &str,
+    worktree_dir: &Path,
+    manager_name: Option<&str>,
+    task_title: &str,
+    test_results: TestResults,
+) -> Result<()> {
+    if !move_task_to_review(daemon, board_dir, task_id, manager_name, engineer)? {
+        return Ok(());
+    }
+    if let Err(error) = record_completion_packet_metadata(
+        daemon.project_root(),
+        board_dir,
+        task_id,
+        task_branch,
+        worktree_dir,
+        true,
+        Some(test_results),
+    ) {
+        escalate_completion_metadata_rejection(daemon, engineer, task_id, manager_name, &error)?;
+        return Ok(());
+    }
+
+    let forge = daemon.config.team_config.forge.clone();
+    let trunk_branch = daemon.config.team_config.trunk_branch().to_string();
+    let delivered = ForgeClient::from_config(&forge).and_then(|client| {
+        forge_sync::deliver_pull_request(
+            daemon.project_root(),
+            &forge,
+            &client,
+            &forge_sync::PullRequestDelivery {
+                task_id,
+                title: task_title,
+                branch: task_branch,
+                base: &trunk_branch,
+                worktree_dir,
+            },
+        )
+    });
+    let summary = match delivered {
+        Ok(link) => {
+            info!(engineer, task_id, url = %link.url, "opened pull request");
+            daemon.record_orchestrator_action(format!(
+                "completion: opened pull request #{} for task #{task_id} ({})",
+                link.number, link.url
+            ));
+            format!(
+                "Pull request: {}\nMerge it on {}; reviews requesting changes come back to {engineer} as rework.",
+                link.url,
+                forge::provider_name(forge.provider)
+            )
+        }
+        Err(error) => {
+            warn!(engineer, task_id, error = %error, "failed to open pull request");
+            daemon.record_orchestrator_action(format!(
+                "completion: pull request for task #{task_id} failed: {error}"
+            ));
+            format!(
+                "Pull request: failed to open ({error}). Push `{task_branch}` and open it manually."
+            )
+        }
+    };
+
+    if let Some(manager_name) = manager_name {
+        let msg = format!(
+            "[{engineer}] Task #{task_id} passed tests.\nTitle: {task_title}\n{summary}{}",
+            acceptance_review_lines(board_dir, task_id)
+        );
+        daemon.queue_message(engineer, manager_name, &msg)?;
+        daemon.mark_member_working(manager_name);
+    }
+    daemon.clear_active_task(engineer);
+    daemon.record_task_completed(engineer, Some(task_id));
+    daemon.set_member_idle(engineer);
+    Ok(())
+}
+
 pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str) -> Result<()> {
     let Some(task_id) = daemon.active_task_id(engineer) else {
         return Ok(());
@@ -590,7 +783,6 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
     let worktree_dir = daemon.worktree_dir(engineer);
     let trunk_branch = daemon.config.team_config.trunk_branch().to_string();
     let board_dir = daemon.board_dir();
-    let board_dir_str = board_dir.to_string_lossy().to_string();
     let manager_name = daemon.manager_name(engineer);
 
     let total_commits = if daemon.is_multi_repo {
@@ -707,24 +899,34 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
         );
     }
 
-    let (verification_run, test_duration_ms) = if let Some(scope_fence) = scope_fence.as_ref() {
+    let (mut verification_run, test_duration_ms) = if let Some(scope_fence) = scope_fence.as_ref() {
         if !scope_fence.ack_present || !scope_fence.out_of_scope_files.is_empty() {
             (
-                run_automatic_verification(&worktree_dir, test_command.as_deref())?,
+                run_automatic_verification(
+                    &worktree_dir,
+                    test_command.as_deref(),
+                    verification_policy.test_report.as_deref(),
+                    daemon.gate_sandbox(engineer, &worktree_dir).as_ref(),
+                )?,
                 0,
             )
         } else if verification_policy.auto_run_tests {
             verification_state.begin_iteration();
             let test_started = Instant::now();
-            let verification_run =
-                run_automatic_verification(&worktree_dir, test_command.as_deref()).with_context(
-                    || {
-                        format!(
-                            "automatic verification failed while running tests in {}",
-                            worktree_dir.display()
-                        )
-                    },
-                )?;
+            let verification_run = run_gate_verification(
+                daemon,
+                engineer,
+                task_id,
+                &worktree_dir,
+                test_command.as_deref(),
+                verification_policy.test_report.as_deref(),
+            )
+            .with_context(|| {
+                format!(
+                    "automatic verification failed while running tests in {}",
+                    worktree_dir.display()
+                )
+            })?;
             let test_duration_ms = test_started.elapsed().as_millis() as u64;
             verification_state.last_test_passed = verification_run.passed;
             verification_state.last_test_output = Some(verification_run.output.clone());
@@ -784,8 +986,15 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
     } else if verification_policy.auto_run_tests {
         verification_state.begin_iteration();
         let test_started = Instant::now();
-        let verification_run = run_automatic_verification(&worktree_dir, test_command.as_deref())
-            .with_context(|| {
+        let verification_run = run_gate_verification(
+            daemon,
+            engineer,
+            task_id,
+            &worktree_dir,
+            test_command.as_deref(),
+            verification_policy.test_report.as_deref(),
+        )
+        .with_context(|| {
             format!(
                 "automatic verification failed while running tests in {}",
                 worktree_dir.display()
@@ -886,9 +1095,30 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
     } else {
         true
     };
+    let acceptance_failures = if has_required_evidence && verification_run.passed {
+        run_task_acceptance(
+            daemon,
+            engineer,
+            task_id,
+            &board_dir,
+            &worktree_dir,
+            &mut verification_state,
+            &mut verification_run,
+        )?
+    } else {
+        Vec::new()
+    };
 
     if !has_required_evidence || !verification_run.passed {
         let verification_results = verification_run.results.clone();
+        let failure_summary = if acceptance_failures.is_empty() {
+            verification_results.failure_summary()
+        } else {
+            format!(
+                "acceptance criteria failed: {}",
+                acceptance_failures.join("; ")
+            )
+        };
         if !verification_run.passed
             && let Some(conn) = &daemon.telemetry_db
         {
@@ -980,10 +1210,13 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
                     }
                 )
             }
+        } else if !acceptance_failures.is_empty() {
+            format!(
+                "Verification failed because the task's acceptance criteria did not pass. Summary: {failure_summary}."
+            )
         } else {
             format!(
-                "Verification failed because the test command did not pass. Summary: {}.",
-                verification_results.failure_summary()
+                "Verification failed because the test command did not pass. Summary: {failure_summary}."
             )
         };
         let engineer_message = verification_fix_message(
@@ -1020,7 +1253,7 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
                     "[daemon] Engineer {engineer} hit verification max iterations on task #{task_id}.\nLatest phase: failed\nAttempts: {}/{}\nSummary: {}\nRecent failures:\n{}\nLatest output:\n```\\n{}\\n```",
                     verification_state.iteration,
                     verification_state.max_iterations,
-                    verification_results.failure_summary(),
+                    failure_summary,
                     structured_failure_details(&verification_results)
                         .iter()
                         .take(8)
@@ -1061,8 +1294,7 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
             } else if has_required_evidence {
                 format!(
                     "verification escalation after {} attempts: {}",
-                    verification_state.iteration,
-                    verification_results.failure_summary()
+                    verification_state.iteration, failure_summary
                 )
             } else {
                 format!(
@@ -1170,6 +1402,21 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
             daemon.record_merge_confidence_scored(&info);
         }
 
+        // With `forge.delivery: pull_request`, the forge does the merge.
+        if daemon.config.team_config.forge.delivers_pull_requests() && !daemon.is_multi_repo {
+            return deliver_pull_request(
+                daemon,
+                engineer,
+                task_id,
+                &board_dir,
+                &task_branch,
+                &worktree_dir,
+                manager_name.as_deref(),
+                &task_title,
+                verification_run.results.clone(),
+            );
+        }
+
         // If override explicitly disables auto-merge, route to manual review
         if auto_merge_override == Some(false) {
             let decision = auto_merge::forced_manual_review_decision(
@@ -1193,7 +1440,8 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
             }
             if let Some(ref manager_name) = manager_name {
                 let msg = format!(
-                    "[{engineer}] Task #{task_id} passed tests. Auto-merge disabled by override — awaiting manual review.\nTitle: {task_title}"
+                    "[{engineer}] Task #{task_id} passed tests. Auto-merge disabled by override — awaiting manual review.\nTitle: {task_title}{}",
+                    acceptance_review_lines(&board_dir, task_id)
                 );
                 daemon.queue_message(engineer, manager_name, &msg)?;
                 daemon.mark_member_working(manager_name);
@@ -1306,8 +1554,9 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
                             if let Some(ref manager_name) = manager_name {
                                 let reason_text = decision.reasons.join("; ");
                                 let msg = format!(
-                                    "[{engineer}] Task #{task_id} passed tests but requires manual review.\nTitle: {task_title}\nConfidence: {confidence:.2}\nReasons: {reason_text}",
-                                    confidence = decision.confidence
+                                    "[{engineer}] Task #{task_id} passed tests but requires manual review.\nTitle: {task_title}\nConfidence: {confidence:.2}\nReasons: {reason_text}{acceptance}",
+                                    confidence = decision.confidence,
+                                    acceptance = acceptance_review_lines(&board_dir, task_id)
                                 );
                                 daemon.queue_message(engineer, manager_name, &msg)?;
                                 daemon.mark_member_working(manager_name);
@@ -1370,16 +1619,13 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
                 }
                 daemon.record_task_manual_merged(task_id, success.mode);
 
-                let board_update_ok = daemon.run_kanban_md_nonfatal(
-                    &[
-                        "move",
-                        &task_id.to_string(),
+                let board_update_ok = daemon.board_update_nonfatal(
+                    BoardStore::open(&board_dir).move_task(
+                        task_id,
                         "done",
-                        "--claim",
-                        engineer,
-                        "--dir",
-                        &board_dir_str,
-                    ],
+                        Some(engineer),
+                        &StatusTransitionAttribution::daemon("daemon.merge.completion"),
+                    ),
                     &format!("move task #{task_id} to done"),
                     manager_name
                         .as_deref()
@@ -1481,15 +1727,9 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
                         daemon.notify_reports_to(manager_name, &escalation)?;
                     }
 
-                    daemon.run_kanban_md_nonfatal(
-                        &[
-                            "edit",
-                            &task_id.to_string(),
-                            "--block",
-                            "merge conflicts after 2 retries",
-                            "--dir",
-                            &board_dir_str,
-                        ],
+                    daemon.board_update_nonfatal(
+                        BoardStore::open(&board_dir)
+                            .block(task_id, "merge conflicts after 2 retries"),
                         &format!("block task #{task_id} after merge conflict retries"),
                         manager_name
                             .as_deref()
@@ -1578,15 +1818,8 @@ pub(crate) fn handle_engineer_completion(daemon: &mut TeamDaemon, engineer: &str
         daemon.notify_reports_to(manager_name, &escalation)?;
     }
 
-    daemon.run_kanban_md_nonfatal(
-        &[
-            "edit",
-            &task_id.to_string(),
-            "--block",
-            "tests failed after 2 retries",
-            "--dir",
-            &board_dir_str,
-        ],
+    daemon.board_update_nonfatal(
+        BoardStore::open(&board_dir).block(task_id, "tests failed after 2 retries"),
         &format!("block task #{task_id} after max test retries"),
         manager_name
             .as_deref()
@@ -2125,6 +2358,7 @@ mod tests {
                 tests_run: Some(true),
                 tests_passed: Some(true),
                 test_results: None,
+                acceptance_results: Vec::new(),
                 artifacts: Vec::new(),
                 outcome: Some("ready_for_review".to_string()),
                 review_blockers: Vec::new(),
@@ -0,0 +1,325 @@
+//! Flaky-test handling in the merge gate.
+//!
+//! With `workflow_policy.flaky_tests.enabled`, the gate skips quarantined
+//! tests through the framework's own skip mechanism. A red run is retried up
+//! to `retries` times when every failing test is outside the branch's diff.
+//! A retry that passes counts each of those tests as flaky, and enough flaky
+//! runs quarantine the test and open a board task to fix it. Green runs move
+//! tests on probation towards release; see [`crate::team::quarantine`].
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::Utc;
+use tracing::warn;
+
+use crate::team::board_store::{BoardStore, NewTask};
+use crate::team::config::FlakyTestPolicy;
+use crate::team::daemon::TeamDaemon;
+use crate::team::daemon::verification::{VerificationRunResult, run_automatic_verification};
+use crate::team::events::TeamEvent;
+use crate::team::quarantine::{self, FlakyOutcome, QuarantineState};
+use crate::team::telemetry_db;
+use crate::team::test_results::{TestFailure, with_skipped_tests};
+
+/// File stems too generic to tie a test to the code it covers.
+const GENERIC_STEMS: &[&str] = &["mod", "lib", "main", "index", "__init__", "tests", "test"];
+
+/// The gate's test command with currently quarantined tests skipped.
+pub(crate) fn gate_test_command(
+    project_root: &Path,
+    board_dir: &Path,
+    policy: &FlakyTestPolicy,
+    test_command: Option<&str>,
+) -> (Option<String>, Vec<String>) {
+    if !policy.enabled {
+        return (test_command.map(str::to_string), Vec::new());
+    }
+    let skipped = match quarantine::load_quarantine(project_root) {
+        Ok(state) => state.skipped_tests(Utc::now(), policy, &quarantine::done_task_ids(board_dir)),
+        Err(error) => {
+            warn!(error = %error, "failed to load test quarantine; running every test");
+            Vec::new()
+        }
+    };
+    if skipped.is_empty() {
+        return (test_command.map(str::to_string), skipped);
+    }
+    let command = with_skipped_tests(test_command.unwrap_or("cargo test"), &skipped);
+    (Some(command), skipped)
+}
+
+/// Run the completion gate for `task_id`, applying the flaky-test policy.
+pub(super) fn run_gate_verification(
+    daemon: &mut TeamDaemon,
+    engineer: &str,
+    task_id: u32,
+    worktree_dir: &Path,
+    test_command: Option<&str>,
+    test_report: Option<&str>,
+) -> Result<VerificationRunResult> {
+    let policy = daemon
+        .config
+        .team_config
+        .workflow_policy
+        .flaky_tests
+        .clone();
+    let sandbox = daemon.gate_sandbox(engineer, worktree_dir);
+    if !policy.enabled {
+        return run_automatic_verification(
+            worktree_dir,
+            test_command,
+            test_report,
+            sandbox.as_ref(),
+        );
+    }
+
+    let board_dir = daemon.board_dir();
+    let (command, skipped) =
+        gate_test_command(daemon.project_root(), &board_dir, &policy, test_command);
+    if !skipped.is_empty() {
+        daemon.record_orchestrator_action(format!(
+            "flaky tests: gate for task #{task_id} skips {} quarantined test(s): {}",
+            skipped.len(),
+            skipped.join(", ")
+        ));
+    }
+    let mut run = run_automatic_verification(
+        worktree_dir,
+        command.as_deref(),
+        test_report,
+        sandbox.as_ref(),
+    )?;
+    let mut flaky_failures = Vec::new();
+    if !run.passed && run.recovery.is_none() && failures_outside_diff(&run) {
+        let failures = run.results.failures.clone();
+        let names: Vec<&str> = failures
+            .iter()
+            .map(|failure| failure.test_name.as_str())
+            .collect();
+        for attempt in 1..=policy.retries {
+            daemon.record_orchestrator_action(format!(
+                "flaky tests: task #{task_id} failed only outside its diff ({}); retry {attempt}/{}",
+                names.join(", "),
+                policy.retries
+            ));
+            let retry = run_automatic_verification(
+                worktree_dir,
+                command.as_deref(),
+                test_report,
+                sandbox.as_ref(),
+            )?;
+            if retry.passed {
+                flaky_failures = failures;
+                run = retry;
+                break;
+            }
+        }
+    }
+
+    let mut state = match quarantine::load_quarantine(daemon.project_root()) {
+        Ok(state) => state,
+        Err(error) => {
+            warn!(error = %error, "failed to load test quarantine; not updating it");
+            return Ok(run);
+        }
+    };
+    let original = state.clone();
+    if !flaky_failures.is_empty() {
+        if let Some(conn) = &daemon.telemetry_db
+            && let Err(error) = telemetry_db::record_test_results(
+                conn,
+                task_id,
+                engineer,
+                &run.results,
+                &flaky_failures,
+            )
+        {
+            warn!(error = %error, "failed to record flaky test results");
+        }
+        for failure in &flaky_failures {
+            record_flaky_failure(daemon, &mut state, &policy, task_id, failure);
+        }
+    }
+    if run.passed {
+        let done = quarantine::done_task_ids(&board_dir);
+        for test in state.record_green_run(Utc::now(), &policy, &done) {
+            let details = format!("{} green gate run(s) on probation", policy.stable_runs);
+            daemon.record_orchestrator_action(format!(
+                "flaky tests: released {test} from quarantine after {details}"
+            ));
+            daemon.emit_event(TeamEvent::test_quarantine_released(&test, &details));
+        }
+    }
+    if state != original
+        && let Err(error) = quarantine::save_quarantine(daemon.project_root(), &state)
+    {
+        warn!(error = %error, "failed to save test quarantine");
+    }
+    Ok(run)
+}
+
+fn record_flaky_failure(
+    daemon: &mut TeamDaemon,
+    state: &mut QuarantineState,
+    policy: &FlakyTestPolicy,
+    task_id: u32,
+    failure: &TestFailure,
+) {
+    let test = failure.test_name.as_str();
+    match state.record_flaky_run(test, task_id, Utc::now(), policy) {
+        FlakyOutcome::Counted(count) => {
+            daemon.record_orchestrator_action(format!(
+                "flaky tests: {test} passed on retry for task #{task_id} (flaky {count}/{})",
+                policy.quarantine_after
+            ));
+        }
+        outcome @ (FlakyOutcome::Quarantined | FlakyOutcome::Requarantined) => {
+            let entry = state.tests.get_mut(test).expect("just quarantined");
+            if entry.fix_task.is_none() {
+                match open_fix_task(&daemon.board_dir(), test, failure, &entry.reason) {
+                    Ok(id) => entry.fix_task = Some(id),
+                    Err(error) => warn!(test, error = %error, "failed to open flaky-test fix task"),
+                }
+            }
+            let verb = if outcome == FlakyOutcome::Quarantined {
+                "quarantined"
+            } else {
+                "quarantined again"
+            };
+            let details = match entry.fix_task {
+                Some(id) => format!("{verb}; fix task #{id}"),
+                None => verb.to_string(),
+            };
+            daemon.record_orchestrator_action(format!(
+                "flaky tests: {test} {details} (task #{task_id})"
+            ));
+            daemon.emit_event(TeamEvent::test_quarantined(test, task_id, &details));
+        }
+    }
+}
+
+fn open_fix_task(board_dir: &Path, test: &str, failure: &TestFailure, reason: &str) -> Result<u32> {
+    let mut body = format!(
+        "`{test}` is quarantined as flaky: it {reason}. The merge gate skips it until this task is done, then runs it on probation.\n"
+    );
+    if let Some(message) = failure.message.as_deref() {
+        body.push_str(&format!("\nLast failure: {message}\n"));
+    }
+    if let Some(location) = failure.location.as_deref() {
+        body.push_str(&format!("Location: {location}\n"));
+    }
+    BoardStore::open(board_dir)
+        .create(&NewTask {
+            title: format!("Fix flaky test {test}"),
+            body,
+            status: Some("todo".to_string()),
+            priority: Some("medium".to_string()),
+            tags: vec!["flaky-test".to_string()],
+            depends_on: Vec::new(),
+        })
+        .map_err(|error| anyhow::anyhow!("{error}"))
+}
+
+/// True when the run failed on named tests and none of them touch the diff.
+fn failures_outside_diff(run: &VerificationRunResult) -> bool {
+    !run.results.failures.is_empty()
+        && run
+            .results
+            .failures
+            .iter()
+            .all(|failure| !failure_touches_diff(failure, &run.file_paths))
+}
+
+fn failure_touches_diff(failure: &TestFailure, changed_files: &[String]) -> bool {
+    if let Some(location) = failure.location.as_deref() {
+        let path = Path::new(location.split(':').next().unwrap_or(location));
+        let location_stem = area_stem(path);
+        for changed in changed_files {
+            let changed = Path::new(changed);
+            let same_dir = path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .is_some_and(|dir| changed.parent() == Some(dir));
+            if same_dir || (location_stem.is_some() && area_stem(changed) == location_stem) {
+                return true;
+            }
+        }
+    }
+
+    let tokens: HashSet<String> = failure
+        .test_name
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .filter(|token| !token.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    changed_files
+        .iter()
+        .filter_map(|changed| area_stem(Path::new(changed)))
+        .any(|stem| tokens.contains(&stem))
+}
+
+/// Lower-cased file stem with test suffixes removed; for generic stems like
+/// `mod.rs` or `index.ts`, the parent directory name stands in.
+fn area_stem(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    let stem = stem
+        .trim_end_matches(".test")
+        .trim_end_matches(".spec")
+        .trim_end_matches("_test")
+        .trim_start_matches("test_")
+        .to_string();
+    if GENERIC_STEMS.contains(&stem.as_str()) {
+        return path
+            .parent()?
+            .file_name()?
+            .to_str()
+            .map(str::to_ascii_lowercase);
+    }
+    Some(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(name: &str, location: Option<&str>) -> TestFailure {
+        TestFailure {
+            test_name: name.to_string(),
+            message: None,
+            location: location.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn failures_in_the_diff_area_are_not_retried() {
+        let changed = vec![
+            "src/team/allocation.rs".to_string(),
+            "web/cart/index.ts".to_string(),
+        ];
+        assert!(failure_touches_diff(
+            &failure("team::allocation::tests::ranks", None),
+            &changed
+        ));
+        assert!(failure_touches_diff(
+            &failure("ui :: cart :: totals", None),
+            &changed
+        ));
+        assert!(failure_touches_diff(
+            &failure("checkout", Some("src/team/allocation_model.rs:10:5")),
+            &changed
+        ));
+        assert!(failure_touches_diff(
+            &failure("example.com/api::TestRetry", Some("allocation_test.go:18")),
+            &changed
+        ));
+        assert!(!failure_touches_diff(
+            &failure(
+                "team::telegram::tests::polls",
+                Some("src/team/telegram.rs:1:1")
+            ),
+            &["src/cli.rs".to_string()]
+        ));
+    }
+}
@@ -11,11 +11,17 @@
 //! one place.
 
 mod completion;
+mod flaky;
 mod git_ops;
 mod lock;
 mod operations;
 
 pub(crate) use completion::handle_engineer_completion;
 pub(crate) use completion::record_merge_test_timing;
+pub(crate) use flaky::gate_test_command;
+pub(crate) use lock::MergeSuccess;
 pub(crate) use lock::{MergeLock, MergeMode, MergeOutcome, infer_merge_mode_from_failure};
-pub(crate) use operations::{RootDirtyState, inspect_root_dirty_state, merge_engineer_branch};
+pub(crate) use operations::{
+    RootDirtyState, advance_trunk_to_commit, inspect_root_dirty_state, merge_engineer_branch,
+    reset_engineer_worktree_to_trunk,
+};
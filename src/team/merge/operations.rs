@@ -258,6 +258,52 @@ pub(crate) fn merge_engineer_branch_into_trunk(
     }))
 }
 
+/// Move trunk from `expected_head` to `new_head`, a descendant built in an
+/// integration worktree. A clean root checkout on trunk is fast-forwarded so
+/// its files follow; otherwise only the ref moves. Either way the update is
+/// refused if trunk no longer points at `expected_head`.
+pub(crate) fn advance_trunk_to_commit(
+    project_root: &Path,
+    trunk_branch: &str,
+    expected_head: &str,
+    new_head: &str,
+) -> Result<MergeMode> {
+    let plan = plan_root_merge(project_root, trunk_branch)?;
+    let args: Vec<String> = match plan.mode {
+        MergeMode::DirectRoot => {
+            let current = run_git_with_context(
+                project_root,
+                &["rev-parse", "HEAD"],
+                &format!("read {trunk_branch} head before fast-forward"),
+            )?;
+            let current = String::from_utf8_lossy(&current.stdout).trim().to_string();
+            if current != expected_head {
+                bail!(
+                    "{trunk_branch} moved from {expected_head} to {current} while the merge train was tested"
+                );
+            }
+            vec!["merge".into(), "--ff-only".into(), new_head.into()]
+        }
+        MergeMode::IsolatedIntegration => vec![
+            "update-ref".into(),
+            format!("refs/heads/{trunk_branch}"),
+            new_head.into(),
+            expected_head.into(),
+        ],
+    };
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let description = format!("advance {trunk_branch} to merge train head {new_head}");
+    let output = run_git_with_context(project_root, &args, &description)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        bail!(
+            "{}",
+            describe_git_failure(project_root, &args, &description, &stderr)
+        );
+    }
+    Ok(plan.mode)
+}
+
 fn plan_root_merge(project_root: &Path, trunk_branch: &str) -> Result<RootMergePlan> {
     let branch = current_worktree_branch(project_root).unwrap_or_else(|_| "HEAD".to_string());
     if branch == trunk_branch {
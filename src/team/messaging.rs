@@ -170,7 +170,16 @@ fn sender_belongs_to_project(project_root: &Path, sender: &str) -> bool {
 
 /// Assign a task to an engineer via their Maildir inbox.
 pub fn assign_task(project_root: &Path, engineer: &str, task: &str) -> Result<String> {
-    let from = effective_sender(project_root, None);
+    assign_task_as(project_root, None, engineer, task)
+}
+
+pub(crate) fn assign_task_as(
+    project_root: &Path,
+    from_override: Option<&str>,
+    engineer: &str,
+    task: &str,
+) -> Result<String> {
+    let from = effective_sender(project_root, from_override);
     let recipient = resolve_member_name(project_root, engineer)?;
 
     let config_path = team_config_path(project_root);
@@ -5,6 +5,9 @@
 //! manages agent lifecycles.
 
 pub mod allocation;
+pub mod allocation_model;
+pub mod api;
+pub mod approval;
 pub mod artifact;
 pub mod auto_merge;
 #[cfg(test)]
@@ -24,6 +27,8 @@ mod messaging;
 pub use messaging::*;
 pub mod board_cmd;
 pub mod board_health;
+pub(crate) mod board_store;
+pub mod budget;
 pub mod capability;
 pub mod checkpoint;
 pub mod comms;
@@ -43,6 +48,8 @@ pub mod errors;
 pub mod estimation;
 pub mod events;
 pub mod failure_patterns;
+pub mod forge;
+pub mod forge_sync;
 pub mod git_cmd;
 pub mod github_feedback;
 pub mod grafana;
@@ -52,6 +59,7 @@ pub mod inbox;
 pub mod inbox_tiered;
 pub mod layout;
 pub mod learnings;
+pub mod learnings_index;
 pub use daemon_mgmt::*;
 mod session;
 pub use session::*;
@@ -65,17 +73,24 @@ pub mod multi_provider;
 pub mod nudge;
 pub mod openclaw;
 pub mod openclaw_contract;
+pub mod operator;
 pub mod parity;
+pub mod pattern_rules;
 pub mod policy;
 pub(crate) mod process_tree;
+pub(crate) mod prometheus;
 pub mod prompt_compose;
 pub mod quality_metrics;
+pub mod quarantine;
 pub mod reload;
+pub mod replay;
 pub mod resolver;
 pub mod retrospective;
 pub mod retry;
 pub mod review;
 pub mod scale;
+pub mod scope_fence;
+pub mod slack;
 pub mod spec_gen;
 pub mod standup;
 pub mod status;
@@ -84,6 +99,7 @@ pub mod supervisory_notice;
 pub mod tact;
 pub mod task_cmd;
 pub mod task_loop;
+pub(crate) mod task_traces;
 pub mod telegram;
 pub mod telemetry_db;
 #[cfg(test)]
@@ -94,6 +110,7 @@ pub mod test_support;
 pub mod validation;
 pub mod verification;
 pub mod watcher;
+pub mod webhooks;
 pub mod workflow;
 pub mod workspace;
 pub mod worktree_health;
@@ -154,13 +171,11 @@ pub(crate) fn orchestrator_ansi_log_path(project_root: &Path) -> PathBuf {
 }
 
 /// Directory containing per-agent PTY log files written by the shim.
-#[allow(dead_code)] // Public API for future shim-mode daemon integration
 pub(crate) fn shim_logs_dir(project_root: &Path) -> PathBuf {
     project_root.join(".batty").join("shim-logs")
 }
 
 /// Path to an individual agent's PTY log file.
-#[allow(dead_code)] // Public API for future shim-mode daemon integration
 pub(crate) fn shim_log_path(project_root: &Path, agent_id: &str) -> PathBuf {
     shim_logs_dir(project_root).join(format!("{agent_id}.pty.log"))
 }
@@ -275,6 +290,33 @@ pub(crate) fn now_unix() -> u64 {
         .as_secs()
 }
 
+/// Login name of the user running this process. On Unix it comes from the
+/// password database rather than `$USER`, which any caller can set.
+#[cfg(unix)]
+pub(crate) fn os_user_name() -> String {
+    let uid = unsafe { libc::getuid() };
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    let mut buf = vec![0 as libc::c_char; 4096];
+    let rc =
+        unsafe { libc::getpwuid_r(uid, &mut passwd, buf.as_mut_ptr(), buf.len(), &mut result) };
+    if rc == 0 && !result.is_null() && !passwd.pw_name.is_null() {
+        let name = unsafe { std::ffi::CStr::from_ptr(passwd.pw_name) };
+        if let Ok(name) = name.to_str()
+            && !name.is_empty()
+        {
+            return name.to_string();
+        }
+    }
+    format!("uid:{uid}")
+}
+
+/// Login name of the user running this process.
+#[cfg(not(unix))]
+pub(crate) fn os_user_name() -> String {
+    std::env::var("USERNAME").unwrap_or_else(|_| "unknown".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
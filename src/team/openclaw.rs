@@ -524,7 +524,7 @@ fn collect_project_events(
 }
 
 impl OpenClawEventSubscription {
-    fn matches(&self, envelope: &OpenClawProjectEventEnvelope) -> bool {
+    pub(crate) fn matches(&self, envelope: &OpenClawProjectEventEnvelope) -> bool {
         if let Some(since_ts) = self.since_ts {
             if envelope.ts < since_ts {
                 return false;
@@ -596,6 +596,44 @@ fn map_team_event_to_openclaw_event(
     })
 }
 
+/// Envelope for one event of `project`, or `None` when the event is outside
+/// the public contract.
+pub(crate) fn project_event_envelope(
+    project: &RegisteredProject,
+    event: &events::TeamEvent,
+) -> Option<OpenClawProjectEventEnvelope> {
+    map_team_event_to_openclaw_event(project, event)
+}
+
+/// The registry entry for `project_root`, or a stand-in named after the team
+/// when the project is not registered.
+pub(crate) fn local_project_identity(project_root: &Path, team_name: &str) -> RegisteredProject {
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let root = canonical(project_root);
+    if let Some(project) = project_registry::list_projects()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|project| canonical(&project.project_root) == root)
+    {
+        return project;
+    }
+    RegisteredProject {
+        project_id: team_name.to_string(),
+        name: team_name.to_string(),
+        aliases: Vec::new(),
+        project_root: project_root.to_path_buf(),
+        board_dir: super::team_config_dir(project_root).join("board"),
+        team_name: team_name.to_string(),
+        session_name: format!("batty-{team_name}"),
+        channel_bindings: Vec::new(),
+        owner: None,
+        tags: Vec::new(),
+        policy_flags: Default::default(),
+        created_at: 0,
+        updated_at: 0,
+    }
+}
+
 fn public_event_contract(event: &events::TeamEvent) -> Option<(OpenClawEventTopic, &'static str)> {
     openclaw_contract::contract_for_internal_event(event)
         .map(|(topic, event_kind)| (legacy_event_topic(topic), event_kind.legacy_event_type()))
@@ -947,7 +985,10 @@ fn load_status_report(project_root: &Path) -> Result<status::TeamStatusJsonRepor
             active_tasks,
             review_queue,
             optional_subsystems: None,
+            pattern_actions: None,
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             members: rows,
         },
     ))
@@ -1067,7 +1108,10 @@ mod tests {
                 failed_test_state: None,
             }],
             optional_subsystems: None,
+            pattern_actions: None,
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             members: Vec::new(),
         }
     }
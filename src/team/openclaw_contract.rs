@@ -1218,7 +1218,10 @@ mod tests {
                 failed_test_state: None,
             }],
             optional_subsystems: None,
+            pattern_actions: None,
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             members: vec![
                 status::TeamStatusRow {
                     name: "eng-1".to_string(),
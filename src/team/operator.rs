@@ -0,0 +1,539 @@
+//! Transport-agnostic operator commands.
+//!
+//! Telegram, Discord, Slack and `batty op` all speak the same grammar: a
+//! command word after a `/` or `$` prefix, then its arguments. Parsing,
+//! argument validation and help text live here; the daemon executes parsed
+//! commands. Every command needs a [`Permission`] level, granted per user ID
+//! in the user role's `channel_config.permissions`. Each invocation is
+//! appended to `.batty/operator-audit.jsonl` with who sent it, where, what it
+//! was, and how it ended.
+
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+
+const AUDIT_LOG_FILE: &str = "operator-audit.jsonl";
+
+/// Access levels, lowest first. Each level may run everything below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    /// Read-only: status, board, logs, health, approvals, help.
+    Viewer,
+    /// Day-to-day control: assign, merge, pause, send, and so on.
+    Operator,
+    /// Team lifecycle and approval decisions: start, stop, grant, deny.
+    Admin,
+}
+
+impl Permission {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum OperatorCommand {
+    Status,
+    Board { filter: Option<String> },
+    Logs { member: String },
+    Health,
+    Assign { engineer: String, task: String },
+    Merge { task_id: u32 },
+    Approve { task_id: u32 },
+    Approvals,
+    Grant { id: u32 },
+    Deny { id: u32, reason: Option<String> },
+    Reauth { backend: String },
+    Kick { member: String },
+    Pause,
+    Resume,
+    Goal { text: String },
+    Task { title: String },
+    Block { task_id: u32, reason: String },
+    Stop { confirm: bool },
+    Start,
+    Help,
+    Send { role: String, message: String },
+}
+
+struct CommandSpec {
+    name: &'static str,
+    usage: &'static str,
+    permission: Permission,
+}
+
+/// The command set, in help order.
+const COMMANDS: &[CommandSpec] = &[
+    spec("status", "status", Permission::Viewer),
+    spec("board", "board [status]", Permission::Viewer),
+    spec("logs", "logs <member>", Permission::Viewer),
+    spec("health", "health", Permission::Viewer),
+    spec("approvals", "approvals", Permission::Viewer),
+    spec("help", "help", Permission::Viewer),
+    spec(
+        "assign",
+        "assign <engineer> <task|id>",
+        Permission::Operator,
+    ),
+    spec("merge", "merge <task>", Permission::Operator),
+    spec("approve", "approve <task>", Permission::Operator),
+    spec("kick", "kick <member>", Permission::Operator),
+    spec("pause", "pause", Permission::Operator),
+    spec("resume", "resume", Permission::Operator),
+    spec("goal", "goal <text>", Permission::Operator),
+    spec("task", "task <title>", Permission::Operator),
+    spec("block", "block <task> <reason>", Permission::Operator),
+    spec("send", "send <role> <message>", Permission::Operator),
+    spec("grant", "grant <approval>", Permission::Admin),
+    spec("deny", "deny <approval> [reason]", Permission::Admin),
+    spec("reauth", "reauth <backend>", Permission::Admin),
+    spec("start", "start", Permission::Admin),
+    spec("stop", "stop confirm", Permission::Admin),
+];
+
+const fn spec(name: &'static str, usage: &'static str, permission: Permission) -> CommandSpec {
+    CommandSpec {
+        name,
+        usage,
+        permission,
+    }
+}
+
+fn command_spec(name: &str) -> &'static CommandSpec {
+    COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .expect("every operator command has a spec")
+}
+
+/// Permission needed to send free text to the team instead of a command.
+pub const MESSAGE_PERMISSION: Permission = Permission::Operator;
+
+impl OperatorCommand {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Board { .. } => "board",
+            Self::Logs { .. } => "logs",
+            Self::Health => "health",
+            Self::Assign { .. } => "assign",
+            Self::Merge { .. } => "merge",
+            Self::Approve { .. } => "approve",
+            Self::Approvals => "approvals",
+            Self::Grant { .. } => "grant",
+            Self::Deny { .. } => "deny",
+            Self::Reauth { .. } => "reauth",
+            Self::Kick { .. } => "kick",
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::Goal { .. } => "goal",
+            Self::Task { .. } => "task",
+            Self::Block { .. } => "block",
+            Self::Stop { .. } => "stop",
+            Self::Start => "start",
+            Self::Help => "help",
+            Self::Send { .. } => "send",
+        }
+    }
+
+    pub(crate) fn permission(&self) -> Permission {
+        command_spec(self.name()).permission
+    }
+}
+
+/// Parse an operator command. Text without a `/` or `$` prefix is a plain
+/// message and yields `Ok(None)`.
+pub(crate) fn parse_operator_command(text: &str) -> Result<Option<OperatorCommand>> {
+    let trimmed = text.trim();
+    let Some(prefix) = trimmed.chars().next().filter(|c| matches!(c, '/' | '$')) else {
+        return Ok(None);
+    };
+    let (word, rest) = trimmed[1..]
+        .split_once(char::is_whitespace)
+        .map(|(word, rest)| (word, rest.trim()))
+        .unwrap_or((&trimmed[1..], ""));
+    // Telegram appends the bot name in groups: `/status@batty_bot`.
+    let word = word.split('@').next().unwrap_or(word).to_ascii_lowercase();
+    let name = match word.as_str() {
+        "go" => "start",
+        other => other,
+    };
+    let Some(spec) = COMMANDS.iter().find(|spec| spec.name == name) else {
+        bail!("Unknown command {prefix}{word}. Send {prefix}help for the command list.");
+    };
+    let usage = || anyhow!("Usage: {prefix}{}", spec.usage);
+
+    let no_args = |command: OperatorCommand| {
+        if rest.is_empty() {
+            Ok(command)
+        } else {
+            Err(usage())
+        }
+    };
+    let required = || {
+        if rest.is_empty() {
+            Err(usage())
+        } else {
+            Ok(rest.to_string())
+        }
+    };
+    let two_part = || {
+        rest.split_once(char::is_whitespace)
+            .map(|(first, second)| (first.trim().to_string(), second.trim().to_string()))
+            .filter(|(first, second)| !first.is_empty() && !second.is_empty())
+            .ok_or_else(usage)
+    };
+    let id = |raw: &str| {
+        raw.trim()
+            .trim_start_matches('#')
+            .parse::<u32>()
+            .map_err(|_| usage())
+    };
+
+    let command = match name {
+        "status" => no_args(OperatorCommand::Status)?,
+        "board" => OperatorCommand::Board {
+            filter: (!rest.is_empty()).then(|| rest.to_string()),
+        },
+        "logs" => OperatorCommand::Logs {
+            member: required()?,
+        },
+        "health" => no_args(OperatorCommand::Health)?,
+        "approvals" => no_args(OperatorCommand::Approvals)?,
+        "help" => no_args(OperatorCommand::Help)?,
+        "assign" => {
+            let (engineer, task) = two_part()?;
+            OperatorCommand::Assign { engineer, task }
+        }
+        "merge" => OperatorCommand::Merge {
+            task_id: id(&required()?)?,
+        },
+        "approve" => OperatorCommand::Approve {
+            task_id: id(&required()?)?,
+        },
+        "kick" => OperatorCommand::Kick {
+            member: required()?,
+        },
+        "pause" => no_args(OperatorCommand::Pause)?,
+        "resume" => no_args(OperatorCommand::Resume)?,
+        "goal" => OperatorCommand::Goal { text: required()? },
+        "task" => OperatorCommand::Task { title: required()? },
+        "block" => {
+            let (task, reason) = two_part()?;
+            OperatorCommand::Block {
+                task_id: id(&task)?,
+                reason,
+            }
+        }
+        "send" => {
+            let (role, message) = two_part()?;
+            OperatorCommand::Send { role, message }
+        }
+        "grant" => OperatorCommand::Grant {
+            id: id(&required()?)?,
+        },
+        "deny" => {
+            let (raw_id, reason) = rest
+                .split_once(char::is_whitespace)
+                .map(|(raw_id, reason)| (raw_id, Some(reason.trim().to_string())))
+                .unwrap_or((rest, None));
+            OperatorCommand::Deny {
+                id: id(raw_id)?,
+                reason,
+            }
+        }
+        "reauth" => OperatorCommand::Reauth {
+            backend: required()?,
+        },
+        "start" => no_args(OperatorCommand::Start)?,
+        "stop" => match rest {
+            "" => OperatorCommand::Stop { confirm: false },
+            "confirm" => OperatorCommand::Stop { confirm: true },
+            _ => return Err(usage()),
+        },
+        _ => unreachable!("command table and parser cover the same names"),
+    };
+    Ok(Some(command))
+}
+
+/// Help text listing every command under its permission level.
+pub(crate) fn help_text(prefix: char) -> String {
+    [Permission::Viewer, Permission::Operator, Permission::Admin]
+        .into_iter()
+        .map(|level| {
+            let commands = COMMANDS
+                .iter()
+                .filter(|spec| spec.permission == level)
+                .map(|spec| format!("{prefix}{}", spec.usage))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{level}: {commands}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// How an audited invocation ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditResult {
+    Ok,
+    Error,
+    Denied,
+    Invalid,
+}
+
+/// One line of the operator audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub ts: u64,
+    /// `telegram`, `discord`, `slack`, or `cli`.
+    pub transport: String,
+    pub user: String,
+    /// Agent session (`BATTY_MEMBER`) a `batty op` call came from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    /// Chat or channel the command arrived in.
+    pub location: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission: Option<Permission>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<Permission>,
+    pub result: AuditResult,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+}
+
+pub fn audit_log_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(AUDIT_LOG_FILE)
+}
+
+/// Append one entry to the audit log. The file is only ever appended to.
+pub fn append_audit(project_root: &Path, entry: &AuditEntry) -> Result<()> {
+    let path = audit_log_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("failed to append to {}", path.display()))
+}
+
+pub fn read_audit(project_root: &Path) -> Result<Vec<AuditEntry>> {
+    let path = audit_log_path(project_root);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("invalid audit entry in {}", path.display()))
+        })
+        .collect()
+}
+
+/// `batty op`: validate the command locally, then run it on the daemon
+/// through the console socket.
+pub fn run_cli(project_root: &Path, words: &[String]) -> Result<()> {
+    let joined = words.join(" ");
+    let text = if joined.starts_with(['/', '$']) {
+        joined
+    } else {
+        format!("/{joined}")
+    };
+    if parse_operator_command(&text)?.is_none() {
+        bail!("empty operator command");
+    }
+    let api_config = super::config::TeamConfig::load(&super::team_config_path(project_root))
+        .map(|config| config.api)
+        .unwrap_or_default();
+    let client = super::api::ApiClient::for_project(project_root, &api_config)?;
+    let member = std::env::var("BATTY_MEMBER")
+        .ok()
+        .filter(|member| !member.trim().is_empty());
+    let reply = client.post(
+        "/v1/operator",
+        &serde_json::json!({
+            "command": text,
+            "user": super::os_user_name(),
+            "member": member,
+        }),
+    )?;
+    if let Some(reply) = reply.get("reply").and_then(|reply| reply.as_str()) {
+        println!("{reply}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> OperatorCommand {
+        parse_operator_command(text).unwrap().unwrap()
+    }
+
+    #[test]
+    fn both_prefixes_share_one_grammar() {
+        assert_eq!(parse("/status"), OperatorCommand::Status);
+        assert_eq!(parse("$status"), OperatorCommand::Status);
+        assert_eq!(parse("/status@batty_bot"), OperatorCommand::Status);
+        assert_eq!(parse("$go"), OperatorCommand::Start);
+        assert_eq!(
+            parse("/board review"),
+            OperatorCommand::Board {
+                filter: Some("review".to_string())
+            }
+        );
+        assert_eq!(
+            parse("$assign eng-1 Task #41: fix flakes"),
+            OperatorCommand::Assign {
+                engineer: "eng-1".to_string(),
+                task: "Task #41: fix flakes".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("/block #41 waiting on CI"),
+            OperatorCommand::Block {
+                task_id: 41,
+                reason: "waiting on CI".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("$send architect Focus on stability"),
+            OperatorCommand::Send {
+                role: "architect".to_string(),
+                message: "Focus on stability".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("$approve #42"),
+            OperatorCommand::Approve { task_id: 42 }
+        );
+        assert_eq!(
+            parse("/deny 7 needs security review"),
+            OperatorCommand::Deny {
+                id: 7,
+                reason: Some("needs security review".to_string()),
+            }
+        );
+        assert_eq!(parse("$grant #3"), OperatorCommand::Grant { id: 3 });
+        assert_eq!(
+            parse("/reauth claude"),
+            OperatorCommand::Reauth {
+                backend: "claude".to_string(),
+            }
+        );
+        assert_eq!(parse("/stop"), OperatorCommand::Stop { confirm: false });
+        assert_eq!(
+            parse("$stop confirm"),
+            OperatorCommand::Stop { confirm: true }
+        );
+        assert_eq!(parse_operator_command("focus on quality").unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_usage_reports_the_usage_line() {
+        for text in [
+            "/assign eng-1",
+            "/logs",
+            "$merge nope",
+            "/goal",
+            "$task",
+            "/block 41",
+            "$grant",
+            "/status now",
+            "/stop please",
+        ] {
+            let error = parse_operator_command(text).unwrap_err().to_string();
+            assert!(error.starts_with("Usage: "), "{text}: {error}");
+        }
+        assert_eq!(
+            parse_operator_command("$merge nope")
+                .unwrap_err()
+                .to_string(),
+            "Usage: $merge <task>"
+        );
+        assert_eq!(
+            parse_operator_command("$nope").unwrap_err().to_string(),
+            "Unknown command $nope. Send $help for the command list."
+        );
+    }
+
+    #[test]
+    fn permissions_rise_from_read_only_to_lifecycle() {
+        assert_eq!(OperatorCommand::Status.permission(), Permission::Viewer);
+        assert_eq!(OperatorCommand::Pause.permission(), Permission::Operator);
+        assert_eq!(
+            OperatorCommand::Stop { confirm: true }.permission(),
+            Permission::Admin
+        );
+        assert_eq!(
+            OperatorCommand::Grant { id: 1 }.permission(),
+            Permission::Admin
+        );
+        assert!(Permission::Admin > Permission::Operator);
+        assert!(Permission::Operator > Permission::Viewer);
+    }
+
+    #[test]
+    fn help_lists_every_command_by_level() {
+        let help = help_text('/');
+        let lines: Vec<&str> = help.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("viewer: /status, /board [status]"));
+        assert!(lines[1].contains("/send <role> <message>"));
+        assert!(lines[2].ends_with("/start, /stop confirm"));
+        for spec in COMMANDS {
+            assert!(help.contains(&format!("/{}", spec.usage)));
+        }
+    }
+
+    #[test]
+    fn audit_log_appends_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let entry = |command: &str, result| AuditEntry {
+            ts: 1,
+            transport: "slack".to_string(),
+            user: "U-ops".to_string(),
+            member: None,
+            location: "C-cmd".to_string(),
+            command: command.to_string(),
+            permission: Some(Permission::Viewer),
+            required: Some(Permission::Admin),
+            result,
+            detail: String::new(),
+        };
+        append_audit(tmp.path(), &entry("$status", AuditResult::Ok)).unwrap();
+        append_audit(tmp.path(), &entry("$stop confirm", AuditResult::Denied)).unwrap();
+
+        let entries = read_audit(tmp.path()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].result, AuditResult::Denied);
+        assert_eq!(entries[1].command, "$stop confirm");
+    }
+}
@@ -569,6 +569,8 @@ overall_parity: 73%
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: std::path::PathBuf::new(),
         }];
 
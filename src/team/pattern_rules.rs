@@ -0,0 +1,566 @@
+//! Declarative failure-pattern rules from `.batty/team_config/patterns.yaml`.
+//!
+//! Each rule matches team events by type, role, task, and an error regex,
+//! counts matches per role (or task) over a sliding window, and fires its
+//! remediation actions once the threshold is reached. The daemon executes the
+//! actions (see `daemon/pattern_actions.rs`); this module only decides when a
+//! rule fires and for whom.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use serde::Deserialize;
+
+use super::events::TeamEvent;
+
+const PATTERNS_FILE: &str = "patterns.yaml";
+/// Event emitted for every action a rule takes; never fed back into rules.
+pub const PATTERN_ACTION_EVENT: &str = "pattern_action_taken";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatternRulesFile {
+    #[serde(default)]
+    pub rules: Vec<PatternRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatternRule {
+    pub name: String,
+    /// Event types to match. Empty matches any failure-relevant event.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Regex over the event's role (member name).
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Regex over the event's task id.
+    #[serde(default)]
+    pub task: Option<String>,
+    /// Regex over the event's error, reason, and details. A `test` named
+    /// group names the test for `pin_flaky_test`.
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default = "default_threshold")]
+    pub threshold: u32,
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    /// Minimum time between firings for the same role or task. Defaults to
+    /// `window_secs`.
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+    #[serde(default)]
+    pub group_by: RuleGrouping,
+    pub actions: Vec<PatternAction>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleGrouping {
+    #[default]
+    Role,
+    Task,
+    Team,
+}
+
+/// Written as a bare name (`bench_engineer`) or, for actions that take an
+/// argument, a one-key map (`switch_model_class: frontier`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawPatternAction")]
+pub enum PatternAction {
+    NotifyManager,
+    NotifyArchitect,
+    BenchEngineer,
+    SplitTask,
+    PinFlakyTest,
+    SwitchModelClass(String),
+    OpenFixupTask,
+}
+
+impl PatternAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotifyManager => "notify_manager",
+            Self::NotifyArchitect => "notify_architect",
+            Self::BenchEngineer => "bench_engineer",
+            Self::SplitTask => "split_task",
+            Self::PinFlakyTest => "pin_flaky_test",
+            Self::SwitchModelClass(_) => "switch_model_class",
+            Self::OpenFixupTask => "open_fixup_task",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawPatternAction {
+    Name(String),
+    WithArgument(BTreeMap<String, String>),
+}
+
+impl TryFrom<RawPatternAction> for PatternAction {
+    type Error = String;
+
+    fn try_from(raw: RawPatternAction) -> std::result::Result<Self, Self::Error> {
+        match raw {
+            RawPatternAction::Name(name) => match name.as_str() {
+                "notify_manager" => Ok(Self::NotifyManager),
+                "notify_architect" => Ok(Self::NotifyArchitect),
+                "bench_engineer" => Ok(Self::BenchEngineer),
+                "split_task" => Ok(Self::SplitTask),
+                "pin_flaky_test" => Ok(Self::PinFlakyTest),
+                "open_fixup_task" => Ok(Self::OpenFixupTask),
+                "switch_model_class" => Err(
+                    "switch_model_class needs a class, e.g. `switch_model_class: frontier`"
+                        .to_string(),
+                ),
+                other => Err(format!("unknown pattern action '{other}'")),
+            },
+            RawPatternAction::WithArgument(map) => {
+                let mut entries = map.into_iter();
+                match (entries.next(), entries.next()) {
+                    (Some((name, class)), None) if name == "switch_model_class" => {
+                        Ok(Self::SwitchModelClass(class))
+                    }
+                    (Some((name, _)), None) => {
+                        Err(format!("pattern action '{name}' takes no argument"))
+                    }
+                    _ => Err("a pattern action map must have exactly one key".to_string()),
+                }
+            }
+        }
+    }
+}
+
+fn default_threshold() -> u32 {
+    3
+}
+
+fn default_window_secs() -> u64 {
+    3600
+}
+
+/// A rule that reached its threshold, with the most recent match's context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTrigger {
+    pub rule: String,
+    pub actions: Vec<PatternAction>,
+    pub count: u32,
+    pub role: Option<String>,
+    pub task: Option<u32>,
+    pub test: Option<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct RuleHit {
+    ts: u64,
+    role: Option<String>,
+    task: Option<String>,
+    text: Option<String>,
+    test: Option<String>,
+}
+
+struct CompiledRule {
+    rule: PatternRule,
+    role: Option<Regex>,
+    task: Option<Regex>,
+    error: Option<Regex>,
+    hits: HashMap<String, VecDeque<RuleHit>>,
+    last_fired: HashMap<String, u64>,
+}
+
+#[derive(Default)]
+pub struct PatternRuleEngine {
+    rules: Vec<CompiledRule>,
+}
+
+pub fn patterns_path(project_root: &Path) -> PathBuf {
+    super::team_config_dir(project_root).join(PATTERNS_FILE)
+}
+
+/// Load and validate `patterns.yaml`; a missing file means no rules.
+pub fn load_rules(project_root: &Path) -> Result<Vec<PatternRule>> {
+    let path = patterns_path(project_root);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let file: PatternRulesFile = if content.trim().is_empty() {
+        PatternRulesFile::default()
+    } else {
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))?
+    };
+    PatternRuleEngine::from_rules(file.rules.clone())
+        .with_context(|| format!("invalid pattern rule in {}", path.display()))?;
+    Ok(file.rules)
+}
+
+impl PatternRuleEngine {
+    pub fn load(project_root: &Path) -> Result<Self> {
+        Self::from_rules(load_rules(project_root)?)
+    }
+
+    pub fn from_rules(rules: Vec<PatternRule>) -> Result<Self> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            validate_rule(&rule, &compiled)?;
+            compiled.push(CompiledRule {
+                role: compile(&rule.name, "role", rule.role.as_deref())?,
+                task: compile(&rule.name, "task", rule.task.as_deref())?,
+                error: compile(&rule.name, "error", rule.error.as_deref())?,
+                rule,
+                hits: HashMap::new(),
+                last_fired: HashMap::new(),
+            });
+        }
+        if let Some(rule) = compiled.iter().find(|rule| {
+            rule.rule.actions.contains(&PatternAction::PinFlakyTest)
+                && !rule
+                    .error
+                    .as_ref()
+                    .is_some_and(|error| error.capture_names().flatten().any(|name| name == "test"))
+        }) {
+            bail!(
+                "rule '{}' uses pin_flaky_test but its error regex has no (?P<test>...) group",
+                rule.rule.name
+            );
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Record `event` against every rule it matches.
+    pub fn observe(&mut self, event: &TeamEvent) {
+        if event.event == PATTERN_ACTION_EVENT {
+            return;
+        }
+        let text = event_text(event);
+        for compiled in &mut self.rules {
+            let rule = &compiled.rule;
+            let type_matches = if rule.events.is_empty() {
+                super::failure_patterns::is_failure_relevant(event)
+            } else {
+                rule.events.contains(&event.event)
+            };
+            if !type_matches
+                || !field_matches(compiled.role.as_ref(), event.role.as_deref())
+                || !field_matches(compiled.task.as_ref(), event.task.as_deref())
+            {
+                continue;
+            }
+            let test = match &compiled.error {
+                Some(error) => {
+                    let Some(captures) = text.as_deref().and_then(|text| error.captures(text))
+                    else {
+                        continue;
+                    };
+                    captures.name("test").map(|test| test.as_str().to_string())
+                }
+                None => None,
+            };
+            let key = match rule.group_by {
+                RuleGrouping::Role => event.role.clone().unwrap_or_default(),
+                RuleGrouping::Task => event.task.clone().unwrap_or_default(),
+                RuleGrouping::Team => String::new(),
+            };
+            let window_start = event.ts.saturating_sub(rule.window_secs);
+            let hits = compiled.hits.entry(key).or_default();
+            hits.push_back(RuleHit {
+                ts: event.ts,
+                role: event.role.clone(),
+                task: event.task.clone(),
+                text: text.clone(),
+                test,
+            });
+            while hits.front().is_some_and(|hit| hit.ts < window_start) {
+                hits.pop_front();
+            }
+        }
+    }
+
+    /// Rules that reached their threshold by `now`. Firing clears the
+    /// group's window, so the next firing needs fresh matches.
+    pub fn take_triggers(&mut self, now: u64) -> Vec<RuleTrigger> {
+        let mut triggers = Vec::new();
+        for compiled in &mut self.rules {
+            let rule = &compiled.rule;
+            let window_start = now.saturating_sub(rule.window_secs);
+            let cooldown = rule.cooldown_secs.unwrap_or(rule.window_secs);
+            let mut keys: Vec<String> = compiled.hits.keys().cloned().collect();
+            keys.sort();
+            for key in keys {
+                let Some(hits) = compiled.hits.get_mut(&key) else {
+                    continue;
+                };
+                hits.retain(|hit| hit.ts >= window_start);
+                if hits.len() < rule.threshold as usize {
+                    continue;
+                }
+                if compiled
+                    .last_fired
+                    .get(&key)
+                    .is_some_and(|fired| now.saturating_sub(*fired) < cooldown)
+                {
+                    continue;
+                }
+                let latest = hits.back().cloned();
+                let count = hits.len() as u32;
+                hits.clear();
+                compiled.last_fired.insert(key, now);
+                let Some(latest) = latest else {
+                    continue;
+                };
+                triggers.push(RuleTrigger {
+                    rule: rule.name.clone(),
+                    actions: rule.actions.clone(),
+                    count,
+                    role: latest.role,
+                    task: latest.task.and_then(|task| task.parse().ok()),
+                    test: latest.test,
+                    last_error: latest.text,
+                });
+            }
+        }
+        triggers
+    }
+}
+
+fn validate_rule(rule: &PatternRule, existing: &[CompiledRule]) -> Result<()> {
+    if rule.name.trim().is_empty() {
+        bail!("pattern rule name must not be empty");
+    }
+    if existing.iter().any(|other| other.rule.name == rule.name) {
+        bail!("duplicate pattern rule '{}'", rule.name);
+    }
+    if rule.actions.is_empty() {
+        bail!("rule '{}' has no actions", rule.name);
+    }
+    if rule.threshold == 0 {
+        bail!("rule '{}' threshold must be > 0", rule.name);
+    }
+    if rule.window_secs == 0 {
+        bail!("rule '{}' window_secs must be > 0", rule.name);
+    }
+    for action in &rule.actions {
+        if let PatternAction::SwitchModelClass(class) = action
+            && !super::prompt_compose::is_known_model_class(class)
+        {
+            bail!(
+                "rule '{}' switches to unknown model_class '{class}' (expected frontier, standard, or fast)",
+                rule.name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn compile(rule: &str, field: &str, pattern: Option<&str>) -> Result<Option<Regex>> {
+    pattern
+        .map(|pattern| {
+            Regex::new(pattern)
+                .with_context(|| format!("rule '{rule}' has an invalid {field} regex '{pattern}'"))
+        })
+        .transpose()
+}
+
+fn field_matches(regex: Option<&Regex>, value: Option<&str>) -> bool {
+    match regex {
+        Some(regex) => value.is_some_and(|value| regex.is_match(value)),
+        None => true,
+    }
+}
+
+fn event_text(event: &TeamEvent) -> Option<String> {
+    let parts: Vec<&str> = [
+        event.error.as_deref(),
+        event.reason.as_deref(),
+        event.details.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!parts.is_empty()).then(|| parts.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(yaml: &str) -> Vec<PatternRule> {
+        serde_yaml::from_str::<PatternRulesFile>(yaml)
+            .unwrap()
+            .rules
+    }
+
+    fn escalation(role: &str, task: &str, reason: &str, ts: u64) -> TeamEvent {
+        let mut event = TeamEvent::task_escalated(role, task, Some(reason));
+        event.ts = ts;
+        event
+    }
+
+    #[test]
+    fn parses_unit_and_parameterised_actions() {
+        let parsed = rules(
+            r#"
+rules:
+  - name: stuck-engineer
+    events: [task_escalated]
+    role: "^eng-"
+    threshold: 2
+    window_secs: 600
+    actions:
+      - notify_manager
+      - switch_model_class: frontier
+      - open_fixup_task
+"#,
+        );
+
+        assert_eq!(parsed[0].group_by, RuleGrouping::Role);
+        assert_eq!(
+            parsed[0].actions,
+            vec![
+                PatternAction::NotifyManager,
+                PatternAction::SwitchModelClass("frontier".to_string()),
+                PatternAction::OpenFixupTask,
+            ]
+        );
+    }
+
+    #[test]
+    fn fires_once_threshold_is_reached_within_the_window_per_role() {
+        let mut engine = PatternRuleEngine::from_rules(rules(
+            r#"
+rules:
+  - name: stuck
+    events: [task_escalated]
+    role: "^eng-"
+    error: "timeout"
+    threshold: 2
+    window_secs: 100
+    actions: [bench_engineer]
+"#,
+        ))
+        .unwrap();
+
+        engine.observe(&escalation("eng-1", "7", "timeout in api", 1_000));
+        engine.observe(&escalation("eng-2", "8", "timeout in db", 1_010));
+        engine.observe(&escalation("eng-1", "9", "compile error", 1_020));
+        engine.observe(&escalation("manager", "9", "timeout", 1_030));
+        assert!(engine.take_triggers(1_040).is_empty());
+
+        engine.observe(&escalation("eng-1", "9", "timeout again", 1_050));
+        let triggers = engine.take_triggers(1_060);
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].role.as_deref(), Some("eng-1"));
+        assert_eq!(triggers[0].task, Some(9));
+        assert_eq!(triggers[0].count, 2);
+
+        // The window was consumed and the cooldown holds off a refire.
+        engine.observe(&escalation("eng-1", "9", "timeout", 1_070));
+        engine.observe(&escalation("eng-1", "9", "timeout", 1_080));
+        assert!(engine.take_triggers(1_090).is_empty());
+        assert_eq!(engine.take_triggers(1_165).len(), 1);
+    }
+
+    #[test]
+    fn hits_outside_the_window_expire() {
+        let mut engine = PatternRuleEngine::from_rules(rules(
+            r#"
+rules:
+  - name: slow
+    threshold: 2
+    window_secs: 60
+    actions: [notify_manager]
+"#,
+        ))
+        .unwrap();
+
+        engine.observe(&escalation("eng-1", "1", "x", 100));
+        engine.observe(&escalation("eng-1", "1", "x", 200));
+        assert!(engine.take_triggers(210).is_empty());
+    }
+
+    #[test]
+    fn captures_test_name_for_pin_flaky_test() {
+        let mut engine = PatternRuleEngine::from_rules(rules(
+            r#"
+rules:
+  - name: flaky
+    group_by: task
+    error: "test (?P<test>[\\w:]+) failed"
+    threshold: 1
+    actions: [pin_flaky_test]
+"#,
+        ))
+        .unwrap();
+
+        engine.observe(&escalation("eng-1", "4", "test api::retries failed", 10));
+        let triggers = engine.take_triggers(11);
+        assert_eq!(triggers[0].test.as_deref(), Some("api::retries"));
+    }
+
+    #[test]
+    fn pattern_action_events_are_ignored() {
+        let mut engine = PatternRuleEngine::from_rules(rules(
+            "rules:\n  - name: any\n    events: [pattern_action_taken]\n    threshold: 1\n    actions: [notify_manager]\n",
+        ))
+        .unwrap();
+
+        engine.observe(&TeamEvent::pattern_action_taken(
+            "any",
+            "notify_manager",
+            None,
+            None,
+            true,
+            "sent",
+        ));
+        assert!(engine.take_triggers(u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let invalid = [
+            "rules:\n  - name: a\n    actions: []\n",
+            "rules:\n  - name: a\n    error: \"(\"\n    actions: [notify_manager]\n",
+            "rules:\n  - name: a\n    actions: [pin_flaky_test]\n",
+            "rules:\n  - name: a\n    actions: [{switch_model_class: huge}]\n",
+            "rules:\n  - name: a\n    actions: [notify_manager]\n  - name: a\n    actions: [notify_manager]\n",
+        ];
+        for yaml in invalid {
+            assert!(
+                PatternRuleEngine::from_rules(rules(yaml)).is_err(),
+                "accepted {yaml}"
+            );
+        }
+    }
+
+    #[test]
+    fn load_rules_reads_team_config_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load_rules(tmp.path()).unwrap().is_empty());
+
+        let path = patterns_path(tmp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "rules:\n  - name: a\n    actions: [open_fixup_task]\n",
+        )
+        .unwrap();
+        assert_eq!(load_rules(tmp.path()).unwrap().len(), 1);
+
+        std::fs::write(&path, "rules:\n  - name: a\n    actions: [reboot]\n").unwrap();
+        assert!(load_rules(tmp.path()).is_err());
+    }
+}
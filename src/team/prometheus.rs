@@ -0,0 +1,694 @@
+//! Prometheus text exposition for live daemon metrics.
+//!
+//! The daemon owns a [`DaemonMetrics`] registry behind a mutex. Counters and
+//! the merge-latency histogram are fed as events are emitted and merges
+//! finish; gauges are replaced wholesale by a [`MetricsSnapshot`] taken from
+//! in-memory daemon state once per poll tick. A background thread answers
+//! `GET /metrics` by rendering the registry, so a scrape never reads
+//! `telemetry.db` and never waits on the poll loop.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+
+use super::config::PrometheusConfig;
+use super::events::TeamEvent;
+
+/// Content type for the Prometheus text format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Upper bounds, in seconds, of the merge latency histogram buckets.
+const MERGE_LATENCY_BUCKETS: &[f64] = &[
+    30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 1800.0, 3600.0, 7200.0,
+];
+
+/// Gauge values for one member.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct MemberMetrics {
+    pub member: String,
+    pub role: String,
+    /// `idle` or `working`.
+    pub state: &'static str,
+    /// [`crate::agent::BackendHealth::as_str`] of the member's backend.
+    pub backend_health: &'static str,
+    pub active_task: Option<u32>,
+    /// Run-scoped usage from the budget ledger.
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// Pending inbox messages per tier; `flat` covers the untiered layout.
+    pub inbox: Vec<(&'static str, usize)>,
+}
+
+/// Gauges sampled from daemon state once per tick.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct MetricsSnapshot {
+    pub members: Vec<MemberMetrics>,
+    pub dispatch_queue: usize,
+    pub merge_queued: usize,
+    pub merge_active: usize,
+    pub merge_awaiting_approval: usize,
+    pub review_queue: usize,
+    /// `(broken, last_run_at)` of the latest main smoke check, if any ran.
+    pub main_smoke: Option<(bool, u64)>,
+    pub run_tokens: u64,
+    pub run_cost_usd: f64,
+    pub poll_cycles: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Counters, histograms, and the latest gauge snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DaemonMetrics {
+    snapshot: MetricsSnapshot,
+    restarts: BTreeMap<String, u64>,
+    stalls: BTreeMap<String, u64>,
+    /// Keyed by (member, kind), where kind is `task` or `review`.
+    escalations: BTreeMap<(String, &'static str), u64>,
+    merges: BTreeMap<&'static str, u64>,
+    merge_latency: Histogram,
+}
+
+impl Default for DaemonMetrics {
+    fn default() -> Self {
+        Self {
+            snapshot: MetricsSnapshot::default(),
+            restarts: BTreeMap::new(),
+            stalls: BTreeMap::new(),
+            escalations: BTreeMap::new(),
+            merges: BTreeMap::new(),
+            merge_latency: Histogram::new(MERGE_LATENCY_BUCKETS),
+        }
+    }
+}
+
+impl DaemonMetrics {
+    /// Bump the counters an emitted event contributes to.
+    pub(crate) fn observe_event(&mut self, event: &TeamEvent) {
+        let member = || event.role.clone().unwrap_or_default();
+        match event.event.as_str() {
+            "agent_restarted" => *self.restarts.entry(member()).or_default() += 1,
+            "stall_detected" => *self.stalls.entry(member()).or_default() += 1,
+            "task_escalated" => *self.escalations.entry((member(), "task")).or_default() += 1,
+            "review_escalated" => *self.escalations.entry((member(), "review")).or_default() += 1,
+            _ => {}
+        }
+    }
+
+    /// Count a finished merge-queue request. Latency is only recorded for
+    /// requests that landed.
+    pub(crate) fn observe_merge(&mut self, outcome: &'static str, queued_for: Duration) {
+        *self.merges.entry(outcome).or_default() += 1;
+        if outcome == "merged" {
+            self.merge_latency.observe(queued_for.as_secs_f64());
+        }
+    }
+
+    pub(crate) fn set_snapshot(&mut self, snapshot: MetricsSnapshot) {
+        self.snapshot = snapshot;
+    }
+
+    /// Render every metric in the Prometheus text format.
+    pub(crate) fn render(&self) -> String {
+        let mut out = String::new();
+        let snapshot = &self.snapshot;
+
+        header(
+            &mut out,
+            "batty_member_state",
+            "gauge",
+            "Current member state; 1 for the state the member is in.",
+        );
+        for member in &snapshot.members {
+            sample(
+                &mut out,
+                "batty_member_state",
+                &[
+                    ("member", &member.member),
+                    ("role", &member.role),
+                    ("state", member.state),
+                ],
+                1.0,
+            );
+        }
+        header(
+            &mut out,
+            "batty_member_backend_healthy",
+            "gauge",
+            "Whether the member's agent backend is healthy; the health label carries the current state.",
+        );
+        for member in &snapshot.members {
+            sample(
+                &mut out,
+                "batty_member_backend_healthy",
+                &[
+                    ("member", &member.member),
+                    ("health", member.backend_health),
+                ],
+                bool_value(member.backend_health == "healthy"),
+            );
+        }
+        header(
+            &mut out,
+            "batty_member_active_task",
+            "gauge",
+            "Board task id the member is working on.",
+        );
+        for member in &snapshot.members {
+            if let Some(task_id) = member.active_task {
+                sample(
+                    &mut out,
+                    "batty_member_active_task",
+                    &[("member", &member.member)],
+                    f64::from(task_id),
+                );
+            }
+        }
+        header(
+            &mut out,
+            "batty_member_tokens",
+            "gauge",
+            "Tokens charged to the member in the current run.",
+        );
+        for member in &snapshot.members {
+            sample(
+                &mut out,
+                "batty_member_tokens",
+                &[("member", &member.member)],
+                member.tokens as f64,
+            );
+        }
+        header(
+            &mut out,
+            "batty_member_cost_usd",
+            "gauge",
+            "Estimated dollars charged to the member in the current run.",
+        );
+        for member in &snapshot.members {
+            sample(
+                &mut out,
+                "batty_member_cost_usd",
+                &[("member", &member.member)],
+                member.cost_usd,
+            );
+        }
+        header(
+            &mut out,
+            "batty_inbox_pending",
+            "gauge",
+            "Undelivered inbox messages per member and tier.",
+        );
+        for member in &snapshot.members {
+            for (tier, count) in &member.inbox {
+                sample(
+                    &mut out,
+                    "batty_inbox_pending",
+                    &[("member", &member.member), ("tier", tier)],
+                    *count as f64,
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "batty_dispatch_queue_depth",
+            "gauge",
+            "Tasks waiting in the dispatch queue.",
+        );
+        sample(
+            &mut out,
+            "batty_dispatch_queue_depth",
+            &[],
+            snapshot.dispatch_queue as f64,
+        );
+        header(
+            &mut out,
+            "batty_merge_queue_depth",
+            "gauge",
+            "Merge requests by queue state.",
+        );
+        for (state, depth) in [
+            ("queued", snapshot.merge_queued),
+            ("merging", snapshot.merge_active),
+            ("awaiting_approval", snapshot.merge_awaiting_approval),
+        ] {
+            sample(
+                &mut out,
+                "batty_merge_queue_depth",
+                &[("state", state)],
+                depth as f64,
+            );
+        }
+        header(
+            &mut out,
+            "batty_review_queue_depth",
+            "gauge",
+            "Tasks currently in review.",
+        );
+        sample(
+            &mut out,
+            "batty_review_queue_depth",
+            &[],
+            snapshot.review_queue as f64,
+        );
+
+        if let Some((broken, last_run_at)) = snapshot.main_smoke {
+            header(
+                &mut out,
+                "batty_main_smoke_broken",
+                "gauge",
+                "Whether the latest main smoke check failed.",
+            );
+            sample(&mut out, "batty_main_smoke_broken", &[], bool_value(broken));
+            header(
+                &mut out,
+                "batty_main_smoke_last_run_timestamp_seconds",
+                "gauge",
+                "Unix time of the latest main smoke check.",
+            );
+            sample(
+                &mut out,
+                "batty_main_smoke_last_run_timestamp_seconds",
+                &[],
+                last_run_at as f64,
+            );
+        }
+
+        header(
+            &mut out,
+            "batty_run_tokens",
+            "gauge",
+            "Tokens charged across the team in the current run.",
+        );
+        sample(
+            &mut out,
+            "batty_run_tokens",
+            &[],
+            snapshot.run_tokens as f64,
+        );
+        header(
+            &mut out,
+            "batty_run_cost_usd",
+            "gauge",
+            "Estimated dollars charged across the team in the current run.",
+        );
+        sample(&mut out, "batty_run_cost_usd", &[], snapshot.run_cost_usd);
+        header(
+            &mut out,
+            "batty_poll_cycles_total",
+            "counter",
+            "Daemon poll cycles since start.",
+        );
+        sample(
+            &mut out,
+            "batty_poll_cycles_total",
+            &[],
+            snapshot.poll_cycles as f64,
+        );
+
+        header(
+            &mut out,
+            "batty_agent_restarts_total",
+            "counter",
+            "Agent restarts since the daemon started.",
+        );
+        for (member, count) in &self.restarts {
+            sample(
+                &mut out,
+                "batty_agent_restarts_total",
+                &[("member", member)],
+                *count as f64,
+            );
+        }
+        header(
+            &mut out,
+            "batty_stalls_total",
+            "counter",
+            "Stalls detected since the daemon started.",
+        );
+        for (member, count) in &self.stalls {
+            sample(
+                &mut out,
+                "batty_stalls_total",
+                &[("member", member)],
+                *count as f64,
+            );
+        }
+        header(
+            &mut out,
+            "batty_escalations_total",
+            "counter",
+            "Task and review escalations since the daemon started.",
+        );
+        for ((member, kind), count) in &self.escalations {
+            sample(
+                &mut out,
+                "batty_escalations_total",
+                &[("member", member), ("kind", kind)],
+                *count as f64,
+            );
+        }
+        header(
+            &mut out,
+            "batty_merges_total",
+            "counter",
+            "Merge-queue requests finished since the daemon started, by outcome.",
+        );
+        for (outcome, count) in &self.merges {
+            sample(
+                &mut out,
+                "batty_merges_total",
+                &[("outcome", outcome)],
+                *count as f64,
+            );
+        }
+
+        let histogram = &self.merge_latency;
+        header(
+            &mut out,
+            "batty_merge_latency_seconds",
+            "histogram",
+            "Time from entering the merge queue to landing on trunk.",
+        );
+        for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+            sample(
+                &mut out,
+                "batty_merge_latency_seconds_bucket",
+                &[("le", &format_value(*bound))],
+                *count as f64,
+            );
+        }
+        sample(
+            &mut out,
+            "batty_merge_latency_seconds_bucket",
+            &[("le", "+Inf")],
+            histogram.count as f64,
+        );
+        sample(
+            &mut out,
+            "batty_merge_latency_seconds_sum",
+            &[],
+            histogram.sum,
+        );
+        sample(
+            &mut out,
+            "batty_merge_latency_seconds_count",
+            &[],
+            histogram.count as f64,
+        );
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (index, (key, label)) in labels.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{key}=\"{}\"", escape_label(label));
+        }
+        out.push('}');
+    }
+    let _ = writeln!(out, " {}", format_value(value));
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value}")
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Daemon-side handle for the `/metrics` listener.
+pub(crate) struct MetricsServer {
+    metrics: Arc<Mutex<DaemonMetrics>>,
+    endpoint: String,
+}
+
+impl MetricsServer {
+    /// Bind `prometheus.listen` and serve scrapes on a background thread.
+    pub(crate) fn start(config: &PrometheusConfig) -> Result<Self> {
+        let metrics = Arc::new(Mutex::new(DaemonMetrics::default()));
+        let listener = TcpListener::bind(&config.listen)
+            .with_context(|| format!("failed to bind Prometheus listener on {}", config.listen))?;
+        let endpoint = format!("http://{}/metrics", listener.local_addr()?);
+        let shared = Arc::clone(&metrics);
+        std::thread::Builder::new()
+            .name("batty-metrics".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => {
+                            if let Err(error) = handle_scrape(stream, &shared) {
+                                warn!(error = %error, "metrics scrape failed");
+                            }
+                        }
+                        Err(error) => warn!(error = %error, "metrics accept failed"),
+                    }
+                }
+            })
+            .context("failed to spawn Prometheus listener thread")?;
+        info!(endpoint = %endpoint, "Prometheus metrics listening");
+        Ok(Self { metrics, endpoint })
+    }
+
+    pub(crate) fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    pub(crate) fn observe_event(&self, event: &TeamEvent) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.observe_event(event);
+        }
+    }
+
+    pub(crate) fn observe_merge(&self, outcome: &'static str, queued_for: Duration) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.observe_merge(outcome, queued_for);
+        }
+    }
+
+    pub(crate) fn set_snapshot(&self, snapshot: MetricsSnapshot) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.set_snapshot(snapshot);
+        }
+    }
+}
+
+/// Answer one connection. Scrapes are small and infrequent, so they are
+/// served inline on the listener thread.
+fn handle_scrape(mut stream: TcpStream, metrics: &Mutex<DaemonMetrics>) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    let (status, content_type, body) = match (method, path.trim_end_matches('/')) {
+        ("GET", "/metrics") => {
+            let body = metrics
+                .lock()
+                .map(|metrics| metrics.render())
+                .unwrap_or_default();
+            ("200 OK", CONTENT_TYPE, body)
+        }
+        (_, "/metrics") => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "use GET\n".to_string(),
+        ),
+        _ => ("404 Not Found", "text/plain", "try /metrics\n".to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn snapshot() -> MetricsSnapshot {
+        MetricsSnapshot {
+            members: vec![MemberMetrics {
+                member: "eng-1".to_string(),
+                role: "engineer".to_string(),
+                state: "working",
+                backend_health: "quota_exhausted",
+                active_task: Some(42),
+                tokens: 1200,
+                cost_usd: 0.25,
+                inbox: vec![("flat", 0), ("priority", 2)],
+            }],
+            dispatch_queue: 3,
+            merge_queued: 1,
+            merge_active: 0,
+            merge_awaiting_approval: 2,
+            review_queue: 4,
+            main_smoke: Some((true, 1_700_000_000)),
+            run_tokens: 1200,
+            run_cost_usd: 0.25,
+            poll_cycles: 9,
+        }
+    }
+
+    #[test]
+    fn render_includes_gauges_from_snapshot() {
+        let mut metrics = DaemonMetrics::default();
+        metrics.set_snapshot(snapshot());
+        let text = metrics.render();
+
+        assert!(text.contains(
+            "batty_member_state{member=\"eng-1\",role=\"engineer\",state=\"working\"} 1\n"
+        ));
+        assert!(text.contains(
+            "batty_member_backend_healthy{member=\"eng-1\",health=\"quota_exhausted\"} 0\n"
+        ));
+        assert!(text.contains("batty_member_active_task{member=\"eng-1\"} 42\n"));
+        assert!(text.contains("batty_member_cost_usd{member=\"eng-1\"} 0.25\n"));
+        assert!(text.contains("batty_inbox_pending{member=\"eng-1\",tier=\"priority\"} 2\n"));
+        assert!(text.contains("batty_dispatch_queue_depth 3\n"));
+        assert!(text.contains("batty_merge_queue_depth{state=\"awaiting_approval\"} 2\n"));
+        assert!(text.contains("batty_review_queue_depth 4\n"));
+        assert!(text.contains("batty_main_smoke_broken 1\n"));
+        assert!(text.contains("# TYPE batty_poll_cycles_total counter\n"));
+    }
+
+    #[test]
+    fn events_and_merges_feed_counters_and_histogram() {
+        let mut metrics = DaemonMetrics::default();
+        metrics.observe_event(&TeamEvent::agent_restarted("eng-1", "7", "stall", 1));
+        metrics.observe_event(&TeamEvent::agent_restarted("eng-1", "7", "stall", 2));
+        metrics.observe_event(&TeamEvent::stall_detected("eng-2", Some(7), 600));
+        metrics.observe_event(&TeamEvent::task_escalated("eng-1", "7", None));
+        metrics.observe_event(&TeamEvent::review_escalated_by_role("manager", "7"));
+        metrics.observe_merge("merged", Duration::from_secs(90));
+        metrics.observe_merge("merged", Duration::from_secs(4000));
+        metrics.observe_merge("conflicted", Duration::from_secs(10));
+        let text = metrics.render();
+
+        assert!(text.contains("batty_agent_restarts_total{member=\"eng-1\"} 2\n"));
+        assert!(text.contains("batty_stalls_total{member=\"eng-2\"} 1\n"));
+        assert!(text.contains("batty_escalations_total{member=\"eng-1\",kind=\"task\"} 1\n"));
+        assert!(text.contains("batty_escalations_total{member=\"manager\",kind=\"review\"} 1\n"));
+        assert!(text.contains("batty_merges_total{outcome=\"conflicted\"} 1\n"));
+        assert!(text.contains("batty_merge_latency_seconds_bucket{le=\"60\"} 0\n"));
+        assert!(text.contains("batty_merge_latency_seconds_bucket{le=\"120\"} 1\n"));
+        assert!(text.contains("batty_merge_latency_seconds_bucket{le=\"7200\"} 2\n"));
+        assert!(text.contains("batty_merge_latency_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("batty_merge_latency_seconds_sum 4090\n"));
+        assert!(text.contains("batty_merge_latency_seconds_count 2\n"));
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        let mut out = String::new();
+        sample(&mut out, "m", &[("a", "x\"y\\z\nw")], 1.5);
+        assert_eq!(out, "m{a=\"x\\\"y\\\\z\\nw\"} 1.5\n");
+    }
+
+    #[test]
+    fn server_answers_scrapes_and_rejects_other_paths() {
+        let server = MetricsServer::start(&PrometheusConfig {
+            enabled: true,
+            listen: "127.0.0.1:0".to_string(),
+        })
+        .unwrap();
+        server.set_snapshot(snapshot());
+        let addr = server
+            .endpoint()
+            .trim_start_matches("http://")
+            .trim_end_matches("/metrics")
+            .to_string();
+
+        let get = |request: &str| {
+            let mut stream = TcpStream::connect(&addr).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let response = get("GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(response.contains("batty_dispatch_queue_depth 3\n"));
+
+        let response = get("GET /other HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404"));
+        let response = get("POST /metrics HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405"));
+    }
+}
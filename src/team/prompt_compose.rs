@@ -153,6 +153,10 @@ fn load_posture(name: &str) -> Option<&'static str> {
     }
 }
 
+pub(crate) fn is_known_model_class(name: &str) -> bool {
+    load_model_class(name).is_some()
+}
+
 fn load_model_class(name: &str) -> Option<&'static str> {
     match normalize_value(name).as_str() {
         "frontier" => Some(MODEL_CLASS_FRONTIER),
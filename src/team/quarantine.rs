@@ -0,0 +1,359 @@
+//! Tests quarantined as flaky, persisted in `.batty/quarantine.json`.
+//!
+//! Entries come from two places: the merge gate, which quarantines a test
+//! after `workflow_policy.flaky_tests.quarantine_after` runs where it failed
+//! without a related diff and then passed on retry, and the `pin_flaky_test`
+//! pattern-rule action. A quarantined test is skipped by the gate until its
+//! quarantine period ends or its fix task is done; it then runs on probation
+//! and is released after `stable_runs` green gate runs. Failing flakily on
+//! probation puts it straight back into quarantine.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::config::FlakyTestPolicy;
+
+const QUARANTINE_FILE: &str = "quarantine.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineState {
+    #[serde(default)]
+    pub tests: BTreeMap<String, QuarantineEntry>,
+    /// Flaky-retry counts for tests that are not (yet) quarantined.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub flaky_runs: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub since: String,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<u32>,
+    #[serde(default)]
+    pub flaky_runs: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix_task: Option<u32>,
+    /// Green gate runs since the test came off quarantine onto probation.
+    #[serde(default)]
+    pub stable_runs: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuarantineStatus {
+    /// Skipped by the merge gate.
+    Quarantined,
+    /// Runs in the merge gate again; released after enough green runs.
+    Probation,
+}
+
+impl QuarantineStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Quarantined => "quarantined",
+            Self::Probation => "probation",
+        }
+    }
+}
+
+/// What [`record_flaky_run`] did with a test that passed on retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlakyOutcome {
+    /// Counted; the test has now been flaky this many times.
+    Counted(u32),
+    /// Newly quarantined.
+    Quarantined,
+    /// Flaky again while on probation, so quarantined again.
+    Requarantined,
+}
+
+impl QuarantineEntry {
+    pub fn status(
+        &self,
+        now: DateTime<Utc>,
+        policy: &FlakyTestPolicy,
+        done_tasks: &HashSet<u32>,
+    ) -> QuarantineStatus {
+        let fixed = self.fix_task.is_some_and(|id| done_tasks.contains(&id));
+        let expired = DateTime::parse_from_rfc3339(&self.since)
+            .map(|since| {
+                now.signed_duration_since(since.with_timezone(&Utc))
+                    .num_seconds()
+                    >= policy.quarantine_secs as i64
+            })
+            .unwrap_or(true);
+        if fixed || expired {
+            QuarantineStatus::Probation
+        } else {
+            QuarantineStatus::Quarantined
+        }
+    }
+}
+
+impl QuarantineState {
+    /// Tests the merge gate should skip right now.
+    pub fn skipped_tests(
+        &self,
+        now: DateTime<Utc>,
+        policy: &FlakyTestPolicy,
+        done_tasks: &HashSet<u32>,
+    ) -> Vec<String> {
+        self.tests
+            .iter()
+            .filter(|(_, entry)| {
+                entry.status(now, policy, done_tasks) == QuarantineStatus::Quarantined
+            })
+            .map(|(test, _)| test.clone())
+            .collect()
+    }
+
+    /// Count a run where `test` failed without a related diff and then passed
+    /// on retry, quarantining it once it reaches the policy threshold.
+    pub fn record_flaky_run(
+        &mut self,
+        test: &str,
+        task: u32,
+        now: DateTime<Utc>,
+        policy: &FlakyTestPolicy,
+    ) -> FlakyOutcome {
+        if let Some(entry) = self.tests.get_mut(test) {
+            entry.flaky_runs += 1;
+            entry.stable_runs = 0;
+            entry.since = now.to_rfc3339();
+            entry.reason = format!("flaky again on probation (task #{task})");
+            entry.task = Some(task);
+            // The old fix task did not fix it; a done one would put the test
+            // straight back on probation, so the caller opens a new one.
+            entry.fix_task = None;
+            return FlakyOutcome::Requarantined;
+        }
+        let count = self.flaky_runs.entry(test.to_string()).or_default();
+        *count += 1;
+        let count = *count;
+        if count < policy.quarantine_after.max(1) {
+            return FlakyOutcome::Counted(count);
+        }
+        self.flaky_runs.remove(test);
+        self.tests.insert(
+            test.to_string(),
+            QuarantineEntry {
+                since: now.to_rfc3339(),
+                reason: format!(
+                    "failed without a related diff then passed on retry {count} time(s)"
+                ),
+                task: Some(task),
+                flaky_runs: count,
+                fix_task: None,
+                stable_runs: 0,
+            },
+        );
+        FlakyOutcome::Quarantined
+    }
+
+    /// Count a green gate run for every test on probation and release the ones
+    /// that have now been stable for `policy.stable_runs` runs.
+    pub fn record_green_run(
+        &mut self,
+        now: DateTime<Utc>,
+        policy: &FlakyTestPolicy,
+        done_tasks: &HashSet<u32>,
+    ) -> Vec<String> {
+        let mut released = Vec::new();
+        for (test, entry) in &mut self.tests {
+            if entry.status(now, policy, done_tasks) != QuarantineStatus::Probation {
+                continue;
+            }
+            entry.stable_runs += 1;
+            if entry.stable_runs >= policy.stable_runs {
+                released.push(test.clone());
+            }
+        }
+        for test in &released {
+            self.tests.remove(test);
+        }
+        released
+    }
+}
+
+pub fn quarantine_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(QUARANTINE_FILE)
+}
+
+pub fn load_quarantine(project_root: &Path) -> Result<QuarantineState> {
+    let path = quarantine_path(project_root);
+    if !path.exists() {
+        return Ok(QuarantineState::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn save_quarantine(project_root: &Path, state: &QuarantineState) -> Result<()> {
+    let path = quarantine_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    super::board_store::write_atomic(&path, &serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Pin `test` as flaky. Returns `false` when it was already pinned.
+pub fn pin_test(project_root: &Path, test: &str, reason: &str, task: Option<u32>) -> Result<bool> {
+    let mut state = load_quarantine(project_root)?;
+    if state.tests.contains_key(test) {
+        return Ok(false);
+    }
+    let flaky_runs = state.flaky_runs.remove(test).unwrap_or_default();
+    state.tests.insert(
+        test.to_string(),
+        QuarantineEntry {
+            since: Utc::now().to_rfc3339(),
+            reason: reason.to_string(),
+            task,
+            flaky_runs,
+            fix_task: None,
+            stable_runs: 0,
+        },
+    );
+    save_quarantine(project_root, &state)?;
+    Ok(true)
+}
+
+/// Ids of board tasks that are done or archived, for fix-task checks.
+pub fn done_task_ids(board_dir: &Path) -> HashSet<u32> {
+    let mut done = HashSet::new();
+    for dir in [board_dir.join("tasks"), board_dir.join("archive")] {
+        if let Ok(tasks) = crate::task::load_tasks_from_dir(&dir) {
+            done.extend(
+                tasks
+                    .into_iter()
+                    .filter(|task| matches!(task.status.as_str(), "done" | "archived"))
+                    .map(|task| task.id),
+            );
+        }
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> FlakyTestPolicy {
+        FlakyTestPolicy {
+            enabled: true,
+            quarantine_after: 2,
+            quarantine_secs: 3600,
+            stable_runs: 2,
+            ..FlakyTestPolicy::default()
+        }
+    }
+
+    #[test]
+    fn pin_test_records_once() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        assert!(pin_test(tmp.path(), "api::retries", "rule flaky-api", Some(7)).unwrap());
+        assert!(!pin_test(tmp.path(), "api::retries", "again", None).unwrap());
+
+        let state = load_quarantine(tmp.path()).unwrap();
+        let entry = &state.tests["api::retries"];
+        assert_eq!(entry.reason, "rule flaky-api");
+        assert_eq!(entry.task, Some(7));
+    }
+
+    #[test]
+    fn flaky_runs_quarantine_then_expire_through_probation() {
+        let mut state = QuarantineState::default();
+        let now = Utc::now();
+        let none = HashSet::new();
+
+        assert_eq!(
+            state.record_flaky_run("api::retries", 4, now, &policy()),
+            FlakyOutcome::Counted(1)
+        );
+        assert_eq!(
+            state.record_flaky_run("api::retries", 5, now, &policy()),
+            FlakyOutcome::Quarantined
+        );
+        assert!(state.flaky_runs.is_empty());
+        assert_eq!(
+            state.skipped_tests(now, &policy(), &none),
+            vec!["api::retries".to_string()]
+        );
+
+        // Still quarantined: green runs do not count towards release.
+        assert!(state.record_green_run(now, &policy(), &none).is_empty());
+        assert_eq!(state.tests["api::retries"].stable_runs, 0);
+
+        let later = now + chrono::Duration::hours(2);
+        assert!(state.skipped_tests(later, &policy(), &none).is_empty());
+        assert!(state.record_green_run(later, &policy(), &none).is_empty());
+        assert_eq!(
+            state.record_flaky_run("api::retries", 6, later, &policy()),
+            FlakyOutcome::Requarantined
+        );
+        assert_eq!(state.tests["api::retries"].stable_runs, 0);
+        assert_eq!(state.skipped_tests(later, &policy(), &none).len(), 1);
+
+        let much_later = later + chrono::Duration::hours(2);
+        assert!(
+            state
+                .record_green_run(much_later, &policy(), &none)
+                .is_empty()
+        );
+        assert_eq!(
+            state.record_green_run(much_later, &policy(), &none),
+            vec!["api::retries".to_string()]
+        );
+        assert!(state.tests.is_empty());
+    }
+
+    #[test]
+    fn done_fix_task_moves_test_to_probation() {
+        let mut state = QuarantineState::default();
+        let now = Utc::now();
+        state.record_flaky_run("ui::menu", 1, now, &policy());
+        state.record_flaky_run("ui::menu", 1, now, &policy());
+        state.tests.get_mut("ui::menu").unwrap().fix_task = Some(30);
+
+        assert_eq!(
+            state.skipped_tests(now, &policy(), &HashSet::new()).len(),
+            1
+        );
+        let done = HashSet::from([30]);
+        assert!(state.skipped_tests(now, &policy(), &done).is_empty());
+        assert_eq!(
+            state.tests["ui::menu"].status(now, &policy(), &done),
+            QuarantineStatus::Probation
+        );
+    }
+
+    #[test]
+    fn requarantine_drops_the_done_fix_task() {
+        let mut state = QuarantineState::default();
+        let now = Utc::now();
+        state.record_flaky_run("ui::menu", 1, now, &policy());
+        state.record_flaky_run("ui::menu", 1, now, &policy());
+        state.tests.get_mut("ui::menu").unwrap().fix_task = Some(30);
+        let done = HashSet::from([30]);
+        assert!(state.skipped_tests(now, &policy(), &done).is_empty());
+
+        assert_eq!(
+            state.record_flaky_run("ui::menu", 2, now, &policy()),
+            FlakyOutcome::Requarantined
+        );
+
+        assert_eq!(state.tests["ui::menu"].fix_task, None);
+        assert_eq!(
+            state.skipped_tests(now, &policy(), &done),
+            vec!["ui::menu".to_string()]
+        );
+    }
+}
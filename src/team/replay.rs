@@ -0,0 +1,905 @@
+//! Deterministic incident replay for `batty replay <run-export>`.
+//!
+//! A run export (see [`super::export_run`]) carries the team config, the
+//! board as it stood at export time, and the recorded `events.jsonl`. Replay
+//! rewinds the board to its state before the first recorded event, builds a
+//! daemon in a scratch project under `<export>/replay/`, backs every member
+//! with a [`FakeShim`], and walks the recording on a virtual clock: recorded
+//! shim-originated events (completions, deaths, context exhaustion, backend
+//! blocks) are re-emitted by the fakes at their recorded offsets while the
+//! daemon ticks in between. The daemon's own decisions — dispatches, nudges,
+//! restarts, merges — are then diffed against the decisions that were
+//! actually recorded.
+//!
+//! Replay reproduces orchestration, not agent work: fakes never write code,
+//! so merge outcomes reflect the replayed board and verification rather than
+//! the original branches.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use serde_yaml::Value;
+
+use super::config::{RoleType, TeamConfig};
+use super::daemon::dispatch::parse_assignment_task_id;
+use super::daemon::{DaemonConfig, TeamDaemon};
+use super::events::{TeamEvent, read_events};
+use super::hierarchy::{MemberInstance, resolve_hierarchy};
+use super::retrospective::format_duration;
+use super::standup::MemberState;
+use super::task_cmd::{set_optional_string, update_task_frontmatter};
+use super::{TEAM_CONFIG_FILE, team_config_dir, team_events_path};
+use crate::shim::fake::{FakeShim, ShimBehavior};
+use crate::shim::protocol::{Event, ShimState};
+use crate::task::{find_task_path_by_id, load_tasks_from_dir};
+
+/// Default virtual seconds between daemon ticks.
+pub const DEFAULT_REPLAY_TICK_SECS: u64 = 30;
+
+/// Subdirectory of the export that holds the scratch replay project.
+const REPLAY_DIR: &str = "replay";
+
+/// Knobs for a replay run.
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// Virtual seconds between daemon ticks while no recorded event is due.
+    pub tick_secs: u64,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            tick_secs: DEFAULT_REPLAY_TICK_SECS,
+        }
+    }
+}
+
+/// Daemon decision categories compared between recording and replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionKind {
+    Dispatch,
+    Nudge,
+    Restart,
+    Merge,
+}
+
+impl DecisionKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Dispatch => "dispatch",
+            Self::Nudge => "nudge",
+            Self::Restart => "restart",
+            Self::Merge => "merge",
+        }
+    }
+
+    fn classify(event: &str) -> Option<Self> {
+        match event {
+            "task_assigned" => Some(Self::Dispatch),
+            "review_nudge_sent" | "narration_nudged" => Some(Self::Nudge),
+            "agent_restarted" | "pane_respawned" | "narration_restart" => Some(Self::Restart),
+            "task_auto_merged" | "task_manual_merged" | "task_merge_failed" => Some(Self::Merge),
+            _ => None,
+        }
+    }
+}
+
+/// A single daemon decision, positioned relative to the start of the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Decision {
+    pub kind: DecisionKind,
+    pub event: String,
+    pub role: Option<String>,
+    pub task: Option<String>,
+    pub offset_secs: u64,
+}
+
+impl Decision {
+    fn from_event(event: &TeamEvent, offset_secs: u64) -> Option<Self> {
+        let kind = DecisionKind::classify(&event.event)?;
+        Some(Self {
+            kind,
+            event: event.event.clone(),
+            role: event.role.clone(),
+            task: event_task_id(event)
+                .map(|id| id.to_string())
+                .or_else(|| event.task.clone()),
+            offset_secs,
+        })
+    }
+
+    fn same_decision(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.event == other.event
+            && self.role == other.role
+            && self.task == other.task
+    }
+
+    fn describe(&self) -> String {
+        let mut line = format!(
+            "+{:<8} {:<8} {}",
+            format_duration(self.offset_secs),
+            self.kind.label(),
+            self.event
+        );
+        if let Some(role) = self.role.as_deref() {
+            line.push_str(&format!(" {role}"));
+        }
+        if let Some(task) = self.task.as_deref() {
+            match task.parse::<u32>() {
+                Ok(id) => line.push_str(&format!(" #{id}")),
+                Err(_) => line.push_str(&format!(" \"{task}\"")),
+            }
+        }
+        line
+    }
+}
+
+/// A recorded decision the replay reproduced, with its timing drift.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedDecision {
+    pub recorded: Decision,
+    pub replayed: Decision,
+    pub drift_secs: i64,
+}
+
+/// Outcome of `batty replay`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub export_dir: PathBuf,
+    pub workspace: PathBuf,
+    pub duration_secs: u64,
+    pub ticks: u64,
+    pub shim_events: usize,
+    pub matched: Vec<MatchedDecision>,
+    /// Recorded decisions the replayed daemon did not make.
+    pub missing: Vec<Decision>,
+    /// Decisions the replayed daemon made that were not recorded.
+    pub extra: Vec<Decision>,
+}
+
+impl ReplayReport {
+    /// True when the replayed daemon made exactly the recorded decisions.
+    pub fn is_faithful(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+
+    /// Human-readable summary printed by `batty replay`.
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "Replayed {} shim event(s) over {} of recorded time ({} ticks)\nWorkspace: {}\n",
+            self.shim_events,
+            format_duration(self.duration_secs),
+            self.ticks,
+            self.workspace.display()
+        );
+
+        let mut counts: BTreeMap<DecisionKind, (usize, usize)> = BTreeMap::new();
+        for matched in &self.matched {
+            let entry = counts.entry(matched.recorded.kind).or_default();
+            entry.0 += 1;
+            entry.1 += 1;
+        }
+        for decision in &self.missing {
+            counts.entry(decision.kind).or_default().0 += 1;
+        }
+        for decision in &self.extra {
+            counts.entry(decision.kind).or_default().1 += 1;
+        }
+        out.push_str("\nDecision    recorded  replayed\n");
+        for (kind, (recorded, replayed)) in &counts {
+            out.push_str(&format!(
+                "{:<10}  {:>8}  {:>8}\n",
+                kind.label(),
+                recorded,
+                replayed
+            ));
+        }
+
+        if !self.missing.is_empty() {
+            out.push_str("\nRecorded but not replayed:\n");
+            for decision in &self.missing {
+                out.push_str(&format!("  {}\n", decision.describe()));
+            }
+        }
+        if !self.extra.is_empty() {
+            out.push_str("\nReplayed but not recorded:\n");
+            for decision in &self.extra {
+                out.push_str(&format!("  {}\n", decision.describe()));
+            }
+        }
+
+        if self.is_faithful() {
+            out.push_str(&format!(
+                "\nReplay matches the recording: {} decision(s).\n",
+                self.matched.len()
+            ));
+        } else {
+            out.push_str(&format!(
+                "\nReplay diverged: {} matched, {} missing, {} extra.\n",
+                self.matched.len(),
+                self.missing.len(),
+                self.extra.len()
+            ));
+        }
+        out
+    }
+}
+
+/// A recorded shim-originated event, due at `ts`.
+#[derive(Debug, Clone)]
+struct Stimulus {
+    ts: u64,
+    member: String,
+    event: Event,
+}
+
+type FakeShims = Rc<RefCell<HashMap<String, FakeShim>>>;
+
+/// Replay a `batty export-run` directory and diff the decisions.
+pub fn replay_run(export_dir: &Path, options: &ReplayOptions) -> Result<ReplayReport> {
+    let config_path = export_dir.join(TEAM_CONFIG_FILE);
+    if !config_path.is_file() {
+        bail!(
+            "{} is not a run export: missing {}",
+            export_dir.display(),
+            TEAM_CONFIG_FILE
+        );
+    }
+    let recorded_events = read_events(&export_dir.join("events.jsonl"))?;
+    if recorded_events.is_empty() {
+        bail!(
+            "no recorded events in {}",
+            export_dir.join("events.jsonl").display()
+        );
+    }
+    let start_ts = recorded_events
+        .iter()
+        .map(|event| event.ts)
+        .min()
+        .unwrap_or(0);
+    let end_ts = recorded_events
+        .iter()
+        .map(|event| event.ts)
+        .max()
+        .unwrap_or(0);
+
+    let team_config = replay_team_config(&config_path)?;
+    let members = resolve_hierarchy(&team_config)?;
+    let workspace = prepare_workspace(export_dir, &team_config)?;
+    let in_flight = rewind_board(&workspace, &recorded_events, &members)?;
+
+    let stimuli = shim_stimuli(&recorded_events, &members);
+    let recorded = recorded_events
+        .iter()
+        .filter_map(|event| Decision::from_event(event, event.ts - start_ts))
+        .collect::<Vec<_>>();
+
+    let mut run = ReplayRun::new(&workspace, team_config, members, &in_flight)?;
+    let tick_secs = options.tick_secs.max(1);
+    let mut clock = start_ts;
+    for stimulus in &stimuli {
+        while clock + tick_secs < stimulus.ts {
+            run.step(tick_secs)?;
+            clock += tick_secs;
+        }
+        run.step(stimulus.ts - clock)?;
+        clock = stimulus.ts;
+        run.deliver(stimulus)?;
+        run.step(0)?;
+    }
+    while clock + tick_secs <= end_ts {
+        run.step(tick_secs)?;
+        clock += tick_secs;
+    }
+    run.step(end_ts - clock)?;
+
+    let (matched, missing, extra) = diff_decisions(&recorded, &run.decisions);
+    Ok(ReplayReport {
+        export_dir: export_dir.to_path_buf(),
+        workspace,
+        duration_secs: end_ts - start_ts,
+        ticks: run.ticks,
+        shim_events: stimuli.len(),
+        matched,
+        missing,
+        extra,
+    })
+}
+
+/// Load the recorded config, forced onto the shim runtime (fakes are the
+/// only agents replay has) with every outward-facing integration removed.
+fn replay_team_config(config_path: &Path) -> Result<TeamConfig> {
+    let mut team_config = TeamConfig::load(config_path)?;
+    team_config.use_shim = true;
+    team_config.use_sdk_mode = false;
+    team_config.orchestrator_pane = false;
+    team_config.api = Default::default();
+    team_config.forge = Default::default();
+    for role in &mut team_config.roles {
+        role.channel = None;
+        role.channel_config = None;
+    }
+    Ok(team_config)
+}
+
+fn prepare_workspace(export_dir: &Path, team_config: &TeamConfig) -> Result<PathBuf> {
+    let workspace = export_dir.join(REPLAY_DIR);
+    if workspace.exists() {
+        std::fs::remove_dir_all(&workspace)
+            .with_context(|| format!("failed to clear {}", workspace.display()))?;
+    }
+    let config_dir = team_config_dir(&workspace);
+    std::fs::create_dir_all(&config_dir)
+        .with_context(|| format!("failed to create {}", config_dir.display()))?;
+    std::fs::copy(
+        export_dir.join(TEAM_CONFIG_FILE),
+        config_dir.join(TEAM_CONFIG_FILE),
+    )
+    .context("failed to copy team config into replay workspace")?;
+    super::init::copy_dir_if_exists(
+        &export_dir.join("board").join("tasks"),
+        &config_dir.join("board").join("tasks"),
+    )?;
+    std::fs::write(workspace.join(".gitignore"), ".batty/\n")
+        .context("failed to write replay .gitignore")?;
+
+    git(&workspace, &["init", "-q", "-b", &team_config.trunk_branch])?;
+    git(&workspace, &["add", ".gitignore"])?;
+    git(
+        &workspace,
+        &[
+            "-c",
+            "user.name=Batty Replay",
+            "-c",
+            "user.email=replay@batty.local",
+            "commit",
+            "-q",
+            "-m",
+            "replay baseline",
+        ],
+    )?;
+    Ok(workspace)
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("failed to run git {args:?} in {}", dir.display()))?;
+    if !output.status.success() {
+        bail!(
+            "git {args:?} failed in {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Task id an event refers to: a bare id, or the `Task #N` an assignment
+/// message names.
+fn event_task_id(event: &TeamEvent) -> Option<u32> {
+    let task = event.task.as_deref()?.trim();
+    task.trim_start_matches('#')
+        .parse()
+        .ok()
+        .or_else(|| parse_assignment_task_id(task))
+}
+
+/// Put every task the recording touches back to where it stood before its
+/// first event. Tasks first seen being assigned go back to `todo`; tasks
+/// first seen in an engineer's hands (already in flight when the recording
+/// starts) are claimed by that engineer. Returns the in-flight work as
+/// `engineer -> task id`.
+fn rewind_board(
+    workspace: &Path,
+    events: &[TeamEvent],
+    members: &[MemberInstance],
+) -> Result<HashMap<String, u32>> {
+    let tasks_dir = team_config_dir(workspace).join("board").join("tasks");
+    let known = load_tasks_from_dir(&tasks_dir)
+        .map(|tasks| tasks.into_iter().map(|task| task.id).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let mut first_events: BTreeMap<u32, &TeamEvent> = BTreeMap::new();
+    let mut ordered = events.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|event| event.ts);
+    for event in ordered {
+        if let Some(id) = event_task_id(event) {
+            first_events.entry(id).or_insert(event);
+        }
+    }
+
+    let mut in_flight = HashMap::new();
+    for (id, event) in first_events {
+        if !known.contains(&id) {
+            continue;
+        }
+        let engineer = event.role.as_deref().filter(|role| {
+            members
+                .iter()
+                .any(|member| member.name == *role && member.role_type == RoleType::Engineer)
+        });
+        let (status, claimed_by) = match (event.event.as_str(), engineer) {
+            ("task_assigned", _) => ("todo", None),
+            (_, Some(engineer)) => {
+                if in_flight.contains_key(engineer) {
+                    continue;
+                }
+                in_flight.insert(engineer.to_string(), id);
+                ("in-progress", Some(engineer))
+            }
+            _ => continue,
+        };
+        let task_path = find_task_path_by_id(&tasks_dir, id)?;
+        update_task_frontmatter(&task_path, |mapping| {
+            mapping.insert(
+                Value::String("status".to_string()),
+                Value::String(status.to_string()),
+            );
+            set_optional_string(mapping, "claimed_by", claimed_by);
+        })?;
+    }
+    Ok(in_flight)
+}
+
+/// Translate recorded daemon events back into the shim events that caused
+/// them. Only members replay runs a fake for are considered.
+fn shim_stimuli(events: &[TeamEvent], members: &[MemberInstance]) -> Vec<Stimulus> {
+    let mut stimuli: Vec<Stimulus> = Vec::new();
+    let mut ordered = events.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|event| event.ts);
+    for event in ordered {
+        let Some(member) = event.role.as_deref().filter(|role| {
+            members
+                .iter()
+                .any(|member| member.name == *role && member.role_type != RoleType::User)
+        }) else {
+            continue;
+        };
+        let message = event.reason.clone().unwrap_or_default();
+        let shim_event = match event.event.as_str() {
+            "task_completed" => Event::Completion {
+                message_id: None,
+                response: message,
+                last_lines: String::new(),
+            },
+            "context_exhausted" => Event::ContextExhausted {
+                message,
+                last_lines: String::new(),
+            },
+            "pane_death" | "member_crashed" => {
+                // One death is often logged as both; replay it once.
+                let duplicate = stimuli.iter().rev().any(|previous| {
+                    previous.member == member
+                        && previous.ts == event.ts
+                        && matches!(previous.event, Event::Died { .. })
+                });
+                if duplicate {
+                    continue;
+                }
+                Event::Died {
+                    exit_code: None,
+                    last_lines: String::new(),
+                }
+            }
+            "backend_quota_exhausted" => Event::QuotaBlocked {
+                message,
+                retry_at_epoch_secs: None,
+                retry_at_label: None,
+            },
+            "backend_auth_required" => Event::AuthRequired { message },
+            _ => continue,
+        };
+        stimuli.push(Stimulus {
+            ts: event.ts,
+            member: member.to_string(),
+            event: shim_event,
+        });
+    }
+    stimuli
+}
+
+/// Pair recorded and replayed decisions in order. Each recorded decision
+/// matches the earliest unused replayed decision of the same kind, event,
+/// member and task.
+fn diff_decisions(
+    recorded: &[Decision],
+    replayed: &[Decision],
+) -> (Vec<MatchedDecision>, Vec<Decision>, Vec<Decision>) {
+    let mut used = vec![false; replayed.len()];
+    let mut matched = Vec::new();
+    let mut missing = Vec::new();
+    for decision in recorded {
+        let found = replayed
+            .iter()
+            .enumerate()
+            .find(|(index, candidate)| !used[*index] && candidate.same_decision(decision));
+        match found {
+            Some((index, candidate)) => {
+                used[index] = true;
+                matched.push(MatchedDecision {
+                    recorded: decision.clone(),
+                    replayed: candidate.clone(),
+                    drift_secs: candidate.offset_secs as i64 - decision.offset_secs as i64,
+                });
+            }
+            None => missing.push(decision.clone()),
+        }
+    }
+    let extra = replayed
+        .iter()
+        .zip(used)
+        .filter(|(_, used)| !used)
+        .map(|(decision, _)| decision.clone())
+        .collect();
+    (matched, missing, extra)
+}
+
+/// The replayed daemon, its fakes, and the decisions observed so far.
+struct ReplayRun {
+    workspace: PathBuf,
+    daemon: TeamDaemon,
+    fakes: FakeShims,
+    events_offset: u64,
+    elapsed_secs: u64,
+    ticks: u64,
+    decisions: Vec<Decision>,
+}
+
+impl ReplayRun {
+    fn new(
+        workspace: &Path,
+        team_config: TeamConfig,
+        members: Vec<MemberInstance>,
+        in_flight: &HashMap<String, u32>,
+    ) -> Result<Self> {
+        let session = format!("batty-replay-{}", team_config.name);
+        let agents = members
+            .iter()
+            .filter(|member| member.role_type != RoleType::User)
+            .map(|member| {
+                let agent = member.agent.clone().unwrap_or_else(|| "claude".to_string());
+                (member.name.clone(), member.role_type, agent)
+            })
+            .collect::<Vec<_>>();
+        let mut daemon = TeamDaemon::new(DaemonConfig {
+            project_root: workspace.to_path_buf(),
+            team_config,
+            session,
+            members,
+            pane_map: HashMap::new(),
+        })?;
+
+        let fakes: FakeShims = Rc::default();
+        let roles = agents
+            .iter()
+            .map(|(name, role_type, _)| (name.clone(), *role_type))
+            .collect::<HashMap<_, _>>();
+        let registry = Rc::clone(&fakes);
+        daemon.shim_spawn_override = Some(Box::new(move |member: &str, _work_dir: &Path| {
+            let (mut fake, parent) = FakeShim::new_pair(member)?;
+            fake.set_default(default_behavior(roles.get(member).copied()));
+            registry.borrow_mut().insert(member.to_string(), fake);
+            Ok(parent)
+        }));
+
+        for (name, _, agent) in &agents {
+            let handle = daemon.spawn_member_shim(name, agent, agent, workspace, None)?;
+            daemon.shim_handles.insert(name.clone(), handle);
+            if let Some(handle) = daemon.shim_handles.get_mut(name) {
+                handle.record_pong();
+                handle.apply_state_change(ShimState::Idle);
+            }
+            daemon.states.insert(name.clone(), MemberState::Idle);
+            daemon.update_automation_timers_for_state(name, MemberState::Idle);
+        }
+        for watcher in daemon.watchers.values_mut() {
+            watcher.confirm_ready();
+        }
+        for (engineer, task_id) in in_flight {
+            daemon.active_tasks.insert(engineer.clone(), *task_id);
+            if let Some(fake) = fakes.borrow_mut().get_mut(engineer) {
+                fake.emit(Event::StateChanged {
+                    from: ShimState::Idle,
+                    to: ShimState::Working,
+                    summary: "replay: in flight at start of recording".to_string(),
+                })?;
+            }
+        }
+
+        Ok(Self {
+            workspace: workspace.to_path_buf(),
+            daemon,
+            fakes,
+            events_offset: 0,
+            elapsed_secs: 0,
+            ticks: 0,
+            decisions: Vec::new(),
+        })
+    }
+
+    /// Advance the virtual clock by `secs`, tick once, let every fake answer
+    /// what the daemon sent it, and collect the decisions the tick made.
+    fn step(&mut self, secs: u64) -> Result<()> {
+        self.daemon.advance_clock(Duration::from_secs(secs));
+        self.elapsed_secs += secs;
+        self.daemon.tick();
+        self.ticks += 1;
+        for fake in self.fakes.borrow_mut().values_mut() {
+            fake.process_inbound(&self.workspace)?;
+        }
+        self.collect_decisions()
+    }
+
+    fn deliver(&mut self, stimulus: &Stimulus) -> Result<()> {
+        let mut fakes = self.fakes.borrow_mut();
+        let Some(fake) = fakes.get_mut(&stimulus.member) else {
+            return Ok(());
+        };
+        if matches!(stimulus.event, Event::Completion { .. }) && fake.state() != ShimState::Idle {
+            fake.emit(Event::StateChanged {
+                from: fake.state(),
+                to: ShimState::Idle,
+                summary: "replay: recorded completion".to_string(),
+            })?;
+        }
+        fake.emit(stimulus.event.clone())
+    }
+
+    fn collect_decisions(&mut self) -> Result<()> {
+        let path = team_events_path(&self.workspace);
+        if !path.exists() {
+            return Ok(());
+        }
+        let mut file =
+            File::open(&path).with_context(|| format!("failed to open {}", path.display()))?;
+        file.seek(SeekFrom::Start(self.events_offset))?;
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            self.events_offset += read as u64;
+            if let Ok(event) = serde_json::from_str::<TeamEvent>(line.trim())
+                && let Some(decision) = Decision::from_event(&event, self.elapsed_secs)
+            {
+                self.decisions.push(decision);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Engineers start working on whatever they are sent and stay busy until
+/// the recording says they finished; everyone else answers immediately.
+fn default_behavior(role_type: Option<RoleType>) -> ShimBehavior {
+    match role_type {
+        Some(RoleType::Engineer) => ShimBehavior::Script(vec![Event::StateChanged {
+            from: ShimState::Idle,
+            to: ShimState::Working,
+            summary: "replay: working".to_string(),
+        }]),
+        _ => ShimBehavior::complete_with("ack", Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(mut event: TeamEvent, ts: u64) -> TeamEvent {
+        event.ts = ts;
+        event
+    }
+
+    fn decision(kind: DecisionKind, event: &str, role: &str, task: &str, offset: u64) -> Decision {
+        Decision {
+            kind,
+            event: event.to_string(),
+            role: Some(role.to_string()),
+            task: Some(task.to_string()),
+            offset_secs: offset,
+        }
+    }
+
+    fn write_export(root: &Path, auto_dispatch: bool, events: &[TeamEvent]) -> PathBuf {
+        let export = root.join("export");
+        std::fs::create_dir_all(export.join("board/tasks")).unwrap();
+        std::fs::write(
+            export.join(TEAM_CONFIG_FILE),
+            format!("name: incident\nuse_shim: true\nboard:\n  auto_dispatch: {auto_dispatch}\nroles:\n  - name: lead\n    role_type: manager\n    agent: claude\n  - name: eng\n    role_type: engineer\n    agent: claude\n    instances: 1\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            export.join("board/tasks/007-fix-login.md"),
+            "---\nid: 7\ntitle: fix-login\nstatus: done\npriority: high\nclaimed_by: eng-1-1\n---\n\nFix login.\n",
+        )
+        .unwrap();
+        let lines = events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(export.join("events.jsonl"), format!("{lines}\n")).unwrap();
+        export
+    }
+
+    #[test]
+    fn classify_maps_decision_events_and_ignores_the_rest() {
+        assert_eq!(
+            DecisionKind::classify("task_assigned"),
+            Some(DecisionKind::Dispatch)
+        );
+        assert_eq!(
+            DecisionKind::classify("narration_nudged"),
+            Some(DecisionKind::Nudge)
+        );
+        assert_eq!(
+            DecisionKind::classify("agent_restarted"),
+            Some(DecisionKind::Restart)
+        );
+        assert_eq!(
+            DecisionKind::classify("task_merge_failed"),
+            Some(DecisionKind::Merge)
+        );
+        assert_eq!(DecisionKind::classify("daemon_heartbeat"), None);
+
+        let assigned = TeamEvent::task_assigned("eng-1", "Task #42: fix the login flow");
+        let decision = Decision::from_event(&assigned, 5).unwrap();
+        assert_eq!(decision.task.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn diff_decisions_pairs_in_order_and_reports_both_sides() {
+        let recorded = vec![
+            decision(DecisionKind::Dispatch, "task_assigned", "eng-1", "1", 10),
+            decision(DecisionKind::Restart, "agent_restarted", "eng-1", "1", 600),
+            decision(DecisionKind::Dispatch, "task_assigned", "eng-2", "2", 20),
+        ];
+        let replayed = vec![
+            decision(DecisionKind::Dispatch, "task_assigned", "eng-2", "2", 30),
+            decision(DecisionKind::Dispatch, "task_assigned", "eng-1", "1", 30),
+            decision(DecisionKind::Nudge, "narration_nudged", "eng-1", "1", 300),
+        ];
+
+        let (matched, missing, extra) = diff_decisions(&recorded, &replayed);
+
+        assert_eq!(matched.len(), 2);
+        assert_eq!(matched[0].drift_secs, 20);
+        assert_eq!(matched[1].drift_secs, 10);
+        assert_eq!(missing, vec![recorded[1].clone()]);
+        assert_eq!(extra, vec![replayed[2].clone()]);
+    }
+
+    #[test]
+    fn shim_stimuli_translate_recorded_events_once() {
+        let members = vec![crate::team::harness::engineer_member(
+            "eng-1",
+            Some("lead"),
+            false,
+        )];
+        let events = vec![
+            at(TeamEvent::task_completed("eng-1", Some("7")), 50),
+            at(TeamEvent::pane_death("eng-1"), 90),
+            at(TeamEvent::member_crashed("eng-1", true), 90),
+            at(TeamEvent::task_assigned("eng-1", "7"), 10),
+            at(TeamEvent::pane_death("lead"), 95),
+        ];
+
+        let stimuli = shim_stimuli(&events, &members);
+
+        assert_eq!(stimuli.len(), 2);
+        assert!(matches!(stimuli[0].event, Event::Completion { .. }));
+        assert!(matches!(stimuli[1].event, Event::Died { .. }));
+        assert_eq!(stimuli[1].ts, 90);
+    }
+
+    #[test]
+    fn rewind_board_reopens_assigned_tasks_and_claims_in_flight_work() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tasks_dir = team_config_dir(tmp.path()).join("board/tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+        for (id, title) in [(1, "assigned"), (2, "inflight"), (3, "untouched")] {
+            std::fs::write(
+                tasks_dir.join(format!("{id:03}-{title}.md")),
+                format!(
+                    "---\nid: {id}\ntitle: {title}\nstatus: done\npriority: high\nclaimed_by: eng-1\n---\n\nBody.\n"
+                ),
+            )
+            .unwrap();
+        }
+        let members = vec![crate::team::harness::engineer_member(
+            "eng-1",
+            Some("lead"),
+            false,
+        )];
+        let events = vec![
+            at(TeamEvent::task_assigned("eng-1", "Task #1: assigned"), 20),
+            at(TeamEvent::task_completed("eng-1", Some("2")), 10),
+        ];
+
+        let in_flight = rewind_board(tmp.path(), &events, &members).unwrap();
+
+        assert_eq!(in_flight, HashMap::from([("eng-1".to_string(), 2)]));
+        let tasks = load_tasks_from_dir(&tasks_dir).unwrap();
+        let status = |id: u32| {
+            let task = tasks.iter().find(|task| task.id == id).unwrap();
+            (task.status.clone(), task.claimed_by.clone())
+        };
+        assert_eq!(status(1), ("todo".to_string(), None));
+        assert_eq!(
+            status(2),
+            ("in-progress".to_string(), Some("eng-1".to_string()))
+        );
+        assert_eq!(status(3), ("done".to_string(), Some("eng-1".to_string())));
+    }
+
+    #[test]
+    fn replay_run_rebuilds_the_export_and_flags_decisions_it_cannot_reproduce() {
+        let tmp = tempfile::tempdir().unwrap();
+        let export = write_export(
+            tmp.path(),
+            false,
+            &[
+                at(TeamEvent::daemon_started(), 1_000),
+                at(TeamEvent::task_completed("eng-1-1", Some("7")), 1_030),
+                at(
+                    TeamEvent::agent_restarted("eng-1-1", "7", "stalled", 1),
+                    1_090,
+                ),
+            ],
+        );
+
+        let report = replay_run(&export, &ReplayOptions { tick_secs: 30 }).unwrap();
+
+        assert_eq!(report.duration_secs, 90);
+        assert_eq!(report.shim_events, 1);
+        assert!(report.ticks >= 4);
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(report.missing[0].kind, DecisionKind::Restart);
+        assert!(!report.is_faithful());
+        assert!(report.render().contains("Recorded but not replayed:"));
+        let task = std::fs::read_to_string(
+            team_config_dir(&report.workspace).join("board/tasks/007-fix-login.md"),
+        )
+        .unwrap();
+        assert!(task.contains("claimed_by: eng-1-1"));
+    }
+
+    #[test]
+    fn replay_run_reproduces_a_recorded_dispatch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let export = write_export(
+            tmp.path(),
+            true,
+            &[
+                at(TeamEvent::daemon_started(), 1_000),
+                at(
+                    TeamEvent::task_assigned("eng-1-1", "Task #7: fix-login"),
+                    1_045,
+                ),
+            ],
+        );
+
+        let report = replay_run(&export, &ReplayOptions::default()).unwrap();
+
+        assert_eq!(report.matched.len(), 1, "{}", report.render());
+        assert_eq!(report.matched[0].recorded.kind, DecisionKind::Dispatch);
+        assert_eq!(report.matched[0].replayed.task.as_deref(), Some("7"));
+    }
+}
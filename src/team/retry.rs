@@ -121,7 +121,8 @@ where
     }
 }
 
-fn next_delay_ms(config: &RetryConfig, retry_index: u32) -> u64 {
+/// Backoff before retry number `retry_index + 1`, capped at `max_delay_ms`.
+pub(crate) fn next_delay_ms(config: &RetryConfig, retry_index: u32) -> u64 {
     let multiplier = 1_u64.checked_shl(retry_index).unwrap_or(u64::MAX);
     let base_delay = config.base_delay_ms.saturating_mul(multiplier);
     let capped_delay = base_delay.min(config.max_delay_ms);
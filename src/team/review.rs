@@ -150,6 +150,35 @@ pub fn apply_review(
     Ok(())
 }
 
+/// Record a disposition that arrived from outside the daemon, such as a
+/// pull request review on the forge, against the board task. Rework sends
+/// the task back to `in-progress` and queues `feedback` for its engineer.
+pub(crate) fn record_external_review(
+    board_dir: &Path,
+    task_id: u32,
+    disposition: MergeDisposition,
+    reviewer: &str,
+    feedback: Option<&str>,
+    attribution: crate::team::task_cmd::StatusTransitionAttribution,
+) -> anyhow::Result<()> {
+    let action = match disposition {
+        MergeDisposition::MergeReady => "approve",
+        MergeDisposition::ReworkRequired => "request-changes",
+        MergeDisposition::Discarded => "reject",
+        MergeDisposition::Escalated => {
+            anyhow::bail!("external reviews cannot escalate task #{task_id}")
+        }
+    };
+    crate::team::task_cmd::cmd_review_structured_with_attribution(
+        board_dir,
+        task_id,
+        action,
+        feedback,
+        reviewer,
+        attribution,
+    )
+}
+
 pub fn validate_review_readiness(meta: &WorkflowMeta) -> Result<(), String> {
     if meta.state == TaskState::Review {
         Ok(())
@@ -1024,6 +1053,8 @@ roles:
             completed: None,
             description: String::new(),
             batty_config: None,
+            scope: None,
+            acceptance: Vec::new(),
             source_path: Path::new("review.md").to_path_buf(),
         }
     }
@@ -50,6 +50,20 @@ fn scale_engineers(project_root: &Path, count: u32) -> Result<()> {
         return Ok(());
     }
 
+    let approvals = &config.workflow_policy.approvals;
+    if count > approvals.scale_max_engineers {
+        super::approval::require_cli_approval(
+            project_root,
+            approvals,
+            super::approval::ApprovalAction::Scale,
+            &format!("engineers={count}"),
+            &format!(
+                "scale engineers from {old_count} to {count} (limit without approval: {})",
+                approvals.scale_max_engineers
+            ),
+        )?;
+    }
+
     // Read raw YAML and update the engineer instances field
     let content = std::fs::read_to_string(&config_path).context("failed to read team.yaml")?;
     let updated = update_role_instances(&content, &eng_role.name, count)?;
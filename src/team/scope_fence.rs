@@ -0,0 +1,332 @@
+//! Commit-time scope-fence enforcement for engineer worktrees.
+//!
+//! Engineer worktrees get a Batty-managed `pre-commit` hook (see
+//! [`crate::worktree::install_scope_fence_hook`]) that runs the hidden
+//! `batty scope-check` command. The check resolves the task the engineer has
+//! claimed, compares the staged paths against the task's `scope:` frontmatter
+//! (or the legacy `SCOPE FENCE:` line), and rejects the commit with a message
+//! the agent can act on. The hook only appends its `scope_fence_violation`
+//! event to `events.jsonl`; the daemon copies it into `telemetry.db`.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use tracing::warn;
+
+use super::daemon::verification::{
+    engineer_worktree_context, find_claimed_task_for_worktree, parse_scope_fence,
+};
+use super::events::{EventSink, TeamEvent};
+
+/// Reason prefix of violations recorded by the pre-commit hook.
+const HOOK_STAGE: &str = "stage=pre-commit";
+use crate::task::{ScopeViolation, Task, TaskScope};
+
+/// A staged path that falls outside the claimed task's scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedViolation {
+    pub path: String,
+    pub violation: ScopeViolation,
+}
+
+/// Result of checking the staged index against the claimed task's scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedScopeCheck {
+    pub engineer: String,
+    pub task_id: u32,
+    pub scope: TaskScope,
+    pub violations: Vec<StagedViolation>,
+}
+
+/// Entry point for `batty scope-check`. Succeeds silently when the commit is
+/// in scope, or when the worktree is not an engineer worktree with a fenced
+/// task; otherwise records a `scope_fence_violation` event and fails with
+/// the rejection message.
+pub fn run_scope_check(worktree_dir: &Path) -> Result<()> {
+    let worktree_dir = worktree_toplevel(worktree_dir)?;
+    let Some(check) = check_staged_scope(&worktree_dir)? else {
+        return Ok(());
+    };
+    if check.violations.is_empty() {
+        return Ok(());
+    }
+
+    if let Some((project_root, _)) = engineer_worktree_context(&worktree_dir)
+        && let Err(error) = record_violation_event(&project_root, &check)
+    {
+        warn!(error = %error, "failed to record scope_fence_violation event");
+    }
+    bail!("{}", rejection_message(&check));
+}
+
+/// Check the staged index of an engineer worktree against the scope of the
+/// task its engineer has claimed. Returns `None` when there is nothing to
+/// enforce.
+pub fn check_staged_scope(worktree_dir: &Path) -> Result<Option<StagedScopeCheck>> {
+    let Some((project_root, engineer)) = engineer_worktree_context(worktree_dir) else {
+        return Ok(None);
+    };
+    let Some(task) = find_claimed_task_for_worktree(&project_root, &engineer, worktree_dir)? else {
+        return Ok(None);
+    };
+    let Some(scope) = effective_task_scope(&task)? else {
+        return Ok(None);
+    };
+
+    let violations = staged_files(worktree_dir)?
+        .into_iter()
+        .filter_map(|path| {
+            scope
+                .check(&path)
+                .map(|violation| StagedViolation { path, violation })
+        })
+        .collect();
+
+    Ok(Some(StagedScopeCheck {
+        engineer,
+        task_id: task.id,
+        scope,
+        violations,
+    }))
+}
+
+/// The structured `scope:` field wins; otherwise a legacy `SCOPE FENCE:`
+/// line in the task body is treated as an allow list.
+pub fn effective_task_scope(task: &Task) -> Result<Option<TaskScope>> {
+    if let Some(scope) = task.scope.as_ref() {
+        return Ok(Some(scope.clone()));
+    }
+    let task_text = std::fs::read_to_string(&task.source_path)
+        .with_context(|| format!("failed to read {}", task.source_path.display()))?;
+    let allow = parse_scope_fence(&task_text);
+    Ok((!allow.is_empty()).then_some(TaskScope {
+        allow,
+        deny: Vec::new(),
+    }))
+}
+
+fn worktree_toplevel(dir: &Path) -> Result<std::path::PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("failed to run git rev-parse in {}", dir.display()))?;
+    if !output.status.success() {
+        bail!(
+            "git rev-parse --show-toplevel failed in {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().into())
+}
+
+fn staged_files(worktree_dir: &Path) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--name-only", "--no-renames", "-z"])
+        .current_dir(worktree_dir)
+        .output()
+        .with_context(|| format!("failed to run git diff in {}", worktree_dir.display()))?;
+    if !output.status.success() {
+        bail!(
+            "git diff --cached failed in {}: {}",
+            worktree_dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn record_violation_event(project_root: &Path, check: &StagedScopeCheck) -> Result<()> {
+    let files = check
+        .violations
+        .iter()
+        .map(|violation| violation.path.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    let details = format!("{HOOK_STAGE} commit_rejected=true files={files}");
+    let event = TeamEvent::scope_fence_violation(&check.engineer, check.task_id, &details);
+    let mut sink = EventSink::new(&super::team_events_path(project_root))?;
+    sink.emit(event)
+}
+
+/// Whether `event` was written by the pre-commit hook rather than the daemon.
+pub fn is_hook_event(event: &TeamEvent) -> bool {
+    event.event == "scope_fence_violation"
+        && event
+            .reason
+            .as_deref()
+            .is_some_and(|reason| reason.starts_with(HOOK_STAGE))
+}
+
+/// Why `violation` puts a path outside the scope, for messages.
+pub fn violation_reason(violation: &ScopeViolation) -> String {
+    match violation {
+        ScopeViolation::Denied(pattern) => format!("denied by `{pattern}`"),
+        ScopeViolation::NotAllowed => "not in the allow list".to_string(),
+    }
+}
+
+fn rejection_message(check: &StagedScopeCheck) -> String {
+    let mut message = format!(
+        "commit rejected: {} staged file(s) fall outside the scope of task #{}\n",
+        check.violations.len(),
+        check.task_id
+    );
+    for StagedViolation { path, violation } in &check.violations {
+        message.push_str(&format!("  {path}  ({})\n", violation_reason(violation)));
+    }
+    if !check.scope.allow.is_empty() {
+        message.push_str(&format!("allowed: {}\n", check.scope.allow.join(", ")));
+    }
+    if !check.scope.deny.is_empty() {
+        message.push_str(&format!("denied: {}\n", check.scope.deny.join(", ")));
+    }
+    let paths = check
+        .violations
+        .iter()
+        .map(|violation| violation.path.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    message.push_str(&format!(
+        "Unstage them with `git restore --staged -- {paths}` and commit the in-scope changes. \
+         If task #{} really needs these files, ask your manager to widen its `scope:` first.",
+        check.task_id
+    ));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn setup_engineer_worktree(task_frontmatter: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let tasks_dir = root.join(".batty/team_config/board/tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+        std::fs::write(
+            tasks_dir.join("042-fenced.md"),
+            format!(
+                "---\nid: 42\ntitle: fenced\nstatus: in-progress\npriority: high\nclaimed_by: eng-1\n{task_frontmatter}---\n\nTask body.\n"
+            ),
+        )
+        .unwrap();
+
+        let worktree = root.join(".batty/worktrees/eng-1");
+        std::fs::create_dir_all(&worktree).unwrap();
+        git(&worktree, &["init", "-q", "-b", "main"]);
+        git(
+            &worktree,
+            &["config", "user.email", "batty-test@example.com"],
+        );
+        git(&worktree, &["config", "user.name", "Batty Test"]);
+        (tmp, worktree)
+    }
+
+    fn stage(worktree: &Path, path: &str) {
+        let file = worktree.join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "x\n").unwrap();
+        git(worktree, &["add", path]);
+    }
+
+    #[test]
+    fn check_staged_scope_reports_denied_and_unlisted_paths() {
+        let (_tmp, worktree) = setup_engineer_worktree(
+            "scope:\n  allow:\n    - src/team/**\n  deny:\n    - src/team/daemon.rs\n",
+        );
+        stage(&worktree, "src/team/slack.rs");
+        stage(&worktree, "src/team/daemon.rs");
+        stage(&worktree, "Cargo.toml");
+
+        let check = check_staged_scope(&worktree).unwrap().unwrap();
+
+        assert_eq!(check.task_id, 42);
+        assert_eq!(
+            check.violations,
+            vec![
+                StagedViolation {
+                    path: "Cargo.toml".to_string(),
+                    violation: ScopeViolation::NotAllowed,
+                },
+                StagedViolation {
+                    path: "src/team/daemon.rs".to_string(),
+                    violation: ScopeViolation::Denied("src/team/daemon.rs".to_string()),
+                },
+            ]
+        );
+        let message = rejection_message(&check);
+        assert!(message.contains("task #42"));
+        assert!(message.contains("git restore --staged -- Cargo.toml src/team/daemon.rs"));
+    }
+
+    #[test]
+    fn check_staged_scope_falls_back_to_legacy_scope_fence_line() {
+        let (tmp, worktree) = setup_engineer_worktree("");
+        let task_path = tmp
+            .path()
+            .join(".batty/team_config/board/tasks/042-fenced.md");
+        let text = std::fs::read_to_string(&task_path).unwrap();
+        std::fs::write(&task_path, format!("{text}SCOPE FENCE: docs/\n")).unwrap();
+        stage(&worktree, "docs/guide.md");
+
+        let check = check_staged_scope(&worktree).unwrap().unwrap();
+
+        assert_eq!(check.scope.allow, vec!["docs".to_string()]);
+        assert!(check.violations.is_empty());
+    }
+
+    #[test]
+    fn run_scope_check_rejects_commit_and_records_event() {
+        let (tmp, worktree) = setup_engineer_worktree("scope:\n  - src/\n");
+        stage(&worktree, "README.md");
+
+        let error = run_scope_check(&worktree).unwrap_err().to_string();
+
+        assert!(error.contains("README.md  (not in the allow list)"));
+        let events =
+            crate::team::events::read_events(&crate::team::team_events_path(tmp.path())).unwrap();
+        let event = events
+            .iter()
+            .find(|event| event.event == "scope_fence_violation")
+            .unwrap();
+        assert_eq!(event.role.as_deref(), Some("eng-1"));
+        assert_eq!(event.task.as_deref(), Some("42"));
+        assert!(
+            event
+                .reason
+                .as_deref()
+                .unwrap()
+                .contains("commit_rejected=true files=README.md")
+        );
+    }
+
+    #[test]
+    fn run_scope_check_ignores_non_engineer_worktrees() {
+        let tmp = tempfile::tempdir().unwrap();
+        git(tmp.path(), &["init", "-q", "-b", "main"]);
+        stage(tmp.path(), "anything.rs");
+
+        run_scope_check(tmp.path()).unwrap();
+    }
+}
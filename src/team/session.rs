@@ -20,6 +20,9 @@ use super::{
 };
 use crate::tmux;
 
+/// Pattern-rule actions listed by `batty status --health`.
+const RECENT_PATTERN_ACTIONS: usize = 10;
+
 /// Path to the pause marker file. Presence pauses nudges and standups.
 pub fn pause_marker_path(project_root: &Path) -> PathBuf {
     project_root.join(".batty").join("paused")
@@ -335,8 +338,65 @@ pub fn attach_team(project_root: &Path) -> Result<()> {
     tmux::attach(&session)
 }
 
-/// Show team status.
-pub fn team_status(project_root: &Path, json: bool, detail: bool, health: bool) -> Result<()> {
+/// Everything `batty status` renders, gathered once so the CLI table, the
+/// `--json` report, and the control API stay in sync.
+struct TeamStatusSnapshot {
+    team: String,
+    session: String,
+    session_running: bool,
+    paused: bool,
+    rows: Vec<status::TeamStatusRow>,
+    workflow_metrics: Option<(String, status::WorkflowMetrics)>,
+    publish_handoff: Option<crate::release::ReleasePublishHandoff>,
+    watchdog: status::WatchdogStatus,
+    main_smoke: Option<super::daemon::MainSmokeState>,
+    bench_state: crate::team::bench::BenchState,
+    active_tasks: Vec<status::StatusTaskEntry>,
+    review_queue: Vec<status::StatusTaskEntry>,
+    engineer_profiles: Option<Vec<crate::team::telemetry_db::EngineerPerformanceProfileRow>>,
+    optional_subsystems: Option<Vec<status::OptionalSubsystemStatus>>,
+    pattern_actions: Option<Vec<status::PatternActionStatus>>,
+    budget: Option<Vec<crate::team::budget::BudgetHeadroomRow>>,
+    webhook_dead_letters: Option<Vec<crate::team::webhooks::WebhookDeadLetter>>,
+}
+
+impl TeamStatusSnapshot {
+    fn into_json_report(self) -> status::TeamStatusJsonReport {
+        status::build_team_status_json_report(status::TeamStatusJsonReportInput {
+            team: self.team,
+            session: self.session,
+            session_running: self.session_running,
+            paused: self.paused,
+            main_smoke: self.main_smoke,
+            watchdog: self.watchdog,
+            workflow_metrics: self.workflow_metrics.map(|(_, metrics)| metrics),
+            publish_handoff: self.publish_handoff,
+            active_tasks: self.active_tasks,
+            review_queue: self.review_queue,
+            optional_subsystems: self.optional_subsystems,
+            pattern_actions: self.pattern_actions,
+            engineer_profiles: self.engineer_profiles,
+            budget: self.budget,
+            webhook_dead_letters: self.webhook_dead_letters,
+            members: self.rows,
+        })
+    }
+}
+
+/// Build the same report `batty status --json` prints.
+pub(crate) fn team_status_json_report(
+    project_root: &Path,
+    detail: bool,
+    health: bool,
+) -> Result<status::TeamStatusJsonReport> {
+    Ok(collect_team_status(project_root, detail, health)?.into_json_report())
+}
+
+fn collect_team_status(
+    project_root: &Path,
+    detail: bool,
+    health: bool,
+) -> Result<TeamStatusSnapshot> {
     let config_path = team_config_path(project_root);
     if !config_path.exists() {
         bail!("no team config found at {}", config_path.display());
@@ -441,28 +501,61 @@ pub fn team_status(project_root: &Path, json: bool, detail: bool, health: bool)
     };
     let optional_subsystems =
         health.then(|| status::load_optional_subsystem_statuses(project_root));
+    let pattern_actions =
+        health.then(|| status::load_recent_pattern_actions(project_root, RECENT_PATTERN_ACTIONS));
+    let budget = crate::team::budget::project_headroom(project_root, &team_config.budget, &members);
+    let webhook_dead_letters = health
+        .then(|| crate::team::webhooks::load_dead_letters(project_root))
+        .filter(|dead_letters| !dead_letters.is_empty());
+
+    Ok(TeamStatusSnapshot {
+        team: team_config.name,
+        session,
+        session_running,
+        paused,
+        rows,
+        workflow_metrics,
+        publish_handoff,
+        watchdog,
+        main_smoke,
+        bench_state,
+        active_tasks,
+        review_queue,
+        engineer_profiles,
+        optional_subsystems,
+        pattern_actions,
+        budget,
+        webhook_dead_letters,
+    })
+}
 
+/// Show team status.
+pub fn team_status(project_root: &Path, json: bool, detail: bool, health: bool) -> Result<()> {
+    let snapshot = collect_team_status(project_root, detail, health)?;
     if json {
-        let report = status::build_team_status_json_report(status::TeamStatusJsonReportInput {
-            team: team_config.name.clone(),
-            session: session.clone(),
+        let report = snapshot.into_json_report();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        let TeamStatusSnapshot {
+            team,
+            session,
             session_running,
-            paused,
-            main_smoke: main_smoke.clone(),
+            paused: _,
+            rows,
+            workflow_metrics,
+            publish_handoff,
             watchdog,
-            workflow_metrics: workflow_metrics
-                .as_ref()
-                .map(|(_, metrics)| metrics.clone()),
-            publish_handoff: publish_handoff.clone(),
+            main_smoke,
+            bench_state,
             active_tasks,
             review_queue,
-            optional_subsystems,
             engineer_profiles,
-            members: rows,
-        });
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        println!("Team: {}", team_config.name);
+            optional_subsystems,
+            pattern_actions,
+            budget,
+            webhook_dead_letters,
+        } = snapshot;
+        println!("Team: {team}");
         println!(
             "Session: {} ({})",
             session,
@@ -543,6 +636,10 @@ pub fn team_status(project_root: &Path, json: bool, detail: bool, health: bool)
             println!();
             println!("{formatted}");
         }
+        if let Some(budget) = budget.filter(|rows| !rows.is_empty()) {
+            println!();
+            println!("{}", crate::team::budget::format_headroom(&budget));
+        }
         if let Some(optional_subsystems) = optional_subsystems {
             println!();
             println!(
@@ -550,6 +647,17 @@ pub fn team_status(project_root: &Path, json: bool, detail: bool, health: bool)
                 status::format_optional_subsystem_statuses(&optional_subsystems)
             );
         }
+        if let Some(pattern_actions) = pattern_actions {
+            println!();
+            println!("{}", status::format_pattern_actions(&pattern_actions));
+        }
+        if let Some(dead_letters) = webhook_dead_letters {
+            println!();
+            println!(
+                "{}",
+                crate::team::webhooks::format_dead_letters(&dead_letters)
+            );
+        }
         if detail {
             if let Some(profiles) = engineer_profiles {
                 println!();
@@ -725,6 +833,7 @@ pub fn validate_team(project_root: &Path, verbose: bool) -> Result<()> {
     team_config.validate_project_refs(project_root)?;
 
     validate_board_layout(project_root, &team_config)?;
+    let pattern_rules = super::pattern_rules::load_rules(project_root)?;
 
     let workflow_mode_is_explicit = workflow_mode_declared(&config_path)?;
 
@@ -743,6 +852,9 @@ pub fn validate_team(project_root: &Path, verbose: bool) -> Result<()> {
     );
     println!("Roles: {}", team_config.roles.len());
     println!("Total members: {}", members.len());
+    if !pattern_rules.is_empty() {
+        println!("Pattern rules: {}", pattern_rules.len());
+    }
 
     // Backend health checks — warn about missing binaries but don't fail validation.
     let backend_warnings = team_config.check_backend_health();
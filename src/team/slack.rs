@@ -0,0 +1,478 @@
+//! Native Slack Web API client for batty.
+//!
+//! Posts through `chat.postMessage`/`chat.update` and polls the commands
+//! channel with `conversations.history`, following the same blocking request
+//! model as the Discord and Telegram bridges. The API base is configurable so
+//! the bridge can run against a local mock of the Web API.
+
+use anyhow::{Context, Result, anyhow, bail};
+use tracing::{debug, warn};
+
+use super::config::ChannelConfig;
+
+const SLACK_API_BASE: &str = "https://slack.com/api";
+/// Slack recommends keeping message `text` under 4,000 characters.
+const MAX_TEXT_LEN: usize = 3_900;
+
+/// An inbound message received from a Slack channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundMessage {
+    pub ts: String,
+    pub channel_id: String,
+    pub from_user_id: String,
+    pub text: String,
+}
+
+/// Blocking Slack Web API client.
+pub struct SlackBot {
+    bot_token: String,
+    api_base: String,
+    allowed_user_ids: Vec<String>,
+    commands_channel_id: String,
+    /// Newest message timestamp already seen in the commands channel. Starts
+    /// at construction time so the backlog is never replayed as commands.
+    last_ts: String,
+}
+
+impl SlackBot {
+    pub fn new(
+        bot_token: String,
+        allowed_user_ids: Vec<String>,
+        commands_channel_id: String,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            bot_token,
+            api_base: SLACK_API_BASE.to_string(),
+            allowed_user_ids,
+            commands_channel_id,
+            last_ts: format!("{}.{:06}", now.timestamp(), now.timestamp_subsec_micros()),
+        }
+    }
+
+    /// Point the client at a different Web API base URL.
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Build a `SlackBot` from a `ChannelConfig`.
+    ///
+    /// Returns `None` if either the token or commands channel ID is missing.
+    /// The token can be provided directly or via `BATTY_SLACK_BOT_TOKEN`.
+    pub fn from_config(config: &ChannelConfig) -> Option<Self> {
+        let token = config
+            .bot_token
+            .clone()
+            .or_else(|| std::env::var("BATTY_SLACK_BOT_TOKEN").ok())?;
+        let commands_channel_id = config.commands_channel_id.clone()?;
+        let bot = Self::new(token, config.authorized_user_ids(), commands_channel_id);
+        Some(match config.api_base.as_deref() {
+            Some(api_base) => bot.with_api_base(api_base),
+            None => bot,
+        })
+    }
+
+    pub fn commands_channel_id(&self) -> &str {
+        &self.commands_channel_id
+    }
+
+    /// Check if a Slack member ID is in the allowed list.
+    ///
+    /// An empty `allowed_slack_user_ids` list denies everyone.
+    pub fn is_authorized(&self, user_id: &str) -> bool {
+        self.allowed_user_ids.iter().any(|id| id == user_id)
+    }
+
+    /// Post a message and return its timestamp, which Slack uses as the
+    /// message ID for later `chat.update` calls.
+    pub fn post_message(&self, channel_id: &str, text: &str) -> Result<String> {
+        let body = serde_json::json!({
+            "channel": channel_id,
+            "text": truncate_for_slack(text, MAX_TEXT_LEN),
+            "mrkdwn": true,
+            "unfurl_links": false,
+        });
+        let json = self.call("chat.postMessage", &body)?;
+        let ts = json
+            .get("ts")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| anyhow!("Slack chat.postMessage response missing ts"))?
+            .to_string();
+        debug!(channel_id, ts, "Slack message accepted");
+        Ok(ts)
+    }
+
+    pub fn update_message(&self, channel_id: &str, ts: &str, text: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "channel": channel_id,
+            "ts": ts,
+            "text": truncate_for_slack(text, MAX_TEXT_LEN),
+        });
+        self.call("chat.update", &body).map(|_| ())
+    }
+
+    pub fn send_command_reply(&self, text: &str) -> Result<()> {
+        self.post_message(&self.commands_channel_id, &escape_mrkdwn(text))
+            .map(|_| ())
+    }
+
+    /// Post an agent message, turning the daemon's `--- Message from X ---`
+    /// header into a bold sender line.
+    pub fn send_formatted_message(&self, channel_id: &str, message: &str) -> Result<()> {
+        self.post_message(channel_id, &outbound_text(message))
+            .map(|_| ())
+    }
+
+    pub fn poll_commands(&mut self) -> Result<Vec<InboundMessage>> {
+        let url = format!(
+            "{}/conversations.history?channel={}&oldest={}&limit=100",
+            self.api_base, self.commands_channel_id, self.last_ts
+        );
+        let response = ureq::get(&url)
+            .set("Authorization", &format!("Bearer {}", self.bot_token))
+            .call();
+        let json = read_response(response, "conversations.history")?;
+
+        let (messages, latest_ts) =
+            parse_history_response(&json, &self.commands_channel_id, &self.allowed_user_ids)?;
+        if let Some(ts) = latest_ts
+            && ts_key(&ts) > ts_key(&self.last_ts)
+        {
+            self.last_ts = ts;
+        }
+        Ok(messages)
+    }
+
+    fn call(&self, method: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        let url = format!("{}/{method}", self.api_base);
+        let response = ureq::post(&url)
+            .set("Authorization", &format!("Bearer {}", self.bot_token))
+            .set("Content-Type", "application/json; charset=utf-8")
+            .send_string(&body.to_string());
+        read_response(response, method)
+    }
+}
+
+/// Decode a Web API response. Slack reports most failures as HTTP 200 with
+/// `"ok": false`, so the envelope is checked as well as the status.
+fn read_response(
+    response: std::result::Result<ureq::Response, ureq::Error>,
+    method: &str,
+) -> Result<serde_json::Value> {
+    let json: serde_json::Value = match response {
+        Ok(resp) => resp
+            .into_json()
+            .with_context(|| format!("failed to parse Slack {method} response"))?,
+        Err(ureq::Error::Status(status, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            warn!(status, detail = %detail, method, "Slack request failed");
+            bail!("Slack {method} failed with status {status}: {detail}");
+        }
+        Err(ureq::Error::Transport(error)) => {
+            warn!(error = %error, method, "Slack request transport failed");
+            bail!("Slack {method} transport failed: {error}");
+        }
+    };
+    if json.get("ok").and_then(|value| value.as_bool()) != Some(true) {
+        let error = json
+            .get("error")
+            .and_then(|value| value.as_str())
+            .unwrap_or("unknown_error");
+        warn!(method, error, "Slack API returned an error");
+        bail!("Slack {method} failed: {error}");
+    }
+    Ok(json)
+}
+
+/// Parse a `conversations.history` page into authorized human messages,
+/// oldest first, plus the newest timestamp seen. The cursor advances past
+/// bot posts, joins, and unauthorized senders so they are not re-read.
+fn parse_history_response(
+    json: &serde_json::Value,
+    channel_id: &str,
+    allowed_user_ids: &[String],
+) -> Result<(Vec<InboundMessage>, Option<String>)> {
+    let messages = json
+        .get("messages")
+        .and_then(|value| value.as_array())
+        .ok_or_else(|| anyhow!("Slack history response missing messages"))?;
+
+    let mut inbound = Vec::new();
+    let mut latest_ts: Option<String> = None;
+    for message in messages {
+        let Some(ts) = message.get("ts").and_then(|value| value.as_str()) else {
+            continue;
+        };
+        if latest_ts
+            .as_deref()
+            .is_none_or(|latest| ts_key(ts) > ts_key(latest))
+        {
+            latest_ts = Some(ts.to_string());
+        }
+        // Bot posts (including our own) and system messages such as
+        // `channel_join` carry a subtype or bot_id.
+        if message.get("subtype").is_some() || message.get("bot_id").is_some() {
+            continue;
+        }
+        let Some(user) = message.get("user").and_then(|value| value.as_str()) else {
+            continue;
+        };
+        if !allowed_user_ids.iter().any(|id| id == user) {
+            debug!(user, "dropping Slack message from unauthorized user");
+            continue;
+        }
+        let text = match message.get("text").and_then(|value| value.as_str()) {
+            Some(text) if !text.trim().is_empty() => unescape_mrkdwn(text.trim()),
+            _ => continue,
+        };
+        inbound.push(InboundMessage {
+            ts: ts.to_string(),
+            channel_id: channel_id.to_string(),
+            from_user_id: user.to_string(),
+            text,
+        });
+    }
+
+    inbound.sort_by_key(|message| ts_key(&message.ts));
+    Ok((inbound, latest_ts))
+}
+
+/// Order Slack `ts` values (`"<seconds>.<micros>"`) numerically.
+fn ts_key(ts: &str) -> (u64, u64) {
+    let (secs, micros) = ts.split_once('.').unwrap_or((ts, "0"));
+    (secs.parse().unwrap_or(0), micros.parse().unwrap_or(0))
+}
+
+pub(super) fn outbound_text(message: &str) -> String {
+    let trimmed = message.trim();
+    if let Some(rest) = trimmed.strip_prefix("--- Message from ")
+        && let Some((sender, body)) = rest.split_once("---\n")
+    {
+        return format!(
+            "*{}*\n{}",
+            escape_mrkdwn(sender.trim()),
+            escape_mrkdwn(body.trim())
+        );
+    }
+    escape_mrkdwn(trimmed)
+}
+
+/// Escape the three characters Slack treats as control sequences.
+pub(super) fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape_mrkdwn(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn truncate_for_slack(input: &str, limit: usize) -> String {
+    if input.chars().count() <= limit {
+        return input.to_string();
+    }
+    let mut truncated: String = input.chars().take(limit.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    //! Minimal local stand-in for the Slack Web API used by bridge tests.
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct RecordedCall {
+        pub(crate) method: String,
+        pub(crate) query: String,
+        pub(crate) authorization: String,
+        pub(crate) body: serde_json::Value,
+    }
+
+    /// Serves canned JSON per Web API method and records every call.
+    pub(crate) struct MockSlack {
+        pub(crate) base: String,
+        pub(crate) calls: Arc<Mutex<Vec<RecordedCall>>>,
+    }
+
+    impl MockSlack {
+        pub(crate) fn start(respond: fn(&str) -> serde_json::Value) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base = format!("http://{}/api", listener.local_addr().unwrap());
+            let calls = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&calls);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+                    let method = path.rsplit('/').next().unwrap_or_default().to_string();
+
+                    let mut content_length = 0;
+                    let mut authorization = String::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            match name.to_ascii_lowercase().as_str() {
+                                "content-length" => {
+                                    content_length = value.trim().parse().unwrap_or(0)
+                                }
+                                "authorization" => authorization = value.trim().to_string(),
+                                _ => {}
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+
+                    let response = respond(&method).to_string();
+                    recorded.lock().unwrap().push(RecordedCall {
+                        method: method.clone(),
+                        query: query.to_string(),
+                        authorization,
+                        body: serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+                    });
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        response.len(),
+                        response
+                    );
+                }
+            });
+            Self { base, calls }
+        }
+
+        pub(crate) fn calls(&self, method: &str) -> Vec<RecordedCall> {
+            self.calls
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|call| call.method == method)
+                .cloned()
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockSlack;
+    use super::*;
+
+    fn history_fixture() -> serde_json::Value {
+        serde_json::json!({
+            "ok": true,
+            "messages": [
+                { "type": "message", "user": "U1", "text": "$status", "ts": "1700000003.000200" },
+                { "type": "message", "user": "U2", "text": "let me in", "ts": "1700000002.000100" },
+                { "type": "message", "subtype": "bot_message", "bot_id": "B1", "text": "echo", "ts": "1700000004.000001" },
+                { "type": "message", "user": "U1", "text": "ship &lt;it&gt;", "ts": "1700000001.000900" }
+            ]
+        })
+    }
+
+    #[test]
+    fn parse_history_filters_bots_and_unauthorized_users() {
+        let (messages, latest) =
+            parse_history_response(&history_fixture(), "C1", &["U1".to_string()]).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].text, "ship <it>");
+        assert_eq!(messages[1].text, "$status");
+        assert!(messages.iter().all(|message| message.channel_id == "C1"));
+        // The cursor moves past the bot post even though it was dropped.
+        assert_eq!(latest.as_deref(), Some("1700000004.000001"));
+    }
+
+    #[test]
+    fn empty_allow_list_denies_everyone() {
+        let (messages, _) = parse_history_response(&history_fixture(), "C1", &[]).unwrap();
+        assert!(messages.is_empty());
+        let bot = SlackBot::new("xoxb".into(), vec![], "C1".into());
+        assert!(!bot.is_authorized("U1"));
+    }
+
+    #[test]
+    fn ts_key_orders_numerically() {
+        assert!(ts_key("1700000010.000001") > ts_key("1700000009.999999"));
+        assert!(ts_key("1700000010.000100") > ts_key("1700000010.000099"));
+    }
+
+    #[test]
+    fn outbound_text_bolds_sender_and_escapes_mrkdwn() {
+        assert_eq!(
+            outbound_text("--- Message from architect ---\nMerged <#42> & moved on"),
+            "*architect*\nMerged &lt;#42&gt; &amp; moved on"
+        );
+        assert_eq!(outbound_text("plain"), "plain");
+    }
+
+    #[test]
+    fn bot_talks_to_mock_web_api() {
+        let server = MockSlack::start(|method| match method {
+            "conversations.history" => history_fixture(),
+            _ => serde_json::json!({ "ok": true, "ts": "1700000005.000001" }),
+        });
+        let mut bot = SlackBot::new("xoxb-test".into(), vec!["U1".into()], "C1".into())
+            .with_api_base(&server.base);
+        bot.last_ts = "1700000000.000000".into();
+
+        let ts = bot.post_message("C2", "hello").unwrap();
+        assert_eq!(ts, "1700000005.000001");
+        bot.update_message("C2", &ts, "hello again").unwrap();
+        let messages = bot.poll_commands().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(bot.last_ts, "1700000004.000001");
+
+        let posts = server.calls("chat.postMessage");
+        assert_eq!(posts[0].authorization, "Bearer xoxb-test");
+        assert_eq!(posts[0].body["channel"], "C2");
+        assert_eq!(posts[0].body["text"], "hello");
+        assert_eq!(server.calls("chat.update")[0].body["ts"], ts);
+        let history = server.calls("conversations.history");
+        assert!(history[0].query.contains("channel=C1"));
+        assert!(history[0].query.contains("oldest=1700000000.000000"));
+    }
+
+    #[test]
+    fn api_error_envelope_is_an_error() {
+        let server =
+            MockSlack::start(|_| serde_json::json!({ "ok": false, "error": "channel_not_found" }));
+        let bot =
+            SlackBot::new("xoxb-test".into(), vec![], "C1".into()).with_api_base(&server.base);
+        let error = bot.post_message("C404", "hello").unwrap_err();
+        assert!(error.to_string().contains("channel_not_found"));
+    }
+
+    #[test]
+    fn from_config_requires_commands_channel() {
+        let mut config = ChannelConfig {
+            bot_token: Some("xoxb-test".into()),
+            allowed_slack_user_ids: vec!["U1".into()],
+            permissions: Default::default(),
+            api_base: Some("http://127.0.0.1:9/api/".into()),
+            ..Default::default()
+        };
+        assert!(SlackBot::from_config(&config).is_none());
+        config.commands_channel_id = Some("C1".into());
+        let bot = SlackBot::from_config(&config).unwrap();
+        assert_eq!(bot.commands_channel_id(), "C1");
+        assert_eq!(bot.api_base, "http://127.0.0.1:9/api");
+        assert!(bot.is_authorized("U1"));
+    }
+}
@@ -0,0 +1,410 @@
+//! Slack bridge orchestration for the daemon poll loop.
+//!
+//! Mirrors the Discord bridge: commands-channel messages from allow-listed
+//! Slack members are either `$` commands or directives routed to the user
+//! role's `talks_to` targets, team events are posted to the events/agents
+//! channels, and a board digest is kept up to date in the board channel.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use tracing::{info, warn};
+
+use super::discord_bridge::{
+    count_done_today, event_channel_id, event_summary_line, event_title, is_noise_event,
+    is_telemetry_only_event, summarize_in_progress_tasks, summarize_review_tasks,
+    summarize_todo_tasks,
+};
+use super::operator_commands::OperatorSource;
+use super::*;
+use crate::task::load_tasks_from_dir;
+use crate::team::config::{ChannelConfig, RoleType, TeamConfig};
+use crate::team::events::{TeamEvent, read_events};
+use crate::team::inbox;
+use crate::team::slack::{SlackBot, escape_mrkdwn};
+
+const SLACK_BOARD_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+const SLACK_BOARD_SYNC_KEY: &str = "slack::board_sync";
+/// Events posted per sync cycle; Slack allows roughly one post per second
+/// per channel before answering 429.
+const SLACK_EVENT_BATCH_LIMIT: usize = 5;
+
+pub(super) fn build_slack_bot(team_config: &TeamConfig) -> Option<SlackBot> {
+    slack_channel_config(team_config).and_then(SlackBot::from_config)
+}
+
+impl TeamDaemon {
+    pub(super) fn process_slack_queue(&mut self) -> Result<()> {
+        self.poll_slack();
+        self.sync_slack_events()?;
+        self.sync_slack_board();
+        self.deliver_user_channel_inbox()
+    }
+
+    fn poll_slack(&mut self) {
+        let Some(bot) = self.slack_bot.as_mut() else {
+            return;
+        };
+        let messages = match bot.poll_commands() {
+            Ok(messages) => messages,
+            Err(error) => {
+                warn!(error = %error, "slack poll failed");
+                return;
+            }
+        };
+        if messages.is_empty() {
+            return;
+        }
+
+        let root = inbox::inboxes_root(&self.config.project_root);
+        let targets: Vec<String> = self
+            .config
+            .team_config
+            .roles
+            .iter()
+            .filter(|role| {
+                role.role_type == RoleType::User && role.channel.as_deref() == Some("slack")
+            })
+            .flat_map(|role| role.talks_to.iter().cloned())
+            .collect();
+
+        for msg in messages {
+            info!(
+                from_user = %msg.from_user_id,
+                text_len = msg.text.len(),
+                "slack inbound"
+            );
+
+            let source = OperatorSource {
+                transport: "slack",
+                user: &msg.from_user_id,
+                member: None,
+                location: &msg.channel_id,
+            };
+            if let Some(reply) = self.handle_operator_message(source, &msg.text) {
+                if let Some(bot) = self.slack_bot.as_ref()
+                    && let Err(error) = bot.send_command_reply(&reply)
+                {
+                    warn!(error = %error, "failed to send slack command reply");
+                }
+                continue;
+            }
+
+            for target in &targets {
+                let inbox_msg = inbox::InboxMessage::new_send("human", target, &msg.text);
+                if let Err(error) = inbox::deliver_to_inbox(&root, &inbox_msg) {
+                    warn!(
+                        to = %target,
+                        error = %error,
+                        "failed to deliver slack message to inbox"
+                    );
+                }
+            }
+
+            self.record_message_routed("human", "slack");
+        }
+    }
+
+    fn sync_slack_events(&mut self) -> Result<()> {
+        let Some(bot) = self.slack_bot.as_ref() else {
+            return Ok(());
+        };
+        let Some(config) = slack_channel_config(&self.config.team_config) else {
+            return Ok(());
+        };
+
+        let event_path = self.event_sink.path().to_path_buf();
+        let events = read_events(&event_path)
+            .with_context(|| format!("failed to read event log {}", event_path.display()))?;
+        if events.len() < self.slack_event_cursor {
+            self.slack_event_cursor = 0;
+        }
+
+        let mut sent = 0;
+        for event in events.iter().skip(self.slack_event_cursor) {
+            if sent >= SLACK_EVENT_BATCH_LIMIT {
+                break;
+            }
+            if !is_telemetry_only_event(event)
+                && !is_noise_event(event)
+                && let Some(channel_id) = event_channel_id(config, event)
+                && let Err(error) = bot.post_message(channel_id, &render_event_text(event))
+            {
+                warn!(error = %error, "slack event send failed; will retry next cycle");
+                break;
+            }
+            sent += 1;
+        }
+        self.slack_event_cursor += sent;
+        Ok(())
+    }
+
+    fn sync_slack_board(&mut self) {
+        let Some(bot) = self.slack_bot.as_ref() else {
+            return;
+        };
+        let Some(channel_id) = slack_channel_config(&self.config.team_config)
+            .and_then(|config| config.board_channel_id.as_deref())
+        else {
+            return;
+        };
+        if self
+            .intervention_cooldowns
+            .get(SLACK_BOARD_SYNC_KEY)
+            .is_some_and(|last| last.elapsed() < SLACK_BOARD_SYNC_INTERVAL)
+        {
+            return;
+        }
+        self.intervention_cooldowns
+            .insert(SLACK_BOARD_SYNC_KEY.to_string(), Instant::now());
+
+        let digest = match build_board_digest(&self.config.project_root) {
+            Ok(digest) => digest,
+            Err(error) => {
+                warn!(error = %error, "failed to build Slack board digest");
+                return;
+            }
+        };
+
+        // Keep one digest message per channel and edit it in place so the
+        // board channel reads as a dashboard rather than a feed.
+        let ts_path = slack_board_ts_path(&self.config.project_root);
+        if let Some(ts) = read_slack_board_ts(&ts_path) {
+            if let Err(error) = bot.update_message(channel_id, &ts, &digest) {
+                warn!(channel_id, ts, error = %error, "failed to update Slack board digest");
+            }
+            return;
+        }
+        match bot.post_message(channel_id, &digest) {
+            Ok(ts) => {
+                if let Err(error) = write_slack_board_ts(&ts_path, &ts) {
+                    warn!(error = %error, "failed to persist Slack board digest ts");
+                }
+            }
+            Err(error) => {
+                warn!(channel_id, error = %error, "failed to post Slack board digest");
+            }
+        }
+    }
+}
+
+fn slack_channel_config(team_config: &TeamConfig) -> Option<&ChannelConfig> {
+    team_config
+        .roles
+        .iter()
+        .find(|role| role.role_type == RoleType::User && role.channel.as_deref() == Some("slack"))
+        .and_then(|role| role.channel_config.as_ref())
+}
+
+/// Render an event as Slack mrkdwn. The shared summaries use Discord's
+/// `**bold**`; Slack spells bold with single asterisks.
+fn render_event_text(event: &TeamEvent) -> String {
+    let title = escape_mrkdwn(&event_title(event));
+    match event_summary_line(event) {
+        Some(summary) => format!("*{title}*\n{}", escape_mrkdwn(&summary).replace("**", "*")),
+        None => format!("*{title}*"),
+    }
+}
+
+fn build_board_digest(project_root: &Path) -> Result<String> {
+    let tasks_dir = project_root
+        .join(".batty")
+        .join("team_config")
+        .join("board")
+        .join("tasks");
+    let tasks = load_tasks_from_dir(&tasks_dir)?;
+    let now = Local::now();
+
+    let mut digest = String::from("*Batty Board*\n");
+    for (label, section) in [
+        ("In Progress", summarize_in_progress_tasks(&tasks)),
+        ("Review", summarize_review_tasks(&tasks)),
+        ("Todo", summarize_todo_tasks(&tasks)),
+    ] {
+        digest.push_str(&format!(
+            "\n*{label} ({})*\n{}\n",
+            section.total,
+            escape_mrkdwn(&section.rendered)
+        ));
+    }
+    digest.push_str(&format!(
+        "\n_Done today: {} · updated {}_",
+        count_done_today(&tasks, now),
+        now.format("%Y-%m-%d %H:%M %Z")
+    ));
+    Ok(digest)
+}
+
+fn slack_board_ts_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join("slack_board_ts.txt")
+}
+
+fn read_slack_board_ts(path: &Path) -> Option<String> {
+    let value = std::fs::read_to_string(path).ok()?;
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn write_slack_board_ts(path: &Path, ts: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, format!("{ts}\n"))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::config::RoleDef;
+    use crate::team::slack::mock::MockSlack;
+    use crate::team::test_support::{TestDaemonBuilder, engineer_member, write_owned_task_file};
+
+    fn slack_respond(method: &str) -> serde_json::Value {
+        match method {
+            "conversations.history" => serde_json::json!({
+                "ok": true,
+                "messages": [
+                    { "type": "message", "user": "U-intruder", "text": "$stop", "ts": "9000000004.000000" },
+                    { "type": "message", "user": "U-ops", "text": "$approve #7", "ts": "9000000003.000000" },
+                    { "type": "message", "user": "U-ops", "text": "$send eng-1 rebase first", "ts": "9000000002.000000" },
+                    { "type": "message", "user": "U-ops", "text": "Focus on the flaky tests", "ts": "9000000001.000000" }
+                ]
+            }),
+            _ => serde_json::json!({ "ok": true, "ts": "9000000010.000000" }),
+        }
+    }
+
+    fn slack_daemon(tmp: &Path, api_base: &str) -> TeamDaemon {
+        let human = RoleDef {
+            name: "human".to_string(),
+            role_type: RoleType::User,
+            talks_to: vec!["architect".to_string()],
+            channel: Some("slack".to_string()),
+            channel_config: Some(ChannelConfig {
+                bot_token: Some("xoxb-test".to_string()),
+                allowed_slack_user_ids: vec!["U-ops".to_string()],
+                permissions: Default::default(),
+                commands_channel_id: Some("C-commands".to_string()),
+                events_channel_id: Some("C-events".to_string()),
+                board_channel_id: Some("C-board".to_string()),
+                api_base: Some(api_base.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut daemon = TestDaemonBuilder::new(tmp)
+            .members(vec![engineer_member("eng-1", Some("manager"), false)])
+            .build();
+        daemon.config.team_config.roles.push(human);
+        daemon.slack_bot = build_slack_bot(&daemon.config.team_config);
+        daemon
+    }
+
+    #[test]
+    fn inbound_commands_and_directives_respect_allow_list() {
+        let tmp = tempfile::tempdir().unwrap();
+        let server = MockSlack::start(slack_respond);
+        let mut daemon = slack_daemon(tmp.path(), &server.base);
+        let board_dir = tmp.path().join(".batty").join("team_config").join("board");
+        write_owned_task_file(tmp.path(), 7, "review me", "review", "eng-1");
+
+        daemon.poll_slack();
+
+        let root = inbox::inboxes_root(tmp.path());
+        let architect = inbox::pending_messages(&root, "architect").unwrap();
+        assert_eq!(architect.len(), 1);
+        assert_eq!(architect[0].from, "human");
+        assert_eq!(architect[0].body, "Focus on the flaky tests");
+        let engineer = inbox::pending_messages(&root, "eng-1").unwrap();
+        assert_eq!(engineer.len(), 1);
+        assert_eq!(engineer[0].body, "rebase first");
+
+        let task_path = crate::team::task_cmd::find_task_path(&board_dir, 7).unwrap();
+        let task = crate::task::Task::from_file(&task_path).unwrap();
+        assert_eq!(task.status, "done");
+
+        let replies = server.calls("chat.postMessage");
+        assert_eq!(replies.len(), 2);
+        assert!(
+            replies
+                .iter()
+                .all(|call| call.body["channel"] == "C-commands")
+        );
+        assert!(
+            replies[1].body["text"]
+                .as_str()
+                .unwrap()
+                .contains("Task #7 approved")
+        );
+        // The unauthorized `$stop` was dropped without a reply.
+        assert!(
+            replies
+                .iter()
+                .all(|call| !call.body["text"].as_str().unwrap().contains("Stopping"))
+        );
+    }
+
+    #[test]
+    fn events_and_board_digest_are_posted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let server = MockSlack::start(slack_respond);
+        let mut daemon = slack_daemon(tmp.path(), &server.base);
+        daemon.slack_event_cursor = 0;
+        write_owned_task_file(tmp.path(), 3, "ship slack", "in-progress", "eng-1");
+
+        daemon.emit_event(TeamEvent::daemon_heartbeat(60));
+        daemon.emit_event(TeamEvent::task_escalated(
+            "eng-1",
+            "Task #3: ship slack",
+            Some("blocked on <token> & scopes"),
+        ));
+        daemon.sync_slack_events().unwrap();
+        daemon.sync_slack_board();
+        // A second board sync inside the interval is a no-op.
+        daemon.sync_slack_board();
+
+        let posts = server.calls("chat.postMessage");
+        let events: Vec<_> = posts
+            .iter()
+            .filter(|call| call.body["channel"] == "C-events")
+            .collect();
+        assert_eq!(events.len(), 1);
+        let text = events[0].body["text"].as_str().unwrap();
+        assert!(text.starts_with("*🚨 Task Escalated — #3*"));
+        assert!(text.contains("&lt;token&gt; &amp; scopes"));
+        assert!(!text.contains("**"));
+
+        let board: Vec<_> = posts
+            .iter()
+            .filter(|call| call.body["channel"] == "C-board")
+            .collect();
+        assert_eq!(board.len(), 1);
+        assert!(
+            board[0].body["text"]
+                .as_str()
+                .unwrap()
+                .contains("*In Progress (1)*\n#3")
+        );
+        assert_eq!(
+            read_slack_board_ts(&slack_board_ts_path(tmp.path())).as_deref(),
+            Some("9000000010.000000")
+        );
+
+        daemon.intervention_cooldowns.remove(SLACK_BOARD_SYNC_KEY);
+        daemon.sync_slack_board();
+        let updates = server.calls("chat.update");
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].body["ts"], "9000000010.000000");
+    }
+
+    #[test]
+    fn build_slack_bot_requires_slack_user_role() {
+        let tmp = tempfile::tempdir().unwrap();
+        let daemon = TestDaemonBuilder::new(tmp.path()).build();
+        assert!(build_slack_bot(&daemon.config.team_config).is_none());
+    }
+}
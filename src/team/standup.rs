@@ -948,6 +948,13 @@ mod tests {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1034,6 +1041,13 @@ mod tests {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1147,6 +1161,13 @@ mod tests {
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1527,6 +1548,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1597,6 +1625,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1656,6 +1691,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1712,6 +1754,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1748,6 +1797,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1810,6 +1866,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -1904,6 +1967,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
@@ -2002,6 +2072,13 @@ last_verified: 2026-04-05
             layout: None,
             cost: Default::default(),
             grafana: Default::default(),
+            api: Default::default(),
+            budget: Default::default(),
+            agents: Default::default(),
+            forge: Default::default(),
+            webhooks: Default::default(),
+            traces: Default::default(),
+            prometheus: Default::default(),
             use_shim: false,
             use_sdk_mode: false,
             auto_respawn_on_crash: false,
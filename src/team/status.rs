@@ -166,6 +166,20 @@ struct PersistedDaemonHealthState {
     optional_subsystem_disabled_remaining_secs: HashMap<String, u64>,
 }
 
+/// One `pattern_action_taken` event, newest first in status output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct PatternActionStatus {
+    pub(crate) ts: u64,
+    pub(crate) rule: String,
+    pub(crate) action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) task: Option<String>,
+    pub(crate) success: bool,
+    pub(crate) details: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub(crate) struct OptionalSubsystemStatus {
     pub(crate) name: String,
@@ -322,8 +336,14 @@ pub(crate) struct TeamStatusJsonReport {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) optional_subsystems: Option<Vec<OptionalSubsystemStatus>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pattern_actions: Option<Vec<PatternActionStatus>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) engineer_profiles:
         Option<Vec<crate::team::telemetry_db::EngineerPerformanceProfileRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) budget: Option<Vec<crate::team::budget::BudgetHeadroomRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) webhook_dead_letters: Option<Vec<crate::team::webhooks::WebhookDeadLetter>>,
     pub(crate) members: Vec<TeamStatusRow>,
 }
 
@@ -1083,6 +1103,61 @@ pub(crate) fn load_optional_subsystem_statuses(
         .collect()
 }
 
+/// The most recent `limit` pattern-rule actions, newest first.
+pub(crate) fn load_recent_pattern_actions(
+    project_root: &Path,
+    limit: usize,
+) -> Vec<PatternActionStatus> {
+    let Ok(events) = events::read_events(&team_events_path(project_root)) else {
+        return Vec::new();
+    };
+    events
+        .into_iter()
+        .rev()
+        .filter(|event| event.event == crate::team::pattern_rules::PATTERN_ACTION_EVENT)
+        .take(limit)
+        .map(|event| PatternActionStatus {
+            ts: event.ts,
+            rule: event.reason.unwrap_or_default(),
+            action: event.action_type.unwrap_or_default(),
+            role: event.role,
+            task: event.task,
+            success: event.success.unwrap_or(false),
+            details: event.details.unwrap_or_default(),
+        })
+        .collect()
+}
+
+pub(crate) fn format_pattern_actions(actions: &[PatternActionStatus]) -> String {
+    let mut lines = vec![
+        "Pattern Actions".to_string(),
+        format!(
+            "{:<20} {:<20} {:<20} {:<8} {}",
+            "RULE", "ACTION", "TARGET", "RESULT", "DETAILS"
+        ),
+    ];
+    if actions.is_empty() {
+        lines.push("No pattern-rule actions recorded.".to_string());
+    }
+    for action in actions {
+        let target = match (action.role.as_deref(), action.task.as_deref()) {
+            (Some(role), Some(task)) => format!("{role} #{task}"),
+            (Some(role), None) => role.to_string(),
+            (None, Some(task)) => format!("#{task}"),
+            (None, None) => "-".to_string(),
+        };
+        lines.push(format!(
+            "{:<20} {:<20} {:<20} {:<8} {}",
+            action.rule,
+            action.action,
+            target,
+            if action.success { "ok" } else { "failed" },
+            action.details
+        ));
+    }
+    lines.join("\n")
+}
+
 pub(crate) fn format_optional_subsystem_statuses(statuses: &[OptionalSubsystemStatus]) -> String {
     let mut lines = vec![
         "Optional Subsystems".to_string(),
@@ -2082,8 +2157,11 @@ pub(crate) struct TeamStatusJsonReportInput {
     pub(crate) active_tasks: Vec<StatusTaskEntry>,
     pub(crate) review_queue: Vec<StatusTaskEntry>,
     pub(crate) optional_subsystems: Option<Vec<OptionalSubsystemStatus>>,
+    pub(crate) pattern_actions: Option<Vec<PatternActionStatus>>,
     pub(crate) engineer_profiles:
         Option<Vec<crate::team::telemetry_db::EngineerPerformanceProfileRow>>,
+    pub(crate) budget: Option<Vec<crate::team::budget::BudgetHeadroomRow>>,
+    pub(crate) webhook_dead_letters: Option<Vec<crate::team::webhooks::WebhookDeadLetter>>,
     pub(crate) members: Vec<TeamStatusRow>,
 }
 
@@ -2140,7 +2218,10 @@ pub(crate) fn build_team_status_json_report(
         active_tasks,
         review_queue,
         optional_subsystems,
+        pattern_actions,
         engineer_profiles,
+        budget,
+        webhook_dead_letters,
         members,
     } = input;
     let health = build_team_status_health(&members, session_running, paused);
@@ -2157,7 +2238,10 @@ pub(crate) fn build_team_status_json_report(
         active_tasks,
         review_queue,
         optional_subsystems,
+        pattern_actions,
         engineer_profiles,
+        budget,
+        webhook_dead_letters,
         members,
     }
 }
@@ -3931,6 +4015,8 @@ mod tests {
                 completed: None,
                 description: "done".to_string(),
                 batty_config: None,
+                scope: None,
+                acceptance: Vec::new(),
                 source_path: repo
                     .join(".batty")
                     .join("team_config")
@@ -4718,6 +4804,7 @@ mod tests {
             active_tasks: Vec::new(),
             review_queue: Vec::new(),
             optional_subsystems: None,
+            pattern_actions: None,
             engineer_profiles: Some(vec![
                 crate::team::telemetry_db::EngineerPerformanceProfileRow {
                     role: "eng-1".to_string(),
@@ -4728,6 +4815,8 @@ mod tests {
                     context_exhaustion_frequency: Some(0.0),
                 },
             ]),
+            budget: None,
+            webhook_dead_letters: None,
             members: vec![TeamStatusRow {
                 name: "eng-1".to_string(),
                 role: "engineer".to_string(),
@@ -5560,7 +5649,10 @@ mod tests {
                 failed_test_state: None,
             }],
             optional_subsystems: None,
+            pattern_actions: None,
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             members: vec![
                 TeamStatusRow {
                     name: "eng-1".to_string(),
@@ -5889,7 +5981,10 @@ mod tests {
             active_tasks: Vec::new(),
             review_queue: Vec::new(),
             optional_subsystems: None,
+            pattern_actions: None,
             engineer_profiles: None,
+            budget: None,
+            webhook_dead_letters: None,
             members: Vec::new(),
         });
         let json = serde_json::to_value(&report).unwrap();
@@ -6623,4 +6718,37 @@ mod tests {
         assert!(formatted.contains("Auto-merge Rate: 100%"));
         assert!(formatted.contains("In Review: 1"));
     }
+
+    #[test]
+    fn recent_pattern_actions_are_listed_newest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut sink = crate::team::events::EventSink::new(&team_events_path(tmp.path())).unwrap();
+        sink.emit(crate::team::events::TeamEvent::pattern_action_taken(
+            "stuck",
+            "bench_engineer",
+            Some("eng-1"),
+            Some(12),
+            true,
+            "benched eng-1",
+        ))
+        .unwrap();
+        sink.emit(crate::team::events::TeamEvent::pattern_action_taken(
+            "stuck",
+            "open_fixup_task",
+            None,
+            None,
+            false,
+            "board locked",
+        ))
+        .unwrap();
+
+        let actions = load_recent_pattern_actions(tmp.path(), 10);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].action, "open_fixup_task");
+        let formatted = format_pattern_actions(&actions);
+        assert!(formatted.starts_with("Pattern Actions"));
+        assert!(formatted.contains("eng-1 #12"));
+        assert!(formatted.contains("failed"));
+        assert_eq!(load_recent_pattern_actions(tmp.path(), 1).len(), 1);
+    }
 }
@@ -6,6 +6,7 @@ use tracing::warn;
 
 use super::{GeneratedTask, TaskSpec};
 use crate::task::load_tasks_from_dir;
+use crate::team::board_store::{BoardStore, NewTask};
 
 #[derive(Debug, Deserialize)]
 struct Frontmatter {
@@ -97,32 +98,17 @@ pub fn implementation_work_summary(
     }
 }
 
-fn build_create_task_args(spec: &TaskSpec) -> Vec<String> {
-    let mut args = vec![
-        "create".to_string(),
-        spec.title.clone(),
-        "--body".to_string(),
-        spec.body.clone(),
-    ];
-    if let Some(priority) = spec.priority.as_deref() {
-        args.push("--priority".to_string());
-        args.push(priority.to_string());
-    }
-    if !spec.tags.is_empty() {
-        args.push("--tags".to_string());
-        args.push(spec.tags.join(","));
-    }
-    if !spec.depends_on.is_empty() {
-        args.push("--depends-on".to_string());
-        args.push(
-            spec.depends_on
-                .iter()
-                .map(u32::to_string)
-                .collect::<Vec<_>>()
-                .join(","),
-        );
+/// Generated tasks land in `todo` so the dispatcher can pick them up
+/// without a separate triage pass.
+fn new_board_task(spec: &TaskSpec) -> NewTask {
+    NewTask {
+        title: spec.title.clone(),
+        body: spec.body.clone(),
+        status: Some("todo".to_string()),
+        priority: spec.priority.clone(),
+        tags: spec.tags.clone(),
+        depends_on: spec.depends_on.clone(),
     }
-    args
 }
 
 fn normalize_generated_text(value: &str) -> String {
@@ -295,11 +281,9 @@ pub(crate) fn dedupe_generated_tasks(
     deduped
 }
 
-fn create_board_tasks_with_program(
-    specs: &[TaskSpec],
-    board_dir: &Path,
-    program: &str,
-) -> Result<Vec<u32>> {
+/// Create board tasks from parsed specs, skipping log dumps and duplicates
+/// of tasks that are already open.
+pub fn create_board_tasks(specs: &[TaskSpec], board_dir: &Path) -> Result<Vec<u32>> {
     if !board_dir.exists() {
         anyhow::bail!("board directory does not exist: {}", board_dir.display());
     }
@@ -310,58 +294,21 @@ fn create_board_tasks_with_program(
         .filter_map(sanitize_generated_task)
         .collect::<Vec<_>>();
     let deduped = dedupe_generated_tasks(&existing_tasks, generated);
+    let store = BoardStore::open(board_dir);
     let mut created_ids = Vec::with_capacity(deduped.len());
     for sanitized in deduped {
-        let args = build_create_task_args(&sanitized);
-        let arg_refs = args.iter().map(String::as_str).collect::<Vec<_>>();
-        let output = crate::team::board_cmd::run_board_with_program(program, board_dir, &arg_refs)
+        let id = store
+            .create(&new_board_task(&sanitized))
             .with_context(|| format!("failed to create board task '{}'", sanitized.title))?;
-
-        // kanban-md output has evolved over versions: older releases print
-        // `Created task #629\n`, newer ones (0.32+) print
-        // `Created task #629: <title>\n`. Parse only the leading run of
-        // digits after `#` so the parser works across both shapes instead
-        // of crashing planning responses whenever a task is created.
-        let raw = output.stdout.trim();
-        let after_prefix = raw.strip_prefix("Created task #").unwrap_or(raw);
-        let digits: String = after_prefix
-            .chars()
-            .take_while(|c| c.is_ascii_digit())
-            .collect();
-        let parsed_id = digits
-            .parse::<u32>()
-            .with_context(|| format!("invalid task id returned by kanban-md: '{raw}'"))?;
-        created_ids.push(parsed_id);
+        created_ids.push(id);
     }
     Ok(created_ids)
 }
 
-/// Create board tasks from parsed specs by shelling out to kanban-md.
-pub fn create_board_tasks(specs: &[TaskSpec], board_dir: &Path) -> Result<Vec<u32>> {
-    create_board_tasks_with_program(specs, board_dir, "kanban-md")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn setup_fake_kanban(tmp: &tempfile::TempDir) -> std::path::PathBuf {
-        let fake_bin = tmp.path().join("fake-bin");
-        std::fs::create_dir_all(&fake_bin).unwrap();
-        let script = fake_bin.join("kanban-md");
-        std::fs::write(
-            &script,
-            "#!/bin/bash\nset -euo pipefail\nif [ \"$1\" != \"create\" ]; then exit 1; fi\nshift\ntitle=\"$1\"\nshift\nbody=\"\"\npriority=\"high\"\ntags=\"\"\ndepends_on=\"\"\nwhile [ $# -gt 0 ]; do\n  case \"$1\" in\n    --body) body=\"$2\"; shift 2 ;;\n    --priority) priority=\"$2\"; shift 2 ;;\n    --tags) tags=\"$2\"; shift 2 ;;\n    --depends-on) depends_on=\"$2\"; shift 2 ;;\n    --dir) board_dir=\"$2\"; shift 2 ;;\n    *) shift ;;\n  esac\ndone\nmkdir -p \"$board_dir/tasks\"\ncount=$(find \"$board_dir/tasks\" -maxdepth 1 -name '*.md' | wc -l | tr -d ' ')\nid=$((count + 1))\nprintf -- '---\\nid: %s\\ntitle: %s\\nstatus: todo\\npriority: %s\\n' \"$id\" \"$title\" \"$priority\" > \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"\nif [ -n \"$tags\" ]; then printf 'tags: [%s]\\n' \"$tags\" >> \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"; fi\nif [ -n \"$depends_on\" ]; then printf 'depends_on: [%s]\\n' \"$depends_on\" >> \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"; fi\nprintf -- '---\\n\\n%s\\n' \"$body\" >> \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"\nprintf 'Created task #%s\\n' \"$id\"\n",
-        )
-        .unwrap();
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
-        }
-        fake_bin
-    }
-
     fn write_task_file(board_dir: &Path, id: u32, title: &str, status: &str) {
         std::fs::write(
             board_dir.join("tasks").join(format!("{id:03}-task.md")),
@@ -537,7 +484,6 @@ No title here."#;
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
 
         let specs = vec![
             TaskSpec {
@@ -556,9 +502,7 @@ No title here."#;
             },
         ];
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
         assert_eq!(ids, vec![1, 2]);
         let tasks = crate::task::load_tasks_from_dir(&board_dir.join("tasks")).unwrap();
         assert_eq!(tasks.len(), 2);
@@ -571,7 +515,6 @@ No title here."#;
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
         let specs = parse_planning_response(
             r#"---
 title: "Drain review backlog for task 736"
@@ -585,9 +528,7 @@ Task body:
 "#,
         );
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
         std::fs::write(
             board_dir.join("tasks").join("736-reviewed.md"),
             "---\nid: 736\ntitle: Reviewed source\nstatus: done\npriority: high\nclass: standard\n---\n\nReview resolved.\n",
@@ -620,8 +561,8 @@ Task body:
     }
 
     #[test]
-    fn test_create_board_tasks_formats_command() {
-        let args = build_create_task_args(&TaskSpec {
+    fn new_board_task_carries_spec_metadata_into_todo() {
+        let task = new_board_task(&TaskSpec {
             title: "Plan tact".into(),
             body: "Create the daemon prompt.".into(),
             priority: Some("high".into()),
@@ -629,19 +570,15 @@ Task body:
             tags: vec!["tact".into(), "daemon".into()],
         });
         assert_eq!(
-            args,
-            vec![
-                "create",
-                "Plan tact",
-                "--body",
-                "Create the daemon prompt.",
-                "--priority",
-                "high",
-                "--tags",
-                "tact,daemon",
-                "--depends-on",
-                "17",
-            ]
+            task,
+            NewTask {
+                title: "Plan tact".into(),
+                body: "Create the daemon prompt.".into(),
+                status: Some("todo".into()),
+                priority: Some("high".into()),
+                tags: vec!["tact".into(), "daemon".into()],
+                depends_on: vec![17],
+            }
         );
     }
 
@@ -650,7 +587,6 @@ Task body:
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
 
         let raw_body = "\
 running 3144 tests
@@ -676,9 +612,7 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
             tags: vec!["stability".into(), "tmux".into()],
         }];
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
         assert!(ids.is_empty());
 
         let tasks = crate::task::load_tasks_from_dir(&board_dir.join("tasks")).unwrap();
@@ -690,7 +624,6 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
 
         write_task_file(
             &board_dir,
@@ -707,9 +640,7 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
             tags: vec!["stability".into()],
         }];
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
         assert!(ids.is_empty());
 
         let tasks = crate::task::load_tasks_from_dir(&board_dir.join("tasks")).unwrap();
@@ -722,7 +653,6 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
 
         write_task_file(&board_dir, 41, "Ship planning telemetry", "todo");
 
@@ -734,9 +664,7 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
             tags: vec!["tact".into()],
         }];
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
         assert!(ids.is_empty());
 
         let tasks = crate::task::load_tasks_from_dir(&board_dir.join("tasks")).unwrap();
@@ -749,7 +677,6 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
 
         let specs = vec![
             TaskSpec {
@@ -768,9 +695,7 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
             },
         ];
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
         assert_eq!(ids, vec![1]);
 
         let tasks = crate::task::load_tasks_from_dir(&board_dir.join("tasks")).unwrap();
@@ -778,62 +703,11 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
         assert_eq!(tasks[0].title, "Plan planning telemetry");
     }
 
-    /// Stand up a fake kanban-md binary that emits the new `Created task
-    /// #ID: Title` output shape introduced in kanban-md 0.32+. The previous
-    /// implementation only understood the older `Created task #ID` form and
-    /// planning cycles would crash with
-    /// `invalid task id returned by kanban-md: '629: Auto-repair…'`.
-    fn setup_fake_kanban_with_title_suffix(tmp: &tempfile::TempDir) -> std::path::PathBuf {
-        let fake_bin = tmp.path().join("fake-bin-titled");
-        std::fs::create_dir_all(&fake_bin).unwrap();
-        let script = fake_bin.join("kanban-md");
-        std::fs::write(
-            &script,
-            "#!/bin/bash\nset -euo pipefail\nif [ \"$1\" != \"create\" ]; then exit 1; fi\nshift\ntitle=\"$1\"\nshift\nbody=\"\"\npriority=\"high\"\nwhile [ $# -gt 0 ]; do\n  case \"$1\" in\n    --body) body=\"$2\"; shift 2 ;;\n    --priority) priority=\"$2\"; shift 2 ;;\n    --tags) shift 2 ;;\n    --depends-on) shift 2 ;;\n    --dir) board_dir=\"$2\"; shift 2 ;;\n    *) shift ;;\n  esac\ndone\nmkdir -p \"$board_dir/tasks\"\ncount=$(find \"$board_dir/tasks\" -maxdepth 1 -name '*.md' | wc -l | tr -d ' ')\nid=$((count + 1))\nprintf -- '---\\nid: %s\\ntitle: %s\\nstatus: todo\\npriority: %s\\n---\\n\\n%s\\n' \"$id\" \"$title\" \"$priority\" \"$body\" > \"$board_dir/tasks/$(printf '%03d' \"$id\")-task.md\"\nprintf 'Created task #%s: %s\\n' \"$id\" \"$title\"\n",
-        )
-        .unwrap();
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
-        }
-        fake_bin
-    }
-
-    #[test]
-    fn create_board_tasks_parses_new_output_shape_with_title_suffix() {
-        // Regression: kanban-md 0.32+ appends `: <title>` after the ID in
-        // its `Created task #` output, and the parser used to crash planning
-        // with `invalid task id returned by kanban-md: '629: Auto-repair…'`.
-        let tmp = tempfile::tempdir().unwrap();
-        let board_dir = tmp.path().join("board");
-        std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban_with_title_suffix(&tmp).join("kanban-md");
-
-        let specs = vec![TaskSpec {
-            title: "Auto-repair legacy telemetry schemas before event writes fail".into(),
-            body: "Planning response body.".into(),
-            priority: Some("high".into()),
-            depends_on: vec![],
-            tags: vec!["stability".into()],
-        }];
-
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
-        assert_eq!(
-            ids,
-            vec![1],
-            "parser should extract numeric id from new output shape"
-        );
-    }
-
     #[test]
     fn create_board_tasks_allows_new_reopen_after_terminal_duplicate() {
         let tmp = tempfile::tempdir().unwrap();
         let board_dir = tmp.path().join("board");
         std::fs::create_dir_all(board_dir.join("tasks")).unwrap();
-        let fake_kanban = setup_fake_kanban(&tmp).join("kanban-md");
 
         write_task_file(
             &board_dir,
@@ -850,10 +724,8 @@ test result: FAILED. 3011 passed; 14 failed; 119 ignored; 0 measured; 0 filtered
             tags: vec!["stability".into()],
         }];
 
-        let ids =
-            create_board_tasks_with_program(&specs, &board_dir, fake_kanban.to_str().unwrap())
-                .unwrap();
-        assert_eq!(ids, vec![2]);
+        let ids = create_board_tasks(&specs, &board_dir).unwrap();
+        assert_eq!(ids, vec![42]);
 
         let tasks = crate::task::load_tasks_from_dir(&board_dir.join("tasks")).unwrap();
         assert_eq!(tasks.len(), 2);
@@ -11,6 +11,7 @@ use serde_yaml::{Mapping, Value};
 use crate::task::Task;
 
 use super::board::{read_workflow_metadata, write_workflow_metadata};
+use super::board_store::{BoardLock, BoardStore, set_task_status};
 use super::workflow::{ReviewDisposition, TaskState, can_transition};
 
 pub fn cmd_transition(board_dir: &Path, task_id: u32, target: &str) -> Result<()> {
@@ -40,22 +41,30 @@ pub(crate) fn transition_task_with_attribution(
     target: &str,
     attribution: StatusTransitionAttribution,
 ) -> Result<()> {
-    let task_path = find_task_path(board_dir, task_id)?;
-    let task = Task::from_file(&task_path)?;
-    let from_status = task.status.clone();
-    let current = parse_task_state(&task.status)?;
+    apply_transition(board_dir, task_id, target, true, &attribution)
+}
+
+/// Check and apply a workflow transition under the board lock, clearing the
+/// block fields unless `clear_block` is false or the target is `blocked`.
+fn apply_transition(
+    board_dir: &Path,
+    task_id: u32,
+    target: &str,
+    clear_block: bool,
+    attribution: &StatusTransitionAttribution,
+) -> Result<()> {
     let target = parse_task_state(target)?;
     let to_status = state_name(target);
-
-    can_transition(current, target).map_err(anyhow::Error::msg)?;
-
-    update_task_frontmatter(&task_path, |mapping| {
+    let from_status = BoardStore::open(board_dir).update_task(task_id, |task, mapping| {
+        let current = parse_task_state(&task.status)?;
+        can_transition(current, target).map_err(anyhow::Error::msg)?;
         set_status(mapping, target);
-        if target != TaskState::Blocked {
+        if clear_block && target != TaskState::Blocked {
             clear_blocked(mapping);
         }
+        Ok(task.status.clone())
     })?;
-    record_status_transition_activity(board_dir, task_id, &from_status, to_status, &attribution)?;
+    record_status_transition_activity(board_dir, task_id, &from_status, to_status, attribution)?;
     Ok(())
 }
 
@@ -87,20 +96,7 @@ pub(crate) fn transition_task_preserving_block_with_attribution(
     target: &str,
     attribution: StatusTransitionAttribution,
 ) -> Result<()> {
-    let task_path = find_task_path(board_dir, task_id)?;
-    let task = Task::from_file(&task_path)?;
-    let from_status = task.status.clone();
-    let current = parse_task_state(&task.status)?;
-    let target = parse_task_state(target)?;
-    let to_status = state_name(target);
-
-    can_transition(current, target).map_err(anyhow::Error::msg)?;
-
-    update_task_frontmatter(&task_path, |mapping| {
-        set_status(mapping, target);
-    })?;
-    record_status_transition_activity(board_dir, task_id, &from_status, to_status, &attribution)?;
-    Ok(())
+    apply_transition(board_dir, task_id, target, false, &attribution)
 }
 
 pub(crate) fn clear_blocked_fields(board_dir: &Path, task_id: u32) -> Result<()> {
@@ -124,12 +120,10 @@ pub(crate) fn block_task_with_reason_and_attribution(
     reason: &str,
     attribution: StatusTransitionAttribution,
 ) -> Result<()> {
-    let task_path = find_task_path(board_dir, task_id)?;
-    let task = Task::from_file(&task_path)?;
-    let from_status = task.status.clone();
-    update_task_frontmatter(&task_path, |mapping| {
+    let from_status = BoardStore::open(board_dir).update_task(task_id, |task, mapping| {
         set_status(mapping, TaskState::Blocked);
         set_blocked_reason(mapping, Some(reason), Some(reason));
+        Ok(task.status.clone())
     })?;
     record_status_transition_activity(board_dir, task_id, &from_status, "blocked", &attribution)?;
     Ok(())
@@ -311,21 +305,12 @@ pub(crate) fn reclaim_task_claim_with_attribution(
     next_action: &str,
     attribution: StatusTransitionAttribution,
 ) -> Result<()> {
-    let task_path = find_task_path(board_dir, task_id)?;
-    let task = Task::from_file(&task_path)?;
-    let from_status = task.status.clone();
-    update_task_frontmatter(&task_path, |mapping| {
-        set_optional_string(mapping, "claimed_by", None);
+    let from_status = BoardStore::open(board_dir).update_task(task_id, |task, mapping| {
         set_optional_string(mapping, "review_owner", None);
-        set_optional_string(mapping, "claimed_at", None);
-        set_optional_u64(mapping, "claim_ttl_secs", None);
-        set_optional_string(mapping, "claim_expires_at", None);
-        set_optional_string(mapping, "last_progress_at", None);
-        set_optional_string(mapping, "claim_warning_sent_at", None);
-        set_optional_u32(mapping, "claim_extensions", None);
-        set_optional_u64(mapping, "last_output_bytes", None);
         set_optional_string(mapping, "next_action", Some(next_action));
+        // Moving to todo drops every claim field.
         set_status(mapping, TaskState::Todo);
+        Ok(task.status.clone())
     })?;
     record_status_transition_activity(board_dir, task_id, &from_status, "todo", &attribution)?;
     Ok(())
@@ -390,9 +375,6 @@ pub(crate) fn cmd_review_with_attribution(
     attribution: StatusTransitionAttribution,
 ) -> Result<()> {
     let task_path = find_task_path(board_dir, task_id)?;
-    let task = Task::from_file(&task_path)?;
-    let from_status = task.status.clone();
-    let current = parse_task_state(&task.status)?;
     let disposition = parse_review_disposition(disposition)?;
     let target = match disposition {
         ReviewDisposition::Approved => TaskState::Done,
@@ -401,15 +383,17 @@ pub(crate) fn cmd_review_with_attribution(
     };
     let to_status = state_name(target);
 
-    can_transition(current, target).map_err(anyhow::Error::msg)?;
-
-    update_task_frontmatter(&task_path, |mapping| {
+    let task = BoardStore::open(board_dir).update_task(task_id, |task, mapping| {
+        let current = parse_task_state(&task.status)?;
+        can_transition(current, target).map_err(anyhow::Error::msg)?;
         set_status(mapping, target);
         clear_blocked(mapping);
         if let Some(text) = feedback {
             set_optional_string(mapping, "review_feedback", Some(text));
         }
+        Ok(task.clone())
     })?;
+    let from_status = task.status.clone();
     record_status_transition_activity(board_dir, task_id, &from_status, to_status, &attribution)?;
 
     let mut metadata = read_workflow_metadata(&task_path)?;
@@ -492,9 +476,6 @@ pub(crate) fn cmd_review_structured_with_attribution(
     attribution: StatusTransitionAttribution,
 ) -> Result<()> {
     let task_path = find_task_path(board_dir, task_id)?;
-    let task = Task::from_file(&task_path)?;
-    let from_status = task.status.clone();
-    let current = parse_task_state(&task.status)?;
 
     let (target_state, disposition_str) = match disposition {
         "approve" => (TaskState::Done, "approved"),
@@ -504,12 +485,12 @@ pub(crate) fn cmd_review_structured_with_attribution(
     };
     let to_status = state_name(target_state);
 
-    can_transition(current, target_state).map_err(anyhow::Error::msg)?;
-
     let now = chrono::Utc::now().to_rfc3339();
     let default_reject_reason = format!("rejected by {reviewer}");
 
-    update_task_frontmatter(&task_path, |mapping| {
+    let task = BoardStore::open(board_dir).update_task(task_id, |task, mapping| {
+        let current = parse_task_state(&task.status)?;
+        can_transition(current, target_state).map_err(anyhow::Error::msg)?;
         set_status(mapping, target_state);
         set_optional_string(mapping, "review_disposition", Some(disposition_str));
         set_optional_string(mapping, "reviewed_by", Some(reviewer));
@@ -523,7 +504,9 @@ pub(crate) fn cmd_review_structured_with_attribution(
         } else {
             clear_blocked(mapping);
         }
+        Ok(task.clone())
     })?;
+    let from_status = task.status.clone();
     record_status_transition_activity(board_dir, task_id, &from_status, to_status, &attribution)?;
 
     // Update workflow metadata outcome
@@ -861,16 +844,53 @@ fn review_disposition_name(disposition: ReviewDisposition) -> &'static str {
     }
 }
 
+/// Rewrite a task file's frontmatter under the lock of the board that holds
+/// it. Checks against the current task content belong in
+/// [`BoardStore::update_task`], which also loads the task under the lock.
 pub(crate) fn update_task_frontmatter<F>(task_path: &Path, mutator: F) -> Result<()>
 where
     F: FnOnce(&mut Mapping),
+{
+    try_update_task_frontmatter(task_path, |mapping| {
+        mutator(mapping);
+        Ok(())
+    })
+}
+
+/// [`update_task_frontmatter`] with a mutator that can fail, leaving the file
+/// untouched.
+pub(crate) fn try_update_task_frontmatter<T, F>(task_path: &Path, mutator: F) -> Result<T>
+where
+    F: FnOnce(&mut Mapping) -> Result<T>,
+{
+    let _lock = BoardLock::acquire(&task_board_dir(task_path))?;
+    rewrite_task_frontmatter(task_path, mutator)
+}
+
+/// Board directory for a task file: the parent of its `tasks/` directory,
+/// or the file's own directory for files kept elsewhere.
+fn task_board_dir(task_path: &Path) -> PathBuf {
+    let dir = task_path.parent().unwrap_or_else(|| Path::new("."));
+    match dir.parent() {
+        Some(board_dir) if dir.file_name().is_some_and(|name| name == "tasks") => {
+            board_dir.to_path_buf()
+        }
+        _ => dir.to_path_buf(),
+    }
+}
+
+/// Read-modify-write of a task's frontmatter for callers that already hold
+/// the board lock.
+pub(crate) fn rewrite_task_frontmatter<T, F>(task_path: &Path, mutator: F) -> Result<T>
+where
+    F: FnOnce(&mut Mapping) -> Result<T>,
 {
     let content = std::fs::read_to_string(task_path)
         .with_context(|| format!("failed to read {}", task_path.display()))?;
     let (frontmatter, body) = split_task_frontmatter(&content)?;
     let mut mapping: Mapping =
         serde_yaml::from_str(frontmatter).context("failed to parse task frontmatter")?;
-    mutator(&mut mapping);
+    let result = mutator(&mut mapping)?;
 
     let mut rendered =
         serde_yaml::to_string(&mapping).context("failed to serialize task frontmatter")?;
@@ -886,9 +906,9 @@ where
     updated.push_str("---\n");
     updated.push_str(body);
 
-    std::fs::write(task_path, updated)
+    super::board_store::write_atomic(task_path, &updated)
         .with_context(|| format!("failed to write {}", task_path.display()))?;
-    Ok(())
+    Ok(result)
 }
 
 fn split_task_frontmatter(content: &str) -> Result<(&str, &str)> {
@@ -909,10 +929,7 @@ fn split_task_frontmatter(content: &str) -> Result<(&str, &str)> {
 }
 
 fn set_status(mapping: &mut Mapping, state: TaskState) {
-    mapping.insert(
-        yaml_key("status"),
-        Value::String(state_name(state).to_string()),
-    );
+    set_task_status(mapping, state_name(state));
 }
 
 fn clear_blocked(mapping: &mut Mapping) {
@@ -11,6 +11,7 @@ use tracing::{debug, info, warn};
 use super::git_cmd;
 use super::retry::{RetryConfig, retry_sync};
 use super::test_results::{self, TestRunOutput};
+use crate::shim::sandbox::SandboxPolicy;
 
 const SHARED_CARGO_CONFIG_MARKER: &str = "# Managed by Batty: shared cargo target";
 const WORKTREE_EXCLUDE_MARKER: &str = "# Managed by Batty worktree ignores";
@@ -110,15 +111,23 @@ pub(crate) fn next_unclaimed_task(board_dir: &Path) -> Result<Option<crate::task
     Ok(available.into_iter().next())
 }
 
+/// Run `test_command` (default `cargo test`) in `worktree_dir`, inside
+/// `sandbox` when the worktree's member is sandboxed.
 pub(crate) fn run_tests_in_worktree(
     worktree_dir: &Path,
     test_command: Option<&str>,
+    test_report: Option<&str>,
+    sandbox: Option<&SandboxPolicy>,
 ) -> Result<TestRunOutput> {
     let command_text = test_command.unwrap_or("cargo test");
-    let mut command = std::process::Command::new("sh");
-    let cargo_home = engineer_worktree_project_root(worktree_dir)
-        .map(|project_root| project_root.join(".batty").join("cargo-home"))
-        .unwrap_or_else(|| worktree_dir.join(".batty").join("cargo-home"));
+    let started_at = std::time::SystemTime::now();
+    // Sandboxed runs get their own cargo home so code they build can never
+    // tamper with the registry sources unsandboxed builds compile.
+    let cargo_home = match engineer_worktree_project_root(worktree_dir) {
+        Some(project_root) if sandbox.is_some() => sandbox_cargo_home_dir(&project_root),
+        Some(project_root) => project_root.join(".batty").join("cargo-home"),
+        None => worktree_dir.join(".batty").join("cargo-home"),
+    };
     std::fs::create_dir_all(&cargo_home)
         .with_context(|| format!("failed to create {}", cargo_home.display()))?;
     // Use `sh -c` (not `sh -lc`): a login shell re-sources profile files and
@@ -126,10 +135,24 @@ pub(crate) fn run_tests_in_worktree(
     // GitHub's hosted runners), causing `cargo` lookups to fail. Plain
     // `sh -c` inherits the parent's PATH unchanged, which is what we want
     // both in production (daemon PATH carries rustup) and in tests.
-    command
-        .arg("-c")
-        .arg(command_text)
-        .current_dir(worktree_dir);
+    let mut command = match sandbox {
+        Some(sandbox) => {
+            let (program, args) = sandbox.wrap(
+                worktree_dir,
+                "sh",
+                &["-c".to_string(), command_text.to_string()],
+            )?;
+            let mut command = std::process::Command::new(program);
+            command.args(args);
+            command
+        }
+        None => {
+            let mut command = std::process::Command::new("sh");
+            command.arg("-c").arg(command_text);
+            command
+        }
+    };
+    command.current_dir(worktree_dir);
     command.env("CARGO_HOME", &cargo_home);
     if let Some(project_root) = engineer_worktree_project_root(worktree_dir) {
         let wt_name = worktree_dir
@@ -161,13 +184,29 @@ pub(crate) fn run_tests_in_worktree(
     let trimmed = if lines.len() > 50 {
         lines[lines.len() - 50..].join("\n")
     } else {
-        combined
+        combined.clone()
     };
 
     let passed = output.status.success();
+    let report = test_report.and_then(|report| {
+        test_results::load_report(worktree_dir, report, started_at)
+            .map_err(|error| {
+                warn!(report, error = %error, "ignoring unreadable test report");
+            })
+            .ok()
+            .flatten()
+    });
+    let results = report.unwrap_or_else(|| {
+        let parse_input = if test_results::streams_json(command_text) {
+            combined.as_str()
+        } else {
+            trimmed.as_str()
+        };
+        test_results::parse(command_text, parse_input, passed)
+    });
     Ok(TestRunOutput {
         passed,
-        results: test_results::parse(command_text, &trimmed, passed),
+        results,
         output: trimmed,
     })
 }
@@ -176,6 +215,10 @@ pub(crate) fn shared_cargo_target_dir(project_root: &Path) -> PathBuf {
     project_root.join(".batty").join("shared-target")
 }
 
+pub(crate) fn sandbox_cargo_home_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join("sandbox-cargo-home")
+}
+
 pub(crate) fn validate_review_ready_worktree(
     worktree_dir: &Path,
     task_text: &str,
@@ -368,10 +411,27 @@ pub(crate) fn setup_engineer_worktree_from_trunk(
     ensure_engineer_worktree_links(worktree_dir, team_config_dir)?;
     ensure_shared_cargo_target_config(project_root, worktree_dir)?;
     ensure_engineer_worktree_excludes(worktree_dir)?;
+    ensure_scope_fence_hook(worktree_dir);
 
     Ok(worktree_dir.to_path_buf())
 }
 
+/// Install the scope-fence pre-commit hook when running as the `batty`
+/// binary. Failures are logged rather than blocking dispatch; the
+/// post-completion scope check still applies.
+fn ensure_scope_fence_hook(worktree_dir: &Path) {
+    let Some(batty_bin) = std::env::current_exe()
+        .ok()
+        .filter(|exe| exe.file_stem().is_some_and(|stem| stem == "batty"))
+    else {
+        debug!(worktree = %worktree_dir.display(), "not running as batty; skipping scope-fence hook");
+        return;
+    };
+    if let Err(error) = crate::worktree::install_scope_fence_hook(worktree_dir, &batty_bin) {
+        warn!(worktree = %worktree_dir.display(), error = %error, "failed to install scope-fence hook");
+    }
+}
+
 #[cfg_attr(not(test), allow(dead_code))]
 pub(crate) fn prepare_engineer_assignment_worktree(
     project_root: &Path,
@@ -2695,7 +2755,7 @@ mod tests {
             "#[cfg(test)]\nmod tests {\n    #[test]\n    fn passes() {\n        assert_eq!(2 + 2, 4);\n    }\n}\n",
         )
         .unwrap();
-        let run = run_tests_in_worktree(worktree, None).unwrap();
+        let run = run_tests_in_worktree(worktree, None, None, None).unwrap();
         assert!(run.passed);
         assert!(run.output.contains("test result: ok"));
         assert_eq!(run.results.framework, "cargo");
@@ -2705,13 +2765,27 @@ mod tests {
             "#[cfg(test)]\nmod tests {\n    #[test]\n    fn fails() {\n        assert_eq!(2 + 2, 5);\n    }\n}\n",
         )
         .unwrap();
-        let run = run_tests_in_worktree(worktree, None).unwrap();
+        let run = run_tests_in_worktree(worktree, None, None, None).unwrap();
         assert!(!run.passed);
         assert!(run.output.contains("FAILED"));
         assert_eq!(run.results.failed, 1);
         assert_eq!(run.results.failures[0].test_name, "tests::fails");
     }
 
+    #[test]
+    fn test_run_tests_in_worktree_reads_configured_report() {
+        let tmp = tempfile::tempdir().unwrap();
+        let worktree = tmp.path();
+        let command = "mkdir -p reports && printf '%s' '<testsuite><testcase classname=\"api\" name=\"retries\"><failure message=\"timeout\"/></testcase><testcase name=\"ok\"/></testsuite>' > reports/junit.xml; exit 1";
+
+        let run = run_tests_in_worktree(worktree, Some(command), Some("reports"), None).unwrap();
+        assert!(!run.passed);
+        assert_eq!(run.results.framework, "junit");
+        assert_eq!(run.results.passed, 1);
+        assert_eq!(run.results.failures[0].test_name, "api::retries");
+        assert_eq!(run.results.failures[0].message.as_deref(), Some("timeout"));
+    }
+
     #[test]
     fn test_run_tests_in_worktree_uses_configured_command() {
         let tmp = tempfile::tempdir().unwrap();
@@ -2731,7 +2805,7 @@ mod tests {
             .unwrap();
         }
 
-        let run = run_tests_in_worktree(worktree, Some("./check.sh")).unwrap();
+        let run = run_tests_in_worktree(worktree, Some("./check.sh"), None, None).unwrap();
         assert!(run.passed);
         assert!(run.output.contains("CONFIG_TEST_OK"));
     }
@@ -2762,7 +2836,7 @@ mod tests {
             .unwrap();
         }
 
-        let run = run_tests_in_worktree(&worktree_dir, Some("./check.sh")).unwrap();
+        let run = run_tests_in_worktree(&worktree_dir, Some("./check.sh"), None, None).unwrap();
         assert!(run.passed);
         assert!(
             run.output
@@ -4062,7 +4136,7 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let fake_dir = tmp.path().join("missing-worktree");
         assert!(!fake_dir.exists(), "test requires a nonexistent directory");
-        let result = run_tests_in_worktree(&fake_dir, None);
+        let result = run_tests_in_worktree(&fake_dir, None, None, None);
         let output = result.expect("missing worktree should surface as a failed test run");
         assert!(
             !output.passed,
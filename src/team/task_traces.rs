@@ -0,0 +1,914 @@
+//! OpenTelemetry traces for the task lifecycle.
+//!
+//! Each board task becomes one trace. A root `task #N` span runs from the
+//! first time the daemon sees the task move until its final outcome, and one
+//! child span covers each lifecycle phase: queued, assigned, working,
+//! verifying, in review, queued for merge, and merging. Restarts and
+//! handoffs are recorded as child spans of the phase they interrupted.
+//!
+//! [`TaskTracer`] is a pure state machine: the daemon reports phase changes
+//! and the tracer buffers every span as it ends. [`TraceExporter`] drains
+//! that buffer as OTLP/HTTP JSON, either POSTed to a collector or appended to
+//! a local JSON-lines file, on a [`TraceExportWorker`] thread so a slow
+//! collector never holds up the poll loop. Open traces are saved with the daemon state and
+//! resumed after a restart. A trace ends when the task is merged, reaches a
+//! terminal board status, leaves the board, or moves to another engineer; the
+//! new engineer's work starts a fresh trace.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::config::{TraceExporterKind, TracesConfig};
+
+/// Instrumentation scope name on exported spans.
+const SCOPE_NAME: &str = "batty.task_lifecycle";
+/// Oldest unexported spans are dropped beyond this many, so a collector that
+/// stays down cannot grow the buffer without bound.
+const MAX_PENDING_SPANS: usize = 10_000;
+
+/// OTLP span kind `SPAN_KIND_INTERNAL`.
+const SPAN_KIND_INTERNAL: u8 = 1;
+/// OTLP status codes.
+const STATUS_OK: u8 = 1;
+const STATUS_ERROR: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TaskPhase {
+    Queued,
+    Assigned,
+    Working,
+    Verifying,
+    InReview,
+    QueuedForMerge,
+    Merging,
+}
+
+impl TaskPhase {
+    pub(crate) fn span_name(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Assigned => "assigned",
+            Self::Working => "working",
+            Self::Verifying => "verifying",
+            Self::InReview => "in_review",
+            Self::QueuedForMerge => "queued_for_merge",
+            Self::Merging => "merging",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AttrValue {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl AttrValue {
+    fn to_otlp(&self) -> Value {
+        match self {
+            Self::Str(value) => json!({ "stringValue": value }),
+            // OTLP JSON encodes 64-bit integers as strings.
+            Self::Int(value) => json!({ "intValue": value.to_string() }),
+            Self::Float(value) => json!({ "doubleValue": value }),
+            Self::Bool(value) => json!({ "boolValue": value }),
+        }
+    }
+}
+
+impl From<&str> for AttrValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for AttrValue {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<u32> for AttrValue {
+    fn from(value: u32) -> Self {
+        Self::Int(i64::from(value))
+    }
+}
+
+impl From<u64> for AttrValue {
+    fn from(value: u64) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<f64> for AttrValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for AttrValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+pub(crate) type Attributes = Vec<(&'static str, AttrValue)>;
+
+/// A finished span, ready for export.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Span {
+    pub trace_id: String,
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub attributes: Attributes,
+    pub error: bool,
+}
+
+impl Span {
+    fn to_otlp(&self) -> Value {
+        let mut span = json!({
+            "traceId": self.trace_id,
+            "spanId": self.span_id,
+            "name": self.name,
+            "kind": SPAN_KIND_INTERNAL,
+            "startTimeUnixNano": self.start_unix_nanos.to_string(),
+            "endTimeUnixNano": self.end_unix_nanos.to_string(),
+            "attributes": otlp_attributes(&self.attributes),
+            "status": { "code": if self.error { STATUS_ERROR } else { STATUS_OK } },
+        });
+        if let Some(parent) = &self.parent_span_id {
+            span["parentSpanId"] = json!(parent);
+        }
+        span
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpenSpan {
+    span_id: String,
+    name: String,
+    start_unix_nanos: u64,
+    attributes: Attributes,
+}
+
+impl OpenSpan {
+    fn start(name: impl Into<String>, at: u64, attributes: Attributes) -> Self {
+        Self {
+            span_id: new_span_id(),
+            name: name.into(),
+            start_unix_nanos: at,
+            attributes,
+        }
+    }
+
+    fn finish(self, trace_id: &str, parent: Option<&str>, at: u64, error: bool) -> Span {
+        Span {
+            trace_id: trace_id.to_string(),
+            span_id: self.span_id,
+            parent_span_id: parent.map(str::to_string),
+            name: self.name,
+            start_unix_nanos: self.start_unix_nanos,
+            end_unix_nanos: at.max(self.start_unix_nanos),
+            attributes: self.attributes,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TaskTrace {
+    trace_id: String,
+    root: OpenSpan,
+    phase: Option<(TaskPhase, OpenSpan)>,
+    engineer: Option<String>,
+}
+
+/// An open trace as saved in the daemon state. Span attributes are rebuilt
+/// on restore from the task id and engineer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PersistedTaskTrace {
+    pub trace_id: String,
+    pub root_span_id: String,
+    pub started_at: u64,
+    #[serde(default)]
+    pub engineer: Option<String>,
+    #[serde(default)]
+    pub phase: Option<PersistedPhase>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PersistedPhase {
+    pub phase: TaskPhase,
+    pub span_id: String,
+    pub started_at: u64,
+}
+
+/// Open task traces and the spans that have ended but not been exported.
+#[derive(Debug, Default)]
+pub(crate) struct TaskTracer {
+    traces: HashMap<u32, TaskTrace>,
+    finished: Vec<Span>,
+}
+
+impl TaskTracer {
+    /// Move `task_id` into `phase`, ending the previous phase span. Starts
+    /// the trace if this is the first phase seen for the task, and starts a
+    /// new one when `engineer` takes over from someone else. Re-entering the
+    /// current phase is a no-op.
+    pub(crate) fn enter_phase(
+        &mut self,
+        task_id: u32,
+        phase: TaskPhase,
+        engineer: Option<&str>,
+        attributes: Attributes,
+        at: u64,
+    ) {
+        if let Some(engineer) = engineer
+            && self
+                .engineer(task_id)
+                .is_some_and(|current| current != engineer)
+        {
+            self.finish(task_id, "reassigned", false, Vec::new(), at);
+        }
+        let trace = self.traces.entry(task_id).or_insert_with(|| TaskTrace {
+            trace_id: new_trace_id(),
+            root: OpenSpan::start(
+                format!("task #{task_id}"),
+                at,
+                vec![("batty.task.id", task_id.into())],
+            ),
+            phase: None,
+            engineer: None,
+        });
+        if trace
+            .phase
+            .as_ref()
+            .is_some_and(|(current, _)| *current == phase)
+        {
+            return;
+        }
+        if let Some(engineer) = engineer {
+            trace.engineer = Some(engineer.to_string());
+        }
+        if let Some((_, open)) = trace.phase.take() {
+            self.finished
+                .push(open.finish(&trace.trace_id, Some(&trace.root.span_id), at, false));
+        }
+        let mut span_attributes = vec![("batty.task.id", task_id.into())];
+        if let Some(engineer) = &trace.engineer {
+            span_attributes.push(("batty.engineer", engineer.as_str().into()));
+        }
+        span_attributes.extend(attributes);
+        trace.phase = Some((
+            phase,
+            OpenSpan::start(phase.span_name(), at, span_attributes),
+        ));
+    }
+
+    /// End the current phase span with `outcome` without starting another.
+    /// The next [`Self::enter_phase`] opens the following phase.
+    pub(crate) fn end_phase(&mut self, task_id: u32, outcome: &str, error: bool, at: u64) {
+        let Some(trace) = self.traces.get_mut(&task_id) else {
+            return;
+        };
+        if let Some((_, mut open)) = trace.phase.take() {
+            open.attributes.push(("batty.outcome", outcome.into()));
+            self.finished
+                .push(open.finish(&trace.trace_id, Some(&trace.root.span_id), at, error));
+        }
+    }
+
+    /// Record a point-in-time child span, such as a restart or handoff,
+    /// under the current phase span.
+    pub(crate) fn record_child(
+        &mut self,
+        task_id: u32,
+        name: &str,
+        attributes: Attributes,
+        error: bool,
+        at: u64,
+    ) {
+        let Some(trace) = self.traces.get(&task_id) else {
+            return;
+        };
+        let parent = trace
+            .phase
+            .as_ref()
+            .map(|(_, open)| open.span_id.as_str())
+            .unwrap_or(trace.root.span_id.as_str());
+        let span =
+            OpenSpan::start(name, at, attributes).finish(&trace.trace_id, Some(parent), at, error);
+        self.finished.push(span);
+    }
+
+    /// End the trace with `outcome`, closing the current phase and the root
+    /// span. `attributes` are added to the root span.
+    pub(crate) fn finish(
+        &mut self,
+        task_id: u32,
+        outcome: &str,
+        error: bool,
+        attributes: Attributes,
+        at: u64,
+    ) {
+        let Some(mut trace) = self.traces.remove(&task_id) else {
+            return;
+        };
+        if let Some((_, open)) = trace.phase.take() {
+            self.finished
+                .push(open.finish(&trace.trace_id, Some(&trace.root.span_id), at, false));
+        }
+        if let Some(engineer) = trace.engineer {
+            trace
+                .root
+                .attributes
+                .push(("batty.engineer", engineer.into()));
+        }
+        trace.root.attributes.extend(attributes);
+        trace
+            .root
+            .attributes
+            .push(("batty.outcome", outcome.into()));
+        self.finished
+            .push(trace.root.finish(&trace.trace_id, None, at, error));
+    }
+
+    pub(crate) fn phase(&self, task_id: u32) -> Option<TaskPhase> {
+        self.traces
+            .get(&task_id)
+            .and_then(|trace| trace.phase.as_ref().map(|(phase, _)| *phase))
+    }
+
+    pub(crate) fn engineer(&self, task_id: u32) -> Option<&str> {
+        self.traces
+            .get(&task_id)
+            .and_then(|trace| trace.engineer.as_deref())
+    }
+
+    /// Tasks with an open trace and the engineer last seen on each.
+    pub(crate) fn open_tasks(&self) -> Vec<(u32, Option<&str>)> {
+        self.traces
+            .iter()
+            .map(|(task_id, trace)| (*task_id, trace.engineer.as_deref()))
+            .collect()
+    }
+
+    pub(crate) fn take_finished(&mut self) -> Vec<Span> {
+        std::mem::take(&mut self.finished)
+    }
+
+    /// Open traces in their saved form.
+    pub(crate) fn snapshot(&self) -> HashMap<u32, PersistedTaskTrace> {
+        self.traces
+            .iter()
+            .map(|(task_id, trace)| {
+                (
+                    *task_id,
+                    PersistedTaskTrace {
+                        trace_id: trace.trace_id.clone(),
+                        root_span_id: trace.root.span_id.clone(),
+                        started_at: trace.root.start_unix_nanos,
+                        engineer: trace.engineer.clone(),
+                        phase: trace.phase.as_ref().map(|(phase, open)| PersistedPhase {
+                            phase: *phase,
+                            span_id: open.span_id.clone(),
+                            started_at: open.start_unix_nanos,
+                        }),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Resume traces saved by [`Self::snapshot`]. `engineer_attributes`
+    /// supplies the extra phase-span attributes for an engineer, as the
+    /// daemon passes to [`Self::enter_phase`]. Traces already open are kept.
+    pub(crate) fn restore(
+        &mut self,
+        traces: HashMap<u32, PersistedTaskTrace>,
+        engineer_attributes: impl Fn(&str) -> Attributes,
+    ) {
+        for (task_id, saved) in traces {
+            if self.traces.contains_key(&task_id) {
+                continue;
+            }
+            let phase = saved.phase.map(|phase| {
+                let mut attributes = vec![("batty.task.id", task_id.into())];
+                if let Some(engineer) = &saved.engineer {
+                    attributes.push(("batty.engineer", engineer.as_str().into()));
+                    attributes.extend(engineer_attributes(engineer));
+                }
+                (
+                    phase.phase,
+                    OpenSpan {
+                        span_id: phase.span_id,
+                        name: phase.phase.span_name().to_string(),
+                        start_unix_nanos: phase.started_at,
+                        attributes,
+                    },
+                )
+            });
+            self.traces.insert(
+                task_id,
+                TaskTrace {
+                    trace_id: saved.trace_id,
+                    root: OpenSpan {
+                        span_id: saved.root_span_id,
+                        name: format!("task #{task_id}"),
+                        start_unix_nanos: saved.started_at,
+                        attributes: vec![("batty.task.id", task_id.into())],
+                    },
+                    phase,
+                    engineer: saved.engineer,
+                },
+            );
+        }
+    }
+}
+
+/// Exports finished spans to the configured sink, keeping failed batches for
+/// the next attempt.
+#[derive(Debug, Clone)]
+pub(crate) struct TraceExporter {
+    kind: TraceExporterKind,
+    endpoint: String,
+    path: PathBuf,
+    timeout: Duration,
+    resource: Attributes,
+    pending: Vec<Span>,
+}
+
+impl TraceExporter {
+    pub(crate) fn new(config: &TracesConfig, project_root: &Path, team_name: &str) -> Self {
+        Self {
+            kind: config.exporter,
+            endpoint: config.endpoint.clone(),
+            path: project_root.join(&config.path),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            resource: vec![
+                ("service.name", config.service_name.as_str().into()),
+                ("batty.team", team_name.into()),
+            ],
+            pending: Vec::new(),
+        }
+    }
+
+    /// Human-readable export target for logs.
+    pub(crate) fn target(&self) -> String {
+        match self.kind {
+            TraceExporterKind::File => self.path.display().to_string(),
+            TraceExporterKind::Otlp => self.endpoint.clone(),
+        }
+    }
+
+    /// Queue `spans` and try to export everything pending. Returns how many
+    /// spans were exported. On failure the spans stay queued.
+    pub(crate) fn export(&mut self, spans: Vec<Span>) -> Result<usize> {
+        self.pending.extend(spans);
+        if self.pending.len() > MAX_PENDING_SPANS {
+            let excess = self.pending.len() - MAX_PENDING_SPANS;
+            self.pending.drain(..excess);
+        }
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let request = export_request(&self.resource, &self.pending);
+        match self.kind {
+            TraceExporterKind::File => append_json_line(&self.path, &request)?,
+            TraceExporterKind::Otlp => post_request(&self.endpoint, &request, self.timeout)?,
+        }
+        let exported = self.pending.len();
+        self.pending.clear();
+        Ok(exported)
+    }
+
+    pub(crate) fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// Background thread that owns a [`TraceExporter`]. The daemon hands it the
+/// spans finished each tick and collects export failures without waiting on
+/// the collector.
+#[derive(Debug)]
+pub(crate) struct TraceExportWorker {
+    spans: mpsc::Sender<Vec<Span>>,
+    failures: mpsc::Receiver<String>,
+}
+
+impl TraceExportWorker {
+    pub(crate) fn start(exporter: TraceExporter) -> Result<Self> {
+        let (spans, span_rx) = mpsc::channel();
+        let (failure_tx, failures) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("batty-traces".to_string())
+            .spawn(move || run_export_worker(exporter, span_rx, failure_tx))
+            .context("failed to spawn trace export thread")?;
+        Ok(Self { spans, failures })
+    }
+
+    /// Queue `spans` and wake the worker to export everything pending. An
+    /// empty batch still retries spans held from a failed export.
+    pub(crate) fn submit(&self, spans: Vec<Span>) -> Result<()> {
+        if self.spans.send(spans).is_err() {
+            bail!("trace export thread exited");
+        }
+        Ok(())
+    }
+
+    /// Export failures the worker reported since the last call.
+    pub(crate) fn take_failures(&self) -> Vec<String> {
+        self.failures.try_iter().collect()
+    }
+}
+
+fn run_export_worker(
+    mut exporter: TraceExporter,
+    spans: mpsc::Receiver<Vec<Span>>,
+    failures: mpsc::Sender<String>,
+) {
+    while let Ok(first) = spans.recv() {
+        // Fold ticks that arrived during a slow export into one request.
+        let batch = std::iter::once(first)
+            .chain(spans.try_iter())
+            .flatten()
+            .collect();
+        if let Err(error) = exporter.export(batch) {
+            let failure = format!(
+                "{error:#}; {} span(s) held for the next export to {}",
+                exporter.pending_len(),
+                exporter.target()
+            );
+            if failures.send(failure).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Tracer and export worker owned by the daemon when `traces.enabled` is
+/// set.
+#[derive(Debug)]
+pub(crate) struct TaskTraces {
+    pub tracer: TaskTracer,
+    /// Starting state for the export worker, kept to restart it.
+    exporter: TraceExporter,
+    worker: Option<TraceExportWorker>,
+}
+
+impl TaskTraces {
+    pub(crate) fn new(config: &TracesConfig, project_root: &Path, team_name: &str) -> Self {
+        Self {
+            tracer: TaskTracer::default(),
+            exporter: TraceExporter::new(config, project_root, team_name),
+            worker: None,
+        }
+    }
+
+    /// Hand the spans finished since the last call to the export worker,
+    /// starting it if needed. Returns the failures it reported meanwhile.
+    pub(crate) fn export_finished(&mut self) -> Result<Vec<String>> {
+        if self.worker.is_none() {
+            self.worker = Some(TraceExportWorker::start(self.exporter.clone())?);
+        }
+        let worker = self.worker.as_ref().expect("trace export worker started");
+        if let Err(error) = worker.submit(self.tracer.take_finished()) {
+            self.worker = None;
+            return Err(error);
+        }
+        Ok(worker.take_failures())
+    }
+}
+
+/// Encode spans as an OTLP `ExportTraceServiceRequest` in JSON form.
+pub(crate) fn export_request(resource: &Attributes, spans: &[Span]) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": otlp_attributes(resource) },
+            "scopeSpans": [{
+                "scope": { "name": SCOPE_NAME },
+                "spans": spans.iter().map(Span::to_otlp).collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+fn otlp_attributes(attributes: &Attributes) -> Vec<Value> {
+    attributes
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": value.to_otlp() }))
+        .collect()
+}
+
+fn append_json_line(path: &Path, request: &Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open trace file {}", path.display()))?;
+    writeln!(file, "{request}")
+        .with_context(|| format!("failed to write trace file {}", path.display()))
+}
+
+fn post_request(endpoint: &str, request: &Value, timeout: Duration) -> Result<()> {
+    match ureq::post(endpoint)
+        .timeout(timeout)
+        .set("Content-Type", "application/json")
+        .send_string(&request.to_string())
+    {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            bail!(
+                "OTLP endpoint {endpoint} returned {status}: {}",
+                body.chars().take(200).collect::<String>()
+            )
+        }
+        Err(ureq::Error::Transport(error)) => {
+            bail!("OTLP endpoint {endpoint} unreachable: {error}")
+        }
+    }
+}
+
+pub(crate) fn unix_nanos_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+fn new_trace_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn new_span_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..16].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    use super::*;
+
+    impl Span {
+        fn attribute(&self, key: &str) -> Option<&AttrValue> {
+            self.attributes
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value)
+        }
+    }
+
+    fn span_named<'a>(spans: &'a [Span], name: &str) -> &'a Span {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap_or_else(|| panic!("no span named {name}"))
+    }
+
+    #[test]
+    fn phases_become_sibling_spans_under_one_root() {
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(7, TaskPhase::Queued, Some("eng-1"), Vec::new(), 100);
+        tracer.enter_phase(7, TaskPhase::Assigned, None, Vec::new(), 200);
+        tracer.enter_phase(7, TaskPhase::Assigned, None, Vec::new(), 250);
+        tracer.enter_phase(
+            7,
+            TaskPhase::Working,
+            Some("eng-1"),
+            vec![("batty.backend", "claude".into())],
+            300,
+        );
+        tracer.record_child(
+            7,
+            "restart",
+            vec![("batty.reason", "context_exhausted".into())],
+            false,
+            350,
+        );
+        tracer.finish(
+            7,
+            "merged",
+            false,
+            vec![("batty.tokens", 1200u64.into())],
+            400,
+        );
+
+        let spans = tracer.take_finished();
+        assert_eq!(spans.len(), 5);
+        assert!(tracer.phase(7).is_none());
+        assert!(spans.iter().all(|span| span.trace_id == spans[0].trace_id));
+
+        let root = span_named(&spans, "task #7");
+        assert!(root.parent_span_id.is_none());
+        assert_eq!((root.start_unix_nanos, root.end_unix_nanos), (100, 400));
+        assert_eq!(root.attribute("batty.outcome"), Some(&"merged".into()));
+        assert_eq!(root.attribute("batty.engineer"), Some(&"eng-1".into()));
+        assert_eq!(root.attribute("batty.tokens"), Some(&AttrValue::Int(1200)));
+
+        let assigned = span_named(&spans, "assigned");
+        assert_eq!(
+            (assigned.start_unix_nanos, assigned.end_unix_nanos),
+            (200, 300)
+        );
+        assert_eq!(assigned.parent_span_id.as_ref(), Some(&root.span_id));
+
+        let working = span_named(&spans, "working");
+        assert_eq!(working.attribute("batty.backend"), Some(&"claude".into()));
+        let restart = span_named(&spans, "restart");
+        assert_eq!(restart.parent_span_id.as_ref(), Some(&working.span_id));
+    }
+
+    #[test]
+    fn end_phase_marks_failure_and_leaves_trace_open() {
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(3, TaskPhase::Merging, Some("eng-2"), Vec::new(), 10);
+        tracer.end_phase(3, "conflicted", true, 20);
+        assert!(tracer.phase(3).is_none());
+        assert_eq!(tracer.engineer(3), Some("eng-2"));
+
+        let spans = tracer.take_finished();
+        assert_eq!(spans.len(), 1);
+        assert!(spans[0].error);
+        assert_eq!(
+            spans[0].attribute("batty.outcome"),
+            Some(&"conflicted".into())
+        );
+
+        tracer.record_child(99, "restart", Vec::new(), false, 30);
+        tracer.finish(99, "merged", false, Vec::new(), 30);
+        assert!(tracer.take_finished().is_empty());
+    }
+
+    #[test]
+    fn saved_traces_resume_with_their_span_ids() {
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(5, TaskPhase::Queued, Some("eng-1"), Vec::new(), 100);
+        tracer.enter_phase(5, TaskPhase::Working, None, Vec::new(), 200);
+        let queued = tracer.take_finished().remove(0);
+        let saved = serde_json::to_string(&tracer.snapshot()).unwrap();
+
+        let mut resumed = TaskTracer::default();
+        resumed.restore(serde_json::from_str(&saved).unwrap(), |engineer| {
+            vec![("batty.backend", format!("{engineer}-backend").into())]
+        });
+        assert_eq!(resumed.phase(5), Some(TaskPhase::Working));
+        resumed.finish(5, "merged", false, Vec::new(), 300);
+
+        let spans = resumed.take_finished();
+        let root = span_named(&spans, "task #5");
+        let working = span_named(&spans, "working");
+        assert_eq!(root.trace_id, queued.trace_id);
+        assert_eq!(queued.parent_span_id.as_ref(), Some(&root.span_id));
+        assert_eq!((root.start_unix_nanos, root.end_unix_nanos), (100, 300));
+        assert_eq!(
+            (working.start_unix_nanos, working.end_unix_nanos),
+            (200, 300)
+        );
+        assert_eq!(working.attribute("batty.engineer"), Some(&"eng-1".into()));
+        assert_eq!(
+            working.attribute("batty.backend"),
+            Some(&"eng-1-backend".into())
+        );
+    }
+
+    #[test]
+    fn another_engineer_ends_the_trace_as_reassigned() {
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(6, TaskPhase::Working, Some("eng-1"), Vec::new(), 100);
+        tracer.enter_phase(6, TaskPhase::Assigned, Some("eng-2"), Vec::new(), 200);
+
+        let spans = tracer.take_finished();
+        let root = span_named(&spans, "task #6");
+        assert_eq!(root.attribute("batty.outcome"), Some(&"reassigned".into()));
+        assert_eq!(root.attribute("batty.engineer"), Some(&"eng-1".into()));
+        assert_eq!(tracer.engineer(6), Some("eng-2"));
+        assert_eq!(tracer.phase(6), Some(TaskPhase::Assigned));
+        assert_eq!(tracer.open_tasks(), vec![(6, Some("eng-2"))]);
+    }
+
+    #[test]
+    fn export_request_follows_otlp_json_encoding() {
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(5, TaskPhase::Verifying, Some("eng-1"), Vec::new(), 1);
+        tracer.finish(5, "merged", false, vec![("batty.cost_usd", 0.25.into())], 2);
+        let spans = tracer.take_finished();
+
+        let request = export_request(&vec![("service.name", "batty".into())], &spans);
+        let resource = &request["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "batty"
+        );
+        let encoded = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(encoded.len(), 2);
+        let root = encoded
+            .iter()
+            .find(|span| span["name"] == "task #5")
+            .unwrap();
+        assert_eq!(root["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(root["spanId"].as_str().unwrap().len(), 16);
+        assert_eq!(root["endTimeUnixNano"], "2");
+        assert_eq!(root["status"]["code"], STATUS_OK);
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(root["attributes"][0]["value"]["intValue"], "5");
+        let phase = encoded
+            .iter()
+            .find(|span| span["name"] == "verifying")
+            .unwrap();
+        assert_eq!(phase["parentSpanId"], root["spanId"]);
+    }
+
+    #[test]
+    fn file_exporter_appends_one_request_per_export() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = TracesConfig {
+            enabled: true,
+            ..TracesConfig::default()
+        };
+        let mut exporter = TraceExporter::new(&config, tmp.path(), "demo");
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(1, TaskPhase::Queued, None, Vec::new(), 1);
+        tracer.finish(1, "done", false, Vec::new(), 2);
+
+        assert_eq!(exporter.export(tracer.take_finished()).unwrap(), 2);
+        assert_eq!(exporter.export(Vec::new()).unwrap(), 0);
+
+        let content = std::fs::read_to_string(tmp.path().join(".batty/traces.jsonl")).unwrap();
+        let lines: Vec<_> = content.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let request: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(
+            request["resourceSpans"][0]["resource"]["attributes"][1]["value"]["stringValue"],
+            "demo"
+        );
+    }
+
+    #[test]
+    fn otlp_exporter_posts_json_and_keeps_spans_on_failure() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                tx.send(String::from_utf8(body).unwrap()).unwrap();
+                write!(stream, "HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n").unwrap();
+            }
+        });
+
+        let config = TracesConfig {
+            enabled: true,
+            exporter: TraceExporterKind::Otlp,
+            endpoint: format!("http://{addr}/v1/traces"),
+            ..TracesConfig::default()
+        };
+        let mut exporter = TraceExporter::new(&config, Path::new("."), "demo");
+        let mut tracer = TaskTracer::default();
+        tracer.enter_phase(2, TaskPhase::Queued, None, Vec::new(), 1);
+        tracer.finish(2, "done", false, Vec::new(), 2);
+
+        assert!(exporter.export(tracer.take_finished()).is_err());
+        assert_eq!(exporter.pending_len(), 2);
+        assert_eq!(exporter.export(Vec::new()).unwrap(), 2);
+        assert_eq!(exporter.pending_len(), 0);
+
+        let first: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+        let second: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+        assert_eq!(first, second);
+    }
+}
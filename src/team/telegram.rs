@@ -47,7 +47,7 @@ impl TelegramBot {
             .clone()
             .or_else(|| std::env::var("BATTY_TELEGRAM_BOT_TOKEN").ok());
 
-        token.map(|t| Self::new(t, config.allowed_user_ids.clone()))
+        token.map(|t| Self::new(t, config.numeric_user_ids()))
     }
 
     /// Check whether a Telegram user ID is in the allowed list.
@@ -549,6 +549,9 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
 
         // If the env var is not set, from_config must return None.
@@ -568,6 +571,9 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
 
         let bot = TelegramBot::from_config(&config).expect("should return Some");
@@ -587,6 +593,9 @@ mod tests {
             agents_channel_id: None,
             commands_channel_id: None,
             board_channel_id: None,
+            allowed_slack_user_ids: Vec::new(),
+            permissions: Default::default(),
+            api_base: None,
         };
 
         let bot = TelegramBot::from_config(&config).unwrap();
@@ -5,6 +5,7 @@ use std::collections::BTreeMap;
 use anyhow::{Context, Result, anyhow, bail};
 use tracing::{debug, info, warn};
 
+use super::operator_commands::OperatorSource;
 use super::*;
 
 pub(super) fn build_telegram_bot(
@@ -65,7 +66,15 @@ impl TeamDaemon {
                 "telegram inbound"
             );
 
-            if let Some(reply) = self.handle_telegram_command(&msg) {
+            let user = msg.from_user_id.to_string();
+            let chat = msg.chat_id.to_string();
+            let source = OperatorSource {
+                transport: "telegram",
+                user: &user,
+                member: None,
+                location: &chat,
+            };
+            if let Some(reply) = self.handle_operator_message(source, &msg.text) {
                 if let Some(bot) = self.telegram_bot.as_ref() {
                     if let Err(error) = bot.send_message(&msg.chat_id.to_string(), &reply) {
                         warn!(chat_id = msg.chat_id, error = %error, "failed to send telegram reply");
@@ -91,91 +100,6 @@ impl TeamDaemon {
         Ok(())
     }
 
-    fn handle_telegram_command(
-        &mut self,
-        msg: &super::super::telegram::InboundMessage,
-    ) -> Option<String> {
-        let command = match parse_telegram_command(&msg.text) {
-            Ok(Some(command)) => command,
-            Ok(None) => return None,
-            Err(error) => return Some(error.to_string()),
-        };
-
-        Some(match self.execute_telegram_command(command) {
-            Ok(reply) => reply,
-            Err(error) => format!("Command failed: {error}"),
-        })
-    }
-
-    pub(super) fn execute_telegram_command(&mut self, command: TelegramCommand) -> Result<String> {
-        match command {
-            TelegramCommand::Status => Ok(self.render_telegram_status_summary()),
-            TelegramCommand::Board { filter } => {
-                render_telegram_board_summary(&self.config.project_root, filter.as_deref())
-            }
-            TelegramCommand::Logs { member } => {
-                render_telegram_logs(&self.config.project_root, &member)
-            }
-            TelegramCommand::Health => {
-                render_telegram_health_summary(&self.config.project_root, &self.config.members)
-            }
-            TelegramCommand::Assign { engineer, task } => {
-                self.execute_telegram_assign_command(&engineer, &task)
-            }
-            TelegramCommand::Merge { task_id } => self.execute_telegram_merge_command(task_id),
-            TelegramCommand::Kick { member } => self.execute_telegram_kick_command(&member),
-            TelegramCommand::Pause => {
-                crate::team::pause_team(&self.config.project_root)?;
-                Ok("Automation paused.".to_string())
-            }
-            TelegramCommand::Resume => {
-                crate::team::resume_team(&self.config.project_root)?;
-                Ok("Automation resumed.".to_string())
-            }
-            TelegramCommand::Goal { text } => {
-                write_telegram_goal(&self.config.project_root, &text)?;
-                Ok(format!("Goal updated: {}", preview_text(&text, 180)))
-            }
-            TelegramCommand::Task { title } => {
-                create_telegram_task(&self.config.project_root, &title)
-            }
-            TelegramCommand::Block { task_id, reason } => {
-                block_telegram_task(&self.config.project_root, task_id, &reason)
-            }
-            TelegramCommand::Stop { confirm } => {
-                if !confirm {
-                    Ok("Reply with /stop confirm to stop the team.".to_string())
-                } else {
-                    let root = self.config.project_root.clone();
-                    std::thread::spawn(move || {
-                        std::thread::sleep(std::time::Duration::from_millis(250));
-                        let _ = crate::team::stop_team(&root);
-                    });
-                    Ok("Stopping team.".to_string())
-                }
-            }
-            TelegramCommand::Start => {
-                let session = format!("batty-{}", self.config.team_config.name);
-                if crate::tmux::session_exists(&session) {
-                    Ok(format!("Team already running: {session}"))
-                } else {
-                    let started = crate::team::start_team(&self.config.project_root, false)?;
-                    Ok(format!("Started team: {started}"))
-                }
-            }
-            TelegramCommand::Help => Ok(render_telegram_help()),
-            TelegramCommand::Send { role, message } => {
-                crate::team::messaging::send_message_as(
-                    &self.config.project_root,
-                    Some("human"),
-                    &role,
-                    &message,
-                )?;
-                Ok(format!("Sent to {role}: {}", preview_text(&message, 120)))
-            }
-        }
-    }
-
     pub(super) fn render_telegram_status_summary(&self) -> String {
         let session = format!("batty-{}", self.config.team_config.name);
         let running = crate::tmux::session_exists(&session);
@@ -325,7 +249,12 @@ impl TeamDaemon {
             .workflow_policy
             .test_command
             .as_deref());
-        let test_run = crate::team::task_loop::run_tests_in_worktree(&worktree_dir, test_command)?;
+        let test_run = crate::team::task_loop::run_tests_in_worktree(
+            &worktree_dir,
+            test_command,
+            verification_policy.test_report.as_deref(),
+            self.gate_sandbox(&engineer, &worktree_dir).as_ref(),
+        )?;
         if !test_run.passed {
             bail!(
                 "Task #{task_id} verification failed before merge.\n{}",
@@ -462,191 +391,7 @@ impl TeamDaemon {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub(super) enum TelegramCommand {
-    Status,
-    Board { filter: Option<String> },
-    Logs { member: String },
-    Health,
-    Assign { engineer: String, task: String },
-    Merge { task_id: u32 },
-    Kick { member: String },
-    Pause,
-    Resume,
-    Goal { text: String },
-    Task { title: String },
-    Block { task_id: u32, reason: String },
-    Stop { confirm: bool },
-    Start,
-    Help,
-    Send { role: String, message: String },
-}
-
-fn parse_telegram_command(text: &str) -> Result<Option<TelegramCommand>> {
-    let trimmed = text.trim();
-    if !trimmed.starts_with('/') {
-        return Ok(None);
-    }
-
-    let (name, rest) = trimmed
-        .split_once(char::is_whitespace)
-        .map(|(name, rest)| (name, rest.trim()))
-        .unwrap_or((trimmed, ""));
-
-    match name {
-        "/status" => {
-            if rest.is_empty() {
-                Ok(Some(TelegramCommand::Status))
-            } else {
-                Err(anyhow!("Usage: /status"))
-            }
-        }
-        "/board" => {
-            let filter = if rest.is_empty() {
-                None
-            } else {
-                Some(rest.to_string())
-            };
-            Ok(Some(TelegramCommand::Board { filter }))
-        }
-        "/logs" => {
-            if rest.is_empty() {
-                bail!("Usage: /logs <engineer>");
-            }
-            Ok(Some(TelegramCommand::Logs {
-                member: rest.to_string(),
-            }))
-        }
-        "/health" => {
-            if rest.is_empty() {
-                Ok(Some(TelegramCommand::Health))
-            } else {
-                Err(anyhow!("Usage: /health"))
-            }
-        }
-        "/assign" => {
-            let mut parts = rest
-                .splitn(2, char::is_whitespace)
-                .filter(|part| !part.is_empty());
-            let engineer = parts
-                .next()
-                .ok_or_else(|| anyhow!("Usage: /assign <engineer> <task>"))?;
-            let task = parts.next().unwrap_or("").trim();
-            if task.is_empty() {
-                bail!("Usage: /assign <engineer> <task>");
-            }
-            Ok(Some(TelegramCommand::Assign {
-                engineer: engineer.to_string(),
-                task: task.to_string(),
-            }))
-        }
-        "/kick" => {
-            if rest.is_empty() {
-                bail!("Usage: /kick <engineer>");
-            }
-            Ok(Some(TelegramCommand::Kick {
-                member: rest.to_string(),
-            }))
-        }
-        "/pause" => {
-            if rest.is_empty() {
-                Ok(Some(TelegramCommand::Pause))
-            } else {
-                Err(anyhow!("Usage: /pause"))
-            }
-        }
-        "/resume" => {
-            if rest.is_empty() {
-                Ok(Some(TelegramCommand::Resume))
-            } else {
-                Err(anyhow!("Usage: /resume"))
-            }
-        }
-        "/goal" => {
-            if rest.is_empty() {
-                bail!("Usage: /goal <text>");
-            }
-            Ok(Some(TelegramCommand::Goal {
-                text: rest.to_string(),
-            }))
-        }
-        "/task" => {
-            if rest.is_empty() {
-                bail!("Usage: /task <title>");
-            }
-            Ok(Some(TelegramCommand::Task {
-                title: rest.to_string(),
-            }))
-        }
-        "/block" => {
-            let mut parts = rest
-                .splitn(2, char::is_whitespace)
-                .filter(|part| !part.is_empty());
-            let task_id = parts
-                .next()
-                .ok_or_else(|| anyhow!("Usage: /block <task> <reason>"))?
-                .trim_start_matches('#')
-                .parse::<u32>()
-                .map_err(|_| anyhow!("Usage: /block <task> <reason>"))?;
-            let reason = parts.next().unwrap_or("").trim();
-            if reason.is_empty() {
-                bail!("Usage: /block <task> <reason>");
-            }
-            Ok(Some(TelegramCommand::Block {
-                task_id,
-                reason: reason.to_string(),
-            }))
-        }
-        "/merge" => {
-            if rest.is_empty() {
-                bail!("Usage: /merge <task>");
-            }
-            let task_id = rest
-                .trim_start_matches('#')
-                .parse::<u32>()
-                .map_err(|_| anyhow!("Usage: /merge <task>"))?;
-            Ok(Some(TelegramCommand::Merge { task_id }))
-        }
-        "/stop" => Ok(Some(TelegramCommand::Stop {
-            confirm: rest == "confirm",
-        })),
-        "/start" => {
-            if rest.is_empty() {
-                Ok(Some(TelegramCommand::Start))
-            } else {
-                Err(anyhow!("Usage: /start"))
-            }
-        }
-        "/help" => {
-            if rest.is_empty() {
-                Ok(Some(TelegramCommand::Help))
-            } else {
-                Err(anyhow!("Usage: /help"))
-            }
-        }
-        "/send" => {
-            let mut parts = rest
-                .splitn(2, char::is_whitespace)
-                .filter(|part| !part.is_empty());
-            let role = parts
-                .next()
-                .ok_or_else(|| anyhow!("Usage: /send <role> <message>"))?;
-            let message = parts.next().unwrap_or("").trim();
-            if message.is_empty() {
-                bail!("Usage: /send <role> <message>");
-            }
-            Ok(Some(TelegramCommand::Send {
-                role: role.to_string(),
-                message: message.to_string(),
-            }))
-        }
-        _ => Err(anyhow!(
-            "Unknown command. Supported: /status, /board, /logs, /health, /assign, /merge, /kick, /pause, /resume, /goal, /task, /block, /stop, /start, /help, /send"
-        )),
-    }
-}
-
-fn render_telegram_board_summary(
+pub(super) fn render_telegram_board_summary(
     project_root: &std::path::Path,
     filter: Option<&str>,
 ) -> Result<String> {
@@ -702,7 +447,7 @@ fn render_telegram_board_summary(
     ))
 }
 
-fn render_telegram_logs(project_root: &std::path::Path, member: &str) -> Result<String> {
+pub(super) fn render_telegram_logs(project_root: &std::path::Path, member: &str) -> Result<String> {
     let path = crate::team::shim_log_path(project_root, member);
     let content = std::fs::read_to_string(&path)
         .with_context(|| format!("log not found for {member}: {}", path.display()))?;
@@ -714,7 +459,7 @@ fn render_telegram_logs(project_root: &std::path::Path, member: &str) -> Result<
     Ok(format!("{member} (last 5 lines)\n{rendered}"))
 }
 
-fn render_telegram_health_summary(
+pub(super) fn render_telegram_health_summary(
     project_root: &std::path::Path,
     members: &[crate::team::hierarchy::MemberInstance],
 ) -> Result<String> {
@@ -757,7 +502,7 @@ fn count_lock_files(project_root: &std::path::Path) -> usize {
     count
 }
 
-fn write_telegram_goal(project_root: &std::path::Path, text: &str) -> Result<()> {
+pub(super) fn write_telegram_goal(project_root: &std::path::Path, text: &str) -> Result<()> {
     let goal_path = project_root.join(".batty").join("goal.yaml");
     if let Some(parent) = goal_path.parent() {
         std::fs::create_dir_all(parent)?;
@@ -767,7 +512,7 @@ fn write_telegram_goal(project_root: &std::path::Path, text: &str) -> Result<()>
     Ok(())
 }
 
-fn create_telegram_task(project_root: &std::path::Path, title: &str) -> Result<String> {
+pub(super) fn create_telegram_task(project_root: &std::path::Path, title: &str) -> Result<String> {
     let board_dir = project_root
         .join(".batty")
         .join("team_config")
@@ -787,7 +532,7 @@ fn create_telegram_task(project_root: &std::path::Path, title: &str) -> Result<S
     ))
 }
 
-fn block_telegram_task(
+pub(super) fn block_telegram_task(
     project_root: &std::path::Path,
     task_id: u32,
     reason: &str,
@@ -806,10 +551,6 @@ fn parse_task_id_arg(text: &str) -> Option<u32> {
     text.trim().trim_start_matches('#').parse::<u32>().ok()
 }
 
-fn render_telegram_help() -> String {
-    "/status, /board [status], /logs <eng>, /health\n/assign <eng> <task|id>, /merge <task>, /kick <eng>, /pause, /resume\n/goal <text>, /task <title>, /block <task> <reason>, /stop [confirm], /start, /help, /send <role> <message>".to_string()
-}
-
 fn summarize_status_entries(entries: &[crate::team::status::StatusTaskEntry]) -> String {
     if entries.is_empty() {
         return "none".to_string();
@@ -859,7 +600,7 @@ fn engineer_for_merge_task(project_root: &std::path::Path, task_id: u32) -> Resu
     Ok(engineer)
 }
 
-fn preview_text(text: &str, max_chars: usize) -> String {
+pub(super) fn preview_text(text: &str, max_chars: usize) -> String {
     let mut chars = text.chars();
     let preview: String = chars.by_ref().take(max_chars).collect();
     if chars.next().is_some() {
@@ -887,6 +628,7 @@ mod tests {
     use crate::team::events::EventSink;
     use crate::team::failure_patterns::FailureTracker;
     use crate::team::hierarchy::MemberInstance;
+    use crate::team::operator::OperatorCommand;
     use crate::team::test_helpers::daemon_config_with_roles;
 
     struct RecordingChannel {
@@ -960,6 +702,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -1070,6 +819,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
@@ -1141,6 +897,8 @@ mod tests {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: Default::default(),
             context_pressure_tracker: Default::default(),
             last_health_check: Instant::now(),
@@ -1161,6 +919,21 @@ mod tests {
             merge_queue: crate::team::daemon::MergeQueue::default(),
             last_binary_freshness_check: Instant::now(),
             last_tiered_inbox_sweep: Instant::now(),
+            budget_ledger: Default::default(),
+            tool_activity: Default::default(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules: Default::default(),
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
+            slack_bot: None,
+            slack_event_cursor: 0,
+            webhook_event_cursor: 0,
+            webhook_worker: None,
+            hook_event_cursor: 0,
+            api_server: None,
+            prometheus: None,
+            task_traces: None,
         };
 
         backdate_idle_grace(&mut daemon, "scientist");
@@ -1237,6 +1010,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
@@ -1299,6 +1079,8 @@ mod tests {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: Default::default(),
             context_pressure_tracker: Default::default(),
             last_health_check: Instant::now(),
@@ -1319,6 +1101,21 @@ mod tests {
             merge_queue: crate::team::daemon::MergeQueue::default(),
             last_binary_freshness_check: Instant::now(),
             last_tiered_inbox_sweep: Instant::now(),
+            budget_ledger: Default::default(),
+            tool_activity: Default::default(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules: Default::default(),
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
+            slack_bot: None,
+            slack_event_cursor: 0,
+            webhook_event_cursor: 0,
+            webhook_worker: None,
+            hook_event_cursor: 0,
+            api_server: None,
+            prometheus: None,
+            task_traces: None,
         };
 
         let root = inbox::inboxes_root(tmp.path());
@@ -1383,6 +1180,13 @@ mod tests {
                     layout: None,
                     cost: Default::default(),
                     grafana: Default::default(),
+                    api: Default::default(),
+                    budget: Default::default(),
+                    agents: Default::default(),
+                    forge: Default::default(),
+                    webhooks: Default::default(),
+                    traces: Default::default(),
+                    prometheus: Default::default(),
                     use_shim: false,
                     use_sdk_mode: false,
                     auto_respawn_on_crash: false,
@@ -1476,6 +1280,8 @@ mod tests {
             manual_assign_cooldowns: HashMap::new(),
             backend_health: HashMap::new(),
             backend_quota_retry_at: HashMap::new(),
+            backend_failovers: HashMap::new(),
+            restored_primaries: HashMap::new(),
             narration_tracker: Default::default(),
             context_pressure_tracker: Default::default(),
             last_health_check: Instant::now(),
@@ -1496,6 +1302,21 @@ mod tests {
             merge_queue: crate::team::daemon::MergeQueue::default(),
             last_binary_freshness_check: Instant::now(),
             last_tiered_inbox_sweep: Instant::now(),
+            budget_ledger: Default::default(),
+            tool_activity: Default::default(),
+            shim_spawn_override: None,
+            last_forge_sync: None,
+            pattern_rules: Default::default(),
+            model_class_overrides: HashMap::new(),
+            allocation_model: Default::default(),
+            slack_bot: None,
+            slack_event_cursor: 0,
+            webhook_event_cursor: 0,
+            webhook_worker: None,
+            hook_event_cursor: 0,
+            api_server: None,
+            prometheus: None,
+            task_traces: None,
         };
 
         assert_eq!(daemon.automation_sender_for("eng-1"), "lead");
@@ -1528,6 +1349,9 @@ mod tests {
                 agents_channel_id: None,
                 commands_channel_id: None,
                 board_channel_id: None,
+                allowed_slack_user_ids: Vec::new(),
+                permissions: Default::default(),
+                api_base: None,
             }),
             nudge_interval_secs: None,
             receives_standup: None,
@@ -1800,6 +1624,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -1842,6 +1673,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -2056,6 +1894,13 @@ mod tests {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -2085,94 +1930,6 @@ mod tests {
         assert_eq!(daemon.automation_sender_for("mgr"), "boss");
     }
 
-    #[test]
-    fn parse_telegram_commands_supports_phone_friendly_forms() {
-        assert_eq!(
-            parse_telegram_command("/status").unwrap(),
-            Some(TelegramCommand::Status)
-        );
-        assert_eq!(
-            parse_telegram_command("/board").unwrap(),
-            Some(TelegramCommand::Board { filter: None })
-        );
-        assert_eq!(
-            parse_telegram_command("/board review").unwrap(),
-            Some(TelegramCommand::Board {
-                filter: Some("review".to_string()),
-            })
-        );
-        assert_eq!(
-            parse_telegram_command("/logs eng-1").unwrap(),
-            Some(TelegramCommand::Logs {
-                member: "eng-1".to_string(),
-            })
-        );
-        assert_eq!(
-            parse_telegram_command("/health").unwrap(),
-            Some(TelegramCommand::Health)
-        );
-        assert_eq!(
-            parse_telegram_command("/pause").unwrap(),
-            Some(TelegramCommand::Pause)
-        );
-        assert_eq!(
-            parse_telegram_command("/resume").unwrap(),
-            Some(TelegramCommand::Resume)
-        );
-        assert_eq!(
-            parse_telegram_command("/task Add remote control").unwrap(),
-            Some(TelegramCommand::Task {
-                title: "Add remote control".to_string(),
-            })
-        );
-        assert_eq!(
-            parse_telegram_command("/block 41 waiting on CI").unwrap(),
-            Some(TelegramCommand::Block {
-                task_id: 41,
-                reason: "waiting on CI".to_string(),
-            })
-        );
-        assert_eq!(
-            parse_telegram_command("/stop confirm").unwrap(),
-            Some(TelegramCommand::Stop { confirm: true })
-        );
-        assert_eq!(
-            parse_telegram_command("/help").unwrap(),
-            Some(TelegramCommand::Help)
-        );
-        assert_eq!(
-            parse_telegram_command("/assign eng-1 Fix flaky test").unwrap(),
-            Some(TelegramCommand::Assign {
-                engineer: "eng-1".to_string(),
-                task: "Fix flaky test".to_string(),
-            })
-        );
-        assert_eq!(
-            parse_telegram_command("/merge #41").unwrap(),
-            Some(TelegramCommand::Merge { task_id: 41 })
-        );
-        assert_eq!(
-            parse_telegram_command("/send architect Need review on task 41").unwrap(),
-            Some(TelegramCommand::Send {
-                role: "architect".to_string(),
-                message: "Need review on task 41".to_string(),
-            })
-        );
-    }
-
-    #[test]
-    fn parse_telegram_command_rejects_invalid_usage() {
-        assert_eq!(parse_telegram_command("hello").unwrap(), None);
-        assert!(parse_telegram_command("/assign eng-1").is_err());
-        assert!(parse_telegram_command("/logs").is_err());
-        assert!(parse_telegram_command("/merge nope").is_err());
-        assert!(parse_telegram_command("/goal").is_err());
-        assert!(parse_telegram_command("/task").is_err());
-        assert!(parse_telegram_command("/block 41").is_err());
-        assert!(parse_telegram_command("/send architect").is_err());
-        assert!(parse_telegram_command("/unknown").is_err());
-    }
-
     #[test]
     fn telegram_assign_command_delivers_assignment_to_inbox() {
         let tmp = tempfile::tempdir().unwrap();
@@ -2199,6 +1956,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
             RoleDef {
                 name: "eng".to_string(),
@@ -2222,6 +1981,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
         ];
         let mut config = daemon_config_with_roles(tmp.path(), roles);
@@ -2256,7 +2017,7 @@ mod tests {
         let mut daemon = TeamDaemon::new(config).unwrap();
 
         let reply = daemon
-            .execute_telegram_command(TelegramCommand::Assign {
+            .execute_operator_command(OperatorCommand::Assign {
                 engineer: "eng".to_string(),
                 task: "Fix flaky test".to_string(),
             })
@@ -2293,6 +2054,8 @@ mod tests {
             owns: Vec::new(),
             barrier_group: None,
             use_worktrees: false,
+            sandbox: None,
+            failover: Vec::new(),
         }];
         let mut config = daemon_config_with_roles(tmp.path(), roles);
         config.members = vec![MemberInstance {
@@ -2314,7 +2077,7 @@ mod tests {
             .insert("eng".to_string(), MemberState::Working);
 
         let error = daemon
-            .execute_telegram_command(TelegramCommand::Assign {
+            .execute_operator_command(OperatorCommand::Assign {
                 engineer: "eng".to_string(),
                 task: "Fix flaky test".to_string(),
             })
@@ -2348,6 +2111,8 @@ mod tests {
             owns: Vec::new(),
             barrier_group: None,
             use_worktrees: false,
+            sandbox: None,
+            failover: Vec::new(),
         }];
         let mut config = daemon_config_with_roles(tmp.path(), roles);
         config.members = vec![MemberInstance {
@@ -2372,7 +2137,7 @@ mod tests {
         );
 
         let error = daemon
-            .execute_telegram_command(TelegramCommand::Assign {
+            .execute_operator_command(OperatorCommand::Assign {
                 engineer: "eng".to_string(),
                 task: "41".to_string(),
             })
@@ -2407,6 +2172,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
             RoleDef {
                 name: "architect".to_string(),
@@ -2430,6 +2197,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
         ];
         let mut config = daemon_config_with_roles(tmp.path(), roles);
@@ -2464,7 +2233,7 @@ mod tests {
         let mut daemon = TeamDaemon::new(config).unwrap();
 
         let reply = daemon
-            .execute_telegram_command(TelegramCommand::Send {
+            .execute_operator_command(OperatorCommand::Send {
                 role: "architect".to_string(),
                 message: "Need a quick review".to_string(),
             })
@@ -2527,7 +2296,7 @@ mod tests {
         let mut daemon = TeamDaemon::new(daemon_config_with_roles(tmp.path(), Vec::new())).unwrap();
 
         let error = daemon
-            .execute_telegram_command(TelegramCommand::Merge { task_id: 41 })
+            .execute_operator_command(OperatorCommand::Merge { task_id: 41 })
             .unwrap_err();
 
         assert!(error.to_string().contains("not in review"));
@@ -2540,7 +2309,7 @@ mod tests {
 
         assert_eq!(
             daemon
-                .execute_telegram_command(TelegramCommand::Pause)
+                .execute_operator_command(OperatorCommand::Pause)
                 .unwrap(),
             "Automation paused."
         );
@@ -2548,7 +2317,7 @@ mod tests {
 
         assert_eq!(
             daemon
-                .execute_telegram_command(TelegramCommand::Resume)
+                .execute_operator_command(OperatorCommand::Resume)
                 .unwrap(),
             "Automation resumed."
         );
@@ -2597,6 +2366,8 @@ mod tests {
             owns: Vec::new(),
             barrier_group: None,
             use_worktrees: true,
+            sandbox: None,
+            failover: Vec::new(),
         }];
         let mut config = daemon_config_with_roles(tmp.path(), roles);
         config.members = vec![MemberInstance {
@@ -2615,7 +2386,7 @@ mod tests {
         let mut daemon = TeamDaemon::new(config).unwrap();
 
         let error = daemon
-            .execute_telegram_command(TelegramCommand::Kick {
+            .execute_operator_command(OperatorCommand::Kick {
                 member: "eng-1".to_string(),
             })
             .unwrap_err();
@@ -2679,6 +2450,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
             RoleDef {
                 name: "architect".to_string(),
@@ -2702,6 +2475,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
             RoleDef {
                 name: "eng".to_string(),
@@ -2725,6 +2500,8 @@ mod tests {
                 owns: Vec::new(),
                 barrier_group: None,
                 use_worktrees: false,
+                sandbox: None,
+                failover: Vec::new(),
             },
         ];
         let mut config = daemon_config_with_roles(tmp.path(), roles);
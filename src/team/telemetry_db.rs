@@ -1,14 +1,16 @@
 //! SQLite-backed telemetry database for agent performance tracking.
 //!
 //! Stores events, per-agent metrics, per-task metrics, and session summaries
-//! in `.batty/telemetry.db`. All tables use `CREATE TABLE IF NOT EXISTS` —
-//! no migration framework needed.
+//! in `.batty/telemetry.db`. The schema is versioned: `schema_version` records
+//! every applied entry of [`MIGRATIONS`], pending migrations run in order when
+//! the database is opened, and an existing database is copied aside first so
+//! an upgrade never loses history.
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
-use rusqlite::{Connection, params};
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, params};
 use serde::{Deserialize, Serialize};
 
 use super::events::TeamEvent;
@@ -155,21 +157,105 @@ impl SchemaRepairReport {
     }
 }
 
-/// Open or create the telemetry database, initializing the schema.
+/// A single schema change. Append new entries to [`MIGRATIONS`]; never edit,
+/// renumber, or reorder one that has shipped.
+struct Migration {
+    version: u32,
+    name: &'static str,
+    up: fn(&Connection) -> Result<()>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline tables and legacy column repair",
+        up: migrate_baseline,
+    },
+    Migration {
+        version: 2,
+        name: "tool_events table",
+        up: migrate_tool_events,
+    },
+];
+
+/// Schema version this build of batty expects.
+pub fn latest_schema_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+/// A migration that has not been applied yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    pub version: u32,
+    pub name: &'static str,
+}
+
+/// Outcome of bringing a database up to [`latest_schema_version`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub pending: Vec<PendingMigration>,
+    /// Copy of the database taken before the first migration ran.
+    pub backup: Option<PathBuf>,
+    pub dry_run: bool,
+}
+
+fn db_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(DB_FILENAME)
+}
+
+/// Open or create the telemetry database, applying pending migrations.
 pub fn open(project_root: &Path) -> Result<Connection> {
-    let db_path = project_root.join(".batty").join(DB_FILENAME);
-    let conn = Connection::open(&db_path)
+    let db_path = db_path(project_root);
+    let conn = open_read_write(&db_path)?;
+    run_migrations(&conn, Some(&db_path))?;
+    Ok(conn)
+}
+
+fn open_read_write(db_path: &Path) -> Result<Connection> {
+    let conn = Connection::open(db_path)
         .with_context(|| format!("failed to open telemetry db at {}", db_path.display()))?;
 
     // WAL mode for better concurrent read/write performance.
     conn.pragma_update(None, "journal_mode", "WAL")?;
     // Prevent indefinite blocking when the daemon holds a write lock (#676).
     conn.pragma_update(None, "busy_timeout", "5000")?;
-
-    init_schema(&conn)?;
     Ok(conn)
 }
 
+/// Report (and unless `dry_run`, apply) the migrations pending for the
+/// project's telemetry database. Backs the database up before changing it.
+pub fn migrate(project_root: &Path, dry_run: bool) -> Result<MigrationReport> {
+    let db_path = db_path(project_root);
+    if dry_run {
+        let Some(conn) = open_readonly(project_root)? else {
+            return Ok(MigrationReport {
+                from_version: 0,
+                to_version: latest_schema_version(),
+                pending: pending_migrations(0),
+                backup: None,
+                dry_run,
+            });
+        };
+        let from_version = schema_version(&conn)?;
+        ensure_known_version(from_version)?;
+        return Ok(MigrationReport {
+            from_version,
+            to_version: latest_schema_version(),
+            pending: pending_migrations(from_version),
+            backup: None,
+            dry_run,
+        });
+    }
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let conn = open_read_write(&db_path)?;
+    run_migrations(&conn, Some(&db_path))
+}
+
 /// Open the telemetry database in read-only mode for CLI queries (#676).
 ///
 /// Skips schema initialization (which requires a write lock) to avoid
@@ -203,7 +289,150 @@ pub fn open_in_memory() -> Result<Connection> {
     Ok(conn)
 }
 
+#[cfg(test)]
 fn init_schema(conn: &Connection) -> Result<()> {
+    run_migrations(conn, None).map(|_| ())
+}
+
+/// Version of the newest migration applied to `conn`; 0 for a database
+/// created before versioning (or not created at all).
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    let has_table: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+            [],
+            |row| row.get(0),
+        )
+        .context("failed to inspect telemetry schema version")?;
+    if !has_table {
+        return Ok(0);
+    }
+    let version: Option<u32> = conn
+        .query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+            row.get(0)
+        })
+        .context("failed to read telemetry schema version")?;
+    Ok(version.unwrap_or(0))
+}
+
+fn pending_migrations(from_version: u32) -> Vec<PendingMigration> {
+    MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > from_version)
+        .map(|migration| PendingMigration {
+            version: migration.version,
+            name: migration.name,
+        })
+        .collect()
+}
+
+fn ensure_known_version(version: u32) -> Result<()> {
+    let latest = latest_schema_version();
+    if version > latest {
+        bail!(
+            "telemetry database schema version {version} is newer than this batty supports ({latest}); upgrade batty"
+        );
+    }
+    Ok(())
+}
+
+/// Apply every pending migration, each in its own immediate transaction so a
+/// concurrent opener (daemon vs. CLI) never applies one twice. When `db_path`
+/// names an existing database with data, it is backed up first.
+fn run_migrations(conn: &Connection, db_path: Option<&Path>) -> Result<MigrationReport> {
+    let from_version = schema_version(conn)?;
+    ensure_known_version(from_version)?;
+    let pending = pending_migrations(from_version);
+    let mut report = MigrationReport {
+        from_version,
+        to_version: from_version,
+        pending,
+        backup: None,
+        dry_run: false,
+    };
+    if report.pending.is_empty() {
+        return Ok(report);
+    }
+
+    if let Some(db_path) = db_path
+        && has_user_tables(conn)?
+    {
+        report.backup = Some(backup_database(conn, db_path, from_version)?);
+    }
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version     INTEGER PRIMARY KEY,
+            name        TEXT NOT NULL,
+            applied_at  INTEGER NOT NULL
+        );",
+    )
+    .context("failed to create schema_version table")?;
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > from_version)
+    {
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)
+            .context("failed to start telemetry migration")?;
+        let applied: Option<u32> = tx
+            .query_row(
+                "SELECT version FROM schema_version WHERE version = ?1",
+                params![migration.version],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if applied.is_none() {
+            (migration.up)(&tx).with_context(|| {
+                format!(
+                    "telemetry migration {} ({}) failed",
+                    migration.version, migration.name
+                )
+            })?;
+            tx.execute(
+                "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![
+                    migration.version,
+                    migration.name,
+                    chrono::Utc::now().timestamp()
+                ],
+            )?;
+        }
+        tx.commit().with_context(|| {
+            format!("failed to commit telemetry migration {}", migration.version)
+        })?;
+        report.to_version = migration.version;
+    }
+    Ok(report)
+}
+
+fn has_user_tables(conn: &Connection) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%')",
+        [],
+        |row| row.get(0),
+    )
+    .context("failed to inspect telemetry schema")
+}
+
+/// Write a consistent copy of the database next to it, named after the
+/// schema version it holds.
+fn backup_database(conn: &Connection, db_path: &Path, version: u32) -> Result<PathBuf> {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S");
+    let backup = db_path.with_file_name(format!("{DB_FILENAME}.v{version}-{stamp}.bak"));
+    if backup.exists() {
+        std::fs::remove_file(&backup)
+            .with_context(|| format!("failed to replace {}", backup.display()))?;
+    }
+    conn.execute(
+        "VACUUM INTO ?1",
+        params![backup.to_string_lossy().into_owned()],
+    )
+    .with_context(|| format!("failed to back up telemetry db to {}", backup.display()))?;
+    Ok(backup)
+}
+
+fn migrate_baseline(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS events (
@@ -308,6 +537,7 @@ fn init_schema(conn: &Connection) -> Result<()> {
         ",
     )
     .context("failed to initialize telemetry schema")?;
+    // Databases created before versioning may predate newer columns.
     let repairs = repair_legacy_schema(conn)?;
     if !repairs.is_empty() {
         record_schema_repair_event(conn, &repairs)?;
@@ -315,6 +545,31 @@ fn init_schema(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn migrate_tool_events(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS tool_events (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp   INTEGER NOT NULL,
+            role        TEXT NOT NULL,
+            task_id     TEXT,
+            kind        TEXT NOT NULL,
+            tool        TEXT,
+            path        TEXT,
+            command     TEXT,
+            exit_code   INTEGER,
+            duration_ms INTEGER,
+            is_error    INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_tool_events_role_ts
+            ON tool_events(role, timestamp);
+        ",
+    )
+    .context("failed to create tool_events table")?;
+    Ok(())
+}
+
 fn repair_legacy_schema(conn: &Connection) -> Result<SchemaRepairReport> {
     let mut repairs = SchemaRepairReport::default();
     ensure_table_columns(conn, "task_metrics", TASK_METRICS_COLUMNS, &mut repairs)?;
@@ -381,6 +636,8 @@ fn record_schema_repair_event(conn: &Connection, repairs: &SchemaRepairReport) -
 pub(crate) fn install_legacy_schema_for_tests(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
+        DROP TABLE IF EXISTS schema_version;
+        DROP TABLE IF EXISTS tool_events;
         DROP TABLE IF EXISTS events;
         DROP TABLE IF EXISTS agent_metrics;
         DROP TABLE IF EXISTS task_metrics;
@@ -1270,6 +1527,78 @@ pub fn query_latest_review_queue_metric(conn: &Connection) -> Result<Option<Revi
     }))
 }
 
+/// One structured tool event reported by an SDK-mode shim.
+///
+/// `kind` is `tool_finished`, `file_edited`, or `command_run`. Tool starts
+/// are tracked in memory only; the finish row carries the duration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolEventRow {
+    pub timestamp: i64,
+    pub role: String,
+    pub task_id: Option<String>,
+    pub kind: String,
+    pub tool: Option<String>,
+    pub path: Option<String>,
+    pub command: Option<String>,
+    pub exit_code: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub is_error: bool,
+}
+
+pub fn record_tool_event(conn: &Connection, row: &ToolEventRow) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tool_events
+         (timestamp, role, task_id, kind, tool, path, command, exit_code, duration_ms, is_error)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            row.timestamp,
+            row.role,
+            row.task_id,
+            row.kind,
+            row.tool,
+            row.path,
+            row.command,
+            row.exit_code,
+            row.duration_ms,
+            row.is_error,
+        ],
+    )
+    .context("failed to record tool event")?;
+    Ok(())
+}
+
+/// Most recent tool events, newest first, optionally for one member.
+pub fn query_tool_events(
+    conn: &Connection,
+    role: Option<&str>,
+    limit: usize,
+) -> Result<Vec<ToolEventRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT timestamp, role, task_id, kind, tool, path, command, exit_code, duration_ms, is_error
+         FROM tool_events
+         WHERE ?1 IS NULL OR role = ?1
+         ORDER BY timestamp DESC, id DESC
+         LIMIT ?2",
+    )?;
+    let rows = stmt
+        .query_map(params![role, limit as i64], |row| {
+            Ok(ToolEventRow {
+                timestamp: row.get(0)?,
+                role: row.get(1)?,
+                task_id: row.get(2)?,
+                kind: row.get(3)?,
+                tool: row.get(4)?,
+                path: row.get(5)?,
+                command: row.get(6)?,
+                exit_code: row.get(7)?,
+                duration_ms: row.get(8)?,
+                is_error: row.get(9)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
 /// Recent events row for `batty telemetry events`.
 #[derive(Debug, Clone)]
 pub struct EventRow {
@@ -1674,6 +2003,155 @@ mod tests {
         init_schema(&conn).unwrap();
     }
 
+    fn user_tables(conn: &Connection) -> BTreeSet<String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table'")
+            .unwrap();
+        stmt.query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap()
+    }
+
+    fn write_legacy_db(project_root: &Path) -> PathBuf {
+        let path = db_path(project_root);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let legacy = Connection::open(&path).unwrap();
+        install_legacy_schema_for_tests(&legacy).unwrap();
+        legacy
+            .execute(
+                "INSERT INTO events (timestamp, event_type, payload) VALUES (1, 'daemon_started', '{}')",
+                [],
+            )
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn migrations_are_strictly_ordered() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        assert_eq!(versions[0], 1);
+        assert!(versions.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        assert_eq!(latest_schema_version(), *versions.last().unwrap());
+    }
+
+    #[test]
+    fn fresh_database_records_every_migration_without_backup() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".batty")).unwrap();
+        let conn = open(tmp.path()).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_schema_version());
+        let recorded: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(recorded as usize, MIGRATIONS.len());
+        assert!(user_tables(&conn).contains("tool_events"));
+
+        let backups = std::fs::read_dir(tmp.path().join(".batty"))
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".bak")
+            })
+            .count();
+        assert_eq!(backups, 0);
+        let report = migrate(tmp.path(), false).unwrap();
+        assert!(report.pending.is_empty());
+        assert!(report.backup.is_none());
+    }
+
+    #[test]
+    fn legacy_database_is_backed_up_then_migrated() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_legacy_db(tmp.path());
+
+        let report = migrate(tmp.path(), false).unwrap();
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.to_version, latest_schema_version());
+        assert_eq!(report.pending.len(), MIGRATIONS.len());
+
+        let backup = Connection::open(report.backup.as_ref().unwrap()).unwrap();
+        assert_eq!(schema_version(&backup).unwrap(), 0);
+        assert!(
+            !query_table_columns(&backup, "task_metrics")
+                .unwrap()
+                .contains("confidence_score")
+        );
+        let backed_up: i64 = backup
+            .query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(backed_up, 1);
+
+        let conn = open(tmp.path()).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_schema_version());
+        assert!(
+            query_table_columns(&conn, "task_metrics")
+                .unwrap()
+                .contains("confidence_score")
+        );
+        let kept: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM events WHERE event_type = 'daemon_started'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(kept, 1);
+    }
+
+    #[test]
+    fn dry_run_reports_pending_without_touching_database() {
+        let tmp = tempfile::tempdir().unwrap();
+        let report = migrate(tmp.path(), true).unwrap();
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.pending.len(), MIGRATIONS.len());
+        assert!(!db_path(tmp.path()).exists());
+
+        write_legacy_db(tmp.path());
+        let report = migrate(tmp.path(), true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.pending[0].version, 1);
+        assert!(report.backup.is_none());
+        let conn = Connection::open(db_path(tmp.path())).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 0);
+        assert!(!user_tables(&conn).contains("tool_events"));
+    }
+
+    #[test]
+    fn partially_migrated_database_applies_only_newer_migrations() {
+        let conn = open_in_memory().unwrap();
+        conn.execute_batch("DROP TABLE tool_events; DELETE FROM schema_version WHERE version = 2;")
+            .unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+
+        let report = run_migrations(&conn, None).unwrap();
+        assert_eq!(report.from_version, 1);
+        assert_eq!(
+            report.pending,
+            vec![PendingMigration {
+                version: 2,
+                name: "tool_events table"
+            }]
+        );
+        assert!(user_tables(&conn).contains("tool_events"));
+    }
+
+    #[test]
+    fn newer_schema_version_is_rejected() {
+        let conn = open_in_memory().unwrap();
+        conn.execute(
+            "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, 'future', 0)",
+            params![latest_schema_version() + 1],
+        )
+        .unwrap();
+        let error = init_schema(&conn).unwrap_err();
+        assert!(error.to_string().contains("upgrade batty"), "{error}");
+    }
+
     #[test]
     fn legacy_schema_repairs_missing_columns_once() {
         let conn = Connection::open_in_memory().unwrap();
@@ -2498,6 +2976,46 @@ mod tests {
         assert_eq!(row.review_slo_secs, 3600);
     }
 
+    #[test]
+    fn tool_events_round_trip_newest_first() {
+        let conn = open_in_memory().unwrap();
+        let edit = ToolEventRow {
+            timestamp: 10,
+            role: "eng-1".to_string(),
+            task_id: Some("42".to_string()),
+            kind: "file_edited".to_string(),
+            tool: Some("Edit".to_string()),
+            path: Some("src/lib.rs".to_string()),
+            command: None,
+            exit_code: None,
+            duration_ms: None,
+            is_error: false,
+        };
+        let command = ToolEventRow {
+            timestamp: 20,
+            kind: "command_run".to_string(),
+            tool: None,
+            path: None,
+            command: Some("cargo test".to_string()),
+            exit_code: Some(101),
+            duration_ms: Some(42_000),
+            is_error: true,
+            ..edit.clone()
+        };
+        let other = ToolEventRow {
+            role: "eng-2".to_string(),
+            ..edit.clone()
+        };
+        for row in [&edit, &command, &other] {
+            record_tool_event(&conn, row).unwrap();
+        }
+
+        let rows = query_tool_events(&conn, Some("eng-1"), 10).unwrap();
+        assert_eq!(rows, vec![command, edit]);
+        assert_eq!(query_tool_events(&conn, None, 10).unwrap().len(), 3);
+        assert_eq!(query_tool_events(&conn, None, 1).unwrap().len(), 1);
+    }
+
     #[test]
     fn task_cycle_time_rows_replace_existing_snapshot() {
         let conn = open_in_memory().unwrap();
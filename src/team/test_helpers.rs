@@ -44,6 +44,13 @@ pub(crate) fn team_config_with_roles(roles: Vec<RoleDef>) -> TeamConfig {
         layout: None,
         cost: Default::default(),
         grafana: Default::default(),
+        api: Default::default(),
+        budget: Default::default(),
+        agents: Default::default(),
+        forge: Default::default(),
+        webhooks: Default::default(),
+        traces: Default::default(),
+        prometheus: Default::default(),
         use_shim: false,
         use_sdk_mode: false,
         auto_respawn_on_crash: false,
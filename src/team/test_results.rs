@@ -1,5 +1,17 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result, bail};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+/// How far before the start of the run's first whole second a report's mtime
+/// may fall and still count as fresh. Filesystems with coarse timestamps
+/// (ext3, HFS+, FAT, many NFS mounts) floor mtimes to 1s or 2s, so a report
+/// written in the same second the run started can appear older than it.
+const REPORT_MTIME_GRANULARITY: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TestFailure {
@@ -89,7 +101,13 @@ pub struct TestRunOutput {
 
 pub fn parse(command_text: &str, output: &str, passed: bool) -> TestResults {
     let command = command_text.to_ascii_lowercase();
-    if command.contains("pytest") {
+    if command.contains("go test") && command.contains("-json") {
+        parse_go_test_json(output, passed)
+    } else if command.contains("nextest") && command.contains("libtest-json") {
+        parse_libtest_json(output, passed)
+    } else if command.contains("vitest") {
+        parse_vitest(output, passed)
+    } else if command.contains("pytest") {
         parse_pytest(output, passed)
     } else if command.contains("jest") {
         parse_jest(output, passed)
@@ -343,6 +361,652 @@ pub fn parse_jest(output: &str, passed: bool) -> TestResults {
     results
 }
 
+/// Rewrite `command_text` so the framework skips `tests`.
+///
+/// Uses each runner's own mechanism: libtest `--skip` for cargo and nextest,
+/// `-skip` for `go test`, `-k "not ..."` for pytest, and a negative
+/// `--testNamePattern` for jest and vitest. The names are also exported as
+/// newline-separated `BATTY_SKIP_TESTS` so custom scripts can honor them.
+/// Flags are only appended to a simple single command: for pipelines, lists,
+/// redirections, or subshells they would land on the wrong command, so those
+/// get only `BATTY_SKIP_TESTS` and run unskipped unless they honor it.
+pub fn with_skipped_tests(command_text: &str, tests: &[String]) -> String {
+    if tests.is_empty() {
+        return command_text.to_string();
+    }
+    let command = command_text.to_ascii_lowercase();
+    let export = format!(
+        "export BATTY_SKIP_TESTS={}; ",
+        shell_quote(&tests.join("\n"))
+    );
+    if !is_simple_command(command_text) {
+        warn!(
+            command = command_text,
+            "test command is not a single simple command; quarantined tests are only exported in BATTY_SKIP_TESTS"
+        );
+        return format!("{export}{command_text}");
+    }
+    let leaf =
+        |test: &String| -> String { test.rsplit("::").next().unwrap_or(test).trim().to_string() };
+    let flags = if command.contains("cargo") {
+        let skips: Vec<String> = tests
+            .iter()
+            .map(|test| format!("--skip {}", shell_quote(test)))
+            .collect();
+        let separator = if command_text.contains(" -- ") {
+            ""
+        } else {
+            " --"
+        };
+        format!("{separator} {}", skips.join(" "))
+    } else if command.contains("go test") {
+        let names: Vec<String> = tests
+            .iter()
+            .map(|test| regex::escape(&leaf(test)))
+            .collect();
+        format!(
+            " -skip {}",
+            shell_quote(&format!("^({})$", names.join("|")))
+        )
+    } else if command.contains("pytest") {
+        let names: Vec<String> = tests.iter().map(leaf).collect();
+        format!(
+            " -k {}",
+            shell_quote(&format!("not ({})", names.join(" or ")))
+        )
+    } else if command.contains("jest") || command.contains("vitest") {
+        let names: Vec<String> = tests
+            .iter()
+            .map(|test| regex::escape(test.rsplit(" :: ").next().unwrap_or(test).trim()))
+            .collect();
+        format!(
+            " --testNamePattern {}",
+            shell_quote(&format!("^(?!.*(?:{})$)", names.join("|")))
+        )
+    } else {
+        String::new()
+    };
+    format!("{export}{command_text}{flags}")
+}
+
+/// Whether `command_text` is one command with arguments and no unquoted shell
+/// operators, so flags appended to it reach that command.
+fn is_simple_command(command_text: &str) -> bool {
+    let mut quote = None;
+    let mut chars = command_text.trim().chars();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some(open), _) if ch == open => quote = None,
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(ch),
+            (None, '\\') => {
+                chars.next();
+            }
+            (None, '|' | '&' | ';' | '<' | '>' | '(' | ')' | '`' | '$' | '\n' | '#') => {
+                return false;
+            }
+            (None, _) => {}
+        }
+    }
+    quote.is_none()
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Whether `command_text` streams one JSON record per line, which must be
+/// parsed from the full output rather than its trimmed tail.
+pub fn streams_json(command_text: &str) -> bool {
+    let command = command_text.to_ascii_lowercase();
+    (command.contains("go test") && command.contains("-json"))
+        || (command.contains("nextest") && command.contains("libtest-json"))
+}
+
+/// Read structured results from `report`, resolved against `dir`.
+///
+/// `report` may name a single file or a directory whose `.xml` and `.json`
+/// files are merged. Files last modified before `since` are ignored so a
+/// report left over from an earlier run is never mistaken for this one.
+/// Returns `Ok(None)` when no fresh report exists.
+pub fn load_report(dir: &Path, report: &str, since: SystemTime) -> Result<Option<TestResults>> {
+    let path = dir.join(report);
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in std::fs::read_dir(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+        {
+            let file = entry?.path();
+            if matches!(
+                file.extension().and_then(|ext| ext.to_str()),
+                Some("xml" | "json")
+            ) {
+                files.push(file);
+            }
+        }
+        files.sort();
+    } else if path.is_file() {
+        files.push(path);
+    }
+
+    let since = report_fresh_cutoff(since);
+    let mut merged: Option<TestResults> = None;
+    for file in files {
+        let fresh = std::fs::metadata(&file)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified >= since);
+        if !fresh {
+            continue;
+        }
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        let results = parse_report(&content)
+            .with_context(|| format!("failed to parse {}", file.display()))?;
+        merged = Some(match merged {
+            Some(existing) => merge_results(existing, results),
+            None => results,
+        });
+    }
+    Ok(merged)
+}
+
+/// Earliest mtime a report written after `started_at` can carry, allowing
+/// for filesystems that floor timestamps to whole (or even) seconds.
+fn report_fresh_cutoff(started_at: SystemTime) -> SystemTime {
+    let whole_secs = started_at
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| Duration::from_secs(elapsed.as_secs()))
+        .unwrap_or_default();
+    (SystemTime::UNIX_EPOCH + whole_secs)
+        .checked_sub(REPORT_MTIME_GRANULARITY)
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Parse a report file, detecting JUnit XML, vitest/jest JSON, `go test -json`,
+/// or libtest JSON from its content.
+pub fn parse_report(content: &str) -> Result<TestResults> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('<') {
+        return parse_junit_xml(content);
+    }
+    if let Ok(document) = serde_json::from_str::<Value>(trimmed)
+        && document.get("testResults").is_some()
+    {
+        return parse_vitest_json(&document);
+    }
+    let first_record = trimmed
+        .lines()
+        .find_map(|line| serde_json::from_str::<Value>(line.trim()).ok());
+    match first_record {
+        Some(record) if record.get("Action").is_some() => Ok(parse_go_test_json(content, true)),
+        Some(record) if record.get("type").is_some() && record.get("event").is_some() => {
+            Ok(parse_libtest_json(content, true))
+        }
+        _ => bail!("unrecognized test report format"),
+    }
+}
+
+fn merge_results(mut left: TestResults, right: TestResults) -> TestResults {
+    if left.framework != right.framework {
+        left.framework = "mixed".to_string();
+    }
+    left.total = match (left.total, right.total) {
+        (None, None) => None,
+        (left_total, right_total) => {
+            Some(left_total.unwrap_or_default() + right_total.unwrap_or_default())
+        }
+    };
+    left.passed += right.passed;
+    left.failed += right.failed;
+    left.ignored += right.ignored;
+    left.failures.extend(right.failures);
+    left.summary = None;
+    left
+}
+
+/// Parse JUnit XML as written by surefire, pytest `--junitxml`, `go-junit-report`,
+/// `cargo nextest` JUnit output, jest-junit, and most other CI reporters.
+pub fn parse_junit_xml(xml: &str) -> Result<TestResults> {
+    let tag_re =
+        Regex::new(r"(?s)<(/?)([A-Za-z_][\w:.-]*)((?:[^>\x22']|\x22[^\x22]*\x22|'[^']*')*?)(/?)>")
+            .expect("valid regex");
+    let attr_re = Regex::new(r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex");
+
+    let mut results = TestResults {
+        framework: "junit".to_string(),
+        total: None,
+        passed: 0,
+        failed: 0,
+        ignored: 0,
+        failures: Vec::new(),
+        summary: None,
+    };
+    let mut saw_testsuite = false;
+    let mut case: Option<JunitCase> = None;
+
+    for captures in tag_re.captures_iter(xml) {
+        let closing = !captures[1].is_empty();
+        let tag = &captures[2];
+        let self_closing = !captures[4].is_empty();
+        let whole = captures.get(0).expect("match");
+        let attrs: std::collections::HashMap<&str, String> = attr_re
+            .captures_iter(&captures[3])
+            .map(|attr| {
+                let value = attr
+                    .get(2)
+                    .or(attr.get(3))
+                    .map_or("", |value| value.as_str());
+                (
+                    attr.get(1).expect("name").as_str(),
+                    decode_xml_entities(value),
+                )
+            })
+            .collect();
+
+        match (tag, closing) {
+            ("testsuite" | "testsuites", false) => saw_testsuite = true,
+            ("testcase", false) => {
+                let name = attrs.get("name").cloned().unwrap_or_default();
+                let test_name = match attrs.get("classname").filter(|class| !class.is_empty()) {
+                    Some(class) => format!("{class}::{name}"),
+                    None => name,
+                };
+                let location = attrs.get("file").map(|file| match attrs.get("line") {
+                    Some(line) => format!("{file}:{line}"),
+                    None => file.clone(),
+                });
+                let open = JunitCase {
+                    name: test_name,
+                    location,
+                    outcome: None,
+                    outcome_body_start: None,
+                };
+                if self_closing {
+                    finish_junit_case(&mut results, open);
+                } else {
+                    case = Some(open);
+                }
+            }
+            ("failure" | "error", false) => {
+                if let Some(open) = case.as_mut() {
+                    open.outcome = Some((false, attrs.get("message").cloned()));
+                    open.outcome_body_start = (!self_closing).then_some(whole.end());
+                }
+            }
+            ("failure" | "error", true) => {
+                if let Some(open) = case.as_mut()
+                    && let Some(start) = open.outcome_body_start.take()
+                    && let Some((false, message)) = open.outcome.as_mut()
+                    && message.as_deref().is_none_or(str::is_empty)
+                {
+                    let body = xml[start..whole.start()]
+                        .trim()
+                        .trim_start_matches("<![CDATA[")
+                        .trim_end_matches("]]>");
+                    let body = decode_xml_entities(body);
+                    *message = body
+                        .lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty())
+                        .map(normalize_message);
+                }
+            }
+            ("skipped", false) => {
+                if let Some(open) = case.as_mut() {
+                    open.outcome = Some((true, None));
+                }
+            }
+            ("testcase", true) => {
+                if let Some(open) = case.take() {
+                    finish_junit_case(&mut results, open);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !saw_testsuite {
+        bail!("no <testsuite> element found");
+    }
+    results.total = Some(results.passed + results.failed + results.ignored);
+    Ok(results)
+}
+
+struct JunitCase {
+    name: String,
+    location: Option<String>,
+    /// `(skipped, message)`; `None` while the case has passed so far.
+    outcome: Option<(bool, Option<String>)>,
+    outcome_body_start: Option<usize>,
+}
+
+fn finish_junit_case(results: &mut TestResults, case: JunitCase) {
+    match case.outcome {
+        None => results.passed += 1,
+        Some((true, _)) => results.ignored += 1,
+        Some((false, message)) => {
+            results.failed += 1;
+            results.failures.push(TestFailure {
+                test_name: case.name,
+                message: message
+                    .map(|message| normalize_message(&message))
+                    .filter(|message| !message.is_empty()),
+                location: case.location,
+            });
+        }
+    }
+}
+
+fn decode_xml_entities(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+/// Parse the event stream from `go test -json`.
+pub fn parse_go_test_json(output: &str, passed: bool) -> TestResults {
+    let location_re = Regex::new(r"^([\w./-]+\.go:\d+):\s*(.*)$").expect("valid regex");
+    let mut results = TestResults {
+        framework: "go".to_string(),
+        total: None,
+        passed: 0,
+        failed: 0,
+        ignored: 0,
+        failures: Vec::new(),
+        summary: None,
+    };
+    let mut outputs: std::collections::HashMap<String, Vec<String>> =
+        std::collections::HashMap::new();
+    let mut saw_record = false;
+
+    for line in output.lines() {
+        let Ok(record) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        let Some(action) = record.get("Action").and_then(Value::as_str) else {
+            continue;
+        };
+        saw_record = true;
+        let Some(test) = record.get("Test").and_then(Value::as_str) else {
+            continue;
+        };
+        let package = record.get("Package").and_then(Value::as_str).unwrap_or("");
+        let key = if package.is_empty() {
+            test.to_string()
+        } else {
+            format!("{package}::{test}")
+        };
+        match action {
+            "output" => {
+                if let Some(text) = record.get("Output").and_then(Value::as_str) {
+                    outputs.entry(key).or_default().push(text.to_string());
+                }
+            }
+            "pass" => results.passed += 1,
+            "skip" => results.ignored += 1,
+            "fail" => {
+                results.failed += 1;
+                let mut failure = TestFailure {
+                    test_name: key.clone(),
+                    message: None,
+                    location: None,
+                };
+                for text in outputs.remove(&key).unwrap_or_default() {
+                    let trimmed = text.trim();
+                    if trimmed.is_empty()
+                        || trimmed.starts_with("=== ")
+                        || trimmed.starts_with("--- ")
+                    {
+                        continue;
+                    }
+                    if let Some(captures) = location_re.captures(trimmed) {
+                        failure.location = Some(captures[1].to_string());
+                        failure.message = Some(normalize_message(&captures[2]));
+                        break;
+                    }
+                    if failure.message.is_none() {
+                        failure.message = Some(normalize_message(trimmed));
+                    }
+                }
+                results.failures.push(failure);
+            }
+            _ => {}
+        }
+    }
+
+    if !saw_record && !passed {
+        results.failed = 1;
+    }
+    results.total = Some(results.passed + results.failed + results.ignored);
+    results
+}
+
+/// Parse libtest JSON (`cargo nextest run --message-format libtest-json`, or
+/// `cargo test -- -Z unstable-options --format json`).
+pub fn parse_libtest_json(output: &str, passed: bool) -> TestResults {
+    let mut results = TestResults {
+        framework: "cargo".to_string(),
+        total: None,
+        passed: 0,
+        failed: 0,
+        ignored: 0,
+        failures: Vec::new(),
+        summary: None,
+    };
+    let mut saw_record = false;
+
+    for line in output.lines() {
+        let Ok(record) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        if record.get("type").and_then(Value::as_str) != Some("test") {
+            continue;
+        }
+        let Some(event) = record.get("event").and_then(Value::as_str) else {
+            continue;
+        };
+        saw_record = true;
+        let name = record.get("name").and_then(Value::as_str).unwrap_or("");
+        // nextest prefixes names with the binary id: `crate::bin$module::test`.
+        let name = name.rsplit_once('$').map_or(name, |(_, test)| test);
+        match event {
+            "ok" => results.passed += 1,
+            "ignored" => results.ignored += 1,
+            "failed" | "timeout" => {
+                results.failed += 1;
+                let stdout = record.get("stdout").and_then(Value::as_str).unwrap_or("");
+                let mut failure = TestFailure {
+                    test_name: name.to_string(),
+                    message: None,
+                    location: None,
+                };
+                let mut lines = stdout.lines().map(str::trim);
+                while let Some(line) = lines.next() {
+                    if let Some((location, message)) = parse_cargo_panic_line(line) {
+                        failure.location = Some(location);
+                        failure.message = message.or_else(|| {
+                            lines
+                                .by_ref()
+                                .find(|line| !line.is_empty())
+                                .map(normalize_message)
+                        });
+                        break;
+                    }
+                }
+                if failure.message.is_none() && event == "timeout" {
+                    failure.message = Some("timed out".to_string());
+                }
+                results.failures.push(failure);
+            }
+            _ => {}
+        }
+    }
+
+    if !saw_record && !passed {
+        results.failed = 1;
+    }
+    results.total = Some(results.passed + results.failed + results.ignored);
+    results
+}
+
+/// Parse the default vitest reporter output.
+pub fn parse_vitest(output: &str, passed: bool) -> TestResults {
+    let summary_re = Regex::new(r"^\s*Tests\s+(.+?)\s+\((\d+)\)\s*$").expect("valid regex");
+    let count_re = Regex::new(r"(\d+)\s+(failed|passed|skipped|todo)").expect("valid regex");
+    let fail_re = Regex::new(r"^\s*FAIL\s+(\S+)\s+>\s+(.+?)\s*$").expect("valid regex");
+    let assertion_re = Regex::new(r"^\s*(\w*Error):\s*(.+)$").expect("valid regex");
+    let location_re = Regex::new(r"^\s*❯\s+(\S+:\d+:\d+)").expect("valid regex");
+
+    let mut results = TestResults {
+        framework: "vitest".to_string(),
+        total: None,
+        passed: 0,
+        failed: if passed { 0 } else { 1 },
+        ignored: 0,
+        failures: Vec::new(),
+        summary: None,
+    };
+    let mut seen = std::collections::HashSet::new();
+
+    for line in output.lines() {
+        let line = strip_ansi(line);
+        if let Some(captures) = summary_re.captures(&line) {
+            results.failed = 0;
+            for count in count_re.captures_iter(&captures[1]) {
+                let value: u32 = count[1].parse().unwrap_or(0);
+                match &count[2] {
+                    "failed" => results.failed = value,
+                    "passed" => results.passed = value,
+                    _ => results.ignored += value,
+                }
+            }
+            results.total = captures[2].parse().ok();
+            results.summary = Some(line.trim().to_string());
+            continue;
+        }
+        if let Some(captures) = fail_re.captures(&line) {
+            let test_name = format!("{} :: {}", &captures[1], captures[2].replace(" > ", " :: "));
+            if seen.insert(test_name.clone()) {
+                results.failures.push(TestFailure {
+                    test_name,
+                    message: None,
+                    location: None,
+                });
+            }
+            continue;
+        }
+        let Some(failure) = results.failures.last_mut() else {
+            continue;
+        };
+        if failure.message.is_none()
+            && let Some(captures) = assertion_re.captures(&line)
+        {
+            failure.message = Some(normalize_message(&captures[2]));
+        } else if failure.location.is_none()
+            && let Some(captures) = location_re.captures(&line)
+        {
+            failure.location = Some(captures[1].to_string());
+        }
+    }
+
+    results.failed = results.failed.max(results.failures.len() as u32);
+    results
+}
+
+/// Parse the JSON reporter shared by vitest and jest (`--reporter=json`,
+/// `--json`).
+pub fn parse_vitest_json(document: &Value) -> Result<TestResults> {
+    let Some(files) = document.get("testResults").and_then(Value::as_array) else {
+        bail!("vitest JSON report has no testResults array");
+    };
+    let count = |key: &str| {
+        document
+            .get(key)
+            .and_then(Value::as_u64)
+            .map(|value| value as u32)
+    };
+    let mut results = TestResults {
+        framework: "vitest".to_string(),
+        total: count("numTotalTests"),
+        passed: count("numPassedTests").unwrap_or(0),
+        failed: count("numFailedTests").unwrap_or(0),
+        ignored: count("numPendingTests").unwrap_or(0) + count("numTodoTests").unwrap_or(0),
+        failures: Vec::new(),
+        summary: None,
+    };
+
+    for file in files {
+        let file_name = file.get("name").and_then(Value::as_str).unwrap_or("");
+        let assertions = file
+            .get("assertionResults")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for assertion in assertions {
+            if assertion.get("status").and_then(Value::as_str) != Some("failed") {
+                continue;
+            }
+            let title = assertion
+                .get("fullName")
+                .or_else(|| assertion.get("title"))
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            let message = assertion
+                .get("failureMessages")
+                .and_then(Value::as_array)
+                .and_then(|messages| messages.first())
+                .and_then(Value::as_str)
+                .and_then(|message| {
+                    message
+                        .lines()
+                        .map(strip_ansi)
+                        .find(|line| !line.trim().is_empty())
+                })
+                .map(|line| normalize_message(&line));
+            let location = assertion
+                .get("location")
+                .and_then(|location| location.get("line"))
+                .and_then(Value::as_u64)
+                .map(|line| format!("{file_name}:{line}"));
+            results.failures.push(TestFailure {
+                test_name: format!("{file_name} :: {title}"),
+                message,
+                location,
+            });
+        }
+    }
+
+    results.failed = results.failed.max(results.failures.len() as u32);
+    if results.total.is_none() {
+        results.total = Some(results.passed + results.failed + results.ignored);
+    }
+    Ok(results)
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 fn normalize_message(message: &str) -> String {
     message.split_whitespace().collect::<Vec<_>>().join(" ")
 }
@@ -468,6 +1132,263 @@ Tests:       1 failed, 2 passed, 3 total
         );
     }
 
+    #[test]
+    fn parses_junit_xml_cases() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="pkg" tests="4">
+    <testcase classname="billing.InvoiceTest" name="totals" file="src/invoice.py" line="42">
+      <failure message="expected 3 &amp; got 4">Traceback...</failure>
+    </testcase>
+    <testcase classname="billing.InvoiceTest" name="rounding">
+      <error><![CDATA[
+        ZeroDivisionError: division by zero
+      ]]></error>
+    </testcase>
+    <testcase classname="billing.InvoiceTest" name="skipped"><skipped/></testcase>
+    <testcase classname="billing.InvoiceTest" name="passes"/>
+  </testsuite>
+</testsuites>
+"#;
+
+        let results = parse_junit_xml(xml).unwrap();
+        assert_eq!(results.framework, "junit");
+        assert_eq!(results.total, Some(4));
+        assert_eq!((results.passed, results.failed, results.ignored), (1, 2, 1));
+        assert_eq!(results.failures[0].test_name, "billing.InvoiceTest::totals");
+        assert_eq!(
+            results.failures[0].message.as_deref(),
+            Some("expected 3 & got 4")
+        );
+        assert_eq!(
+            results.failures[0].location.as_deref(),
+            Some("src/invoice.py:42")
+        );
+        assert_eq!(
+            results.failures[1].message.as_deref(),
+            Some("ZeroDivisionError: division by zero")
+        );
+        assert!(parse_junit_xml("<html></html>").is_err());
+    }
+
+    #[test]
+    fn parses_go_test_json_stream() {
+        let output = r#"{"Action":"run","Package":"example.com/api","Test":"TestRetry"}
+{"Action":"output","Package":"example.com/api","Test":"TestRetry","Output":"=== RUN   TestRetry\n"}
+{"Action":"output","Package":"example.com/api","Test":"TestRetry","Output":"    retry_test.go:18: expected 3 attempts, got 1\n"}
+{"Action":"fail","Package":"example.com/api","Test":"TestRetry","Elapsed":0.01}
+{"Action":"pass","Package":"example.com/api","Test":"TestHealth","Elapsed":0}
+{"Action":"skip","Package":"example.com/api","Test":"TestSlow","Elapsed":0}
+{"Action":"fail","Package":"example.com/api","Elapsed":0.02}
+"#;
+
+        let results = parse("go test -json ./...", output, false);
+        assert_eq!(results.framework, "go");
+        assert_eq!(results.total, Some(3));
+        assert_eq!((results.passed, results.failed, results.ignored), (1, 1, 1));
+        assert_eq!(results.failures[0].test_name, "example.com/api::TestRetry");
+        assert_eq!(
+            results.failures[0].message.as_deref(),
+            Some("expected 3 attempts, got 1")
+        );
+        assert_eq!(
+            results.failures[0].location.as_deref(),
+            Some("retry_test.go:18")
+        );
+    }
+
+    #[test]
+    fn parses_nextest_libtest_json() {
+        let output = r#"{"type":"suite","event":"started","test_count":2}
+{"type":"test","event":"started","name":"batty-cli::bin/batty$tests::fails"}
+{"type":"test","event":"failed","name":"batty-cli::bin/batty$tests::fails","stdout":"thread 'tests::fails' panicked at src/main.rs:9:5:\nassertion failed: ready\n"}
+{"type":"test","event":"ok","name":"batty-cli::bin/batty$tests::passes"}
+{"type":"suite","event":"failed","passed":1,"failed":1,"ignored":0}
+"#;
+
+        let results = parse(
+            "cargo nextest run --message-format libtest-json",
+            output,
+            false,
+        );
+        assert_eq!(results.framework, "cargo");
+        assert_eq!((results.passed, results.failed), (1, 1));
+        assert_eq!(results.failures[0].test_name, "tests::fails");
+        assert_eq!(
+            results.failures[0].message.as_deref(),
+            Some("assertion failed: ready")
+        );
+        assert_eq!(
+            results.failures[0].location.as_deref(),
+            Some("src/main.rs:9:5")
+        );
+    }
+
+    #[test]
+    fn parses_vitest_text_and_json_reports() {
+        let output = "\u{1b}[31m FAIL \u{1b}[39m src/cart.test.ts > cart > applies discount
+AssertionError: expected 90 to be 80
+ ❯ src/cart.test.ts:12:20
+
+ Test Files  1 failed (1)
+      Tests  1 failed | 3 passed | 1 skipped (5)
+";
+        let text = parse("npx vitest run", output, false);
+        assert_eq!(text.framework, "vitest");
+        assert_eq!(text.total, Some(5));
+        assert_eq!((text.passed, text.failed, text.ignored), (3, 1, 1));
+        assert_eq!(
+            text.failures[0].test_name,
+            "src/cart.test.ts :: cart :: applies discount"
+        );
+        assert_eq!(
+            text.failures[0].message.as_deref(),
+            Some("expected 90 to be 80")
+        );
+        assert_eq!(
+            text.failures[0].location.as_deref(),
+            Some("src/cart.test.ts:12:20")
+        );
+
+        let report = r#"{"numTotalTests":2,"numPassedTests":1,"numFailedTests":1,"numPendingTests":0,
+"testResults":[{"name":"src/cart.test.ts","assertionResults":[
+{"fullName":"cart applies discount","status":"failed","failureMessages":["AssertionError: expected 90 to be 80\n    at src/cart.test.ts:12:20"],"location":{"line":12,"column":20}},
+{"fullName":"cart totals","status":"passed","failureMessages":[]}]}]}"#;
+        let json = parse_report(report).unwrap();
+        assert_eq!(json.framework, "vitest");
+        assert_eq!((json.passed, json.failed), (1, 1));
+        assert_eq!(
+            json.failures[0].test_name,
+            "src/cart.test.ts :: cart applies discount"
+        );
+        assert_eq!(
+            json.failures[0].message.as_deref(),
+            Some("AssertionError: expected 90 to be 80")
+        );
+        assert_eq!(
+            json.failures[0].location.as_deref(),
+            Some("src/cart.test.ts:12")
+        );
+    }
+
+    #[test]
+    fn load_report_merges_directory_and_skips_stale_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let reports = tmp.path().join("reports");
+        std::fs::create_dir_all(&reports).unwrap();
+        std::fs::write(
+            reports.join("stale.xml"),
+            r#"<testsuite><testcase name="old"><failure message="old"/></testcase></testsuite>"#,
+        )
+        .unwrap();
+        let since = SystemTime::now() + std::time::Duration::from_secs(5);
+        assert!(load_report(tmp.path(), "reports", since).unwrap().is_none());
+
+        let since = SystemTime::now() - std::time::Duration::from_secs(1);
+        std::fs::write(
+            reports.join("go.json"),
+            "{\"Action\":\"pass\",\"Package\":\"p\",\"Test\":\"TestA\"}\n",
+        )
+        .unwrap();
+        let merged = load_report(tmp.path(), "reports", since).unwrap().unwrap();
+        assert_eq!(merged.framework, "mixed");
+        assert_eq!((merged.passed, merged.failed), (1, 1));
+        assert_eq!(merged.total, Some(2));
+    }
+
+    #[test]
+    fn load_report_accepts_reports_floored_to_the_start_second() {
+        let tmp = tempfile::tempdir().unwrap();
+        let report = tmp.path().join("report.json");
+        std::fs::write(
+            &report,
+            "{\"Action\":\"pass\",\"Package\":\"p\",\"Test\":\"TestA\"}\n",
+        )
+        .unwrap();
+        let set_mtime = |secs: u64| {
+            std::fs::File::options()
+                .write(true)
+                .open(&report)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+        // The run starts late in second 1000 and the report lands in the same
+        // second, floored by the filesystem (or to an even second, as on FAT).
+        let started_at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_000_900);
+        set_mtime(1_000);
+        assert!(
+            load_report(tmp.path(), "report.json", started_at)
+                .unwrap()
+                .is_some()
+        );
+        set_mtime(999);
+        assert!(
+            load_report(tmp.path(), "report.json", started_at)
+                .unwrap()
+                .is_some()
+        );
+        set_mtime(998);
+        assert!(
+            load_report(tmp.path(), "report.json", started_at)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn skipped_tests_use_each_framework_mechanism() {
+        let tests = vec!["api::retries".to_string(), "it's::flaky".to_string()];
+        assert_eq!(with_skipped_tests("cargo test", &[]), "cargo test");
+        assert_eq!(
+            with_skipped_tests("cargo test", &tests),
+            "export BATTY_SKIP_TESTS='api::retries\nit'\\''s::flaky'; cargo test -- --skip 'api::retries' --skip 'it'\\''s::flaky'"
+        );
+        assert!(
+            with_skipped_tests("cargo nextest run -- --test-threads 2", &tests)
+                .ends_with("--test-threads 2 --skip 'api::retries' --skip 'it'\\''s::flaky'")
+        );
+        assert!(
+            with_skipped_tests("go test ./...", &["example.com/api::TestRetry".to_string()])
+                .ends_with("go test ./... -skip '^(TestRetry)$'")
+        );
+        assert!(
+            with_skipped_tests("pytest -q", &tests)
+                .ends_with("pytest -q -k 'not (retries or flaky)'")
+        );
+        assert!(
+            with_skipped_tests(
+                "npx vitest run",
+                &["src/cart.test.ts :: cart :: applies discount".to_string()]
+            )
+            .ends_with("--testNamePattern '^(?!.*(?:applies discount)$)'")
+        );
+        assert!(with_skipped_tests("./check.sh", &tests).ends_with("; ./check.sh"));
+    }
+
+    #[test]
+    fn compound_commands_only_get_the_skip_list_exported() {
+        let tests = vec!["api::retries".to_string()];
+        for command in [
+            "cargo test 2>&1 | tee test.log",
+            "cargo build && cargo test",
+            "cd api; pytest -q",
+            "cargo test > out.txt",
+            "(cd api && go test ./...)",
+            "cargo test $EXTRA",
+        ] {
+            assert_eq!(
+                with_skipped_tests(command, &tests),
+                format!("export BATTY_SKIP_TESTS='api::retries'; {command}"),
+                "{command}"
+            );
+        }
+        assert!(
+            with_skipped_tests("pytest -q -m 'not slow && fast'", &tests)
+                .ends_with("-k 'not (retries)'")
+        );
+    }
+
     #[test]
     fn parses_legacy_cargo_panic_message_and_location() {
         let output = r#"
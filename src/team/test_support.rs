@@ -29,6 +29,14 @@ impl EnvVarGuard {
         }
         Self { key, original }
     }
+
+    pub(crate) fn unset(key: &'static str) -> Self {
+        let original = std::env::var(key).ok();
+        unsafe {
+            std::env::remove_var(key);
+        }
+        Self { key, original }
+    }
 }
 
 impl Drop for EnvVarGuard {
@@ -363,6 +371,8 @@ pub(crate) fn inferred_role_defs(members: &[MemberInstance]) -> Vec<RoleDef> {
             owns: Vec::new(),
             barrier_group: None,
             use_worktrees,
+            sandbox: None,
+            failover: Vec::new(),
         });
     }
     roles
@@ -463,6 +473,13 @@ impl<'a> TestDaemonBuilder<'a> {
                 layout: None,
                 cost: Default::default(),
                 grafana: Default::default(),
+                api: Default::default(),
+                budget: Default::default(),
+                agents: Default::default(),
+                forge: Default::default(),
+                webhooks: Default::default(),
+                traces: Default::default(),
+                prometheus: Default::default(),
                 use_shim: false,
                 use_sdk_mode: false,
                 auto_respawn_on_crash: false,
@@ -34,6 +34,16 @@ pub enum EvidenceKind {
     CodeFilesChanged,
     TestsPassed,
     TestsFailed,
+    AcceptancePassed,
+    AcceptanceFailed,
+}
+
+/// Outcome of one task acceptance criterion, stored in the completion packet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptanceResult {
+    pub criterion: String,
+    pub passed: bool,
+    pub detail: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
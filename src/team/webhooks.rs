@@ -0,0 +1,856 @@
+//! Outbound webhook sinks for team events.
+//!
+//! The daemon maps each new `TeamEvent` to an OpenClaw project-event envelope
+//! and queues one delivery per matching sink in `.batty/webhooks.json`, so
+//! pending deliveries survive restarts. Deliveries are POSTed as JSON, signed
+//! with HMAC-SHA256 when the sink names a secret, and retried with the
+//! exponential backoff from [`super::retry`]. Deliveries that exhaust
+//! `max_attempts` or get a non-retryable response move to a bounded
+//! dead-letter list that `batty status --health` reports.
+//!
+//! Delivery runs on a [`WebhookWorker`] thread that owns the queue file, so
+//! a slow or unreachable endpoint never holds up the daemon poll loop.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use super::config::{WebhookSinkConfig, WebhooksConfig};
+use super::openclaw::{OpenClawEventSubscription, OpenClawProjectEventEnvelope};
+use super::retry::{self, RetryConfig, Retryable};
+
+const WEBHOOK_STATE_FILE: &str = "webhooks.json";
+/// Oldest dead letters are dropped beyond this many.
+const MAX_DEAD_LETTERS: usize = 100;
+/// Response bodies are cut to this length in `last_error`.
+const MAX_ERROR_DETAIL_CHARS: usize = 200;
+
+pub const SIGNATURE_HEADER: &str = "X-Batty-Signature";
+pub const DELIVERY_HEADER: &str = "X-Batty-Delivery";
+pub const EVENT_HEADER: &str = "X-Batty-Event";
+
+/// One queued POST of an envelope to a sink.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: u64,
+    pub sink: String,
+    pub event_type: String,
+    /// Serialized envelope, sent as-is so the signature covers exact bytes.
+    pub body: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub next_attempt_at: u64,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub failed_at: Option<u64>,
+}
+
+/// Durable delivery queue plus dead letters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebhookQueue {
+    #[serde(default)]
+    pub next_id: u64,
+    #[serde(default)]
+    pub pending: Vec<WebhookDelivery>,
+    #[serde(default)]
+    pub dead_letters: Vec<WebhookDelivery>,
+}
+
+/// Dead-letter row shown by `batty status --health`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WebhookDeadLetter {
+    pub id: u64,
+    pub sink: String,
+    pub event_type: String,
+    pub attempts: u32,
+    pub failed_at: u64,
+    pub last_error: String,
+}
+
+/// Outcome of one [`deliver_due`] pass.
+#[derive(Debug, Default)]
+pub struct WebhookRunSummary {
+    pub delivered: usize,
+    pub retrying: usize,
+    /// Due deliveries held back because their sink already failed this pass.
+    pub deferred: usize,
+    pub dead_lettered: Vec<WebhookDelivery>,
+}
+
+impl WebhookRunSummary {
+    pub fn changed(&self) -> bool {
+        self.delivered > 0
+            || self.retrying > 0
+            || self.deferred > 0
+            || !self.dead_lettered.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookError {
+    Status(u16, String),
+    Transport(String),
+    /// The sink has `secret_env` but the variable is unset or empty.
+    MissingSecret(String),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status(status, detail) if detail.is_empty() => write!(f, "HTTP {status}"),
+            Self::Status(status, detail) => write!(f, "HTTP {status}: {detail}"),
+            Self::Transport(error) => write!(f, "transport: {error}"),
+            Self::MissingSecret(name) => write!(
+                f,
+                "secret_env {name} is not set; refusing to send an unsigned payload"
+            ),
+        }
+    }
+}
+
+impl Retryable for WebhookError {
+    /// Connection failures, timeouts, throttling, and server errors are
+    /// retried; other client errors mean the sink rejected the payload.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Status(status, _) => matches!(status, 408 | 429) || *status >= 500,
+            Self::Transport(_) => true,
+            Self::MissingSecret(_) => false,
+        }
+    }
+}
+
+impl WebhookQueue {
+    /// Queue `envelope` for `sink`, due immediately.
+    pub fn enqueue(
+        &mut self,
+        sink: &str,
+        envelope: &OpenClawProjectEventEnvelope,
+        now: u64,
+    ) -> Result<u64> {
+        self.next_id += 1;
+        self.pending.push(WebhookDelivery {
+            id: self.next_id,
+            sink: sink.to_string(),
+            event_type: envelope.event_type.clone(),
+            body: serde_json::to_string(envelope)?,
+            created_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            failed_at: None,
+        });
+        Ok(self.next_id)
+    }
+
+    fn dead_letter(&mut self, mut delivery: WebhookDelivery, now: u64) {
+        delivery.failed_at = Some(now);
+        self.dead_letters.push(delivery);
+        if self.dead_letters.len() > MAX_DEAD_LETTERS {
+            let excess = self.dead_letters.len() - MAX_DEAD_LETTERS;
+            self.dead_letters.drain(..excess);
+        }
+    }
+}
+
+pub fn webhook_state_path(project_root: &Path) -> PathBuf {
+    project_root.join(".batty").join(WEBHOOK_STATE_FILE)
+}
+
+pub fn load_queue(project_root: &Path) -> Result<WebhookQueue> {
+    let path = webhook_state_path(project_root);
+    if !path.exists() {
+        return Ok(WebhookQueue::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn save_queue(project_root: &Path, queue: &WebhookQueue) -> Result<()> {
+    let path = webhook_state_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    super::board_store::write_atomic(&path, &serde_json::to_string_pretty(queue)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// True when `envelope` passes every non-empty filter on `sink`.
+pub fn sink_matches(sink: &WebhookSinkConfig, envelope: &OpenClawProjectEventEnvelope) -> bool {
+    OpenClawEventSubscription {
+        topics: sink.topics.clone(),
+        roles: sink.roles.clone(),
+        task_ids: sink.tasks.clone(),
+        event_types: sink.event_types.clone(),
+        ..Default::default()
+    }
+    .matches(envelope)
+}
+
+/// `sha256=<hex>` HMAC-SHA256 of `body` under `secret`.
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mac = hmac_sha256(secret, body);
+    let hex: String = mac.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256={hex}")
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let inner_pad: Vec<u8> = block.iter().map(|byte| byte ^ 0x36).collect();
+    let outer_pad: Vec<u8> = block.iter().map(|byte| byte ^ 0x5c).collect();
+    let inner = Sha256::new()
+        .chain_update(&inner_pad)
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(&outer_pad)
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// POST one delivery to `sink`. A sink with `secret_env` is never sent an
+/// unsigned payload.
+pub fn post_delivery(
+    sink: &WebhookSinkConfig,
+    delivery: &WebhookDelivery,
+    timeout: Duration,
+) -> std::result::Result<(), WebhookError> {
+    let secret = match sink.secret_env.as_deref() {
+        Some(name) => match std::env::var(name) {
+            Ok(secret) if !secret.is_empty() => Some(secret),
+            _ => return Err(WebhookError::MissingSecret(name.to_string())),
+        },
+        None => None,
+    };
+    let mut request = ureq::post(&sink.url)
+        .timeout(timeout)
+        .set("User-Agent", "batty")
+        .set("Content-Type", "application/json")
+        .set(DELIVERY_HEADER, &delivery.id.to_string())
+        .set(EVENT_HEADER, &delivery.event_type);
+    if let Some(secret) = secret {
+        request = request.set(
+            SIGNATURE_HEADER,
+            &sign_payload(secret.as_bytes(), delivery.body.as_bytes()),
+        );
+    }
+    match request.send_string(&delivery.body) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(status, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            Err(WebhookError::Status(
+                status,
+                detail.trim().chars().take(MAX_ERROR_DETAIL_CHARS).collect(),
+            ))
+        }
+        Err(ureq::Error::Transport(error)) => Err(WebhookError::Transport(error.to_string())),
+    }
+}
+
+/// Send up to `limit` due deliveries, rescheduling transient failures and
+/// dead-lettering the rest. After a sink fails transiently, its remaining due
+/// deliveries wait for the same retry time instead of each paying the
+/// request timeout.
+pub fn deliver_due(
+    queue: &mut WebhookQueue,
+    config: &WebhooksConfig,
+    now: u64,
+    limit: usize,
+) -> WebhookRunSummary {
+    let backoff = RetryConfig {
+        max_retries: config.max_attempts.saturating_sub(1),
+        base_delay_ms: config.base_delay_secs.saturating_mul(1_000),
+        max_delay_ms: config.max_delay_secs.saturating_mul(1_000),
+        jitter: true,
+    };
+    let timeout = Duration::from_secs(config.timeout_secs.max(1));
+    let mut summary = WebhookRunSummary::default();
+    let mut remaining = Vec::with_capacity(queue.pending.len());
+    let mut attempted = 0;
+    let mut sink_retry_at: HashMap<String, u64> = HashMap::new();
+
+    for mut delivery in std::mem::take(&mut queue.pending) {
+        if attempted >= limit || delivery.next_attempt_at > now {
+            remaining.push(delivery);
+            continue;
+        }
+        if let Some(&retry_at) = sink_retry_at.get(&delivery.sink) {
+            delivery.next_attempt_at = retry_at;
+            summary.deferred += 1;
+            remaining.push(delivery);
+            continue;
+        }
+        let Some(sink) = config.sinks.iter().find(|sink| sink.name == delivery.sink) else {
+            delivery.last_error = Some("sink removed from webhooks config".to_string());
+            summary.dead_lettered.push(delivery.clone());
+            queue.dead_letter(delivery, now);
+            continue;
+        };
+        attempted += 1;
+        delivery.attempts += 1;
+        match post_delivery(sink, &delivery, timeout) {
+            Ok(()) => summary.delivered += 1,
+            Err(error) => {
+                warn!(
+                    sink = %delivery.sink,
+                    delivery = delivery.id,
+                    attempt = delivery.attempts,
+                    error = %error,
+                    "webhook delivery failed"
+                );
+                delivery.last_error = Some(error.to_string());
+                let retry_at =
+                    now + retry::next_delay_ms(&backoff, delivery.attempts - 1).div_ceil(1_000);
+                if error.is_transient() {
+                    sink_retry_at.insert(delivery.sink.clone(), retry_at);
+                }
+                if error.is_transient() && delivery.attempts < config.max_attempts {
+                    delivery.next_attempt_at = retry_at;
+                    summary.retrying += 1;
+                    remaining.push(delivery);
+                } else {
+                    summary.dead_lettered.push(delivery.clone());
+                    queue.dead_letter(delivery, now);
+                }
+            }
+        }
+    }
+    queue.pending = remaining;
+    summary
+}
+
+/// New deliveries for the worker, with the webhooks config in force.
+struct WorkerBatch {
+    config: WebhooksConfig,
+    deliveries: Vec<(String, OpenClawProjectEventEnvelope)>,
+}
+
+/// Background thread that owns `.batty/webhooks.json` and performs every
+/// delivery. The daemon hands it one batch per tick and collects dead
+/// letters without ever waiting on the network.
+pub struct WebhookWorker {
+    batches: mpsc::Sender<WorkerBatch>,
+    dead_letters: mpsc::Receiver<WebhookDelivery>,
+}
+
+impl WebhookWorker {
+    /// Load the queue and start the delivery thread. Fails if the queue file
+    /// is unreadable, so a corrupt file is reported instead of overwritten.
+    pub fn start(project_root: &Path, limit: usize) -> Result<Self> {
+        let queue = load_queue(project_root)?;
+        let project_root = project_root.to_path_buf();
+        let (batches, batch_rx) = mpsc::channel();
+        let (dead_tx, dead_letters) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("batty-webhooks".to_string())
+            .spawn(move || run_worker(project_root, queue, limit, batch_rx, dead_tx))
+            .context("failed to spawn webhook delivery thread")?;
+        Ok(Self {
+            batches,
+            dead_letters,
+        })
+    }
+
+    /// Queue `deliveries` as `(sink, envelope)` pairs and wake the worker to
+    /// send whatever is due. An empty batch still triggers a retry pass.
+    pub fn submit(
+        &self,
+        config: &WebhooksConfig,
+        deliveries: Vec<(String, OpenClawProjectEventEnvelope)>,
+    ) -> Result<()> {
+        let batch = WorkerBatch {
+            config: config.clone(),
+            deliveries,
+        };
+        if self.batches.send(batch).is_err() {
+            bail!("webhook delivery thread exited");
+        }
+        Ok(())
+    }
+
+    /// Deliveries the worker dead-lettered since the last call.
+    pub fn take_dead_letters(&self) -> Vec<WebhookDelivery> {
+        self.dead_letters.try_iter().collect()
+    }
+}
+
+fn run_worker(
+    project_root: PathBuf,
+    mut queue: WebhookQueue,
+    limit: usize,
+    batches: mpsc::Receiver<WorkerBatch>,
+    dead_letters: mpsc::Sender<WebhookDelivery>,
+) {
+    while let Ok(first) = batches.recv() {
+        // Fold ticks that arrived during a slow pass into one pass under the
+        // newest config.
+        let mut config = first.config.clone();
+        let now = super::now_unix();
+        let mut queued = 0;
+        for batch in std::iter::once(first).chain(batches.try_iter()) {
+            for (sink, envelope) in &batch.deliveries {
+                match queue.enqueue(sink, envelope, now) {
+                    Ok(_) => queued += 1,
+                    Err(error) => warn!(sink = %sink, error = %error, "failed to queue webhook"),
+                }
+            }
+            config = batch.config;
+        }
+
+        // Persist new deliveries before sending, so a pass stuck on a slow
+        // sink cannot lose them.
+        if queued > 0 {
+            save_worker_queue(&project_root, &queue);
+        }
+        let summary = deliver_due(&mut queue, &config, now, limit);
+        if summary.changed() {
+            save_worker_queue(&project_root, &queue);
+        }
+        for delivery in summary.dead_lettered {
+            if dead_letters.send(delivery).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn save_worker_queue(project_root: &Path, queue: &WebhookQueue) {
+    if let Err(error) = save_queue(project_root, queue) {
+        warn!(error = %error, "failed to save webhook queue");
+    }
+}
+
+/// Dead letters for `batty status --health`, newest first.
+pub fn load_dead_letters(project_root: &Path) -> Vec<WebhookDeadLetter> {
+    let queue = match load_queue(project_root) {
+        Ok(queue) => queue,
+        Err(error) => {
+            warn!(error = %error, "failed to load webhook queue for status");
+            return Vec::new();
+        }
+    };
+    queue
+        .dead_letters
+        .into_iter()
+        .rev()
+        .map(|delivery| WebhookDeadLetter {
+            id: delivery.id,
+            sink: delivery.sink,
+            event_type: delivery.event_type,
+            attempts: delivery.attempts,
+            failed_at: delivery.failed_at.unwrap_or(delivery.created_at),
+            last_error: delivery.last_error.unwrap_or_default(),
+        })
+        .collect()
+}
+
+pub fn format_dead_letters(dead_letters: &[WebhookDeadLetter]) -> String {
+    let mut lines = vec![
+        "Webhook Dead Letters".to_string(),
+        format!(
+            "{:<6} {:<16} {:<24} {:>8} {}",
+            "ID", "SINK", "EVENT", "ATTEMPTS", "LAST ERROR"
+        ),
+    ];
+    for letter in dead_letters {
+        lines.push(format!(
+            "{:<6} {:<16} {:<24} {:>8} {}",
+            letter.id, letter.sink, letter.event_type, letter.attempts, letter.last_error
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::team::openclaw::{OpenClawEventIdentifiers, OpenClawEventTopic};
+
+    #[derive(Debug, Clone)]
+    struct Received {
+        headers: Vec<(String, String)>,
+        body: String,
+    }
+
+    impl Received {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Local HTTP receiver that answers with `statuses` in order (repeating
+    /// the last one) and records every request.
+    struct Receiver {
+        url: String,
+        received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    impl Receiver {
+        fn start(statuses: Vec<u16>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/hook", listener.local_addr().unwrap());
+            let received = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&received);
+            std::thread::spawn(move || {
+                for (index, stream) in listener.incoming().enumerate() {
+                    let Ok(mut stream) = stream else { break };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut headers = Vec::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap_or(0);
+                            }
+                            headers.push((name.to_string(), value.trim().to_string()));
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+                    recorded.lock().unwrap().push(Received {
+                        headers,
+                        body: String::from_utf8(body).unwrap(),
+                    });
+                    let status = statuses
+                        .get(index)
+                        .or(statuses.last())
+                        .copied()
+                        .unwrap_or(200);
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {status} X\r\nContent-Length: 4\r\nConnection: close\r\n\r\nnope"
+                    );
+                }
+            });
+            Self { url, received }
+        }
+
+        fn received(&self) -> Vec<Received> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    fn envelope(
+        topic: OpenClawEventTopic,
+        event_type: &str,
+        role: &str,
+    ) -> OpenClawProjectEventEnvelope {
+        OpenClawProjectEventEnvelope {
+            kind: "batty.openclaw.projectEvent".to_string(),
+            schema_version: 1,
+            topic,
+            event_type: event_type.to_string(),
+            project_id: "demo".to_string(),
+            project_name: "demo".to_string(),
+            project_root: "/tmp/demo".to_string(),
+            team_name: "demo".to_string(),
+            session_name: "batty-demo".to_string(),
+            ts: 100,
+            identifiers: OpenClawEventIdentifiers {
+                role: Some(role.to_string()),
+                task_id: Some("41".to_string()),
+                ..Default::default()
+            },
+            reason: None,
+            details: None,
+            action_type: None,
+            success: None,
+            restart_count: None,
+            load: None,
+            uptime_secs: None,
+            session_running: None,
+        }
+    }
+
+    fn sink(name: &str, url: &str) -> WebhookSinkConfig {
+        WebhookSinkConfig {
+            name: name.to_string(),
+            url: url.to_string(),
+            secret_env: None,
+            topics: Vec::new(),
+            event_types: Vec::new(),
+            roles: Vec::new(),
+            tasks: Vec::new(),
+        }
+    }
+
+    fn config(sinks: Vec<WebhookSinkConfig>, max_attempts: u32) -> WebhooksConfig {
+        WebhooksConfig {
+            sinks,
+            max_attempts,
+            base_delay_secs: 10,
+            max_delay_secs: 60,
+            timeout_secs: 5,
+        }
+    }
+
+    #[test]
+    fn hmac_matches_rfc_4231_vector() {
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        let long_key = [0xaa; 131];
+        assert_eq!(
+            sign_payload(
+                &long_key,
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "sha256=60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn sink_filters_on_topic_role_and_task() {
+        let completed = envelope(OpenClawEventTopic::Completion, "task.completed", "eng-1");
+        let mut filtered = sink("pager", "http://127.0.0.1:1/");
+        assert!(sink_matches(&filtered, &completed));
+
+        filtered.topics = vec![OpenClawEventTopic::Escalation];
+        assert!(!sink_matches(&filtered, &completed));
+        filtered.topics = vec![OpenClawEventTopic::Completion];
+        filtered.roles = vec!["eng-2".to_string()];
+        assert!(!sink_matches(&filtered, &completed));
+        filtered.roles = vec!["eng-1".to_string()];
+        filtered.tasks = vec!["41".to_string()];
+        assert!(sink_matches(&filtered, &completed));
+        filtered.tasks = vec!["42".to_string()];
+        assert!(!sink_matches(&filtered, &completed));
+    }
+
+    #[test]
+    fn delivers_signed_payload_to_local_receiver() {
+        let receiver = Receiver::start(vec![200]);
+        let mut signed = sink("internal", &receiver.url);
+        signed.secret_env = Some("BATTY_WEBHOOK_SECRET_TEST".to_string());
+        let _secret =
+            crate::team::test_support::EnvVarGuard::set("BATTY_WEBHOOK_SECRET_TEST", "s3cret");
+        let config = config(vec![signed], 3);
+        let mut queue = WebhookQueue::default();
+        let envelope = envelope(OpenClawEventTopic::Escalation, "task.escalated", "eng-1");
+        queue.enqueue("internal", &envelope, 100).unwrap();
+
+        let summary = deliver_due(&mut queue, &config, 100, 10);
+
+        assert_eq!(summary.delivered, 1);
+        assert!(queue.pending.is_empty());
+        let received = receiver.received();
+        assert_eq!(received.len(), 1);
+        let body: OpenClawProjectEventEnvelope = serde_json::from_str(&received[0].body).unwrap();
+        assert_eq!(body, envelope);
+        assert_eq!(received[0].header(EVENT_HEADER), Some("task.escalated"));
+        assert_eq!(received[0].header(DELIVERY_HEADER), Some("1"));
+        assert_eq!(
+            received[0].header(SIGNATURE_HEADER),
+            Some(sign_payload(b"s3cret", received[0].body.as_bytes()).as_str())
+        );
+    }
+
+    #[test]
+    fn missing_secret_dead_letters_instead_of_sending_unsigned() {
+        let receiver = Receiver::start(vec![200]);
+        let mut signed = sink("internal", &receiver.url);
+        signed.secret_env = Some("BATTY_WEBHOOK_SECRET_TEST_UNSET".to_string());
+        let config = config(vec![signed], 3);
+        let mut queue = WebhookQueue::default();
+        let envelope = envelope(OpenClawEventTopic::Escalation, "task.escalated", "eng-1");
+        queue.enqueue("internal", &envelope, 100).unwrap();
+
+        let summary = deliver_due(&mut queue, &config, 100, 10);
+
+        assert_eq!(summary.delivered, 0);
+        assert_eq!(summary.dead_lettered.len(), 1);
+        assert!(queue.pending.is_empty());
+        assert_eq!(
+            queue.dead_letters[0].last_error.as_deref(),
+            Some(
+                "secret_env BATTY_WEBHOOK_SECRET_TEST_UNSET is not set; refusing to send an unsigned payload"
+            )
+        );
+        assert!(receiver.received().is_empty());
+    }
+
+    #[test]
+    fn transient_failures_back_off_then_dead_letter() {
+        let receiver = Receiver::start(vec![503]);
+        let config = config(vec![sink("flaky", &receiver.url)], 2);
+        let mut queue = WebhookQueue::default();
+        queue
+            .enqueue(
+                "flaky",
+                &envelope(OpenClawEventTopic::Stall, "agent.stalled", "eng-1"),
+                100,
+            )
+            .unwrap();
+
+        let first = deliver_due(&mut queue, &config, 100, 10);
+        assert_eq!(first.retrying, 1);
+        let retry_at = queue.pending[0].next_attempt_at;
+        assert!((107..=113).contains(&retry_at), "{retry_at}");
+        assert!(!deliver_due(&mut queue, &config, retry_at - 1, 10).changed());
+
+        let second = deliver_due(&mut queue, &config, retry_at, 10);
+        assert_eq!(second.dead_lettered.len(), 1);
+        assert!(queue.pending.is_empty());
+        assert_eq!(queue.dead_letters[0].attempts, 2);
+        assert_eq!(queue.dead_letters[0].failed_at, Some(retry_at));
+        assert_eq!(
+            queue.dead_letters[0].last_error.as_deref(),
+            Some("HTTP 503: nope")
+        );
+        assert_eq!(receiver.received().len(), 2);
+    }
+
+    #[test]
+    fn unreachable_sink_is_attempted_once_per_pass() {
+        // Accepts connections into the backlog but never answers.
+        let blackhole = TcpListener::bind("127.0.0.1:0").unwrap();
+        let healthy = Receiver::start(vec![200]);
+        let mut config = config(
+            vec![
+                sink(
+                    "down",
+                    &format!("http://{}/", blackhole.local_addr().unwrap()),
+                ),
+                sink("up", &healthy.url),
+            ],
+            3,
+        );
+        config.timeout_secs = 1;
+        let mut queue = WebhookQueue::default();
+        for _ in 0..3 {
+            let event = envelope(OpenClawEventTopic::Stall, "agent.stalled", "eng-1");
+            queue.enqueue("down", &event, 100).unwrap();
+            queue.enqueue("up", &event, 100).unwrap();
+        }
+
+        let started = std::time::Instant::now();
+        let summary = deliver_due(&mut queue, &config, 100, 10);
+
+        assert!(
+            started.elapsed() < Duration::from_secs(3),
+            "{:?}",
+            started.elapsed()
+        );
+        assert_eq!(summary.delivered, 3);
+        assert_eq!(summary.retrying, 1);
+        assert_eq!(summary.deferred, 2);
+        assert_eq!(queue.pending.len(), 3);
+        let retry_at = queue.pending[0].next_attempt_at;
+        assert!(retry_at > 100);
+        assert!(
+            queue
+                .pending
+                .iter()
+                .all(|delivery| delivery.sink == "down" && delivery.next_attempt_at == retry_at)
+        );
+        assert_eq!(
+            queue
+                .pending
+                .iter()
+                .map(|delivery| delivery.attempts)
+                .collect::<Vec<_>>(),
+            vec![1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn worker_delivers_and_reports_dead_letters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let receiver = Receiver::start(vec![200, 400]);
+        let config = config(vec![sink("ops", &receiver.url)], 3);
+        let worker = WebhookWorker::start(tmp.path(), 10).unwrap();
+        let event = envelope(OpenClawEventTopic::Completion, "task.completed", "eng-1");
+
+        worker
+            .submit(
+                &config,
+                vec![
+                    ("ops".to_string(), event.clone()),
+                    ("ops".to_string(), event),
+                ],
+            )
+            .unwrap();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        let mut dead = Vec::new();
+        while dead.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            dead = worker.take_dead_letters();
+        }
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].id, 2);
+        assert_eq!(receiver.received().len(), 2);
+        let queue = load_queue(tmp.path()).unwrap();
+        assert!(queue.pending.is_empty());
+        assert_eq!(queue.dead_letters.len(), 1);
+    }
+
+    #[test]
+    fn client_errors_dead_letter_immediately_and_persist() {
+        let tmp = tempfile::tempdir().unwrap();
+        let receiver = Receiver::start(vec![400]);
+        let config = config(vec![sink("strict", &receiver.url)], 5);
+        let mut queue = WebhookQueue::default();
+        queue
+            .enqueue(
+                "strict",
+                &envelope(OpenClawEventTopic::Merge, "task.merged.automatic", "eng-1"),
+                100,
+            )
+            .unwrap();
+        queue
+            .enqueue(
+                "gone",
+                &envelope(OpenClawEventTopic::Merge, "task.merged.automatic", "eng-1"),
+                100,
+            )
+            .unwrap();
+
+        let summary = deliver_due(&mut queue, &config, 100, 10);
+        assert_eq!(summary.dead_lettered.len(), 2);
+        save_queue(tmp.path(), &queue).unwrap();
+
+        let dead = load_dead_letters(tmp.path());
+        assert_eq!(dead.len(), 2);
+        assert_eq!(dead[0].sink, "gone");
+        assert_eq!(dead[0].last_error, "sink removed from webhooks config");
+        assert_eq!(dead[1].sink, "strict");
+        assert_eq!(dead[1].attempts, 1);
+        assert!(format_dead_letters(&dead).contains("HTTP 400: nope"));
+    }
+}
@@ -1419,6 +1419,160 @@ pub(crate) fn archive_branch_if_commits_ahead(
     Ok(Some(archive_branch))
 }
 
+/// Directory (inside the worktree's private git dir) holding Batty-managed hooks.
+pub const SCOPE_FENCE_HOOKS_DIR: &str = "batty-hooks";
+
+/// Every hook name git runs (githooks(5)). The worktree's hooks path gets a
+/// forwarder for each, so pointing it at Batty's dir disables none of them.
+const GIT_HOOK_NAMES: &[&str] = &[
+    "applypatch-msg",
+    "pre-applypatch",
+    "post-applypatch",
+    "pre-commit",
+    "pre-merge-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+    "pre-rebase",
+    "post-checkout",
+    "post-merge",
+    "pre-push",
+    "pre-receive",
+    "update",
+    "proc-receive",
+    "post-receive",
+    "post-update",
+    "reference-transaction",
+    "push-to-checkout",
+    "pre-auto-gc",
+    "post-rewrite",
+    "sendemail-validate",
+    "fsmonitor-watchman",
+    "p4-changelist",
+    "p4-prepare-changelist",
+    "p4-post-changelist",
+    "p4-pre-submit",
+    "post-index-change",
+];
+
+/// Install Batty's scope-fence `pre-commit` hook for one worktree.
+///
+/// The hook lives in the worktree's own git dir and is activated with a
+/// per-worktree `core.hooksPath`, so the main checkout and other worktrees
+/// keep their hooks. Every hook name in that dir forwards to the
+/// repository's effective hooks dir (its own `core.hooksPath`, as set by
+/// husky and friends, or `hooks/` in the common git dir); `pre-commit` runs
+/// `batty scope-check` first.
+pub fn install_scope_fence_hook(worktree_path: &Path, batty_bin: &Path) -> Result<()> {
+    let output = run_git(worktree_path, ["rev-parse", "--absolute-git-dir"])?;
+    if !output.status.success() {
+        bail!(
+            "failed to resolve git dir for {}: {}",
+            worktree_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let hooks_dir = git_dir.join(SCOPE_FENCE_HOOKS_DIR);
+    std::fs::create_dir_all(&hooks_dir)
+        .with_context(|| format!("failed to create {}", hooks_dir.display()))?;
+
+    let repo_hooks = match repo_hooks_path(worktree_path)? {
+        Some(path) => shell_single_quote(&path),
+        None => r#""$(git rev-parse --git-common-dir)/hooks""#.to_string(),
+    };
+    let batty = shell_single_quote(&batty_bin.display().to_string());
+    for name in GIT_HOOK_NAMES {
+        let scope_check = if *name == "pre-commit" {
+            format!(
+                r#"# Rejects commits outside the claimed task's scope.
+batty={batty}
+if [ -x "$batty" ]; then
+  "$batty" scope-check || exit 1
+else
+  echo "batty: scope-fence hook skipped, $batty not found" >&2
+fi
+"#
+            )
+        } else {
+            String::new()
+        };
+        let script = format!(
+            r#"#!/bin/sh
+# Installed by batty: forwards to the repository's own {name} hook.
+{scope_check}repo_hook={repo_hooks}/{name}
+if [ -x "$repo_hook" ]; then
+  exec "$repo_hook" "$@"
+fi
+"#
+        );
+        let hook_path = hooks_dir.join(name);
+        std::fs::write(&hook_path, script)
+            .with_context(|| format!("failed to write {}", hook_path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755))
+                .with_context(|| format!("failed to chmod {}", hook_path.display()))?;
+        }
+    }
+
+    let hooks_path = hooks_dir.to_string_lossy().into_owned();
+    for args in [
+        vec!["config", "extensions.worktreeConfig", "true"],
+        vec![
+            "config",
+            "--worktree",
+            "core.hooksPath",
+            hooks_path.as_str(),
+        ],
+    ] {
+        let output = run_git(worktree_path, &args)?;
+        if !output.status.success() {
+            bail!(
+                "git {} failed in {}: {}",
+                args.join(" "),
+                worktree_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The `core.hooksPath` the repository itself configures, ignoring the
+/// per-worktree value Batty sets. `None` means git's default hooks dir.
+fn repo_hooks_path(worktree_path: &Path) -> Result<Option<String>> {
+    let output = run_git(
+        worktree_path,
+        [
+            "config",
+            "--show-scope",
+            "--type=path",
+            "--get-all",
+            "core.hooksPath",
+        ],
+    )?;
+    // Exit status 1 means the key is unset.
+    if !output.status.success() && output.status.code() != Some(1) {
+        bail!(
+            "failed to read core.hooksPath in {}: {}",
+            worktree_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter(|(scope, _)| *scope != "worktree")
+        .map(|(_, path)| path.to_string())
+        .next_back())
+}
+
+fn shell_single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     std::fs::create_dir_all(dst)?;
     for entry in std::fs::read_dir(src)? {
@@ -1493,6 +1647,121 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn scope_fence_hook_blocks_commit_when_check_fails_and_chains_repo_hook() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let Some(tmp) = init_repo() else {
+            return;
+        };
+        let repo = tmp.path();
+        let worktree = repo.join("wt");
+        git(
+            repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "eng",
+                worktree.to_str().unwrap(),
+            ],
+        );
+
+        let marker = repo.join("repo-hook-ran");
+        let msg_marker = repo.join("commit-msg-ran");
+        for (name, marker) in [("pre-commit", &marker), ("commit-msg", &msg_marker)] {
+            let repo_hook = repo.join(".git/hooks").join(name);
+            fs::write(
+                &repo_hook,
+                format!("#!/bin/sh\ntouch '{}'\n", marker.display()),
+            )
+            .unwrap();
+            fs::set_permissions(&repo_hook, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let fake_batty = repo.join("fake-batty");
+        fs::write(
+            &fake_batty,
+            "#!/bin/sh\n[ \"$1\" = scope-check ] || exit 2\n! git diff --cached --name-only | grep -q '^blocked'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&fake_batty, fs::Permissions::from_mode(0o755)).unwrap();
+
+        install_scope_fence_hook(&worktree, &fake_batty).unwrap();
+
+        fs::write(worktree.join("blocked.txt"), "no\n").unwrap();
+        git(&worktree, &["add", "blocked.txt"]);
+        let rejected = Command::new("git")
+            .current_dir(&worktree)
+            .args(["commit", "-q", "-m", "out of scope"])
+            .output()
+            .unwrap();
+        assert!(!rejected.status.success());
+        assert!(!marker.exists());
+
+        git(&worktree, &["rm", "-q", "--cached", "blocked.txt"]);
+        fs::write(worktree.join("allowed.txt"), "yes\n").unwrap();
+        git(&worktree, &["add", "allowed.txt"]);
+        git(&worktree, &["commit", "-q", "-m", "in scope"]);
+        assert!(marker.exists());
+        assert!(msg_marker.exists());
+
+        // The main checkout keeps its own hooks path.
+        let main_hooks = run_git(repo, ["config", "--get", "core.hooksPath"]).unwrap();
+        assert!(
+            String::from_utf8_lossy(&main_hooks.stdout)
+                .trim()
+                .is_empty()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scope_fence_hook_chains_a_custom_repo_hooks_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let Some(tmp) = init_repo() else {
+            return;
+        };
+        let repo = tmp.path();
+        // A husky-style hooks dir, relative to the checkout.
+        git(repo, &["config", "core.hooksPath", ".husky"]);
+        let worktree = repo.join("wt");
+        git(
+            repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "eng",
+                worktree.to_str().unwrap(),
+            ],
+        );
+        let husky = worktree.join(".husky");
+        fs::create_dir_all(&husky).unwrap();
+        for name in ["pre-commit", "commit-msg"] {
+            let hook = husky.join(name);
+            fs::write(&hook, format!("#!/bin/sh\ntouch '{name}-ran'\n")).unwrap();
+            fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let fake_batty = repo.join("fake-batty");
+        fs::write(&fake_batty, "#!/bin/sh\nexit 0\n").unwrap();
+        fs::set_permissions(&fake_batty, fs::Permissions::from_mode(0o755)).unwrap();
+
+        install_scope_fence_hook(&worktree, &fake_batty).unwrap();
+        // Reinstalling must not chain to Batty's own per-worktree hooks path.
+        install_scope_fence_hook(&worktree, &fake_batty).unwrap();
+
+        fs::write(worktree.join("file.txt"), "x\n").unwrap();
+        git(&worktree, &["add", "file.txt"]);
+        git(&worktree, &["commit", "-q", "-m", "chained"]);
+        assert!(worktree.join("pre-commit-ran").exists());
+        assert!(worktree.join("commit-msg-ran").exists());
+    }
+
     #[test]
     fn sanitize_phase_for_branch_normalizes_phase() {
         assert_eq!(sanitize_phase_for_branch("phase-2.5"), "phase-2-5");